@@ -0,0 +1,113 @@
+//! Standalone mock CLOB venue for running `polymarket_v2` live mode locally.
+//!
+//! Prints the env block to point the bot at it, applies an optional JSONL
+//! script (see `mock_clob::MockScriptStep`), then serves until Ctrl-C.
+
+use std::env;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use pm_as_ofi::polymarket::mock_clob::{load_script, MockClobConfig, MockClobServer};
+use pm_as_ofi::polymarket::types::Side;
+
+fn get_arg(flag: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(a) = args.next() {
+        if a == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn has_flag(flag: &str) -> bool {
+    env::args().any(|a| a == flag)
+}
+
+fn parse_book(arg: &str) -> anyhow::Result<Vec<(f64, f64)>> {
+    // "0.48:100,0.47:250"
+    arg.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|level| {
+            let (p, s) = level
+                .split_once(':')
+                .ok_or_else(|| anyhow::anyhow!("level '{}' must be price:size", level))?;
+            Ok((p.trim().parse()?, s.trim().parse()?))
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    if has_flag("--help") || has_flag("-h") {
+        println!(
+            "Usage: cargo run --bin mock_clob_server -- [options]\n\
+             --rest-addr <ip:port>        default 127.0.0.1:0\n\
+             --ws-addr <ip:port>          default 127.0.0.1:0\n\
+             --yes-asset-id <id>          --no-asset-id <id>   --market-id <0x..>\n\
+             --yes-bids <p:s,..>          --yes-asks <p:s,..>  initial street book\n\
+             --no-bids <p:s,..>           --no-asks <p:s,..>\n\
+             --script <file.jsonl>        timed level/trade steps\n"
+        );
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let mut cfg = MockClobConfig::default();
+    if let Some(addr) = get_arg("--rest-addr") {
+        cfg.rest_addr = addr.parse::<SocketAddr>()?;
+    }
+    if let Some(addr) = get_arg("--ws-addr") {
+        cfg.ws_addr = addr.parse::<SocketAddr>()?;
+    }
+    if let Some(id) = get_arg("--yes-asset-id") {
+        cfg.yes_asset_id = id;
+    }
+    if let Some(id) = get_arg("--no-asset-id") {
+        cfg.no_asset_id = id;
+    }
+    if let Some(id) = get_arg("--market-id") {
+        cfg.market_id = id;
+    }
+    let script = get_arg("--script")
+        .map(|p| load_script(&PathBuf::from(p)))
+        .transpose()?;
+
+    let server = MockClobServer::start(cfg).await?;
+    for (side, bids_flag, asks_flag) in [
+        (Side::Yes, "--yes-bids", "--yes-asks"),
+        (Side::No, "--no-bids", "--no-asks"),
+    ] {
+        let bids = get_arg(bids_flag).map(|a| parse_book(&a)).transpose()?;
+        let asks = get_arg(asks_flag).map(|a| parse_book(&a)).transpose()?;
+        if bids.is_some() || asks.is_some() {
+            server.set_book(side, &bids.unwrap_or_default(), &asks.unwrap_or_default());
+        }
+    }
+
+    let cfg = server.config();
+    println!(
+        "POLYMARKET_REST_URL={}\n\
+         POLYMARKET_WS_BASE_URL={}\n\
+         POLYMARKET_MARKET_ID={}\n\
+         POLYMARKET_YES_ASSET_ID={}\n\
+         POLYMARKET_NO_ASSET_ID={}",
+        server.rest_url(),
+        server.ws_base_url(),
+        cfg.market_id,
+        cfg.yes_asset_id,
+        cfg.no_asset_id,
+    );
+
+    if let Some(steps) = script {
+        server.run_script(&steps).await;
+    }
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
@@ -5,6 +5,8 @@ use std::str::FromStr;
 use rusqlite::{params, Connection};
 use serde_json::json;
 
+use pm_as_ofi::polymarket::fees::taker_fee_per_share;
+
 const PAIR_ARB_NET_EPS: f64 = 0.001;
 const TIER_1_NET_DIFF: f64 = 5.0;
 const TIER_2_NET_DIFF: f64 = 10.0;
@@ -699,10 +701,6 @@ fn check_fill(our_bid: f64, market_ask: f64, model: FillModel) -> bool {
     }
 }
 
-fn fee_per_share(px: f64, rate: f64) -> f64 {
-    rate * px.clamp(0.0, 1.0).min((1.0 - px).max(0.0))
-}
-
 fn try_salvage_completion(
     cfg: Config,
     inv: &mut Inventory,
@@ -726,7 +724,7 @@ fn try_salvage_completion(
         return None;
     }
 
-    let fee = fee_per_share(completion_ask, cfg.taker_fee_rate);
+    let fee = taker_fee_per_share(completion_ask, cfg.taker_fee_rate);
     let net_pair_cost = held_avg + completion_ask + fee;
     if net_pair_cost > cfg.salvage_net_cap + 1e-9 {
         return None;
@@ -17,7 +17,7 @@ use std::fs::{self, OpenOptions};
 use std::hash::{Hash, Hasher};
 use std::io::{BufWriter, Write};
 use std::net::TcpListener;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
@@ -48,20 +48,90 @@ use pm_as_ofi::polymarket::ingress::{
 };
 
 // V2 Actor modules
+use pm_as_ofi::polymarket::actor_state_store::{
+    restore_past, ActorStateHandle, ActorStateStore, ActorStateStoreConfig, RestoredState,
+    StateSaveThrottle,
+};
 use pm_as_ofi::polymarket::claims::{
     execute_market_merge, maybe_auto_claim, run_auto_claim_once, scan_claimable_positions,
     scan_mergeable_full_set_usdc, AutoClaimConfig, AutoClaimState,
 };
+use pm_as_ofi::polymarket::config_file::{
+    apply_config_file, config_file_path_from_args_or_env, render_effective_env, AppliedConfig,
+    ConfigOrigin,
+};
+use pm_as_ofi::polymarket::config_validation::{
+    build_configs_and_collect_issues, env_bool, strict_config_failure, strict_mode_enabled,
+    ConfigIssueKind, CONFIG_STRICT_ENV,
+};
 use pm_as_ofi::polymarket::coordinator::{
     CoordinatorConfig, CoordinatorObsSnapshot, StrategyCoordinator,
 };
+use pm_as_ofi::polymarket::data_streams::{
+    full_report_hex, latest_report_path, report_at_path, DataStreamsHmacAuth, DataStreamsReport,
+    DataStreamsVerifier, ReportReject, DATA_STREAMS_REPORTS_API_DEFAULT,
+};
 use pm_as_ofi::polymarket::executor::{init_clob_client, AuthClient, Executor, ExecutorConfig};
-use pm_as_ofi::polymarket::glft::{GlftRuntimeConfig, GlftSignalEngine, GlftSignalSnapshot};
+use pm_as_ofi::polymarket::fees::{FeeQuote, FeeSchedule, FeeTally};
+use pm_as_ofi::polymarket::glft::{
+    GlftRuntimeConfig, GlftSignalEngine, GlftSignalSnapshot, ReferenceBlendConfig,
+    ReferencePriceTick,
+};
 use pm_as_ofi::polymarket::inventory::{InventoryConfig, InventoryManager};
+use pm_as_ofi::polymarket::inventory_recovery::{recover_inventory, InventoryRecoveryConfig};
+use pm_as_ofi::polymarket::l2_book::{L2BookAssembler, L2BookSnapshot};
 use pm_as_ofi::polymarket::messages::*;
+use pm_as_ofi::polymarket::metrics::{
+    metrics_registry, run_market_metrics_collector, spawn_metrics_server, MetricsConfig,
+};
 use pm_as_ofi::polymarket::ofi::{OfiConfig, OfiEngine};
+use pm_as_ofi::polymarket::ofi_attribution::{OfiAttribution, OfiAttributionConfig};
 use pm_as_ofi::polymarket::order_manager::OrderManager;
-use pm_as_ofi::polymarket::recorder::{RecorderHandle, RecorderSessionMeta, RecorderSessionStart};
+use pm_as_ofi::polymarket::pnl_ledger::{
+    LedgerFill, MarketPnlLedger, PnlLedger, PnlLedgerConfig, PnlLedgerWriter, RedeemRecord,
+    RoundAggregate, RoundFilter, RoundRecord,
+};
+use pm_as_ofi::polymarket::portfolio_risk::{
+    PortfolioRiskConfig, PortfolioRiskHub, PortfolioRiskWorker,
+};
+use pm_as_ofi::polymarket::price_agg::bias::SourceBiasBook;
+use pm_as_ofi::polymarket::price_agg::boundary::{
+    aggregate_local_close, format_local_source_boundary_state, ingest_boundary_tick,
+    local_close_source_contributions, local_price_agg_collect_boundary_tape_tick,
+    local_price_agg_ingest_state, pick_local_source_close_point, pick_local_source_open_point,
+    pick_local_source_points, source_confidence, AggregatedPricePoint, CloseAggReject,
+    LocalCloseOnlyAggHit, LocalCloseSourceContribution, LocalSourceBoundaryState,
+    LocalSourceBoundaryTapeState,
+};
+use pm_as_ofi::polymarket::price_agg::policy::{
+    local_boundary_filtered_close_only_allowed_without_weighted,
+    local_boundary_hybrid_prefers_filtered_close_only_over_weighted,
+    local_boundary_hybrid_prefers_weighted_over_close_only,
+    local_boundary_maybe_debias_hype_close_only,
+    local_boundary_maybe_equalize_xrp_binance_coinbase_fast_no,
+    local_boundary_policy_specs_weighted, local_boundary_shadow_candidate_ready,
+    local_boundary_symbol_router_allows_close_only_fallback,
+    local_boundary_symbol_router_fallback_policy_specs,
+    local_boundary_symbol_router_missing_filter_reason,
+    local_boundary_symbol_router_prefers_weighted_primary,
+    local_boundary_symbol_router_source_fallback_allowed,
+    local_boundary_weighted_candidate_allowed_for_policy,
+    local_boundary_weighted_candidate_filter_reason_for_policy, run_local_boundary_shadow_policy,
+    LocalBoundaryShadowHit, LocalBoundaryShadowOutcome, LOCAL_BOUNDARY_POLICY_POST_MS,
+};
+use pm_as_ofi::polymarket::price_agg::uncertainty_gate::{
+    file_modified_ms, OnlineModelMeta, UncertaintyGateBucket, UncertaintyGateConfig,
+    UncertaintyGateFile, UncertaintyGateWriter, UNCERTAINTY_GATE_MODEL_NAME,
+    UNCERTAINTY_GATE_ONLINE_WINDOW_DEFAULT,
+};
+use pm_as_ofi::polymarket::price_agg::{
+    median_u64, normalize_chainlink_symbol, robust_median, spread_bps, weighted_mean,
+    LocalPriceHub, LocalPriceSource, PriceAggConfig, SourceTick, LOCAL_PRICE_SOURCES,
+};
+use pm_as_ofi::polymarket::recorder::{
+    RecorderConfig, RecorderHandle, RecorderSessionMeta, RecorderSessionStart,
+};
+use pm_as_ofi::polymarket::rtds_pool::{RtdsConnectionPool, RtdsPoolConfig};
 use pm_as_ofi::polymarket::types::Side;
 use pm_as_ofi::polymarket::user_ws::{UserWsConfig, UserWsListener};
 
@@ -416,6 +486,7 @@ async fn spawn_shared_ingress_broker_sidecar() -> anyhow::Result<()> {
         .env("PM_SHARED_INGRESS_ROLE", "broker")
         .env("PM_SHARED_INGRESS_ROOT", &root)
         .env("PM_SHARED_INGRESS_IDLE_EXIT_ENABLED", "true")
+        .env("PM_METRICS_ENABLED", "false")
         .env("PM_LOG_ROOT", shared_ingress_broker_runtime_log_root())
         .env(
             "PM_LOCAL_PRICE_AGG_BIAS_CACHE_PATH",
@@ -439,6 +510,10 @@ async fn spawn_shared_ingress_broker_sidecar() -> anyhow::Result<()> {
         "PM_LOCAL_PRICE_AGG_BIAS_LEARNING_ENABLED",
         "PM_LOCAL_PRICE_AGG_SOURCES",
         "PM_CHAINLINK_HUB_TICK_STALL_RECONNECT_MS",
+        "PM_CHAINLINK_HUB_MAX_CONCURRENT_CONNECTS",
+        "PM_CHAINLINK_HUB_RECONNECT_STAGGER_MS",
+        "PM_CHAINLINK_HUB_BACKOFF_MAX_MS",
+        "PM_CHAINLINK_HUB_STALL_FAILOVER",
         "PM_POST_CLOSE_CHAINLINK_WS_URL",
         "PM_POST_CLOSE_CHAINLINK_MAX_WAIT_SECS",
     ] {
@@ -578,8 +653,15 @@ fn spawn_shared_ingress_client_lease_heartbeat() {
     });
 }
 
+/// Aggregator tunables, read from env once per process.
+fn price_agg_config() -> &'static PriceAggConfig {
+    static CFG: OnceLock<PriceAggConfig> = OnceLock::new();
+    CFG.get_or_init(PriceAggConfig::from_env)
+}
+
 fn local_price_source_names() -> String {
-    LOCAL_PRICE_SOURCES
+    price_agg_config()
+        .enabled_sources()
         .iter()
         .map(|s| s.as_str())
         .collect::<Vec<_>>()
@@ -780,6 +862,9 @@ struct RoundValidationSummary {
     mean_fill_slippage_vs_posted: Option<f64>,
     fill_to_adverse_move_3s: Option<f64>,
     fill_to_adverse_move_10s: Option<f64>,
+    /// Per-fill rows for the PnL ledger; kept out of the JSONL line.
+    #[serde(skip)]
+    fills: Vec<LedgerFill>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -792,32 +877,18 @@ enum RoundLossAttribution {
     EMarketNotSuitable,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct RoundValidationAggregate {
-    rounds_total: usize,
-    rounds_partial: usize,
-    rounds_full: usize,
-    total_realized_cash_pnl: f64,
-    total_realized_round_pnl_ex_residual: f64,
-    total_mark_to_mid_pnl_end: f64,
-    total_residual_inventory_cost_end: f64,
-    median_round_pnl: Option<f64>,
-    median_round_pnl_ex_residual: Option<f64>,
-    mean_fill_to_adverse_move_3s: Option<f64>,
-    mean_fill_to_adverse_move_10s: Option<f64>,
-    mean_max_abs_net_diff: Option<f64>,
-    mean_time_weighted_abs_net_diff: Option<f64>,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct RoundValidationAggregateReport {
     generated_at_ts_ms: i64,
     strategy: String,
     market_slug: String,
-    all: RoundValidationAggregate,
-    last_5: Option<RoundValidationAggregate>,
-    last_20: Option<RoundValidationAggregate>,
-    last_50: Option<RoundValidationAggregate>,
+    all: RoundAggregate,
+    last_5: Option<RoundAggregate>,
+    last_20: Option<RoundAggregate>,
+    last_50: Option<RoundAggregate>,
+    /// Same strategy across every market; only available from the PnL ledger.
+    #[serde(default)]
+    strategy_all_markets: Option<RoundAggregate>,
 }
 
 #[derive(Debug, Clone)]
@@ -826,10 +897,41 @@ struct FillTrace {
     direction: TradeDirection,
     size: f64,
     price: f64,
+    fee: FeeQuote,
     ts: Instant,
     trusted_mid_yes_at_fill: Option<f64>,
 }
 
+/// Restart-surviving [`RoundValidationCollector`] counters. The trusted-mid
+/// series is not persisted, so post-fill adverse-move stats only cover fills
+/// observed after the restart window refills it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RoundValidationCollectorState {
+    elapsed_ms: u64,
+    fills: Vec<PersistedFillTrace>,
+    seen_matched: Vec<String>,
+    max_abs_net_diff: f64,
+    tw_abs_net_diff_accum: f64,
+    max_inventory_value: f64,
+    time_in_guarded_ms: u64,
+    time_in_blocked_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedFillTrace {
+    key: String,
+    side: Side,
+    direction: TradeDirection,
+    size: f64,
+    price: f64,
+    #[serde(default)]
+    fee: FeeQuote,
+    ago_ms: u64,
+    trusted_mid_yes_at_fill: Option<f64>,
+}
+
+const ROUND_VALIDATION_STATE_ACTOR: &str = "round_validation";
+
 #[derive(Debug)]
 struct RoundValidationCollector {
     market_slug: String,
@@ -850,6 +952,7 @@ struct RoundValidationCollector {
     last_regime_ts: Instant,
     time_in_guarded_ms: u64,
     time_in_blocked_ms: u64,
+    fees: FeeSchedule,
 }
 
 impl RoundValidationCollector {
@@ -873,7 +976,74 @@ impl RoundValidationCollector {
             last_regime_ts: now,
             time_in_guarded_ms: 0,
             time_in_blocked_ms: 0,
+            fees: FeeSchedule::from_env(),
+        }
+    }
+
+    fn export_state(&self, now: Instant) -> RoundValidationCollectorState {
+        RoundValidationCollectorState {
+            elapsed_ms: now
+                .saturating_duration_since(self.start_instant)
+                .as_millis() as u64,
+            fills: self
+                .fills
+                .iter()
+                .map(|(key, trace)| PersistedFillTrace {
+                    key: key.clone(),
+                    side: trace.side,
+                    direction: trace.direction,
+                    size: trace.size,
+                    price: trace.price,
+                    fee: trace.fee,
+                    ago_ms: now.saturating_duration_since(trace.ts).as_millis() as u64,
+                    trusted_mid_yes_at_fill: trace.trusted_mid_yes_at_fill,
+                })
+                .collect(),
+            seen_matched: self.seen_matched.iter().cloned().collect(),
+            max_abs_net_diff: self.max_abs_net_diff,
+            tw_abs_net_diff_accum: self.tw_abs_net_diff_accum,
+            max_inventory_value: self.max_inventory_value,
+            time_in_guarded_ms: self.time_in_guarded_ms,
+            time_in_blocked_ms: self.time_in_blocked_ms,
+        }
+    }
+
+    /// Resume counters of the same round; the restart gap counts as elapsed
+    /// round time but contributes no net-diff or regime time.
+    fn restore_state(
+        &mut self,
+        restored: RestoredState<RoundValidationCollectorState>,
+        now: Instant,
+    ) {
+        let RestoredState { state, age } = restored;
+        if let Some(start) = restore_past(Some(state.elapsed_ms), age, now) {
+            self.start_instant = start;
+        }
+        for fill in state.fills {
+            let ts = restore_past(Some(fill.ago_ms), age, now).unwrap_or(self.start_instant);
+            self.fills.insert(
+                fill.key,
+                FillTrace {
+                    side: fill.side,
+                    direction: fill.direction,
+                    size: fill.size,
+                    price: fill.price,
+                    fee: fill.fee,
+                    ts,
+                    trusted_mid_yes_at_fill: fill.trusted_mid_yes_at_fill,
+                },
+            );
         }
+        self.seen_matched.extend(state.seen_matched);
+        self.max_abs_net_diff = self.max_abs_net_diff.max(state.max_abs_net_diff);
+        self.tw_abs_net_diff_accum += state.tw_abs_net_diff_accum;
+        self.max_inventory_value = self.max_inventory_value.max(state.max_inventory_value);
+        self.time_in_guarded_ms = self
+            .time_in_guarded_ms
+            .saturating_add(state.time_in_guarded_ms);
+        self.time_in_blocked_ms = self
+            .time_in_blocked_ms
+            .saturating_add(state.time_in_blocked_ms);
     }
 
     fn fill_key(fill: &FillEvent) -> String {
@@ -901,11 +1071,13 @@ impl RoundValidationCollector {
         }
 
         let trusted_mid_yes_at_fill = self.trusted_mid_at_or_before(now);
+        let fee = self.fees.quote_fill(&fill);
         self.fills.entry(key).or_insert_with(|| FillTrace {
             side: fill.side,
             direction: fill.direction,
             size: fill.filled_size.max(0.0),
             price: fill.price,
+            fee,
             ts: fill.ts,
             trusted_mid_yes_at_fill,
         });
@@ -1013,6 +1185,8 @@ impl RoundValidationCollector {
         let (mut yes_buy_cost, mut yes_sell_notional, mut no_buy_cost, mut no_sell_notional) =
             (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64);
         let mut realized_cash_pnl = 0.0_f64;
+        let mut fee_tally = FeeTally::default();
+        let mut ledger_fills = Vec::with_capacity(self.fills.len());
 
         let mut edge_weighted_sum = 0.0_f64;
         let mut edge_weight = 0.0_f64;
@@ -1025,6 +1199,19 @@ impl RoundValidationCollector {
             if fill.size <= 0.0 || !fill.price.is_finite() {
                 continue;
             }
+            fee_tally.add(fill.fee);
+            ledger_fills.push(LedgerFill {
+                ts_ms: self.round_start_ts_ms
+                    + fill
+                        .ts
+                        .saturating_duration_since(self.start_instant)
+                        .as_millis() as i64,
+                side: fill.side,
+                direction: fill.direction,
+                size: fill.size,
+                price: fill.price,
+                fee: fill.fee,
+            });
             let side_trusted_at_fill =
                 fill.trusted_mid_yes_at_fill.map(|yes_mid| match fill.side {
                     Side::Yes => yes_mid,
@@ -1147,6 +1334,8 @@ impl RoundValidationCollector {
             None
         };
 
+        realized_cash_pnl -= fee_tally.net();
+
         let mean_entry_edge_vs_trusted_mid = if edge_weight > 0.0 {
             Some(edge_weighted_sum / edge_weight)
         } else {
@@ -1218,6 +1407,8 @@ impl RoundValidationCollector {
             Some(RoundLossAttribution::EMarketNotSuitable)
         };
 
+        ledger_fills.sort_by_key(|f| f.ts_ms);
+
         RoundValidationSummary {
             market_slug: self.market_slug,
             strategy: self.strategy,
@@ -1247,8 +1438,8 @@ impl RoundValidationCollector {
             residual_inventory_cost_end,
             residual_exit_required,
             loss_attribution,
-            fees_paid_est: None,
-            maker_rebate_est: None,
+            fees_paid_est: Some(fee_tally.fees_paid),
+            maker_rebate_est: Some(fee_tally.maker_rebates),
             replace_events: coord_obs.replace_events,
             cancel_events: coord_obs.cancel_events,
             publish_events: coord_obs.publish_events,
@@ -1256,6 +1447,7 @@ impl RoundValidationCollector {
             mean_fill_slippage_vs_posted: None,
             fill_to_adverse_move_3s,
             fill_to_adverse_move_10s,
+            fills: ledger_fills,
         }
     }
 }
@@ -1271,15 +1463,39 @@ async fn run_round_validation_collector(
     coord_obs_rx: watch::Receiver<CoordinatorObsSnapshot>,
     mut stop_rx: oneshot::Receiver<()>,
     partial_round: bool,
+    state_store: Option<ActorStateHandle>,
 ) -> RoundValidationSummary {
     let start = Instant::now();
     let mut collector =
         RoundValidationCollector::new(market_slug, strategy, round_start_ts_ms, start);
+    let restored = match state_store.as_ref() {
+        Some(store) => {
+            store
+                .load::<RoundValidationCollectorState>(ROUND_VALIDATION_STATE_ACTOR)
+                .await
+        }
+        None => None,
+    };
+    if let Some(restored) = restored {
+        info!(
+            "♻️ RoundValidation state restored | fills={} age={}ms",
+            restored.state.fills.len(),
+            restored.age.as_millis()
+        );
+        collector.restore_state(restored, start);
+    }
     collector.note_inventory(inv_rx.borrow().working, start);
     collector.note_glft(*glft_rx.borrow(), start);
     collector.note_market_data(md_rx.borrow().clone());
+    let mut state_save = StateSaveThrottle::default();
 
     loop {
+        if let Some(store) = state_store.as_ref() {
+            let now = Instant::now();
+            if state_save.due(now, store.interval()) {
+                store.save(ROUND_VALIDATION_STATE_ACTOR, &collector.export_state(now));
+            }
+        }
         tokio::select! {
             _ = &mut stop_rx => {
                 break;
@@ -1367,6 +1583,7 @@ fn log_path(file_name: &str) -> PathBuf {
     log_root().join(file_name)
 }
 
+/// Historical file name; rows carry `strategy` and every strategy appends here.
 fn round_validation_jsonl_path() -> PathBuf {
     log_path("round_validation_glft_mm.jsonl")
 }
@@ -1429,7 +1646,7 @@ fn median(mut values: Vec<f64>) -> Option<f64> {
     }
 }
 
-fn aggregate_round_validation(entries: &[RoundValidationSummary]) -> RoundValidationAggregate {
+fn aggregate_round_validation(entries: &[RoundValidationSummary]) -> RoundAggregate {
     let rounds_total = entries.len();
     let rounds_partial = entries.iter().filter(|s| s.partial_round).count();
     let rounds_full = rounds_total.saturating_sub(rounds_partial);
@@ -1477,7 +1694,7 @@ fn aggregate_round_validation(entries: &[RoundValidationSummary]) -> RoundValida
     } else {
         None
     };
-    RoundValidationAggregate {
+    RoundAggregate {
         rounds_total,
         rounds_partial,
         rounds_full,
@@ -1485,6 +1702,8 @@ fn aggregate_round_validation(entries: &[RoundValidationSummary]) -> RoundValida
         total_realized_round_pnl_ex_residual,
         total_mark_to_mid_pnl_end,
         total_residual_inventory_cost_end,
+        total_fees_paid: entries.iter().filter_map(|s| s.fees_paid_est).sum(),
+        total_maker_rebate: entries.iter().filter_map(|s| s.maker_rebate_est).sum(),
         median_round_pnl,
         median_round_pnl_ex_residual,
         mean_fill_to_adverse_move_3s,
@@ -1494,24 +1713,80 @@ fn aggregate_round_validation(entries: &[RoundValidationSummary]) -> RoundValida
     }
 }
 
-fn update_round_validation_report(strategy: &str, market_slug: &str) -> anyhow::Result<()> {
-    let mut entries = load_round_validation_summaries()?;
-    entries.retain(|s| s.strategy == strategy && s.market_slug == market_slug);
-    let all = aggregate_round_validation(&entries);
-    let last_5 = if entries.len() >= 5 {
-        Some(aggregate_round_validation(&entries[entries.len() - 5..]))
-    } else {
-        None
-    };
-    let last_20 = if entries.len() >= 20 {
-        Some(aggregate_round_validation(&entries[entries.len() - 20..]))
-    } else {
-        None
+fn record_round_validation_summary(
+    ledger: &PnlLedger,
+    summary: &RoundValidationSummary,
+) -> anyhow::Result<()> {
+    let loss_attribution = summary
+        .loss_attribution
+        .and_then(|a| serde_json::to_value(a).ok())
+        .and_then(|v| v.as_str().map(str::to_string));
+    let record = RoundRecord {
+        strategy: summary.strategy.clone(),
+        market_slug: summary.market_slug.clone(),
+        round_start_ts_ms: summary.round_start_ts_ms,
+        round_end_ts_ms: summary.round_end_ts_ms,
+        partial_round: summary.partial_round,
+        buy_fill_count: summary.buy_fill_count,
+        sell_fill_count: summary.sell_fill_count,
+        realized_cash_pnl: summary.realized_cash_pnl,
+        realized_round_pnl_ex_residual: summary.realized_round_pnl_ex_residual,
+        mark_to_mid_pnl_end: summary.mark_to_mid_pnl_end,
+        residual_inventory_cost_end: summary.residual_inventory_cost_end,
+        fees_paid: summary.fees_paid_est.unwrap_or(0.0),
+        maker_rebate: summary.maker_rebate_est.unwrap_or(0.0),
+        max_abs_net_diff: summary.max_abs_net_diff,
+        time_weighted_abs_net_diff: summary.time_weighted_abs_net_diff,
+        fill_to_adverse_move_3s: summary.fill_to_adverse_move_3s,
+        fill_to_adverse_move_10s: summary.fill_to_adverse_move_10s,
+        loss_attribution,
+        summary_json: serde_json::to_string(summary)?,
     };
-    let last_50 = if entries.len() >= 50 {
-        Some(aggregate_round_validation(&entries[entries.len() - 50..]))
-    } else {
-        None
+    ledger.record_round(&record, &summary.fills)?;
+    Ok(())
+}
+
+/// Rolling aggregates come from the PnL ledger when it is open; without it
+/// the JSONL log is rescanned.
+fn update_round_validation_report(
+    ledger: Option<&PnlLedger>,
+    strategy: &str,
+    market_slug: &str,
+) -> anyhow::Result<()> {
+    let (all, last_5, last_20, last_50, strategy_all_markets) = match ledger {
+        Some(ledger) => {
+            let market = RoundFilter {
+                instance_id: Some(ledger.instance_id()),
+                strategy: Some(strategy),
+                market_slug: Some(market_slug),
+            };
+            let strategy_wide = RoundFilter {
+                market_slug: None,
+                ..market
+            };
+            (
+                ledger.aggregate(market, None)?,
+                ledger.aggregate_last(market, 5)?,
+                ledger.aggregate_last(market, 20)?,
+                ledger.aggregate_last(market, 50)?,
+                Some(ledger.aggregate(strategy_wide, None)?),
+            )
+        }
+        None => {
+            let mut entries = load_round_validation_summaries()?;
+            entries.retain(|s| s.strategy == strategy && s.market_slug == market_slug);
+            let last_n = |n: usize| {
+                (entries.len() >= n)
+                    .then(|| aggregate_round_validation(&entries[entries.len() - n..]))
+            };
+            (
+                aggregate_round_validation(&entries),
+                last_n(5),
+                last_n(20),
+                last_n(50),
+                None,
+            )
+        }
     };
 
     let report = RoundValidationAggregateReport {
@@ -1522,6 +1797,7 @@ fn update_round_validation_report(strategy: &str, market_slug: &str) -> anyhow::
         last_5,
         last_20,
         last_50,
+        strategy_all_markets,
     };
     let path = round_validation_aggregate_path();
     if let Some(parent) = path.parent() {
@@ -2398,6 +2674,16 @@ fn oracle_lag_symbol_from_slug(slug: &str) -> Option<String> {
     }
 }
 
+/// LocalPriceHub symbol backing the GLFT multi-venue reference (any timeframe).
+fn glft_reference_hub_symbol(slug: &str) -> Option<String> {
+    let (symbol, _) = extract_updown_symbol_timeframe(slug)?;
+    Some(format!("{}/usd", symbol))
+}
+
+fn glft_multi_source_reference_enabled(coord_cfg: &CoordinatorConfig) -> bool {
+    coord_cfg.strategy.is_glft_mm() && ReferenceBlendConfig::from_env().multi_source
+}
+
 fn parse_multi_market_prefixes_from_env() -> Vec<String> {
     let Some(raw) = env::var("PM_MULTI_MARKET_PREFIXES")
         .ok()
@@ -3051,6 +3337,70 @@ async fn maybe_log_claimable_positions(funder_address: Option<&str>, signer_addr
     }
 }
 
+const ACTOR_STATE_PROCESS_SCOPE: &str = "process";
+const AUTO_CLAIM_STATE_ACTOR: &str = "auto_claim";
+const PENDING_ROUND_CLAIM_ACTOR: &str = "round_claim";
+
+/// Round claim window still running when the process stopped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingRoundClaim {
+    ended_condition: Option<String>,
+    launched_at_ms: i64,
+}
+
+/// Relaunch a round claim window interrupted by a restart, provided its
+/// window has not elapsed yet.
+async fn resume_pending_round_claim(
+    state: Option<&ActorStateHandle>,
+    cfg: &AutoClaimConfig,
+    runner_cfg: &RoundClaimRunnerConfig,
+    funder_address: Option<String>,
+    signer_address: Option<String>,
+    private_key: Option<String>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let store = state?.clone();
+    let pending = store
+        .load::<PendingRoundClaim>(PENDING_ROUND_CLAIM_ACTOR)
+        .await?
+        .state;
+    let elapsed_ms = unix_now_ms().saturating_sub(pending.launched_at_ms).max(0) as u128;
+    if elapsed_ms >= runner_cfg.window.as_millis() {
+        store.clear(PENDING_ROUND_CLAIM_ACTOR);
+        return None;
+    }
+    let ended_condition = pending
+        .ended_condition
+        .as_deref()
+        .and_then(|v| v.parse::<alloy::primitives::B256>().ok());
+    info!(
+        "♻️ Resuming interrupted round claim window | ended_condition={} elapsed={}ms",
+        pending.ended_condition.as_deref().unwrap_or("none"),
+        elapsed_ms
+    );
+    let cfg = cfg.clone();
+    let runner_cfg = runner_cfg.clone();
+    Some(tokio::spawn(async move {
+        let mut round_state = AutoClaimState::default();
+        if let Err(e) = run_round_claim_window(
+            &cfg,
+            &mut round_state,
+            &runner_cfg,
+            ended_condition,
+            funder_address.as_deref(),
+            signer_address.as_deref(),
+            private_key.as_deref(),
+            None,
+            None,
+            None,
+        )
+        .await
+        {
+            warn!("⚠️ Resumed round claim runner failed: {:?}", e);
+        }
+        store.clear(PENDING_ROUND_CLAIM_ACTOR);
+    }))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_round_claim_window(
     cfg: &AutoClaimConfig,
@@ -3062,6 +3412,7 @@ async fn run_round_claim_window(
     private_key: Option<&str>,
     recorder: Option<&RecorderHandle>,
     recorder_meta: Option<&RecorderSessionMeta>,
+    pnl_ledger: Option<&MarketPnlLedger>,
 ) -> anyhow::Result<()> {
     if !cfg.enabled {
         info!("💸 Round claim runner skipped: PM_AUTO_CLAIM disabled");
@@ -3153,6 +3504,21 @@ async fn run_round_claim_window(
         .await
         {
             Ok(outcome) => {
+                if let Some(ledger) = pnl_ledger {
+                    for evidence in &outcome.redeem_evidence {
+                        ledger.record_redeem(
+                            &RedeemRecord {
+                                condition_id: evidence.condition_id.clone(),
+                                execution_mode: evidence.execution_mode.clone(),
+                                tx_hash: evidence.tx_hash.clone(),
+                                relayer_tx_id: evidence.relayer_tx_id.clone(),
+                                confirmed: evidence.confirmed,
+                                value_usdc: evidence.value_usdc,
+                            },
+                            unix_now_ms(),
+                        );
+                    }
+                }
                 if let (Some(rec), Some(meta)) = (recorder, recorder_meta) {
                     rec.emit_redeem_result(
                         meta,
@@ -3482,14 +3848,8 @@ const DATA_STREAMS_CONNECT_TIMEOUT_MS: u64 = 2_500;
 const SELF_BUILT_AGG_OPEN_TOLERANCE_MS_DEFAULT: u64 = 1_200;
 const SELF_BUILT_AGG_CLOSE_TOLERANCE_MS_DEFAULT: u64 = 1_500;
 const SELF_BUILT_AGG_MIN_CONFIDENCE_DEFAULT: f64 = 0.80;
-const LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS_DEFAULT: u64 = 600;
 // close-only shadow compare is diagnostic, not execution-critical; allow a wider
 // close window so we can collect enough multi-source samples near round end.
-const LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS_DEFAULT: u64 = 2_500;
-const LOCAL_PRICE_AGG_MIN_CONFIDENCE_DEFAULT: f64 = 0.85;
-const LOCAL_PRICE_AGG_MIN_SOURCES_DEFAULT: usize = 1;
-const LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS_DEFAULT: f64 = 12.0;
-const LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS_DEFAULT: f64 = 1.25;
 const LOCAL_PRICE_AGG_RELIEF_MIN_CONFIDENCE_DEFAULT: f64 = 0.80;
 const LOCAL_PRICE_AGG_RELIEF_MIN_DIRECTION_MARGIN_BPS_DEFAULT: f64 = 5.0;
 const LOCAL_PRICE_AGG_RELIEF_MAX_SOURCE_SPREAD_BPS_DEFAULT: f64 = 2.0;
@@ -3497,16 +3857,6 @@ const LOCAL_PRICE_AGG_SAFE_LOW_CONF_RELIEF_MIN_SOURCES: usize = 2;
 const LOCAL_PRICE_AGG_SAFE_LOW_CONF_RELIEF_MIN_DIRECTION_MARGIN_BPS: f64 = 3.0;
 const LOCAL_PRICE_AGG_SAFE_LOW_CONF_RELIEF_MAX_SOURCE_SPREAD_BPS: f64 = 5.0;
 const LOCAL_PRICE_AGG_DECISION_WAIT_MS_DEFAULT: u64 = 1_500;
-const LOCAL_PRICE_AGG_WEIGHT_BINANCE_DEFAULT: f64 = 1.0;
-const LOCAL_PRICE_AGG_WEIGHT_BYBIT_DEFAULT: f64 = 1.0;
-const LOCAL_PRICE_AGG_WEIGHT_OKX_DEFAULT: f64 = 1.0;
-const LOCAL_PRICE_AGG_WEIGHT_COINBASE_DEFAULT: f64 = 1.0;
-const LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID_DEFAULT: f64 = 0.5;
-const LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS_DEFAULT: f64 = 900.0;
-const LOCAL_PRICE_AGG_EXACT_BOOST_DEFAULT: f64 = 1.25;
-const LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA: f64 = 0.30;
-const LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS: f64 = 10.0;
-const LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS: f64 = 25.0;
 const LOCAL_PRICE_AGG_COMPARE_DEADLINE_GRACE_MS: u64 = 250;
 const LOCAL_PRICE_AGG_COMPARE_TARGET_BPS: f64 = 5.0;
 const LOCAL_PRICE_AGG_COMPARE_TARGET_MIN_MATCH_DECIMALS: usize = 12;
@@ -3564,17 +3914,6 @@ fn post_close_chainlink_max_wait_secs() -> u64 {
         .unwrap_or(8)
 }
 
-fn env_bool(name: &str, default: bool) -> bool {
-    match env::var(name) {
-        Ok(v) => match v.trim().to_ascii_lowercase().as_str() {
-            "1" | "true" | "yes" | "on" => true,
-            "0" | "false" | "no" | "off" => false,
-            _ => default,
-        },
-        Err(_) => default,
-    }
-}
-
 fn self_built_price_agg_enabled() -> bool {
     env_bool("PM_SELF_BUILT_PRICE_AGG_ENABLED", true)
 }
@@ -3611,54 +3950,6 @@ fn local_price_agg_decision_enabled() -> bool {
     env_bool("PM_LOCAL_PRICE_AGG_DECISION_ENABLED", false)
 }
 
-fn local_price_agg_open_tolerance_ms() -> u64 {
-    env::var("PM_LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .map(|v| v.clamp(50, 5_000))
-        .unwrap_or(LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS_DEFAULT)
-}
-
-fn local_price_agg_close_tolerance_ms() -> u64 {
-    env::var("PM_LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .map(|v| v.clamp(50, 5_000))
-        .unwrap_or(LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS_DEFAULT)
-}
-
-fn local_price_agg_min_confidence() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_MIN_CONFIDENCE")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 1.0))
-        .unwrap_or(LOCAL_PRICE_AGG_MIN_CONFIDENCE_DEFAULT)
-}
-
-fn local_price_agg_min_sources() -> usize {
-    env::var("PM_LOCAL_PRICE_AGG_MIN_SOURCES")
-        .ok()
-        .and_then(|v| v.parse::<usize>().ok())
-        .map(|v| v.clamp(1, 8))
-        .unwrap_or(LOCAL_PRICE_AGG_MIN_SOURCES_DEFAULT)
-}
-
-fn local_price_agg_max_source_spread_bps() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 500.0))
-        .unwrap_or(LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS_DEFAULT)
-}
-
-fn local_price_agg_single_source_min_direction_margin_bps() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 20.0))
-        .unwrap_or(LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS_DEFAULT)
-}
-
 fn local_price_agg_decision_wait_ms() -> u64 {
     env::var("PM_LOCAL_PRICE_AGG_DECISION_WAIT_MS")
         .ok()
@@ -3667,70 +3958,6 @@ fn local_price_agg_decision_wait_ms() -> u64 {
         .unwrap_or(LOCAL_PRICE_AGG_DECISION_WAIT_MS_DEFAULT)
 }
 
-fn local_price_agg_weight_binance() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_WEIGHT_BINANCE")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 10.0))
-        .unwrap_or(LOCAL_PRICE_AGG_WEIGHT_BINANCE_DEFAULT)
-}
-
-fn local_price_agg_weight_bybit() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_WEIGHT_BYBIT")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 10.0))
-        .unwrap_or(LOCAL_PRICE_AGG_WEIGHT_BYBIT_DEFAULT)
-}
-
-fn local_price_agg_weight_okx() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_WEIGHT_OKX")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 10.0))
-        .unwrap_or(LOCAL_PRICE_AGG_WEIGHT_OKX_DEFAULT)
-}
-
-fn local_price_agg_weight_coinbase() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_WEIGHT_COINBASE")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 10.0))
-        .unwrap_or(LOCAL_PRICE_AGG_WEIGHT_COINBASE_DEFAULT)
-}
-
-fn local_price_agg_weight_hyperliquid() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(0.0, 10.0))
-        .unwrap_or(LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID_DEFAULT)
-}
-
-fn local_price_agg_close_time_decay_ms() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(50.0, 10_000.0))
-        .unwrap_or(LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS_DEFAULT)
-}
-
-fn local_price_agg_exact_boost() -> f64 {
-    env::var("PM_LOCAL_PRICE_AGG_EXACT_BOOST")
-        .ok()
-        .and_then(|v| v.parse::<f64>().ok())
-        .map(|v| v.clamp(1.0, 3.0))
-        .unwrap_or(LOCAL_PRICE_AGG_EXACT_BOOST_DEFAULT)
-}
-
-fn local_price_agg_boundary_window_ms() -> u64 {
-    env::var("PM_LOCAL_PRICE_AGG_BOUNDARY_WINDOW_MS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .map(|v| v.clamp(100, 30_000))
-        .unwrap_or(5_000)
-}
-
 fn local_price_agg_uncertainty_gate_enabled() -> bool {
     env_bool("PM_LOCAL_AGG_UNCERTAINTY_GATE_ENABLED", false)
 }
@@ -3743,6 +3970,24 @@ fn local_price_agg_uncertainty_gate_finalize_ms() -> u64 {
         .unwrap_or(2_500)
 }
 
+fn local_price_agg_uncertainty_gate_frozen() -> bool {
+    env_bool("PM_LOCAL_AGG_UNCERTAINTY_GATE_FROZEN", false)
+}
+
+fn local_price_agg_uncertainty_gate_online_window() -> usize {
+    env::var("PM_LOCAL_AGG_UNCERTAINTY_GATE_ONLINE_WINDOW")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .map(|v| v.clamp(16, 20_000))
+        .unwrap_or(UNCERTAINTY_GATE_ONLINE_WINDOW_DEFAULT)
+}
+
+fn local_price_agg_uncertainty_gate_online_model_path(base: &Path) -> PathBuf {
+    env_nonempty_var(&["PM_LOCAL_AGG_UNCERTAINTY_GATE_ONLINE_MODEL_PATH"])
+        .map(PathBuf::from)
+        .unwrap_or_else(|| base.with_file_name("local_agg_uncertainty_gate_model.online.json"))
+}
+
 fn local_price_agg_uncertainty_gate_model_path() -> Option<PathBuf> {
     env_nonempty_var(&["PM_LOCAL_AGG_UNCERTAINTY_GATE_MODEL_PATH"])
         .map(PathBuf::from)
@@ -3833,424 +4078,46 @@ fn post_close_gamma_poll_ms() -> u64 {
         .unwrap_or(300)
 }
 
-fn normalize_chainlink_symbol(raw: &str) -> String {
-    raw.trim()
-        .to_ascii_lowercase()
-        .replace(['-', '_'], "/")
-        .replace("//", "/")
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum LocalPriceSource {
-    Binance,
-    Bybit,
-    Okx,
-    Coinbase,
-    Hyperliquid,
+fn chainlink_symbol_from_slug(slug: &str) -> Option<String> {
+    let prefix = slug.split("-updown-").next()?.trim().to_ascii_lowercase();
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(format!("{}/usd", prefix))
+    }
 }
 
-impl LocalPriceSource {
-    fn as_str(self) -> &'static str {
-        match self {
-            Self::Binance => "binance",
-            Self::Bybit => "bybit",
-            Self::Okx => "okx",
-            Self::Coinbase => "coinbase",
-            Self::Hyperliquid => "hyperliquid",
-        }
+fn parse_u64_value(v: &Value) -> Option<u64> {
+    if let Some(u) = v.as_u64() {
+        return Some(u);
     }
-
-    fn from_str(raw: &str) -> Option<Self> {
-        match raw.trim().to_ascii_lowercase().as_str() {
-            "binance" => Some(Self::Binance),
-            "bybit" => Some(Self::Bybit),
-            "okx" => Some(Self::Okx),
-            "coinbase" => Some(Self::Coinbase),
-            "hyperliquid" => Some(Self::Hyperliquid),
-            _ => None,
-        }
+    if let Some(i) = v.as_i64() {
+        return (i >= 0).then_some(i as u64);
     }
-}
-
-fn local_price_agg_source_weight(source: LocalPriceSource) -> f64 {
-    match source {
-        LocalPriceSource::Binance => local_price_agg_weight_binance(),
-        LocalPriceSource::Bybit => local_price_agg_weight_bybit(),
-        LocalPriceSource::Okx => local_price_agg_weight_okx(),
-        LocalPriceSource::Coinbase => local_price_agg_weight_coinbase(),
-        LocalPriceSource::Hyperliquid => local_price_agg_weight_hyperliquid(),
+    if let Some(f) = v.as_f64() {
+        return (f.is_finite() && f >= 0.0).then_some(f as u64);
     }
+    v.as_str()?.trim().parse::<u64>().ok()
 }
 
-const LOCAL_PRICE_SOURCES: [LocalPriceSource; 5] = [
-    LocalPriceSource::Binance,
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Coinbase,
-    LocalPriceSource::Hyperliquid,
-];
-
-fn binance_stream_symbol_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
-    let (base, quote) = chainlink_symbol.split_once('/')?;
-    if !quote.eq_ignore_ascii_case("usd") {
-        return None;
-    }
-    let base = base.trim().to_ascii_lowercase();
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}usdt", base))
+fn parse_f64_value(v: &Value) -> Option<f64> {
+    v.as_f64()
+        .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+        .filter(|x| x.is_finite())
 }
 
-fn chainlink_symbol_from_binance_stream_symbol(binance_symbol: &str) -> Option<String> {
-    let lower = binance_symbol.trim().to_ascii_lowercase();
-    let base = lower.strip_suffix("usdt")?;
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}/usd", base))
+#[derive(Debug, Deserialize)]
+struct DataStreamsAuthorizeEnvelope {
+    d: Option<DataStreamsAuthorizeData>,
+    #[allow(dead_code)]
+    s: Option<String>,
 }
 
-fn parse_binance_combined_trade_tick(text: &str) -> Option<(String, f64, u64)> {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return None;
-    };
-    let stream = value
-        .get("stream")
-        .and_then(|v| v.as_str())
-        .or_else(|| value.get("s").and_then(|v| v.as_str()))?;
-    let stream_symbol = stream
-        .split('@')
-        .next()
-        .map(str::to_string)
-        .unwrap_or_default();
-    let chainlink_symbol = chainlink_symbol_from_binance_stream_symbol(&stream_symbol)?;
-    let data = value.get("data").unwrap_or(&value);
-    let price = data.get("p").and_then(parse_f64_value)?;
-    if !price.is_finite() || price <= 0.0 {
-        return None;
-    }
-    let ts_ms = data
-        .get("T")
-        .and_then(parse_unix_ms_value)
-        .or_else(|| data.get("E").and_then(parse_unix_ms_value))
-        .unwrap_or_else(unix_now_millis_u64);
-    Some((chainlink_symbol, price, ts_ms))
-}
-
-fn bybit_stream_symbol_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
-    let (base, quote) = chainlink_symbol.split_once('/')?;
-    if !quote.eq_ignore_ascii_case("usd") {
-        return None;
-    }
-    let base = base.trim();
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}USDT", base.to_ascii_uppercase()))
-}
-
-fn chainlink_symbol_from_bybit_stream_symbol(bybit_symbol: &str) -> Option<String> {
-    let upper = bybit_symbol.trim().to_ascii_uppercase();
-    let base = upper.strip_suffix("USDT")?;
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}/usd", base.to_ascii_lowercase()))
-}
-
-fn parse_bybit_public_trade_ticks(text: &str) -> Vec<(String, f64, u64)> {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return vec![];
-    };
-    let topic = value.get("topic").and_then(|v| v.as_str()).unwrap_or("");
-    if !topic.starts_with("publicTrade.") {
-        return vec![];
-    }
-    let bybit_symbol = topic.trim_start_matches("publicTrade.");
-    let Some(chainlink_symbol) = chainlink_symbol_from_bybit_stream_symbol(bybit_symbol) else {
-        return vec![];
-    };
-    let mut out = Vec::new();
-    if let Some(items) = value.get("data").and_then(|v| v.as_array()) {
-        for item in items {
-            let price = item.get("p").and_then(parse_f64_value).unwrap_or(0.0);
-            if !price.is_finite() || price <= 0.0 {
-                continue;
-            }
-            let ts_ms = item
-                .get("T")
-                .and_then(parse_unix_ms_value)
-                .or_else(|| item.get("ts").and_then(parse_unix_ms_value))
-                .unwrap_or_else(unix_now_millis_u64);
-            out.push((chainlink_symbol.clone(), price, ts_ms));
-        }
-    }
-    out
-}
-
-fn okx_inst_id_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
-    let (base, quote) = chainlink_symbol.split_once('/')?;
-    if !quote.eq_ignore_ascii_case("usd") {
-        return None;
-    }
-    let base = base.trim();
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}-USDT", base.to_ascii_uppercase()))
-}
-
-fn chainlink_symbol_from_okx_inst_id(inst_id: &str) -> Option<String> {
-    let upper = inst_id.trim().to_ascii_uppercase();
-    let base = upper.strip_suffix("-USDT")?;
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}/usd", base.to_ascii_lowercase()))
-}
-
-fn parse_okx_trade_ticks(text: &str) -> Vec<(String, f64, u64)> {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return vec![];
-    };
-    let arg = value.get("arg");
-    let channel = arg
-        .and_then(|a| a.get("channel"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    if channel != "trades" {
-        return vec![];
-    }
-    let inst_id = arg
-        .and_then(|a| a.get("instId"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    let Some(chainlink_symbol) = chainlink_symbol_from_okx_inst_id(inst_id) else {
-        return vec![];
-    };
-    let mut out = Vec::new();
-    if let Some(items) = value.get("data").and_then(|v| v.as_array()) {
-        for item in items {
-            let price = item.get("px").and_then(parse_f64_value).unwrap_or(0.0);
-            if !price.is_finite() || price <= 0.0 {
-                continue;
-            }
-            let ts_ms = item
-                .get("ts")
-                .and_then(parse_unix_ms_value)
-                .unwrap_or_else(unix_now_millis_u64);
-            out.push((chainlink_symbol.clone(), price, ts_ms));
-        }
-    }
-    out
-}
-
-fn coinbase_product_id_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
-    let (base, quote) = chainlink_symbol.split_once('/')?;
-    if !quote.eq_ignore_ascii_case("usd") {
-        return None;
-    }
-    let base = base.trim();
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}-USD", base.to_ascii_uppercase()))
-}
-
-fn hyperliquid_coin_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
-    let (base, quote) = chainlink_symbol.split_once('/')?;
-    if !quote.eq_ignore_ascii_case("usd") {
-        return None;
-    }
-    let base = base.trim().to_ascii_uppercase();
-    if base == "HYPE" {
-        Some(base)
-    } else {
-        None
-    }
-}
-
-fn chainlink_symbol_from_coinbase_product_id(product_id: &str) -> Option<String> {
-    let upper = product_id.trim().to_ascii_uppercase();
-    let base = upper.strip_suffix("-USD")?;
-    if base.is_empty() {
-        return None;
-    }
-    Some(format!("{}/usd", base.to_ascii_lowercase()))
-}
-
-fn parse_coinbase_ticker_tick(text: &str) -> Option<(String, f64, u64)> {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return None;
-    };
-    if value.get("type").and_then(|v| v.as_str()) != Some("ticker") {
-        return None;
-    }
-    let product_id = value.get("product_id").and_then(|v| v.as_str())?;
-    let chainlink_symbol = chainlink_symbol_from_coinbase_product_id(product_id)?;
-    let price = value.get("price").and_then(parse_f64_value)?;
-    if !price.is_finite() || price <= 0.0 {
-        return None;
-    }
-    let ts_ms = value
-        .get("time")
-        .and_then(|v| v.as_str())
-        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
-        .map(|dt| dt.timestamp_millis().max(0) as u64)
-        .unwrap_or_else(unix_now_millis_u64);
-    Some((chainlink_symbol, price, ts_ms))
-}
-
-fn parse_hyperliquid_all_mids_ticks(
-    text: &str,
-    wanted_coins: &HashMap<String, String>,
-) -> Vec<(String, f64, u64)> {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return Vec::new();
-    };
-    let channel = value
-        .get("channel")
-        .and_then(|v| v.as_str())
-        .or_else(|| value.get("type").and_then(|v| v.as_str()))
-        .unwrap_or_default();
-    if channel != "allMids" {
-        return Vec::new();
-    }
-    let Some(mids) = value
-        .get("data")
-        .and_then(|v| v.get("mids"))
-        .and_then(|v| v.as_object())
-    else {
-        return Vec::new();
-    };
-    let ts_ms = value
-        .get("data")
-        .and_then(|v| v.get("time"))
-        .and_then(parse_u64_value)
-        .unwrap_or_else(unix_now_millis_u64);
-    let mut out = Vec::new();
-    for (coin, pxv) in mids {
-        let Some(symbol) = wanted_coins.get(&coin.to_ascii_uppercase()) else {
-            continue;
-        };
-        let Some(price) = parse_f64_value(pxv).filter(|v| *v > 0.0) else {
-            continue;
-        };
-        out.push((symbol.clone(), price, ts_ms));
-    }
-    out
-}
-
-fn robust_median(mut values: Vec<f64>) -> Option<f64> {
-    if values.is_empty() {
-        return None;
-    }
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-    let mid = values.len() / 2;
-    if values.len() % 2 == 1 {
-        Some(values[mid])
-    } else {
-        Some((values[mid - 1] + values[mid]) / 2.0)
-    }
-}
-
-fn robust_center(mut values: Vec<f64>) -> Option<f64> {
-    if values.is_empty() {
-        return None;
-    }
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-    match values.len() {
-        1 => Some(values[0]),
-        2 => Some((values[0] + values[1]) / 2.0),
-        // Drop one-side outliers and average the center mass; this is usually
-        // closer to Chainlink-style aggregated closes than picking one median tick.
-        _ => {
-            let inner = &values[1..values.len() - 1];
-            let sum: f64 = inner.iter().sum();
-            Some(sum / inner.len() as f64)
-        }
-    }
-}
-
-fn weighted_mean(values: &[(f64, f64)]) -> Option<f64> {
-    let mut weighted_sum = 0.0f64;
-    let mut weight_sum = 0.0f64;
-    for (value, weight) in values {
-        if !value.is_finite() || !weight.is_finite() || *weight <= 0.0 {
-            continue;
-        }
-        weighted_sum += value * weight;
-        weight_sum += weight;
-    }
-    if weight_sum <= 0.0 {
-        None
-    } else {
-        Some(weighted_sum / weight_sum)
-    }
-}
-
-fn spread_bps(values: &[f64]) -> Option<f64> {
-    if values.is_empty() {
-        return None;
-    }
-    let min_v = values.iter().copied().fold(f64::INFINITY, f64::min);
-    let max_v = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-    let median = robust_median(values.to_vec())?;
-    if !median.is_finite() || median <= 0.0 {
-        return None;
-    }
-    Some(((max_v - min_v) / median.abs()) * 10_000.0)
-}
-
-fn median_u64(mut values: Vec<u64>) -> Option<u64> {
-    if values.is_empty() {
-        return None;
-    }
-    values.sort_unstable();
-    Some(values[values.len() / 2])
-}
-
-fn chainlink_symbol_from_slug(slug: &str) -> Option<String> {
-    let prefix = slug.split("-updown-").next()?.trim().to_ascii_lowercase();
-    if prefix.is_empty() {
-        None
-    } else {
-        Some(format!("{}/usd", prefix))
-    }
-}
-
-fn parse_u64_value(v: &Value) -> Option<u64> {
-    if let Some(u) = v.as_u64() {
-        return Some(u);
-    }
-    if let Some(i) = v.as_i64() {
-        return (i >= 0).then_some(i as u64);
-    }
-    if let Some(f) = v.as_f64() {
-        return (f.is_finite() && f >= 0.0).then_some(f as u64);
-    }
-    v.as_str()?.trim().parse::<u64>().ok()
-}
-
-fn parse_f64_value(v: &Value) -> Option<f64> {
-    v.as_f64()
-        .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
-        .filter(|x| x.is_finite())
-}
-
-#[derive(Debug, Deserialize)]
-struct DataStreamsAuthorizeEnvelope {
-    d: Option<DataStreamsAuthorizeData>,
-    #[allow(dead_code)]
-    s: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct DataStreamsAuthorizeData {
-    access_token: String,
-    #[allow(dead_code)]
-    expiration: Option<u64>,
+#[derive(Debug, Deserialize)]
+struct DataStreamsAuthorizeData {
+    access_token: String,
+    #[allow(dead_code)]
+    expiration: Option<u64>,
 }
 
 static DATA_STREAMS_TOKEN_CACHE: OnceLock<Mutex<Option<(String, u64)>>> = OnceLock::new();
@@ -4336,6 +4203,99 @@ fn parse_data_streams_tick(line: &str, target_symbol: &str) -> Option<(f64, u64)
     Some((price, ts_s))
 }
 
+fn data_streams_verifier() -> &'static DataStreamsVerifier {
+    static VERIFIER: OnceLock<DataStreamsVerifier> = OnceLock::new();
+    VERIFIER.get_or_init(|| {
+        let verifier = DataStreamsVerifier::from_env();
+        info!(
+            "🔏 data_streams_verifier | require_verified={} signers={} required_signatures={}",
+            verifier.require_verified(),
+            verifier.signer_count(),
+            verifier.required_signatures(),
+        );
+        if !verifier.require_verified() {
+            warn!(
+                "🚨 data_streams_verifier UNVERIFIED | PM_DATA_STREAMS_REQUIRE_VERIFIED=false: winner hints trust unsigned candlestick prices"
+            );
+        }
+        verifier
+    })
+}
+
+fn data_streams_reports_api_base_url() -> String {
+    env_nonempty_var(&["PM_DATA_STREAMS_REPORTS_API_URL"])
+        .unwrap_or_else(|| DATA_STREAMS_REPORTS_API_DEFAULT.to_string())
+}
+
+fn data_streams_hmac_auth() -> Option<&'static DataStreamsHmacAuth> {
+    static AUTH: OnceLock<Option<DataStreamsHmacAuth>> = OnceLock::new();
+    AUTH.get_or_init(DataStreamsHmacAuth::from_env).as_ref()
+}
+
+/// Verified prices come only from the signed report API, which needs the
+/// symbol's feed ID, the DON signer set and HMAC client credentials.
+fn data_streams_signed_reports_available(chainlink_symbol: &str) -> bool {
+    let verifier = data_streams_verifier();
+    verifier.require_verified()
+        && data_streams_hmac_auth().is_some()
+        && data_streams_symbol_from_chainlink_symbol(chainlink_symbol)
+            .is_some_and(|stream_symbol| verifier.can_verify(&stream_symbol))
+}
+
+/// Decodes and verifies a report API response (`{"report": {"fullReport": ...}}`).
+fn parse_verified_data_streams_report(
+    body: &str,
+    target_symbol: &str,
+    verifier: &DataStreamsVerifier,
+) -> Result<DataStreamsReport, ReportReject> {
+    let value = serde_json::from_str::<Value>(body)
+        .map_err(|err| ReportReject::Malformed(err.to_string()))?;
+    let full_report = full_report_hex(&value)
+        .ok_or_else(|| ReportReject::Malformed("missing fullReport".to_string()))?;
+    verifier.verify_hex(target_symbol, full_report)
+}
+
+/// One HMAC-signed GET against the report API. `None` while the report is not
+/// published yet or the request failed; `Some(Err)` when it fails verification.
+async fn fetch_verified_data_streams_report(
+    client: &reqwest::Client,
+    auth: &DataStreamsHmacAuth,
+    path: &str,
+    stream_symbol: &str,
+) -> Option<Result<DataStreamsReport, ReportReject>> {
+    let base = data_streams_reports_api_base_url();
+    let mut req = client.get(format!("{}{}", base.trim_end_matches('/'), path));
+    for (name, value) in auth.headers("GET", path, b"", unix_now_millis_u64()) {
+        req = req.header(name, value);
+    }
+    let resp = match req.send().await {
+        Ok(resp) => resp,
+        Err(e) => {
+            warn!(
+                "⚠️ data_streams_report_fetch_failed | symbol={} err={}",
+                stream_symbol, e
+            );
+            return None;
+        }
+    };
+    if !resp.status().is_success() {
+        if resp.status() != reqwest::StatusCode::NOT_FOUND {
+            warn!(
+                "⚠️ data_streams_report_http_status | symbol={} status={}",
+                stream_symbol,
+                resp.status()
+            );
+        }
+        return None;
+    }
+    let body = resp.text().await.ok()?;
+    Some(parse_verified_data_streams_report(
+        &body,
+        stream_symbol,
+        data_streams_verifier(),
+    ))
+}
+
 fn parse_unix_ms_value(v: &Value) -> Option<u64> {
     let raw = parse_u64_value(v)?;
     if raw > 10_000_000_000 {
@@ -4545,8 +4505,6 @@ fn parse_chainlink_multi_symbol_ticks(text: &str) -> Vec<(String, f64, u64)> {
 
 const CHAINLINK_HUB_TICK_STALL_RECONNECT_MS_DEFAULT: u64 = 15_000;
 const CHAINLINK_HUB_RECENT_TICKS_PER_SYMBOL: usize = 16_384;
-const LOCAL_PRICE_HUB_RECENT_TICKS_PER_SYMBOL: usize = 16_384;
-
 fn chainlink_hub_tick_stall_reconnect_ms() -> u64 {
     env::var("PM_CHAINLINK_HUB_TICK_STALL_RECONNECT_MS")
         .ok()
@@ -4661,10 +4619,12 @@ fn partial_book_tick_from_post_close_update(side: Side, bid: f64, ask: f64) -> M
 struct ChainlinkHub {
     senders: Arc<HashMap<String, broadcast::Sender<(f64, u64)>>>,
     recent_ticks: Arc<Mutex<HashMap<String, VecDeque<(f64, u64)>>>>,
+    /// Only set for the in-process hub that owns the RTDS connections.
+    pool: Option<Arc<RtdsConnectionPool>>,
 }
 
 impl ChainlinkHub {
-    fn new(symbols: &HashSet<String>) -> Arc<Self> {
+    fn new(symbols: &HashSet<String>, pool: Option<Arc<RtdsConnectionPool>>) -> Arc<Self> {
         let mut senders = HashMap::new();
         let mut recent_ticks = HashMap::new();
         for sym in symbols {
@@ -4675,9 +4635,25 @@ impl ChainlinkHub {
         Arc::new(Self {
             senders: Arc::new(senders),
             recent_ticks: Arc::new(Mutex::new(recent_ticks)),
+            pool,
         })
     }
 
+    /// True when the pool reports no RTDS tick for the symbol within the
+    /// stall window. Remote hubs have no pool and never report a stall.
+    fn symbol_stalled(&self, symbol: &str) -> bool {
+        let sym = normalize_chainlink_symbol(symbol);
+        self.pool
+            .as_ref()
+            .is_some_and(|pool| pool.is_stalled(&sym, unix_now_millis_u64()))
+    }
+
+    fn stall_failover_enabled(&self) -> bool {
+        self.pool
+            .as_ref()
+            .is_some_and(|pool| pool.config().stall_failover)
+    }
+
     fn publish_tick(&self, symbol: &str, price: f64, ts_ms: u64) {
         let sym = normalize_chainlink_symbol(symbol);
         if sym.is_empty() || !price.is_finite() || price <= 0.0 {
@@ -4697,36 +4673,47 @@ impl ChainlinkHub {
     }
 
     fn spawn(symbols: HashSet<String>) -> Arc<Self> {
-        let hub = Self::new(&symbols);
         let ws_url = post_close_chainlink_ws_url();
         let stall_reconnect_ms = chainlink_hub_tick_stall_reconnect_ms();
+        let pool_cfg = RtdsPoolConfig::from_env(stall_reconnect_ms);
         info!(
-            "📡 chainlink_hub_config | tick_stall_reconnect_ms={}",
-            stall_reconnect_ms
+            "📡 chainlink_hub_config | tick_stall_reconnect_ms={} symbols={} max_concurrent_connects={} reconnect_stagger_ms={} backoff_max_ms={} stall_failover={}",
+            stall_reconnect_ms,
+            symbols.len(),
+            pool_cfg.max_concurrent_connects,
+            pool_cfg.reconnect_stagger_ms,
+            pool_cfg.backoff_max_ms,
+            pool_cfg.stall_failover,
         );
+        let pool = Arc::new(RtdsConnectionPool::new(
+            pool_cfg,
+            symbols.iter().map(String::as_str),
+            unix_now_millis_u64(),
+        ));
+        let hub = Self::new(&symbols, Some(Arc::clone(&pool)));
         // One WS connection per symbol: the server only honours ONE active
         // subscription per connection, so sharing a single connection for N
-        // symbols only delivers ticks for the last-subscribed symbol.
+        // symbols only delivers ticks for the last-subscribed symbol. The pool
+        // bounds and staggers their (re)connects and tracks per-symbol health.
         for sym in symbols {
             let tx = hub.senders.get(&sym).expect("sender just inserted").clone();
             let recent_ticks = Arc::clone(&hub.recent_ticks);
             let url = ws_url.clone();
             let stall_reconnect_ms = stall_reconnect_ms;
+            let pool = Arc::clone(&pool);
             tokio::spawn(async move {
-                let mut reconnect_backoff = Duration::from_millis(300);
                 let mut stat_last_log = Instant::now();
                 let mut stat_msgs: u64 = 0;
                 let mut stat_ticks: u64 = 0;
                 let mut stat_delivered: u64 = 0;
                 loop {
+                    let connect_permit = pool.acquire_connect(&sym, unix_now_millis_u64()).await;
                     let connect =
                         tokio::time::timeout(Duration::from_secs(3), connect_async(&url)).await;
                     let Ok(Ok((ws, _resp))) = connect else {
-                        sleep(reconnect_backoff).await;
-                        reconnect_backoff = (reconnect_backoff * 2).min(Duration::from_secs(2));
+                        pool.on_connect_failed(&sym, unix_now_millis_u64());
                         continue;
                     };
-                    reconnect_backoff = Duration::from_millis(300);
                     let (mut write, mut read) = ws.split();
                     let subscribe_msg = json!({
                         "action": "subscribe",
@@ -4741,10 +4728,14 @@ impl ChainlinkHub {
                         .await
                         .is_err()
                     {
+                        pool.on_connect_failed(&sym, unix_now_millis_u64());
                         continue;
                     }
+                    drop(connect_permit);
+                    pool.on_connected(&sym, unix_now_millis_u64());
                     let mut last_tick_at = Instant::now();
                     let mut last_tick_ts_ms: Option<u64> = None;
+                    let mut stalled = false;
                     loop {
                         let next =
                             tokio::time::timeout(Duration::from_millis(1500), read.next()).await;
@@ -4754,6 +4745,7 @@ impl ChainlinkHub {
                             Err(_) => {
                                 let idle = last_tick_at.elapsed();
                                 if idle.as_millis() >= stall_reconnect_ms as u128 {
+                                    stalled = true;
                                     warn!(
                                         "⚠️ chainlink_hub_tick_stall_reconnect | symbol={} idle_ms={} threshold_ms={} msgs={} ticks={} delivered={} last_tick_ts_ms={:?}",
                                         sym,
@@ -4789,6 +4781,7 @@ impl ChainlinkHub {
                             }
                             let idle = last_tick_at.elapsed();
                             if idle.as_millis() >= stall_reconnect_ms as u128 {
+                                stalled = true;
                                 warn!(
                                     "⚠️ chainlink_hub_no_tick_payload_stall | symbol={} idle_ms={} threshold_ms={} msgs={} ticks={} delivered={} last_tick_ts_ms={:?}",
                                     sym,
@@ -4818,6 +4811,7 @@ impl ChainlinkHub {
                                 }
                             }
                             let _ = tx.send((price, ts_ms));
+                            pool.on_tick(&sym, unix_now_millis_u64());
                             stat_delivered = stat_delivered.saturating_add(1);
                             matched_ticks = matched_ticks.saturating_add(1);
                             last_tick_at = Instant::now();
@@ -4827,6 +4821,7 @@ impl ChainlinkHub {
                         if matched_ticks == 0 {
                             let idle = last_tick_at.elapsed();
                             if idle.as_millis() >= stall_reconnect_ms as u128 {
+                                stalled = true;
                                 warn!(
                                     "⚠️ chainlink_hub_symbol_mismatch_stall | symbol={} idle_ms={} threshold_ms={} msgs={} ticks={} delivered={} last_tick_ts_ms={:?}",
                                     sym,
@@ -4848,14 +4843,20 @@ impl ChainlinkHub {
                             stat_last_log = Instant::now();
                         }
                     }
+                    if stalled {
+                        pool.on_stall(&sym, unix_now_millis_u64());
+                    } else {
+                        pool.on_disconnected(&sym, unix_now_millis_u64());
+                    }
                 }
             });
         }
+        tokio::spawn(run_chainlink_hub_pool_supervisor(Arc::clone(&hub), pool));
         hub
     }
 
     fn spawn_remote(symbols: HashSet<String>, socket_path: PathBuf) -> Arc<Self> {
-        let hub = Self::new(&symbols);
+        let hub = Self::new(&symbols, None);
         let target_symbols = symbols
             .into_iter()
             .map(|sym| normalize_chainlink_symbol(&sym))
@@ -4954,422 +4955,257 @@ impl ChainlinkHub {
     }
 }
 
-#[derive(Clone)]
-struct LocalPriceHub {
-    senders: Arc<HashMap<String, broadcast::Sender<(f64, u64, LocalPriceSource)>>>,
-    recent_ticks: Arc<Mutex<HashMap<String, VecDeque<(f64, u64, LocalPriceSource)>>>>,
+/// Data Streams can stand in for a stalled RTDS symbol only when its ticks
+/// would be accepted by the winner-hint path as well.
+fn data_streams_failover_available(chainlink_symbol: &str) -> bool {
+    if data_streams_verifier().require_verified() {
+        return data_streams_signed_reports_available(chainlink_symbol);
+    }
+    chainlink_data_streams_enabled()
+        && data_streams_symbol_from_chainlink_symbol(chainlink_symbol).is_some()
 }
 
-impl LocalPriceHub {
-    fn new(symbols: &HashSet<String>) -> Arc<Self> {
-        let mut senders = HashMap::new();
-        let mut recent_ticks = HashMap::new();
-        for sym in symbols {
-            let (tx, _rx) = broadcast::channel::<(f64, u64, LocalPriceSource)>(4096);
-            senders.insert(sym.clone(), tx);
-            recent_ticks.insert(sym.clone(), VecDeque::new());
+/// Logs pool health and starts a Data Streams feeder for each symbol whose
+/// RTDS stream is stalled; the feeder publishes into the hub until RTDS
+/// ticks resume.
+async fn run_chainlink_hub_pool_supervisor(hub: Arc<ChainlinkHub>, pool: Arc<RtdsConnectionPool>) {
+    let failover_running: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+    let mut last_health_log = Instant::now();
+    loop {
+        sleep(Duration::from_secs(1)).await;
+        let now_ms = unix_now_millis_u64();
+        let snapshot = pool.health_snapshot(now_ms);
+        if last_health_log.elapsed() >= Duration::from_secs(30) {
+            last_health_log = Instant::now();
+            let connected = snapshot.iter().filter(|h| h.health.connected).count();
+            let stalled = snapshot
+                .iter()
+                .filter(|h| h.stalled)
+                .map(|h| format!("{}:{}ms", h.symbol, h.health.idle_ms(now_ms)))
+                .collect::<Vec<_>>();
+            let worst = snapshot
+                .iter()
+                .min_by(|a, b| a.score.total_cmp(&b.score))
+                .map(|h| format!("{}:{:.2}", h.symbol, h.score));
+            info!(
+                "📡 chainlink_hub_pool_health | connected={}/{} stalled={:?} worst={:?} backoff_ms={}",
+                connected,
+                snapshot.len(),
+                stalled,
+                worst,
+                pool.backoff_ms(),
+            );
         }
-        Arc::new(Self {
-            senders: Arc::new(senders),
-            recent_ticks: Arc::new(Mutex::new(recent_ticks)),
-        })
-    }
-
-    fn spawn(symbols: HashSet<String>) -> Arc<Self> {
-        let hub = Self::new(&symbols);
-        Self::spawn_binance_feeder(Arc::clone(&hub), symbols.clone());
-        Self::spawn_bybit_feeder(Arc::clone(&hub), symbols.clone());
-        Self::spawn_okx_feeder(Arc::clone(&hub), symbols.clone());
-        Self::spawn_hyperliquid_feeder(Arc::clone(&hub), symbols.clone());
-        Self::spawn_coinbase_feeder(Arc::clone(&hub), symbols);
-        hub
-    }
-
-    fn spawn_remote(symbols: HashSet<String>, socket_path: PathBuf) -> Arc<Self> {
-        let hub = Self::new(&symbols);
-        let target_symbols = symbols
-            .into_iter()
-            .map(|sym| normalize_chainlink_symbol(&sym))
-            .filter(|sym| !sym.is_empty())
-            .collect::<Vec<_>>();
-        let shared_hub = Arc::clone(&hub);
-        tokio::spawn(async move {
-            let mut reconnect_backoff = Duration::from_millis(300);
-            loop {
-                let _ = ensure_shared_ingress_broker_running().await;
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(2), UnixStream::connect(&socket_path))
-                        .await;
-                let Ok(Ok(stream)) = connect else {
-                    sleep(reconnect_backoff).await;
-                    reconnect_backoff = (reconnect_backoff * 2).min(Duration::from_secs(2));
-                    continue;
-                };
-                reconnect_backoff = Duration::from_millis(300);
-                let (read_half, mut write_half) = stream.into_split();
-                let req = SharedIngressSubscribeReq {
-                    stream: "local_price".to_string(),
-                    symbols: target_symbols.clone(),
-                    market_slug: None,
-                    market_id: None,
-                    yes_asset_id: None,
-                    no_asset_id: None,
-                    ws_base_url: None,
-                    custom_feature_enabled: None,
-                };
-                let mut line = match serde_json::to_vec(&req) {
-                    Ok(v) => v,
-                    Err(_) => break,
-                };
-                line.push(b'\n');
-                if write_half.write_all(&line).await.is_err() || write_half.flush().await.is_err() {
-                    continue;
-                }
-                let mut reader = BufReader::new(read_half);
-                let mut buf = String::new();
-                loop {
-                    buf.clear();
-                    let read = reader.read_line(&mut buf).await;
-                    let Ok(n) = read else {
-                        break;
-                    };
-                    if n == 0 {
-                        break;
-                    }
-                    let Ok(msg) = serde_json::from_str::<SharedIngressWireMsg>(buf.trim()) else {
-                        continue;
-                    };
-                    match msg {
-                        SharedIngressWireMsg::LocalPriceSnapshot {
-                            symbol,
-                            price,
-                            ts_ms,
-                            source,
-                        }
-                        | SharedIngressWireMsg::LocalPriceTick {
-                            symbol,
-                            price,
-                            ts_ms,
-                            source,
-                        } => {
-                            let Some(source) = LocalPriceSource::from_str(&source) else {
-                                continue;
-                            };
-                            shared_hub.publish_tick(&symbol, price, ts_ms, source);
-                        }
-                        SharedIngressWireMsg::SnapshotDone => {}
-                        SharedIngressWireMsg::Error { message } => {
-                            warn!("⚠️ local_price_remote_hub_error | msg={}", message);
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        });
-        hub
-    }
-
-    fn publish_tick(&self, symbol: &str, price: f64, ts_ms: u64, source: LocalPriceSource) {
-        let sym = normalize_chainlink_symbol(symbol);
-        if sym.is_empty() || !price.is_finite() || price <= 0.0 {
-            return;
+        if !pool.config().stall_failover {
+            continue;
         }
-        if let Ok(mut guard) = self.recent_ticks.lock() {
-            if let Some(buf) = guard.get_mut(&sym) {
-                buf.push_back((price, ts_ms, source));
-                while buf.len() > LOCAL_PRICE_HUB_RECENT_TICKS_PER_SYMBOL {
-                    buf.pop_front();
-                }
+        for health in snapshot.iter().filter(|h| h.stalled) {
+            if !data_streams_failover_available(&health.symbol) {
+                continue;
             }
+            let Ok(mut running) = failover_running.lock() else {
+                continue;
+            };
+            if !running.insert(health.symbol.clone()) {
+                continue;
+            }
+            drop(running);
+            let hub = Arc::clone(&hub);
+            let pool = Arc::clone(&pool);
+            let running = Arc::clone(&failover_running);
+            let symbol = health.symbol.clone();
+            tokio::spawn(async move {
+                run_chainlink_hub_data_streams_failover(hub, pool, &symbol).await;
+                if let Ok(mut guard) = running.lock() {
+                    guard.remove(&symbol);
+                }
+            });
         }
-        if let Some(tx) = self.senders.get(&sym) {
-            let _ = tx.send((price, ts_ms, source));
-        }
-    }
-
-    fn subscribe(&self, symbol: &str) -> Option<broadcast::Receiver<(f64, u64, LocalPriceSource)>> {
-        let sym = normalize_chainlink_symbol(symbol);
-        self.senders.get(&sym).map(|tx| tx.subscribe())
     }
+}
 
-    fn snapshot_recent_ticks(&self, symbol: &str) -> Vec<(f64, u64, LocalPriceSource)> {
-        let sym = normalize_chainlink_symbol(symbol);
-        if sym.is_empty() {
-            return Vec::new();
-        }
-        let Ok(guard) = self.recent_ticks.lock() else {
-            return Vec::new();
-        };
-        guard
-            .get(&sym)
-            .map(|buf| buf.iter().copied().collect::<Vec<_>>())
-            .unwrap_or_default()
+async fn run_chainlink_hub_data_streams_failover(
+    hub: Arc<ChainlinkHub>,
+    pool: Arc<RtdsConnectionPool>,
+    symbol: &str,
+) {
+    let Some(stream_symbol) = data_streams_symbol_from_chainlink_symbol(symbol) else {
+        return;
+    };
+    if data_streams_verifier().require_verified() {
+        run_chainlink_hub_signed_report_failover(hub, pool, symbol, &stream_symbol).await;
+        return;
     }
-
-    fn spawn_binance_feeder(hub: Arc<Self>, symbols: HashSet<String>) {
-        let streams: Vec<String> = symbols
-            .iter()
-            .filter_map(|sym| binance_stream_symbol_from_chainlink_symbol(sym))
-            .map(|sym| format!("{}@trade", sym))
-            .collect();
-        if streams.is_empty() {
+    let Some(access_token) = data_streams_authorize_token().await else {
+        warn!(
+            "⚠️ chainlink_hub_failover_authorize_failed | symbol={} source=data_streams",
+            symbol
+        );
+        sleep(Duration::from_secs(5)).await;
+        return;
+    };
+    let base = chainlink_data_streams_price_api_base_url();
+    let url = format!(
+        "{}/api/v1/streaming?symbol={}",
+        base.trim_end_matches('/'),
+        stream_symbol
+    );
+    let Ok(client) = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(DATA_STREAMS_CONNECT_TIMEOUT_MS))
+        .build()
+    else {
+        return;
+    };
+    let mut resp = match client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", access_token))
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp,
+        Ok(resp) => {
+            warn!(
+                "⚠️ chainlink_hub_failover_http_status | symbol={} source=data_streams status={}",
+                symbol,
+                resp.status()
+            );
+            sleep(Duration::from_secs(5)).await;
             return;
         }
-        tokio::spawn(async move {
-            let url = format!(
-                "wss://stream.binance.com:9443/stream?streams={}",
-                streams.join("/")
+        Err(err) => {
+            warn!(
+                "⚠️ chainlink_hub_failover_connect_failed | symbol={} source=data_streams err={}",
+                symbol, err
             );
-            let mut backoff = Duration::from_millis(300);
-            loop {
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(3), connect_async(&url)).await;
-                let Ok(Ok((ws, _))) = connect else {
-                    sleep(backoff).await;
-                    backoff = (backoff * 2).min(Duration::from_secs(3));
-                    continue;
-                };
-                backoff = Duration::from_millis(300);
-                let (_write, mut read) = ws.split();
-                while let Some(next) = read.next().await {
-                    let msg = match next {
-                        Ok(m) => m,
-                        Err(_) => break,
-                    };
-                    let text = match msg {
-                        Message::Text(t) => t.to_string(),
-                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
-                            Ok(t) => t,
-                            Err(_) => continue,
-                        },
-                        _ => continue,
-                    };
-                    if let Some((symbol, price, ts_ms)) = parse_binance_combined_trade_tick(&text) {
-                        hub.publish_tick(&symbol, price, ts_ms, LocalPriceSource::Binance);
-                    }
-                }
-            }
-        });
-    }
-
-    fn spawn_bybit_feeder(hub: Arc<Self>, symbols: HashSet<String>) {
-        let topics: Vec<String> = symbols
-            .iter()
-            .filter_map(|sym| bybit_stream_symbol_from_chainlink_symbol(sym))
-            .map(|sym| format!("publicTrade.{}", sym))
-            .collect();
-        if topics.is_empty() {
+            sleep(Duration::from_secs(5)).await;
             return;
         }
-        tokio::spawn(async move {
-            let url = "wss://stream.bybit.com/v5/public/spot";
-            let mut backoff = Duration::from_millis(300);
-            loop {
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(3), connect_async(url)).await;
-                let Ok(Ok((ws, _))) = connect else {
-                    sleep(backoff).await;
-                    backoff = (backoff * 2).min(Duration::from_secs(3));
-                    continue;
-                };
-                backoff = Duration::from_millis(300);
-                let (mut write, mut read) = ws.split();
-                let subscribe_msg = json!({
-                    "op": "subscribe",
-                    "args": topics,
-                });
-                if write
-                    .send(Message::Text(subscribe_msg.to_string().into()))
-                    .await
-                    .is_err()
-                {
-                    continue;
-                }
-                while let Some(next) = read.next().await {
-                    let msg = match next {
-                        Ok(m) => m,
-                        Err(_) => break,
-                    };
-                    let text = match msg {
-                        Message::Text(t) => t.to_string(),
-                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
-                            Ok(t) => t,
-                            Err(_) => continue,
-                        },
-                        _ => continue,
-                    };
-                    for (symbol, price, ts_ms) in parse_bybit_public_trade_ticks(&text) {
-                        hub.publish_tick(&symbol, price, ts_ms, LocalPriceSource::Bybit);
-                    }
-                }
+    };
+    warn!(
+        "🔀 chainlink_hub_failover_start | symbol={} source=data_streams",
+        symbol
+    );
+    let mut line_buf = String::new();
+    let mut delivered: u64 = 0;
+    let reason = loop {
+        if !pool.is_stalled(symbol, unix_now_millis_u64()) {
+            break "rtds_recovered";
+        }
+        let chunk = match tokio::time::timeout(Duration::from_millis(1_500), resp.chunk()).await {
+            Ok(Ok(Some(chunk))) => chunk,
+            Ok(Ok(None)) => break "stream_closed",
+            Ok(Err(_)) => break "read_error",
+            Err(_) => continue,
+        };
+        line_buf.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some(newline_idx) = line_buf.find('\n') {
+            let line = line_buf[..newline_idx].trim().to_string();
+            line_buf.drain(..=newline_idx);
+            if let Some((price, ts_s)) = parse_data_streams_tick(&line, &stream_symbol) {
+                hub.publish_tick(symbol, price, ts_s.saturating_mul(1_000));
+                delivered = delivered.saturating_add(1);
             }
-        });
-    }
+        }
+        if line_buf.len() > 16 * 1024 {
+            line_buf.clear();
+        }
+    };
+    info!(
+        "🔀 chainlink_hub_failover_end | symbol={} source=data_streams reason={} delivered={}",
+        symbol, reason, delivered
+    );
+}
 
-    fn spawn_okx_feeder(hub: Arc<Self>, symbols: HashSet<String>) {
-        let args: Vec<Value> = symbols
-            .iter()
-            .filter_map(|sym| okx_inst_id_from_chainlink_symbol(sym))
-            .map(|inst| json!({"channel":"trades","instId": inst}))
-            .collect();
-        if args.is_empty() {
-            return;
+/// Verified failover feeder: polls the latest signed report once a second
+/// and publishes each new benchmark price into the hub.
+async fn run_chainlink_hub_signed_report_failover(
+    hub: Arc<ChainlinkHub>,
+    pool: Arc<RtdsConnectionPool>,
+    symbol: &str,
+    stream_symbol: &str,
+) {
+    let (Some(auth), Some(feed_id)) = (
+        data_streams_hmac_auth(),
+        data_streams_verifier().feed_id(stream_symbol),
+    ) else {
+        return;
+    };
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(Duration::from_millis(DATA_STREAMS_CONNECT_TIMEOUT_MS))
+        .build()
+    else {
+        return;
+    };
+    let path = latest_report_path(&feed_id);
+    warn!(
+        "🔀 chainlink_hub_failover_start | symbol={} source=data_streams_signed",
+        symbol
+    );
+    let mut delivered: u64 = 0;
+    let mut rejected: u64 = 0;
+    let mut last_observation_ts: u64 = 0;
+    while pool.is_stalled(symbol, unix_now_millis_u64()) {
+        match fetch_verified_data_streams_report(&client, auth, &path, stream_symbol).await {
+            Some(Ok(report)) if report.observations_ts > last_observation_ts => {
+                last_observation_ts = report.observations_ts;
+                hub.publish_tick(
+                    symbol,
+                    report.benchmark_price,
+                    report.observations_ts.saturating_mul(1_000),
+                );
+                delivered = delivered.saturating_add(1);
+            }
+            Some(Err(_)) => rejected = rejected.saturating_add(1),
+            _ => {}
         }
-        tokio::spawn(async move {
-            let url = "wss://ws.okx.com:8443/ws/v5/public";
-            let mut backoff = Duration::from_millis(300);
-            loop {
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(3), connect_async(url)).await;
-                let Ok(Ok((ws, _))) = connect else {
-                    sleep(backoff).await;
-                    backoff = (backoff * 2).min(Duration::from_secs(3));
-                    continue;
-                };
-                backoff = Duration::from_millis(300);
-                let (mut write, mut read) = ws.split();
-                let subscribe_msg = json!({
-                    "op": "subscribe",
-                    "args": args,
-                });
-                if write
-                    .send(Message::Text(subscribe_msg.to_string().into()))
-                    .await
-                    .is_err()
-                {
-                    continue;
-                }
-                while let Some(next) = read.next().await {
-                    let msg = match next {
-                        Ok(m) => m,
-                        Err(_) => break,
-                    };
-                    let text = match msg {
-                        Message::Text(t) => t.to_string(),
-                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
-                            Ok(t) => t,
-                            Err(_) => continue,
-                        },
-                        _ => continue,
-                    };
-                    for (symbol, price, ts_ms) in parse_okx_trade_ticks(&text) {
-                        hub.publish_tick(&symbol, price, ts_ms, LocalPriceSource::Okx);
-                    }
+        sleep(Duration::from_secs(1)).await;
+    }
+    info!(
+        "🔀 chainlink_hub_failover_end | symbol={} source=data_streams_signed reason=rtds_recovered delivered={} rejected={}",
+        symbol, delivered, rejected
+    );
+}
+
+/// Winner hint from the Polymarket frontend round-price API (Chainlink
+/// open/close as published by Polymarket). Only used while the symbol's RTDS
+/// stream is stalled and no Data Streams failover is available for it.
+async fn run_frontend_winner_hint_failover(
+    symbol: &str,
+    round_start_ts: u64,
+    round_end_ts: u64,
+    hard_deadline_ts: u64,
+) -> Option<(Side, f64, f64, u64, u64, bool)> {
+    let start_ms = round_start_ts.saturating_mul(1_000);
+    let end_ms = round_end_ts.saturating_mul(1_000);
+    while unix_now_secs() <= hard_deadline_ts {
+        if unix_now_secs() >= round_end_ts {
+            if let Some(hit) =
+                fetch_frontend_crypto_round_prices(symbol, round_start_ts, round_end_ts).await
+            {
+                if let (Some(open), Some(close), Some(true), false) = (
+                    hit.open_price,
+                    hit.close_price,
+                    hit.completed,
+                    hit.incomplete.unwrap_or(false),
+                ) {
+                    let side = if close >= open { Side::Yes } else { Side::No };
+                    info!(
+                        "🏁 Chainlink winner hint ready | unix_ms={} symbol={} side={:?} open_ref={:.6}@{} close={:.6}@{} source=frontend_api_failover",
+                        unix_now_millis_u64(),
+                        symbol,
+                        side,
+                        open,
+                        start_ms,
+                        close,
+                        end_ms,
+                    );
+                    return Some((side, open, close, start_ms, end_ms, true));
                 }
             }
-        });
-    }
-
-    fn spawn_coinbase_feeder(hub: Arc<Self>, symbols: HashSet<String>) {
-        let product_ids: Vec<String> = symbols
-            .iter()
-            .filter_map(|sym| coinbase_product_id_from_chainlink_symbol(sym))
-            .collect();
-        if product_ids.is_empty() {
-            return;
-        }
-        tokio::spawn(async move {
-            let url = "wss://ws-feed.exchange.coinbase.com";
-            let mut backoff = Duration::from_millis(300);
-            loop {
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(3), connect_async(url)).await;
-                let Ok(Ok((ws, _))) = connect else {
-                    sleep(backoff).await;
-                    backoff = (backoff * 2).min(Duration::from_secs(3));
-                    continue;
-                };
-                backoff = Duration::from_millis(300);
-                let (mut write, mut read) = ws.split();
-                let subscribe_msg = json!({
-                    "type": "subscribe",
-                    "product_ids": product_ids,
-                    "channels": ["ticker"],
-                });
-                if write
-                    .send(Message::Text(subscribe_msg.to_string().into()))
-                    .await
-                    .is_err()
-                {
-                    continue;
-                }
-                while let Some(next) = read.next().await {
-                    let msg = match next {
-                        Ok(m) => m,
-                        Err(_) => break,
-                    };
-                    let text = match msg {
-                        Message::Text(t) => t.to_string(),
-                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
-                            Ok(t) => t,
-                            Err(_) => continue,
-                        },
-                        _ => continue,
-                    };
-                    if let Some((symbol, price, ts_ms)) = parse_coinbase_ticker_tick(&text) {
-                        hub.publish_tick(&symbol, price, ts_ms, LocalPriceSource::Coinbase);
-                    }
-                }
-            }
-        });
-    }
-
-    fn spawn_hyperliquid_feeder(hub: Arc<Self>, symbols: HashSet<String>) {
-        let wanted: HashMap<String, String> = symbols
-            .iter()
-            .filter_map(|sym| {
-                hyperliquid_coin_from_chainlink_symbol(sym)
-                    .map(|coin| (coin, normalize_chainlink_symbol(sym)))
-            })
-            .collect();
-        if wanted.is_empty() {
-            return;
-        }
-        tokio::spawn(async move {
-            let url = "wss://api.hyperliquid.xyz/ws";
-            let mut backoff = Duration::from_millis(300);
-            loop {
-                let connect =
-                    tokio::time::timeout(Duration::from_secs(3), connect_async(url)).await;
-                let Ok(Ok((ws, _))) = connect else {
-                    sleep(backoff).await;
-                    backoff = (backoff * 2).min(Duration::from_secs(3));
-                    continue;
-                };
-                backoff = Duration::from_millis(300);
-                let (mut write, mut read) = ws.split();
-                let subscribe_msg = json!({
-                    "method": "subscribe",
-                    "subscription": { "type": "allMids" },
-                });
-                if write
-                    .send(Message::Text(subscribe_msg.to_string().into()))
-                    .await
-                    .is_err()
-                {
-                    continue;
-                }
-                while let Some(next) = read.next().await {
-                    let msg = match next {
-                        Ok(m) => m,
-                        Err(_) => break,
-                    };
-                    let text = match msg {
-                        Message::Text(t) => t.to_string(),
-                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
-                            Ok(t) => t,
-                            Err(_) => continue,
-                        },
-                        _ => continue,
-                    };
-                    for (symbol, price, ts_ms) in parse_hyperliquid_all_mids_ticks(&text, &wanted) {
-                        hub.publish_tick(&symbol, price, ts_ms, LocalPriceSource::Hyperliquid);
-                    }
-                }
-            }
-        });
+        }
+        sleep(Duration::from_millis(500)).await;
     }
+    None
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -5690,16 +5526,6 @@ fn side_label(side: Option<Side>) -> Option<String> {
     side.map(|s| format!("{:?}", s))
 }
 
-type LocalPriceAggBiasMap = HashMap<(String, LocalPriceSource), f64>;
-static LOCAL_PRICE_AGG_SOURCE_BIAS_BPS: OnceLock<Mutex<LocalPriceAggBiasMap>> = OnceLock::new();
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct LocalPriceAggBiasCacheEntry {
-    symbol: String,
-    source: String,
-    bias_bps: f64,
-}
-
 fn local_price_agg_bias_cache_path() -> PathBuf {
     if let Ok(path) = env::var("PM_LOCAL_PRICE_AGG_BIAS_CACHE_PATH") {
         let trimmed = path.trim();
@@ -5710,127 +5536,17 @@ fn local_price_agg_bias_cache_path() -> PathBuf {
     log_path("local_price_agg_bias_cache.json")
 }
 
-fn load_local_price_agg_bias_cache() -> LocalPriceAggBiasMap {
-    let path = local_price_agg_bias_cache_path();
-    let Ok(raw) = fs::read_to_string(&path) else {
-        return HashMap::new();
-    };
-    let Ok(entries) = serde_json::from_str::<Vec<LocalPriceAggBiasCacheEntry>>(&raw) else {
-        warn!(
-            "⚠️ Failed to parse local price agg bias cache | path={}",
-            path.display()
-        );
-        return HashMap::new();
-    };
-    let mut out = HashMap::new();
-    for entry in entries {
-        let symbol = normalize_chainlink_symbol(&entry.symbol);
-        let Some(source) = LocalPriceSource::from_str(&entry.source) else {
-            continue;
-        };
-        if symbol.is_empty() || !entry.bias_bps.is_finite() {
-            continue;
-        }
-        out.insert((symbol, source), entry.bias_bps);
-    }
-    if !out.is_empty() {
-        info!(
-            "🧠 Loaded local price agg bias cache | path={} entries={}",
-            path.display(),
-            out.len()
-        );
-    }
-    out
-}
-
-fn persist_local_price_agg_bias_cache(map: &LocalPriceAggBiasMap) {
-    let path = local_price_agg_bias_cache_path();
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    let entries = map
-        .iter()
-        .map(|((symbol, source), bias_bps)| LocalPriceAggBiasCacheEntry {
-            symbol: symbol.clone(),
-            source: source.as_str().to_string(),
-            bias_bps: *bias_bps,
-        })
-        .collect::<Vec<_>>();
-    let Ok(payload) = serde_json::to_vec(&entries) else {
-        return;
-    };
-    let tmp = path.with_extension("json.tmp");
-    if fs::write(&tmp, payload).is_err() {
-        return;
-    }
-    if fs::rename(&tmp, &path).is_err() {
-        let _ = fs::remove_file(&tmp);
-    }
-}
-
-fn local_price_agg_source_bias_map() -> &'static Mutex<LocalPriceAggBiasMap> {
-    LOCAL_PRICE_AGG_SOURCE_BIAS_BPS.get_or_init(|| Mutex::new(load_local_price_agg_bias_cache()))
-}
-
-fn local_price_agg_get_source_bias_bps(symbol: &str, source: LocalPriceSource) -> f64 {
-    let normalized = normalize_chainlink_symbol(symbol);
-    if normalized.is_empty() {
-        return 0.0;
-    }
-    let Ok(guard) = local_price_agg_source_bias_map().lock() else {
-        return 0.0;
-    };
-    guard.get(&(normalized, source)).copied().unwrap_or(0.0)
-}
+static LOCAL_PRICE_AGG_BIAS_BOOK: OnceLock<SourceBiasBook> = OnceLock::new();
 
-fn local_price_agg_apply_source_bias(symbol: &str, source: LocalPriceSource, raw: f64) -> f64 {
-    if !raw.is_finite() || raw <= 0.0 {
-        return raw;
-    }
-    let bias_bps = local_price_agg_get_source_bias_bps(symbol, source);
-    let adjusted = raw * (1.0 + bias_bps / 10_000.0);
-    if adjusted.is_finite() && adjusted > 0.0 {
-        adjusted
-    } else {
-        raw
-    }
+fn local_price_agg_bias_book() -> &'static SourceBiasBook {
+    LOCAL_PRICE_AGG_BIAS_BOOK
+        .get_or_init(|| SourceBiasBook::load(local_price_agg_bias_cache_path()))
 }
 
 fn local_price_agg_bias_learning_enabled() -> bool {
     env_flag_or("PM_LOCAL_PRICE_AGG_BIAS_LEARNING_ENABLED", true)
 }
 
-fn local_price_agg_update_source_bias_bps(
-    symbol: &str,
-    source: LocalPriceSource,
-    residual_bps: f64,
-) -> Option<f64> {
-    let normalized = normalize_chainlink_symbol(symbol);
-    if normalized.is_empty() || !residual_bps.is_finite() {
-        return None;
-    }
-    let clamped_residual = residual_bps.clamp(
-        -LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS,
-        LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS,
-    );
-    let Ok(mut guard) = local_price_agg_source_bias_map().lock() else {
-        return None;
-    };
-    let key = (normalized, source);
-    let prev = guard.get(&key).copied().unwrap_or(0.0);
-    let next = (prev * (1.0 - LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA)
-        + clamped_residual * LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA)
-        .clamp(
-            -LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS,
-            LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS,
-        );
-    guard.insert(key, next);
-    let snapshot = guard.clone();
-    drop(guard);
-    persist_local_price_agg_bias_cache(&snapshot);
-    Some(next)
-}
-
 type LastCloseMap = HashMap<String, (u64, f64)>;
 static CHAINLINK_LAST_CLOSE: OnceLock<Mutex<LastCloseMap>> = OnceLock::new();
 
@@ -6426,10 +6142,15 @@ async fn run_local_price_open_prewarm(
         return;
     }
     let start_ms = round_start_ts.saturating_mul(1_000);
-    let open_tol_ms = local_price_agg_open_tolerance_ms();
+    let open_tol_ms = price_agg_config().open_tolerance_ms;
     let mut states: HashMap<LocalPriceSource, LocalSourceBoundaryState> = HashMap::new();
 
-    for (price, ts_ms, source) in hub.snapshot_recent_ticks(&target_symbol) {
+    for SourceTick {
+        price,
+        ts_ms,
+        source,
+    } in hub.snapshot_recent_ticks(&target_symbol)
+    {
         local_price_agg_ingest_state(&mut states, source, price, ts_ms, start_ms, start_ms);
     }
 
@@ -6437,7 +6158,11 @@ async fn run_local_price_open_prewarm(
         while unix_now_secs() <= hard_deadline_ts {
             let next = tokio::time::timeout(Duration::from_millis(700), rx.recv()).await;
             match next {
-                Ok(Ok((price, ts_ms, source))) => {
+                Ok(Ok(SourceTick {
+                    price,
+                    ts_ms,
+                    source,
+                })) => {
                     local_price_agg_ingest_state(
                         &mut states,
                         source,
@@ -7662,6 +7387,7 @@ async fn run_post_close_winner_hint_listener(
                         hit,
                         round_end_ts,
                         first_ref,
+                        price_agg_config(),
                     );
                 }
                 for outcome in &mut compare_hit.boundary_shadow_outcomes {
@@ -7753,9 +7479,9 @@ async fn run_post_close_winner_hint_listener(
                                         + 1e-9
                                 && direction_margin_bps + 1e-9
                                     >= LOCAL_PRICE_AGG_COMPARE_SOL_SAFE_PRECLOSE_RELIEF_MIN_DIRECTION_MARGIN_BPS_TWO_SOURCE));
-                    let single_source_min_direction_margin_bps =
-                        local_price_agg_single_source_min_direction_margin_bps()
-                            .max(LOCAL_PRICE_AGG_RELIEF_MIN_DIRECTION_MARGIN_BPS_DEFAULT);
+                    let single_source_min_direction_margin_bps = price_agg_config()
+                        .single_source_min_direction_margin_bps
+                        .max(LOCAL_PRICE_AGG_RELIEF_MIN_DIRECTION_MARGIN_BPS_DEFAULT);
                     let safe_single_source_relief_applied = hit.source_count == 1
                         && hit.close_exact_sources == 0
                         && direction_margin_bps + 1e-9
@@ -8115,7 +7841,7 @@ async fn run_post_close_winner_hint_listener(
                         );
                         close_only_filtered = true;
                     }
-                    let min_confidence = local_price_agg_min_confidence();
+                    let min_confidence = price_agg_config().min_confidence;
                     let relief_min_confidence = LOCAL_PRICE_AGG_RELIEF_MIN_CONFIDENCE_DEFAULT;
                     let relief_min_direction_margin_bps =
                         LOCAL_PRICE_AGG_RELIEF_MIN_DIRECTION_MARGIN_BPS_DEFAULT;
@@ -8141,28 +7867,13 @@ async fn run_post_close_winner_hint_listener(
                                 + 1e-9
                         && direction_margin_bps + 1e-9
                             >= LOCAL_PRICE_AGG_COMPARE_SOL_SAFE_LOW_CONF_MIN_DIRECTION_MARGIN_BPS;
-                    let confidence = if hit.source_count == 1 {
-                        if hit.close_exact_sources > 0 {
-                            0.93
-                        } else {
-                            0.87
-                        }
-                    } else {
-                        let source_factor = (hit.source_count.min(3) as f64) / 3.0;
-                        let spread_factor = if local_price_agg_max_source_spread_bps() > 0.0 {
-                            (1.0 - (hit.source_spread_bps
-                                / local_price_agg_max_source_spread_bps()))
-                            .clamp(0.0, 1.0)
-                        } else {
-                            1.0
-                        };
-                        let exact_factor = (hit.close_exact_sources.min(2) as f64) / 2.0;
-                        (0.50 * source_agreement
-                            + 0.20 * source_factor
-                            + 0.20 * spread_factor
-                            + 0.10 * exact_factor)
-                            .clamp(0.0, 1.0)
-                    };
+                    let confidence = source_confidence(
+                        hit.source_count,
+                        source_agreement,
+                        hit.source_spread_bps,
+                        price_agg_config().max_source_spread_bps,
+                        hit.close_exact_sources,
+                    );
                     let confidence_relief_applied = hit.source_count >= 2
                         && (source_agreement >= 1.0 - 1e-9)
                         && hit.source_spread_bps <= relief_max_source_spread_bps + 1e-9
@@ -8255,8 +7966,8 @@ async fn run_post_close_winner_hint_listener(
                             hit.source_count,
                             hit.source_spread_bps,
                             hit.close_exact_sources,
-                            local_price_agg_close_time_decay_ms(),
-                            local_price_agg_exact_boost(),
+                            price_agg_config().close_time_decay_ms,
+                            price_agg_config().exact_boost,
                             started_ms,
                             ready_ms,
                             deadline_ms,
@@ -9468,6 +9179,27 @@ async fn run_data_streams_winner_hint(
         );
         return None;
     };
+    let verifier = data_streams_verifier();
+    if verifier.require_verified() {
+        if data_streams_signed_reports_available(symbol) {
+            return run_data_streams_signed_winner_hint(
+                symbol,
+                &stream_symbol,
+                round_start_ts,
+                round_end_ts,
+                hard_deadline_ts,
+            )
+            .await;
+        }
+        warn!(
+            "⚠️ data_streams_verifier_unconfigured | symbol={} signers={} feed_id={} hmac_auth={} — skip unverified Data Streams prices",
+            stream_symbol,
+            verifier.signer_count(),
+            verifier.feed_id(&stream_symbol).is_some(),
+            data_streams_hmac_auth().is_some(),
+        );
+        return None;
+    }
     let chainlink_symbol = normalize_chainlink_symbol(symbol);
     let start_ms = round_start_ts.saturating_mul(1_000);
     let end_ms = round_end_ts.saturating_mul(1_000);
@@ -9697,82 +9429,79 @@ async fn run_data_streams_winner_hint(
     None
 }
 
-#[derive(Debug, Clone, Copy)]
-struct AggregatedPricePoint {
-    price: f64,
-    ts_ms: u64,
-    exact: bool,
-    abs_delta_ms: u64,
-    source: &'static str,
-}
-
-impl AggregatedPricePoint {
-    fn from_exact(price: f64, ts_ms: u64, source: &'static str) -> Self {
-        Self {
-            price,
-            ts_ms,
-            exact: true,
-            abs_delta_ms: 0,
-            source,
-        }
-    }
-}
-
-fn self_built_agg_ingest_tick(
-    price: f64,
-    ts_ms: u64,
-    start_ms: u64,
-    end_ms: u64,
-    first_tick_ts_ms: &mut Option<u64>,
-    open_exact: &mut Option<AggregatedPricePoint>,
-    close_exact: &mut Option<AggregatedPricePoint>,
-    nearest_start: &mut Option<(u64, u64, f64)>,
-    nearest_end: &mut Option<(u64, u64, f64)>,
-    first_after_start: &mut Option<(u64, u64, f64)>,
-    first_after_end: &mut Option<(u64, u64, f64)>,
-) {
-    first_tick_ts_ms.get_or_insert(ts_ms);
-
-    if open_exact.is_none() && ts_ms == start_ms {
-        *open_exact = Some(AggregatedPricePoint::from_exact(
-            price,
-            ts_ms,
-            "hub_open_exact",
-        ));
-    }
-    if close_exact.is_none() && ts_ms == end_ms {
-        *close_exact = Some(AggregatedPricePoint::from_exact(
-            price,
-            ts_ms,
-            "hub_close_exact",
-        ));
-    }
-
-    let start_delta = ts_ms.abs_diff(start_ms);
-    match *nearest_start {
-        Some((best_delta, _, _)) if start_delta >= best_delta => {}
-        _ => *nearest_start = Some((start_delta, ts_ms, price)),
-    }
-    if ts_ms >= start_ms {
-        let after_delta = ts_ms.saturating_sub(start_ms);
-        match *first_after_start {
-            Some((best_delta, _, _)) if after_delta >= best_delta => {}
-            _ => *first_after_start = Some((after_delta, ts_ms, price)),
+/// Winner hint from DON-signed reports at the exact round boundaries. The
+/// report API serves the report observed at a given second, so open and close
+/// are each one verified report rather than a scan over a stream.
+async fn run_data_streams_signed_winner_hint(
+    symbol: &str,
+    stream_symbol: &str,
+    round_start_ts: u64,
+    round_end_ts: u64,
+    hard_deadline_ts: u64,
+) -> Option<(Side, f64, f64, u64, u64, bool)> {
+    let chainlink_symbol = normalize_chainlink_symbol(symbol);
+    let auth = data_streams_hmac_auth()?;
+    let feed_id = data_streams_verifier().feed_id(stream_symbol)?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(DATA_STREAMS_CONNECT_TIMEOUT_MS))
+        .build()
+        .ok()?;
+    let mut open_point: Option<(f64, u64)> = None;
+    let mut close_point: Option<(f64, u64)> = None;
+    let mut rejected_reports: u64 = 0;
+    while unix_now_secs() <= hard_deadline_ts {
+        for (ts_s, point) in [
+            (round_start_ts, &mut open_point),
+            (round_end_ts, &mut close_point),
+        ] {
+            if point.is_some() || unix_now_secs() < ts_s {
+                continue;
+            }
+            let path = report_at_path(&feed_id, ts_s);
+            match fetch_verified_data_streams_report(&client, auth, &path, stream_symbol).await {
+                Some(Ok(report)) if report.observations_ts == ts_s => {
+                    *point = Some((report.benchmark_price, ts_s.saturating_mul(1_000)));
+                }
+                Some(Err(reject)) => {
+                    rejected_reports = rejected_reports.saturating_add(1);
+                    if rejected_reports <= 3 {
+                        warn!(
+                            "🛑 data_streams_report_rejected | symbol={} report_ts={} rejected={} reason={:?}",
+                            stream_symbol, ts_s, rejected_reports, reject
+                        );
+                    }
+                }
+                _ => {}
+            }
         }
-    }
-
-    let end_delta = ts_ms.abs_diff(end_ms);
-    match *nearest_end {
-        Some((best_delta, _, _)) if end_delta >= best_delta => {}
-        _ => *nearest_end = Some((end_delta, ts_ms, price)),
-    }
-    if ts_ms >= end_ms {
-        let after_delta = ts_ms.saturating_sub(end_ms);
-        match *first_after_end {
-            Some((best_delta, _, _)) if after_delta >= best_delta => {}
-            _ => *first_after_end = Some((after_delta, ts_ms, price)),
+        if let (Some((open, open_ts_ms)), Some((close, close_ts_ms))) = (open_point, close_point) {
+            let side = if close >= open { Side::Yes } else { Side::No };
+            info!(
+                "🏁 Chainlink winner hint ready | unix_ms={} symbol={} side={:?} open_ref={:.6}@{} close={:.6}@{} source=data_streams_signed",
+                unix_now_millis_u64(),
+                chainlink_symbol,
+                side,
+                open,
+                open_ts_ms,
+                close,
+                close_ts_ms,
+            );
+            set_last_chainlink_close(&chainlink_symbol, close_ts_ms, close);
+            return Some((side, open, close, open_ts_ms, close_ts_ms, true));
         }
+        sleep(Duration::from_millis(250)).await;
     }
+    warn!(
+        "⚠️ data_streams_signed_winner_hint_unresolved | symbol={} round_start_ts={} round_end_ts={} deadline_ts={} has_open={} has_close={} rejected={}",
+        chainlink_symbol,
+        round_start_ts,
+        round_end_ts,
+        hard_deadline_ts,
+        open_point.is_some(),
+        close_point.is_some(),
+        rejected_reports,
+    );
+    None
 }
 
 /// Self-built round price aggregator:
@@ -9863,7 +9592,7 @@ async fn run_self_built_price_aggregator(
     // Phase-1: consume hub in-memory recent ticks first (zero network wait).
     for (price, ts_ms) in hub.snapshot_recent_ticks(&target_symbol) {
         observed_ticks = observed_ticks.saturating_add(1);
-        self_built_agg_ingest_tick(
+        ingest_boundary_tick(
             price,
             ts_ms,
             start_ms,
@@ -9949,7 +9678,7 @@ async fn run_self_built_price_aggregator(
                 Err(_) => continue,
             };
             observed_ticks = observed_ticks.saturating_add(1);
-            self_built_agg_ingest_tick(
+            ingest_boundary_tick(
                 price,
                 ts_ms,
                 start_ms,
@@ -10089,4148 +9818,158 @@ async fn run_self_built_price_aggregator(
                 None,
                 None,
                 open_point,
-                close_point,
-                observed_ticks,
-                observed_snapshot_ticks,
-                observed_live_ticks,
-                lagged_ticks,
-                nearest_start,
-                nearest_end,
-                first_after_start,
-                first_after_end,
-                Some(open_pick_rule),
-                Some(close_pick_rule),
-            );
-            return None;
-        }
-    };
-
-    let mut confidence: f64 = match (open_hit.exact, close_hit.exact) {
-        (true, true) => 1.0,
-        (true, false) => 0.9,
-        (false, true) => 0.86,
-        (false, false) => 0.76,
-    };
-    if !open_hit.exact && open_hit.abs_delta_ms > open_tol_ms / 2 {
-        confidence -= 0.05;
-    }
-    if !close_hit.exact && close_hit.abs_delta_ms > close_tol_ms / 2 {
-        confidence -= 0.05;
-    }
-    if open_hit.source == "prev_round_close" {
-        confidence -= 0.08;
-    }
-    confidence = confidence.clamp(0.0, 1.0);
-
-    if confidence + 1e-9 < min_confidence {
-        warn!(
-            "⚠️ self_built_price_agg_low_confidence | symbol={} round_start_ts={} round_end_ts={} confidence={:.3} min_confidence={:.3} open_source={} close_source={} open_delta_ms={} close_delta_ms={}",
-            target_symbol,
-            round_start_ts,
-            round_end_ts,
-            confidence,
-            min_confidence,
-            open_hit.source,
-            close_hit.source,
-            open_hit.abs_delta_ms,
-            close_hit.abs_delta_ms,
-        );
-        emit_probe(
-            "low_confidence",
-            None,
-            Some(confidence),
-            Some(open_hit),
-            Some(close_hit),
-            observed_ticks,
-            observed_snapshot_ticks,
-            observed_live_ticks,
-            lagged_ticks,
-            nearest_start,
-            nearest_end,
-            first_after_start,
-            first_after_end,
-            Some(open_pick_rule),
-            Some(close_pick_rule),
-        );
-        return None;
-    }
-
-    let side = if close_hit.price >= open_hit.price {
-        Side::Yes
-    } else {
-        Side::No
-    };
-    set_last_chainlink_close(&target_symbol, close_hit.ts_ms, close_hit.price);
-    info!(
-        "🧠 self_built_price_agg_ready | unix_ms={} symbol={} side={:?} confidence={:.3} open={:.6}@{} open_source={} open_exact={} open_delta_ms={} close={:.6}@{} close_source={} close_exact={} close_delta_ms={} observed_ticks={} observed_snapshot_ticks={} observed_live_ticks={} lagged_ticks={}",
-        unix_now_millis_u64(),
-        target_symbol,
-        side,
-        confidence,
-        open_hit.price,
-        open_hit.ts_ms,
-        open_hit.source,
-        open_hit.exact,
-        open_hit.abs_delta_ms,
-        close_hit.price,
-        close_hit.ts_ms,
-        close_hit.source,
-        close_hit.exact,
-        close_hit.abs_delta_ms,
-        observed_ticks,
-        observed_snapshot_ticks,
-        observed_live_ticks,
-        lagged_ticks,
-    );
-    emit_probe(
-        "ready",
-        Some(side),
-        Some(confidence),
-        Some(open_hit),
-        Some(close_hit),
-        observed_ticks,
-        observed_snapshot_ticks,
-        observed_live_ticks,
-        lagged_ticks,
-        nearest_start,
-        nearest_end,
-        first_after_start,
-        first_after_end,
-        Some(open_pick_rule),
-        Some(close_pick_rule),
-    );
-
-    Some((
-        side,
-        open_hit.price,
-        close_hit.price,
-        open_hit.ts_ms,
-        close_hit.ts_ms,
-        open_hit.exact,
-    ))
-}
-
-#[derive(Debug, Default, Clone, Copy)]
-struct LocalSourceBoundaryState {
-    first_tick_ts_ms: Option<u64>,
-    open_exact: Option<AggregatedPricePoint>,
-    close_exact: Option<AggregatedPricePoint>,
-    nearest_start: Option<(u64, u64, f64)>,
-    nearest_end: Option<(u64, u64, f64)>,
-    first_after_start: Option<(u64, u64, f64)>,
-    first_after_end: Option<(u64, u64, f64)>,
-}
-
-#[derive(Debug, Default, Clone)]
-struct LocalSourceBoundaryTapeState {
-    open_window_ticks: Vec<(u64, f64)>,
-    close_window_ticks: Vec<(u64, f64)>,
-}
-
-const LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE: usize = 512;
-
-fn format_local_source_boundary_state(
-    source: LocalPriceSource,
-    state: &LocalSourceBoundaryState,
-) -> String {
-    let first_tick = state
-        .first_tick_ts_ms
-        .map(|v| v.to_string())
-        .unwrap_or_else(|| "-".to_string());
-    let open_exact = state
-        .open_exact
-        .map(|p| format!("{}@{}", p.price, p.ts_ms))
-        .unwrap_or_else(|| "-".to_string());
-    let close_exact = state
-        .close_exact
-        .map(|p| format!("{}@{}", p.price, p.ts_ms))
-        .unwrap_or_else(|| "-".to_string());
-    let nearest_start = state
-        .nearest_start
-        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
-        .unwrap_or_else(|| "-".to_string());
-    let first_after_start = state
-        .first_after_start
-        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
-        .unwrap_or_else(|| "-".to_string());
-    let nearest_end = state
-        .nearest_end
-        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
-        .unwrap_or_else(|| "-".to_string());
-    let first_after_end = state
-        .first_after_end
-        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
-        .unwrap_or_else(|| "-".to_string());
-    format!(
-        "{}:first_tick={} open_exact={} first_after_start={} nearest_start={} close_exact={} first_after_end={} nearest_end={}",
-        source.as_str(),
-        first_tick,
-        open_exact,
-        first_after_start,
-        nearest_start,
-        close_exact,
-        first_after_end,
-        nearest_end,
-    )
-}
-
-fn local_price_agg_collect_boundary_tape_tick(
-    tapes: &mut HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
-    source: LocalPriceSource,
-    price: f64,
-    ts_ms: u64,
-    start_ms: u64,
-    end_ms: u64,
-    boundary_window_ms: u64,
-) {
-    let tape = tapes.entry(source).or_default();
-    let open_low = start_ms.saturating_sub(boundary_window_ms);
-    let open_high = start_ms.saturating_add(boundary_window_ms);
-    if ts_ms >= open_low && ts_ms <= open_high {
-        if tape.open_window_ticks.len() < LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE {
-            tape.open_window_ticks.push((ts_ms, price));
-        }
-    }
-    let close_low = end_ms.saturating_sub(boundary_window_ms);
-    let close_high = end_ms.saturating_add(boundary_window_ms);
-    if ts_ms >= close_low && ts_ms <= close_high {
-        if tape.close_window_ticks.len() < LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE {
-            tape.close_window_ticks.push((ts_ms, price));
-        }
-    }
-}
-
-fn build_local_price_agg_boundary_source_probes(
-    tapes: &HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
-    start_ms: u64,
-    end_ms: u64,
-) -> Vec<LocalPriceAggBoundarySourceProbe> {
-    let mut items = tapes
-        .iter()
-        .map(|(source, tape)| LocalPriceAggBoundarySourceProbe {
-            source: source.as_str().to_string(),
-            open_window_ticks: tape
-                .open_window_ticks
-                .iter()
-                .map(|(ts_ms, price)| LocalBoundaryTickProbe {
-                    ts_ms: *ts_ms,
-                    offset_ms: (*ts_ms as i64) - (start_ms as i64),
-                    price: *price,
-                })
-                .collect(),
-            close_window_ticks: tape
-                .close_window_ticks
-                .iter()
-                .map(|(ts_ms, price)| LocalBoundaryTickProbe {
-                    ts_ms: *ts_ms,
-                    offset_ms: (*ts_ms as i64) - (end_ms as i64),
-                    price: *price,
-                })
-                .collect(),
-        })
-        .collect::<Vec<_>>();
-    items.sort_by(|a, b| a.source.cmp(&b.source));
-    items
-}
-
-fn pick_local_source_points(
-    state: &LocalSourceBoundaryState,
-    open_tol_ms: u64,
-    close_tol_ms: u64,
-) -> Option<(AggregatedPricePoint, AggregatedPricePoint)> {
-    let open = if let Some(p) = state.open_exact {
-        Some(p)
-    } else if let Some((delta, ts_ms, px)) = state.first_after_start {
-        if delta <= open_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_first_after_open",
-            })
-        } else {
-            None
-        }
-    } else if let Some((delta, ts_ms, px)) = state.nearest_start {
-        if delta <= open_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_nearest_open",
-            })
-        } else {
-            None
-        }
-    } else {
-        None
-    }?;
-
-    let close = if let Some(p) = state.close_exact {
-        Some(p)
-    } else if let Some((delta, ts_ms, px)) = state.first_after_end {
-        if delta <= close_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_first_after_close",
-            })
-        } else {
-            None
-        }
-    } else if let Some((delta, ts_ms, px)) = state.nearest_end {
-        if delta <= close_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_nearest_close",
-            })
-        } else {
-            None
-        }
-    } else {
-        None
-    }?;
-
-    Some((open, close))
-}
-
-fn pick_local_source_close_point(
-    state: &LocalSourceBoundaryState,
-    close_tol_ms: u64,
-) -> Option<AggregatedPricePoint> {
-    if let Some(p) = state.close_exact {
-        Some(p)
-    } else if let Some((delta, ts_ms, px)) = state.first_after_end {
-        if delta <= close_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_first_after_close",
-            })
-        } else {
-            None
-        }
-    } else if let Some((delta, ts_ms, px)) = state.nearest_end {
-        if delta <= close_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_nearest_close",
-            })
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
-fn pick_local_boundary_tape_close_point(
-    tape: &LocalSourceBoundaryTapeState,
-    end_ms: u64,
-    pre_ms: u64,
-    post_ms: u64,
-    rule: LocalBoundaryCloseRule,
-) -> Option<(AggregatedPricePoint, f64)> {
-    let ticks = tape
-        .close_window_ticks
-        .iter()
-        .filter_map(|(ts_ms, price)| {
-            let offset_ms = (*ts_ms as i64) - (end_ms as i64);
-            let in_range = offset_ms >= -(pre_ms as i64) && offset_ms <= post_ms as i64;
-            if in_range {
-                Some((*ts_ms, *price, offset_ms))
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<_>>();
-    if ticks.is_empty() {
-        return None;
-    }
-
-    let picked = match rule {
-        LocalBoundaryCloseRule::LastBefore => ticks
-            .iter()
-            .filter(|(_, _, offset_ms)| *offset_ms <= 0)
-            .max_by_key(|(ts_ms, _, _)| *ts_ms)
-            .copied(),
-        LocalBoundaryCloseRule::NearestAbs => ticks
-            .iter()
-            .min_by_key(|(ts_ms, _, offset_ms)| (offset_ms.abs(), *offset_ms > 0, *ts_ms))
-            .copied(),
-        LocalBoundaryCloseRule::AfterThenBefore => ticks
-            .iter()
-            .filter(|(_, _, offset_ms)| *offset_ms >= 0)
-            .min_by_key(|(ts_ms, _, _)| *ts_ms)
-            .copied()
-            .or_else(|| {
-                ticks
-                    .iter()
-                    .filter(|(_, _, offset_ms)| *offset_ms <= 0)
-                    .max_by_key(|(ts_ms, _, _)| *ts_ms)
-                    .copied()
-            }),
-    }?;
-
-    let (ts_ms, raw_price, offset_ms) = picked;
-    Some((
-        AggregatedPricePoint {
-            price: raw_price,
-            ts_ms,
-            exact: offset_ms == 0,
-            abs_delta_ms: offset_ms.unsigned_abs(),
-            source: rule.as_str(),
-        },
-        raw_price,
-    ))
-}
-
-fn local_boundary_policy_specs_fully_free(symbol: &str) -> LocalBoundaryShadowPolicySpec {
-    match symbol {
-        "bnb/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "full",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
-        },
-        "btc/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        "doge/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        "eth/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        "hype/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_hyperliquid",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_HYPERLIQUID,
-        },
-        "sol/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_bybit",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 2,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BYBIT,
-        },
-        "xrp/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        _ => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_fully_free",
-            source_subset_name: "full",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
-        },
-    }
-}
-
-fn local_boundary_policy_specs_shared_core(symbol: &str) -> LocalBoundaryShadowPolicySpec {
-    match symbol {
-        "bnb/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        "btc/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        "doge/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        "eth/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "full",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
-        },
-        "hype/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_coinbase",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_COINBASE,
-        },
-        "sol/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        "xrp/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        _ => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_shared_core",
-            source_subset_name: "full",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
-        },
-    }
-}
-
-fn local_boundary_policy_specs_weighted(symbol: &str) -> LocalBoundaryShadowPolicySpec {
-    match symbol {
-        "bnb/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "drop_okx",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
-        },
-        "btc/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "only_coinbase",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
-        },
-        "doge/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        "eth/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "only_coinbase",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
-        },
-        "hype/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "drop_binance",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 2,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
-        },
-        "sol/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "only_okx_coinbase",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 2,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX_COINBASE,
-        },
-        "xrp/usd" => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "only_binance_coinbase",
-            rule: LocalBoundaryCloseRule::NearestAbs,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE,
-        },
-        _ => LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_weighted",
-            source_subset_name: "full",
-            rule: LocalBoundaryCloseRule::LastBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
-        },
-    }
-}
-
-fn local_boundary_symbol_router_fallback_policy_specs(
-    symbol: &str,
-) -> Vec<LocalBoundaryShadowPolicySpec> {
-    match symbol {
-        "bnb/usd" => vec![
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "bnb_okx_no_fallback",
-                rule: LocalBoundaryCloseRule::AfterThenBefore,
-                min_sources: 1,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
-            },
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "bnb_okx_fallback",
-                rule: LocalBoundaryCloseRule::AfterThenBefore,
-                min_sources: 1,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
-            },
-        ],
-        "doge/usd" => vec![LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_symbol_router_fallback",
-            source_subset_name: "doge_binance_fallback",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
-        }],
-        "eth/usd" => vec![LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_symbol_router_fallback",
-            source_subset_name: "eth_binance_missing_fallback",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
-        }],
-        "hype/usd" => vec![LocalBoundaryShadowPolicySpec {
-            policy_name: "boundary_symbol_router_fallback",
-            source_subset_name: "hype_hyperliquid_fallback",
-            rule: LocalBoundaryCloseRule::AfterThenBefore,
-            min_sources: 1,
-            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_HYPERLIQUID,
-        }],
-        "sol/usd" => vec![
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "sol_binance_coinbase_fallback",
-                rule: LocalBoundaryCloseRule::NearestAbs,
-                min_sources: 2,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE,
-            },
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "sol_coinbase_fallback",
-                rule: LocalBoundaryCloseRule::AfterThenBefore,
-                min_sources: 1,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
-            },
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "sol_binance_fallback",
-                rule: LocalBoundaryCloseRule::AfterThenBefore,
-                min_sources: 1,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
-            },
-            LocalBoundaryShadowPolicySpec {
-                policy_name: "boundary_symbol_router_fallback",
-                source_subset_name: "sol_okx_missing_fallback",
-                rule: LocalBoundaryCloseRule::AfterThenBefore,
-                min_sources: 1,
-                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
-            },
-        ],
-        _ => Vec::new(),
-    }
-}
-
-fn local_boundary_policy_source_weight(
-    policy_name: &str,
-    symbol: &str,
-    source: LocalPriceSource,
-) -> f64 {
-    if policy_name != "boundary_weighted" {
-        return local_price_agg_source_weight(source);
-    }
-    if symbol == "doge/usd" {
-        return match source {
-            LocalPriceSource::Binance => 0.5,
-            LocalPriceSource::Bybit => 0.5,
-            LocalPriceSource::Coinbase => 1.0,
-            LocalPriceSource::Hyperliquid => 1.5,
-            LocalPriceSource::Okx => 0.5,
-        };
-    }
-    if symbol == "xrp/usd" {
-        return match source {
-            LocalPriceSource::Binance => 0.462_086,
-            LocalPriceSource::Bybit => 0.0,
-            LocalPriceSource::Coinbase => 2.329_335,
-            LocalPriceSource::Okx => 0.050_000,
-            LocalPriceSource::Hyperliquid => 1.083_887,
-        };
-    }
-    if symbol == "btc/usd" {
-        return match source {
-            LocalPriceSource::Coinbase => 1.0,
-            _ => 0.0,
-        };
-    }
-    if symbol == "bnb/usd" {
-        return match source {
-            LocalPriceSource::Binance => 0.5,
-            LocalPriceSource::Bybit => 0.5,
-            LocalPriceSource::Okx => 0.5,
-            LocalPriceSource::Coinbase => 1.0,
-            LocalPriceSource::Hyperliquid => 1.5,
-        };
-    }
-    if symbol == "hype/usd" {
-        return match source {
-            LocalPriceSource::Bybit => 1.0,
-            LocalPriceSource::Coinbase => 1.0,
-            LocalPriceSource::Okx => 1.0,
-            LocalPriceSource::Hyperliquid => 0.25,
-            LocalPriceSource::Binance => 0.0,
-        };
-    }
-    match source {
-        LocalPriceSource::Binance => 0.5,
-        LocalPriceSource::Bybit => 0.5,
-        LocalPriceSource::Okx => 0.5,
-        LocalPriceSource::Coinbase => 1.0,
-        LocalPriceSource::Hyperliquid => 1.5,
-    }
-}
-
-fn local_boundary_weighted_candidate_filter_reason_for_policy(
-    symbol: &str,
-    close_only_filter_reason: Option<&'static str>,
-    close_only_direction_margin_bps: Option<f64>,
-    round_end_ts: u64,
-    rtds_open: f64,
-    hit: &LocalBoundaryShadowHit,
-) -> Option<&'static str> {
-    let weighted_direction_margin_bps =
-        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
-    if hit.source_count >= 2 && hit.source_spread_bps > 20.0 {
-        return Some("cross_source_extreme_spread");
-    }
-    match symbol {
-        "bnb/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.source_subset_name == "bnb_okx_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-            {
-                return Some("bnb_okx_fallback_far_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 4.5
-            {
-                return Some("bnb_single_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 7.6
-                && close_abs_delta_ms >= 400
-                && close_abs_delta_ms <= 1_200
-            {
-                return Some("bnb_single_yes_fast_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 9.3 + 1e-9
-                && (close_abs_delta_ms < 400 || close_abs_delta_ms >= 1_800)
-            {
-                return Some("bnb_single_yes_upper_mid_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 9.3 + 1e-9
-                && hit.source_spread_bps <= 0.5 + 1e-9
-                && close_abs_delta_ms >= 2_500
-            {
-                return Some("bnb_two_lowspread_yes_upper_mid_stale_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 5.8
-                && close_abs_delta_ms <= 500
-            {
-                return Some("bnb_single_binance_yes_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 5.5 + 1e-9
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("bnb_single_binance_yes_stale_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_direction_margin_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps < 8.0
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("bnb_single_binance_yes_stale_upper_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_direction_margin_bps + 1e-9 >= 11.5
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("bnb_single_binance_yes_stale_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count <= 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-                && close_abs_delta_ms >= 100
-                && close_abs_delta_ms < 1_500
-            {
-                return Some("bnb_no_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps <= 2.0 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 2.2
-            {
-                return Some("bnb_two_no_midspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 6.0
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("bnb_single_binance_no_fast_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-            {
-                return Some("bnb_single_bybit_no_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 2.5
-            {
-                return Some("bnb_three_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps + 1e-9 >= 30.0
-                && close_abs_delta_ms <= 100
-            {
-                return Some("bnb_three_high_spread_fast_far_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps + 1e-9 < 3.5
-            {
-                return Some("bnb_three_wide_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps <= 0.55 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps < 7.3
-                && close_abs_delta_ms < 250
-            {
-                return Some("bnb_two_tight_spread_yes_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources >= 1
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 0.5 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-            {
-                return Some("bnb_two_binance_bybit_exact_lowspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 0.6 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 15.0
-                && close_abs_delta_ms <= 200
-            {
-                return Some("bnb_two_binance_bybit_yes_lowspread_fast_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 1.3 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps < 3.3
-                && close_abs_delta_ms <= 800
-            {
-                return Some("bnb_two_binance_bybit_yes_lowspread_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 1.0 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 5.5
-                && weighted_direction_margin_bps < 7.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("bnb_two_binance_bybit_yes_lowspread_fast_upper_mid_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 0.3 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 9.0
-                && weighted_direction_margin_bps < 10.0
-                && close_abs_delta_ms >= 800
-                && close_abs_delta_ms <= 1_500
-            {
-                return Some("bnb_two_binance_bybit_yes_lowspread_fast_high_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 2.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 8.5
-                && close_abs_delta_ms >= 1_000
-                && close_abs_delta_ms <= 1_500
-            {
-                return Some("bnb_two_binance_bybit_yes_midspread_stale_upper_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 < 5.0
-            {
-                return Some("bnb_two_wide_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-            {
-                return Some("bnb_high_spread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-            {
-                return Some("bnb_mid_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps <= 1.3 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 2.2
-            {
-                return Some("bnb_tight_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 1.2 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms <= 800
-            {
-                return Some("bnb_two_binance_bybit_yes_midspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 1.3 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 3.0
-            {
-                return Some("bnb_two_binance_bybit_yes_lowspread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps <= 1.3 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps < 8.0
-                && close_abs_delta_ms >= 2_500
-            {
-                return Some("bnb_two_binance_bybit_yes_stale_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-            {
-                return Some("bnb_yes_near_flat");
-            }
-            if close_only_filter_reason == Some("preclose_near_flat")
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-            {
-                return Some("bnb_close_only_preclose_near_flat");
-            }
-        }
-        "btc/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 0.451
-            {
-                return Some("btc_single_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 < 0.9
-                && close_abs_delta_ms <= 300
-            {
-                return Some("btc_single_coinbase_yes_fast_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 < 0.9
-                && close_abs_delta_ms > 300
-                && close_abs_delta_ms <= 600
-            {
-                return Some("btc_single_coinbase_yes_midlag_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 1.0
-                && weighted_direction_margin_bps < 1.5
-                && close_abs_delta_ms <= 200
-            {
-                return Some("btc_single_coinbase_yes_fast_low_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 2.2
-                && weighted_direction_margin_bps < 2.5
-                && close_abs_delta_ms <= 100
-            {
-                return Some("btc_single_coinbase_yes_very_fast_upper_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 0.9
-                && weighted_direction_margin_bps < 1.2
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 550
-            {
-                return Some("btc_single_coinbase_yes_midlag_upper_near_flat_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 1.8
-                && weighted_direction_margin_bps < 2.1
-                && close_abs_delta_ms > 300
-                && close_abs_delta_ms <= 500
-            {
-                return Some("btc_single_coinbase_yes_midlag_upper_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 2.4
-                && weighted_direction_margin_bps < 2.7
-                && close_abs_delta_ms > 500
-                && close_abs_delta_ms <= 700
-            {
-                return Some("btc_single_coinbase_yes_midlag_upper_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 < 0.9
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("btc_single_coinbase_yes_stale_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 < 0.5
-                && close_abs_delta_ms <= 100
-            {
-                return Some("btc_single_coinbase_no_fast_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 < 0.5
-                && close_abs_delta_ms > 300
-                && close_abs_delta_ms <= 700
-            {
-                return Some("btc_single_coinbase_no_midlag_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 2.6
-                && close_abs_delta_ms <= 300
-            {
-                return Some("btc_single_coinbase_no_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-            {
-                return Some("btc_two_source_near_flat");
-            }
-        }
-        "xrp/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps < 0.05
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-                && close_abs_delta_ms <= 300
-            {
-                return Some("xrp_binance_coinbase_yes_zero_spread_fast_near_flat");
-            }
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 1.7
-                && weighted_direction_margin_bps + 1e-9 < 0.35
-                && close_abs_delta_ms <= 300
-            {
-                return Some("xrp_binance_coinbase_yes_fast_midspread_near_flat");
-            }
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 1.7
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 800
-            {
-                return Some("xrp_binance_coinbase_yes_midlag_midspread_near_flat");
-            }
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 0.5
-                && hit.source_spread_bps < 1.0
-                && weighted_direction_margin_bps + 1e-9 < 0.7
-                && close_abs_delta_ms <= 300
-            {
-                return Some("xrp_binance_coinbase_yes_fast_tightspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 0.45
-                && !((!weighted_side_yes && close_abs_delta_ms >= 400)
-                    || (weighted_side_yes && close_abs_delta_ms < 300))
-            {
-                return Some("xrp_nearest_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-                && !(!weighted_side_yes && close_abs_delta_ms >= 50)
-            {
-                return Some("xrp_nearest_wide_spread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.3
-                && !(!weighted_side_yes && weighted_direction_margin_bps + 1e-9 < 1.13)
-            {
-                return Some("xrp_single_nearest_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && !weighted_side_yes
-                && hit.source_subset_name == "only_binance_coinbase"
-                && weighted_direction_margin_bps + 1e-9 < 0.7
-                && close_abs_delta_ms <= 300
-            {
-                return Some("xrp_single_binance_no_fast_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_side_yes
-                && weighted_direction_margin_bps < 2.5
-                && close_abs_delta_ms >= 1_000
-            {
-                return Some("xrp_single_binance_yes_stale_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 4.5
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("xrp_single_binance_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps < 2.6
-                && close_abs_delta_ms <= 100
-            {
-                return Some("xrp_nearest_wide_spread_yes_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms >= 800
-            {
-                return Some("xrp_binance_coinbase_no_stale_mid_margin");
-            }
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 1.7
-                && weighted_direction_margin_bps + 1e-9 < 0.8
-                && close_abs_delta_ms >= 1_000
-            {
-                return Some("xrp_binance_coinbase_no_stale_midspread_near_flat");
-            }
-            if hit.source_subset_name == "only_binance_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Binance)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 < 1.2
-                && close_abs_delta_ms <= 500
-            {
-                return Some("xrp_binance_coinbase_no_fast_midspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 2.5
-                && weighted_direction_margin_bps < 3.5
-                && close_abs_delta_ms >= 2_000
-            {
-                return Some("xrp_single_binance_yes_stale_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-            {
-                return Some("xrp_last_yes_near_flat");
-            }
-        }
-        "doge/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && close_abs_delta_ms <= 1_000
-                && weighted_direction_margin_bps + 1e-9 < 4.0
-                && !(hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
-                    && weighted_direction_margin_bps + 1e-9 >= 0.45)
-            {
-                return Some("doge_single_last_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps < 5.0
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("doge_single_bybit_yes_fast_upper_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
-                && weighted_direction_margin_bps + 1e-9 >= 14.0
-                && close_abs_delta_ms <= 300
-            {
-                return Some("doge_single_bybit_fast_high_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
-                && (900..=1_000).contains(&close_abs_delta_ms)
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 15.0
-            {
-                return Some("doge_single_okx_late_fast_mid_margin_tail");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 2.5
-                && close_abs_delta_ms >= 4_000
-                && close_abs_delta_ms <= 4_500
-            {
-                return Some("doge_single_okx_last_very_stale_yes_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 3.8
-                && weighted_direction_margin_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps < 8.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("doge_three_last_fast_spread_tail");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 6.0
-                && hit.source_spread_bps < 7.0
-                && weighted_direction_margin_bps + 1e-9 >= 25.0
-                && weighted_direction_margin_bps < 27.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("doge_three_bybit_coinbase_okx_last_fast_highspread_far_margin_tail");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 10.0
-                && close_abs_delta_ms >= 400
-                && close_abs_delta_ms <= 600
-            {
-                return Some("doge_three_bybit_coinbase_okx_last_fast_midspread_no_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps + 1e-9 < 3.5
-                && close_abs_delta_ms <= 800
-            {
-                return Some("doge_three_last_fast_spread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps <= 1.1 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 15.0
-                && close_abs_delta_ms <= 800
-            {
-                return Some("doge_three_last_fast_tightspread_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps <= 1.1 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && hit.source_spread_bps + 1e-9 >= 0.9
-                && hit.source_spread_bps < 1.0
-                && close_abs_delta_ms <= 800
-            {
-                return Some("doge_multi_last_fast_tightspread_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps <= 0.1 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 6.0
-                && close_abs_delta_ms <= 800
-            {
-                return Some("doge_two_last_tightspread_fast_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 0.8
-                && hit.source_spread_bps < 1.1
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps < 15.0
-                && close_abs_delta_ms >= 1_500
-            {
-                return Some("doge_two_bybit_okx_last_stale_tightspread_high_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && hit.source_spread_bps < 2.0
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("doge_multi_last_fast_midspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && close_abs_delta_ms <= 500
-                && matches!(
-                    close_only_filter_reason,
-                    Some("preclose_near_flat" | "low_confidence")
-                )
-                && close_only_direction_margin_bps
-                    .map(|margin_bps| margin_bps + 1e-9 < 0.5)
-                    .unwrap_or(false)
-            {
-                return Some("doge_three_last_fast_midspread_low_signal_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms >= 1_000
-                && close_abs_delta_ms <= 1_800
-            {
-                return Some("doge_two_bybit_coinbase_last_stale_widespread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 7.5
-                && hit.source_spread_bps < 2.0
-                && close_abs_delta_ms <= 700
-            {
-                return Some("doge_multi_last_fast_midspread_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps < 10.0
-                && close_abs_delta_ms >= 1_000
-                && close_abs_delta_ms <= 1_800
-            {
-                return Some("doge_multi_last_midspread_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 >= 15.0
-                && weighted_direction_margin_bps < 23.0
-                && close_abs_delta_ms >= 2_500
-            {
-                return Some("doge_multi_last_stale_midspread_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 >= 23.0
-                && close_abs_delta_ms >= 4_500
-            {
-                return Some("doge_multi_last_very_stale_midspread_high_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("doge_three_last_stale_highspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 < 3.5
-                && close_abs_delta_ms < 2_148
-                && close_abs_delta_ms >= 1_800
-            {
-                return Some("doge_multi_last_stale_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 0.8
-                && hit.source_spread_bps < 1.1
-                && weighted_direction_margin_bps + 1e-9 < 0.5
-                && close_abs_delta_ms >= 1_800
-                && close_abs_delta_ms <= 3_000
-            {
-                return Some("doge_three_last_stale_tightspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 0.8
-                && hit.source_spread_bps < 1.1
-                && weighted_direction_margin_bps + 1e-9 < 0.2
-                && close_abs_delta_ms >= 2_000
-                && close_abs_delta_ms <= 2_500
-            {
-                return Some("doge_two_bybit_coinbase_last_stale_tightspread_no_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 2.0
-                && weighted_direction_margin_bps + 1e-9 < 3.5
-                && close_abs_delta_ms >= 2_148
-                && close_abs_delta_ms <= 3_000
-            {
-                return Some("doge_multi_last_stale_midspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-                && close_abs_delta_ms >= 3_000
-            {
-                return Some("doge_three_last_stale_midspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 2.2
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && close_abs_delta_ms >= 1_500
-            {
-                return Some("doge_two_bybit_okx_last_stale_midspread_near_flat");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.5
-                && hit.source_spread_bps < 3.1
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-                && close_abs_delta_ms >= 2_000
-                && close_abs_delta_ms <= 2_600
-            {
-                return Some("doge_two_bybit_okx_last_stale_highmidspread_yes_near_flat");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 2.2
-                && weighted_direction_margin_bps + 1e-9 < 0.3
-                && close_abs_delta_ms >= 2_500
-                && close_abs_delta_ms <= 3_000
-            {
-                return Some("doge_two_bybit_okx_last_stale_midspread_no_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 0.8
-                && hit.source_spread_bps < 1.1
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-                && close_abs_delta_ms >= 2_500
-            {
-                return Some("doge_two_bybit_okx_last_very_stale_tight_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-            {
-                return Some("doge_single_last_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 0.8
-                && hit.source_spread_bps < 1.2
-                && weighted_direction_margin_bps + 1e-9 < 0.8
-                && close_abs_delta_ms <= 300
-            {
-                return Some("doge_two_coinbase_okx_last_fast_tightspread_no_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-                && !((!weighted_side_yes && weighted_direction_margin_bps + 1e-9 < 1.25)
-                    || close_abs_delta_ms >= 1_500)
-            {
-                return Some("doge_last_multi_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && !(weighted_side_yes
-                    && weighted_direction_margin_bps + 1e-9 >= 20.0
-                    && hit.source_spread_bps <= 11.0 + 1e-9
-                    && close_abs_delta_ms <= 500)
-            {
-                return Some("doge_last_high_spread");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 0.5
-            {
-                return Some("doge_nearest_near_flat");
-            }
-            if hit.source_subset_name == "doge_binance_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && ((weighted_direction_margin_bps + 1e-9 >= 7.8
-                    && weighted_direction_margin_bps < 8.2)
-                    || weighted_direction_margin_bps + 1e-9 >= 13.5)
-            {
-                return Some("doge_binance_fallback_tail_margin");
-            }
-            if hit.source_subset_name == "doge_binance_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps < 7.8
-            {
-                return Some("doge_binance_fallback_mid_margin");
-            }
-        }
-        "hype/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps + 1e-9 < 8.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_three_after_high_spread_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && hit.source_spread_bps < 2.5 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps < 8.0
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("hype_three_after_mid_spread_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 4
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps + 1e-9 < 8.0
-            {
-                return Some("hype_four_after_high_spread_mid_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 4
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && hit.source_spread_bps < 10.0
-                && weighted_direction_margin_bps + 1e-9 < 8.0
-                && close_abs_delta_ms >= 1_500
-            {
-                return Some("hype_four_drop_binance_stale_highspread_yes_mid_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 4
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps + 1e-9 < 20.0
-            {
-                return Some("hype_four_after_high_spread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 4
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 15.0
-                && weighted_direction_margin_bps + 1e-9 < 5.0
-            {
-                return Some("hype_after_very_high_spread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps + 1e-9 >= 30.0
-                && close_abs_delta_ms >= 900
-            {
-                return Some("hype_after_high_spread_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 30.0
-            {
-                return Some("hype_multi_after_high_spread_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-                && close_abs_delta_ms >= 3_000
-            {
-                return Some("hype_multi_after_stale_wide_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 4
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 4.5
-                && hit.source_spread_bps < 5.5
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-                && close_abs_delta_ms >= 1_000
-                && close_abs_delta_ms <= 1_500
-            {
-                return Some("hype_four_drop_binance_stale_midspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps <= 4.0 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 >= 40.0
-            {
-                return Some("hype_three_source_stale_spread_fallback");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-            {
-                return Some("hype_single_after_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.2
-            {
-                return Some("hype_three_after_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 2.5
-                && weighted_direction_margin_bps + 1e-9 >= 2.5
-                && weighted_direction_margin_bps < 3.2
-                && close_abs_delta_ms >= 2_500
-                && close_abs_delta_ms <= 3_000
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_stale_tightspread_yes_near_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 2.5
-                && weighted_direction_margin_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps < 2.0
-                && close_abs_delta_ms >= 1_000
-                && close_abs_delta_ms <= 1_200
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_midlag_midspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 4.5
-                && hit.source_spread_bps < 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 2.5
-                && close_abs_delta_ms >= 3_000
-                && close_abs_delta_ms <= 3_500
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_stale_widespread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 2.5
-                && hit.source_spread_bps < 3.1
-                && weighted_direction_margin_bps + 1e-9 >= 13.0
-                && weighted_direction_margin_bps < 15.0
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 700
-            {
-                return Some(
-                    "hype_three_bybit_hyperliquid_okx_midlag_midspread_yes_upper_margin_tail",
-                );
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 7.0
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && close_abs_delta_ms <= 1_500
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_fast_highspread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && hit.source_spread_bps < 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 20.0
-                && weighted_direction_margin_bps < 25.0
-                && close_abs_delta_ms <= 100
-            {
-                return Some(
-                    "hype_three_bybit_hyperliquid_okx_fast_highspread_yes_far_margin_tail",
-                );
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 7.0
-                && weighted_direction_margin_bps + 1e-9 >= 20.0
-                && weighted_direction_margin_bps < 25.0
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 700
-            {
-                return Some(
-                    "hype_three_bybit_hyperliquid_okx_fast_highspread_yes_midlag_far_margin_tail",
-                );
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && hit.source_spread_bps < 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps < 5.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_fast_midspread_no_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 2.5
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 < 2.5
-                && close_abs_delta_ms <= 600
-            {
-                return Some("hype_two_hyperliquid_okx_yes_midspread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 0.7
-                && hit.source_spread_bps < 1.0
-                && weighted_direction_margin_bps + 1e-9 >= 1.8
-                && weighted_direction_margin_bps < 2.4
-                && close_abs_delta_ms >= 700
-                && close_abs_delta_ms <= 1_200
-            {
-                return Some("hype_two_hyperliquid_okx_yes_tightspread_midlag_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 1.5 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 1.8
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("hype_two_after_mid_spread_fast_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 2.6
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_midspread_yes_near_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.0
-                && weighted_direction_margin_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps < 5.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_midspread_yes_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && hit.source_spread_bps < 3.5
-                && weighted_direction_margin_bps + 1e-9 < 2.2
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 800
-            {
-                return Some("hype_two_bybit_hyperliquid_midlag_widespread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 1.5
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps < 12.5
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_tightspread_yes_upper_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && hit.source_spread_bps < 4.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_widespread_yes_near_margin");
-            }
-            if hit.source_subset_name == "drop_binance"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit.source_spread_bps + 1e-9 >= 3.5
-                && hit.source_spread_bps < 4.0
-                && weighted_direction_margin_bps + 1e-9 >= 2.5
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 900
-            {
-                return Some("hype_two_bybit_hyperliquid_midlag_widespread_yes_near_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.5
-                && hit.source_spread_bps < 4.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 9.0
-                && close_abs_delta_ms <= 100
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_widespread_yes_upper_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 6.0
-                && weighted_direction_margin_bps + 1e-9 >= 3.5
-                && weighted_direction_margin_bps < 4.5
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 700
-            {
-                return Some("hype_two_bybit_hyperliquid_midlag_highspread_yes_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_subset_name == "drop_binance"
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && hit.source_spread_bps < 4.5
-                && weighted_direction_margin_bps + 1e-9 >= 13.0
-                && weighted_direction_margin_bps < 15.0
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 700
-            {
-                return Some("hype_two_bybit_hyperliquid_midlag_midspread_yes_upper_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps + 1e-9 < 4.0
-            {
-                return Some("hype_three_coinbase_hl_okx_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit.source_spread_bps + 1e-9 >= 4.0
-                && hit.source_spread_bps < 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 40.0
-                && close_abs_delta_ms >= 800
-            {
-                return Some("hype_three_coinbase_hl_okx_no_midspread_far_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 3
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && hit.source_spread_bps < 5.0
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps < 15.0
-                && close_abs_delta_ms >= 1_500
-            {
-                return Some("hype_three_bybit_hyperliquid_okx_stale_midspread_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 10.0
-                && (close_abs_delta_ms < 200
-                    || (!weighted_side_yes
-                        && hit
-                            .source_contributions
-                            .iter()
-                            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                        && hit
-                            .source_contributions
-                            .iter()
-                            .any(|c| c.source == LocalPriceSource::Okx)
-                        && !hit
-                            .source_contributions
-                            .iter()
-                            .any(|c| c.source == LocalPriceSource::Bybit)
-                        && !hit
-                            .source_contributions
-                            .iter()
-                            .any(|c| c.source == LocalPriceSource::Coinbase)
-                        && weighted_direction_margin_bps + 1e-9 < 1.5))
-            {
-                return Some("hype_two_after_very_high_spread");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 6.0
-                && weighted_direction_margin_bps + 1e-9 < 4.5
-                && close_abs_delta_ms <= 1_000
-            {
-                return Some("hype_two_hyperliquid_okx_yes_widespread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 6.5
-                && weighted_direction_margin_bps + 1e-9 >= 15.0
-                && weighted_direction_margin_bps < 22.0
-                && close_abs_delta_ms <= 700
-            {
-                return Some("hype_two_hyperliquid_okx_yes_highspread_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && !hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 6.5
-                && weighted_direction_margin_bps + 1e-9 >= 15.0
-                && weighted_direction_margin_bps < 22.0
-                && close_abs_delta_ms <= 700
-            {
-                return Some("hype_two_hyperliquid_okx_no_highspread_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 6.0
-                && (weighted_direction_margin_bps + 1e-9 >= 30.0
-                    || (weighted_direction_margin_bps + 1e-9 >= 16.0
-                        && hit.source_spread_bps < 6.5))
-                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 >= 40.0)
-            {
-                return Some("hype_two_after_high_spread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit.source_spread_bps + 1e-9 >= 6.0
-                && hit.source_spread_bps < 7.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 9.0
-                && close_abs_delta_ms <= 200
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_highspread_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit.source_spread_bps + 1e-9 >= 5.5
-                && hit.source_spread_bps < 8.0
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 10.0
-                && close_abs_delta_ms >= 800
-                && close_abs_delta_ms <= 1_200
-            {
-                return Some("hype_two_bybit_hyperliquid_stale_highspread_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Bybit)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
-                && hit.source_spread_bps + 1e-9 >= 5.0
-                && hit.source_spread_bps < 6.0
-                && weighted_direction_margin_bps + 1e-9 >= 20.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("hype_two_bybit_hyperliquid_fast_highspread_high_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 6.0
-                && hit.source_spread_bps < 8.0
-                && weighted_direction_margin_bps + 1e-9 < 3.5
-            {
-                return Some("hype_after_high_spread_yes_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count >= 3
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit.source_spread_bps + 1e-9 >= 6.0
-                && hit.source_spread_bps < 8.0
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && weighted_direction_margin_bps < 20.0 + 1e-9
-            {
-                return Some("hype_three_after_high_spread_yes_mid_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps <= 1.0 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 2.0
-            {
-                return Some("hype_two_after_tight_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps + 1e-9 >= 1.5
-                && weighted_direction_margin_bps + 1e-9 < 1.8
-                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 >= 1.25)
-            {
-                return Some("hype_two_after_wide_spread_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.close_exact_sources == 0
-                && ((hit.source_count >= 2
-                    && hit.source_spread_bps <= 1.0 + 1e-9
-                    && weighted_direction_margin_bps + 1e-9 < 1.8)
-                    || (hit.source_count == 1 && weighted_direction_margin_bps + 1e-9 < 2.0))
-            {
-                return Some("hype_nearest_near_flat");
-            }
-        }
-        "eth/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-                && !(!weighted_side_yes && weighted_direction_margin_bps + 1e-9 >= 0.63)
-            {
-                return Some("eth_single_last_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && !weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 0.63
-                && weighted_direction_margin_bps < 0.8
-                && close_abs_delta_ms >= 3_000
-                && close_abs_delta_ms <= 5_000
-            {
-                return Some("eth_single_coinbase_no_very_stale_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 1.0
-                && weighted_direction_margin_bps < 2.2
-                && close_abs_delta_ms <= 500
-            {
-                return Some("eth_single_coinbase_yes_fast_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 1.0
-                && weighted_direction_margin_bps < 2.5
-                && close_abs_delta_ms >= 3_000
-            {
-                return Some("eth_single_coinbase_yes_stale_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 1.0
-                && weighted_direction_margin_bps < 1.3
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms < 3_000
-            {
-                return Some("eth_single_coinbase_yes_mid_stale_near_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_subset_name == "only_coinbase"
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 2.5
-                && weighted_direction_margin_bps < 3.0
-                && close_abs_delta_ms <= 150
-            {
-                return Some("eth_single_coinbase_yes_fast_upper_mid_margin");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_subset_name == "only_coinbase"
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
-                && weighted_side_yes
-                && weighted_direction_margin_bps + 1e-9 >= 8.0
-                && weighted_direction_margin_bps < 13.0
-                && close_abs_delta_ms <= 50
-            {
-                return Some("eth_single_coinbase_yes_fast_mid_margin_tail");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps <= 1.5 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 0.35
-            {
-                return Some("eth_two_last_near_flat");
-            }
-            if hit.rule == LocalBoundaryCloseRule::LastBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && hit.source_spread_bps <= 1.1 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 0.2
-            {
-                return Some("eth_two_last_tight_near_flat");
-            }
-        }
-        "sol/usd" => {
-            let weighted_side_yes = hit.close_price >= rtds_open;
-            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_direction_margin_bps + 1e-9 < 1.0
-                && (weighted_side_yes || hit.source_spread_bps < 2.5)
-            {
-                return Some("sol_after_near_flat");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 1.0
-                && hit.source_spread_bps < 2.5
-                && weighted_direction_margin_bps + 1e-9 >= 1.45
-                && weighted_direction_margin_bps < 1.7
-                && close_abs_delta_ms >= 500
-                && close_abs_delta_ms <= 900
-            {
-                return Some("sol_okx_coinbase_no_midlag_midmargin_near_flat");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && hit.source_spread_bps < 4.2
-                && weighted_direction_margin_bps + 1e-9 >= 4.0
-                && weighted_direction_margin_bps < 5.0
-                && close_abs_delta_ms <= 250
-            {
-                return Some("sol_okx_coinbase_no_fast_midspread_midmargin_tail");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 7.0
-                && weighted_direction_margin_bps + 1e-9 < 1.5
-                && close_abs_delta_ms <= 600
-            {
-                return Some("sol_okx_coinbase_high_spread_yes_near_flat");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.0
-                && weighted_direction_margin_bps + 1e-9 < 3.0
-                && close_abs_delta_ms <= 100
-            {
-                return Some("sol_okx_coinbase_mid_spread_yes_fast_near_flat");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps < 3.0
-                && weighted_direction_margin_bps + 1e-9 < 1.6
-                && close_abs_delta_ms > 100
-                && close_abs_delta_ms <= 500
-            {
-                return Some("sol_okx_coinbase_mid_spread_yes_fastish_near_flat");
-            }
-            if hit.source_subset_name == "only_okx_coinbase"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && !weighted_side_yes
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Coinbase)
-                && hit
-                    .source_contributions
-                    .iter()
-                    .any(|c| c.source == LocalPriceSource::Okx)
-                && hit.source_spread_bps + 1e-9 >= 3.0
-                && weighted_direction_margin_bps + 1e-9 >= 10.0
-                && close_abs_delta_ms <= 500
-            {
-                return Some("sol_okx_coinbase_no_fast_high_spread_tail");
-            }
-            if hit.source_subset_name == "sol_okx_missing_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && weighted_side_yes
-                && hit
-                    .source_contributions
-                    .first()
-                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
-                && weighted_direction_margin_bps + 1e-9 >= 9.0
-                && weighted_direction_margin_bps < 10.5
-                && close_abs_delta_ms >= 2_000
-            {
-                return Some("sol_okx_missing_stale_borderline_tail");
-            }
-            if close_only_filter_reason == Some("preclose_near_flat")
-                && close_only_direction_margin_bps
-                    .map(|v| v + 1e-9 < 0.2)
-                    .unwrap_or(false)
-                && hit.source_count == 2
-                && hit.source_spread_bps <= 0.1 + 1e-9
-            {
-                return Some("sol_close_only_preclose_near_flat");
-            }
-        }
-        _ => {}
-    }
-    None
-}
-
-fn local_boundary_weighted_candidate_allowed_for_policy(
-    symbol: &str,
-    close_only_filter_reason: Option<&'static str>,
-    close_only_direction_margin_bps: Option<f64>,
-    round_end_ts: u64,
-    rtds_open: f64,
-    hit: &LocalBoundaryShadowHit,
-) -> bool {
-    local_boundary_weighted_candidate_filter_reason_for_policy(
-        symbol,
-        close_only_filter_reason,
-        close_only_direction_margin_bps,
-        round_end_ts,
-        rtds_open,
-        hit,
-    )
-    .is_none()
-}
-
-fn local_boundary_symbol_router_prefers_weighted_primary(symbol: &str) -> bool {
-    let _ = symbol;
-    true
-}
-
-fn local_boundary_symbol_router_allows_close_only_fallback(
-    symbol: &str,
-    hit: &LocalCloseOnlyAggHit,
-    round_end_ts: u64,
-    rtds_open: f64,
-) -> bool {
-    if symbol != "hype/usd" {
-        return false;
-    }
-    let direction_margin_bps =
-        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_only_side_yes = hit.close_price >= rtds_open;
-    if hit.source_count == 1 && hit.close_exact_sources == 0 {
-        let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
-        if direction_margin_bps + 1e-9 < 2.6
-            && !(close_only_side_yes && direction_margin_bps + 1e-9 < 2.5)
-        {
-            return false;
-        }
-        if close_only_side_yes && direction_margin_bps + 1e-9 >= 3.5 && direction_margin_bps < 5.5 {
-            return false;
-        }
-        if close_only_side_yes && direction_margin_bps + 1e-9 < 7.0 && close_abs_delta_ms >= 500 {
-            return false;
-        }
-        let close_only_single_hyperliquid = hit
-            .source_contributions
-            .first()
-            .is_some_and(|contribution| contribution.source == LocalPriceSource::Hyperliquid);
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 7.0
-            && direction_margin_bps < 13.0
-            && close_abs_delta_ms <= 500
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 6.5
-            && direction_margin_bps < 7.0
-            && (300..=500).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 5.5
-            && direction_margin_bps < 6.0
-            && close_abs_delta_ms <= 150
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 15.0
-            && direction_margin_bps < 17.0
-            && close_abs_delta_ms <= 200
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 7.5
-            && direction_margin_bps < 8.2
-            && (840..=950).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 5.0
-            && direction_margin_bps < 20.0
-            && (650..=850).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 10.0
-            && direction_margin_bps < 13.0
-            && (950..=1_100).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 3.0
-            && direction_margin_bps < 4.0
-            && close_abs_delta_ms <= 150
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 2.0
-            && direction_margin_bps < 2.5
-            && close_abs_delta_ms <= 100
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 6.0
-            && direction_margin_bps < 7.0
-            && (200..=500).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 5.0
-            && direction_margin_bps < 6.0
-            && (300..=500).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 10.0
-            && direction_margin_bps < 11.5
-            && (350..=500).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 7.0
-            && direction_margin_bps < 8.0
-            && close_abs_delta_ms <= 300
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 4.0
-            && direction_margin_bps < 10.0
-            && (650..720).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 10.0
-            && direction_margin_bps < 16.0
-            && (720..850).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-        if close_only_single_hyperliquid
-            && !close_only_side_yes
-            && direction_margin_bps + 1e-9 >= 17.0
-            && direction_margin_bps < 19.0
-            && (700..=750).contains(&close_abs_delta_ms)
-        {
-            return false;
-        }
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit.source_spread_bps + 1e-9 >= 1.0
-        && hit.source_spread_bps < 1.5
-        && direction_margin_bps + 1e-9 >= 7.0
-        && direction_margin_bps < 10.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 500
-    {
-        return false;
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && direction_margin_bps + 1e-9 < 5.0
-    {
-        return false;
-    }
-    if hit.source_count == 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Bybit)
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-        && hit.source_spread_bps + 1e-9 >= 0.5
-        && hit.source_spread_bps < 1.0
-        && direction_margin_bps + 1e-9 >= 5.5
-        && direction_margin_bps < 7.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_500
-    {
-        return false;
-    }
-    if hit.source_count == 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Bybit)
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-        && hit.source_spread_bps + 1e-9 >= 1.0
-        && hit.source_spread_bps < 2.5
-        && direction_margin_bps + 1e-9 >= 5.0
-        && direction_margin_bps < 7.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
-    {
-        return false;
-    }
-    if hit.source_count == 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Bybit)
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-        && hit.source_spread_bps + 1e-9 >= 1.0
-        && hit.source_spread_bps < 1.5
-        && direction_margin_bps + 1e-9 >= 20.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_000
-    {
-        return false;
-    }
-    if hit.source_count == 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Bybit)
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-        && hit.source_spread_bps < 0.5
-        && direction_margin_bps + 1e-9 >= 30.0
-        && direction_margin_bps < 40.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_500
-    {
-        return false;
-    }
-    if hit.source_count == 2
-        && hit.close_exact_sources == 0
-        && close_only_side_yes
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Bybit)
-        && hit
-            .source_contributions
-            .iter()
-            .any(|c| c.source == LocalPriceSource::Hyperliquid)
-        && hit.source_spread_bps + 1e-9 >= 0.5
-        && hit.source_spread_bps < 0.7
-        && direction_margin_bps + 1e-9 >= 12.0
-        && direction_margin_bps < 15.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 2_000
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 2_500
-    {
-        return false;
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && hit.source_spread_bps <= 1.0 + 1e-9
-        && direction_margin_bps + 1e-9 >= 40.0
-    {
-        return false;
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && hit.source_spread_bps + 1e-9 >= 1.5
-        && direction_margin_bps + 1e-9 >= 8.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_000
-    {
-        return false;
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && hit.source_spread_bps + 1e-9 >= 2.0
-        && hit.source_spread_bps < 4.0 + 1e-9
-        && direction_margin_bps + 1e-9 >= 40.0
-        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
-    {
-        return false;
-    }
-    if hit.source_count >= 2
-        && hit.close_exact_sources == 0
-        && hit.source_spread_bps + 1e-9 >= 4.0
-        && direction_margin_bps + 1e-9 >= 8.0
-    {
-        return false;
-    }
-    true
-}
-
-fn local_boundary_symbol_router_source_fallback_allowed(
-    symbol: &str,
-    weighted_shadow_hit: Option<&LocalBoundaryShadowHit>,
-    weighted_shadow_filter_reason: Option<&'static str>,
-    hit: &LocalBoundaryShadowHit,
-    round_end_ts: u64,
-    rtds_open: f64,
-) -> bool {
-    if weighted_shadow_hit.is_some()
-        && !local_boundary_symbol_router_source_fallback_rescues_filtered(
-            symbol,
-            weighted_shadow_filter_reason,
-            hit,
-        )
-    {
-        return false;
-    }
-    let direction_margin_bps =
-        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
-    let source_fallback_allowed = match symbol {
-        "bnb/usd" => {
-            let fallback_side_yes = hit.close_price >= rtds_open;
-            (hit.source_subset_name == "bnb_okx_no_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && !fallback_side_yes
-                && direction_margin_bps + 1e-9 >= 4.0
-                && direction_margin_bps < 7.0 + 1e-9)
-                || (hit.source_subset_name == "bnb_okx_fallback"
-                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                    && hit.source_count == 1
-                    && hit.close_exact_sources == 0
-                    && direction_margin_bps + 1e-9 >= 7.0
-                    && direction_margin_bps < 8.0 + 1e-9)
-        }
-        "doge/usd" => {
-            hit.source_subset_name == "doge_binance_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && direction_margin_bps + 1e-9 >= 5.0
-                && direction_margin_bps <= 15.0 + 1e-9
-                && close_abs_delta_ms <= 4_000
-        }
-        "eth/usd" => {
-            hit.source_subset_name == "eth_binance_missing_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && direction_margin_bps + 1e-9 >= 3.0
-                && close_abs_delta_ms <= 2_500
-        }
-        "hype/usd" => {
-            hit.source_subset_name == "hype_hyperliquid_fallback"
-                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                && hit.source_count == 1
-                && hit.close_exact_sources == 0
-                && direction_margin_bps + 1e-9 >= 4.0
-                && direction_margin_bps < 5.0 + 1e-9
-                && close_abs_delta_ms <= 900
-        }
-        "sol/usd" => {
-            (hit.source_subset_name == "sol_binance_coinbase_fallback"
-                && hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && hit.source_count == 2
-                && hit.close_exact_sources == 0
-                && direction_margin_bps + 1e-9 >= 2.0
-                && hit.source_spread_bps <= 7.0 + 1e-9)
-                || (hit.source_subset_name == "sol_coinbase_fallback"
-                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                    && hit.source_count == 1
-                    && hit.close_exact_sources == 0
-                    && direction_margin_bps + 1e-9 >= 5.0)
-                || (hit.source_subset_name == "sol_binance_fallback"
-                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                    && hit.source_count == 1
-                    && hit.close_exact_sources == 0
-                    && direction_margin_bps + 1e-9 >= 12.0
-                    && close_abs_delta_ms <= 2_500)
-                || (hit.source_subset_name == "sol_okx_missing_fallback"
-                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-                    && hit.source_count == 1
-                    && hit.close_exact_sources == 0
-                    && direction_margin_bps + 1e-9 >= 4.0
-                    && close_abs_delta_ms <= 4_000)
-        }
-        _ => false,
+                close_point,
+                observed_ticks,
+                observed_snapshot_ticks,
+                observed_live_ticks,
+                lagged_ticks,
+                nearest_start,
+                nearest_end,
+                first_after_start,
+                first_after_end,
+                Some(open_pick_rule),
+                Some(close_pick_rule),
+            );
+            return None;
+        }
     };
-    if !source_fallback_allowed {
-        return false;
-    }
-    local_boundary_weighted_candidate_filter_reason_for_policy(
-        symbol,
-        None,
-        None,
-        round_end_ts,
-        rtds_open,
-        hit,
-    )
-    .is_none()
-}
 
-fn local_boundary_symbol_router_source_fallback_rescues_filtered(
-    symbol: &str,
-    weighted_shadow_filter_reason: Option<&'static str>,
-    hit: &LocalBoundaryShadowHit,
-) -> bool {
-    let Some(reason) = weighted_shadow_filter_reason else {
-        return false;
+    let mut confidence: f64 = match (open_hit.exact, close_hit.exact) {
+        (true, true) => 1.0,
+        (true, false) => 0.9,
+        (false, true) => 0.86,
+        (false, false) => 0.76,
     };
-    match symbol {
-        "bnb/usd" => {
-            hit.source_subset_name == "bnb_okx_no_fallback"
-                && matches!(
-                    reason,
-                    "bnb_no_near_flat"
-                        | "bnb_single_binance_no_fast_tail"
-                        | "bnb_two_no_midspread_near_flat"
-                )
-        }
-        "doge/usd" => {
-            hit.source_subset_name == "doge_binance_fallback"
-                && matches!(
-                    reason,
-                    "doge_multi_last_fast_midspread_tail"
-                        | "doge_multi_last_midspread_tail"
-                        | "doge_multi_last_stale_near_flat"
-                        | "doge_two_bybit_okx_last_stale_tightspread_high_margin_tail"
-                )
-        }
-        "sol/usd" => {
-            hit.source_subset_name == "sol_binance_fallback" && reason == "sol_after_near_flat"
-        }
-        "hype/usd" => {
-            hit.source_subset_name == "hype_hyperliquid_fallback"
-                && reason == "hype_three_after_near_flat"
-        }
-        _ => false,
+    if !open_hit.exact && open_hit.abs_delta_ms > open_tol_ms / 2 {
+        confidence -= 0.05;
     }
-}
-
-fn local_close_only_source_agreement(hit: &LocalCloseOnlyAggHit, rtds_open: f64) -> f64 {
-    if hit.source_contributions.is_empty() {
-        return 0.0;
+    if !close_hit.exact && close_hit.abs_delta_ms > close_tol_ms / 2 {
+        confidence -= 0.05;
     }
-    let close_only_side = if hit.close_price >= rtds_open {
-        Side::Yes
-    } else {
-        Side::No
-    };
-    let agree_cnt = hit
-        .source_contributions
-        .iter()
-        .filter(|src| {
-            let src_side = if src.adjusted_close_price >= rtds_open {
-                Side::Yes
-            } else {
-                Side::No
-            };
-            src_side == close_only_side
-        })
-        .count();
-    agree_cnt as f64 / hit.source_contributions.len() as f64
-}
-
-fn local_boundary_hybrid_prefers_weighted_over_close_only(
-    symbol: &str,
-    close_only_hit: &LocalCloseOnlyAggHit,
-    weighted_hit: &LocalBoundaryShadowHit,
-    rtds_open: f64,
-) -> bool {
-    let close_only_direction_margin_bps =
-        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let weighted_direction_margin_bps =
-        ((weighted_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_only_side = if close_only_hit.close_price >= rtds_open {
-        Side::Yes
-    } else {
-        Side::No
-    };
-    let weighted_side = if weighted_hit.close_price >= rtds_open {
-        Side::Yes
-    } else {
-        Side::No
-    };
-    if close_only_side == weighted_side {
-        return false;
+    if open_hit.source == "prev_round_close" {
+        confidence -= 0.08;
     }
-    match symbol {
-        "bnb/usd" => {
-            (close_only_hit.source_count == 1
-                && close_only_hit.close_exact_sources == 0
-                && close_only_direction_margin_bps + 1e-9 < 2.5
-                && weighted_hit.source_count >= 2
-                && weighted_direction_margin_bps + 1e-9 >= 1.25)
-                || (close_only_hit.source_count == 1
-                    && close_only_hit.close_exact_sources == 0
-                    && close_only_direction_margin_bps + 1e-9 < 3.6
-                    && weighted_hit.source_count >= 2
-                    && weighted_hit.source_spread_bps + 1e-9 >= 3.5
-                    && weighted_direction_margin_bps + 1e-9 >= 0.65)
-        }
-        "eth/usd" => {
-            close_only_hit.close_exact_sources == 0
-                && weighted_hit.source_count >= 2
-                && weighted_hit.rule == LocalBoundaryCloseRule::LastBefore
-        }
-        "doge/usd" => close_only_hit.close_exact_sources == 0 && weighted_hit.source_count >= 2,
-        "sol/usd" => {
-            close_only_hit.close_exact_sources == 0
-                && weighted_hit.source_count >= 2
-                && weighted_hit.rule == LocalBoundaryCloseRule::AfterThenBefore
-        }
-        _ => false,
+    confidence = confidence.clamp(0.0, 1.0);
+
+    if confidence + 1e-9 < min_confidence {
+        warn!(
+            "⚠️ self_built_price_agg_low_confidence | symbol={} round_start_ts={} round_end_ts={} confidence={:.3} min_confidence={:.3} open_source={} close_source={} open_delta_ms={} close_delta_ms={}",
+            target_symbol,
+            round_start_ts,
+            round_end_ts,
+            confidence,
+            min_confidence,
+            open_hit.source,
+            close_hit.source,
+            open_hit.abs_delta_ms,
+            close_hit.abs_delta_ms,
+        );
+        emit_probe(
+            "low_confidence",
+            None,
+            Some(confidence),
+            Some(open_hit),
+            Some(close_hit),
+            observed_ticks,
+            observed_snapshot_ticks,
+            observed_live_ticks,
+            lagged_ticks,
+            nearest_start,
+            nearest_end,
+            first_after_start,
+            first_after_end,
+            Some(open_pick_rule),
+            Some(close_pick_rule),
+        );
+        return None;
     }
-}
 
-fn local_boundary_hybrid_prefers_filtered_close_only_over_weighted(
-    symbol: &str,
-    close_only_filter_reason: Option<&'static str>,
-    close_only_hit: &LocalCloseOnlyAggHit,
-    weighted_hit: &LocalBoundaryShadowHit,
-    rtds_open: f64,
-) -> bool {
-    let close_only_direction_margin_bps =
-        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let weighted_direction_margin_bps =
-        ((weighted_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_only_side = if close_only_hit.close_price >= rtds_open {
-        Side::Yes
-    } else {
-        Side::No
-    };
-    let weighted_side = if weighted_hit.close_price >= rtds_open {
+    let side = if close_hit.price >= open_hit.price {
         Side::Yes
     } else {
         Side::No
     };
-    if close_only_side == weighted_side {
-        return false;
-    }
-    let source_agreement = local_close_only_source_agreement(close_only_hit, rtds_open);
-    match symbol {
-        "doge/usd" => {
-            (close_only_filter_reason == Some("low_confidence")
-                && close_only_hit.source_count >= 2
-                && close_only_hit.close_exact_sources == 0
-                && source_agreement >= 1.0 - 1e-9
-                && close_only_hit.source_spread_bps <= 0.5 + 1e-9
-                && close_only_direction_margin_bps + 1e-9 >= 2.0
-                && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && weighted_hit.source_count >= 2
-                && weighted_hit.close_exact_sources == 0
-                && weighted_hit.source_spread_bps <= 0.1 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 0.5)
-                || (close_only_filter_reason == Some("preclose_near_flat")
-                    && close_only_hit.source_count >= 3
-                    && close_only_hit.close_exact_sources == 0
-                    && source_agreement >= 1.0 - 1e-9
-                    && close_only_hit.source_spread_bps <= 3.1 + 1e-9
-                    && close_only_direction_margin_bps + 1e-9 >= 1.5
-                    && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
-                    && weighted_hit.source_count >= 2
-                    && weighted_hit.close_exact_sources == 0
-                    && weighted_hit.source_spread_bps <= 0.1 + 1e-9
-                    && weighted_direction_margin_bps + 1e-9 < 0.5)
-        }
-        "hype/usd" => {
-            close_only_filter_reason == Some("low_confidence")
-                && close_only_hit.source_count >= 2
-                && close_only_hit.close_exact_sources == 0
-                && source_agreement >= 1.0 - 1e-9
-                && close_only_hit.source_spread_bps <= 3.0 + 1e-9
-                && close_only_direction_margin_bps + 1e-9 >= 2.3
-                && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
-                && weighted_hit.source_count >= 2
-                && weighted_hit.close_exact_sources == 0
-                && weighted_hit.source_spread_bps <= 1.0 + 1e-9
-                && weighted_direction_margin_bps + 1e-9 < 1.8
-        }
-        _ => false,
-    }
-}
-
-fn local_boundary_filtered_close_only_allowed_without_weighted(
-    symbol: &str,
-    close_only_filter_reason: Option<&'static str>,
-    close_only_hit: &LocalCloseOnlyAggHit,
-    round_end_ts: u64,
-    rtds_open: f64,
-) -> bool {
-    if symbol != "hype/usd"
-        || close_only_filter_reason != Some("hype_close_only_single_far_margin")
-        || close_only_hit.source_count != 1
-        || close_only_hit.close_exact_sources != 0
-    {
-        return false;
-    }
-    let close_only_single_hyperliquid = close_only_hit
-        .source_contributions
-        .first()
-        .is_some_and(|contribution| contribution.source == LocalPriceSource::Hyperliquid);
-    if !close_only_single_hyperliquid {
-        return false;
-    }
-    let direction_margin_bps =
-        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_abs_delta_ms = close_only_hit
-        .close_ts_ms
-        .abs_diff(round_end_ts.saturating_mul(1_000));
-    close_abs_delta_ms <= 500 && direction_margin_bps + 1e-9 >= 21.0 && direction_margin_bps < 24.0
-}
+    set_last_chainlink_close(&target_symbol, close_hit.ts_ms, close_hit.price);
+    info!(
+        "🧠 self_built_price_agg_ready | unix_ms={} symbol={} side={:?} confidence={:.3} open={:.6}@{} open_source={} open_exact={} open_delta_ms={} close={:.6}@{} close_source={} close_exact={} close_delta_ms={} observed_ticks={} observed_snapshot_ticks={} observed_live_ticks={} lagged_ticks={}",
+        unix_now_millis_u64(),
+        target_symbol,
+        side,
+        confidence,
+        open_hit.price,
+        open_hit.ts_ms,
+        open_hit.source,
+        open_hit.exact,
+        open_hit.abs_delta_ms,
+        close_hit.price,
+        close_hit.ts_ms,
+        close_hit.source,
+        close_hit.exact,
+        close_hit.abs_delta_ms,
+        observed_ticks,
+        observed_snapshot_ticks,
+        observed_live_ticks,
+        lagged_ticks,
+    );
+    emit_probe(
+        "ready",
+        Some(side),
+        Some(confidence),
+        Some(open_hit),
+        Some(close_hit),
+        observed_ticks,
+        observed_snapshot_ticks,
+        observed_live_ticks,
+        lagged_ticks,
+        nearest_start,
+        nearest_end,
+        first_after_start,
+        first_after_end,
+        Some(open_pick_rule),
+        Some(close_pick_rule),
+    );
 
-fn local_boundary_symbol_router_missing_filter_reason(
-    symbol: &str,
-    close_only_filter_reason: Option<&'static str>,
-    close_only_direction_margin_bps: Option<f64>,
-    close_only_hit: &LocalCloseOnlyAggHit,
-) -> Option<&'static str> {
-    if symbol == "sol/usd"
-        && close_only_filter_reason == Some("preclose_near_flat")
-        && close_only_direction_margin_bps
-            .map(|margin_bps| margin_bps + 1e-9 < 1.0)
-            .unwrap_or(false)
-        && close_only_hit.source_count >= 2
-        && close_only_hit.close_exact_sources == 0
-        && close_only_hit.source_spread_bps <= 5.0 + 1e-9
-    {
-        return Some("sol_missing_preclose_near_flat");
-    }
-    None
+    Some((
+        side,
+        open_hit.price,
+        close_hit.price,
+        open_hit.ts_ms,
+        close_hit.ts_ms,
+        open_hit.exact,
+    ))
 }
 
-fn run_local_boundary_shadow_policy(
-    symbol: &str,
-    spec: LocalBoundaryShadowPolicySpec,
+fn build_local_price_agg_boundary_source_probes(
     tapes: &HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
+    start_ms: u64,
     end_ms: u64,
-) -> LocalBoundaryShadowOutcome {
-    let mut contributions = Vec::new();
-    for source in spec.allowed_sources {
-        let Some(tape) = tapes.get(source) else {
-            continue;
-        };
-        let Some((point, raw_close_price)) = pick_local_boundary_tape_close_point(
-            tape,
-            end_ms,
-            LOCAL_BOUNDARY_POLICY_PRE_MS,
-            LOCAL_BOUNDARY_POLICY_POST_MS,
-            spec.rule,
-        ) else {
-            continue;
-        };
-        contributions.push(LocalCloseSourceContribution {
-            source: *source,
-            raw_close_price,
-            adjusted_close_price: point.price,
-            close_ts_ms: point.ts_ms,
-            close_exact: point.exact,
-        });
-    }
-
-    if contributions.len() < spec.min_sources {
-        return LocalBoundaryShadowOutcome {
-            policy_name: spec.policy_name,
-            source_subset_name: spec.source_subset_name,
-            rule: spec.rule,
-            min_sources: spec.min_sources,
-            hit: None,
-        };
-    }
-
-    let close_values = contributions
-        .iter()
-        .map(|src| src.adjusted_close_price)
-        .collect::<Vec<_>>();
-    let close_timestamps = contributions
-        .iter()
-        .map(|src| src.close_ts_ms)
-        .collect::<Vec<_>>();
-    let num = contributions
-        .iter()
-        .map(|src| {
-            local_boundary_policy_source_weight(spec.policy_name, symbol, src.source)
-                * src.adjusted_close_price
-        })
-        .sum::<f64>();
-    let den = contributions
+) -> Vec<LocalPriceAggBoundarySourceProbe> {
+    let mut items = tapes
         .iter()
-        .map(|src| local_boundary_policy_source_weight(spec.policy_name, symbol, src.source))
-        .sum::<f64>();
-    let close_price = if den > 0.0 {
-        num / den
-    } else {
-        robust_center(close_values.clone()).unwrap_or_default()
-    };
-    let close_ts_ms = median_u64(close_timestamps).unwrap_or(end_ms);
-    let source_spread_bps = spread_bps(&close_values).unwrap_or(0.0);
-    let close_exact_sources = contributions.iter().filter(|src| src.close_exact).count();
-    LocalBoundaryShadowOutcome {
-        policy_name: spec.policy_name,
-        source_subset_name: spec.source_subset_name,
-        rule: spec.rule,
-        min_sources: spec.min_sources,
-        hit: Some(LocalBoundaryShadowHit {
-            policy_name: spec.policy_name,
-            source_subset_name: spec.source_subset_name,
-            rule: spec.rule,
-            min_sources: spec.min_sources,
-            close_price,
-            close_ts_ms,
-            source_count: contributions.len(),
-            source_spread_bps,
-            close_exact_sources,
-            source_contributions: contributions,
-        }),
-    }
-}
-
-fn local_boundary_shadow_candidate_ready(
-    symbol: &str,
-    tapes: &HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
-    end_ms: u64,
-) -> bool {
-    let weighted = run_local_boundary_shadow_policy(
-        symbol,
-        local_boundary_policy_specs_weighted(symbol),
-        tapes,
-        end_ms,
-    );
-    if weighted.hit.is_some() {
-        return true;
-    }
-    local_boundary_symbol_router_fallback_policy_specs(symbol)
-        .into_iter()
-        .any(|spec| {
-            run_local_boundary_shadow_policy(symbol, spec, tapes, end_ms)
-                .hit
-                .is_some()
-        })
-}
-
-fn pick_local_source_open_point(
-    state: &LocalSourceBoundaryState,
-    open_tol_ms: u64,
-) -> Option<AggregatedPricePoint> {
-    if let Some(p) = state.open_exact {
-        Some(p)
-    } else if let Some((delta, ts_ms, px)) = state.first_after_start {
-        if delta <= open_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_first_after_open",
-            })
-        } else {
-            None
-        }
-    } else if let Some((delta, ts_ms, px)) = state.nearest_start {
-        if delta <= open_tol_ms {
-            Some(AggregatedPricePoint {
-                price: px,
-                ts_ms,
-                exact: false,
-                abs_delta_ms: delta,
-                source: "local_nearest_open",
-            })
-        } else {
-            None
-        }
-    } else {
-        None
-    }
+        .map(|(source, tape)| LocalPriceAggBoundarySourceProbe {
+            source: source.as_str().to_string(),
+            open_window_ticks: tape
+                .open_window_ticks
+                .iter()
+                .map(|(ts_ms, price)| LocalBoundaryTickProbe {
+                    ts_ms: *ts_ms,
+                    offset_ms: (*ts_ms as i64) - (start_ms as i64),
+                    price: *price,
+                })
+                .collect(),
+            close_window_ticks: tape
+                .close_window_ticks
+                .iter()
+                .map(|(ts_ms, price)| LocalBoundaryTickProbe {
+                    ts_ms: *ts_ms,
+                    offset_ms: (*ts_ms as i64) - (end_ms as i64),
+                    price: *price,
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| a.source.cmp(&b.source));
+    items
 }
 
 fn apply_local_prewarmed_open_seed(
@@ -14270,266 +10009,24 @@ fn count_matching_fractional_digits(a: f64, b: f64, max_decimals: usize) -> usiz
         .count()
 }
 
-fn local_price_agg_ingest_state(
-    states: &mut HashMap<LocalPriceSource, LocalSourceBoundaryState>,
-    source: LocalPriceSource,
-    price: f64,
-    ts_ms: u64,
-    start_ms: u64,
-    end_ms: u64,
-) {
-    let st = states.entry(source).or_default();
-    self_built_agg_ingest_tick(
-        price,
-        ts_ms,
-        start_ms,
-        end_ms,
-        &mut st.first_tick_ts_ms,
-        &mut st.open_exact,
-        &mut st.close_exact,
-        &mut st.nearest_start,
-        &mut st.nearest_end,
-        &mut st.first_after_start,
-        &mut st.first_after_end,
-    );
-}
-
-#[derive(Debug, Clone, Copy)]
-struct LocalCloseSourceContribution {
-    source: LocalPriceSource,
-    raw_close_price: f64,
-    adjusted_close_price: f64,
-    close_ts_ms: u64,
-    close_exact: bool,
-}
-
-#[derive(Debug, Clone)]
-struct LocalCloseOnlyAggHit {
-    close_price: f64,
-    close_ts_ms: u64,
-    source_count: usize,
-    source_spread_bps: f64,
-    close_exact_sources: usize,
-    source_contributions: Vec<LocalCloseSourceContribution>,
-}
-
-fn local_close_only_contribution_weight(
-    contribution: &LocalCloseSourceContribution,
-    round_end_ts: u64,
-) -> f64 {
-    let end_ms = round_end_ts.saturating_mul(1_000);
-    let abs_delta_ms = contribution.close_ts_ms.abs_diff(end_ms);
-    let exact = if contribution.close_exact {
-        local_price_agg_exact_boost()
-    } else {
-        1.0
-    };
-    local_price_agg_source_weight(contribution.source)
-        * local_price_agg_temporal_weight(abs_delta_ms)
-        * exact
-}
-
-fn local_close_only_apply_raw_contributions(
-    hit: &mut LocalCloseOnlyAggHit,
-    round_end_ts: u64,
-) -> bool {
-    let weighted = hit
-        .source_contributions
-        .iter()
-        .map(|contribution| {
-            (
-                contribution.raw_close_price,
-                local_close_only_contribution_weight(contribution, round_end_ts),
-            )
-        })
-        .collect::<Vec<_>>();
-    let Some(raw_close) = weighted_mean(&weighted) else {
-        return false;
-    };
-    let raw_values = hit
-        .source_contributions
-        .iter()
-        .map(|contribution| contribution.raw_close_price)
-        .collect::<Vec<_>>();
-    let raw_spread_bps = spread_bps(&raw_values).unwrap_or(0.0);
-    if raw_spread_bps > local_price_agg_max_source_spread_bps() + 1e-9 {
-        return false;
-    }
-    hit.close_price = raw_close;
-    hit.source_spread_bps = raw_spread_bps;
-    for contribution in &mut hit.source_contributions {
-        contribution.adjusted_close_price = contribution.raw_close_price;
-    }
-    true
-}
-
-fn local_boundary_maybe_debias_hype_close_only(
-    symbol: &str,
-    hit: &mut LocalCloseOnlyAggHit,
-    round_end_ts: u64,
-    rtds_open: f64,
-) {
-    if symbol != "hype/usd" || !rtds_open.is_finite() || rtds_open <= 0.0 {
-        return;
-    }
-    if hit.source_count == 1 && hit.close_exact_sources == 0 {
-        let Some(contribution) = hit.source_contributions.first() else {
-            return;
-        };
-        if contribution.source != LocalPriceSource::Hyperliquid {
-            return;
-        }
-        let adjusted = hit.close_price;
-        let raw = contribution.raw_close_price;
-        if !adjusted.is_finite() || !raw.is_finite() || adjusted < rtds_open || raw < rtds_open {
-            return;
-        }
-        let adjusted_margin_bps =
-            ((adjusted - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-        let raw_margin_bps = ((raw - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-        if adjusted_margin_bps + 1e-9 >= 10.0 && raw_margin_bps + 1e-9 >= 7.0 {
-            let _ = local_close_only_apply_raw_contributions(hit, round_end_ts);
-        }
-        return;
-    }
-
-    if hit.source_count < 2 || hit.close_exact_sources != 0 {
-        return;
-    }
-    let end_ms = round_end_ts.saturating_mul(1_000);
-    let all_preclose = hit
-        .source_contributions
-        .iter()
-        .all(|contribution| contribution.close_ts_ms < end_ms);
-    let all_postclose = hit
-        .source_contributions
-        .iter()
-        .all(|contribution| contribution.close_ts_ms > end_ms);
-    if !all_preclose && !all_postclose {
-        return;
-    }
-    let raw_weighted = hit
-        .source_contributions
-        .iter()
-        .map(|contribution| {
-            (
-                contribution.raw_close_price,
-                local_close_only_contribution_weight(contribution, round_end_ts),
-            )
-        })
-        .collect::<Vec<_>>();
-    let Some(raw_close) = weighted_mean(&raw_weighted) else {
-        return;
-    };
-    let adjusted = hit.close_price;
-    if !adjusted.is_finite()
-        || !raw_close.is_finite()
-        || adjusted >= rtds_open
-        || raw_close >= rtds_open
-    {
-        return;
-    }
-    let adjusted_margin_bps =
-        ((adjusted - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    if (all_preclose && (2.0..12.0).contains(&adjusted_margin_bps))
-        || (all_postclose && (4.0..12.0).contains(&adjusted_margin_bps))
-    {
-        let _ = local_close_only_apply_raw_contributions(hit, round_end_ts);
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum LocalBoundaryCloseRule {
-    LastBefore,
-    NearestAbs,
-    AfterThenBefore,
-}
-
-impl LocalBoundaryCloseRule {
-    fn as_str(self) -> &'static str {
-        match self {
-            Self::LastBefore => "last_before",
-            Self::NearestAbs => "nearest_abs",
-            Self::AfterThenBefore => "after_then_before",
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct LocalBoundaryShadowPolicySpec {
-    policy_name: &'static str,
-    source_subset_name: &'static str,
-    rule: LocalBoundaryCloseRule,
-    min_sources: usize,
-    allowed_sources: &'static [LocalPriceSource],
-}
-
-#[derive(Debug, Clone)]
-struct LocalBoundaryShadowHit {
-    policy_name: &'static str,
-    source_subset_name: &'static str,
-    rule: LocalBoundaryCloseRule,
-    min_sources: usize,
-    close_price: f64,
-    close_ts_ms: u64,
-    source_count: usize,
-    source_spread_bps: f64,
-    close_exact_sources: usize,
-    source_contributions: Vec<LocalCloseSourceContribution>,
-}
-
 #[derive(Debug, Clone)]
-struct LocalBoundaryShadowOutcome {
-    policy_name: &'static str,
-    source_subset_name: &'static str,
-    rule: LocalBoundaryCloseRule,
-    min_sources: usize,
-    hit: Option<LocalBoundaryShadowHit>,
-}
-
-#[derive(Debug, Clone, Deserialize, Default)]
-struct LocalAggUncertaintyGateConfig {
-    min_samples: usize,
-    min_margin_bps: f64,
-    max_train_quantile_bps: f64,
-    safety_bps: f64,
-    max_side_rate: f64,
-    max_source_spread_bps: f64,
-    max_round_max_margin_bps: f64,
-    #[serde(default)]
-    rescue_min_samples: usize,
-    #[serde(default)]
-    rescue_max_train_max_bps: f64,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-struct LocalAggUncertaintyGateBucketFile {
-    level: String,
-    key: Vec<String>,
-    n: usize,
-    side_errors: usize,
-    side_rate: f64,
-    q_bps: f64,
-    q95_bps: f64,
-    q99_bps: f64,
-    mean_bps: f64,
-    max_bps: f64,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-struct LocalAggUncertaintyGateFile {
-    model: String,
-    config: LocalAggUncertaintyGateConfig,
-    buckets: Vec<LocalAggUncertaintyGateBucketFile>,
-    #[serde(default)]
-    rescue_buckets: Vec<LocalAggUncertaintyGateBucketFile>,
+struct LocalAggUncertaintyGateModel {
+    config: UncertaintyGateConfig,
+    buckets: HashMap<String, UncertaintyGateBucket>,
+    rescue_buckets: HashMap<String, UncertaintyGateBucket>,
+    /// File header (model name, config, unknown fields) written back on persist.
+    file_meta: UncertaintyGateFile,
+    /// `None` in frozen mode.
+    online: Option<LocalAggUncertaintyGateOnline>,
 }
 
 #[derive(Debug, Clone)]
-struct LocalAggUncertaintyGateModel {
-    config: LocalAggUncertaintyGateConfig,
-    buckets: HashMap<String, LocalAggUncertaintyGateBucketFile>,
-    rescue_buckets: HashMap<String, LocalAggUncertaintyGateBucketFile>,
+struct LocalAggUncertaintyGateOnline {
+    path: PathBuf,
+    base_path: PathBuf,
+    base_modified_ms: u64,
+    rounds: u64,
+    window: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -14579,6 +10076,8 @@ struct LocalAggUncertaintyGateCandidate {
 struct LocalAggUncertaintyGateRoundState {
     candidates: HashMap<String, LocalAggUncertaintyGateCandidate>,
     finalized: bool,
+    /// Symbols already folded into the online model for this round.
+    trained: HashSet<String>,
 }
 
 static LOCAL_AGG_UNCERTAINTY_GATE_ROUNDS: OnceLock<
@@ -14602,7 +10101,7 @@ impl LocalAggUncertaintyGateModel {
                 return None;
             }
         };
-        let file = match serde_json::from_str::<LocalAggUncertaintyGateFile>(&raw) {
+        let file = match serde_json::from_str::<UncertaintyGateFile>(&raw) {
             Ok(file) => file,
             Err(err) => {
                 warn!(
@@ -14613,7 +10112,11 @@ impl LocalAggUncertaintyGateModel {
                 return None;
             }
         };
-        if file.model != "local_agg_uncertainty_gate_v1" {
+        Self::from_parsed(file, &path)
+    }
+
+    fn from_parsed(mut file: UncertaintyGateFile, path: &Path) -> Option<Self> {
+        if file.model != UNCERTAINTY_GATE_MODEL_NAME {
             warn!(
                 "⚠️ local_price_agg_uncertainty_gate_model_unsupported | path={} model={}",
                 path.display(),
@@ -14621,8 +10124,7 @@ impl LocalAggUncertaintyGateModel {
             );
             return None;
         }
-        let buckets = file
-            .buckets
+        let buckets = std::mem::take(&mut file.buckets)
             .into_iter()
             .map(|bucket| {
                 (
@@ -14631,8 +10133,7 @@ impl LocalAggUncertaintyGateModel {
                 )
             })
             .collect::<HashMap<_, _>>();
-        let rescue_buckets = file
-            .rescue_buckets
+        let rescue_buckets = std::mem::take(&mut file.rescue_buckets)
             .into_iter()
             .map(|bucket| {
                 (
@@ -14656,16 +10157,67 @@ impl LocalAggUncertaintyGateModel {
             file.config.rescue_max_train_max_bps,
         );
         Some(Self {
-            config: file.config,
+            config: file.config.clone(),
             buckets,
             rescue_buckets,
+            file_meta: file,
+            online: None,
         })
     }
 
+    /// Folds finalized candidates (close error vs the RTDS close) into every
+    /// bucket level they map to, then queues the online file for the writer
+    /// thread so the model lock is never held across disk I/O.
+    fn train(&mut self, candidates: &[LocalAggUncertaintyGateCandidate]) {
+        let Some(online) = self.online.as_mut() else {
+            return;
+        };
+        let quantile = self.config.quantile;
+        let window = online.window;
+        for candidate in candidates {
+            let side_error = !candidate.side_match;
+            for (level, key) in local_agg_uncertainty_gate_key_levels(candidate) {
+                self.buckets
+                    .entry(local_agg_uncertainty_gate_bucket_key(level, &key))
+                    .or_insert_with(|| UncertaintyGateBucket::new(level, key))
+                    .record(candidate.close_diff_bps, side_error, quantile, window);
+            }
+            let level = "rescue_shape";
+            let key = local_agg_uncertainty_gate_rescue_key(candidate);
+            self.rescue_buckets
+                .entry(local_agg_uncertainty_gate_bucket_key(level, &key))
+                .or_insert_with(|| UncertaintyGateBucket::new(level, key))
+                .record(candidate.close_diff_bps, side_error, quantile, window);
+        }
+        online.rounds = online.rounds.saturating_add(1);
+        let mut file = self.file_meta.clone();
+        file.buckets = self.buckets.values().cloned().collect();
+        file.buckets
+            .sort_by(|a, b| (&a.level, &a.key).cmp(&(&b.level, &b.key)));
+        file.rescue_buckets = self.rescue_buckets.values().cloned().collect();
+        file.rescue_buckets
+            .sort_by(|a, b| (&a.level, &a.key).cmp(&(&b.level, &b.key)));
+        file.online = Some(OnlineModelMeta {
+            base_path: online.base_path.clone(),
+            base_modified_ms: online.base_modified_ms,
+            rounds: online.rounds,
+            updated_ms: unix_now_millis_u64(),
+        });
+        local_agg_uncertainty_gate_writer().save(online.path.clone(), file);
+        info!(
+            "🧪 local_price_agg_uncertainty_gate_model_trained | path={} rounds={} candidates={} buckets={} rescue_buckets={}",
+            online.path.display(),
+            online.rounds,
+            candidates.len(),
+            self.buckets.len(),
+            self.rescue_buckets.len(),
+        );
+    }
+
     fn choose_stats(
         &self,
         candidate: &LocalAggUncertaintyGateCandidate,
-    ) -> Option<(&'static str, &LocalAggUncertaintyGateBucketFile)> {
+    ) -> Option<(&'static str, &UncertaintyGateBucket)> {
         for (level, key) in local_agg_uncertainty_gate_key_levels(candidate) {
             let lookup = local_agg_uncertainty_gate_bucket_key(level, &key);
             if let Some(stats) = self.buckets.get(&lookup) {
@@ -14680,7 +10232,7 @@ impl LocalAggUncertaintyGateModel {
     fn choose_rescue_stats(
         &self,
         candidate: &LocalAggUncertaintyGateCandidate,
-    ) -> Option<(&'static str, &LocalAggUncertaintyGateBucketFile)> {
+    ) -> Option<(&'static str, &UncertaintyGateBucket)> {
         if self.config.rescue_min_samples == 0 || self.config.rescue_max_train_max_bps <= 0.0 {
             return None;
         }
@@ -14763,7 +10315,7 @@ impl LocalAggUncertaintyGateDecision {
         accepted: bool,
         reason: &'static str,
         key_level: &'static str,
-        stats: &LocalAggUncertaintyGateBucketFile,
+        stats: &UncertaintyGateBucket,
         safety_bps: f64,
     ) -> Self {
         Self::from_stats_with_required_margin(
@@ -14779,7 +10331,7 @@ impl LocalAggUncertaintyGateDecision {
         accepted: bool,
         reason: &'static str,
         key_level: &'static str,
-        stats: &LocalAggUncertaintyGateBucketFile,
+        stats: &UncertaintyGateBucket,
         required_margin_bps: f64,
     ) -> Self {
         Self {
@@ -14798,8 +10350,13 @@ impl LocalAggUncertaintyGateDecision {
     }
 }
 
-fn local_agg_uncertainty_gate_model() -> Option<&'static LocalAggUncertaintyGateModel> {
-    static MODEL: OnceLock<Option<LocalAggUncertaintyGateModel>> = OnceLock::new();
+fn local_agg_uncertainty_gate_writer() -> &'static UncertaintyGateWriter {
+    static WRITER: OnceLock<UncertaintyGateWriter> = OnceLock::new();
+    WRITER.get_or_init(UncertaintyGateWriter::spawn)
+}
+
+fn local_agg_uncertainty_gate_model() -> Option<&'static Mutex<LocalAggUncertaintyGateModel>> {
+    static MODEL: OnceLock<Option<Mutex<LocalAggUncertaintyGateModel>>> = OnceLock::new();
     MODEL
         .get_or_init(|| {
             if !local_price_agg_uncertainty_gate_enabled() {
@@ -14809,7 +10366,35 @@ fn local_agg_uncertainty_gate_model() -> Option<&'static LocalAggUncertaintyGate
                 warn!("⚠️ local_price_agg_uncertainty_gate_model_missing | reason=no_model_path");
                 return None;
             };
-            LocalAggUncertaintyGateModel::from_file(path)
+            if local_price_agg_uncertainty_gate_frozen() {
+                info!(
+                    "🧊 local_price_agg_uncertainty_gate_frozen | path={}",
+                    path.display()
+                );
+                return LocalAggUncertaintyGateModel::from_file(path).map(Mutex::new);
+            }
+            let online_path = local_price_agg_uncertainty_gate_online_model_path(&path);
+            // Resume online stats only if they were trained on this exact base file;
+            // an offline refit replaces the base and restarts online training.
+            let resumed = UncertaintyGateFile::load(&online_path)
+                .ok()
+                .filter(|file| file.continues_base(&path))
+                .and_then(|file| LocalAggUncertaintyGateModel::from_parsed(file, &online_path));
+            let rounds = resumed
+                .as_ref()
+                .and_then(|model| model.file_meta.online.as_ref())
+                .map(|meta| meta.rounds)
+                .unwrap_or(0);
+            let mut model =
+                resumed.or_else(|| LocalAggUncertaintyGateModel::from_file(path.clone()))?;
+            model.online = Some(LocalAggUncertaintyGateOnline {
+                path: online_path,
+                base_modified_ms: file_modified_ms(&path).unwrap_or(0),
+                base_path: path,
+                rounds,
+                window: local_price_agg_uncertainty_gate_online_window(),
+            });
+            Some(Mutex::new(model))
         })
         .as_ref()
 }
@@ -15231,6 +10816,7 @@ fn local_agg_uncertainty_gate_finalize_round(round_end_ts: u64) {
         match guard.get_mut(&round_end_ts) {
             Some(state) if !state.finalized => {
                 state.finalized = true;
+                state.trained.extend(state.candidates.keys().cloned());
                 let mut candidates = state.candidates.values().cloned().collect::<Vec<_>>();
                 candidates.sort_by(|a, b| a.symbol.cmp(&b.symbol));
                 let round_max = candidates
@@ -15251,15 +10837,18 @@ fn local_agg_uncertainty_gate_finalize_round(round_end_ts: u64) {
     }) else {
         return;
     };
-    for candidate in candidates {
+    let mut model = model.lock().expect("local agg gate model mutex poisoned");
+    for candidate in &candidates {
         local_agg_uncertainty_gate_emit_candidate(
-            model,
-            &candidate,
+            &model,
+            candidate,
             "final",
             observed_candidates,
             Some(round_max_margin_bps),
         );
     }
+    // Decisions above use the pre-round stats; learn from the round afterwards.
+    model.train(&candidates);
 }
 
 fn local_agg_uncertainty_gate_observe_candidate(candidate: LocalAggUncertaintyGateCandidate) {
@@ -15271,6 +10860,7 @@ fn local_agg_uncertainty_gate_observe_candidate(candidate: LocalAggUncertaintyGa
     };
     let finalize_ms = local_price_agg_uncertainty_gate_finalize_ms();
     let mut spawn_finalize = false;
+    let mut train_late = false;
     let mut final_update_candidates = Vec::new();
     let (round_max_margin_bps, observed_candidates) = {
         let rounds = LOCAL_AGG_UNCERTAINTY_GATE_ROUNDS.get_or_init(|| Mutex::new(HashMap::new()));
@@ -15298,38 +10888,44 @@ fn local_agg_uncertainty_gate_observe_candidate(candidate: LocalAggUncertaintyGa
             });
         let observed = state.candidates.len();
         if state.finalized {
+            train_late = state.trained.insert(candidate.symbol.clone());
             final_update_candidates = state.candidates.values().cloned().collect::<Vec<_>>();
             final_update_candidates.sort_by(|a, b| a.symbol.cmp(&b.symbol));
         }
         (round_max, observed)
     };
 
+    let mut model_guard = model.lock().expect("local agg gate model mutex poisoned");
     local_agg_uncertainty_gate_emit_candidate(
-        model,
+        &model_guard,
         &candidate,
         "preliminary",
         observed_candidates,
         Some(round_max_margin_bps),
     );
 
-    if spawn_finalize {
-        tokio::spawn(async move {
-            sleep(Duration::from_millis(finalize_ms)).await;
-            local_agg_uncertainty_gate_finalize_round(candidate.round_end_ts);
-        });
-    }
-
     if !final_update_candidates.is_empty() {
-        for candidate in final_update_candidates {
+        for candidate in &final_update_candidates {
             local_agg_uncertainty_gate_emit_candidate(
-                model,
-                &candidate,
+                &model_guard,
+                candidate,
                 "final_update",
                 observed_candidates,
                 Some(round_max_margin_bps),
             );
         }
     }
+    if train_late {
+        model_guard.train(std::slice::from_ref(&candidate));
+    }
+    drop(model_guard);
+
+    if spawn_finalize {
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(finalize_ms)).await;
+            local_agg_uncertainty_gate_finalize_round(candidate.round_end_ts);
+        });
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -15338,130 +10934,6 @@ struct LocalCloseAggCompareResult {
     boundary_shadow_outcomes: Vec<LocalBoundaryShadowOutcome>,
 }
 
-fn local_boundary_maybe_equalize_xrp_binance_coinbase_fast_no(
-    symbol: &str,
-    hit: &mut LocalBoundaryShadowHit,
-    round_end_ts: u64,
-    rtds_open: f64,
-) {
-    if symbol != "xrp/usd"
-        || hit.policy_name != "boundary_weighted"
-        || hit.source_subset_name != "only_binance_coinbase"
-        || hit.rule != LocalBoundaryCloseRule::NearestAbs
-        || hit.source_count != 2
-        || hit.close_exact_sources != 0
-        || !rtds_open.is_finite()
-        || rtds_open <= 0.0
-        || hit.close_price >= rtds_open
-        || hit.source_spread_bps < 4.0
-    {
-        return;
-    }
-    let direction_margin_bps =
-        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
-    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
-    if direction_margin_bps < 6.0 || close_abs_delta_ms > 150 {
-        return;
-    }
-    let Some(binance) = hit
-        .source_contributions
-        .iter()
-        .find(|contribution| contribution.source == LocalPriceSource::Binance)
-    else {
-        return;
-    };
-    let Some(coinbase) = hit
-        .source_contributions
-        .iter()
-        .find(|contribution| contribution.source == LocalPriceSource::Coinbase)
-    else {
-        return;
-    };
-    let end_ms = round_end_ts.saturating_mul(1_000);
-    if binance.close_ts_ms.abs_diff(end_ms) >= coinbase.close_ts_ms.abs_diff(end_ms)
-        || binance.adjusted_close_price <= coinbase.adjusted_close_price
-    {
-        return;
-    }
-    let equalized = (binance.adjusted_close_price + coinbase.adjusted_close_price) / 2.0;
-    if equalized.is_finite() && equalized > 0.0 {
-        hit.close_price = equalized;
-    }
-}
-
-const LOCAL_BOUNDARY_SOURCES_FULL: &[LocalPriceSource] = &[
-    LocalPriceSource::Binance,
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Coinbase,
-    LocalPriceSource::Hyperliquid,
-];
-const LOCAL_BOUNDARY_SOURCES_DROP_BINANCE: &[LocalPriceSource] = &[
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Coinbase,
-    LocalPriceSource::Hyperliquid,
-];
-const LOCAL_BOUNDARY_SOURCES_DROP_BYBIT: &[LocalPriceSource] = &[
-    LocalPriceSource::Binance,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Coinbase,
-    LocalPriceSource::Hyperliquid,
-];
-const LOCAL_BOUNDARY_SOURCES_DROP_OKX: &[LocalPriceSource] = &[
-    LocalPriceSource::Binance,
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Coinbase,
-    LocalPriceSource::Hyperliquid,
-];
-const LOCAL_BOUNDARY_SOURCES_DROP_COINBASE: &[LocalPriceSource] = &[
-    LocalPriceSource::Binance,
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Hyperliquid,
-];
-const LOCAL_BOUNDARY_SOURCES_DROP_HYPERLIQUID: &[LocalPriceSource] = &[
-    LocalPriceSource::Binance,
-    LocalPriceSource::Bybit,
-    LocalPriceSource::Okx,
-    LocalPriceSource::Coinbase,
-];
-const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE: &[LocalPriceSource] =
-    &[LocalPriceSource::Binance, LocalPriceSource::Coinbase];
-const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_HYPERLIQUID: &[LocalPriceSource] =
-    &[LocalPriceSource::Binance, LocalPriceSource::Hyperliquid];
-const LOCAL_BOUNDARY_SOURCES_ONLY_BYBIT_COINBASE: &[LocalPriceSource] =
-    &[LocalPriceSource::Bybit, LocalPriceSource::Coinbase];
-const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE: &[LocalPriceSource] = &[LocalPriceSource::Binance];
-const LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE: &[LocalPriceSource] = &[LocalPriceSource::Coinbase];
-const LOCAL_BOUNDARY_SOURCES_ONLY_HYPERLIQUID: &[LocalPriceSource] =
-    &[LocalPriceSource::Hyperliquid];
-const LOCAL_BOUNDARY_SOURCES_ONLY_OKX: &[LocalPriceSource] = &[LocalPriceSource::Okx];
-const LOCAL_BOUNDARY_SOURCES_ONLY_OKX_COINBASE: &[LocalPriceSource] =
-    &[LocalPriceSource::Okx, LocalPriceSource::Coinbase];
-
-const LOCAL_BOUNDARY_POLICY_PRE_MS: u64 = 5_000;
-const LOCAL_BOUNDARY_POLICY_POST_MS: u64 = 250;
-
-fn local_price_agg_temporal_weight(abs_delta_ms: u64) -> f64 {
-    let decay = local_price_agg_close_time_decay_ms();
-    if !decay.is_finite() || decay <= 0.0 {
-        return 1.0;
-    }
-    1.0 / (1.0 + (abs_delta_ms as f64 / decay))
-}
-
-fn local_price_agg_point_weight(source: LocalPriceSource, point: &AggregatedPricePoint) -> f64 {
-    let base = local_price_agg_source_weight(source);
-    let temporal = local_price_agg_temporal_weight(point.abs_delta_ms);
-    let exact = if point.exact {
-        local_price_agg_exact_boost()
-    } else {
-        1.0
-    };
-    base * temporal * exact
-}
-
 fn local_price_agg_learn_source_biases_from_rtds(
     symbol: &str,
     rtds_close: f64,
@@ -15470,31 +10942,23 @@ fn local_price_agg_learn_source_biases_from_rtds(
     if !local_price_agg_bias_learning_enabled() {
         return;
     }
-    if !rtds_close.is_finite() || rtds_close <= 0.0 {
-        return;
-    }
-    for point in points {
-        if !point.raw_close_price.is_finite() || point.raw_close_price <= 0.0 {
+    let book = local_price_agg_bias_book();
+    for update in book.learn_from_reference(symbol, rtds_close, points) {
+        let Some(point) = points.iter().find(|p| p.source == update.source) else {
             continue;
-        }
-        let residual_bps =
-            ((rtds_close - point.raw_close_price) / rtds_close.abs().max(1e-12)) * 10_000.0;
-        if let Some(new_bias_bps) =
-            local_price_agg_update_source_bias_bps(symbol, point.source, residual_bps)
-        {
-            info!(
-                "🧪 local_price_agg_bias_update | symbol={} source={} rtds_close={:.15} raw_close={:.15} adjusted_close={:.15} residual_bps={:.6} new_bias_bps={:.6} close_ts_ms={} close_exact={}",
-                symbol,
-                point.source.as_str(),
-                rtds_close,
-                point.raw_close_price,
-                point.adjusted_close_price,
-                residual_bps,
-                new_bias_bps,
-                point.close_ts_ms,
-                point.close_exact,
-            );
-        }
+        };
+        info!(
+            "🧪 local_price_agg_bias_update | symbol={} source={} rtds_close={:.15} raw_close={:.15} adjusted_close={:.15} residual_bps={:.6} new_bias_bps={:.6} close_ts_ms={} close_exact={}",
+            symbol,
+            point.source.as_str(),
+            rtds_close,
+            point.raw_close_price,
+            point.adjusted_close_price,
+            update.residual_bps,
+            update.new_bias_bps,
+            point.close_ts_ms,
+            point.close_exact,
+        );
     }
 }
 
@@ -15521,14 +10985,19 @@ async fn run_local_price_close_aggregator(
 
     let start_ms = round_start_ts.saturating_mul(1_000);
     let end_ms = round_end_ts.saturating_mul(1_000);
-    let close_tol_ms = local_price_agg_close_tolerance_ms();
-    let min_sources = local_price_agg_min_sources();
-    let max_source_spread_bps = local_price_agg_max_source_spread_bps();
-    let boundary_window_ms = local_price_agg_boundary_window_ms();
+    let agg_cfg = price_agg_config();
+    let close_tol_ms = agg_cfg.close_tolerance_ms;
+    let min_sources = agg_cfg.min_sources;
+    let boundary_window_ms = agg_cfg.boundary_window_ms;
 
     let mut states: HashMap<LocalPriceSource, LocalSourceBoundaryState> = HashMap::new();
     let mut tapes: HashMap<LocalPriceSource, LocalSourceBoundaryTapeState> = HashMap::new();
-    for (price, ts_ms, source) in hub.snapshot_recent_ticks(&target_symbol) {
+    for SourceTick {
+        price,
+        ts_ms,
+        source,
+    } in hub.snapshot_recent_ticks(&target_symbol)
+    {
         local_price_agg_ingest_state(&mut states, source, price, ts_ms, start_ms, end_ms);
         local_price_agg_collect_boundary_tape_tick(
             &mut tapes,
@@ -15554,7 +11023,11 @@ async fn run_local_price_close_aggregator(
             while unix_now_millis_u64() <= hard_deadline_ms {
                 let next = tokio::time::timeout(Duration::from_millis(50), rx.recv()).await;
                 match next {
-                    Ok(Ok((price, ts_ms, source))) => {
+                    Ok(Ok(SourceTick {
+                        price,
+                        ts_ms,
+                        source,
+                    })) => {
                         local_price_agg_ingest_state(
                             &mut states,
                             source,
@@ -15581,7 +11054,12 @@ async fn run_local_price_close_aggregator(
                 let now_ms = unix_now_millis_u64();
                 let close_ready = now_ms >= end_ms && close_ready_sources(&states) >= min_sources;
                 let boundary_ready = now_ms >= boundary_ready_after_ms
-                    && local_boundary_shadow_candidate_ready(&target_symbol, &tapes, end_ms);
+                    && local_boundary_shadow_candidate_ready(
+                        &target_symbol,
+                        &tapes,
+                        end_ms,
+                        agg_cfg,
+                    );
                 if close_ready || boundary_ready {
                     break;
                 }
@@ -15589,44 +11067,28 @@ async fn run_local_price_close_aggregator(
         }
     }
 
-    let mut source_hits: Vec<(LocalPriceSource, AggregatedPricePoint, f64)> = Vec::new();
-    for (source, st) in &states {
-        if let Some(mut close) = pick_local_source_close_point(st, close_tol_ms) {
-            let raw_close_price = close.price;
-            close.price = local_price_agg_apply_source_bias(&target_symbol, *source, close.price);
-            source_hits.push((*source, close, raw_close_price));
-        }
-    }
-    let source_contributions = source_hits
-        .iter()
-        .map(
-            |(source, close, raw_close_price)| LocalCloseSourceContribution {
-                source: *source,
-                raw_close_price: *raw_close_price,
-                adjusted_close_price: close.price,
-                close_ts_ms: close.ts_ms,
-                close_exact: close.exact,
-            },
-        )
-        .collect::<Vec<_>>();
-    let source_points = source_hits
+    let source_contributions = local_close_source_contributions(
+        &target_symbol,
+        &states,
+        close_tol_ms,
+        Some(local_price_agg_bias_book()),
+    );
+    let source_points = source_contributions
         .iter()
-        .map(
-            |(source, close, _raw_close_price)| LocalPriceAggSourcePointProbe {
-                source: source.as_str().to_string(),
-                open_price: None,
-                open_ts_ms: None,
-                open_exact: None,
-                raw_close_price: Some(*_raw_close_price),
-                close_price: Some(close.price),
-                close_ts_ms: Some(close.ts_ms),
-                close_exact: Some(close.exact),
-                close_abs_delta_ms: Some(close.abs_delta_ms),
-                close_pick_kind: Some(close.source.to_string()),
-            },
-        )
+        .map(|c| LocalPriceAggSourcePointProbe {
+            source: c.source.as_str().to_string(),
+            open_price: None,
+            open_ts_ms: None,
+            open_exact: None,
+            raw_close_price: Some(c.raw_close_price),
+            close_price: Some(c.adjusted_close_price),
+            close_ts_ms: Some(c.close_ts_ms),
+            close_exact: Some(c.close_exact),
+            close_abs_delta_ms: Some(c.close_abs_delta_ms),
+            close_pick_kind: Some(c.close_pick.to_string()),
+        })
         .collect::<Vec<_>>();
-    let status = if source_hits.len() >= min_sources {
+    let status = if source_contributions.len() >= min_sources {
         "ready"
     } else {
         "insufficient_sources"
@@ -15650,73 +11112,47 @@ async fn run_local_price_close_aggregator(
         status: status.to_string(),
         boundary_window_ms,
         source_tapes: build_local_price_agg_boundary_source_probes(&tapes, start_ms, end_ms),
-    });
-
-    let mut boundary_shadow_outcomes = vec![run_local_boundary_shadow_policy(
-        &target_symbol,
-        local_boundary_policy_specs_weighted(&target_symbol),
-        &tapes,
-        end_ms,
-    )];
-    for spec in local_boundary_symbol_router_fallback_policy_specs(&target_symbol) {
-        boundary_shadow_outcomes.push(run_local_boundary_shadow_policy(
-            &target_symbol,
-            spec,
-            &tapes,
-            end_ms,
-        ));
-    }
-
-    if source_hits.len() < min_sources {
-        return LocalCloseAggCompareResult {
-            base_hit: None,
-            boundary_shadow_outcomes,
-        };
-    }
-
-    let close_values: Vec<f64> = source_hits.iter().map(|(_, c, _)| c.price).collect();
-    let close_timestamps: Vec<u64> = source_hits.iter().map(|(_, c, _)| c.ts_ms).collect();
-    let close_exact_sources = source_hits.iter().filter(|(_, c, _)| c.exact).count();
-    let close_weighted = source_hits
-        .iter()
-        .map(|(source, close, _)| (close.price, local_price_agg_point_weight(*source, close)))
-        .collect::<Vec<_>>();
-    let Some(close_med) =
-        weighted_mean(&close_weighted).or_else(|| robust_center(close_values.clone()))
-    else {
-        return LocalCloseAggCompareResult {
-            base_hit: None,
-            boundary_shadow_outcomes,
-        };
-    };
-    let close_ts_med = median_u64(close_timestamps).unwrap_or(end_ms);
-    let source_spread_bps = spread_bps(&close_values).unwrap_or(0.0);
-
-    if source_spread_bps > max_source_spread_bps + 1e-9 {
-        warn!(
-            "⚠️ local_price_agg_close_only_spread_reject | symbol={} round_start_ts={} round_end_ts={} spread_bps={:.3} max_bps={:.3} source_count={}",
-            target_symbol,
-            round_start_ts,
-            round_end_ts,
-            source_spread_bps,
-            max_source_spread_bps,
-            source_hits.len(),
-        );
-        return LocalCloseAggCompareResult {
-            base_hit: None,
-            boundary_shadow_outcomes,
-        };
+    });
+
+    let mut boundary_shadow_outcomes = vec![run_local_boundary_shadow_policy(
+        &target_symbol,
+        local_boundary_policy_specs_weighted(&target_symbol),
+        &tapes,
+        end_ms,
+        agg_cfg,
+    )];
+    for spec in local_boundary_symbol_router_fallback_policy_specs(&target_symbol) {
+        boundary_shadow_outcomes.push(run_local_boundary_shadow_policy(
+            &target_symbol,
+            spec,
+            &tapes,
+            end_ms,
+            agg_cfg,
+        ));
     }
 
+    let source_count = source_contributions.len();
+    let base_hit = match aggregate_local_close(source_contributions, end_ms, agg_cfg) {
+        Ok(hit) => Some(hit),
+        Err(CloseAggReject::SpreadTooWide {
+            spread_bps,
+            max_bps,
+        }) => {
+            warn!(
+                "⚠️ local_price_agg_close_only_spread_reject | symbol={} round_start_ts={} round_end_ts={} spread_bps={:.3} max_bps={:.3} source_count={}",
+                target_symbol,
+                round_start_ts,
+                round_end_ts,
+                spread_bps,
+                max_bps,
+                source_count,
+            );
+            None
+        }
+        Err(_) => None,
+    };
     LocalCloseAggCompareResult {
-        base_hit: Some(LocalCloseOnlyAggHit {
-            close_price: close_med,
-            close_ts_ms: close_ts_med,
-            source_count: source_hits.len(),
-            source_spread_bps,
-            close_exact_sources,
-            source_contributions,
-        }),
+        base_hit,
         boundary_shadow_outcomes,
     }
 }
@@ -15738,14 +11174,14 @@ async fn run_local_price_aggregator(
 
     let start_ms = round_start_ts.saturating_mul(1_000);
     let end_ms = round_end_ts.saturating_mul(1_000);
-    let open_tol_ms = local_price_agg_open_tolerance_ms();
-    let close_tol_ms = local_price_agg_close_tolerance_ms();
-    let min_confidence = local_price_agg_min_confidence();
-    let min_sources = local_price_agg_min_sources();
-    let max_source_spread_bps = local_price_agg_max_source_spread_bps();
-    let boundary_window_ms = local_price_agg_boundary_window_ms();
-    let single_source_min_direction_margin_bps =
-        local_price_agg_single_source_min_direction_margin_bps();
+    let agg_cfg = price_agg_config();
+    let open_tol_ms = agg_cfg.open_tolerance_ms;
+    let close_tol_ms = agg_cfg.close_tolerance_ms;
+    let min_confidence = agg_cfg.min_confidence;
+    let min_sources = agg_cfg.min_sources;
+    let max_source_spread_bps = agg_cfg.max_source_spread_bps;
+    let boundary_window_ms = agg_cfg.boundary_window_ms;
+    let single_source_min_direction_margin_bps = agg_cfg.single_source_min_direction_margin_bps;
     let relief_min_confidence = LOCAL_PRICE_AGG_RELIEF_MIN_CONFIDENCE_DEFAULT;
     let relief_min_direction_margin_bps = LOCAL_PRICE_AGG_RELIEF_MIN_DIRECTION_MARGIN_BPS_DEFAULT;
     let relief_max_source_spread_bps = LOCAL_PRICE_AGG_RELIEF_MAX_SOURCE_SPREAD_BPS_DEFAULT;
@@ -15757,7 +11193,12 @@ async fn run_local_price_aggregator(
     let mut observed_live_ticks: u64 = 0;
     let mut lagged_ticks: u64 = 0;
 
-    for (price, ts_ms, source) in hub.snapshot_recent_ticks(&target_symbol) {
+    for SourceTick {
+        price,
+        ts_ms,
+        source,
+    } in hub.snapshot_recent_ticks(&target_symbol)
+    {
         observed_ticks = observed_ticks.saturating_add(1);
         observed_snapshot_ticks = observed_snapshot_ticks.saturating_add(1);
         local_price_agg_ingest_state(&mut states, source, price, ts_ms, start_ms, end_ms);
@@ -15785,7 +11226,11 @@ async fn run_local_price_aggregator(
             while unix_now_millis_u64() <= hard_deadline_ms {
                 let next = tokio::time::timeout(Duration::from_millis(120), rx.recv()).await;
                 match next {
-                    Ok(Ok((price, ts_ms, source))) => {
+                    Ok(Ok(SourceTick {
+                        price,
+                        ts_ms,
+                        source,
+                    })) => {
                         observed_ticks = observed_ticks.saturating_add(1);
                         observed_live_ticks = observed_live_ticks.saturating_add(1);
                         local_price_agg_ingest_state(
@@ -15832,8 +11277,8 @@ async fn run_local_price_aggregator(
         {
             let raw_open = open.price;
             let raw_close = close.price;
-            open.price = local_price_agg_apply_source_bias(&target_symbol, *source, open.price);
-            close.price = local_price_agg_apply_source_bias(&target_symbol, *source, close.price);
+            open.price = local_price_agg_bias_book().apply(&target_symbol, *source, open.price);
+            close.price = local_price_agg_bias_book().apply(&target_symbol, *source, close.price);
             source_hits.push((*source, open, close, raw_open, raw_close));
         }
     }
@@ -15974,7 +11419,7 @@ async fn run_local_price_aggregator(
 
     let open_weighted = source_hits
         .iter()
-        .map(|(source, open, _, _, _)| (open.price, local_price_agg_point_weight(*source, open)))
+        .map(|(source, open, _, _, _)| (open.price, agg_cfg.point_weight(*source, open)))
         .collect::<Vec<_>>();
     let Some(open_med) =
         weighted_mean(&open_weighted).or_else(|| robust_median(open_values.clone()))
@@ -16000,7 +11445,7 @@ async fn run_local_price_aggregator(
     };
     let close_weighted = source_hits
         .iter()
-        .map(|(source, _, close, _, _)| (close.price, local_price_agg_point_weight(*source, close)))
+        .map(|(source, _, close, _, _)| (close.price, agg_cfg.point_weight(*source, close)))
         .collect::<Vec<_>>();
     let Some(close_med) =
         weighted_mean(&close_weighted).or_else(|| robust_median(close_values.clone()))
@@ -16079,26 +11524,13 @@ async fn run_local_price_aggregator(
         return None;
     }
 
-    let confidence = if source_hits.len() == 1 {
-        if open_exact_sources > 0 && close_exact_sources > 0 {
-            0.93
-        } else {
-            0.87
-        }
-    } else {
-        let source_factor = (source_hits.len().min(3) as f64) / 3.0;
-        let spread_factor = if max_source_spread_bps > 0.0 {
-            (1.0 - (source_spread / max_source_spread_bps)).clamp(0.0, 1.0)
-        } else {
-            1.0
-        };
-        let exact_factor = ((open_exact_sources.min(close_exact_sources).min(2)) as f64) / 2.0;
-        (0.50 * source_agreement
-            + 0.20 * source_factor
-            + 0.20 * spread_factor
-            + 0.10 * exact_factor)
-            .clamp(0.0, 1.0)
-    };
+    let confidence = source_confidence(
+        source_hits.len(),
+        source_agreement,
+        source_spread,
+        max_source_spread_bps,
+        open_exact_sources.min(close_exact_sources),
+    );
 
     if source_hits.len() == 1
         && open_exact_sources == 0
@@ -16211,8 +11643,8 @@ async fn run_local_price_aggregator(
         close_ts_med,
         open_exact_sources,
         close_exact_sources,
-        local_price_agg_close_time_decay_ms(),
-        local_price_agg_exact_boost(),
+        price_agg_config().close_time_decay_ms,
+        price_agg_config().exact_boost,
         observed_ticks,
         observed_snapshot_ticks,
         observed_live_ticks,
@@ -16620,7 +12052,7 @@ async fn run_chainlink_winner_hint(
         );
     }
 
-    if chainlink_data_streams_enabled() {
+    if chainlink_data_streams_enabled() || data_streams_signed_reports_available(symbol) {
         if let Some(hit) =
             run_data_streams_winner_hint(symbol, round_start_ts, round_end_ts, hard_deadline_ts)
                 .await
@@ -16643,15 +12075,62 @@ async fn run_chainlink_winner_hint(
     }
 
     if let Some(hub) = chainlink_hub {
-        return run_chainlink_winner_hint_via_hub(
-            hub,
+        // The frontend API is the last resort: only when the hub would not get a
+        // Data Streams feeder for this symbol.
+        let frontend_failover =
+            hub.stall_failover_enabled() && !data_streams_failover_available(symbol);
+        let hub_hint = run_chainlink_winner_hint_via_hub(
+            Arc::clone(&hub),
             symbol,
             round_start_ts,
             round_end_ts,
             hard_deadline_ts,
-        )
-        .await
-        .map(
+        );
+        let hit = if frontend_failover {
+            tokio::pin!(hub_hint);
+            // Wait on the hub alone until the symbol stalls, which may happen
+            // mid-round; from then on race it against the frontend round prices.
+            let mut stall_check = tokio::time::interval(Duration::from_secs(1));
+            let hub_done = loop {
+                tokio::select! {
+                    hit = &mut hub_hint => break Some(hit),
+                    _ = stall_check.tick() => {
+                        if hub.symbol_stalled(symbol) {
+                            break None;
+                        }
+                    }
+                }
+            };
+            match hub_done {
+                Some(hit) => hit,
+                None => {
+                    warn!(
+                        "🔀 chainlink_hub_stall_failover | symbol={} round_start_ts={} round_end_ts={} source=frontend_api — racing RTDS hub against frontend round prices",
+                        symbol, round_start_ts, round_end_ts
+                    );
+                    let frontend_hint = run_frontend_winner_hint_failover(
+                        symbol,
+                        round_start_ts,
+                        round_end_ts,
+                        hard_deadline_ts,
+                    );
+                    tokio::pin!(frontend_hint);
+                    tokio::select! {
+                        hit = &mut hub_hint => match hit {
+                            Some(hit) => Some(hit),
+                            None => frontend_hint.await,
+                        },
+                        hit = &mut frontend_hint => match hit {
+                            Some(hit) => Some(hit),
+                            None => hub_hint.await,
+                        },
+                    }
+                }
+            }
+        } else {
+            hub_hint.await
+        };
+        return hit.map(
             |(side, open_ref, close_px, open_ts_ms, close_ts_ms, open_is_exact)| {
                 (
                     WinnerHintSource::Chainlink,
@@ -17415,38 +12894,50 @@ fn parse_ws_message_with_drop_reason(
     (msgs, Some(reason))
 }
 
+/// Decodes one market WS text frame; keepalives yield `Ok(None)`. The L2
+/// assembler and `parse_ws_frame` share the decoded value.
+fn decode_ws_text(text: &str) -> Result<Option<Value>, serde_json::Error> {
+    let trimmed = text.trim();
+    if trimmed.is_empty()
+        || trimmed.eq_ignore_ascii_case("PING")
+        || trimmed.eq_ignore_ascii_case("PONG")
+    {
+        return Ok(None);
+    }
+    serde_json::from_str::<Value>(trimmed).map(Some)
+}
+
 /// Parse one WS text payload and return parsed market-data messages plus ingest stats.
 fn parse_ws_payload(
     settings: &Settings,
     text: &str,
+) -> (Vec<MarketDataMsg>, u64, u64, Vec<String>) {
+    parse_ws_frame(settings, &decode_ws_text(text))
+}
+
+fn parse_ws_frame(
+    settings: &Settings,
+    frame: &Result<Option<Value>, serde_json::Error>,
 ) -> (Vec<MarketDataMsg>, u64, u64, Vec<String>) {
     let mut out = Vec::new();
     let mut unknown_events = 0_u64;
     let mut parse_drops = 0_u64;
     let mut drop_reasons = Vec::new();
-    let trimmed = text.trim();
-    if trimmed.is_empty()
-        || trimmed.eq_ignore_ascii_case("PING")
-        || trimmed.eq_ignore_ascii_case("PONG")
-    {
-        return (out, unknown_events, parse_drops, drop_reasons);
-    }
-
-    let value = match serde_json::from_str::<Value>(trimmed) {
-        Ok(v) => v,
+    let value = match frame {
+        Ok(Some(v)) => v,
+        Ok(None) => return (out, unknown_events, parse_drops, drop_reasons),
         Err(err) => {
             drop_reasons.push(format!("event_type=parse_error reason={}", err));
             return (out, unknown_events, 1, drop_reasons);
         }
     };
 
-    let values = if value.is_array() {
-        value.as_array().cloned().unwrap_or_default()
-    } else {
-        vec![value]
-    };
+    let values = value
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(std::slice::from_ref(value));
 
-    for val in &values {
+    for val in values {
         let event_type = val
             .get("event_type")
             .and_then(|v| v.as_str())
@@ -18445,6 +13936,7 @@ async fn run_market_ws_with_wall_guard(
     dry_run_touch_md_tx: Option<broadcast::Sender<MarketDataMsg>>,
     coord_accept_partial_book: bool,
     post_close_book_tx: mpsc::Sender<PostCloseSideBookUpdate>,
+    l2_book_tx: watch::Sender<L2BookSnapshot>,
     end_ts: u64,
     recorder: Option<RecorderHandle>,
     recorder_meta: Option<RecorderSessionMeta>,
@@ -18459,6 +13951,7 @@ async fn run_market_ws_with_wall_guard(
         dry_run_touch_md_tx,
         coord_accept_partial_book,
         post_close_book_tx,
+        l2_book_tx,
         end_ts,
         recorder,
         recorder_meta,
@@ -18536,6 +14029,10 @@ async fn run_inproc_supervisor(prefixes: Vec<String>) -> anyhow::Result<()> {
             None
         };
 
+    let portfolio_risk_cfg = PortfolioRiskConfig::from_env();
+    let portfolio_risk = (portfolio_risk_cfg.enabled && portfolio_risk_cfg.has_limits())
+        .then(|| PortfolioRiskHub::spawn(portfolio_risk_cfg, false));
+
     let mut joinset: tokio::task::JoinSet<(String, anyhow::Result<()>)> =
         tokio::task::JoinSet::new();
     for prefix in prefixes {
@@ -18544,6 +14041,7 @@ async fn run_inproc_supervisor(prefixes: Vec<String>) -> anyhow::Result<()> {
             shared_ingress: shared_ingress.clone(),
             arbiter_tx: arbiter_sender.clone(),
             round_tail_tx: round_tail_sender.clone(),
+            portfolio_risk: portfolio_risk.clone(),
         });
         let slug = prefix.clone();
         joinset.spawn(async move {
@@ -18612,6 +14110,10 @@ async fn run_multi_market_supervisor(prefixes: Vec<String>) -> anyhow::Result<()
         prefixes.len(),
         prefixes.join(",")
     );
+    // Worker processes exchange exposure/directive files with this actor.
+    let portfolio_risk_cfg = PortfolioRiskConfig::from_env();
+    let _portfolio_risk = (portfolio_risk_cfg.enabled && portfolio_risk_cfg.has_limits())
+        .then(|| PortfolioRiskHub::spawn(portfolio_risk_cfg, true));
 
     #[derive(Debug)]
     struct WorkerExit {
@@ -18623,7 +14125,8 @@ async fn run_multi_market_supervisor(prefixes: Vec<String>) -> anyhow::Result<()
     let (exit_tx, mut exit_rx) = mpsc::channel::<WorkerExit>(prefixes.len().max(1) * 2);
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
 
-    for prefix in prefixes {
+    let metrics_cfg = MetricsConfig::from_env();
+    for (worker_idx, prefix) in prefixes.into_iter().enumerate() {
         let mut cmd = tokio::process::Command::new(&exe);
         cmd.stdin(Stdio::null())
             .stdout(Stdio::inherit())
@@ -18631,6 +14134,13 @@ async fn run_multi_market_supervisor(prefixes: Vec<String>) -> anyhow::Result<()
             .env("PM_MULTI_MARKET_CHILD", "1")
             .env("POLYMARKET_MARKET_SLUG", &prefix)
             .env_remove("PM_MULTI_MARKET_PREFIXES");
+        if metrics_cfg.enabled {
+            // Supervisor keeps the base port; each worker scrapes on its own.
+            cmd.env(
+                "PM_METRICS_ADDR",
+                metrics_cfg.worker_addr(worker_idx).to_string(),
+            );
+        }
 
         // Always narrow child universe to its own symbol: each worker only needs
         // its own symbol's Chainlink ticks. Overriding regardless of parent env
@@ -18738,6 +14248,7 @@ async fn run_market_ws(
     dry_run_touch_md_tx: Option<broadcast::Sender<MarketDataMsg>>,
     coord_accept_partial_book: bool,
     post_close_book_tx: mpsc::Sender<PostCloseSideBookUpdate>,
+    l2_book_tx: watch::Sender<L2BookSnapshot>,
     end_ts: u64,
     recorder: Option<RecorderHandle>,
     recorder_meta: Option<RecorderSessionMeta>,
@@ -18789,6 +14300,9 @@ async fn run_market_ws(
         // previous session to be mixed with fresh data on reconnect. E.g., old NO
         // price combined with new YES price would produce a wrong BookTick.
         let mut book_asm = BookAssembler::default();
+        // Same for the L2 ladder; drop any ladder published by the previous session.
+        let mut l2_asm = L2BookAssembler::new(&settings.yes_asset_id, &settings.no_asset_id);
+        l2_book_tx.send_replace(L2BookSnapshot::default());
 
         let url = settings.ws_url("market");
         info!(%url, "📡 connecting market WS");
@@ -18983,9 +14497,15 @@ async fn run_market_ws(
                                     if let (Some(rec), Some(meta)) = (&recorder, &recorder_meta) {
                                         rec.record_market_ws_raw(meta, &text);
                                     }
+                                    let frame = decode_ws_text(&text);
+                                    if let Ok(Some(value)) = &frame {
+                                        if l2_asm.apply_ws_value(value, Instant::now()) {
+                                            l2_book_tx.send_replace(l2_asm.snapshot());
+                                        }
+                                    }
 
                                     let (parsed, unknown_events, parse_drops, _drop_reasons) =
-                                        parse_ws_payload(&settings, &text);
+                                        parse_ws_frame(&settings, &frame);
                                     session_unknown_event_count = session_unknown_event_count
                                         .saturating_add(unknown_events);
                                     session_parse_drop_count =
@@ -19471,6 +14991,25 @@ fn install_rustls_crypto_provider() {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenv::dotenv().ok();
+    // Config file layers sit under real env / .env: only missing keys are exported.
+    let applied_config = match config_file_path_from_args_or_env() {
+        Some(path) => Some(apply_config_file(&path)?),
+        None => None,
+    };
+    if env::args().any(|a| a == "--print-effective-config") {
+        print_effective_config(applied_config.as_ref());
+        return Ok(());
+    }
+    // Build the env-driven configs once up front so values they ignore or clamp
+    // land in the validation report alongside unknown / unparsable env keys.
+    let config_issues = build_configs_and_collect_issues();
+    if let Some(report) = strict_config_failure(&config_issues, strict_mode_enabled()) {
+        eprint!("{}", report);
+        anyhow::bail!(
+            "strict config validation failed with {} issue(s)",
+            config_issues.len()
+        );
+    }
     let active_instance_id = instance_id();
     let active_log_root = log_root();
     // Per-worker log isolation: when spawned as a supervisor child, each worker
@@ -19506,6 +15045,34 @@ async fn main() -> anyhow::Result<()> {
         active_instance_id.as_deref().unwrap_or("unset"),
         active_log_root.display()
     );
+    let metrics_cfg = MetricsConfig::from_env();
+    let _metrics_server = spawn_metrics_server(&metrics_cfg).await;
+    if let Some(applied) = applied_config.as_ref() {
+        info!(
+            "🧾 config_file | path={} strategy_section={} market_section={} exported_keys={} env_overrides={}",
+            applied.path.display(),
+            applied.strategy_section.as_deref().unwrap_or("-"),
+            applied.market_section.as_deref().unwrap_or("-"),
+            applied.exported,
+            applied
+                .entries
+                .iter()
+                .filter(|e| e.origin == ConfigOrigin::Env)
+                .count()
+        );
+    }
+    for issue in config_issues
+        .iter()
+        .filter(|i| i.kind != ConfigIssueKind::Conflict)
+    {
+        warn!(
+            "⚠️ config {}: {} {} (set {}=1 to refuse startup)",
+            issue.kind.as_str(),
+            issue.key,
+            issue.detail,
+            CONFIG_STRICT_ENV
+        );
+    }
     if shared_ingress_role() == SharedIngressRole::Auto {
         info!(
             "🤖 shared ingress auto mode | root={} protocol={} schema={} build_id={}",
@@ -19588,6 +15155,18 @@ async fn main() -> anyhow::Result<()> {
     run_prefix_worker(None).await
 }
 
+/// `--print-effective-config`: every `PM_*` / `POLYMARKET_*` key with its origin,
+/// then the typed configs exactly as a worker would build them.
+fn print_effective_config(applied: Option<&AppliedConfig>) {
+    print!("{}", render_effective_env(applied));
+    println!();
+    println!("# CoordinatorConfig\n{:#?}", CoordinatorConfig::from_env());
+    println!("# OfiConfig\n{:#?}", OfiConfig::from_env());
+    println!("# InventoryConfig\n{:#?}", InventoryConfig::from_env());
+    println!("# RecorderConfig\n{:#?}", RecorderConfig::from_env());
+    println!("# AutoClaimConfig\n{:#?}", AutoClaimConfig::from_env());
+}
+
 /// Shared ingress plane passed from the in-proc supervisor to per-slug workers.
 /// This keeps one upstream data plane and multiple downstream workers.
 #[derive(Clone)]
@@ -19618,6 +15197,9 @@ fn shared_ingress_hub_symbols(
             }
         }
     }
+    if glft_multi_source_reference_enabled(coord_cfg) {
+        hub_symbols.extend(prefixes.iter().filter_map(|p| glft_reference_hub_symbol(p)));
+    }
     hub_symbols
 }
 
@@ -19647,12 +15229,101 @@ fn build_chainlink_hub_for_symbols(symbols: &HashSet<String>) -> Option<Arc<Chai
     }
 }
 
+fn spawn_remote_local_price_hub(
+    symbols: HashSet<String>,
+    socket_path: PathBuf,
+) -> Arc<LocalPriceHub> {
+    let hub = LocalPriceHub::new(&symbols);
+    let target_symbols = symbols
+        .into_iter()
+        .map(|sym| normalize_chainlink_symbol(&sym))
+        .filter(|sym| !sym.is_empty())
+        .collect::<Vec<_>>();
+    let shared_hub = Arc::clone(&hub);
+    tokio::spawn(async move {
+        let mut reconnect_backoff = Duration::from_millis(300);
+        loop {
+            let _ = ensure_shared_ingress_broker_running().await;
+            let connect =
+                tokio::time::timeout(Duration::from_secs(2), UnixStream::connect(&socket_path))
+                    .await;
+            let Ok(Ok(stream)) = connect else {
+                sleep(reconnect_backoff).await;
+                reconnect_backoff = (reconnect_backoff * 2).min(Duration::from_secs(2));
+                continue;
+            };
+            reconnect_backoff = Duration::from_millis(300);
+            let (read_half, mut write_half) = stream.into_split();
+            let req = SharedIngressSubscribeReq {
+                stream: "local_price".to_string(),
+                symbols: target_symbols.clone(),
+                market_slug: None,
+                market_id: None,
+                yes_asset_id: None,
+                no_asset_id: None,
+                ws_base_url: None,
+                custom_feature_enabled: None,
+            };
+            let mut line = match serde_json::to_vec(&req) {
+                Ok(v) => v,
+                Err(_) => break,
+            };
+            line.push(b'\n');
+            if write_half.write_all(&line).await.is_err() || write_half.flush().await.is_err() {
+                continue;
+            }
+            let mut reader = BufReader::new(read_half);
+            let mut buf = String::new();
+            loop {
+                buf.clear();
+                let read = reader.read_line(&mut buf).await;
+                let Ok(n) = read else {
+                    break;
+                };
+                if n == 0 {
+                    break;
+                }
+                let Ok(msg) = serde_json::from_str::<SharedIngressWireMsg>(buf.trim()) else {
+                    continue;
+                };
+                match msg {
+                    SharedIngressWireMsg::LocalPriceSnapshot {
+                        symbol,
+                        price,
+                        ts_ms,
+                        source,
+                    }
+                    | SharedIngressWireMsg::LocalPriceTick {
+                        symbol,
+                        price,
+                        ts_ms,
+                        source,
+                    } => {
+                        let Some(source) = LocalPriceSource::parse(&source) else {
+                            continue;
+                        };
+                        shared_hub.publish_tick(&symbol, price, ts_ms, source);
+                    }
+                    SharedIngressWireMsg::SnapshotDone => {}
+                    SharedIngressWireMsg::Error { message } => {
+                        warn!("⚠️ local_price_remote_hub_error | msg={}", message);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    });
+    hub
+}
+
 fn build_local_price_hub_for_symbols(
     symbols: &HashSet<String>,
     coord_cfg: &CoordinatorConfig,
 ) -> Option<Arc<LocalPriceHub>> {
-    if !coord_cfg.strategy.is_oracle_lag_sniping()
-        || !local_price_agg_enabled()
+    let oracle_lag_wants_hub =
+        coord_cfg.strategy.is_oracle_lag_sniping() && local_price_agg_enabled();
+    if !(oracle_lag_wants_hub || glft_multi_source_reference_enabled(coord_cfg))
         || symbols.is_empty()
     {
         return None;
@@ -19669,7 +15340,7 @@ fn build_local_price_hub_for_symbols(
                 local_price_source_names(),
                 local_price_agg_bias_learning_enabled(),
             );
-            Some(LocalPriceHub::spawn_remote(symbols.clone(), socket_path))
+            Some(spawn_remote_local_price_hub(symbols.clone(), socket_path))
         }
         SharedIngressRole::Standalone | SharedIngressRole::Broker => {
             info!(
@@ -19678,7 +15349,7 @@ fn build_local_price_hub_for_symbols(
                 local_price_source_names(),
                 local_price_agg_bias_learning_enabled(),
             );
-            Some(LocalPriceHub::spawn(symbols.clone()))
+            Some(LocalPriceHub::spawn(symbols.clone(), price_agg_config()))
         }
     }
 }
@@ -19815,7 +15486,12 @@ async fn handle_local_price_ingress_client(
     symbols.sort();
     symbols.dedup();
     for symbol in &symbols {
-        for (price, ts_ms, source) in hub.snapshot_recent_ticks(symbol) {
+        for SourceTick {
+            price,
+            ts_ms,
+            source,
+        } in hub.snapshot_recent_ticks(symbol)
+        {
             shared_ingress_send_wire_line(
                 &mut write_half,
                 &SharedIngressWireMsg::LocalPriceSnapshot {
@@ -19841,7 +15517,11 @@ async fn handle_local_price_ingress_client(
         tasks.push(tokio::spawn(async move {
             loop {
                 match sub.recv().await {
-                    Ok((price, ts_ms, source)) => {
+                    Ok(SourceTick {
+                        price,
+                        ts_ms,
+                        source,
+                    }) => {
                         if tx_clone
                             .send(SharedIngressWireMsg::LocalPriceTick {
                                 symbol: symbol_clone.clone(),
@@ -20102,7 +15782,7 @@ async fn run_shared_ingress_broker() -> anyhow::Result<()> {
         );
     }
     let chainlink_hub = ChainlinkHub::spawn(hub_symbols.clone());
-    let local_price_hub = LocalPriceHub::spawn(hub_symbols.clone());
+    let local_price_hub = LocalPriceHub::spawn(hub_symbols.clone(), price_agg_config());
     let market_registry: Arc<Mutex<HashMap<SharedMarketFeedKey, Arc<SharedMarketIngressFeed>>>> =
         Arc::new(Mutex::new(HashMap::new()));
     let mut chainlink_task =
@@ -20238,6 +15918,8 @@ struct WorkerCtx {
     /// When Some, hint listeners report one final observation per round so
     /// supervisor can dispatch exactly one tail action across markets.
     round_tail_tx: Option<mpsc::Sender<RoundTailObservation>>,
+    /// Supervisor-level portfolio risk actor shared by all in-proc workers.
+    portfolio_risk: Option<PortfolioRiskHub>,
 }
 
 async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
@@ -20250,6 +15932,20 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
         .clone()
         .unwrap_or_else(|| "btc-updown-15m".to_string());
     let prefix_mode = is_prefix_slug(&raw_slug);
+    let portfolio_risk = match ctx.as_ref().and_then(|c| c.portfolio_risk.as_ref()) {
+        Some(hub) => Some(hub.worker(&raw_slug)),
+        None => {
+            let cfg = PortfolioRiskConfig::from_env();
+            let is_child = env::var("PM_MULTI_MARKET_CHILD").ok().as_deref() == Some("1");
+            (is_child && cfg.enabled && cfg.has_limits()).then(|| {
+                PortfolioRiskWorker::file_backed(
+                    &cfg.root,
+                    &raw_slug,
+                    Duration::from_secs(cfg.stale_secs),
+                )
+            })
+        }
+    };
 
     if prefix_mode {
         info!("🔄 PREFIX mode: '{}' — will auto-rotate markets", raw_slug);
@@ -20262,8 +15958,15 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
         oracle_lag_symbol_universe.describe()
     );
     let inv_cfg_base = InventoryConfig::from_env();
-    let ofi_cfg = OfiConfig::from_env();
+    let inventory_recovery_cfg = InventoryRecoveryConfig::from_env();
+    let ofi_cfg = OfiConfig::from_env().for_market(&raw_slug);
+    let ofi_attribution_cfg = OfiAttributionConfig::from_env();
     let coord_cfg_base = CoordinatorConfig::from_env();
+    if coord_cfg_base.strategy.is_oracle_lag_sniping() {
+        // Log the verifier settings (and an opted-out verification) at startup
+        // rather than at the first winner hint.
+        let _ = data_streams_verifier();
+    }
     // Slug lock: standalone mode only (ctx=None = single OS-process worker).
     // In inproc mode the supervisor IS the single process, so no cross-process
     // conflict is possible and we skip the lock.
@@ -20310,6 +16013,10 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             hub_symbols.insert(format!("{}/usd", base));
         }
         build_local_price_hub_for_symbols(&hub_symbols, &coord_cfg_base)
+    } else if glft_multi_source_reference_enabled(&coord_cfg_base) {
+        let hub_symbols: HashSet<String> =
+            glft_reference_hub_symbol(&raw_slug).into_iter().collect();
+        build_local_price_hub_for_symbols(&hub_symbols, &coord_cfg_base)
     } else {
         None
     };
@@ -20318,6 +16025,35 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
     let recycle_cfg = CapitalRecycleConfig::from_env();
     let recorder = RecorderHandle::from_env();
     let mut auto_claim_state = AutoClaimState::default();
+    // Crash-safe actor snapshots (one SQLite file per instance). Rows are
+    // scoped by this worker's prefix so in-proc supervisor workers sharing the
+    // file stay apart. Round actors reload only when the stored slug matches;
+    // claim state is per worker.
+    let actor_state_cfg = ActorStateStoreConfig::from_env();
+    let actor_state_store = actor_state_cfg
+        .as_ref()
+        .and_then(ActorStateStore::open_from_config)
+        .map(|store| store.scoped(&raw_slug));
+    let actor_state_interval = actor_state_cfg
+        .as_ref()
+        .map(|cfg| cfg.interval)
+        .unwrap_or_default();
+    // Round / fill / tranche / merge / redeem rows; shared by every instance
+    // on the host and readable by offline tools.
+    let pnl_ledger = PnlLedgerConfig::from_env()
+        .as_ref()
+        .and_then(PnlLedger::open_from_config);
+    let pnl_ledger_writer = pnl_ledger.clone().map(PnlLedgerWriter::spawn);
+    let process_actor_state = actor_state_store
+        .as_ref()
+        .map(|store| store.for_market(ACTOR_STATE_PROCESS_SCOPE, actor_state_interval));
+    let restored = match process_actor_state.as_ref() {
+        Some(store) => store.load(AUTO_CLAIM_STATE_ACTOR).await,
+        None => None,
+    };
+    if let Some(restored) = restored {
+        auto_claim_state.restore_state(restored);
+    }
     let mut round_claim_task: Option<tokio::task::JoinHandle<()>> = None;
     let mut pgt_shadow_redeem_task: Option<tokio::task::JoinHandle<()>> = None;
 
@@ -20752,6 +16488,20 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
     {
         warn!("⚠️ Auto-claim runner failed at startup: {:?}", e);
     }
+    if let Some(store) = process_actor_state.as_ref() {
+        store.save(AUTO_CLAIM_STATE_ACTOR, &auto_claim_state.export_state());
+    }
+    if auto_claim_cfg.enabled {
+        round_claim_task = resume_pending_round_claim(
+            process_actor_state.as_ref(),
+            &auto_claim_cfg,
+            &round_claim_cfg,
+            funder_address.clone(),
+            signer_address.clone(),
+            base_settings.private_key.clone(),
+        )
+        .await;
+    }
 
     // ═══ L2 API credentials for User WS (live mode only) ═══
     // Always source credentials from authenticated CLOB client to avoid REST/WS identity drift.
@@ -20792,6 +16542,9 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
     let mut market_cache: HashMap<String, ResolvedMarket> = HashMap::new();
 
     let mut round = 0u64;
+    // Wallet reconciliation only matters for the market live when the process
+    // (re)started; later rounds begin flat and rely on the recorder alone.
+    let mut wallet_recovery_pending = true;
     loop {
         // ── Step 1: Resolve current market ──
         let (slug, slug_start_ts, mut expected_end_ts) = if prefix_mode {
@@ -21177,8 +16930,12 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             strategy: coord_cfg.strategy.as_str().to_string(),
             dry_run,
         };
-        let enable_round_validation =
-            !dry_run && coord_cfg.strategy.is_glft_mm() && slug.starts_with("btc-updown-5m");
+        // Every live round is validated and lands in the PnL ledger with its
+        // fills, whatever the strategy or market.
+        let enable_round_validation = !dry_run;
+        let round_actor_state = actor_state_store
+            .as_ref()
+            .map(|store| store.for_market(&slug, actor_state_interval));
 
         // Fill fanout: UserWS → fill_tx → splitter → (InventoryManager, Executor)
         let (fill_tx, mut fill_rx) = mpsc::channel::<FillEvent>(64);
@@ -21231,6 +16988,7 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
         let inv_watch_rx_postclose = inv_watch_rx.clone();
         let (ofi_watch_tx, ofi_watch_rx) = watch::channel(OfiSnapshot::default());
         let (glft_watch_tx, glft_watch_rx) = watch::channel(GlftSignalSnapshot::default());
+        let (l2_book_tx, l2_book_rx) = watch::channel(L2BookSnapshot::default());
         let (coord_obs_tx, coord_obs_rx) = watch::channel(CoordinatorObsSnapshot::default());
         let (slot_release_tx, slot_release_rx) = mpsc::channel::<SlotReleaseEvent>(64);
         let (winner_hint_tx, winner_hint_rx) = mpsc::channel::<MarketDataMsg>(16);
@@ -21259,12 +17017,32 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
                 coord_obs_rx_validation,
                 stop_rx,
                 false,
+                round_actor_state.clone(),
             )));
         }
 
+        let portfolio_risk_reporter = portfolio_risk
+            .as_ref()
+            .map(|link| link.spawn_reporter(inv_watch_rx.clone()));
+        let metrics_handle = MetricsConfig::from_env().enabled.then(|| {
+            tokio::spawn(run_market_metrics_collector(
+                slug.clone(),
+                coord_cfg.strategy.as_str().to_string(),
+                inv_watch_rx.clone(),
+                ofi_watch_rx.clone(),
+                coord_obs_rx.clone(),
+                coord_md_rx.clone(),
+                recorder.enabled().then_some(recorder.clone()),
+            ))
+        });
+
         // Opt-4: Direct kill channel from OFI Engine → Coordinator.
         // Capacity 4: at most one kill per side (YES/NO) queued without blocking OFI heartbeat.
         let (kill_tx, kill_rx) = mpsc::channel::<KillSwitchSignal>(4);
+        let ofi_attribution = ofi_attribution_cfg
+            .enabled
+            .then(|| OfiAttribution::new(ofi_attribution_cfg.clone(), ofi_cfg.tick_size));
+        let (toxic_cancel_tx, toxic_cancel_rx) = mpsc::channel::<ToxicCancelReport>(32);
 
         if oracle_lag_sniping_active {
             let hint_tx = winner_hint_tx.clone();
@@ -21396,16 +17174,40 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             }));
         }
 
+        // Restart recovery: rebuild held inventory + tranche history for this
+        // market before the strategy sees its first snapshot. Dry-run skips the
+        // Data API (wallet positions are not the simulated book), as does every
+        // round after the first.
+        let inv_recovery = recover_inventory(
+            &inventory_recovery_cfg,
+            &slug,
+            &market_id,
+            funder_address
+                .as_deref()
+                .filter(|_| !dry_run && wallet_recovery_pending),
+            unix_now_ms().max(0) as u64,
+        )
+        .await;
+        wallet_recovery_pending = false;
         let inv = InventoryManager::new(
             inv_cfg.clone(),
             inv_event_rx,
             inv_watch_tx,
             recorder.enabled().then_some(recorder.clone()),
             recorder.enabled().then_some(recorder_meta.clone()),
-        );
+        )
+        .with_recovery(&inv_recovery);
+        let inv = match pnl_ledger_writer.as_ref() {
+            Some(writer) => inv.with_pnl_ledger(writer.for_market(&slug)),
+            None => inv,
+        };
         session_handles.push(tokio::spawn(inv.run()));
 
         let ofi = OfiEngine::new(ofi_cfg.clone(), ofi_md_rx, ofi_watch_tx).with_kill_tx(kill_tx);
+        let ofi = match ofi_attribution.as_ref() {
+            Some(attribution) => ofi.with_attribution(attribution.clone(), toxic_cancel_rx),
+            None => ofi,
+        };
         session_handles.push(tokio::spawn(ofi.run()));
 
         if coord_cfg.strategy.is_glft_mm() {
@@ -21419,7 +17221,37 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
                     glft_cfg.refit_interval.as_secs(),
                     glft_cfg.intensity_window.as_secs()
                 );
-                let glft_engine = GlftSignalEngine::new(glft_cfg, glft_md_rx, glft_watch_tx);
+                let mut glft_engine = GlftSignalEngine::new(glft_cfg, glft_md_rx, glft_watch_tx);
+                let glft_reference_rx = glft_reference_hub_symbol(&slug).and_then(|sym| {
+                    local_price_hub
+                        .as_ref()
+                        .filter(|_| glft_multi_source_reference_enabled(&coord_cfg))
+                        .and_then(|hub| hub.subscribe(&sym))
+                });
+                if let Some(mut hub_rx) = glft_reference_rx {
+                    let (reference_tx, reference_rx) = mpsc::channel::<ReferencePriceTick>(1024);
+                    glft_engine = glft_engine.with_reference_rx(reference_rx);
+                    let agg_cfg = price_agg_config();
+                    session_handles.push(tokio::spawn(async move {
+                        loop {
+                            match hub_rx.recv().await {
+                                Ok(SourceTick { price, source, .. }) => {
+                                    let tick = ReferencePriceTick {
+                                        source: source.as_str(),
+                                        price,
+                                        weight: agg_cfg.source_weight(source),
+                                        ts: Instant::now(),
+                                    };
+                                    if reference_tx.send(tick).await.is_err() {
+                                        break;
+                                    }
+                                }
+                                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                                Err(broadcast::error::RecvError::Closed) => break,
+                            }
+                        }
+                    }));
+                }
                 session_handles.push(tokio::spawn(glft_engine.run()));
             } else {
                 warn!(
@@ -21447,7 +17279,21 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             slot_release_rx,
             shared_pgt_winner_side.clone(),
         )
-        .with_obs_tx(coord_obs_tx);
+        .with_obs_tx(coord_obs_tx)
+        .with_l2_book_rx(l2_book_rx.clone());
+        let coord = match portfolio_risk.as_ref() {
+            Some(link) => coord.with_portfolio_risk_rx(link.directive_rx()),
+            None => coord,
+        };
+        let coord = if ofi_attribution.is_some() {
+            coord.with_toxic_cancel_tx(toxic_cancel_tx)
+        } else {
+            coord
+        };
+        let coord = match round_actor_state.clone() {
+            Some(store) => coord.with_state_store(store).await,
+            None => coord,
+        };
         session_handles.push(tokio::spawn(coord.run()));
 
         let pgt_buy_fill_reopen_cooldown = if coord_cfg.strategy.is_pair_gated_tranche_arb() {
@@ -21462,6 +17308,10 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             slot_release_tx,
             pgt_buy_fill_reopen_cooldown,
         );
+        let om = match round_actor_state.clone() {
+            Some(store) => om.with_state_store(store).await,
+            None => om,
+        };
         session_handles.push(tokio::spawn(om.run()));
 
         if !dry_run && recycle_cfg.enabled {
@@ -21509,7 +17359,12 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             Some(feedback_tx),
             recorder.enabled().then_some(recorder.clone()),
             recorder.enabled().then_some(recorder_meta.clone()),
-        );
+        )
+        .with_l2_book_rx(l2_book_rx);
+        let executor = match round_actor_state.clone() {
+            Some(store) => executor.with_state_store(store).await,
+            None => executor,
+        };
         let executor_handle = tokio::spawn(executor.run());
         let executor_abort = executor_handle.abort_handle();
 
@@ -21586,6 +17441,7 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             dry_run_touch_md_tx,
             coord_cfg.strategy.is_pair_gated_tranche_arb(),
             post_close_book_tx,
+            l2_book_tx,
             ws_round_end_ts,
             recorder.enabled().then_some(recorder.clone()),
             recorder.enabled().then_some(recorder_meta.clone()),
@@ -21595,6 +17451,10 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             recorder.emit_session_end(&recorder_meta, &format!("{:?}", reason));
         }
         info!("🏁 Market ended: {:?}", reason);
+        metrics_registry().note_round_end(match reason {
+            MarketEnd::Expired => "expired",
+            MarketEnd::WsDegraded { .. } => "ws_degraded",
+        });
         if let MarketEnd::WsDegraded {
             consecutive_failures,
             remaining_secs,
@@ -21701,6 +17561,15 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
         if let Some(stop_tx) = validation_stop_tx.take() {
             let _ = stop_tx.send(());
         }
+        if let Some(handle) = metrics_handle {
+            handle.abort();
+        }
+        if let Some(handle) = portfolio_risk_reporter {
+            handle.abort();
+        }
+        if let Some(link) = portfolio_risk.as_ref() {
+            link.clear().await;
+        }
         let mut round_validation_summary: Option<RoundValidationSummary> = None;
         if let Some(handle) = validation_handle.take() {
             match tokio::time::timeout(Duration::from_secs(3), handle).await {
@@ -21724,8 +17593,23 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             let _ = h.await;
         }
 
+        if let Some(attribution) = ofi_attribution.as_ref() {
+            attribution.finish_round(
+                &raw_slug,
+                &slug,
+                ofi_cfg.toxicity_model.as_str(),
+                ofi_cfg.toxicity_threshold,
+                !market_settled,
+            );
+        }
+
         if let Some(mut summary) = round_validation_summary {
             summary.partial_round = !market_settled;
+            if let Some(ledger) = pnl_ledger.as_ref() {
+                if let Err(e) = record_round_validation_summary(ledger, &summary) {
+                    warn!("⚠️ Failed to record round in pnl ledger: {:?}", e);
+                }
+            }
             if let Err(e) = append_round_validation_summary(&summary) {
                 warn!("⚠️ Failed to append round validation summary: {:?}", e);
             } else {
@@ -21747,9 +17631,11 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
                     summary.cancel_events,
                     summary.publish_events,
                 );
-                if let Err(e) =
-                    update_round_validation_report(&summary.strategy, &summary.market_slug)
-                {
+                if let Err(e) = update_round_validation_report(
+                    pnl_ledger.as_ref(),
+                    &summary.strategy,
+                    &summary.market_slug,
+                ) {
                     warn!(
                         "⚠️ Failed to refresh round validation aggregate report: {:?}",
                         e
@@ -21829,6 +17715,19 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
             let claim_pk = base_settings.private_key.clone();
             let claim_recorder = recorder.enabled().then_some(recorder.clone());
             let claim_recorder_meta = recorder.enabled().then_some(recorder_meta.clone());
+            let claim_ledger = pnl_ledger_writer
+                .as_ref()
+                .map(|writer| writer.for_market(&slug));
+            let claim_state = process_actor_state.clone();
+            if let Some(store) = claim_state.as_ref() {
+                store.save(
+                    PENDING_ROUND_CLAIM_ACTOR,
+                    &PendingRoundClaim {
+                        ended_condition: ended_condition.map(|v| v.to_string()),
+                        launched_at_ms: unix_now_ms(),
+                    },
+                );
+            }
             round_claim_task = Some(tokio::spawn(async move {
                 let mut round_state = AutoClaimState::default();
                 if let Err(e) = run_round_claim_window(
@@ -21841,11 +17740,15 @@ async fn run_prefix_worker(ctx: Option<Arc<WorkerCtx>>) -> anyhow::Result<()> {
                     claim_pk.as_deref(),
                     claim_recorder.as_ref(),
                     claim_recorder_meta.as_ref(),
+                    claim_ledger.as_ref(),
                 )
                 .await
                 {
                     warn!("⚠️ Round claim runner failed after market end: {:?}", e);
                 }
+                if let Some(store) = claim_state.as_ref() {
+                    store.clear(PENDING_ROUND_CLAIM_ACTOR);
+                }
             }));
             info!(
                 "💸 Round claim runner launched in background (non-blocking; rotation continues immediately) ended_condition={} dry_run={} window={}s",
@@ -21982,6 +17885,7 @@ mod tests {
             mean_fill_slippage_vs_posted: None,
             fill_to_adverse_move_3s: Some(-0.02),
             fill_to_adverse_move_10s: Some(-0.03),
+            fills: Vec::new(),
         }
     }
 
@@ -22096,6 +18000,49 @@ mod tests {
         assert_eq!(tick, Some((25.0, 1_776_148_200)));
     }
 
+    #[test]
+    fn test_parse_verified_data_streams_report_uses_signed_benchmark() {
+        let fixture: Value = serde_json::from_str(include_str!(
+            "../../tests/fixtures/data_streams_reports.json"
+        ))
+        .unwrap();
+        let verifier = DataStreamsVerifier::new()
+            .with_signers(
+                fixture["signers"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|s| s.as_str().unwrap().parse().unwrap()),
+            )
+            .with_feed_id(
+                "BTCUSD",
+                fixture["feed_id"].as_str().unwrap().parse().unwrap(),
+            );
+        let body = |name: &str| {
+            format!(
+                r#"{{"report":{{"feedID":"{}","fullReport":"{}"}}}}"#,
+                fixture["feed_id"].as_str().unwrap(),
+                fixture["reports"][name]["fullReport"].as_str().unwrap()
+            )
+        };
+        let report = parse_verified_data_streams_report(&body("valid"), "BTCUSD", &verifier)
+            .expect("valid signed report");
+        assert_eq!(
+            (report.benchmark_price, report.observations_ts),
+            (84_123.45, 1_776_148_200)
+        );
+        assert!(matches!(
+            parse_verified_data_streams_report(&body("tampered_price"), "BTCUSD", &verifier),
+            Err(ReportReject::UnknownSigner(_))
+        ));
+        // Candlestick ticks carry no report and never verify.
+        let unsigned = r#"{"f":"t","i":"BTCUSD","p":2.50e19,"t":1776148200,"s":1}"#;
+        assert!(matches!(
+            parse_verified_data_streams_report(unsigned, "BTCUSD", &verifier),
+            Err(ReportReject::Malformed(_))
+        ));
+    }
+
     #[test]
     fn test_parse_chainlink_multi_symbol_ticks_with_payload_symbol() {
         let msg = r#"{"payload":{"symbol":"bnb/usd","data":[{"timestamp":1776488226000,"value":644.658},{"timestamp":1776488227000,"value":644.654}]}}"#;
@@ -22122,36 +18069,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_parse_binance_combined_trade_tick() {
-        let msg = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1776488227000,"s":"BTCUSDT","t":1,"p":"85000.12000000","q":"0.001","T":1776488226999}}"#;
-        let tick = parse_binance_combined_trade_tick(msg);
-        assert_eq!(
-            tick,
-            Some(("btc/usd".to_string(), 85000.12, 1_776_488_226_999))
-        );
-    }
-
-    #[test]
-    fn test_parse_bybit_public_trade_ticks() {
-        let msg = r#"{"topic":"publicTrade.BTCUSDT","type":"snapshot","ts":1776488227000,"data":[{"T":1776488226998,"s":"BTCUSDT","S":"Buy","v":"0.001","p":"85000.12"}]}"#;
-        let ticks = parse_bybit_public_trade_ticks(msg);
-        assert_eq!(
-            ticks,
-            vec![("btc/usd".to_string(), 85000.12, 1_776_488_226_998)]
-        );
-    }
-
-    #[test]
-    fn test_parse_okx_trade_ticks() {
-        let msg = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"1","px":"85000.12","sz":"0.1","side":"buy","ts":"1776488226997"}]}"#;
-        let ticks = parse_okx_trade_ticks(msg);
-        assert_eq!(
-            ticks,
-            vec![("btc/usd".to_string(), 85000.12, 1_776_488_226_997)]
-        );
-    }
-
     fn arbiter_obs_for_test(
         round_end_ts: u64,
         slug: &str,
@@ -0,0 +1,118 @@
+//! Replay a recorder session (`data/recorder/<date>/<slug>/`) back through the
+//! strategy stack.
+//!
+//! - `--mode pipeline` (default): live actor wiring in dry-run, paced by `--speed`.
+//!   Concurrent actors, so results can differ run to run.
+//! - `--mode backtest`: synchronous `backtest::run_window` on the same records;
+//!   deterministic.
+
+use std::env;
+use std::path::PathBuf;
+
+use pm_as_ofi::polymarket::backtest::{run_window, BacktestConfig};
+use pm_as_ofi::polymarket::coordinator::CoordinatorConfig;
+use pm_as_ofi::polymarket::inventory::InventoryConfig;
+use pm_as_ofi::polymarket::replay::{
+    run_dry_run_pipeline, session_dir, RecorderReplaySession, ReplaySpeed,
+};
+use pm_as_ofi::polymarket::types::Side;
+
+fn get_arg(flag: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(a) = args.next() {
+        if a == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn has_flag(flag: &str) -> bool {
+    env::args().any(|a| a == flag)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    if has_flag("--help") || has_flag("-h") {
+        println!(
+            "Usage: cargo run --bin recorder_replay -- [options]\n\
+             --dir <session dir>          or --root <dir> --date <YYYY-MM-DD> --slug <slug>\n\
+             --mode <pipeline|backtest>   default pipeline\n\
+             --speed <original|max|Nx>    pipeline pacing, default original\n\
+             --strategy <name>            overrides PM_STRATEGY (default: recorded strategy)\n\
+             --outcome <UP|DOWN>          backtest settlement side\n"
+        );
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let dir = match get_arg("--dir") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let root = get_arg("--root").unwrap_or_else(|| "data/recorder".to_string());
+            let date = get_arg("--date").ok_or_else(|| anyhow::anyhow!("--date is required"))?;
+            let slug = get_arg("--slug").ok_or_else(|| anyhow::anyhow!("--slug is required"))?;
+            session_dir(&PathBuf::from(root), &date, &slug)
+        }
+    };
+    let session = RecorderReplaySession::load(&dir)?;
+
+    let strategy = get_arg("--strategy").or_else(|| {
+        session
+            .meta
+            .as_ref()
+            .map(|m| m.strategy.clone())
+            .filter(|s| !s.is_empty())
+    });
+    if let Some(strategy) = strategy {
+        env::set_var("PM_STRATEGY", strategy);
+    }
+    env::set_var("PM_DRY_RUN", "1");
+    let coord_cfg = CoordinatorConfig::from_env();
+
+    match get_arg("--mode").as_deref().unwrap_or("pipeline") {
+        "backtest" => {
+            let outcome = match get_arg("--outcome").as_deref() {
+                Some("UP") | Some("up") => Some(Side::Yes),
+                Some("DOWN") | Some("down") => Some(Side::No),
+                _ => None,
+            };
+            let cfg = BacktestConfig::new(coord_cfg, InventoryConfig::from_env());
+            let report = run_window(&cfg, &session.to_backtest_window(outcome)).await;
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        "pipeline" => {
+            let speed = get_arg("--speed")
+                .map(|raw| {
+                    ReplaySpeed::parse(&raw)
+                        .ok_or_else(|| anyhow::anyhow!("invalid --speed '{}'", raw))
+                })
+                .transpose()?
+                .unwrap_or(ReplaySpeed::Original);
+            let report = run_dry_run_pipeline(&session, coord_cfg, speed).await;
+            let inv = report.inventory.working;
+            println!(
+                "records={} ticks={} placed={} cancels={} ofi_kill_events={} | working YES={:.2}@{:.4} NO={:.2}@{:.4} net={:.2} | recorded_own_events={}",
+                report.records_sent,
+                report.coordinator.ticks,
+                report.coordinator.placed,
+                report.coordinator.cancel_events,
+                report.coordinator.ofi_kill_events,
+                inv.yes_qty,
+                inv.yes_avg_cost,
+                inv.no_qty,
+                inv.no_avg_cost,
+                inv.net_diff,
+                session.own_events.len(),
+            );
+        }
+        other => anyhow::bail!("unsupported --mode '{}'", other),
+    }
+    Ok(())
+}
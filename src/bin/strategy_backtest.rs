@@ -0,0 +1,118 @@
+//! Backtest the real strategy stack (`StrategyCoordinator` + `OrderManager` +
+//! `InventoryManager`) against historical ticks.
+//!
+//! Strategy parameters come from the usual `PM_*` env vars, exactly as in live
+//! trading; `--strategy` is a shortcut for `PM_STRATEGY`.
+
+use std::env;
+
+use rusqlite::Connection;
+use serde_json::json;
+
+use pm_as_ofi::polymarket::backtest::{
+    load_sqlite_windows, run_windows, BacktestConfig, BacktestSummary,
+};
+use pm_as_ofi::polymarket::coordinator::CoordinatorConfig;
+use pm_as_ofi::polymarket::inventory::InventoryConfig;
+
+fn get_arg(flag: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(a) = args.next() {
+        if a == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn has_flag(flag: &str) -> bool {
+    env::args().any(|a| a == flag)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    if has_flag("--help") || has_flag("-h") {
+        println!(
+            "Usage: cargo run --bin strategy_backtest -- [options]\n\
+             --db <path>                 SQLite db with settlement_records + market_ticks\n\
+             --strategy <name>           overrides PM_STRATEGY\n\
+             --limit <n>\n\
+             --skip <n>\n\
+             --taker-fee-rate <v>        overrides the PM_FEE_CATEGORY schedule rate\n\
+             --watchdog-ms <ms>          synthetic step while history is silent\n\
+             --taker-depth <shares>      taker size at the touch per book update (0 = uncapped)\n\
+             --jsonl                     one JSON line per window + summary\n"
+        );
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    if let Some(strategy) = get_arg("--strategy") {
+        env::set_var("PM_STRATEGY", strategy);
+    }
+    // Simulated venue only; never let a backtest believe it is live.
+    env::set_var("PM_DRY_RUN", "1");
+
+    let db = get_arg("--db").ok_or_else(|| anyhow::anyhow!("--db <path> is required"))?;
+    let limit = get_arg("--limit")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let skip = get_arg("--skip")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let jsonl = has_flag("--jsonl");
+
+    let coordinator = CoordinatorConfig::from_env();
+    let mut cfg = BacktestConfig::new(coordinator, InventoryConfig::from_env());
+    if let Some(v) = get_arg("--taker-fee-rate").and_then(|v| v.parse::<f64>().ok()) {
+        cfg.fees.taker_rate = v.max(0.0);
+    }
+    if let Some(v) = get_arg("--watchdog-ms").and_then(|v| v.parse::<u64>().ok()) {
+        cfg.watchdog_step_ms = v.max(1);
+    }
+    if let Some(v) = get_arg("--taker-depth").and_then(|v| v.parse::<f64>().ok()) {
+        cfg.taker_touch_depth = v.max(0.0);
+    }
+
+    let conn = Connection::open(&db)?;
+    let windows = load_sqlite_windows(&conn, limit, skip)?;
+    let reports = run_windows(&cfg, &windows).await;
+    let summary = BacktestSummary::from_reports(&reports);
+
+    if jsonl {
+        for report in &reports {
+            println!("{}", serde_json::to_string(report)?);
+        }
+        println!(
+            "{}",
+            json!({
+                "strategy": cfg.coordinator.strategy.as_str(),
+                "fees": cfg.fees,
+                "summary": summary,
+            })
+        );
+    } else {
+        println!(
+            "strategy={} windows={} active={} fills={} (maker={} taker={}) fees={:.4} rebates={:.4} pnl={:+.4} avg={:+.4} win/loss={}/{}",
+            cfg.coordinator.strategy.as_str(),
+            summary.windows,
+            summary.active_windows,
+            summary.fills,
+            summary.maker_fills,
+            summary.taker_fills,
+            summary.fees,
+            summary.rebates,
+            summary.pnl,
+            summary.avg_pnl,
+            summary.wins,
+            summary.losses,
+        );
+    }
+    Ok(())
+}
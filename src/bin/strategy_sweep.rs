@@ -0,0 +1,244 @@
+//! Parameter sweep over the real strategy stack.
+//!
+//! Every combination is an overlay of `PM_*` env keys on top of the current
+//! environment, so any `CoordinatorConfig` / `InventoryConfig` field can be
+//! swept. Backtests run in parallel across cores; results are ranked by PnL
+//! and, with `--train-days/--test-days`, scored walk-forward by UTC date.
+
+use std::env;
+
+use rusqlite::Connection;
+use serde_json::json;
+
+use pm_as_ofi::polymarket::backtest::{load_sqlite_windows, BacktestConfig};
+use pm_as_ofi::polymarket::coordinator::CoordinatorConfig;
+use pm_as_ofi::polymarket::inventory::InventoryConfig;
+use pm_as_ofi::polymarket::sweep::{
+    run_sweep, with_env_overrides, SweepMetrics, SweepMode, SweepParam, SweepReport, SweepSpec,
+    WalkForwardSpec,
+};
+
+fn get_arg(flag: &str) -> Option<String> {
+    get_args(flag).into_iter().next()
+}
+
+fn get_args(flag: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(a) = args.next() {
+        if a == flag {
+            if let Some(v) = args.next() {
+                out.push(v);
+            }
+        }
+    }
+    out
+}
+
+fn has_flag(flag: &str) -> bool {
+    env::args().any(|a| a == flag)
+}
+
+fn metrics_line(m: &SweepMetrics) -> String {
+    format!(
+        "windows={} active={} fills={} (maker={} taker={}) pnl={:+.4} avg={:+.4} dd={:.4} residual_ratio={:.3}",
+        m.windows,
+        m.active_windows,
+        m.fills,
+        m.maker_fills,
+        m.taker_fills,
+        m.pnl,
+        m.avg_pnl,
+        m.max_drawdown,
+        m.residual_exposure_ratio,
+    )
+}
+
+fn main() -> anyhow::Result<()> {
+    if has_flag("--help") || has_flag("-h") {
+        println!(
+            "Usage: cargo run --release --bin strategy_sweep -- [options]\n\
+             --db <path>                 SQLite db with settlement_records + market_ticks\n\
+             --strategy <name>           overrides PM_STRATEGY\n\
+             --param KEY=a,b,c           candidate values (repeatable; bare names map to PM_*)\n\
+             --param KEY=lo:hi[:step]    numeric range; grid mode needs a step\n\
+             --samples <n>               random search with n samples instead of a full grid\n\
+             --seed <n>                  random search seed (default 42)\n\
+             --jobs <n>                  worker threads (default: all cores)\n\
+             --train-days <n>            walk-forward train slice (UTC days)\n\
+             --test-days <n>             walk-forward test slice (default 1)\n\
+             --limit <n>\n\
+             --skip <n>\n\
+             --taker-fee-rate <v>        overrides the PM_FEE_CATEGORY schedule rate\n\
+             --watchdog-ms <ms>          synthetic step while history is silent\n\
+             --taker-depth <shares>      taker size at the touch per book update (0 = uncapped)\n\
+             --top <n>                   combinations printed (default 10)\n\
+             --jsonl                     one JSON line per combination + walk-forward summary\n"
+        );
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                tracing_subscriber::EnvFilter::new("warn,pm_as_ofi::polymarket::sweep=info")
+            }),
+        )
+        .init();
+
+    if let Some(strategy) = get_arg("--strategy") {
+        env::set_var("PM_STRATEGY", strategy);
+    }
+    // Simulated venue only; never let a backtest believe it is live.
+    env::set_var("PM_DRY_RUN", "1");
+
+    let db = get_arg("--db").ok_or_else(|| anyhow::anyhow!("--db <path> is required"))?;
+    let limit = get_arg("--limit")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let skip = get_arg("--skip")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let jobs = get_arg("--jobs")
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
+    let top = get_arg("--top")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(10);
+    let jsonl = has_flag("--jsonl");
+    let taker_fee_rate = get_arg("--taker-fee-rate").and_then(|v| v.parse::<f64>().ok());
+    let watchdog_ms = get_arg("--watchdog-ms").and_then(|v| v.parse::<u64>().ok());
+    let taker_depth = get_arg("--taker-depth").and_then(|v| v.parse::<f64>().ok());
+    let walk_forward = get_arg("--train-days")
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .map(|train_days| WalkForwardSpec {
+            train_days,
+            test_days: get_arg("--test-days")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(1),
+        });
+
+    let spec = SweepSpec {
+        params: get_args("--param")
+            .iter()
+            .map(|raw| SweepParam::parse(raw))
+            .collect::<anyhow::Result<_>>()?,
+        mode: match get_arg("--samples").and_then(|v| v.parse::<usize>().ok()) {
+            Some(samples) if samples > 0 => SweepMode::Random {
+                samples,
+                seed: get_arg("--seed")
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(42),
+            },
+            _ => SweepMode::Grid,
+        },
+    };
+    let combos = spec.combinations()?;
+
+    // Configs are materialised serially before any worker reads the env.
+    let configs: Vec<BacktestConfig> = combos
+        .iter()
+        .map(|overrides| {
+            with_env_overrides(overrides, || {
+                let mut cfg =
+                    BacktestConfig::new(CoordinatorConfig::from_env(), InventoryConfig::from_env());
+                if let Some(v) = taker_fee_rate {
+                    cfg.fees.taker_rate = v.max(0.0);
+                }
+                if let Some(v) = watchdog_ms {
+                    cfg.watchdog_step_ms = v.max(1);
+                }
+                if let Some(v) = taker_depth {
+                    cfg.taker_touch_depth = v.max(0.0);
+                }
+                cfg
+            })
+        })
+        .collect();
+
+    let conn = Connection::open(&db)?;
+    let windows = load_sqlite_windows(&conn, limit, skip)?;
+    eprintln!(
+        "🧪 sweep: {} combinations x {} windows on {} threads",
+        combos.len(),
+        windows.len(),
+        jobs
+    );
+    let reports = run_sweep(&configs, &windows, jobs)?;
+    let sweep = SweepReport::build(&combos, &windows, &reports, walk_forward);
+
+    if jsonl {
+        for combo in sweep.ranked() {
+            println!("{}", serde_json::to_string(combo)?);
+        }
+        println!(
+            "{}",
+            json!({
+                "strategy": configs
+                    .first()
+                    .map(|cfg| cfg.coordinator.strategy.as_str())
+                    .unwrap_or("?"),
+                "combinations": combos.len(),
+                "windows": windows.len(),
+                "walk_forward": sweep.walk_forward,
+                "out_of_sample": sweep.out_of_sample,
+            })
+        );
+        return Ok(());
+    }
+
+    for combo in sweep.ranked().into_iter().take(top) {
+        let overrides = combo
+            .overrides
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!(
+            "#{:<4} {} | {}",
+            combo.index,
+            metrics_line(&combo.all),
+            overrides
+        );
+    }
+    for sel in &sweep.walk_forward {
+        println!(
+            "fold {} train={}..{} test={}..{} best=#{} train_pnl={:+.4} | test {}",
+            sel.fold.index,
+            sel.fold
+                .train_dates
+                .first()
+                .map(String::as_str)
+                .unwrap_or("?"),
+            sel.fold
+                .train_dates
+                .last()
+                .map(String::as_str)
+                .unwrap_or("?"),
+            sel.fold
+                .test_dates
+                .first()
+                .map(String::as_str)
+                .unwrap_or("?"),
+            sel.fold
+                .test_dates
+                .last()
+                .map(String::as_str)
+                .unwrap_or("?"),
+            sel.best_combo,
+            sel.train.pnl,
+            metrics_line(&sel.test),
+        );
+    }
+    if let Some(oos) = &sweep.out_of_sample {
+        println!("walk-forward out-of-sample | {}", metrics_line(oos));
+    }
+    Ok(())
+}
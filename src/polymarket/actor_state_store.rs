@@ -0,0 +1,541 @@
+//! Crash-safe actor state snapshots.
+//!
+//! Each runtime actor (OMS, executor, coordinator, claim runner, round
+//! validation collector) periodically writes a small JSON snapshot of the state
+//! a restart would otherwise lose: cooldown deadlines, sell-availability
+//! warmups, maker friction, the PGT flat-seed latch and validation counters.
+//! One SQLite file per `PM_INSTANCE_ID` holds the latest row per (scope, actor),
+//! where the scope is the worker's market prefix so in-process supervisor
+//! workers sharing the file never overwrite each other. On boot an actor only
+//! reloads its row when the stored market slug matches the round it is about
+//! to trade and the row is younger than `max_age_secs`.
+//!
+//! Writes go through a dedicated writer thread so actor loops never block on
+//! SQLite; loads flush pending writes first on tokio's blocking pool.
+//!
+//! Snapshots never carry `Instant`s. Deadlines are stored as milliseconds
+//! remaining at `saved_at` and shortened by the wall-clock gap on reload.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::warn;
+
+#[derive(Debug, Clone)]
+pub struct ActorStateStoreConfig {
+    pub path: PathBuf,
+    /// Minimum gap between two snapshot writes of the same actor.
+    pub interval: Duration,
+    /// Rows older than this are ignored on reload.
+    pub max_age_secs: u64,
+}
+
+impl Default for ActorStateStoreConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("data/state/default.sqlite"),
+            interval: Duration::from_millis(1_000),
+            max_age_secs: 15 * 60,
+        }
+    }
+}
+
+impl ActorStateStoreConfig {
+    /// `None` when `PM_ACTOR_STATE_ENABLED=false`. The default path is keyed by
+    /// `PM_INSTANCE_ID` so parallel instances never share a snapshot file.
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("PM_ACTOR_STATE_ENABLED")
+            .ok()
+            .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if !enabled {
+            return None;
+        }
+        let defaults = Self::default();
+        let instance = std::env::var("PM_INSTANCE_ID")
+            .ok()
+            .map(|v| sanitize_file_stem(&v))
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "default".to_string());
+        Some(Self {
+            path: std::env::var("PM_ACTOR_STATE_PATH")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(format!("data/state/{instance}.sqlite"))),
+            interval: std::env::var("PM_ACTOR_STATE_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.interval),
+            max_age_secs: std::env::var("PM_ACTOR_STATE_MAX_AGE_SECS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_age_secs),
+        })
+    }
+}
+
+enum StoreWrite {
+    Save {
+        scope: String,
+        actor: String,
+        slug: String,
+        saved_at_ms: u64,
+        payload: String,
+    },
+    Clear {
+        scope: String,
+        actor: String,
+    },
+    Flush(std_mpsc::Sender<()>),
+}
+
+/// Shared SQLite handle; cheap to clone across actors.
+#[derive(Clone)]
+pub struct ActorStateStore {
+    conn: Arc<Mutex<Connection>>,
+    writer: std_mpsc::Sender<StoreWrite>,
+    scope: String,
+    max_age_ms: u64,
+}
+
+impl ActorStateStore {
+    pub fn open(path: &Path, max_age_secs: u64) -> rusqlite::Result<Self> {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        Self::init(Connection::open(path)?, max_age_secs)
+    }
+
+    pub fn open_in_memory(max_age_secs: u64) -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?, max_age_secs)
+    }
+
+    /// Open the store from config, logging and returning `None` on failure so
+    /// a broken state file never blocks startup.
+    pub fn open_from_config(cfg: &ActorStateStoreConfig) -> Option<Self> {
+        match Self::open(&cfg.path, cfg.max_age_secs) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                warn!(
+                    "⚠️ actor state store unavailable at {}: {}",
+                    cfg.path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn init(conn: Connection, max_age_secs: u64) -> rusqlite::Result<Self> {
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+             PRAGMA synchronous=NORMAL;",
+        )?;
+        // Rows from the pre-scope layout (one row per actor) are only snapshots;
+        // drop them rather than migrate.
+        if conn
+            .prepare("SELECT scope FROM actor_state LIMIT 0")
+            .is_err()
+        {
+            conn.execute_batch("DROP TABLE IF EXISTS actor_state;")?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS actor_state (
+                 scope TEXT NOT NULL,
+                 actor TEXT NOT NULL,
+                 slug TEXT NOT NULL,
+                 saved_at_ms INTEGER NOT NULL,
+                 payload TEXT NOT NULL,
+                 PRIMARY KEY (scope, actor)
+             );",
+        )?;
+        let conn = Arc::new(Mutex::new(conn));
+        let (writer, rx) = std_mpsc::channel();
+        let writer_conn = Arc::clone(&conn);
+        std::thread::spawn(move || run_writer(writer_conn, rx));
+        Ok(Self {
+            conn,
+            writer,
+            scope: String::new(),
+            max_age_ms: max_age_secs.saturating_mul(1_000),
+        })
+    }
+
+    /// Same file, rows namespaced by `scope` (the worker's market prefix).
+    pub fn scoped(&self, scope: &str) -> Self {
+        Self {
+            scope: scope.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Upsert the latest snapshot of `actor` for `slug`.
+    pub fn save_raw(
+        &self,
+        actor: &str,
+        slug: &str,
+        saved_at_ms: u64,
+        payload: &str,
+    ) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        write_row(&conn, &self.scope, actor, slug, saved_at_ms, payload)
+    }
+
+    pub fn clear_raw(&self, actor: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        delete_row(&conn, &self.scope, actor)
+    }
+
+    /// Block until every queued write has reached SQLite.
+    pub fn flush(&self) {
+        let (ack_tx, ack_rx) = std_mpsc::channel();
+        if self.writer.send(StoreWrite::Flush(ack_tx)).is_ok() {
+            let _ = ack_rx.recv();
+        }
+    }
+
+    fn enqueue(&self, write: StoreWrite) {
+        if self.writer.send(write).is_err() {
+            warn!("⚠️ actor state writer stopped; snapshot dropped");
+        }
+    }
+
+    /// Stored payload and its age, only when the slug matches and the row is fresh.
+    pub fn load_raw(
+        &self,
+        actor: &str,
+        slug: &str,
+        now_ms: u64,
+    ) -> rusqlite::Result<Option<(String, u64)>> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let row = conn
+            .query_row(
+                "SELECT slug, saved_at_ms, payload FROM actor_state
+                 WHERE scope = ?1 AND actor = ?2",
+                params![self.scope, actor],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?.max(0) as u64,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(row.and_then(|(stored_slug, saved_at_ms, payload)| {
+            let age_ms = now_ms.saturating_sub(saved_at_ms);
+            (stored_slug == slug && age_ms <= self.max_age_ms).then_some((payload, age_ms))
+        }))
+    }
+
+    /// Bind the store to one market round.
+    pub fn for_market(&self, slug: &str, interval: Duration) -> ActorStateHandle {
+        ActorStateHandle {
+            store: self.clone(),
+            slug: slug.to_string(),
+            interval,
+        }
+    }
+}
+
+/// A reloaded snapshot together with how long ago it was written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoredState<T> {
+    pub state: T,
+    pub age: Duration,
+}
+
+/// Per-round view of the store handed to each actor via `with_state_store`.
+#[derive(Clone)]
+pub struct ActorStateHandle {
+    store: ActorStateStore,
+    slug: String,
+    interval: Duration,
+}
+
+impl ActorStateHandle {
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn save<T: Serialize>(&self, actor: &str, state: &T) {
+        let payload = match serde_json::to_string(state) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("⚠️ actor state encode failed actor={}: {}", actor, e);
+                return;
+            }
+        };
+        self.store.enqueue(StoreWrite::Save {
+            scope: self.store.scope.clone(),
+            actor: actor.to_string(),
+            slug: self.slug.clone(),
+            saved_at_ms: unix_now_ms(),
+            payload,
+        });
+    }
+
+    /// Drop the row of `actor` once the state it described is finished.
+    pub fn clear(&self, actor: &str) {
+        self.store.enqueue(StoreWrite::Clear {
+            scope: self.store.scope.clone(),
+            actor: actor.to_string(),
+        });
+    }
+
+    pub async fn load<T: DeserializeOwned>(&self, actor: &str) -> Option<RestoredState<T>> {
+        let store = self.store.clone();
+        let (actor_key, slug) = (actor.to_string(), self.slug.clone());
+        // The flush waits on the writer thread; keep it off the async workers.
+        let row = tokio::task::spawn_blocking(move || {
+            store.flush();
+            store.load_raw(&actor_key, &slug, unix_now_ms())
+        })
+        .await;
+        let (payload, age_ms) = match row {
+            Ok(Ok(row)) => row?,
+            Ok(Err(e)) => {
+                warn!("⚠️ actor state load failed actor={}: {}", actor, e);
+                return None;
+            }
+            Err(e) => {
+                warn!("⚠️ actor state load task failed actor={}: {}", actor, e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&payload) {
+            Ok(state) => Some(RestoredState {
+                state,
+                age: Duration::from_millis(age_ms),
+            }),
+            Err(e) => {
+                warn!("⚠️ actor state decode failed actor={}: {}", actor, e);
+                None
+            }
+        }
+    }
+}
+
+fn write_row(
+    conn: &Connection,
+    scope: &str,
+    actor: &str,
+    slug: &str,
+    saved_at_ms: u64,
+    payload: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO actor_state (scope, actor, slug, saved_at_ms, payload)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(scope, actor) DO UPDATE SET
+             slug = excluded.slug,
+             saved_at_ms = excluded.saved_at_ms,
+             payload = excluded.payload",
+        params![scope, actor, slug, saved_at_ms as i64, payload],
+    )?;
+    Ok(())
+}
+
+fn delete_row(conn: &Connection, scope: &str, actor: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM actor_state WHERE scope = ?1 AND actor = ?2",
+        params![scope, actor],
+    )?;
+    Ok(())
+}
+
+fn run_writer(conn: Arc<Mutex<Connection>>, rx: std_mpsc::Receiver<StoreWrite>) {
+    for write in rx {
+        let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+        match write {
+            StoreWrite::Save {
+                scope,
+                actor,
+                slug,
+                saved_at_ms,
+                payload,
+            } => {
+                if let Err(e) = write_row(&conn, &scope, &actor, &slug, saved_at_ms, &payload) {
+                    warn!("⚠️ actor state save failed actor={}: {}", actor, e);
+                }
+            }
+            StoreWrite::Clear { scope, actor } => {
+                if let Err(e) = delete_row(&conn, &scope, &actor) {
+                    warn!("⚠️ actor state clear failed actor={}: {}", actor, e);
+                }
+            }
+            StoreWrite::Flush(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+}
+
+/// Periodic write gate shared by actors that snapshot from their own loops.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StateSaveThrottle {
+    last_saved: Option<Instant>,
+}
+
+impl StateSaveThrottle {
+    pub fn due(&mut self, now: Instant, interval: Duration) -> bool {
+        if self
+            .last_saved
+            .is_some_and(|last| now.saturating_duration_since(last) < interval)
+        {
+            return false;
+        }
+        self.last_saved = Some(now);
+        true
+    }
+}
+
+/// Milliseconds left until `deadline`, or `None` when it already passed.
+pub fn remaining_ms(deadline: Option<Instant>, now: Instant) -> Option<u64> {
+    deadline
+        .map(|d| d.saturating_duration_since(now).as_millis() as u64)
+        .filter(|ms| *ms > 0)
+}
+
+/// Rebuild a deadline from a stored remaining duration minus the restart gap.
+pub fn restore_deadline(remaining_ms: Option<u64>, age: Duration, now: Instant) -> Option<Instant> {
+    let left = Duration::from_millis(remaining_ms?).checked_sub(age)?;
+    (!left.is_zero()).then(|| now + left)
+}
+
+/// Rebuild a past timestamp from a stored "ms ago" plus the restart gap.
+pub fn restore_past(ago_ms: Option<u64>, age: Duration, now: Instant) -> Option<Instant> {
+    now.checked_sub(Duration::from_millis(ago_ms?) + age)
+}
+
+fn sanitize_file_stem(raw: &str) -> String {
+    raw.trim()
+        .chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | '-' => ch,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Probe {
+        cooldown_ms: Option<u64>,
+        ticks: u8,
+    }
+
+    #[tokio::test]
+    async fn store_reloads_only_matching_fresh_rows() {
+        let store = ActorStateStore::open_in_memory(60).unwrap();
+        store
+            .save_raw("oms", "btc-updown-15m-1", 1_000, "{\"a\":1}")
+            .unwrap();
+        store
+            .save_raw("oms", "btc-updown-15m-1", 2_000, "{\"a\":2}")
+            .unwrap();
+
+        let (payload, age) = store
+            .load_raw("oms", "btc-updown-15m-1", 2_500)
+            .unwrap()
+            .unwrap();
+        assert_eq!(payload, "{\"a\":2}");
+        assert_eq!(age, 500);
+        // Other round, other actor and stale rows are all ignored.
+        assert!(store
+            .load_raw("oms", "btc-updown-15m-2", 2_500)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .load_raw("executor", "btc-updown-15m-1", 2_500)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .load_raw("oms", "btc-updown-15m-1", 2_000 + 61_000)
+            .unwrap()
+            .is_none());
+
+        let handle = store.for_market("btc-updown-15m-3", Duration::from_millis(500));
+        let probe = Probe {
+            cooldown_ms: Some(1_500),
+            ticks: 2,
+        };
+        handle.save("coordinator", &probe);
+        let restored = handle.load::<Probe>("coordinator").await.unwrap();
+        assert_eq!(restored.state, probe);
+    }
+
+    #[tokio::test]
+    async fn scoped_workers_keep_separate_rows_in_one_file() {
+        let store = ActorStateStore::open_in_memory(60).unwrap();
+        let btc = store
+            .scoped("btc-updown-5m")
+            .for_market("process", Duration::from_millis(1));
+        let eth = store
+            .scoped("eth-updown-5m")
+            .for_market("process", Duration::from_millis(1));
+        let probe = |ticks| Probe {
+            cooldown_ms: None,
+            ticks,
+        };
+        btc.save("auto_claim", &probe(1));
+        eth.save("auto_claim", &probe(2));
+        assert_eq!(
+            btc.load::<Probe>("auto_claim").await.unwrap().state,
+            probe(1)
+        );
+        assert_eq!(
+            eth.load::<Probe>("auto_claim").await.unwrap().state,
+            probe(2)
+        );
+
+        eth.clear("auto_claim");
+        assert!(eth.load::<Probe>("auto_claim").await.is_none());
+        assert_eq!(
+            btc.load::<Probe>("auto_claim").await.unwrap().state,
+            probe(1)
+        );
+    }
+
+    #[test]
+    fn deadlines_shrink_by_restart_gap() {
+        let now = Instant::now();
+        let gap = Duration::from_millis(400);
+        let until = restore_deadline(Some(1_000), gap, now).unwrap();
+        assert_eq!(until.duration_since(now), Duration::from_millis(600));
+        assert!(restore_deadline(Some(300), gap, now).is_none());
+        assert!(restore_deadline(None, gap, now).is_none());
+        assert_eq!(remaining_ms(Some(now + gap), now), Some(400));
+        assert_eq!(remaining_ms(Some(now), now), None);
+
+        let mut throttle = StateSaveThrottle::default();
+        assert!(throttle.due(now, gap));
+        assert!(!throttle.due(now + Duration::from_millis(100), gap));
+        assert!(throttle.due(now + gap, gap));
+    }
+}
@@ -0,0 +1,1228 @@
+//! Event-driven backtest harness.
+//!
+//! Runs the real `StrategyCoordinator` (and therefore every `StrategyRegistry`
+//! entry), `OrderManager` and `InventoryManager` against historical market
+//! data. Actors are stepped synchronously instead of via their `run()` loops:
+//!
+//!   history → SimVenue (touch fills) → Coordinator::drive_step
+//!           → OrderManagerCmd → OrderManager → ExecutionCmd → SimVenue
+//!           → OrderResult / FillEvent → OrderManager / InventoryManager
+//!
+//! Time is driven by the event timestamps: every actor shares one
+//! `clock::SimClock`, so endgame phases, cooldowns and debounces follow
+//! simulated time and a window runs as fast as the CPU allows.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info};
+
+use super::clock::{Clock, SharedClock, SimClock};
+use super::coordinator::{CoordinatorConfig, StrategyCoordinator};
+use super::fees::{FeeSchedule, LiquidityRole};
+use super::glft::GlftSignalSnapshot;
+use super::inventory::{InventoryConfig, InventoryManager};
+use super::messages::{
+    BidReason, DesiredTarget, ExecutionCmd, ExecutionFeedback, FillEvent, FillSource, FillStatus,
+    InventoryEvent, InventorySnapshot, KillSwitchSignal, MarketDataMsg, OfiSnapshot,
+    OrderManagerCmd, OrderResult, OrderSlot, SlotReleaseEvent, TakerSide, TradeDirection,
+    TradeIntent, TradeUrgency,
+};
+use super::order_manager::OrderManager;
+use super::types::Side;
+
+/// Post-only cross reject cooldown reported back to OMS (mirrors live executor).
+const SIM_POST_ONLY_REJECT_COOLDOWN_MS: u64 = 500;
+/// Taker FAK that finds no executable liquidity.
+const SIM_TAKER_FAIL_COOLDOWN_MS: u64 = 1_000;
+/// Upper bound on actor ping-pong rounds per step (guards against livelock).
+const MAX_SETTLE_ROUNDS: usize = 64;
+const SIZE_EPS: f64 = 1e-9;
+/// Shares available at the touch per book update when history carries no size.
+pub const SIM_TAKER_TOUCH_DEPTH_DEFAULT: f64 = 100.0;
+
+// ─────────────────────────────────────────────────────────
+// Inputs
+// ─────────────────────────────────────────────────────────
+
+/// One historical input. Market data is routed to the venue and the
+/// coordinator; signal snapshots replace the corresponding watch value.
+#[derive(Debug, Clone)]
+pub enum BacktestInput {
+    Market(MarketDataMsg),
+    Ofi(OfiSnapshot),
+    Glft(GlftSignalSnapshot),
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestEvent {
+    /// Simulated UNIX ms. `ts: Instant` fields inside market data are
+    /// rewritten at dispatch time.
+    pub ts_ms: u64,
+    pub input: BacktestInput,
+}
+
+/// One market round to backtest with a fresh set of actors.
+#[derive(Debug, Clone)]
+pub struct BacktestWindow {
+    pub id: String,
+    pub end_ts_ms: u64,
+    /// Resolved winner (`Yes` = UP). `None` leaves residuals unsettled at cost.
+    pub outcome: Option<Side>,
+    pub events: Vec<BacktestEvent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    pub coordinator: CoordinatorConfig,
+    pub inventory: InventoryConfig,
+    /// Fee model applied to simulated fills.
+    pub fees: FeeSchedule,
+    /// Synthetic re-evaluation step while history is silent (ms).
+    pub watchdog_step_ms: u64,
+    /// Taker depth at the touch when market data has no size (0 = uncapped).
+    pub taker_touch_depth: f64,
+}
+
+impl BacktestConfig {
+    pub fn new(coordinator: CoordinatorConfig, inventory: InventoryConfig) -> Self {
+        let watchdog_step_ms = coordinator.watchdog_tick_ms.max(50);
+        Self {
+            coordinator,
+            inventory,
+            fees: FeeSchedule::from_env(),
+            watchdog_step_ms,
+            taker_touch_depth: SIM_TAKER_TOUCH_DEPTH_DEFAULT,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Simulated venue
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimLiquidity {
+    Maker,
+    Taker,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimFill {
+    pub ts_ms: u64,
+    pub order_id: String,
+    pub side: &'static str,
+    pub direction: &'static str,
+    pub maker: bool,
+    pub price: f64,
+    pub size: f64,
+    pub fee: f64,
+    pub rebate: f64,
+}
+
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    order_id: String,
+    price: f64,
+    remaining: f64,
+}
+
+/// Side effects produced by the venue, routed by the harness.
+#[derive(Debug)]
+pub(crate) enum VenueOutput {
+    Result(OrderResult),
+    Feedback(ExecutionFeedback),
+    Fill(FillEvent),
+}
+
+/// Top-of-book venue model.
+///
+/// - Post-only orders that would cross are rejected (`PostOnlyCrossed`).
+/// - Resting BUY fills when the ask trades through its price or a taker SELL
+///   prints at or below it; resting SELL mirrors this on the bid.
+/// - Taker FAK fills at the touch, capped by the intent limit and by the depth
+///   left at the touch: the size of the last depth tick at that price, else
+///   `taker_touch_depth` per book update. Takes never walk past the touch.
+#[derive(Debug)]
+pub(crate) struct SimVenue {
+    book: [(f64, f64); 2],
+    /// Shares left at each side's (bid, ask) touch.
+    touch_depth: [(f64, f64); 2],
+    taker_touch_depth: f64,
+    resting: [Option<RestingOrder>; 4],
+    next_order_id: u64,
+    fees: FeeSchedule,
+    clock: SharedClock,
+    fills: Vec<SimFill>,
+}
+
+impl SimVenue {
+    pub(crate) fn new(fees: FeeSchedule, clock: SharedClock) -> Self {
+        Self {
+            book: Default::default(),
+            touch_depth: Default::default(),
+            taker_touch_depth: f64::INFINITY,
+            resting: Default::default(),
+            next_order_id: 0,
+            fees,
+            clock,
+            fills: Vec::new(),
+        }
+    }
+
+    /// Shares a taker may take at a touch that has no observed size; `0`
+    /// leaves takes uncapped.
+    pub(crate) fn with_taker_touch_depth(mut self, depth: f64) -> Self {
+        self.taker_touch_depth = if depth > 0.0 { depth } else { f64::INFINITY };
+        self
+    }
+
+    pub(crate) fn fills(&self) -> &[SimFill] {
+        &self.fills
+    }
+
+    fn bid(&self, side: Side) -> f64 {
+        self.book[side.index()].0
+    }
+
+    fn ask(&self, side: Side) -> f64 {
+        self.book[side.index()].1
+    }
+
+    fn next_id(&mut self) -> String {
+        self.next_order_id += 1;
+        format!("bt-{:08}", self.next_order_id)
+    }
+
+    /// Update the book from market data and sweep resting orders.
+    pub(crate) fn on_market_data(&mut self, msg: &MarketDataMsg) -> Vec<VenueOutput> {
+        let mut out = Vec::new();
+        match msg {
+            MarketDataMsg::BookTick {
+                yes_bid,
+                yes_ask,
+                no_bid,
+                no_ask,
+                ..
+            } => {
+                let book = [(*yes_bid, *yes_ask), (*no_bid, *no_ask)];
+                for (idx, (bid, ask)) in book.into_iter().enumerate() {
+                    if bid != self.book[idx].0 {
+                        self.touch_depth[idx].0 = self.taker_touch_depth;
+                    }
+                    if ask != self.book[idx].1 {
+                        self.touch_depth[idx].1 = self.taker_touch_depth;
+                    }
+                }
+                self.book = book;
+                for slot in OrderSlot::ALL {
+                    let (bid, ask) = self.book[slot.side.index()];
+                    let crossed = match slot.direction {
+                        TradeDirection::Buy => ask > 0.0,
+                        TradeDirection::Sell => bid > 0.0,
+                    };
+                    if !crossed {
+                        continue;
+                    }
+                    let Some(order) = self.resting[slot.index()].as_ref() else {
+                        continue;
+                    };
+                    let through = match slot.direction {
+                        TradeDirection::Buy => ask <= order.price + SIZE_EPS,
+                        TradeDirection::Sell => bid >= order.price - SIZE_EPS,
+                    };
+                    if through {
+                        let qty = order.remaining;
+                        self.fill_resting(slot, qty, &mut out);
+                    }
+                }
+            }
+            MarketDataMsg::TradeTick {
+                market_side,
+                taker_side,
+                price,
+                size,
+                ..
+            } => {
+                let slot = match taker_side {
+                    TakerSide::Sell => OrderSlot::new(*market_side, TradeDirection::Buy),
+                    TakerSide::Buy => OrderSlot::new(*market_side, TradeDirection::Sell),
+                };
+                if let Some(order) = self.resting[slot.index()].as_ref() {
+                    let touches = match slot.direction {
+                        TradeDirection::Buy => *price <= order.price + SIZE_EPS,
+                        TradeDirection::Sell => *price >= order.price - SIZE_EPS,
+                    };
+                    if touches && *size > 0.0 {
+                        let qty = size.min(order.remaining);
+                        self.fill_resting(slot, qty, &mut out);
+                    }
+                }
+            }
+            MarketDataMsg::BookDepthTick {
+                market_side,
+                best_bid,
+                best_ask,
+                best_bid_size,
+                best_ask_size,
+                ..
+            } => {
+                let (bid, ask) = self.book[market_side.index()];
+                let depth = &mut self.touch_depth[market_side.index()];
+                if let Some(size) = best_bid_size.filter(|_| (*best_bid - bid).abs() <= SIZE_EPS) {
+                    depth.0 = size.max(0.0);
+                }
+                if let Some(size) = best_ask_size
+                    .filter(|_| best_ask.is_some_and(|px| (px - ask).abs() <= SIZE_EPS))
+                {
+                    depth.1 = size.max(0.0);
+                }
+            }
+            _ => {}
+        }
+        out
+    }
+
+    fn fill_resting(&mut self, slot: OrderSlot, qty: f64, out: &mut Vec<VenueOutput>) {
+        let Some(order) = self.resting[slot.index()].as_mut() else {
+            return;
+        };
+        order.remaining -= qty;
+        let price = order.price;
+        let order_id = order.order_id.clone();
+        let done = order.remaining <= SIZE_EPS;
+        if done {
+            self.resting[slot.index()] = None;
+        }
+        self.record_fill(&order_id, slot, price, qty, SimLiquidity::Maker, out);
+        if done {
+            out.push(VenueOutput::Result(OrderResult::OrderFilled { slot }));
+        }
+    }
+
+    fn record_fill(
+        &mut self,
+        order_id: &str,
+        slot: OrderSlot,
+        price: f64,
+        size: f64,
+        liquidity: SimLiquidity,
+        out: &mut Vec<VenueOutput>,
+    ) {
+        let role = match liquidity {
+            SimLiquidity::Maker => LiquidityRole::Maker,
+            SimLiquidity::Taker => LiquidityRole::Taker,
+        };
+        let quote = self.fees.quote(role, price, size, None);
+        self.fills.push(SimFill {
+            ts_ms: self.clock.unix_now_ms(),
+            order_id: order_id.to_string(),
+            side: slot.side.as_str(),
+            direction: match slot.direction {
+                TradeDirection::Buy => "BUY",
+                TradeDirection::Sell => "SELL",
+            },
+            maker: liquidity == SimLiquidity::Maker,
+            price,
+            size,
+            fee: quote.fee,
+            rebate: quote.rebate,
+        });
+        out.push(VenueOutput::Fill(FillEvent {
+            order_id: order_id.to_string(),
+            side: slot.side,
+            direction: slot.direction,
+            filled_size: size,
+            price,
+            status: FillStatus::Confirmed,
+            source: FillSource::Backtest,
+            liquidity: role,
+            fee_rate_bps: None,
+            ts: self.clock.now(),
+        }));
+    }
+
+    /// Apply one OMS execution command.
+    pub(crate) fn execute(&mut self, cmd: ExecutionCmd) -> Vec<VenueOutput> {
+        let mut out = Vec::new();
+        match cmd {
+            ExecutionCmd::ExecuteIntent { intent } => match intent.urgency {
+                TradeUrgency::MakerPostOnly => self.place_maker(intent, &mut out),
+                TradeUrgency::TakerFak => self.take(intent, &mut out),
+            },
+            ExecutionCmd::PlacePostOnlyBid {
+                side,
+                direction,
+                price,
+                size,
+                reason,
+            } => {
+                self.place_maker_raw(side, direction, price, size, reason, &mut out);
+            }
+            ExecutionCmd::PlaceTakerHedge {
+                side,
+                direction,
+                size,
+            } => {
+                self.take_raw(side, direction, size, None, &mut out);
+            }
+            ExecutionCmd::CancelOrder { order_id, .. } => {
+                if let Some(slot) = OrderSlot::ALL.into_iter().find(|slot| {
+                    self.resting[slot.index()]
+                        .as_ref()
+                        .is_some_and(|o| o.order_id == order_id)
+                }) {
+                    self.resting[slot.index()] = None;
+                    out.push(VenueOutput::Result(OrderResult::CancelAck { slot }));
+                }
+            }
+            ExecutionCmd::CancelSlot { slot, .. } => self.cancel_slots(&[slot], &mut out),
+            ExecutionCmd::CancelSide { side, .. } => {
+                self.cancel_slots(&OrderSlot::side_slots(side), &mut out)
+            }
+            ExecutionCmd::CancelAll { .. } => self.cancel_slots(&OrderSlot::ALL, &mut out),
+            ExecutionCmd::ReconcileNow { .. } => {}
+        }
+        out
+    }
+
+    fn cancel_slots(&mut self, slots: &[OrderSlot], out: &mut Vec<VenueOutput>) {
+        for &slot in slots {
+            self.resting[slot.index()] = None;
+            out.push(VenueOutput::Result(OrderResult::CancelAck { slot }));
+        }
+    }
+
+    fn place_maker(&mut self, intent: TradeIntent, out: &mut Vec<VenueOutput>) {
+        let Some(price) = intent.price else {
+            let slot = OrderSlot::new(intent.side, intent.direction);
+            out.push(VenueOutput::Result(OrderResult::OrderFailed {
+                slot,
+                cooldown_ms: SIM_POST_ONLY_REJECT_COOLDOWN_MS,
+            }));
+            return;
+        };
+        self.place_maker_raw(
+            intent.side,
+            intent.direction,
+            price,
+            intent.size,
+            intent.purpose.as_bid_reason(),
+            out,
+        );
+    }
+
+    fn place_maker_raw(
+        &mut self,
+        side: Side,
+        direction: TradeDirection,
+        price: f64,
+        size: f64,
+        reason: BidReason,
+        out: &mut Vec<VenueOutput>,
+    ) {
+        let slot = OrderSlot::new(side, direction);
+        if self.resting[slot.index()].is_some() {
+            out.push(VenueOutput::Result(OrderResult::SlotBusy { slot }));
+            return;
+        }
+        let crosses = match direction {
+            TradeDirection::Buy => self.ask(side) > 0.0 && price >= self.ask(side) - SIZE_EPS,
+            TradeDirection::Sell => self.bid(side) > 0.0 && price <= self.bid(side) + SIZE_EPS,
+        };
+        if crosses || size <= SIZE_EPS {
+            if crosses {
+                out.push(VenueOutput::Feedback(ExecutionFeedback::PostOnlyCrossed {
+                    slot,
+                    ts: self.clock.now(),
+                    rejected_action_price: price,
+                }));
+            }
+            out.push(VenueOutput::Result(OrderResult::OrderFailed {
+                slot,
+                cooldown_ms: SIM_POST_ONLY_REJECT_COOLDOWN_MS,
+            }));
+            return;
+        }
+        let order_id = self.next_id();
+        self.resting[slot.index()] = Some(RestingOrder {
+            order_id,
+            price,
+            remaining: size,
+        });
+        out.push(VenueOutput::Feedback(ExecutionFeedback::OrderAccepted {
+            slot,
+            ts: self.clock.now(),
+        }));
+        out.push(VenueOutput::Result(OrderResult::OrderPlaced {
+            slot,
+            target: DesiredTarget {
+                side,
+                direction,
+                price,
+                size,
+                reason,
+            },
+        }));
+    }
+
+    fn take(&mut self, intent: TradeIntent, out: &mut Vec<VenueOutput>) {
+        self.take_raw(
+            intent.side,
+            intent.direction,
+            intent.size,
+            intent.price,
+            out,
+        );
+    }
+
+    fn take_raw(
+        &mut self,
+        side: Side,
+        direction: TradeDirection,
+        size: f64,
+        limit: Option<f64>,
+        out: &mut Vec<VenueOutput>,
+    ) {
+        let touch = match direction {
+            TradeDirection::Buy => self.ask(side),
+            TradeDirection::Sell => self.bid(side),
+        };
+        let depth = match direction {
+            TradeDirection::Buy => &mut self.touch_depth[side.index()].1,
+            TradeDirection::Sell => &mut self.touch_depth[side.index()].0,
+        };
+        let size = size.min(*depth);
+        let executable = touch > 0.0
+            && size > SIZE_EPS
+            && match (direction, limit) {
+                (_, None) => true,
+                (TradeDirection::Buy, Some(limit)) => touch <= limit + SIZE_EPS,
+                (TradeDirection::Sell, Some(limit)) => touch >= limit - SIZE_EPS,
+            };
+        if !executable {
+            out.push(VenueOutput::Result(OrderResult::TakerHedgeFailed {
+                side,
+                cooldown_ms: SIM_TAKER_FAIL_COOLDOWN_MS,
+            }));
+            return;
+        }
+        *depth -= size;
+        let order_id = self.next_id();
+        let slot = OrderSlot::new(side, direction);
+        self.record_fill(&order_id, slot, touch, size, SimLiquidity::Taker, out);
+        out.push(VenueOutput::Result(OrderResult::TakerHedgeDone { side }));
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Reports
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WindowReport {
+    pub window_id: String,
+    pub outcome: Option<&'static str>,
+    pub events: usize,
+    pub steps: u64,
+    pub fills: usize,
+    pub maker_fills: usize,
+    pub taker_fills: usize,
+    pub buy_notional: f64,
+    pub sell_notional: f64,
+    pub fees: f64,
+    pub rebates: f64,
+    pub yes_qty: f64,
+    pub no_qty: f64,
+    pub paired_qty: f64,
+    pub residual_qty: f64,
+    pub settlement_value: f64,
+    pub pnl: f64,
+}
+
+impl WindowReport {
+    fn from_fills(window: &BacktestWindow, fills: &[SimFill], steps: u64) -> Self {
+        let mut report = Self {
+            window_id: window.id.clone(),
+            outcome: window.outcome.map(|side| side.as_str()),
+            events: window.events.len(),
+            steps,
+            fills: fills.len(),
+            ..Self::default()
+        };
+        for fill in fills {
+            if fill.maker {
+                report.maker_fills += 1;
+            } else {
+                report.taker_fills += 1;
+            }
+            let signed = if fill.direction == "BUY" {
+                report.buy_notional += fill.price * fill.size;
+                fill.size
+            } else {
+                report.sell_notional += fill.price * fill.size;
+                -fill.size
+            };
+            if fill.side == Side::Yes.as_str() {
+                report.yes_qty += signed;
+            } else {
+                report.no_qty += signed;
+            }
+            report.fees += fill.fee;
+            report.rebates += fill.rebate;
+        }
+        report.paired_qty = report.yes_qty.min(report.no_qty).max(0.0);
+        report.residual_qty = (report.yes_qty - report.no_qty).abs();
+        report.settlement_value = match window.outcome {
+            Some(Side::Yes) => report.yes_qty,
+            Some(Side::No) => report.no_qty,
+            // Unknown outcome: only the paired leg has a guaranteed payout.
+            None => report.paired_qty,
+        };
+        report.pnl = report.settlement_value + report.sell_notional + report.rebates
+            - report.buy_notional
+            - report.fees;
+        report
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BacktestSummary {
+    pub windows: usize,
+    pub active_windows: usize,
+    pub fills: usize,
+    pub maker_fills: usize,
+    pub taker_fills: usize,
+    pub fees: f64,
+    pub rebates: f64,
+    pub pnl: f64,
+    pub avg_pnl: f64,
+    pub wins: usize,
+    pub losses: usize,
+}
+
+impl BacktestSummary {
+    pub fn from_reports(reports: &[WindowReport]) -> Self {
+        let mut summary = Self {
+            windows: reports.len(),
+            ..Self::default()
+        };
+        for r in reports {
+            if r.fills > 0 {
+                summary.active_windows += 1;
+            }
+            summary.fills += r.fills;
+            summary.maker_fills += r.maker_fills;
+            summary.taker_fills += r.taker_fills;
+            summary.fees += r.fees;
+            summary.rebates += r.rebates;
+            summary.pnl += r.pnl;
+            if r.pnl > 1e-9 {
+                summary.wins += 1;
+            } else if r.pnl < -1e-9 {
+                summary.losses += 1;
+            }
+        }
+        if summary.windows > 0 {
+            summary.avg_pnl = summary.pnl / summary.windows as f64;
+        }
+        summary
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Harness
+// ─────────────────────────────────────────────────────────
+
+/// One window's worth of wired actors.
+struct Harness {
+    clock: Arc<SimClock>,
+    coordinator: StrategyCoordinator,
+    order_manager: OrderManager,
+    inventory: InventoryManager,
+    venue: SimVenue,
+    om_rx: mpsc::Receiver<OrderManagerCmd>,
+    exec_rx: mpsc::Receiver<ExecutionCmd>,
+    result_tx: mpsc::Sender<OrderResult>,
+    result_rx: mpsc::Receiver<OrderResult>,
+    feedback_tx: mpsc::Sender<ExecutionFeedback>,
+    ofi_tx: watch::Sender<OfiSnapshot>,
+    glft_tx: watch::Sender<GlftSignalSnapshot>,
+    // Held so the actors' unused receivers stay open.
+    _md_tx: watch::Sender<MarketDataMsg>,
+    _winner_hint_tx: mpsc::Sender<MarketDataMsg>,
+    _kill_tx: mpsc::Sender<KillSwitchSignal>,
+    _idle_fill_tx: mpsc::Sender<InventoryEvent>,
+    _idle_cmd_tx: mpsc::Sender<OrderManagerCmd>,
+    _slot_release_tx: mpsc::Sender<SlotReleaseEvent>,
+}
+
+impl Harness {
+    fn new(cfg: &BacktestConfig, clock: Arc<SimClock>, end_ts_ms: u64) -> Self {
+        const CAP: usize = 1024;
+        let shared: SharedClock = clock.clone();
+        let (ofi_tx, ofi_rx) = watch::channel(OfiSnapshot::default());
+        let (inv_tx, inv_rx) = watch::channel(InventorySnapshot::default());
+        let (md_tx, md_rx) = watch::channel(MarketDataMsg::BookTick {
+            yes_bid: 0.0,
+            yes_ask: 0.0,
+            no_bid: 0.0,
+            no_ask: 0.0,
+            depth: None,
+            ts: clock.now(),
+        });
+        let (glft_tx, glft_rx) = watch::channel(GlftSignalSnapshot::default());
+        let (winner_hint_tx, winner_hint_rx) = mpsc::channel(CAP);
+        let (kill_tx, kill_rx) = mpsc::channel(CAP);
+        let (om_tx, om_rx) = mpsc::channel(CAP);
+        let (exec_tx, exec_rx) = mpsc::channel(CAP);
+        let (result_tx, result_rx) = mpsc::channel(CAP);
+        let (feedback_tx, feedback_rx) = mpsc::channel(CAP);
+        let (slot_release_tx, slot_release_rx) = mpsc::channel(CAP);
+        // OMS / inventory are driven through their handlers; their own inbound
+        // receivers are never polled.
+        let (idle_cmd_tx, idle_cmd_rx) = mpsc::channel(1);
+        let (_idle_result_tx, idle_result_rx) = mpsc::channel(1);
+        let (idle_fill_tx, idle_fill_rx) = mpsc::channel(1);
+
+        let mut coordinator_cfg = cfg.coordinator.clone();
+        coordinator_cfg.market_end_ts = Some(end_ts_ms / 1_000);
+        let coordinator = StrategyCoordinator::with_aux_rx(
+            coordinator_cfg,
+            ofi_rx,
+            inv_rx,
+            md_rx,
+            winner_hint_rx,
+            glft_rx,
+            om_tx,
+            kill_rx,
+            feedback_rx,
+            slot_release_rx,
+        )
+        .with_clock(shared.clone());
+        let order_manager = OrderManager::new(
+            idle_cmd_rx,
+            exec_tx,
+            idle_result_rx,
+            slot_release_tx.clone(),
+        )
+        .with_clock(shared.clone());
+        let inventory =
+            InventoryManager::new(cfg.inventory.clone(), idle_fill_rx, inv_tx, None, None)
+                .with_clock(shared.clone());
+
+        Self {
+            clock,
+            coordinator,
+            order_manager,
+            inventory,
+            venue: SimVenue::new(cfg.fees, shared).with_taker_touch_depth(cfg.taker_touch_depth),
+            om_rx,
+            exec_rx,
+            result_tx,
+            result_rx,
+            feedback_tx,
+            ofi_tx,
+            glft_tx,
+            _md_tx: md_tx,
+            _winner_hint_tx: winner_hint_tx,
+            _kill_tx: kill_tx,
+            _idle_fill_tx: idle_fill_tx,
+            _idle_cmd_tx: idle_cmd_tx,
+            _slot_release_tx: slot_release_tx,
+        }
+    }
+
+    fn route(&mut self, outputs: Vec<VenueOutput>) -> bool {
+        let mut any = false;
+        for output in outputs {
+            any = true;
+            match output {
+                VenueOutput::Result(result) => {
+                    let _ = self.result_tx.try_send(result);
+                }
+                VenueOutput::Feedback(feedback) => {
+                    let _ = self.feedback_tx.try_send(feedback);
+                }
+                VenueOutput::Fill(fill) => self.inventory.handle_event(InventoryEvent::Fill(fill)),
+            }
+        }
+        any
+    }
+
+    /// Apply one simulated step at the current clock time, then ping-pong the
+    /// actors until quiescent.
+    async fn step(&mut self, input: Option<BacktestInput>) {
+        let msg = match input {
+            Some(BacktestInput::Market(msg)) => {
+                let msg = restamp(msg, self.clock.now());
+                let outputs = self.venue.on_market_data(&msg);
+                self.route(outputs);
+                Some(msg)
+            }
+            Some(BacktestInput::Ofi(snapshot)) => {
+                let _ = self.ofi_tx.send(snapshot);
+                None
+            }
+            Some(BacktestInput::Glft(snapshot)) => {
+                let _ = self.glft_tx.send(snapshot);
+                None
+            }
+            None => None,
+        };
+        // OMS timers and pending-fill promotion follow simulated time, so they
+        // must advance on event steps too, not only on watchdog steps.
+        self.order_manager.pump_all().await;
+        self.inventory.promote_due_pending();
+        self.coordinator.drive_step(msg).await;
+
+        for _ in 0..MAX_SETTLE_ROUNDS {
+            let mut progressed = false;
+            let mut reevaluate = false;
+            while let Ok(cmd) = self.om_rx.try_recv() {
+                self.order_manager.handle_command(cmd).await;
+                progressed = true;
+            }
+            while let Ok(cmd) = self.exec_rx.try_recv() {
+                let outputs = self.venue.execute(cmd);
+                reevaluate |= self.route(outputs);
+                progressed = true;
+            }
+            while let Ok(result) = self.result_rx.try_recv() {
+                self.order_manager.handle_result(result).await;
+                progressed = true;
+            }
+            if !progressed {
+                break;
+            }
+            if reevaluate {
+                self.coordinator.drive_step(None).await;
+            }
+        }
+    }
+}
+
+/// Rewrite the `ts: Instant` of replayed market data to dispatch time so
+/// staleness guards see it as fresh.
+pub(crate) fn restamp(msg: MarketDataMsg, now: Instant) -> MarketDataMsg {
+    match msg {
+        MarketDataMsg::BookTick {
+            yes_bid,
+            yes_ask,
+            no_bid,
+            no_ask,
+            depth,
+            ..
+        } => MarketDataMsg::BookTick {
+            yes_bid,
+            yes_ask,
+            no_bid,
+            no_ask,
+            depth,
+            ts: now,
+        },
+        MarketDataMsg::BookDepthTick {
+            market_side,
+            best_bid,
+            best_ask,
+            best_bid_size,
+            best_ask_size,
+            best_bid_drop_qty,
+            best_ask_drop_qty,
+            event_time_ms,
+            source_sequence_id,
+            ..
+        } => MarketDataMsg::BookDepthTick {
+            market_side,
+            best_bid,
+            best_ask,
+            best_bid_size,
+            best_ask_size,
+            best_bid_drop_qty,
+            best_ask_drop_qty,
+            event_time_ms,
+            source_sequence_id,
+            ts: now,
+        },
+        MarketDataMsg::TradeTick {
+            asset_id,
+            trade_id,
+            source_sequence_id,
+            event_time_ms,
+            market_side,
+            taker_side,
+            price,
+            size,
+            ..
+        } => MarketDataMsg::TradeTick {
+            asset_id,
+            trade_id,
+            source_sequence_id,
+            event_time_ms,
+            market_side,
+            taker_side,
+            price,
+            size,
+            ts: now,
+        },
+        other => other,
+    }
+}
+
+/// Backtest one window with a fresh set of actors.
+pub async fn run_window(cfg: &BacktestConfig, window: &BacktestWindow) -> WindowReport {
+    let start_ms = window
+        .events
+        .first()
+        .map(|e| e.ts_ms)
+        .unwrap_or(window.end_ts_ms);
+    let clock = SimClock::shared(start_ms);
+    let mut harness = Harness::new(cfg, clock.clone(), window.end_ts_ms);
+    let step_ms = cfg.watchdog_step_ms.max(1);
+    let mut steps = 0u64;
+
+    for event in &window.events {
+        // Synthesize watchdog steps across silent stretches so endgame / cooldown
+        // logic keeps being evaluated.
+        while event.ts_ms > clock.unix_now_ms().saturating_add(step_ms) {
+            clock.advance_to_unix_ms(clock.unix_now_ms() + step_ms);
+            harness.step(None).await;
+            steps += 1;
+        }
+        clock.advance_to_unix_ms(event.ts_ms);
+        harness.step(Some(event.input.clone())).await;
+        steps += 1;
+    }
+    // Run the tail up to market end so endgame phases are exercised.
+    while clock.unix_now_ms().saturating_add(step_ms) <= window.end_ts_ms {
+        clock.advance_to_unix_ms(clock.unix_now_ms() + step_ms);
+        harness.step(None).await;
+        steps += 1;
+    }
+
+    let report = WindowReport::from_fills(window, harness.venue.fills(), steps);
+    debug!(
+        "🧪 Backtest window {} | steps={} fills={} pnl={:+.4}",
+        report.window_id, report.steps, report.fills, report.pnl
+    );
+    report
+}
+
+/// Backtest every window sequentially.
+pub async fn run_windows(cfg: &BacktestConfig, windows: &[BacktestWindow]) -> Vec<WindowReport> {
+    let mut reports = Vec::with_capacity(windows.len());
+    for (idx, window) in windows.iter().enumerate() {
+        let report = run_window(cfg, window).await;
+        info!(
+            "🧪 [{}/{}] {} outcome={} fills={} (maker={} taker={}) yes={:.2} no={:.2} pnl={:+.4}",
+            idx + 1,
+            windows.len(),
+            report.window_id,
+            report.outcome.unwrap_or("?"),
+            report.fills,
+            report.maker_fills,
+            report.taker_fills,
+            report.yes_qty,
+            report.no_qty,
+            report.pnl,
+        );
+        reports.push(report);
+    }
+    reports
+}
+
+// ─────────────────────────────────────────────────────────
+// History loader (same SQLite schema as `pair_arb_backtest`)
+// ─────────────────────────────────────────────────────────
+
+/// Load resolved windows from `settlement_records` + `market_ticks`.
+/// Each tick becomes one `BookTick`; rows with an incomplete book are skipped.
+pub fn load_sqlite_windows(
+    conn: &Connection,
+    limit: usize,
+    skip: usize,
+) -> anyhow::Result<Vec<BacktestWindow>> {
+    let mut settlements_stmt = conn.prepare(
+        "SELECT condition_id, outcome FROM settlement_records
+         WHERE outcome IN ('UP','DOWN') ORDER BY ts_end",
+    )?;
+    let mut settlements: Vec<(String, String)> = settlements_stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(Result::ok)
+        .skip(skip)
+        .collect();
+    if limit > 0 && settlements.len() > limit {
+        settlements.truncate(limit);
+    }
+
+    let mut ticks_stmt = conn.prepare(
+        "SELECT ts, remaining_sec, ask_up, bid_up, ask_down, bid_down
+         FROM market_ticks WHERE condition_id = ?1 ORDER BY ts ASC",
+    )?;
+
+    let mut windows = Vec::new();
+    for (condition_id, outcome) in settlements {
+        let rows: Vec<(i64, f64, f64, f64, f64, f64)> = ticks_stmt
+            .query_map(params![condition_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, Option<f64>>(2)?.unwrap_or(0.0),
+                    row.get::<_, Option<f64>>(3)?.unwrap_or(0.0),
+                    row.get::<_, Option<f64>>(4)?.unwrap_or(0.0),
+                    row.get::<_, Option<f64>>(5)?.unwrap_or(0.0),
+                ))
+            })?
+            .filter_map(Result::ok)
+            .collect();
+        let Some(&(first_ts, first_remaining, ..)) = rows.first() else {
+            continue;
+        };
+        let first_ts_ms = normalize_ts_ms(first_ts);
+        let end_ts_ms = first_ts_ms + (first_remaining.max(0.0) * 1_000.0) as u64;
+        let events: Vec<BacktestEvent> = rows
+            .into_iter()
+            .filter(|&(_, _, ask_up, bid_up, ask_down, bid_down)| {
+                ask_up > 0.0 && bid_up > 0.0 && ask_down > 0.0 && bid_down > 0.0
+            })
+            .map(
+                |(ts, _, ask_up, bid_up, ask_down, bid_down)| BacktestEvent {
+                    ts_ms: normalize_ts_ms(ts),
+                    input: BacktestInput::Market(MarketDataMsg::BookTick {
+                        yes_bid: bid_up,
+                        yes_ask: ask_up,
+                        no_bid: bid_down,
+                        no_ask: ask_down,
+                        depth: None,
+                        ts: Instant::now(),
+                    }),
+                },
+            )
+            .collect();
+        if events.len() < 10 {
+            continue;
+        }
+        windows.push(BacktestWindow {
+            id: condition_id,
+            end_ts_ms,
+            outcome: match outcome.as_str() {
+                "UP" => Some(Side::Yes),
+                "DOWN" => Some(Side::No),
+                _ => None,
+            },
+            events,
+        });
+    }
+    Ok(windows)
+}
+
+/// `market_ticks.ts` is stored in seconds by older collectors, ms by newer ones.
+fn normalize_ts_ms(ts: i64) -> u64 {
+    let ts = ts.max(0) as u64;
+    if ts < 100_000_000_000 {
+        ts * 1_000
+    } else {
+        ts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::fees::taker_fee_per_share;
+
+    fn book(yb: f64, ya: f64, nb: f64, na: f64) -> MarketDataMsg {
+        MarketDataMsg::BookTick {
+            yes_bid: yb,
+            yes_ask: ya,
+            no_bid: nb,
+            no_ask: na,
+            depth: None,
+            ts: Instant::now(),
+        }
+    }
+
+    fn maker_buy(side: Side, price: f64, size: f64) -> ExecutionCmd {
+        ExecutionCmd::ExecuteIntent {
+            intent: TradeIntent {
+                side,
+                direction: TradeDirection::Buy,
+                urgency: TradeUrgency::MakerPostOnly,
+                size,
+                price: Some(price),
+                expected_fill_price: None,
+                purpose: super::super::messages::TradePurpose::Provide,
+                local_unreleased_matched_notional_usdc: 0.0,
+                trace: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_sim_venue_rejects_crossing_post_only() {
+        let mut venue = SimVenue::new(FeeSchedule::default(), SimClock::shared(0));
+        venue.on_market_data(&book(0.45, 0.47, 0.52, 0.54));
+        let out = venue.execute(maker_buy(Side::Yes, 0.47, 5.0));
+        assert!(out.iter().any(|o| matches!(
+            o,
+            VenueOutput::Feedback(ExecutionFeedback::PostOnlyCrossed { .. })
+        )));
+        assert!(out.iter().any(|o| matches!(
+            o,
+            VenueOutput::Result(OrderResult::OrderFailed { slot, .. }) if *slot == OrderSlot::YES_BUY
+        )));
+    }
+
+    #[test]
+    fn test_sim_venue_fills_resting_bid_on_trade_through_and_partial_trade() {
+        let mut venue = SimVenue::new(FeeSchedule::default(), SimClock::shared(0));
+        venue.on_market_data(&book(0.45, 0.47, 0.52, 0.54));
+        let out = venue.execute(maker_buy(Side::No, 0.50, 5.0));
+        assert!(out.iter().any(|o| matches!(
+            o,
+            VenueOutput::Result(OrderResult::OrderPlaced { slot, .. }) if *slot == OrderSlot::NO_BUY
+        )));
+
+        // Taker SELL prints 2 shares at our price → partial fill, order stays live.
+        let out = venue.on_market_data(&MarketDataMsg::TradeTick {
+            asset_id: "no".to_string(),
+            trade_id: None,
+            source_sequence_id: None,
+            event_time_ms: None,
+            market_side: Side::No,
+            taker_side: TakerSide::Sell,
+            price: 0.50,
+            size: 2.0,
+            ts: Instant::now(),
+        });
+        assert_eq!(out.len(), 1);
+        assert!(matches!(&out[0], VenueOutput::Fill(f) if (f.filled_size - 2.0).abs() < 1e-9));
+
+        // Ask trades through → remaining 3 shares fill and the slot is released.
+        let out = venue.on_market_data(&book(0.45, 0.47, 0.48, 0.50));
+        assert!(matches!(&out[0], VenueOutput::Fill(f) if (f.filled_size - 3.0).abs() < 1e-9));
+        assert!(matches!(
+            &out[1],
+            VenueOutput::Result(OrderResult::OrderFilled { slot }) if *slot == OrderSlot::NO_BUY
+        ));
+        assert_eq!(venue.fills().len(), 2);
+        assert!(venue
+            .fills()
+            .iter()
+            .all(|f| f.maker && f.fee == 0.0 && f.rebate > 0.0));
+    }
+
+    #[test]
+    fn test_sim_venue_caps_taker_at_touch_depth() {
+        let mut venue =
+            SimVenue::new(FeeSchedule::default(), SimClock::shared(0)).with_taker_touch_depth(4.0);
+        venue.on_market_data(&book(0.45, 0.47, 0.52, 0.54));
+        let take = |size: f64| ExecutionCmd::PlaceTakerHedge {
+            side: Side::Yes,
+            direction: TradeDirection::Buy,
+            size,
+        };
+
+        // No size in history: the default depth caps the take, then it is used up.
+        let out = venue.execute(take(10.0));
+        assert!(matches!(&out[0], VenueOutput::Fill(f) if (f.filled_size - 4.0).abs() < 1e-9));
+        let out = venue.execute(take(1.0));
+        assert!(matches!(
+            &out[0],
+            VenueOutput::Result(OrderResult::TakerHedgeFailed {
+                side: Side::Yes,
+                ..
+            })
+        ));
+
+        // A depth tick at the touch price replaces the default.
+        venue.on_market_data(&MarketDataMsg::BookDepthTick {
+            market_side: Side::Yes,
+            best_bid: 0.45,
+            best_ask: Some(0.47),
+            best_bid_size: Some(50.0),
+            best_ask_size: Some(2.5),
+            best_bid_drop_qty: 0.0,
+            best_ask_drop_qty: 0.0,
+            event_time_ms: None,
+            source_sequence_id: None,
+            ts: Instant::now(),
+        });
+        let out = venue.execute(take(10.0));
+        assert!(matches!(&out[0], VenueOutput::Fill(f) if (f.filled_size - 2.5).abs() < 1e-9));
+
+        // A new ask level refreshes the default depth.
+        venue.on_market_data(&book(0.45, 0.48, 0.51, 0.53));
+        let out = venue.execute(take(10.0));
+        assert!(matches!(
+            &out[0],
+            VenueOutput::Fill(f) if (f.filled_size - 4.0).abs() < 1e-9 && (f.price - 0.48).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn test_window_report_settles_paired_and_residual() {
+        let window = BacktestWindow {
+            id: "w".to_string(),
+            end_ts_ms: 0,
+            outcome: Some(Side::No),
+            events: Vec::new(),
+        };
+        let fill = |side: Side, price: f64, size: f64, fee: f64| SimFill {
+            ts_ms: 0,
+            order_id: String::new(),
+            side: side.as_str(),
+            direction: "BUY",
+            maker: fee == 0.0,
+            price,
+            size,
+            fee,
+            rebate: 0.0,
+        };
+        let fills = vec![
+            fill(Side::Yes, 0.45, 10.0, 0.0),
+            fill(Side::No, 0.50, 5.0, 0.0),
+            fill(Side::No, 0.50, 5.0, 5.0 * taker_fee_per_share(0.50, 0.07)),
+        ];
+        let report = WindowReport::from_fills(&window, &fills, 0);
+        assert!((report.paired_qty - 10.0).abs() < 1e-9);
+        assert!((report.fees - 0.175).abs() < 1e-9);
+        // 10 pairs pay 10.0; cost 4.5 + 5.0 + fee 0.175.
+        assert!((report.pnl - 0.325).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_run_window_drives_real_strategy_to_fills() {
+        let mut coordinator = CoordinatorConfig::default();
+        coordinator.dry_run = true;
+        let cfg = BacktestConfig::new(coordinator, InventoryConfig::default());
+        let start_ms = 1_700_000_000_000;
+        let mut events = Vec::new();
+        for i in 0..20u64 {
+            events.push(BacktestEvent {
+                ts_ms: start_ms + i * 1_000,
+                input: BacktestInput::Market(book(0.47, 0.49, 0.49, 0.51)),
+            });
+        }
+        // Both asks collapse through any resting bids.
+        events.push(BacktestEvent {
+            ts_ms: start_ms + 21_000,
+            input: BacktestInput::Market(book(0.20, 0.21, 0.20, 0.21)),
+        });
+        let window = BacktestWindow {
+            id: "synthetic".to_string(),
+            end_ts_ms: start_ms + 300_000,
+            outcome: Some(Side::Yes),
+            events,
+        };
+        let report = run_window(&cfg, &window).await;
+        assert!(report.steps >= window.events.len() as u64);
+        assert!(
+            report.maker_fills > 0,
+            "default strategy should rest bids that fill: {report:?}"
+        );
+    }
+}
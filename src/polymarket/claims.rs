@@ -29,11 +29,12 @@ use polymarket_client_sdk::data::Client as DataClient;
 use polymarket_client_sdk::types::{Address, Decimal, B256};
 use polymarket_client_sdk::POLYGON;
 use reqwest::header::{HeaderMap, HeaderValue};
-use rust_decimal::prelude::FromPrimitive;
-use serde::Serialize;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::Sha256;
 
+use crate::polymarket::actor_state_store::{restore_past, RestoredState};
 use crate::polymarket::clob_v2::v2_contract_config;
 
 sol! {
@@ -96,13 +97,24 @@ pub struct ClaimableSummary {
     pub all_conditions: Vec<ClaimableCondition>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct BuilderCredentials {
     pub api_key: String,
     pub secret: String,
     pub passphrase: String,
 }
 
+/// Redacted: configs holding credentials are dumped with `{:#?}`.
+impl std::fmt::Debug for BuilderCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuilderCredentials")
+            .field("api_key", &"***")
+            .field("secret", &"***")
+            .field("passphrase", &"***")
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AutoClaimConfig {
     pub enabled: bool,
@@ -129,6 +141,38 @@ pub struct AutoClaimState {
     pub warned_proxy_mode: bool,
 }
 
+/// Restart-surviving [`AutoClaimState`]; `last_run` is stored as "ms ago" so
+/// the run interval keeps throttling across a restart.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoClaimStateSnapshot {
+    pub last_run_ago_ms: Option<u64>,
+    pub warned_safe_mode: bool,
+    pub warned_builder_creds_partial: bool,
+    pub warned_builder_creds_missing: bool,
+    pub warned_proxy_mode: bool,
+}
+
+impl AutoClaimState {
+    pub fn export_state(&self) -> AutoClaimStateSnapshot {
+        AutoClaimStateSnapshot {
+            last_run_ago_ms: self.last_run.map(|ts| ts.elapsed().as_millis() as u64),
+            warned_safe_mode: self.warned_safe_mode,
+            warned_builder_creds_partial: self.warned_builder_creds_partial,
+            warned_builder_creds_missing: self.warned_builder_creds_missing,
+            warned_proxy_mode: self.warned_proxy_mode,
+        }
+    }
+
+    pub fn restore_state(&mut self, restored: RestoredState<AutoClaimStateSnapshot>) {
+        let state = restored.state;
+        self.last_run = restore_past(state.last_run_ago_ms, restored.age, Instant::now());
+        self.warned_safe_mode = state.warned_safe_mode;
+        self.warned_builder_creds_partial = state.warned_builder_creds_partial;
+        self.warned_builder_creds_missing = state.warned_builder_creds_missing;
+        self.warned_proxy_mode = state.warned_proxy_mode;
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct AutoClaimRunResult {
     pub positions: usize,
@@ -167,33 +211,37 @@ impl AutoClaimRunResult {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct RedeemExecutionEvidence {
     pub condition_id: String,
     pub execution_mode: String,
     pub relayer_tx_id: Option<String>,
     pub tx_hash: Option<String>,
     pub confirmed: bool,
+    /// Data API value of the redeemed positions at submit time.
+    pub value_usdc: f64,
 }
 
 impl RedeemExecutionEvidence {
-    fn eoa(condition_id: B256, tx_hash: impl ToString) -> Self {
+    fn eoa(c: &ClaimableCondition, tx_hash: impl ToString) -> Self {
         Self {
-            condition_id: condition_id.to_string(),
+            condition_id: c.condition_id.to_string(),
             execution_mode: "eoa_onchain".to_string(),
             relayer_tx_id: None,
             tx_hash: Some(tx_hash.to_string()),
             confirmed: true,
+            value_usdc: c.total_value.to_f64().unwrap_or(0.0),
         }
     }
 
-    fn safe_relayer(condition_id: B256, relayer_tx_id: String) -> Self {
+    fn safe_relayer(c: &ClaimableCondition, relayer_tx_id: String) -> Self {
         Self {
-            condition_id: condition_id.to_string(),
+            condition_id: c.condition_id.to_string(),
             execution_mode: "safe_relayer".to_string(),
             relayer_tx_id: Some(relayer_tx_id),
             tx_hash: None,
             confirmed: false,
+            value_usdc: c.total_value.to_f64().unwrap_or(0.0),
         }
     }
 
@@ -992,10 +1040,7 @@ async fn run_eoa_onchain_claims(
                 .build();
             match neg_risk.redeem_neg_risk(&req).await {
                 Ok(resp) => {
-                    evidence.push(RedeemExecutionEvidence::eoa(
-                        c.condition_id,
-                        resp.transaction_hash,
-                    ));
+                    evidence.push(RedeemExecutionEvidence::eoa(&c, resp.transaction_hash));
                     tracing::info!(
                         "✅ AUTO-CLAIM neg-risk success: condition={} tx={} block={}",
                         c.condition_id,
@@ -1013,10 +1058,7 @@ async fn run_eoa_onchain_claims(
             let req = RedeemPositionsRequest::for_binary_market(collateral, c.condition_id);
             match standard.redeem_positions(&req).await {
                 Ok(resp) => {
-                    evidence.push(RedeemExecutionEvidence::eoa(
-                        c.condition_id,
-                        resp.transaction_hash,
-                    ));
+                    evidence.push(RedeemExecutionEvidence::eoa(&c, resp.transaction_hash));
                     tracing::info!(
                         "✅ AUTO-CLAIM success: condition={} tx={} block={}",
                         c.condition_id,
@@ -1110,8 +1152,7 @@ async fn run_safe_relayer_claims(
         )
         .await
         .with_context(|| format!("relayer submit failed for condition {}", c.condition_id))?;
-        let mut redeem_evidence =
-            RedeemExecutionEvidence::safe_relayer(c.condition_id, tx_id.clone());
+        let mut redeem_evidence = RedeemExecutionEvidence::safe_relayer(&c, tx_id.clone());
 
         if !cfg.relayer_wait_confirm {
             tracing::info!(
@@ -1376,6 +1417,20 @@ pub async fn maybe_auto_claim(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_builder_credentials_debug_is_redacted() {
+        let creds = BuilderCredentials {
+            api_key: "key-123".to_string(),
+            secret: "c2VjcmV0".to_string(),
+            passphrase: "pass-456".to_string(),
+        };
+        let rendered = format!("{:#?}", creds);
+        for value in ["key-123", "c2VjcmV0", "pass-456"] {
+            assert!(!rendered.contains(value), "{rendered}");
+        }
+        assert!(rendered.contains("***"));
+    }
+
     #[test]
     fn test_auto_claim_run_result_success_live_mode() {
         let outcome = AutoClaimRunResult {
@@ -1408,6 +1463,7 @@ mod tests {
             relayer_tx_id: None,
             tx_hash: Some("0xtx".to_string()),
             confirmed: true,
+            value_usdc: 0.0,
         };
 
         assert!(evidence.has_tx_hash());
@@ -1422,6 +1478,7 @@ mod tests {
             relayer_tx_id: Some("relayer-1".to_string()),
             tx_hash: None,
             confirmed: true,
+            value_usdc: 0.0,
         };
 
         assert!(!evidence.has_tx_hash());
@@ -1436,6 +1493,7 @@ mod tests {
             relayer_tx_id: Some("relayer-1".to_string()),
             tx_hash: None,
             confirmed: false,
+            value_usdc: 0.0,
         };
 
         assert!(!evidence.has_tx_hash());
@@ -0,0 +1,137 @@
+//! Pluggable time source for the actors.
+//!
+//! Live wiring uses `SystemClock` (plain `Instant::now()` / `SystemTime::now()`).
+//! Tests, replays and the backtest harness inject a `SimClock` and advance it
+//! explicitly, so endgame phases, cooldowns and toxicity holds can be exercised
+//! exactly at their boundaries without sleeping.
+//!
+//! Only decision-relevant time goes through the clock. tokio `interval` timers
+//! that merely wake an actor loop stay on the runtime clock; synchronous drivers
+//! (e.g. `backtest`) call the actor handlers directly instead.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Monotonic time, drop-in for `Instant::now()`.
+    fn now(&self) -> Instant;
+
+    /// Wall time, drop-in for `SystemTime::now()`.
+    fn system_now(&self) -> SystemTime;
+
+    /// Drop-in for `since.elapsed()`.
+    fn elapsed(&self, since: Instant) -> Duration {
+        self.now().saturating_duration_since(since)
+    }
+
+    fn unix_now_secs(&self) -> u64 {
+        self.system_now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn unix_now_ms(&self) -> u64 {
+        self.system_now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Real time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Manually advanced time. `now()` is anchored to the `Instant` at creation and
+/// `system_now()` to `start_unix_ms`; both move together and never rewind.
+#[derive(Debug)]
+pub struct SimClock {
+    base_instant: Instant,
+    base_unix_ms: u64,
+    elapsed_ns: AtomicU64,
+}
+
+impl SimClock {
+    pub fn new(start_unix_ms: u64) -> Self {
+        Self {
+            base_instant: Instant::now(),
+            base_unix_ms: start_unix_ms,
+            elapsed_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub fn shared(start_unix_ms: u64) -> Arc<Self> {
+        Arc::new(Self::new(start_unix_ms))
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_ns.load(Ordering::Acquire))
+    }
+
+    pub fn advance(&self, dt: Duration) {
+        let dt_ns = dt.as_nanos().min(u128::from(u64::MAX)) as u64;
+        self.elapsed_ns.fetch_add(dt_ns, Ordering::AcqRel);
+    }
+
+    /// Advance to `unix_ms`; returns the simulated time elapsed (zero if in the past).
+    pub fn advance_to_unix_ms(&self, unix_ms: u64) -> Duration {
+        let target_ns = unix_ms
+            .saturating_sub(self.base_unix_ms)
+            .saturating_mul(1_000_000);
+        let prev_ns = self.elapsed_ns.fetch_max(target_ns, Ordering::AcqRel);
+        Duration::from_nanos(target_ns.saturating_sub(prev_ns))
+    }
+}
+
+impl Clock for SimClock {
+    fn now(&self) -> Instant {
+        self.base_instant + self.elapsed()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.base_unix_ms) + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sim_clock_moves_instant_and_wall_time_together() {
+        let clock = SimClock::new(1_700_000_000_000);
+        let t0 = clock.now();
+        assert_eq!(clock.unix_now_ms(), 1_700_000_000_000);
+
+        clock.advance(Duration::from_millis(1_500));
+        assert_eq!(clock.now() - t0, Duration::from_millis(1_500));
+        assert_eq!(clock.unix_now_secs(), 1_700_000_001);
+
+        assert_eq!(
+            clock.advance_to_unix_ms(1_700_000_005_000),
+            Duration::from_millis(3_500)
+        );
+        // Never rewinds.
+        assert_eq!(clock.advance_to_unix_ms(1_700_000_000_000), Duration::ZERO);
+        assert_eq!(clock.unix_now_ms(), 1_700_000_005_000);
+    }
+}
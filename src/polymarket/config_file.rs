@@ -0,0 +1,487 @@
+//! Layered TOML/JSON config file for `polymarket_v2`.
+//!
+//! Every runtime config (`CoordinatorConfig`, `OfiConfig`, `InventoryConfig`,
+//! `RecorderConfig`, `AutoClaimConfig`, executor / bin settings) is read from
+//! `PM_*` / `POLYMARKET_*` env vars, so the file is a structured source of those
+//! same keys rather than a second schema. Layers, lowest to highest priority:
+//!
+//! 1. top-level keys and group tables (`[glft] gamma = 0.1` → `PM_GLFT_GAMMA`)
+//! 2. `[strategy.<name>]` matching the active `PM_STRATEGY` (aliases accepted)
+//! 3. `[market."<prefix>"]` whose name prefixes the active market slug/prefix
+//!    (longest match wins)
+//! 4. the process environment (incl. `.env`) — always wins
+//!
+//! Keys may be written as full env names (`PM_BID_SIZE`, `POLYMARKET_REST_URL`)
+//! or bare lowercase (`bid_size` → `PM_BID_SIZE`). Arrays join with commas.
+//!
+//! Resolved keys missing from the environment are exported into it before any
+//! config is built. Multi-market supervisor children inherit those exports; the
+//! `PM_CONFIG_FILE_KEYS` fingerprint lets a child re-resolve them against its own
+//! `[market.*]` section while still honouring values the supervisor overrode.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde_json::Value;
+
+use super::strategy::StrategyKind;
+
+pub const CONFIG_FILE_ENV: &str = "PM_CONFIG_FILE";
+const CONFIG_FILE_KEYS_ENV: &str = "PM_CONFIG_FILE_KEYS";
+
+type KeyValues = BTreeMap<String, String>;
+
+/// Parsed config file, one flat env-key map per layer.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    base: KeyValues,
+    strategies: BTreeMap<String, KeyValues>,
+    markets: BTreeMap<String, KeyValues>,
+}
+
+/// Where an effective key came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    Env,
+    FileBase,
+    FileStrategy(String),
+    FileMarket(String),
+}
+
+impl ConfigOrigin {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Env => "env".to_string(),
+            Self::FileBase => "file".to_string(),
+            Self::FileStrategy(name) => format!("file:strategy.{}", name),
+            Self::FileMarket(name) => format!("file:market.{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectiveEntry {
+    pub key: String,
+    pub value: String,
+    pub origin: ConfigOrigin,
+}
+
+/// Outcome of `apply_config_file`: the sections picked and every effective key.
+#[derive(Debug, Clone)]
+pub struct AppliedConfig {
+    pub path: PathBuf,
+    pub strategy_section: Option<String>,
+    pub market_section: Option<String>,
+    /// Keys this process exported from the file.
+    pub exported: usize,
+    pub entries: Vec<EffectiveEntry>,
+}
+
+pub(crate) fn normalize_key(raw: &str, group: Option<&str>) -> String {
+    let upper = raw.trim().replace(['-', '.'], "_").to_ascii_uppercase();
+    if upper.starts_with("PM_") || upper.starts_with("POLYMARKET_") {
+        return upper;
+    }
+    match group {
+        Some(g) => format!("PM_{}_{}", g.replace('-', "_").to_ascii_uppercase(), upper),
+        None => format!("PM_{}", upper),
+    }
+}
+
+fn scalar_to_env(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(scalar_to_env)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Value::Null | Value::Object(_) => None,
+    }
+}
+
+/// Flatten one layer. Nested tables become key groups (`[glft]`, `[pair_arb]`).
+fn flatten_layer(obj: &serde_json::Map<String, Value>, out: &mut KeyValues) -> anyhow::Result<()> {
+    for (key, value) in obj {
+        match value {
+            Value::Object(group) => {
+                for (inner, v) in group {
+                    let scalar = scalar_to_env(v).with_context(|| {
+                        format!("config key {}.{} must be a scalar or array", key, inner)
+                    })?;
+                    out.insert(normalize_key(inner, Some(key)), scalar);
+                }
+            }
+            other => {
+                let scalar = scalar_to_env(other)
+                    .with_context(|| format!("config key {} must not be null", key))?;
+                out.insert(normalize_key(key, None), scalar);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn toml_value_to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => serde_json::Number::from_f64(*f.value())
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(arr) => Value::Array(arr.iter().map(toml_value_to_json).collect()),
+        toml_edit::Value::InlineTable(t) => Value::Object(
+            t.iter()
+                .map(|(k, v)| (k.to_string(), toml_value_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn toml_item_to_json(item: &toml_edit::Item) -> Value {
+    match item {
+        toml_edit::Item::None => Value::Null,
+        toml_edit::Item::Value(v) => toml_value_to_json(v),
+        toml_edit::Item::Table(t) => Value::Object(
+            t.iter()
+                .map(|(k, v)| (k.to_string(), toml_item_to_json(v)))
+                .collect(),
+        ),
+        toml_edit::Item::ArrayOfTables(arr) => Value::Array(
+            arr.iter()
+                .map(|t| toml_item_to_json(&toml_edit::Item::Table(t.clone())))
+                .collect(),
+        ),
+    }
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let is_json = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+        let mut cfg = Self::parse_str(&text, is_json)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        cfg.path = path.to_path_buf();
+        Ok(cfg)
+    }
+
+    pub fn parse_str(text: &str, is_json: bool) -> anyhow::Result<Self> {
+        let root = if is_json {
+            serde_json::from_str::<Value>(text)?
+        } else {
+            let doc = text.parse::<toml_edit::DocumentMut>()?;
+            toml_item_to_json(doc.as_item())
+        };
+        let Value::Object(mut root) = root else {
+            anyhow::bail!("config root must be a table/object");
+        };
+
+        let mut cfg = Self::default();
+        for (section, target) in [
+            ("strategy", &mut cfg.strategies),
+            ("market", &mut cfg.markets),
+        ] {
+            let Some(value) = root.remove(section) else {
+                continue;
+            };
+            let Value::Object(entries) = value else {
+                anyhow::bail!("[{}] must be a table of named sections", section);
+            };
+            for (name, layer) in entries {
+                let Value::Object(layer) = layer else {
+                    anyhow::bail!("[{}.{}] must be a table", section, name);
+                };
+                let mut kv = KeyValues::new();
+                flatten_layer(&layer, &mut kv)?;
+                target.insert(name, kv);
+            }
+        }
+        flatten_layer(&root, &mut cfg.base)?;
+        Ok(cfg)
+    }
+
+    fn strategy_section(&self, strategy: &str) -> Option<(&String, &KeyValues)> {
+        let active = StrategyKind::parse(strategy);
+        self.strategies.iter().find(|(name, _)| match active {
+            Some(kind) => StrategyKind::parse(name) == Some(kind),
+            None => name.eq_ignore_ascii_case(strategy.trim()),
+        })
+    }
+
+    fn market_section(&self, slug: &str) -> Option<(&String, &KeyValues)> {
+        let slug = slug.trim().to_ascii_lowercase();
+        self.markets
+            .iter()
+            .filter(|(name, _)| !name.is_empty() && slug.starts_with(&name.to_ascii_lowercase()))
+            .max_by_key(|(name, _)| name.len())
+    }
+
+    /// Merge file layers for a given environment. `env` answers lookups for keys
+    /// that come from the real environment (they select sections and win).
+    pub fn resolve(&self, env: &dyn Fn(&str) -> Option<String>) -> AppliedConfig {
+        let lookup = |key: &str, layers: &[&KeyValues]| -> Option<String> {
+            env(key).or_else(|| layers.iter().rev().find_map(|l| l.get(key).cloned()))
+        };
+        let slug = lookup("POLYMARKET_MARKET_SLUG", &[&self.base])
+            .or_else(|| lookup("POLYMARKET_MARKET_PREFIX", &[&self.base]))
+            .filter(|s| !s.trim().is_empty());
+        let market = slug.as_deref().and_then(|s| self.market_section(s));
+        let market_layer: Vec<&KeyValues> = market.iter().map(|(_, kv)| *kv).collect();
+        let mut strategy_layers = vec![&self.base];
+        strategy_layers.extend(market_layer.iter().copied());
+        let strategy =
+            lookup("PM_STRATEGY", &strategy_layers).and_then(|s| self.strategy_section(&s));
+
+        let mut merged: BTreeMap<String, (String, ConfigOrigin)> = BTreeMap::new();
+        for (k, v) in &self.base {
+            merged.insert(k.clone(), (v.clone(), ConfigOrigin::FileBase));
+        }
+        if let Some((name, kv)) = strategy {
+            for (k, v) in kv {
+                merged.insert(
+                    k.clone(),
+                    (v.clone(), ConfigOrigin::FileStrategy(name.clone())),
+                );
+            }
+        }
+        if let Some((name, kv)) = market {
+            for (k, v) in kv {
+                merged.insert(
+                    k.clone(),
+                    (v.clone(), ConfigOrigin::FileMarket(name.clone())),
+                );
+            }
+        }
+        let entries = merged
+            .into_iter()
+            .map(|(key, (value, origin))| match env(&key) {
+                Some(env_value) => EffectiveEntry {
+                    key,
+                    value: env_value,
+                    origin: ConfigOrigin::Env,
+                },
+                None => EffectiveEntry { key, value, origin },
+            })
+            .collect();
+        AppliedConfig {
+            path: self.path.clone(),
+            strategy_section: strategy.map(|(name, _)| name.clone()),
+            market_section: market.map(|(name, _)| name.clone()),
+            exported: 0,
+            entries,
+        }
+    }
+}
+
+/// `--config <path>` on the command line, else `PM_CONFIG_FILE`.
+pub fn config_file_path_from_args_or_env() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(a) = args.next() {
+        if a == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = a.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    std::env::var(CONFIG_FILE_ENV)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Load `path`, resolve it against the current environment and export every
+/// file-derived key that the environment does not already set.
+pub fn apply_config_file(path: &Path) -> anyhow::Result<AppliedConfig> {
+    let file = ConfigFile::load(path)?;
+    // Values a parent process exported from the file are not "real" env: a
+    // supervisor child re-resolves them unless the parent changed them.
+    let inherited: HashMap<String, String> = std::env::var(CONFIG_FILE_KEYS_ENV)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    let env_lookup = |key: &str| -> Option<String> {
+        let value = std::env::var(key).ok()?;
+        match inherited.get(key) {
+            Some(exported) if *exported == value => None,
+            _ => Some(value),
+        }
+    };
+    let mut applied = file.resolve(&env_lookup);
+
+    let mut exported: BTreeMap<String, String> = BTreeMap::new();
+    for entry in &applied.entries {
+        if entry.origin != ConfigOrigin::Env {
+            std::env::set_var(&entry.key, &entry.value);
+            exported.insert(entry.key.clone(), entry.value.clone());
+        }
+    }
+    applied.exported = exported.len();
+    std::env::set_var(CONFIG_FILE_ENV, path);
+    std::env::set_var(
+        CONFIG_FILE_KEYS_ENV,
+        serde_json::to_string(&exported).unwrap_or_default(),
+    );
+    Ok(applied)
+}
+
+fn is_secret_key(key: &str) -> bool {
+    [
+        "PRIVATE_KEY",
+        "SECRET",
+        "PASSPHRASE",
+        "API_KEY",
+        "PASSWORD",
+        "TOKEN",
+    ]
+    .iter()
+    .any(|needle| key.contains(needle))
+}
+
+/// `KEY=value  # origin` lines for every `PM_*` / `POLYMARKET_*` key in effect.
+/// Secrets are masked.
+pub fn render_effective_env(applied: Option<&AppliedConfig>) -> String {
+    let mut rows: BTreeMap<String, (String, String)> = std::env::vars()
+        .filter(|(k, _)| {
+            (k.starts_with("PM_") || k.starts_with("POLYMARKET_")) && k != CONFIG_FILE_KEYS_ENV
+        })
+        .map(|(k, v)| (k, (v, ConfigOrigin::Env.label())))
+        .collect();
+    if let Some(applied) = applied {
+        for entry in &applied.entries {
+            rows.insert(
+                entry.key.clone(),
+                (entry.value.clone(), entry.origin.label()),
+            );
+        }
+    }
+    let mut out = String::new();
+    if let Some(applied) = applied {
+        let _ = writeln!(
+            out,
+            "# config_file={} strategy_section={} market_section={}",
+            applied.path.display(),
+            applied.strategy_section.as_deref().unwrap_or("-"),
+            applied.market_section.as_deref().unwrap_or("-"),
+        );
+    }
+    for (key, (value, origin)) in rows {
+        let shown = if is_secret_key(&key) && !value.is_empty() {
+            "***".to_string()
+        } else {
+            value
+        };
+        let _ = writeln!(out, "{}={}  # {}", key, shown, origin);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+PM_STRATEGY = "pair_arb"
+bid_size = 5
+PM_DRY_RUN = true
+PM_MULTI_MARKET_PREFIXES = ["btc-updown-15m", "eth-updown-15m"]
+
+[glft]
+gamma = 0.12
+
+[strategy.pair_arb]
+PM_PAIR_TARGET = 0.97
+bid_size = 8
+
+[strategy.glft_mm]
+PM_PAIR_TARGET = 0.99
+
+[market."btc-updown"]
+PM_MAX_NET_DIFF = 40
+
+[market."btc-updown-15m"]
+bid_size = 12
+"#;
+
+    fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |k: &str| map.get(k).cloned()
+    }
+
+    fn value_of<'a>(applied: &'a AppliedConfig, key: &str) -> Option<&'a EffectiveEntry> {
+        applied.entries.iter().find(|e| e.key == key)
+    }
+
+    #[test]
+    fn layers_apply_base_then_strategy_then_longest_market_then_env() {
+        let file = ConfigFile::parse_str(SAMPLE, false).unwrap();
+        let applied = file.resolve(&env_of(&[(
+            "POLYMARKET_MARKET_SLUG",
+            "btc-updown-15m-1760000000",
+        )]));
+        assert_eq!(applied.strategy_section.as_deref(), Some("pair_arb"));
+        assert_eq!(applied.market_section.as_deref(), Some("btc-updown-15m"));
+
+        let bid = value_of(&applied, "PM_BID_SIZE").unwrap();
+        assert_eq!(bid.value, "12");
+        assert_eq!(
+            bid.origin,
+            ConfigOrigin::FileMarket("btc-updown-15m".into())
+        );
+        assert_eq!(value_of(&applied, "PM_PAIR_TARGET").unwrap().value, "0.97");
+        assert_eq!(value_of(&applied, "PM_GLFT_GAMMA").unwrap().value, "0.12");
+        assert_eq!(
+            value_of(&applied, "PM_MULTI_MARKET_PREFIXES")
+                .unwrap()
+                .value,
+            "btc-updown-15m,eth-updown-15m"
+        );
+        assert_eq!(value_of(&applied, "PM_DRY_RUN").unwrap().value, "true");
+        // Shorter market prefix is not layered underneath the longest match.
+        assert!(value_of(&applied, "PM_MAX_NET_DIFF").is_none());
+
+        let with_env = file.resolve(&env_of(&[("PM_STRATEGY", "glft-mm"), ("PM_BID_SIZE", "3")]));
+        assert_eq!(with_env.strategy_section.as_deref(), Some("glft_mm"));
+        assert_eq!(with_env.market_section, None);
+        assert_eq!(value_of(&with_env, "PM_PAIR_TARGET").unwrap().value, "0.99");
+        let bid = value_of(&with_env, "PM_BID_SIZE").unwrap();
+        assert_eq!((bid.value.as_str(), &bid.origin), ("3", &ConfigOrigin::Env));
+    }
+
+    #[test]
+    fn json_files_share_the_toml_layout() {
+        let json = r#"{
+            "bid_size": 5,
+            "strategy": {"pair_arb": {"PM_PAIR_TARGET": 0.96}},
+            "market": {"eth-updown": {"POLYMARKET_MARKET_PREFIX": "eth-updown-5m"}}
+        }"#;
+        let file = ConfigFile::parse_str(json, true).unwrap();
+        let applied = file.resolve(&env_of(&[
+            ("PM_STRATEGY", "pair_arb"),
+            ("POLYMARKET_MARKET_SLUG", "eth-updown-5m"),
+        ]));
+        assert_eq!(value_of(&applied, "PM_PAIR_TARGET").unwrap().value, "0.96");
+        assert_eq!(value_of(&applied, "PM_BID_SIZE").unwrap().value, "5");
+        assert_eq!(applied.market_section.as_deref(), Some("eth-updown"));
+
+        assert!(ConfigFile::parse_str(r#"{"strategy": 3}"#, true).is_err());
+        assert!(ConfigFile::parse_str("bid_size = ", false).is_err());
+    }
+}
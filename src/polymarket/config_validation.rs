@@ -0,0 +1,818 @@
+//! Strict config validation for `polymarket_v2`.
+//!
+//! Config parsing is deliberately forgiving: an unparsable `PM_*` value keeps the
+//! default, out-of-range values are ignored with a warning, and
+//! `CoordinatorConfig::finalize_invariants` clamps or swaps inconsistent pairs
+//! (seed price lo/hi, endgame windows). That is the right behaviour for a long
+//! running bot, but it hides typos such as `PM_BID_SZIE` or `PM_DRY_RUN=ture`.
+//!
+//! This module keeps a registry of every env key the crate reads together with
+//! the value type it expects, plus a process-wide collector that config loaders
+//! feed whenever they ignore, clamp or override a setting (`CoordinatorConfig`,
+//! `InventoryConfig`, `OfiConfig`, `ExecutorTunables`, `FeeSchedule`,
+//! `PriceAggConfig`, `RtdsPoolConfig` and `DataStreamsVerifier` report through
+//! `env_checked`, `clamp_reported`, `at_least_reported` or
+//! `report_config_issue`). With `PM_CONFIG_STRICT=1` (or `--strict-config`)
+//! startup builds them all via `build_configs_and_collect_issues`, gathers
+//! everything into one report and refuses to run; otherwise unknown keys are
+//! only logged.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write as _};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use super::coordinator::CoordinatorConfig;
+use super::data_streams::DataStreamsVerifier;
+use super::executor::ExecutorTunables;
+use super::fees::FeeSchedule;
+use super::inventory::InventoryConfig;
+use super::ofi::OfiConfig;
+use super::price_agg::PriceAggConfig;
+use super::rtds_pool::{RtdsPoolConfig, RTDS_POOL_STALL_MS_DEFAULT};
+
+/// Env var that turns strict validation on.
+pub const CONFIG_STRICT_ENV: &str = "PM_CONFIG_STRICT";
+/// CLI flag equivalent of `PM_CONFIG_STRICT=1`.
+pub const CONFIG_STRICT_FLAG: &str = "--strict-config";
+
+/// Value type a config key is parsed as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvKind {
+    Bool,
+    Unsigned,
+    Signed,
+    Float,
+    /// Free-form (urls, paths, ids, enum names validated by their own loader).
+    Text,
+}
+
+impl EnvKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Unsigned => "unsigned integer",
+            Self::Signed => "integer",
+            Self::Float => "number",
+            Self::Text => "text",
+        }
+    }
+
+    /// Whether `raw` is acceptable for this kind. Empty values are treated as
+    /// unset by every loader and therefore always pass.
+    pub fn accepts(self, raw: &str) -> bool {
+        let v = raw.trim();
+        if v.is_empty() {
+            return true;
+        }
+        match self {
+            Self::Bool => matches!(
+                v.to_ascii_lowercase().as_str(),
+                "0" | "1" | "true" | "false" | "yes" | "no" | "on" | "off"
+            ),
+            Self::Unsigned => v.parse::<u64>().is_ok(),
+            Self::Signed => v.parse::<i64>().is_ok(),
+            Self::Float => v.parse::<f64>().map(|f| f.is_finite()).unwrap_or(false),
+            Self::Text => true,
+        }
+    }
+}
+
+/// Every `PM_*` / `POLYMARKET_*` key read anywhere in the crate.
+/// `tests::registry_covers_all_env_literals` keeps this in sync with the source.
+pub const KNOWN_ENV_KEYS: &[(&str, EnvKind)] = &[
+    ("PM_ACTOR_STATE_ENABLED", EnvKind::Bool),
+    ("PM_ACTOR_STATE_INTERVAL_MS", EnvKind::Unsigned),
+    ("PM_ACTOR_STATE_MAX_AGE_SECS", EnvKind::Unsigned),
+    ("PM_ACTOR_STATE_PATH", EnvKind::Text),
+    ("PM_ALLOW_ZERO_ALLOWANCE", EnvKind::Bool),
+    ("PM_AS_SKEW_FACTOR", EnvKind::Float),
+    ("PM_AS_TIME_DECAY_K", EnvKind::Float),
+    ("PM_AUTO_CLAIM", EnvKind::Bool),
+    ("PM_AUTO_CLAIM_DRY_RUN", EnvKind::Bool),
+    ("PM_AUTO_CLAIM_INTERVAL_SECONDS", EnvKind::Unsigned),
+    ("PM_AUTO_CLAIM_MAX_CONDITIONS", EnvKind::Unsigned),
+    ("PM_AUTO_CLAIM_MIN_VALUE", EnvKind::Float),
+    ("PM_AUTO_CLAIM_ROUND_RETRY_MODE", EnvKind::Text),
+    ("PM_AUTO_CLAIM_ROUND_RETRY_SCHEDULE", EnvKind::Text),
+    ("PM_AUTO_CLAIM_ROUND_SCOPE", EnvKind::Text),
+    ("PM_AUTO_CLAIM_ROUND_WINDOW_SECS", EnvKind::Unsigned),
+    ("PM_AUTO_CLAIM_WAIT_CONFIRM", EnvKind::Bool),
+    ("PM_AUTO_CLAIM_WAIT_TIMEOUT_SECONDS", EnvKind::Unsigned),
+    ("PM_BALANCE_CACHE_TTL_MS", EnvKind::Unsigned),
+    ("PM_BID_PCT", EnvKind::Float),
+    ("PM_BID_SIZE", EnvKind::Float),
+    ("PM_BINANCE_SYMBOL_OVERRIDE", EnvKind::Text),
+    ("PM_CHAINLINK_HUB_BACKOFF_MAX_MS", EnvKind::Unsigned),
+    (
+        "PM_CHAINLINK_HUB_MAX_CONCURRENT_CONNECTS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_CHAINLINK_HUB_RECONNECT_STAGGER_MS", EnvKind::Unsigned),
+    ("PM_CHAINLINK_HUB_STALL_FAILOVER", EnvKind::Bool),
+    (
+        "PM_CHAINLINK_HUB_TICK_STALL_RECONNECT_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_CLAIM_MONITOR", EnvKind::Text),
+    ("PM_CLOB_URL", EnvKind::Text),
+    ("PM_CLOB_V2_BUILDER_CODE", EnvKind::Text),
+    ("PM_COMPLETION_FIRST_GATE_DEFAULTS", EnvKind::Text),
+    ("PM_COMPLETION_FIRST_MODE", EnvKind::Text),
+    ("PM_CONFIG_FILE", EnvKind::Text),
+    ("PM_CONFIG_FILE_KEYS", EnvKind::Text),
+    ("PM_CONFIG_STRICT", EnvKind::Bool),
+    ("PM_COORD_WATCHDOG_MS", EnvKind::Unsigned),
+    ("PM_DATA_STREAMS_CLIENT_ID", EnvKind::Text),
+    ("PM_DATA_STREAMS_CLIENT_SECRET", EnvKind::Text),
+    ("PM_DATA_STREAMS_FEED_IDS", EnvKind::Text),
+    ("PM_DATA_STREAMS_MIN_SIGNATURES", EnvKind::Unsigned),
+    ("PM_DATA_STREAMS_REPORTS_API_URL", EnvKind::Text),
+    ("PM_DATA_STREAMS_REQUIRE_VERIFIED", EnvKind::Bool),
+    ("PM_DATA_STREAMS_SIGNERS", EnvKind::Text),
+    ("PM_DEBOUNCE_MS", EnvKind::Unsigned),
+    ("PM_DIP_BUY_MAX_ENTRY_PRICE", EnvKind::Float),
+    ("PM_DRY_RUN", EnvKind::Bool),
+    ("PM_DRY_RUN_FILL_PROBABILITY", EnvKind::Float),
+    ("PM_DRY_RUN_MARKET_TOUCH_BOOK_DEPTH_FILLS", EnvKind::Bool),
+    (
+        "PM_DRY_RUN_MARKET_TOUCH_BOOK_DEPTH_FILL_FRACTION",
+        EnvKind::Float,
+    ),
+    ("PM_DRY_RUN_MARKET_TOUCH_BOOK_FILLS", EnvKind::Bool),
+    ("PM_DRY_RUN_MARKET_TOUCH_BOOK_FILL_FRACTION", EnvKind::Float),
+    ("PM_DRY_RUN_MARKET_TOUCH_BOOK_PARTIAL_FILLS", EnvKind::Bool),
+    ("PM_DRY_RUN_MARKET_TOUCH_MIN_FILL_SIZE", EnvKind::Float),
+    ("PM_DRY_RUN_MARKET_TOUCH_QUEUE_FILLS", EnvKind::Bool),
+    ("PM_DRY_RUN_MARKET_TOUCH_TRADE_FILLS", EnvKind::Bool),
+    (
+        "PM_DRY_RUN_MARKET_TOUCH_TRADE_FILL_FRACTION",
+        EnvKind::Float,
+    ),
+    ("PM_DRY_RUN_MARKET_TOUCH_TRADE_PARTIAL_FILLS", EnvKind::Bool),
+    ("PM_ENDGAME_EDGE_EXIT_MULT", EnvKind::Float),
+    ("PM_ENDGAME_EDGE_KEEP_MULT", EnvKind::Float),
+    ("PM_ENDGAME_FREEZE_SECS", EnvKind::Unsigned),
+    ("PM_ENDGAME_HARD_CLOSE_SECS", EnvKind::Unsigned),
+    ("PM_ENDGAME_MAKER_REPAIR_MIN_SECS", EnvKind::Unsigned),
+    ("PM_ENDGAME_SOFT_CLOSE_SECS", EnvKind::Unsigned),
+    ("PM_ENTRY_GRACE_SECONDS", EnvKind::Unsigned),
+    ("PM_FEE_CATEGORY", EnvKind::Text),
+    ("PM_GLFT_FIT_STORE_BLEND_ROUNDS", EnvKind::Unsigned),
+    ("PM_GLFT_FIT_STORE_ENABLED", EnvKind::Bool),
+    ("PM_GLFT_FIT_STORE_MAX_AGE_HOURS", EnvKind::Unsigned),
+    ("PM_GLFT_FIT_STORE_PATH", EnvKind::Text),
+    ("PM_GLFT_FIT_STORE_SAME_HOUR_WEIGHT", EnvKind::Float),
+    ("PM_GLFT_GAMMA", EnvKind::Float),
+    ("PM_GLFT_INTENSITY_WINDOW_SECS", EnvKind::Unsigned),
+    ("PM_GLFT_MULTI_SOURCE_REF_ENABLED", EnvKind::Bool),
+    ("PM_GLFT_OFI_ALPHA", EnvKind::Float),
+    ("PM_GLFT_OFI_SPREAD_BETA", EnvKind::Float),
+    ("PM_GLFT_REFIT_SECS", EnvKind::Unsigned),
+    ("PM_GLFT_REF_MIN_SOURCES", EnvKind::Unsigned),
+    ("PM_GLFT_REF_OUTLIER_BPS", EnvKind::Float),
+    ("PM_GLFT_REF_SOURCE_STALE_MS", EnvKind::Unsigned),
+    ("PM_GLFT_XI", EnvKind::Float),
+    ("PM_HEDGE_DEBOUNCE_MS", EnvKind::Unsigned),
+    ("PM_HEDGE_MIN_MARKETABLE_MAX_EXTRA", EnvKind::Float),
+    ("PM_HEDGE_MIN_MARKETABLE_MAX_EXTRA_PCT", EnvKind::Float),
+    ("PM_HEDGE_MIN_MARKETABLE_NOTIONAL", EnvKind::Float),
+    ("PM_HEDGE_ROUND_UP", EnvKind::Bool),
+    ("PM_INPROC_SUPERVISOR", EnvKind::Bool),
+    ("PM_INSTANCE_ID", EnvKind::Text),
+    ("PM_INVENTORY_RECOVERY_ENABLED", EnvKind::Bool),
+    ("PM_INVENTORY_RECOVERY_QTY_TOLERANCE", EnvKind::Float),
+    ("PM_INVENTORY_RECOVERY_TIMEOUT_MS", EnvKind::Unsigned),
+    ("PM_L2_ASK_DEPTH_5LVL", EnvKind::Float),
+    ("PM_L2_BID_DEPTH_5LVL", EnvKind::Float),
+    ("PM_L2_BOOK_MAX_AGE_MS", EnvKind::Unsigned),
+    ("PM_L2_DEPTH_TAPE_PATH", EnvKind::Text),
+    ("PM_LOCAL_AGG_BOUNDARY_TAPE_PATH", EnvKind::Text),
+    ("PM_LOCAL_AGG_UNCERTAINTY_GATE_ENABLED", EnvKind::Bool),
+    (
+        "PM_LOCAL_AGG_UNCERTAINTY_GATE_FINALIZE_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_LOCAL_AGG_UNCERTAINTY_GATE_FROZEN", EnvKind::Bool),
+    ("PM_LOCAL_AGG_UNCERTAINTY_GATE_MODEL_PATH", EnvKind::Text),
+    (
+        "PM_LOCAL_AGG_UNCERTAINTY_GATE_ONLINE_MODEL_PATH",
+        EnvKind::Text,
+    ),
+    (
+        "PM_LOCAL_AGG_UNCERTAINTY_GATE_ONLINE_WINDOW",
+        EnvKind::Unsigned,
+    ),
+    ("PM_LOCAL_PRICE_AGG_BIAS_CACHE_PATH", EnvKind::Text),
+    ("PM_LOCAL_PRICE_AGG_BIAS_LEARNING_ENABLED", EnvKind::Bool),
+    ("PM_LOCAL_PRICE_AGG_BOUNDARY_WINDOW_MS", EnvKind::Unsigned),
+    ("PM_LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS", EnvKind::Unsigned),
+    ("PM_LOCAL_PRICE_AGG_DECISION_ENABLED", EnvKind::Bool),
+    ("PM_LOCAL_PRICE_AGG_DECISION_WAIT_MS", EnvKind::Unsigned),
+    ("PM_LOCAL_PRICE_AGG_ENABLED", EnvKind::Bool),
+    ("PM_LOCAL_PRICE_AGG_EXACT_BOOST", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_EXTRA_SOURCES", EnvKind::Text),
+    ("PM_LOCAL_PRICE_AGG_FEED_STALL_MS", EnvKind::Unsigned),
+    ("PM_LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_MIN_CONFIDENCE", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_MIN_SOURCES", EnvKind::Unsigned),
+    ("PM_LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS", EnvKind::Unsigned),
+    (
+        "PM_LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS",
+        EnvKind::Float,
+    ),
+    ("PM_LOCAL_PRICE_AGG_SOURCES", EnvKind::Text),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_BINANCE", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_BITSTAMP", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_BYBIT", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_COINBASE", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_CRYPTO_COM", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_GATE", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_KRAKEN", EnvKind::Float),
+    ("PM_LOCAL_PRICE_AGG_WEIGHT_OKX", EnvKind::Float),
+    ("PM_LOG_ROOT", EnvKind::Text),
+    ("PM_MAKER_REBATE_SHARE", EnvKind::Float),
+    ("PM_MARKET_PRELOAD_DEADLINE_SLACK_SECS", EnvKind::Unsigned),
+    ("PM_MARKET_PRELOAD_LEAD_SECS", EnvKind::Unsigned),
+    ("PM_MARKET_PRELOAD_RETRY_INTERVAL_MS", EnvKind::Unsigned),
+    ("PM_MARKET_WS_HARD_CUTOFF_GRACE_SECS", EnvKind::Unsigned),
+    ("PM_MAX_LOSS_PCT", EnvKind::Float),
+    ("PM_MAX_NET_DIFF", EnvKind::Float),
+    ("PM_MAX_PORTFOLIO_COST", EnvKind::Float),
+    ("PM_METRICS_ADDR", EnvKind::Text),
+    ("PM_METRICS_ENABLED", EnvKind::Bool),
+    ("PM_MIN_HALF_SPREAD_TICKS", EnvKind::Float),
+    ("PM_MIN_HEDGE_SIZE", EnvKind::Float),
+    ("PM_MIN_MARKETABLE_AUTO_DETECT", EnvKind::Bool),
+    ("PM_MIN_MARKETABLE_COOLDOWN_MS", EnvKind::Unsigned),
+    ("PM_MIN_MARKETABLE_NOTIONAL_FLOOR", EnvKind::Float),
+    ("PM_MIN_ORDER_SIZE", EnvKind::Float),
+    ("PM_MULTI_MARKET_CHILD", EnvKind::Bool),
+    ("PM_MULTI_MARKET_PREFIXES", EnvKind::Text),
+    ("PM_NAGI_5M_P5_RISK_CAP", EnvKind::Float),
+    ("PM_NET_DIFF_PCT", EnvKind::Float),
+    ("PM_OFI_ADAPTIVE", EnvKind::Bool),
+    ("PM_OFI_ADAPTIVE_K", EnvKind::Float),
+    ("PM_OFI_ADAPTIVE_MAX", EnvKind::Float),
+    ("PM_OFI_ADAPTIVE_MIN", EnvKind::Float),
+    ("PM_OFI_ADAPTIVE_RISE_CAP_PCT", EnvKind::Float),
+    ("PM_OFI_ADAPTIVE_WINDOW", EnvKind::Unsigned),
+    ("PM_OFI_ADVERSE_THRESHOLD", EnvKind::Float),
+    ("PM_OFI_ATTRIBUTION_DIR", EnvKind::Text),
+    ("PM_OFI_ATTRIBUTION_ENABLED", EnvKind::Bool),
+    ("PM_OFI_ATTRIBUTION_FP_TICKS", EnvKind::Float),
+    ("PM_OFI_ATTRIBUTION_HORIZON_SECS", EnvKind::Unsigned),
+    ("PM_OFI_EXIT_RATIO", EnvKind::Float),
+    ("PM_OFI_HAWKES_HALF_LIFE_MS", EnvKind::Unsigned),
+    ("PM_OFI_HAWKES_THRESHOLD", EnvKind::Float),
+    ("PM_OFI_HEARTBEAT_MS", EnvKind::Unsigned),
+    ("PM_OFI_MIN_TOXIC_MS", EnvKind::Unsigned),
+    ("PM_OFI_RATIO_ENTER", EnvKind::Float),
+    ("PM_OFI_RATIO_EXIT", EnvKind::Float),
+    ("PM_OFI_TOXICITY_MODEL", EnvKind::Text),
+    ("PM_OFI_TOXICITY_MODEL_BY_MARKET", EnvKind::Text),
+    ("PM_OFI_TOXICITY_THRESHOLD", EnvKind::Float),
+    ("PM_OFI_VPIN_BUCKETS", EnvKind::Unsigned),
+    ("PM_OFI_VPIN_BUCKET_VOLUME", EnvKind::Float),
+    ("PM_OFI_VPIN_THRESHOLD", EnvKind::Float),
+    ("PM_OFI_WINDOW_MS", EnvKind::Unsigned),
+    ("PM_OPEN_PAIR_BAND", EnvKind::Float),
+    ("PM_ORACLE_LAG_ARBITER_BOOK_MAX_AGE_MS", EnvKind::Unsigned),
+    (
+        "PM_ORACLE_LAG_ARBITER_COLLECTION_WINDOW_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_ORACLE_LAG_CROSS_MARKET_ARBITER_ENABLED", EnvKind::Bool),
+    ("PM_ORACLE_LAG_DRYRUN_ALLOW_FALLBACK_OPEN", EnvKind::Bool),
+    ("PM_ORACLE_LAG_DRYRUN_EXECUTE", EnvKind::Bool),
+    ("PM_ORACLE_LAG_LAB_ONLY", EnvKind::Bool),
+    ("PM_ORACLE_LAG_MAX_ORDER_NOTIONAL_USDC", EnvKind::Float),
+    ("PM_ORACLE_LAG_SYMBOL_UNIVERSE", EnvKind::Text),
+    ("PM_PAIR_ARB_MIN_OPEN_EDGE_FOR_RISK_ADD", EnvKind::Float),
+    ("PM_PAIR_ARB_PAIR_COST_SAFETY_MARGIN", EnvKind::Float),
+    ("PM_PAIR_ARB_RISK_OPEN_CUTOFF_SECS", EnvKind::Unsigned),
+    ("PM_PAIR_ARB_TIER_1_MULT", EnvKind::Float),
+    ("PM_PAIR_ARB_TIER_2_MULT", EnvKind::Float),
+    ("PM_PAIR_ARB_TIER_MODE", EnvKind::Text),
+    ("PM_PAIR_TARGET", EnvKind::Float),
+    ("PM_PGT_SHADOW_PROFILE", EnvKind::Text),
+    ("PM_PGT_SHADOW_REDEEM_LIFECYCLE_ENABLED", EnvKind::Bool),
+    ("PM_PNL_LEDGER_ENABLED", EnvKind::Bool),
+    ("PM_PNL_LEDGER_PATH", EnvKind::Text),
+    ("PM_PORTFOLIO_CANCEL_ALL_RATIO", EnvKind::Float),
+    ("PM_PORTFOLIO_EVAL_INTERVAL_MS", EnvKind::Unsigned),
+    ("PM_PORTFOLIO_MAX_CAPITAL_AT_RISK_USDC", EnvKind::Float),
+    ("PM_PORTFOLIO_MAX_DIRECTIONAL_NOTIONAL_USDC", EnvKind::Float),
+    ("PM_PORTFOLIO_MAX_UNPAIRED_NOTIONAL_USDC", EnvKind::Float),
+    ("PM_PORTFOLIO_RISK_ENABLED", EnvKind::Bool),
+    ("PM_PORTFOLIO_RISK_ROOT", EnvKind::Text),
+    ("PM_PORTFOLIO_STALE_SECS", EnvKind::Unsigned),
+    ("PM_PORTFOLIO_THROTTLE_RATIO", EnvKind::Float),
+    ("PM_PORTFOLIO_THROTTLE_SIZE_MULT", EnvKind::Float),
+    ("PM_POST_CLOSE_CHAINLINK_MAX_WAIT_SECS", EnvKind::Unsigned),
+    ("PM_POST_CLOSE_CHAINLINK_WS_URL", EnvKind::Text),
+    ("PM_POST_CLOSE_DATA_STREAMS_PRICE_API_URL", EnvKind::Text),
+    ("PM_POST_CLOSE_GAMMA_POLL_MS", EnvKind::Unsigned),
+    ("PM_POST_CLOSE_WINDOW_SECS", EnvKind::Unsigned),
+    ("PM_POST_ONLY_EXTRA_TIGHT_TICKS", EnvKind::Float),
+    ("PM_POST_ONLY_SAFETY_TICKS", EnvKind::Float),
+    ("PM_POST_ONLY_TIGHT_SPREAD_TICKS", EnvKind::Float),
+    ("PM_PROBE_AFTER", EnvKind::Signed),
+    ("PM_PROBE_BEFORE", EnvKind::Signed),
+    ("PM_PROBE_TRADER", EnvKind::Text),
+    ("PM_PROBE_TX_HASH", EnvKind::Text),
+    ("PM_RECONCILE_INTERVAL_SECS", EnvKind::Unsigned),
+    ("PM_RECORDER_ENABLED", EnvKind::Bool),
+    ("PM_RECORDER_FLUSH_EVERY_MS", EnvKind::Unsigned),
+    ("PM_RECORDER_MARKET_MODE", EnvKind::Text),
+    ("PM_RECORDER_MD_QUEUE_CAP", EnvKind::Unsigned),
+    ("PM_RECORDER_OPS_QUEUE_CAP", EnvKind::Unsigned),
+    ("PM_RECORDER_ROOT", EnvKind::Text),
+    ("PM_RECYCLE_COOLDOWN_SECS", EnvKind::Unsigned),
+    ("PM_RECYCLE_ENABLED", EnvKind::Bool),
+    ("PM_RECYCLE_LOW_WATER_USDC", EnvKind::Float),
+    ("PM_RECYCLE_MAX_BATCH_USDC", EnvKind::Float),
+    ("PM_RECYCLE_MAX_MERGES_PER_ROUND", EnvKind::Unsigned),
+    ("PM_RECYCLE_MIN_BATCH_USDC", EnvKind::Float),
+    ("PM_RECYCLE_MIN_EXECUTABLE_USDC", EnvKind::Float),
+    ("PM_RECYCLE_ONLY_HEDGE", EnvKind::Bool),
+    ("PM_RECYCLE_POLL_SECS", EnvKind::Unsigned),
+    ("PM_RECYCLE_PROACTIVE", EnvKind::Bool),
+    ("PM_RECYCLE_SHORTFALL_MULT", EnvKind::Float),
+    ("PM_RECYCLE_TARGET_FREE_USDC", EnvKind::Float),
+    ("PM_RECYCLE_TRIGGER_REJECTS", EnvKind::Unsigned),
+    ("PM_RECYCLE_TRIGGER_WINDOW_SECS", EnvKind::Unsigned),
+    ("PM_REPRICE_THRESHOLD", EnvKind::Float),
+    ("PM_RESIDUAL_UNWIND_ENABLED", EnvKind::Bool),
+    ("PM_RESIDUAL_UNWIND_MIN_EDGE", EnvKind::Float),
+    ("PM_RESOLVE_RETRY_ATTEMPTS", EnvKind::Unsigned),
+    ("PM_RESOLVE_TIMEOUT_MS", EnvKind::Unsigned),
+    (
+        "PM_SELF_BUILT_PRICE_AGG_CLOSE_TOLERANCE_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_SELF_BUILT_PRICE_AGG_ENABLED", EnvKind::Bool),
+    ("PM_SELF_BUILT_PRICE_AGG_MIN_CONFIDENCE", EnvKind::Float),
+    (
+        "PM_SELF_BUILT_PRICE_AGG_OPEN_TOLERANCE_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_SHARED_INGRESS_BROKER_INSTANCE_ID", EnvKind::Text),
+    ("PM_SHARED_INGRESS_BROKER_REPLACE_EXISTING", EnvKind::Bool),
+    (
+        "PM_SHARED_INGRESS_FIXED_MARKET_CONNECT_JITTER_MS",
+        EnvKind::Unsigned,
+    ),
+    (
+        "PM_SHARED_INGRESS_FIXED_MARKET_CONNECT_PERMITS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_SHARED_INGRESS_IDLE_EXIT_ENABLED", EnvKind::Bool),
+    (
+        "PM_SHARED_INGRESS_MARKET_CONNECT_JITTER_MS",
+        EnvKind::Unsigned,
+    ),
+    (
+        "PM_SHARED_INGRESS_MARKET_CONNECT_PERMITS",
+        EnvKind::Unsigned,
+    ),
+    (
+        "PM_SHARED_INGRESS_MARKET_HEALTH_JITTER_MS",
+        EnvKind::Unsigned,
+    ),
+    (
+        "PM_SHARED_INGRESS_MARKET_ONLY_AUTO_WAIT_MS",
+        EnvKind::Unsigned,
+    ),
+    ("PM_SHARED_INGRESS_ROLE", EnvKind::Text),
+    ("PM_SHARED_INGRESS_ROOT", EnvKind::Text),
+    ("PM_SIGNATURE_TYPE", EnvKind::Unsigned),
+    ("PM_STALE_TTL_MS", EnvKind::Unsigned),
+    ("PM_STRATEGY", EnvKind::Text),
+    ("PM_STRATEGY_METRICS_LOG_SECS", EnvKind::Unsigned),
+    ("PM_TAKER_FEE_RATE", EnvKind::Float),
+    ("PM_TICK_SIZE", EnvKind::Float),
+    ("PM_TOXIC_RECOVERY_HOLD_MS", EnvKind::Unsigned),
+    ("PM_V2_SMOKE_CANCEL_ALL", EnvKind::Bool),
+    ("PM_V2_SMOKE_DEFER_EXEC", EnvKind::Bool),
+    ("PM_V2_SMOKE_DIRECTION", EnvKind::Text),
+    ("PM_V2_SMOKE_ORDER_TYPE", EnvKind::Text),
+    ("PM_V2_SMOKE_PLACE_ORDER", EnvKind::Bool),
+    ("PM_V2_SMOKE_POST_ONLY", EnvKind::Bool),
+    ("PM_V2_SMOKE_PRICE", EnvKind::Text),
+    ("PM_V2_SMOKE_SIDE", EnvKind::Bool),
+    ("PM_V2_SMOKE_SIZE", EnvKind::Text),
+    ("PM_WS_CONNECT_TIMEOUT_MS", EnvKind::Unsigned),
+    ("PM_WS_DEGRADE_MAX_FAILURES", EnvKind::Unsigned),
+    ("PM_XUAN_B27_DPLUS_ALLOW_PASSIVE_TAKER", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_EDGE", EnvKind::Float),
+    ("PM_XUAN_B27_DPLUS_EXPLICIT_CANARY_APPROVAL", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_IMBALANCE_QTY_CAP", EnvKind::Float),
+    ("PM_XUAN_B27_DPLUS_MARKET_SLUG", EnvKind::Text),
+    ("PM_XUAN_B27_DPLUS_MAX_ACTIVE_MARKETS", EnvKind::Unsigned),
+    ("PM_XUAN_B27_DPLUS_MAX_LIVE_ORDERS", EnvKind::Unsigned),
+    ("PM_XUAN_B27_DPLUS_MAX_OPEN_COST_USDC", EnvKind::Float),
+    (
+        "PM_XUAN_B27_DPLUS_MAX_STRATEGY_EXPOSURE_USDC",
+        EnvKind::Float,
+    ),
+    ("PM_XUAN_B27_DPLUS_MODE", EnvKind::Text),
+    ("PM_XUAN_B27_DPLUS_OMS_ADAPTER_ENABLED", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_POST_ONLY", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_RUNTIME_WIRING_ENABLED", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_SALVAGE_NET_CAP", EnvKind::Float),
+    ("PM_XUAN_B27_DPLUS_SEED_PX_HI", EnvKind::Float),
+    ("PM_XUAN_B27_DPLUS_SEED_PX_LO", EnvKind::Float),
+    ("PM_XUAN_B27_DPLUS_STOP_ON_UNKNOWN", EnvKind::Bool),
+    ("PM_XUAN_B27_DPLUS_TARGET_QTY", EnvKind::Float),
+    ("POLYMARKET_API_KEY", EnvKind::Text),
+    ("POLYMARKET_API_PASSPHRASE", EnvKind::Text),
+    ("POLYMARKET_API_SECRET", EnvKind::Text),
+    ("POLYMARKET_BUILDER_API_KEY", EnvKind::Text),
+    ("POLYMARKET_BUILDER_PASSPHRASE", EnvKind::Text),
+    ("POLYMARKET_BUILDER_SECRET", EnvKind::Text),
+    ("POLYMARKET_CUSTOM_FEATURE", EnvKind::Bool),
+    ("POLYMARKET_DATA_API_URL", EnvKind::Text),
+    ("POLYMARKET_FUNDER_ADDRESS", EnvKind::Text),
+    ("POLYMARKET_MARKET_ID", EnvKind::Text),
+    ("POLYMARKET_MARKET_INTERVAL", EnvKind::Text),
+    ("POLYMARKET_MARKET_PREFIX", EnvKind::Text),
+    ("POLYMARKET_MARKET_SLUG", EnvKind::Text),
+    ("POLYMARKET_MARKET_SYMBOL", EnvKind::Text),
+    ("POLYMARKET_MARKET_TIMEFRAME", EnvKind::Text),
+    ("POLYMARKET_NO_ASSET_ID", EnvKind::Text),
+    ("POLYMARKET_PRIVATE_KEY", EnvKind::Text),
+    ("POLYMARKET_RELAYER_URL", EnvKind::Text),
+    ("POLYMARKET_REST_URL", EnvKind::Text),
+    ("POLYMARKET_RPC_URL", EnvKind::Text),
+    ("POLYMARKET_WS_BASE_URL", EnvKind::Text),
+    ("POLYMARKET_YES_ASSET_ID", EnvKind::Text),
+];
+
+pub fn known_env_kind(key: &str) -> Option<EnvKind> {
+    KNOWN_ENV_KEYS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, kind)| *kind)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigIssueKind {
+    /// Key is not read by anything (usually a typo).
+    Unknown,
+    /// Value does not parse or is out of range; the default was kept.
+    Invalid,
+    /// Value was clamped, swapped or overridden to satisfy another setting.
+    Conflict,
+}
+
+impl ConfigIssueKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Invalid => "invalid",
+            Self::Conflict => "conflict",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub kind: ConfigIssueKind,
+    pub key: String,
+    pub detail: String,
+}
+
+static REPORTED_ISSUES: Mutex<Vec<ConfigIssue>> = Mutex::new(Vec::new());
+
+/// Record a setting that a config loader ignored, clamped or overrode.
+/// Loaders keep their existing `warn!`; this only feeds the strict-mode report.
+pub fn report_config_issue(kind: ConfigIssueKind, key: &str, detail: impl Into<String>) {
+    let issue = ConfigIssue {
+        kind,
+        key: key.to_string(),
+        detail: detail.into(),
+    };
+    let mut guard = REPORTED_ISSUES.lock().unwrap_or_else(|e| e.into_inner());
+    if !guard.contains(&issue) {
+        guard.push(issue);
+    }
+}
+
+/// Bool env flag with the spellings `EnvKind::Bool` accepts; unset or
+/// unrecognised values fall back to `default`.
+pub fn env_bool(name: &str, default: bool) -> bool {
+    match std::env::var(name) {
+        Ok(v) => match v.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => default,
+        },
+        Err(_) => default,
+    }
+}
+
+/// Env value parsed as `T` and accepted by `valid`. A value that parses but is
+/// rejected is reported as `Invalid` with `rule` and `None` is returned so the
+/// loader keeps its default; unparsable values are left to the env scan.
+pub fn env_checked<T: FromStr + Display>(
+    key: &str,
+    rule: &str,
+    valid: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let value = std::env::var(key).ok()?.parse::<T>().ok()?;
+    if valid(&value) {
+        Some(value)
+    } else {
+        report_config_issue(ConfigIssueKind::Invalid, key, format!("{value} ({rule})"));
+        None
+    }
+}
+
+/// `value` clamped into `[lo, hi]`; a clamped value is reported as `Conflict`.
+pub fn clamp_reported<T: PartialOrd + Copy + Display>(key: &str, value: T, lo: T, hi: T) -> T {
+    let clamped = if value < lo {
+        lo
+    } else if value > hi {
+        hi
+    } else {
+        return value;
+    };
+    report_config_issue(
+        ConfigIssueKind::Conflict,
+        key,
+        format!("{value} outside [{lo}, {hi}]; clamped to {clamped}"),
+    );
+    clamped
+}
+
+/// `value` raised to `min` (NaN included, like `f64::max`); a raised value is
+/// reported as `Conflict`.
+pub fn at_least_reported<T: PartialOrd + Copy + Display>(key: &str, value: T, min: T) -> T {
+    match value.partial_cmp(&min) {
+        Some(Ordering::Less) | None => {
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                key,
+                format!("{value} below {min}; raised to {min}"),
+            );
+            min
+        }
+        _ => value,
+    }
+}
+
+fn reported_issues() -> Vec<ConfigIssue> {
+    REPORTED_ISSUES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+pub fn strict_mode_enabled() -> bool {
+    let env_on = std::env::var(CONFIG_STRICT_ENV)
+        .map(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false);
+    env_on || std::env::args().any(|a| a == CONFIG_STRICT_FLAG)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let sub = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = sub.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Closest registered key within a small edit distance, for "did you mean".
+pub fn suggest_known_key(key: &str) -> Option<&'static str> {
+    KNOWN_ENV_KEYS
+        .iter()
+        .map(|(k, _)| (*k, edit_distance(key, k)))
+        .filter(|(_, d)| *d <= 3)
+        .min_by_key(|(_, d)| *d)
+        .map(|(k, _)| k)
+}
+
+/// Check `PM_*` / `POLYMARKET_*` entries of `vars` against the registry:
+/// unknown names and values that do not parse as the expected type.
+pub fn validate_env_vars<I>(vars: I) -> Vec<ConfigIssue>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut issues = Vec::new();
+    let sorted: BTreeMap<String, String> = vars
+        .into_iter()
+        .filter(|(k, _)| k.starts_with("PM_") || k.starts_with("POLYMARKET_"))
+        .collect();
+    for (key, value) in sorted {
+        match known_env_kind(&key) {
+            None => {
+                let detail = match suggest_known_key(&key) {
+                    Some(s) => format!("not a recognised setting (did you mean {s}?)"),
+                    None => "not a recognised setting".to_string(),
+                };
+                issues.push(ConfigIssue {
+                    kind: ConfigIssueKind::Unknown,
+                    key,
+                    detail,
+                });
+            }
+            Some(kind) if !kind.accepts(&value) => {
+                issues.push(ConfigIssue {
+                    kind: ConfigIssueKind::Invalid,
+                    detail: format!("'{}' is not a valid {}", value.trim(), kind.as_str()),
+                    key,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    issues
+}
+
+/// All issues for the current process: env scan plus whatever config loaders
+/// have reported so far. Build the configs first so their reports are in.
+pub fn collect_config_issues() -> Vec<ConfigIssue> {
+    let mut issues = validate_env_vars(std::env::vars());
+    for issue in reported_issues() {
+        if !issues
+            .iter()
+            .any(|i| i.key == issue.key && i.kind == issue.kind)
+        {
+            issues.push(issue);
+        }
+    }
+    issues.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.key.cmp(&b.key)));
+    issues
+}
+
+/// Build every config whose loader reports ignored or clamped values, then
+/// collect. Only the reports matter; the built configs are dropped.
+pub fn build_configs_and_collect_issues() -> Vec<ConfigIssue> {
+    let _ = CoordinatorConfig::from_env();
+    let _ = OfiConfig::from_env();
+    let _ = InventoryConfig::from_env();
+    let _ = ExecutorTunables::from_env();
+    let _ = FeeSchedule::from_env();
+    let _ = PriceAggConfig::from_env();
+    let _ = RtdsPoolConfig::from_env(RTDS_POOL_STALL_MS_DEFAULT);
+    let _ = DataStreamsVerifier::from_env();
+    collect_config_issues()
+}
+
+/// The rendered report when strict mode is on and anything was found.
+pub fn strict_config_failure(issues: &[ConfigIssue], strict: bool) -> Option<String> {
+    (strict && !issues.is_empty()).then(|| render_config_issues(issues))
+}
+
+pub fn render_config_issues(issues: &[ConfigIssue]) -> String {
+    let mut out = format!("config validation failed: {} issue(s)\n", issues.len());
+    for issue in issues {
+        let _ = writeln!(
+            out,
+            "  [{:<8}] {:<44} {}",
+            issue.kind.as_str(),
+            issue.key,
+            issue.detail
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn flags_unknown_keys_and_bad_values() {
+        let issues = validate_env_vars(vars(&[
+            ("PM_BID_SZIE", "5"),
+            ("PM_BID_SIZE", "five"),
+            ("PM_RECORDER_ENABLED", "ture"),
+            ("PM_STALE_TTL_MS", "-3"),
+            ("PM_DRY_RUN", "1"),
+            ("PM_TICK_SIZE", "0.01"),
+            ("HOME", "/root"),
+        ]));
+        assert_eq!(issues.len(), 4, "{issues:?}");
+        let typo = issues.iter().find(|i| i.key == "PM_BID_SZIE").unwrap();
+        assert_eq!(typo.kind, ConfigIssueKind::Unknown);
+        assert!(typo.detail.contains("PM_BID_SIZE"));
+        for key in ["PM_BID_SIZE", "PM_RECORDER_ENABLED", "PM_STALE_TTL_MS"] {
+            let issue = issues.iter().find(|i| i.key == key).unwrap();
+            assert_eq!(issue.kind, ConfigIssueKind::Invalid, "{key}");
+        }
+    }
+
+    #[test]
+    fn range_helpers_report_rejected_and_clamped_values() {
+        assert_eq!(clamp_reported("PM_OFI_RATIO_ENTER", 0.4, 0.0, 1.0), 0.4);
+        assert_eq!(clamp_reported("PM_OFI_RATIO_ENTER", 1.7, 0.0, 1.0), 1.0);
+        assert_eq!(at_least_reported("PM_OFI_ADAPTIVE_WINDOW", 4_usize, 10), 10);
+        assert_eq!(at_least_reported("PM_OFI_ADAPTIVE_K", f64::NAN, 0.5), 0.5);
+
+        std::env::set_var("PM_OFI_VPIN_BUCKET_VOLUME", "-5");
+        let bucket = env_checked::<f64>("PM_OFI_VPIN_BUCKET_VOLUME", "must be > 0", |v| *v > 0.0);
+        std::env::remove_var("PM_OFI_VPIN_BUCKET_VOLUME");
+        assert_eq!(bucket, None);
+
+        let issues = reported_issues();
+        let detail = |key: &str| {
+            issues
+                .iter()
+                .find(|i| i.key == key)
+                .map(|i| (i.kind, i.detail.clone()))
+        };
+        assert_eq!(
+            detail("PM_OFI_RATIO_ENTER"),
+            Some((
+                ConfigIssueKind::Conflict,
+                "1.7 outside [0, 1]; clamped to 1".to_string()
+            ))
+        );
+        assert_eq!(
+            detail("PM_OFI_ADAPTIVE_WINDOW"),
+            Some((
+                ConfigIssueKind::Conflict,
+                "4 below 10; raised to 10".to_string()
+            ))
+        );
+        assert_eq!(
+            detail("PM_OFI_VPIN_BUCKET_VOLUME"),
+            Some((ConfigIssueKind::Invalid, "-5 (must be > 0)".to_string()))
+        );
+    }
+
+    #[test]
+    fn clamped_config_value_fails_strict_mode() {
+        // A key no other test reports, so the issue can only come from OfiConfig.
+        std::env::set_var("PM_OFI_ADAPTIVE_RISE_CAP_PCT", "7.5");
+        let issues = build_configs_and_collect_issues();
+        std::env::remove_var("PM_OFI_ADAPTIVE_RISE_CAP_PCT");
+
+        assert!(reported_issues().contains(&ConfigIssue {
+            kind: ConfigIssueKind::Conflict,
+            key: "PM_OFI_ADAPTIVE_RISE_CAP_PCT".to_string(),
+            detail: "7.5 outside [0, 5]; clamped to 5".to_string(),
+        }));
+        let report = strict_config_failure(&issues, true).expect("strict mode must fail");
+        assert!(report.contains("PM_OFI_ADAPTIVE_RISE_CAP_PCT"), "{report}");
+    }
+
+    #[test]
+    fn registry_covers_all_env_literals() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut stack = vec![root];
+        let mut missing = std::collections::BTreeSet::new();
+        while let Some(dir) = stack.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                if path.extension().and_then(|e| e.to_str()) != Some("rs")
+                    || path.ends_with("config_validation.rs")
+                {
+                    continue;
+                }
+                let src = std::fs::read_to_string(&path).unwrap();
+                for chunk in src.split('"').skip(1).step_by(2) {
+                    let is_key = (chunk.starts_with("PM_") || chunk.starts_with("POLYMARKET_"))
+                        && chunk.len() > 3
+                        && !chunk.ends_with('_')
+                        && chunk
+                            .chars()
+                            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+                    if is_key && known_env_kind(chunk).is_none() {
+                        missing.insert(chunk.to_string());
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "unregistered env keys: {missing:?}");
+    }
+}
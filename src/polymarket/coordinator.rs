@@ -19,8 +19,13 @@ use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use tracing::{debug, info, warn};
 
+use super::actor_state_store::{ActorStateHandle, StateSaveThrottle};
+use super::clock::{system_clock, SharedClock};
+use super::config_validation::{env_checked, report_config_issue, ConfigIssueKind};
 use super::glft::GlftSignalSnapshot;
+use super::l2_book::L2BookSnapshot;
 use super::messages::*;
+use super::portfolio_risk::{PortfolioRiskAction, PortfolioRiskDirective};
 use super::recorder::{RecorderHandle, RecorderSessionMeta};
 use super::strategy::{
     completion_first::{CompletionFirstGateDefaults, CompletionFirstPhase},
@@ -99,6 +104,10 @@ mod coordinator_metrics;
 mod coordinator_order_io;
 #[path = "coordinator_pricing.rs"]
 mod coordinator_pricing;
+#[path = "coordinator_residual_unwind.rs"]
+mod coordinator_residual_unwind;
+#[path = "coordinator_state.rs"]
+mod coordinator_state;
 #[path = "coordinator_xuan_b27_dplus.rs"]
 mod coordinator_xuan_b27_dplus;
 
@@ -349,6 +358,17 @@ pub struct OracleLagSnipingStrategyConfig {
     pub lab_only: bool,
 }
 
+/// Residual unwind for the pair strategies (pair_arb / PGT / completion_first):
+/// post-only SELL of the excess leg when completing it is no longer economic.
+#[derive(Debug, Clone)]
+pub struct ResidualUnwindConfig {
+    /// Master switch. Default: false (hold residuals, legacy behaviour).
+    pub enabled: bool,
+    /// Required edge of `sell_price + opposite_ask` over 1.0 before unwinding,
+    /// i.e. selling must beat buying the completion leg by this much. Default: 0.0.
+    pub min_edge: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct CoordinatorConfig {
     /// Strategy implementation selected at runtime.
@@ -400,6 +420,8 @@ pub struct CoordinatorConfig {
     pub xuan_b27_dplus: XuanB27DplusStrategyConfig,
     /// Post-close HYPE strategy-specific runtime config.
     pub oracle_lag_sniping: OracleLagSnipingStrategyConfig,
+    /// Residual-unwind overlay for the pair strategies.
+    pub residual_unwind: ResidualUnwindConfig,
     /// Unix timestamp (seconds) when the market expires. None = no decay.
     pub market_end_ts: Option<u64>,
     /// Opt-3: Faster debounce for hedge orders (urgent, shouldn't wait 500ms).
@@ -430,6 +452,9 @@ pub struct CoordinatorConfig {
     pub dry_run: bool,
     /// Configurable TTL for stale book data (ms). Default 3000ms.
     pub stale_ttl_ms: u64,
+    /// Max age of the live L2 ladder handed to strategies (ms). Wall-clock age,
+    /// so quiet but accurate books also age out; 0 (default) disables.
+    pub l2_book_max_age_ms: u64,
     /// Periodic watchdog tick (ms) to enforce stale/toxic cancels even when md stream is silent.
     pub watchdog_tick_ms: u64,
     /// Periodic strategy metrics snapshot cadence (seconds). 0 disables.
@@ -519,6 +544,10 @@ impl Default for CoordinatorConfig {
                 max_order_notional_usdc: 0.0,
                 lab_only: false,
             },
+            residual_unwind: ResidualUnwindConfig {
+                enabled: false,
+                min_edge: 0.0,
+            },
             market_end_ts: None,
             hedge_debounce_ms: 100, // Hedge orders bypass normal 500ms debounce
             max_portfolio_cost: 1.02, // Emergency hedge ceiling
@@ -533,6 +562,7 @@ impl Default for CoordinatorConfig {
             endgame_edge_exit_mult: 1.25,
             dry_run: true,
             stale_ttl_ms: 3000,
+            l2_book_max_age_ms: 0,
             watchdog_tick_ms: 500,
             strategy_metrics_log_secs: 15,
             toxic_recovery_hold_ms: 1200,
@@ -557,6 +587,7 @@ impl CoordinatorConfig {
         cfg.apply_completion_first_env();
         cfg.apply_xuan_b27_dplus_env();
         cfg.apply_oracle_lag_env();
+        cfg.apply_residual_unwind_env();
         cfg.apply_hedge_env();
         cfg.apply_runtime_env();
         cfg.apply_endgame_env();
@@ -570,6 +601,11 @@ impl CoordinatorConfig {
     pub fn validate_and_warn(&self) {
         if self.bid_size <= 0.0 {
             eprintln!("⚠️ CONFIG: bid_size <= 0 — orders will be skipped");
+            report_config_issue(
+                ConfigIssueKind::Invalid,
+                "PM_BID_SIZE",
+                format!("{} (must be > 0)", self.bid_size),
+            );
         }
         if self.max_net_diff < self.bid_size * 1.5 {
             eprintln!(
@@ -617,13 +653,16 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_OPEN_PAIR_BAND={} (must satisfy 0 < p <= 1), using {}",
                         f, self.open_pair_band
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_OPEN_PAIR_BAND",
+                        format!("{} (must satisfy 0 < p <= 1)", f),
+                    );
                 }
             }
         }
-        if let Ok(v) = std::env::var("PM_MAX_NET_DIFF") {
-            if let Ok(f) = v.parse() {
-                self.max_net_diff = f;
-            }
+        if let Some(f) = env_checked::<f64>("PM_MAX_NET_DIFF", "must be >= 0", |f| *f >= 0.0) {
+            self.max_net_diff = f;
         }
         if let Ok(v) = std::env::var("PM_BID_SIZE") {
             if let Ok(f) = v.parse() {
@@ -639,6 +678,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_DIP_BUY_MAX_ENTRY_PRICE={} (must satisfy 0 < p < 1), using {}",
                         f, self.dip_buy_max_entry_price
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_DIP_BUY_MAX_ENTRY_PRICE",
+                        format!("{} (must satisfy 0 < p < 1)", f),
+                    );
                 }
             }
         }
@@ -651,6 +695,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_TICK_SIZE={} (must satisfy 0 < tick < 1), using {}",
                         f, self.tick_size
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_TICK_SIZE",
+                        format!("{} (must satisfy 0 < tick < 1)", f),
+                    );
                 }
             }
         }
@@ -740,6 +789,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_MIN_HALF_SPREAD_TICKS={} (must be > 0), using {}",
                         f, self.glft_min_half_spread_ticks
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_MIN_HALF_SPREAD_TICKS",
+                        format!("{} (must be > 0)", f),
+                    );
                 }
             }
         }
@@ -755,6 +809,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_PAIR_ARB_TIER_1_MULT={} (must satisfy 0 <= x <= 1), using {}",
                         f, self.pair_arb.tier_1_mult
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_PAIR_ARB_TIER_1_MULT",
+                        format!("{} (must satisfy 0 <= x <= 1)", f),
+                    );
                 }
             }
         }
@@ -767,6 +826,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_PAIR_ARB_TIER_2_MULT={} (must satisfy 0 <= x <= 1), using {}",
                         f, self.pair_arb.tier_2_mult
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_PAIR_ARB_TIER_2_MULT",
+                        format!("{} (must satisfy 0 <= x <= 1)", f),
+                    );
                 }
             }
         }
@@ -779,6 +843,11 @@ impl CoordinatorConfig {
                     v,
                     self.pair_arb.tier_mode.as_str()
                 );
+                report_config_issue(
+                    ConfigIssueKind::Invalid,
+                    "PM_PAIR_ARB_TIER_MODE",
+                    format!("{} (supported: disabled|discrete|continuous)", v),
+                );
             }
         }
         if let Ok(v) = std::env::var("PM_PAIR_ARB_PAIR_COST_SAFETY_MARGIN") {
@@ -790,6 +859,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_PAIR_ARB_PAIR_COST_SAFETY_MARGIN={} (must satisfy 0 <= x < 1), using {}",
                         f, self.pair_arb.pair_cost_safety_margin
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_PAIR_ARB_PAIR_COST_SAFETY_MARGIN",
+                        format!("{} (must satisfy 0 <= x < 1)", f),
+                    );
                 }
             }
         }
@@ -855,6 +929,11 @@ impl CoordinatorConfig {
                     v,
                     self.xuan_b27_dplus.mode.as_str()
                 );
+                report_config_issue(
+                    ConfigIssueKind::Invalid,
+                    "PM_XUAN_B27_DPLUS_MODE",
+                    format!("{} (supported: disabled|observer|auth_observer|canary)", v),
+                );
             }
         }
         self.xuan_b27_dplus.market_slug = std::env::var("PM_XUAN_B27_DPLUS_MARKET_SLUG")
@@ -982,6 +1061,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_ORACLE_LAG_MAX_ORDER_NOTIONAL_USDC={} (must satisfy x >= 0), using {}",
                         f, self.oracle_lag_sniping.max_order_notional_usdc
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_ORACLE_LAG_MAX_ORDER_NOTIONAL_USDC",
+                        format!("{} (must satisfy x >= 0)", f),
+                    );
                 }
             }
         }
@@ -991,16 +1075,28 @@ impl CoordinatorConfig {
         }
     }
 
+    fn apply_residual_unwind_env(&mut self) {
+        if let Ok(v) = std::env::var("PM_RESIDUAL_UNWIND_ENABLED") {
+            let lv = v.to_ascii_lowercase();
+            self.residual_unwind.enabled = v == "1" || lv == "true";
+        }
+        if let Ok(v) = std::env::var("PM_RESIDUAL_UNWIND_MIN_EDGE") {
+            if let Ok(f) = v.parse::<f64>() {
+                if f.is_finite() && f >= 0.0 {
+                    self.residual_unwind.min_edge = f;
+                }
+            }
+        }
+    }
+
     fn apply_hedge_env(&mut self) {
         if let Ok(v) = std::env::var("PM_HEDGE_DEBOUNCE_MS") {
             if let Ok(ms) = v.parse::<u64>() {
                 self.hedge_debounce_ms = ms;
             }
         }
-        if let Ok(v) = std::env::var("PM_MAX_PORTFOLIO_COST") {
-            if let Ok(f) = v.parse() {
-                self.max_portfolio_cost = f;
-            }
+        if let Some(f) = env_checked::<f64>("PM_MAX_PORTFOLIO_COST", "must be > 0", |f| *f > 0.0) {
+            self.max_portfolio_cost = f;
         }
         if let Ok(v) = std::env::var("PM_MIN_ORDER_SIZE") {
             if let Ok(f) = v.parse::<f64>() {
@@ -1051,6 +1147,11 @@ impl CoordinatorConfig {
                 self.stale_ttl_ms = ms;
             }
         }
+        if let Ok(v) = std::env::var("PM_L2_BOOK_MAX_AGE_MS") {
+            if let Ok(ms) = v.parse::<u64>() {
+                self.l2_book_max_age_ms = ms;
+            }
+        }
         if let Ok(v) = std::env::var("PM_COORD_WATCHDOG_MS") {
             if let Ok(ms) = v.parse::<u64>() {
                 self.watchdog_tick_ms = ms.max(50);
@@ -1082,6 +1183,11 @@ impl CoordinatorConfig {
                         "⚠️ Ignoring invalid PM_MAX_LOSS_PCT={} (must satisfy 0 <= pct < 1), using {}",
                         f, self.max_loss_pct
                     );
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_MAX_LOSS_PCT",
+                        format!("{} (must satisfy 0 <= pct < 1)", f),
+                    );
                 }
             }
         }
@@ -1127,6 +1233,14 @@ impl CoordinatorConfig {
                 "⚠️ Swapping PM_XUAN_B27_DPLUS_SEED_PX_LO/HI because lo {:.4} > hi {:.4}",
                 self.xuan_b27_dplus.seed_px_lo, self.xuan_b27_dplus.seed_px_hi
             );
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                "PM_XUAN_B27_DPLUS_SEED_PX_LO",
+                format!(
+                    "lo {:.4} > hi {:.4}; swapped",
+                    self.xuan_b27_dplus.seed_px_lo, self.xuan_b27_dplus.seed_px_hi
+                ),
+            );
             std::mem::swap(
                 &mut self.xuan_b27_dplus.seed_px_lo,
                 &mut self.xuan_b27_dplus.seed_px_hi,
@@ -1135,14 +1249,29 @@ impl CoordinatorConfig {
         if self.xuan_b27_dplus.mode.allows_order_submission() {
             if !self.xuan_b27_dplus.post_only {
                 warn!("⚠️ Forcing xuan_b27_dplus canary post_only=true");
+                report_config_issue(
+                    ConfigIssueKind::Conflict,
+                    "PM_XUAN_B27_DPLUS_POST_ONLY",
+                    "canary mode forces true",
+                );
                 self.xuan_b27_dplus.post_only = true;
             }
             if self.xuan_b27_dplus.allow_passive_taker {
                 warn!("⚠️ Forcing xuan_b27_dplus canary allow_passive_taker=false");
+                report_config_issue(
+                    ConfigIssueKind::Conflict,
+                    "PM_XUAN_B27_DPLUS_ALLOW_PASSIVE_TAKER",
+                    "canary mode forces false",
+                );
                 self.xuan_b27_dplus.allow_passive_taker = false;
             }
             if !self.xuan_b27_dplus.stop_on_unknown {
                 warn!("⚠️ Forcing xuan_b27_dplus canary stop_on_unknown=true");
+                report_config_issue(
+                    ConfigIssueKind::Conflict,
+                    "PM_XUAN_B27_DPLUS_STOP_ON_UNKNOWN",
+                    "canary mode forces true",
+                );
                 self.xuan_b27_dplus.stop_on_unknown = true;
             }
         }
@@ -1151,6 +1280,14 @@ impl CoordinatorConfig {
                 "⚠️ Clamping PM_ENDGAME_EDGE_EXIT_MULT from {:.4} to {:.4} (must be <= keep_mult)",
                 self.endgame_edge_exit_mult, self.endgame_edge_keep_mult
             );
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                "PM_ENDGAME_EDGE_EXIT_MULT",
+                format!(
+                    "{:.4} > keep_mult {:.4}; clamped",
+                    self.endgame_edge_exit_mult, self.endgame_edge_keep_mult
+                ),
+            );
             self.endgame_edge_exit_mult = self.endgame_edge_keep_mult;
         }
         // Keep windows ordered: soft >= hard >= freeze.
@@ -1159,6 +1296,14 @@ impl CoordinatorConfig {
                 "⚠️ Clamping PM_ENDGAME_HARD_CLOSE_SECS from {} to {} (must be <= soft-close)",
                 self.endgame_hard_close_secs, self.endgame_soft_close_secs
             );
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                "PM_ENDGAME_HARD_CLOSE_SECS",
+                format!(
+                    "{} > soft-close {}; clamped",
+                    self.endgame_hard_close_secs, self.endgame_soft_close_secs
+                ),
+            );
             self.endgame_hard_close_secs = self.endgame_soft_close_secs;
         }
         if self.endgame_freeze_secs > self.endgame_hard_close_secs {
@@ -1166,6 +1311,14 @@ impl CoordinatorConfig {
                 "⚠️ Clamping PM_ENDGAME_FREEZE_SECS from {} to {} (must be <= hard-close)",
                 self.endgame_freeze_secs, self.endgame_hard_close_secs
             );
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                "PM_ENDGAME_FREEZE_SECS",
+                format!(
+                    "{} > hard-close {}; clamped",
+                    self.endgame_freeze_secs, self.endgame_hard_close_secs
+                ),
+            );
             self.endgame_freeze_secs = self.endgame_hard_close_secs;
         }
         if self.endgame_maker_repair_min_secs > self.endgame_hard_close_secs {
@@ -1350,6 +1503,7 @@ struct Stats {
     pgt_high_pressure_no_seed: [u64; PGT_HIGH_PRESSURE_NO_SEED_REASON_COUNT],
     market_trade_ticks: u64,
     market_sell_trade_ticks: u64,
+    placement_rejects: [u64; RejectKind::COUNT],
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -1800,10 +1954,19 @@ pub struct StrategyCoordinator {
     /// Kept separate from `md_rx` watch channel to avoid coalescing loss under high WS tick rate.
     winner_hint_rx: mpsc::Receiver<MarketDataMsg>,
     glft_rx: watch::Receiver<GlftSignalSnapshot>,
+    /// Live L2 ladder from the market WS. Read at tick time only, so it never
+    /// wakes the loop; defaults to a never-updated channel.
+    l2_book_rx: watch::Receiver<L2BookSnapshot>,
+    /// Supervisor-level portfolio risk directive, read at tick time like the
+    /// L2 ladder; defaults to a never-updated `Normal` channel.
+    portfolio_risk_rx: watch::Receiver<PortfolioRiskDirective>,
+    portfolio_risk_cancel_sent: bool,
     om_tx: mpsc::Sender<OrderManagerCmd>,
     /// Opt-4: Direct high-priority kill channel from OFI Engine.
     /// Fires on toxicity onset without waiting for the next book tick.
     kill_rx: mpsc::Receiver<KillSwitchSignal>,
+    /// ToxicFlow cancels echoed back to the OFI engine for kill attribution.
+    toxic_cancel_tx: Option<mpsc::Sender<ToxicCancelReport>>,
     /// Execution-layer feedback channel used for adaptive maker safety.
     feedback_rx: mpsc::Receiver<ExecutionFeedback>,
     /// D+ source-truth feed channel. Carries no-secret order/fill/wallet/redeem/cashflow facts.
@@ -1817,6 +1980,11 @@ pub struct StrategyCoordinator {
     /// Optional shared winner-side cache for non-oracle strategies that need
     /// post-close winner awareness after the coordinator task has been moved.
     shared_post_close_winner_side: Option<Arc<Mutex<Option<Side>>>>,
+    /// Crash-safe snapshot of maker friction and the PGT flat-seed latch.
+    state_store: Option<ActorStateHandle>,
+    state_save: StateSaveThrottle,
+    /// Time source for every decision timestamp (system clock unless injected).
+    clock: SharedClock,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -1899,6 +2067,7 @@ pub struct CoordinatorObsSnapshot {
     pub pgt_high_pressure_no_seed: [u64; PGT_HIGH_PRESSURE_NO_SEED_REASON_COUNT],
     pub market_trade_ticks: u64,
     pub market_sell_trade_ticks: u64,
+    pub placement_rejects: [u64; RejectKind::COUNT],
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -2036,7 +2205,11 @@ impl StrategyCoordinator {
         shared_post_close_winner_side: Option<Arc<Mutex<Option<Side>>>>,
     ) -> Self {
         let (_dead_xuan_source_truth_tx, dead_xuan_source_truth_rx) = mpsc::channel(1);
-        let now = Instant::now();
+        let (_dead_l2_book_tx, dead_l2_book_rx) = watch::channel(L2BookSnapshot::default());
+        let (_dead_portfolio_risk_tx, dead_portfolio_risk_rx) =
+            watch::channel(PortfolioRiskDirective::default());
+        let clock = system_clock();
+        let now = clock.now();
         let last_metrics_log_ts = if cfg.strategy_metrics_log_secs > 0 {
             now.checked_sub(Duration::from_secs(cfg.strategy_metrics_log_secs))
                 .unwrap_or(now)
@@ -2052,14 +2225,12 @@ impl StrategyCoordinator {
             last_public_buy_trade_by_side: [None; 2],
             last_high_side_public_buy_trade: None,
             public_buy_pressure_events: VecDeque::new(),
-            last_valid_ts_yes: Instant::now(),
-            last_valid_ts_no: Instant::now(),
+            last_valid_ts_yes: now,
+            last_valid_ts_no: now,
             yes_stale_since: None,
             no_stale_since: None,
             slot_targets: std::array::from_fn(|_| None),
-            slot_last_ts: std::array::from_fn(|_| {
-                Instant::now() - std::time::Duration::from_secs(60)
-            }),
+            slot_last_ts: std::array::from_fn(|_| now - std::time::Duration::from_secs(60)),
             slot_shadow_targets: std::array::from_fn(|_| None),
             slot_shadow_since: std::array::from_fn(|_| None),
             slot_shadow_velocity_tps: [0.0; 4],
@@ -2073,11 +2244,11 @@ impl StrategyCoordinator {
             slot_last_recovery_publish_at: std::array::from_fn(|_| None),
             slot_last_recovery_cross_seen_at: std::array::from_fn(|_| None),
             slot_last_regime_seen: std::array::from_fn(|_| None),
-            slot_regime_changed_at: std::array::from_fn(|_| Instant::now()),
+            slot_regime_changed_at: std::array::from_fn(|_| now),
             slot_publish_budget: [2.0; 4],
-            slot_last_budget_refill: std::array::from_fn(|_| Instant::now()),
+            slot_last_budget_refill: std::array::from_fn(|_| now),
             slot_publish_debt_accum: [0.0; 4],
-            slot_last_debt_refill: std::array::from_fn(|_| Instant::now()),
+            slot_last_debt_refill: std::array::from_fn(|_| now),
             slot_absent_clear_since: std::array::from_fn(|_| None),
             slot_pair_arb_state_keys: std::array::from_fn(|_| None),
             slot_pair_arb_intent_state_keys: std::array::from_fn(|_| None),
@@ -2105,11 +2276,11 @@ impl StrategyCoordinator {
             pair_arb_slot_blocked_at: [None; 4],
             yes_target: None,
             no_target: None,
-            yes_last_ts: Instant::now() - std::time::Duration::from_secs(60),
-            no_last_ts: Instant::now() - std::time::Duration::from_secs(60),
-            market_start: Instant::now(),
-            yes_toxic_hold_until: Instant::now() - std::time::Duration::from_secs(60),
-            no_toxic_hold_until: Instant::now() - std::time::Duration::from_secs(60),
+            yes_last_ts: now - std::time::Duration::from_secs(60),
+            no_last_ts: now - std::time::Duration::from_secs(60),
+            market_start: now,
+            yes_toxic_hold_until: now - std::time::Duration::from_secs(60),
+            no_toxic_hold_until: now - std::time::Duration::from_secs(60),
             was_hot_yes: false,
             was_hot_no: false,
             was_toxic_yes: false,
@@ -2179,8 +2350,12 @@ impl StrategyCoordinator {
             md_rx,
             winner_hint_rx,
             glft_rx,
+            l2_book_rx: dead_l2_book_rx,
+            portfolio_risk_rx: dead_portfolio_risk_rx,
+            portfolio_risk_cancel_sent: false,
             om_tx,
             kill_rx,
+            toxic_cancel_tx: None,
             feedback_rx,
             xuan_b27_dplus_source_truth_rx: dead_xuan_source_truth_rx,
             slot_release_rx,
@@ -2188,14 +2363,60 @@ impl StrategyCoordinator {
             recorder: None,
             recorder_meta: None,
             shared_post_close_winner_side,
+            state_store: None,
+            state_save: StateSaveThrottle::default(),
+            clock,
         }
     }
 
+    /// Swap in a different time source (e.g. `clock::SimClock` for replays and
+    /// tests). Construction-time timestamps are re-anchored to the new clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        let now = clock.now();
+        let stale = now - Duration::from_secs(60);
+        self.last_valid_ts_yes = now;
+        self.last_valid_ts_no = now;
+        self.slot_last_ts = [stale; 4];
+        self.slot_regime_changed_at = [now; 4];
+        self.slot_last_budget_refill = [now; 4];
+        self.slot_last_debt_refill = [now; 4];
+        self.yes_last_ts = stale;
+        self.no_last_ts = stale;
+        self.market_start = now;
+        self.yes_toxic_hold_until = stale;
+        self.no_toxic_hold_until = stale;
+        self.last_metrics_log_ts = if self.cfg.strategy_metrics_log_secs > 0 {
+            now.checked_sub(Duration::from_secs(self.cfg.strategy_metrics_log_secs))
+                .unwrap_or(now)
+        } else {
+            now
+        };
+        self.pair_arb_gate_last_log_ts = now;
+        self.pgt_gate_last_log_ts = now;
+        self.clock = clock;
+        self
+    }
+
     pub fn with_obs_tx(mut self, obs_tx: watch::Sender<CoordinatorObsSnapshot>) -> Self {
         self.obs_tx = Some(obs_tx);
         self
     }
 
+    pub fn with_l2_book_rx(mut self, rx: watch::Receiver<L2BookSnapshot>) -> Self {
+        self.l2_book_rx = rx;
+        self
+    }
+
+    pub fn with_portfolio_risk_rx(mut self, rx: watch::Receiver<PortfolioRiskDirective>) -> Self {
+        self.portfolio_risk_rx = rx;
+        self
+    }
+
+    pub fn with_toxic_cancel_tx(mut self, tx: mpsc::Sender<ToxicCancelReport>) -> Self {
+        self.toxic_cancel_tx = Some(tx);
+        self
+    }
+
     pub fn with_xuan_b27_dplus_source_truth_rx(
         mut self,
         rx: mpsc::Receiver<XuanB27DplusSourceTruthEvent>,
@@ -2226,6 +2447,10 @@ impl StrategyCoordinator {
         &self.cfg
     }
 
+    pub(crate) fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     pub(crate) fn set_xuan_b27_dplus_runtime_source_truth(
         &mut self,
         source_truth: XuanB27DplusRuntimeSourceTruth,
@@ -2323,10 +2548,7 @@ impl StrategyCoordinator {
         if window == 0 {
             return false;
         }
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now = self.clock.unix_now_secs();
         now >= end_ts && now < end_ts.saturating_add(window)
     }
 
@@ -2457,13 +2679,14 @@ impl StrategyCoordinator {
                 _ = watchdog.tick() => {
                     self.tick().await;
                     self.emit_obs_snapshot();
+                    self.maybe_save_state();
                 }
             }
         }
 
         let final_inv = *self.inv_rx.borrow();
         let final_metrics = self.derive_inventory_metrics(&final_inv.working);
-        self.flush_reference_blocked_time(Instant::now());
+        self.flush_reference_blocked_time(self.clock.now());
         let dominant_side = match final_metrics.dominant_side {
             Some(Side::Yes) => "YES",
             Some(Side::No) => "NO",
@@ -2550,6 +2773,24 @@ impl StrategyCoordinator {
         self.emit_live_observability_tags();
     }
 
+    /// Synchronous single step used by the backtest harness instead of `run()`.
+    ///
+    /// Drains pending execution feedback / slot releases, applies `msg` (if any)
+    /// and re-evaluates once, mirroring the corresponding `run()` select arms.
+    pub(crate) async fn drive_step(&mut self, msg: Option<MarketDataMsg>) {
+        while let Ok(feedback) = self.feedback_rx.try_recv() {
+            self.handle_execution_feedback(feedback);
+        }
+        while let Ok(release) = self.slot_release_rx.try_recv() {
+            self.handle_slot_release_event(release).await;
+        }
+        if let Some(msg) = msg {
+            self.handle_market_data(msg).await;
+        }
+        self.tick().await;
+        self.emit_obs_snapshot();
+    }
+
     fn emit_obs_snapshot(&self) {
         let Some(obs_tx) = &self.obs_tx else {
             return;
@@ -2629,6 +2870,7 @@ impl StrategyCoordinator {
             pgt_high_pressure_no_seed: self.stats.pgt_high_pressure_no_seed,
             market_trade_ticks: self.stats.market_trade_ticks,
             market_sell_trade_ticks: self.stats.market_sell_trade_ticks,
+            placement_rejects: self.stats.placement_rejects,
         };
         let _ = obs_tx.send(snapshot);
     }
@@ -2823,7 +3065,7 @@ impl StrategyCoordinator {
             if self.book.yes_ask > 0.0 {
                 self.last_valid_book.yes_ask = self.book.yes_ask;
             }
-            self.last_valid_ts_yes = Instant::now();
+            self.last_valid_ts_yes = self.clock.now();
             self.yes_stale_since = None;
         }
         let no_fresh = if pgt_bid_only_freshness {
@@ -2836,14 +3078,14 @@ impl StrategyCoordinator {
             if self.book.no_ask > 0.0 {
                 self.last_valid_book.no_ask = self.book.no_ask;
             }
-            self.last_valid_ts_no = Instant::now();
+            self.last_valid_ts_no = self.clock.now();
             self.no_stale_since = None;
         }
     }
 
     pub(crate) fn recent_public_trade(&self, max_age: Duration) -> Option<PublicTradeSnapshot> {
         let trade = self.last_public_trade?;
-        if Instant::now().saturating_duration_since(trade.ts) <= max_age {
+        if self.clock.now().saturating_duration_since(trade.ts) <= max_age {
             Some(trade)
         } else {
             None
@@ -2856,7 +3098,7 @@ impl StrategyCoordinator {
         max_age: Duration,
     ) -> Option<PublicTradeSnapshot> {
         let trade = self.last_public_trade_by_side[side.index()]?;
-        if Instant::now().saturating_duration_since(trade.ts) <= max_age {
+        if self.clock.now().saturating_duration_since(trade.ts) <= max_age {
             Some(trade)
         } else {
             None
@@ -2869,7 +3111,7 @@ impl StrategyCoordinator {
         max_age: Duration,
     ) -> Option<PublicTradeSnapshot> {
         let trade = self.last_public_buy_trade_by_side[side.index()]?;
-        if Instant::now().saturating_duration_since(trade.ts) <= max_age {
+        if self.clock.now().saturating_duration_since(trade.ts) <= max_age {
             Some(trade)
         } else {
             None
@@ -2882,7 +3124,7 @@ impl StrategyCoordinator {
         lookback: Duration,
     ) -> Option<PublicBuyPressureSnapshot> {
         let latest_trade = self.last_high_side_public_buy_trade?;
-        if Instant::now().saturating_duration_since(latest_trade.ts) > max_age {
+        if self.clock.now().saturating_duration_since(latest_trade.ts) > max_age {
             return None;
         }
         let cutoff = latest_trade
@@ -2909,15 +3151,16 @@ impl StrategyCoordinator {
     }
 
     pub(crate) fn pgt_buy_slot_age(&self, side: Side) -> Duration {
-        self.slot_last_ts(OrderSlot::new(side, TradeDirection::Buy))
-            .elapsed()
+        self.clock
+            .elapsed(self.slot_last_ts(OrderSlot::new(side, TradeDirection::Buy)))
     }
 
     /// P5 FIX: Check if either side's book data is stale (>30s without fresh data).
     /// Uses per-side timestamps so YES updates don't mask NO staleness.
     fn is_book_stale(&self) -> bool {
         let limit = std::time::Duration::from_secs(30);
-        self.last_valid_ts_yes.elapsed() > limit || self.last_valid_ts_no.elapsed() > limit
+        self.clock.elapsed(self.last_valid_ts_yes) > limit
+            || self.clock.elapsed(self.last_valid_ts_no) > limit
     }
 
     fn stale_side_actionable(
@@ -3009,7 +3252,7 @@ impl StrategyCoordinator {
         if !self.cfg.strategy.is_pair_gated_tranche_arb() {
             return None;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         if !self.pgt_flat_seed_latch_exhausted {
             if let (Some(side), Some(until)) = (
                 self.pgt_flat_seed_latched_side,
@@ -3069,7 +3312,7 @@ impl StrategyCoordinator {
         if self.pgt_flat_seed_latch_exhausted {
             return;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         if quotes.diagnostics.pgt_single_seed_bias == 0 {
             return;
         }
@@ -3267,7 +3510,7 @@ impl StrategyCoordinator {
         let replace_ratio = Self::obs_ratio(self.stats.replace_events, self.stats.placed);
         let reprice_ratio_raw = Self::obs_ratio(self.stats.cancel_reprice, self.stats.placed);
         let reprice_ratio = reprice_ratio_raw;
-        let elapsed_secs = self.market_start.elapsed().as_secs_f64().max(1.0);
+        let elapsed_secs = self.clock.elapsed(self.market_start).as_secs_f64().max(1.0);
         let replace_per_min = (self.stats.replace_events as f64) * 60.0 / elapsed_secs;
         let low_sample = self.stats.placed < LIVE_OBS_MIN_PLACED_SAMPLE;
 
@@ -3576,7 +3819,7 @@ impl StrategyCoordinator {
     // ═════════════════════════════════════════════════
 
     async fn tick(&mut self) {
-        let now = Instant::now();
+        let now = self.clock.now();
         self.decay_maker_friction(now);
         let ofi = *self.ofi_rx.borrow();
         let inv_snapshot = self.current_inventory_snapshot();
@@ -3798,7 +4041,7 @@ impl StrategyCoordinator {
         if self.cfg.strategy.is_oracle_lag_sniping() && self.is_in_post_close_window() {
             let emit = match self.last_post_close_snapshot_ts {
                 None => true,
-                Some(prev) => prev.elapsed() >= Duration::from_millis(500),
+                Some(prev) => self.clock.elapsed(prev) >= Duration::from_millis(500),
             };
             if emit {
                 self.last_post_close_snapshot_ts = Some(now);
@@ -3824,10 +4067,7 @@ impl StrategyCoordinator {
                 };
                 let winner_ask_tradable = winner_ask > 0.0
                     && (winner_bid <= 0.0 || winner_ask > winner_bid + 0.5 * winner_tick + 1e-9);
-                let now_ms = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as i64;
+                let now_ms = self.clock.unix_now_ms() as i64;
                 let market_end_ms = self
                     .cfg
                     .market_end_ts
@@ -3856,10 +4096,25 @@ impl StrategyCoordinator {
 
         // Priority 4: Strategy quote + unified flow-risk overlay + execution.
         let metrics = self.derive_inventory_metrics(&decision_inv);
-        let l2_depth = crate::polymarket::strategy::get_l2_depth_with_fallback(
-            self.cfg.slug.as_deref(),
-            self.cfg.market_end_ts,
-        );
+        // Live ladder first; the offline depth tape only covers rounds without one.
+        let l2_book = Some(self.l2_book_rx.borrow().clone())
+            .filter(L2BookSnapshot::is_complete)
+            .filter(|book| {
+                self.cfg.l2_book_max_age_ms == 0
+                    || book.is_fresh(
+                        self.clock.now(),
+                        Duration::from_millis(self.cfg.l2_book_max_age_ms),
+                    )
+            });
+        let l2_depth = l2_book
+            .as_ref()
+            .and_then(L2BookSnapshot::depth_5lvl)
+            .or_else(|| {
+                crate::polymarket::strategy::get_l2_depth_with_fallback(
+                    self.cfg.slug.as_deref(),
+                    self.cfg.market_end_ts,
+                )
+            });
         let input = StrategyTickInput {
             inv: &decision_inv,
             settled_inv: &settled_inv,
@@ -3872,6 +4127,7 @@ impl StrategyCoordinator {
             ofi: Some(&ofi),
             glft: glft_snapshot.as_ref(),
             l2_depth,
+            l2_book: l2_book.as_ref(),
         };
         let mut quotes = self.cfg.strategy.compute_quotes(self, input);
         if self.cfg.strategy == StrategyKind::XuanB27Dplus {
@@ -3897,6 +4153,16 @@ impl StrategyCoordinator {
             yes_toxic_blocked,
             no_toxic_blocked,
         );
+        self.apply_portfolio_risk(&working_inv, &mut quotes).await;
+        self.apply_residual_unwind(
+            &settled_inv,
+            &ub,
+            &inv_snapshot.pair_ledger,
+            yes_stale_raw && !post_close_stale_immune,
+            no_stale_raw && !post_close_stale_immune,
+            &mut quotes,
+        )
+        .await;
         self.update_pgt_flat_seed_latch(&quotes, inv_snapshot.pair_ledger.active_tranche.is_some());
         if self.cfg.strategy.is_pair_gated_tranche_arb() {
             let remaining_quotes =
@@ -3918,6 +4184,83 @@ impl StrategyCoordinator {
         .await;
     }
 
+    // Policy-2b: Portfolio risk overlay (supervisor-level caps across markets)
+    async fn apply_portfolio_risk(&mut self, inv: &InventoryState, quotes: &mut StrategyQuotes) {
+        let directive = *self.portfolio_risk_rx.borrow();
+        if directive.action != PortfolioRiskAction::CancelAll {
+            self.portfolio_risk_cancel_sent = false;
+        }
+        match directive.action {
+            PortfolioRiskAction::Normal => {}
+            PortfolioRiskAction::Throttle => {
+                for slot in [OrderSlot::YES_BUY, OrderSlot::NO_BUY] {
+                    let Some(mut intent) = quotes.get(slot) else {
+                        continue;
+                    };
+                    intent.size *= directive.size_mult;
+                    if intent.size > 0.0 && intent.size + 1e-9 >= self.cfg.min_order_size {
+                        quotes.set(intent);
+                    } else {
+                        quotes.clear(slot);
+                    }
+                }
+            }
+            PortfolioRiskAction::ReduceOnly => {
+                let keep_side = PortfolioRiskDirective::reduce_only_side(inv);
+                let gap = (inv.yes_qty - inv.no_qty).abs();
+                for side in [Side::Yes, Side::No] {
+                    let slot = OrderSlot::new(side, TradeDirection::Buy);
+                    let Some(mut intent) = quotes.get(slot) else {
+                        continue;
+                    };
+                    intent.size = intent.size.min(gap);
+                    if keep_side == Some(side)
+                        && intent.size > 0.0
+                        && intent.size + 1e-9 >= self.cfg.min_order_size
+                    {
+                        quotes.set(intent);
+                    } else {
+                        quotes.clear(slot);
+                    }
+                }
+            }
+            PortfolioRiskAction::CancelAll => {
+                for slot in [OrderSlot::YES_BUY, OrderSlot::NO_BUY] {
+                    quotes.clear(slot);
+                }
+                if !self.portfolio_risk_cancel_sent {
+                    warn!(
+                        "🛡️ portfolio_risk cancel_all | limit={} yes_qty={:.2} no_qty={:.2}",
+                        directive.limit.map(|l| l.as_str()).unwrap_or("-"),
+                        inv.yes_qty,
+                        inv.no_qty
+                    );
+                    let _ = self.om_tx.send(OrderManagerCmd::CancelAll).await;
+                    self.portfolio_risk_cancel_sent = true;
+                }
+            }
+        }
+    }
+
+    /// Portfolio-risk size for a taker BUY, which bypasses `apply_portfolio_risk`.
+    /// Under ReduceOnly and CancelAll only the pairing side may buy, capped at
+    /// the residual gap; 0 rejects.
+    pub(super) fn portfolio_risk_taker_buy_size(&self, side: Side, size: f64) -> f64 {
+        let directive = *self.portfolio_risk_rx.borrow();
+        match directive.action {
+            PortfolioRiskAction::Normal => size,
+            PortfolioRiskAction::Throttle => size * directive.size_mult,
+            PortfolioRiskAction::ReduceOnly | PortfolioRiskAction::CancelAll => {
+                let inv = self.current_working_inventory();
+                if PortfolioRiskDirective::reduce_only_side(&inv) == Some(side) {
+                    size.min((inv.yes_qty - inv.no_qty).abs())
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
     // Policy-2: Flow Risk Overlay (toxicity / staleness)
     fn apply_flow_risk(
         &self,
@@ -4078,6 +4421,7 @@ impl StrategyCoordinator {
                 kind,
                 ts: _,
             } => {
+                self.stats.placement_rejects[kind.index()] += 1;
                 if self.cfg.strategy.is_oracle_lag_sniping()
                     && self.cfg.oracle_lag_sniping.market_enabled
                     && kind == RejectKind::BalanceOrAllowance
@@ -4115,7 +4459,7 @@ impl StrategyCoordinator {
     pub(super) fn recent_cross_reject(&self, side: Side, within: Duration) -> bool {
         self.maker_friction(side)
             .last_cross_reject_ts
-            .map(|ts| ts.elapsed() <= within)
+            .map(|ts| self.clock.elapsed(ts) <= within)
             .unwrap_or(false)
     }
 
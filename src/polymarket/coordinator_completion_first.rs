@@ -46,8 +46,8 @@ impl StrategyCoordinator {
 
     pub(crate) fn completion_first_book_age_ms(&self, side: Side) -> u64 {
         match side {
-            Side::Yes => self.last_valid_ts_yes.elapsed().as_millis() as u64,
-            Side::No => self.last_valid_ts_no.elapsed().as_millis() as u64,
+            Side::Yes => self.clock.elapsed(self.last_valid_ts_yes).as_millis() as u64,
+            Side::No => self.clock.elapsed(self.last_valid_ts_no).as_millis() as u64,
         }
     }
 
@@ -55,10 +55,7 @@ impl StrategyCoordinator {
         let Some(end_ts) = self.cfg.market_end_ts else {
             return false;
         };
-        let now_secs = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now_secs = self.clock.unix_now_secs();
         now_secs >= end_ts
     }
 
@@ -173,10 +170,7 @@ impl StrategyCoordinator {
             let Some(end_ts) = self.cfg.market_end_ts else {
                 return;
             };
-            let now_secs = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
+            let now_secs = self.clock.unix_now_secs();
             let elapsed = now_secs.saturating_sub(end_ts);
             let winner_side = self.post_close_winner_side;
             let residual = match winner_side {
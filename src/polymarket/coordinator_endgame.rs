@@ -123,10 +123,7 @@ impl StrategyCoordinator {
 
     pub(crate) fn seconds_to_market_end(&self) -> Option<u64> {
         let end_ts = self.cfg.market_end_ts?;
-        let now_secs = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now_secs = self.clock.unix_now_secs();
         Some(end_ts.saturating_sub(now_secs))
     }
 
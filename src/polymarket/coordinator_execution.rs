@@ -106,7 +106,7 @@ impl StrategyCoordinator {
             && event.slot.direction == TradeDirection::Buy
         {
             self.pgt_same_side_release_quarantine_until[event.slot.side.index()] = Some(
-                std::time::Instant::now()
+                self.clock.now()
                     + std::time::Duration::from_millis(PGT_SAME_SIDE_RELEASE_QUARANTINE_MS),
             );
         }
@@ -138,7 +138,7 @@ impl StrategyCoordinator {
             // Pairing/reducing leg is event-driven plus a slow timed upward
             // freshness check to avoid long stale quotes in drifting books.
             crate::polymarket::strategy::pair_arb::PairArbRiskEffect::PairingOrReducing => {
-                let slot_age = self.slot_last_ts(slot).elapsed();
+                let slot_age = self.clock.elapsed(self.slot_last_ts(slot));
                 let timed_reprice_due = slot_age >= std::time::Duration::from_secs(8);
                 let upward_stale = delta_ticks >= 3.0 - 1e-9;
                 if timed_reprice_due && upward_stale {
@@ -583,7 +583,7 @@ impl StrategyCoordinator {
         }
         let tick = self.cfg.tick_size.max(1e-9);
         let delta_ticks = (intent.price - current.price) / tick;
-        let slot_age = self.slot_last_ts(slot).elapsed();
+        let slot_age = self.clock.elapsed(self.slot_last_ts(slot));
         let remaining_secs = self.seconds_to_market_end().unwrap_or(u64::MAX);
         let (retain, retain_reason) =
             self.pgt_buy_retain_decision(intent.reason, delta_ticks, slot_age, remaining_secs);
@@ -646,7 +646,10 @@ impl StrategyCoordinator {
         if remaining_secs <= PGT_TAIL_NO_NEW_OPEN_SECS {
             return false;
         }
-        if !pgt_absent_seed_retain_allowed(remaining_secs, self.slot_last_ts(slot).elapsed()) {
+        if !pgt_absent_seed_retain_allowed(
+            remaining_secs,
+            self.clock.elapsed(self.slot_last_ts(slot)),
+        ) {
             return false;
         }
         let snapshot = self.inv_rx.borrow();
@@ -1051,7 +1054,7 @@ impl StrategyCoordinator {
             return None;
         }
         let gap = std::time::Duration::from_secs(XUAN_PAIR_ASK_RESCUE_GAP_COOLDOWN_SECS);
-        if self.yes_last_ts.elapsed() < gap || self.no_last_ts.elapsed() < gap {
+        if self.clock.elapsed(self.yes_last_ts) < gap || self.clock.elapsed(self.no_last_ts) < gap {
             return None;
         }
         if yes_toxic_blocked || no_toxic_blocked || yes_stale || no_stale {
@@ -1064,9 +1067,9 @@ impl StrategyCoordinator {
             return None;
         }
         if self.pgt_same_side_release_quarantine_until[Side::Yes.index()]
-            .is_some_and(|until| until > std::time::Instant::now())
+            .is_some_and(|until| until > self.clock.now())
             || self.pgt_same_side_release_quarantine_until[Side::No.index()]
-                .is_some_and(|until| until > std::time::Instant::now())
+                .is_some_and(|until| until > self.clock.now())
         {
             return None;
         }
@@ -1115,7 +1118,7 @@ impl StrategyCoordinator {
             XUAN_PAIR_ASK_RESCUE_GAP_COOLDOWN_SECS,
         );
 
-        let now = std::time::Instant::now();
+        let now = self.clock.now();
         for slot in [OrderSlot::YES_BUY, OrderSlot::NO_BUY] {
             self.slot_last_ts[slot.index()] = now;
         }
@@ -1232,7 +1235,7 @@ impl StrategyCoordinator {
             return None;
         }
         if self.pgt_same_side_release_quarantine_until[side.index()]
-            .is_some_and(|until| until > std::time::Instant::now())
+            .is_some_and(|until| until > self.clock.now())
         {
             return None;
         }
@@ -1314,7 +1317,7 @@ impl StrategyCoordinator {
             let glft = *self.glft_rx.borrow();
             if !self.glft_is_tradeable_snapshot(glft) {
                 let retain_short_source_block =
-                    self.glft_should_retain_on_short_source_block(glft, std::time::Instant::now());
+                    self.glft_should_retain_on_short_source_block(glft, self.clock.now());
                 for slot in OrderSlot::ALL {
                     if self.slot_target_active(slot) {
                         if retain_short_source_block {
@@ -1538,7 +1541,7 @@ impl StrategyCoordinator {
         }
 
         if self.cfg.strategy.is_pair_arb() {
-            let now = std::time::Instant::now();
+            let now = self.clock.now();
             let idx = slot.index();
             let state_key = self.pair_arb_state_key(inv, phase);
             let prev_state = self.slot_pair_arb_state_keys[idx];
@@ -1623,7 +1626,7 @@ impl StrategyCoordinator {
             );
         }
 
-        let now = std::time::Instant::now();
+        let now = self.clock.now();
         let idx = slot.index();
         let since = self.slot_absent_clear_since[idx].get_or_insert(now);
         let elapsed = now.saturating_duration_since(*since);
@@ -1736,7 +1739,7 @@ impl StrategyCoordinator {
         if self.cfg.strategy.is_pair_gated_tranche_arb()
             && intent.direction == TradeDirection::Buy
             && self.pgt_same_side_release_quarantine_until[intent.side.index()]
-                .is_some_and(|until| until > std::time::Instant::now())
+                .is_some_and(|until| until > self.clock.now())
         {
             return false;
         }
@@ -1812,7 +1815,7 @@ impl StrategyCoordinator {
         let Some(last_ts) = self.pair_arb_slot_blocked_at[idx] else {
             return false;
         };
-        if last_ts.elapsed()
+        if self.clock.elapsed(last_ts)
             > std::time::Duration::from_millis(super::PAIR_ARB_OPPOSITE_SLOT_BLOCK_TTL_MS)
         {
             return false;
@@ -1981,7 +1984,7 @@ impl StrategyCoordinator {
                     side, intent.price, intent.size, limit_price
                 );
                 let slot = OrderSlot::new(side, intent.direction);
-                let now = std::time::Instant::now();
+                let now = self.clock.now();
                 self.slot_last_ts[slot.index()] = now;
                 match side {
                     Side::Yes => self.yes_last_ts = now,
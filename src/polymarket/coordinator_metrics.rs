@@ -375,7 +375,7 @@ impl StrategyCoordinator {
         if self.cfg.strategy != StrategyKind::PairArb {
             return;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         let interval = Duration::from_secs(PAIR_ARB_GATE_SUMMARY_SECS);
         if now.duration_since(self.pair_arb_gate_last_log_ts) < interval {
             return;
@@ -424,7 +424,7 @@ impl StrategyCoordinator {
         if self.cfg.strategy != StrategyKind::PairGatedTrancheArb {
             return;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         let interval = Duration::from_secs(PAIR_ARB_GATE_SUMMARY_SECS);
         if now.duration_since(self.pgt_gate_last_log_ts) < interval {
             return;
@@ -842,7 +842,7 @@ impl StrategyCoordinator {
             return;
         }
         let interval = Duration::from_secs(self.cfg.strategy_metrics_log_secs);
-        let now = Instant::now();
+        let now = self.clock.now();
         if now.duration_since(self.last_metrics_log_ts) < interval {
             return;
         }
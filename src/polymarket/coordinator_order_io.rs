@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
 
 use tracing::{debug, info, warn};
 
@@ -157,11 +157,12 @@ impl StrategyCoordinator {
             return;
         }
         if self.oracle_lag_maker_followup_last_ts.is_some_and(|prev| {
-            prev.elapsed() < Duration::from_millis(ORACLE_LAG_MAKER_FOLLOWUP_MIN_INTERVAL_MS)
+            self.clock.elapsed(prev)
+                < Duration::from_millis(ORACLE_LAG_MAKER_FOLLOWUP_MIN_INTERVAL_MS)
         }) {
             return;
         }
-        if self.slot_last_ts(slot).elapsed()
+        if self.clock.elapsed(self.slot_last_ts(slot))
             < Duration::from_millis(ORACLE_LAG_MAKER_FOLLOWUP_INFLIGHT_LOCK_MS)
         {
             debug!(
@@ -189,7 +190,7 @@ impl StrategyCoordinator {
             let same_candidate = (candidate - prev_candidate).abs() <= 0.5 * tick + 1e-9;
             if same_candidate
                 && self.oracle_lag_maker_followup_last_ts.is_some_and(|prev| {
-                    prev.elapsed()
+                    self.clock.elapsed(prev)
                         < Duration::from_millis(
                             ORACLE_LAG_MAKER_FOLLOWUP_SAME_CANDIDATE_COOLDOWN_MS,
                         )
@@ -207,7 +208,7 @@ impl StrategyCoordinator {
             }
         }
 
-        self.oracle_lag_maker_followup_last_ts = Some(Instant::now());
+        self.oracle_lag_maker_followup_last_ts = Some(self.clock.now());
         self.oracle_lag_maker_followup_last_candidate_price[slot.index()] = Some(candidate);
         info!(
             "🎯 oracle_lag_followup_upward | slot={} side={:?} live={:.4} bid={:.4} ask={:.4} next={:.4} tick={:.4}",
@@ -301,7 +302,7 @@ impl StrategyCoordinator {
         if self.cfg.strategy.is_pair_gated_tranche_arb()
             && slot.direction == TradeDirection::Buy
             && self.pgt_same_side_release_quarantine_until[slot.side.index()]
-                .is_some_and(|until| until > Instant::now())
+                .is_some_and(|until| until > self.clock.now())
         {
             debug!(
                 "🧭 PGT stale place/reprice suppressed | slot={} reason={:?}",
@@ -343,7 +344,7 @@ impl StrategyCoordinator {
         {
             price = slot_price;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         let reprice_eps = 1e-9;
         let raw_target_price = price;
         let pair_arb_state_changed = if self.cfg.strategy.is_pair_arb()
@@ -472,7 +473,8 @@ impl StrategyCoordinator {
         if self.cfg.strategy.is_glft_mm() {
             let glft = *self.glft_rx.borrow();
             if !self.glft_is_tradeable_snapshot(glft) {
-                if active && !self.glft_should_retain_on_short_source_block(glft, Instant::now()) {
+                let now = self.clock.now();
+                if active && !self.glft_should_retain_on_short_source_block(glft, now) {
                     self.clear_slot_target(slot, CancelReason::StaleData).await;
                 } else if active {
                     self.stats.retain_hits = self.stats.retain_hits.saturating_add(1);
@@ -501,7 +503,7 @@ impl StrategyCoordinator {
             // must not be throttled by generic maker debounce.
             BidReason::OracleLagProvide => 0,
         };
-        let elapsed = last_ts.elapsed();
+        let elapsed = self.clock.elapsed(last_ts);
         let debounce = std::time::Duration::from_millis(debounce_ms);
         if elapsed < debounce {
             self.stats.skipped_debounce += 1;
@@ -606,7 +608,7 @@ impl StrategyCoordinator {
             shadow_target_price = shadow_target.price;
             let shadow_age = self
                 .slot_shadow_since(slot)
-                .map(|ts| ts.elapsed())
+                .map(|ts| self.clock.elapsed(ts))
                 .unwrap_or_default();
             if active {
                 if let Some((trusted_dist, action_target_dist, _)) =
@@ -814,7 +816,7 @@ impl StrategyCoordinator {
                 } else {
                     let tick = self.cfg.tick_size.max(1e-9);
                     let delta_ticks = (price - slot_price) / tick;
-                    let slot_age = self.slot_last_ts(slot).elapsed();
+                    let slot_age = self.clock.elapsed(self.slot_last_ts(slot));
                     let remaining_secs = self.seconds_to_market_end().unwrap_or(u64::MAX);
                     let (retain, retain_reason) =
                         self.pgt_buy_retain_decision(reason, delta_ticks, slot_age, remaining_secs);
@@ -896,7 +898,7 @@ impl StrategyCoordinator {
             if glft_shadow_mode && publish_reason.is_none() && needs_reprice {
                 let shadow_age = self
                     .slot_shadow_since(slot)
-                    .map(|ts| ts.elapsed())
+                    .map(|ts| self.clock.elapsed(ts))
                     .unwrap_or_default();
                 // In GLFT shadow mode, publish debt is defined as divergence between the
                 // last published order and the latest shadow target.
@@ -1344,9 +1346,22 @@ impl StrategyCoordinator {
                 .is_some_and(|t| t.reason == BidReason::Provide);
         if pgt_seed_reprice_clear {
             self.pgt_same_side_release_quarantine_until[slot.side.index()] =
-                Some(Instant::now() + Duration::from_millis(PGT_SAME_SIDE_RELEASE_QUARANTINE_MS));
+                Some(self.clock.now() + Duration::from_millis(PGT_SAME_SIDE_RELEASE_QUARANTINE_MS));
         }
         self.note_cancel_reason(reason);
+        if reason == CancelReason::ToxicFlow {
+            if let (Some(tx), Some(target)) =
+                (self.toxic_cancel_tx.as_ref(), self.slot_target(slot))
+            {
+                let _ = tx.try_send(ToxicCancelReport {
+                    side: slot.side,
+                    direction: slot.direction,
+                    price: target.price,
+                    size: target.size,
+                    ts: self.clock.now(),
+                });
+            }
+        }
 
         self.slot_targets[slot.index()] = None;
         self.slot_shadow_targets[slot.index()] = None;
@@ -1453,6 +1468,22 @@ impl StrategyCoordinator {
             );
             return;
         }
+        let size = if direction == TradeDirection::Buy {
+            let allowed = self.portfolio_risk_taker_buy_size(side, size);
+            if allowed + 1e-9 < size {
+                warn!(
+                    "🛡️ portfolio_risk taker_buy_limited | action={} side={:?} size={:.4}->{:.4} purpose={:?}",
+                    self.portfolio_risk_rx.borrow().action.as_str(),
+                    side,
+                    size,
+                    allowed.max(0.0),
+                    purpose
+                );
+            }
+            allowed
+        } else {
+            size
+        };
         let rounded = (size * 100.0).floor() / 100.0;
         if rounded < 0.01 {
             debug!(
@@ -1884,7 +1915,7 @@ impl StrategyCoordinator {
                                 Some(winner_ask),
                             )
                             .await;
-                            self.oracle_lag_fak_last_dispatch = Some(Instant::now());
+                            self.oracle_lag_fak_last_dispatch = Some(self.clock.now());
                             self.oracle_lag_fak_shots_this_round =
                                 self.oracle_lag_fak_shots_this_round.saturating_add(1);
                             self.oracle_lag_fak_inflight_by_slug
@@ -2015,7 +2046,7 @@ impl StrategyCoordinator {
 
         let replacing = self.slot_target(slot).is_some();
         self.slot_targets[slot.index()] = Some(target.clone());
-        self.slot_last_ts[slot.index()] = Instant::now();
+        self.slot_last_ts[slot.index()] = self.clock.now();
         if self.cfg.strategy.is_pair_arb() && slot.direction == TradeDirection::Buy {
             let inv = self.current_working_inventory();
             let phase = self.endgame_phase();
@@ -2112,16 +2143,13 @@ impl StrategyCoordinator {
         {
             return;
         }
-        let now_ms = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        let now_ms = self.clock.unix_now_ms();
         let market_end_ts = self.cfg.market_end_ts;
         let market_end_ms = market_end_ts.map(|ts| ts.saturating_mul(1_000));
         let delta_from_end_ms = market_end_ms.map(|end_ms| now_ms.saturating_sub(end_ms));
         let winner_to_submit_ms = self
             .post_close_winner_ts
-            .map(|ts| ts.elapsed().as_millis() as u64);
+            .map(|ts| self.clock.elapsed(ts).as_millis() as u64);
         let final_detect_to_submit_ms = self
             .post_close_winner_final_detect_unix_ms
             .map(|ms| now_ms.saturating_sub(ms));
@@ -3255,7 +3283,7 @@ impl StrategyCoordinator {
             size,
             reason,
         };
-        let now = Instant::now();
+        let now = self.clock.now();
         if let Some(last_ts) = self.slot_shadow_last_change_ts[idx] {
             let dt = now.saturating_duration_since(last_ts).as_secs_f64();
             let prev_velocity = self.slot_shadow_velocity_tps[idx].max(0.0);
@@ -31,16 +31,13 @@ impl StrategyCoordinator {
         let Some(end_ts) = self.cfg.market_end_ts else {
             return 1.0;
         };
-        let now_secs = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now_secs = self.clock.unix_now_secs();
         if now_secs >= end_ts {
             return 1.0 + k; // Market over — max urgency
         }
         // Total window = from bot start (market_start) to end_ts.
         // Use wall-clock elapsed since we need absolute time to end_ts.
-        let elapsed = self.market_start.elapsed().as_secs_f64();
+        let elapsed = self.clock.elapsed(self.market_start).as_secs_f64();
         let remaining = (end_ts - now_secs) as f64;
         let total = elapsed + remaining;
         if total <= 0.0 {
@@ -210,9 +207,9 @@ impl StrategyCoordinator {
     pub(super) fn soft_reset_slot_publish_state(&mut self, slot: OrderSlot) {
         let idx = slot.index();
         self.slot_publish_budget[idx] = Self::glft_publish_budget_cap();
-        self.slot_last_budget_refill[idx] = std::time::Instant::now();
+        self.slot_last_budget_refill[idx] = self.clock.now();
         self.slot_publish_debt_accum[idx] = 0.0;
-        self.slot_last_debt_refill[idx] = std::time::Instant::now();
+        self.slot_last_debt_refill[idx] = self.clock.now();
         self.slot_shadow_velocity_tps[idx] = 0.0;
         self.slot_shadow_last_change_ts[idx] = None;
         self.slot_last_policy_transition[idx] = None;
@@ -229,7 +226,7 @@ impl StrategyCoordinator {
         self.slot_policy_states[idx] = None;
         self.slot_policy_since[idx] = None;
         self.slot_last_regime_seen[idx] = None;
-        self.slot_regime_changed_at[idx] = std::time::Instant::now();
+        self.slot_regime_changed_at[idx] = self.clock.now();
         self.stats.full_reset_count = self.stats.full_reset_count.saturating_add(1);
     }
 
@@ -416,7 +413,7 @@ impl StrategyCoordinator {
         // Publish cycle should settle accumulated debt decisively; partial subtraction
         // tends to create repetitive near-periodic debt publishes in trends.
         self.slot_publish_debt_accum[idx] = 0.0;
-        self.slot_last_debt_refill[idx] = std::time::Instant::now();
+        self.slot_last_debt_refill[idx] = self.clock.now();
     }
 
     fn glft_publish_debt_gain_per_sec() -> f64 {
@@ -730,7 +727,7 @@ impl StrategyCoordinator {
             return false;
         };
         let slot = OrderSlot::new(side, direction);
-        let order_age = self.slot_last_ts(slot).elapsed();
+        let order_age = self.clock.elapsed(self.slot_last_ts(slot));
         if current.direction != direction || current.reason != reason {
             return false;
         }
@@ -0,0 +1,160 @@
+use super::*;
+use crate::polymarket::pair_ledger::PairLedgerSnapshot;
+
+/// Post-only SELL of the excess leg of an active pair tranche.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ResidualUnwindPlan {
+    pub(crate) side: Side,
+    pub(crate) price: f64,
+    pub(crate) size: f64,
+    /// First-leg VWAP of the residual shares.
+    pub(crate) residual_cost: f64,
+    /// `residual_cost + opposite_ask`; INFINITY when the opposite book is empty.
+    pub(crate) completion_cost: f64,
+}
+
+impl StrategyCoordinator {
+    /// Config and strategy gate. Completion-first in shadow mode never places
+    /// live orders.
+    fn residual_unwind_enabled(&self) -> bool {
+        let strategy_applies = self.cfg.strategy.is_pair_arb()
+            || self.cfg.strategy.is_pair_gated_tranche_arb()
+            || (self.cfg.strategy == StrategyKind::CompletionFirst
+                && self.completion_first_mode() != CompletionFirstMode::Shadow);
+        self.cfg.residual_unwind.enabled && strategy_applies
+    }
+
+    /// Whether a residual SELL may be planned now: a portfolio-risk CancelAll
+    /// must not be followed by fresh SELLs.
+    pub(crate) fn residual_unwind_applies(&self) -> bool {
+        self.residual_unwind_enabled()
+            && self.portfolio_risk_rx.borrow().action != PortfolioRiskAction::CancelAll
+    }
+
+    /// Unwind when completing the residual no longer clears `pair_target` and a
+    /// post-only sell one tick above the bid beats buying the opposite leg:
+    /// `sell_price + opposite_ask > 1 + min_edge`.
+    ///
+    /// Size is capped by settled inventory so freshly matched fills are not
+    /// sold before they settle; the OMS additionally defers SELL placement
+    /// until its post-fill sell-availability warmup has elapsed.
+    pub(crate) fn plan_residual_unwind(
+        &self,
+        settled_inv: &InventoryState,
+        ub: &Book,
+        ledger: &PairLedgerSnapshot,
+    ) -> Option<ResidualUnwindPlan> {
+        if !self.residual_unwind_applies()
+            || self.market_has_ended()
+            || self.endgame_phase() == EndgamePhase::Freeze
+        {
+            return None;
+        }
+        let side = ledger.residual_side?;
+        let tranche = ledger.active_tranche?;
+        if ledger.residual_qty <= 1e-9 || tranche.first_vwap <= 0.0 {
+            return None;
+        }
+        let (residual_bid, residual_ask, opposite_ask, settled_qty) = match side {
+            Side::Yes => (ub.yes_bid, ub.yes_ask, ub.no_ask, settled_inv.yes_qty),
+            Side::No => (ub.no_bid, ub.no_ask, ub.yes_ask, settled_inv.no_qty),
+        };
+        if residual_bid <= 0.0 {
+            return None;
+        }
+        let completion_cost = if opposite_ask > 0.0 {
+            tranche.first_vwap + opposite_ask
+        } else {
+            f64::INFINITY
+        };
+        if completion_cost <= self.cfg.pair_target + 1e-9 {
+            return None;
+        }
+
+        let tick = self.cfg.tick_size.max(1e-9);
+        let mut price = self.quantize_up_to_tick(residual_bid + tick);
+        if residual_ask > 0.0 && price > residual_ask + 1e-9 {
+            price = residual_ask;
+        }
+        if price >= 1.0 - tick + 1e-9 {
+            return None;
+        }
+        if opposite_ask > 0.0
+            && price + opposite_ask <= 1.0 + self.cfg.residual_unwind.min_edge + 1e-9
+        {
+            return None;
+        }
+
+        let size = ledger.residual_qty.min(settled_qty.max(0.0));
+        if size + 1e-9 < self.cfg.min_order_size.max(0.0) || size <= 1e-9 {
+            return None;
+        }
+        Some(ResidualUnwindPlan {
+            side,
+            price,
+            size,
+            residual_cost: tranche.first_vwap,
+            completion_cost,
+        })
+    }
+
+    /// Residual-unwind overlay. While an unwind is live the strategy's buy
+    /// intents are dropped so the tranche is not re-grown or half-completed
+    /// against the resting sell; SELL targets are cleared once the plan lapses.
+    pub(super) async fn apply_residual_unwind(
+        &mut self,
+        settled_inv: &InventoryState,
+        ub: &Book,
+        ledger: &PairLedgerSnapshot,
+        yes_stale: bool,
+        no_stale: bool,
+        quotes: &mut StrategyQuotes,
+    ) {
+        if !self.residual_unwind_enabled() {
+            return;
+        }
+        // Under CancelAll the OMS already dropped the SELLs; the plan below is
+        // None, so the loop clears both targets to keep our view in sync.
+        let plan = self
+            .plan_residual_unwind(settled_inv, ub, ledger)
+            .filter(|plan| match plan.side {
+                Side::Yes => !yes_stale,
+                Side::No => !no_stale,
+            });
+
+        for slot in [OrderSlot::YES_SELL, OrderSlot::NO_SELL] {
+            if plan.is_some_and(|plan| plan.side == slot.side) {
+                continue;
+            }
+            if self.slot_target_active(slot) {
+                self.clear_slot_target(slot, CancelReason::InventoryLimit)
+                    .await;
+            }
+        }
+        let Some(plan) = plan else {
+            return;
+        };
+
+        quotes.yes_buy = None;
+        quotes.no_buy = None;
+        let slot = OrderSlot::new(plan.side, TradeDirection::Sell);
+        let log_msg = match self.slot_target(slot) {
+            Some(current)
+                if (current.price - plan.price).abs() < 1e-9
+                    && (current.size - plan.size).abs() <= 0.1 =>
+            {
+                None
+            }
+            _ => Some(format!(
+                "♻️ residual_unwind {} SELL@{:.3} sz={:.2} residual_cost={:.4} completion_cost={:.4}",
+                slot.as_str(),
+                plan.price,
+                plan.size,
+                plan.residual_cost,
+                plan.completion_cost,
+            )),
+        };
+        self.slot_place_or_reprice(slot, plan.price, plan.size, BidReason::Provide, log_msg)
+            .await;
+    }
+}
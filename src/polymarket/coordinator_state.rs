@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::polymarket::actor_state_store::{
+    remaining_ms, restore_deadline, restore_past, RestoredState,
+};
+
+const COORDINATOR_STATE_ACTOR: &str = "coordinator";
+
+/// Restart-surviving coordinator state. Timestamps in the past are stored as
+/// "ms ago", deadlines as "ms remaining", both relative to `saved_at`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoordinatorStateSnapshot {
+    /// Extra maker safety ticks per side (YES, NO).
+    pub maker_friction_ticks: [u8; 2],
+    pub maker_friction_cross_reject_ago_ms: [Option<u64>; 2],
+    pub pgt_flat_seed_latched_side: Option<Side>,
+    pub pgt_flat_seed_latched_since_ago_ms: Option<u64>,
+    pub pgt_flat_seed_latched_until_ms: Option<u64>,
+    pub pgt_flat_seed_latch_exhausted: bool,
+}
+
+impl StrategyCoordinator {
+    /// Persist maker friction and the PGT flat-seed latch through `store`,
+    /// reloading the previous snapshot of the same market first.
+    pub async fn with_state_store(mut self, store: ActorStateHandle) -> Self {
+        if let Some(restored) = store
+            .load::<CoordinatorStateSnapshot>(COORDINATOR_STATE_ACTOR)
+            .await
+        {
+            info!(
+                "♻️ Coordinator state restored | slug={} age={}ms friction(yes/no)={}/{} pgt_latch={:?}",
+                store.slug(),
+                restored.age.as_millis(),
+                restored.state.maker_friction_ticks[0],
+                restored.state.maker_friction_ticks[1],
+                restored.state.pgt_flat_seed_latched_side,
+            );
+            self.restore_state(restored);
+        }
+        self.state_store = Some(store);
+        self
+    }
+
+    pub(crate) fn export_state(&self) -> CoordinatorStateSnapshot {
+        let now = self.clock.now();
+        let ago_ms =
+            |ts: Option<Instant>| ts.map(|ts| now.saturating_duration_since(ts).as_millis() as u64);
+        CoordinatorStateSnapshot {
+            maker_friction_ticks: Side::ALL
+                .map(|side| self.maker_friction(side).extra_safety_ticks),
+            maker_friction_cross_reject_ago_ms: Side::ALL
+                .map(|side| ago_ms(self.maker_friction(side).last_cross_reject_ts)),
+            pgt_flat_seed_latched_side: self.pgt_flat_seed_latched_side,
+            pgt_flat_seed_latched_since_ago_ms: ago_ms(self.pgt_flat_seed_latched_since),
+            pgt_flat_seed_latched_until_ms: remaining_ms(self.pgt_flat_seed_latched_until, now),
+            pgt_flat_seed_latch_exhausted: self.pgt_flat_seed_latch_exhausted,
+        }
+    }
+
+    pub(crate) fn restore_state(&mut self, restored: RestoredState<CoordinatorStateSnapshot>) {
+        let now = self.clock.now();
+        let RestoredState { state, age } = restored;
+        for side in Side::ALL {
+            let friction = self.maker_friction_mut(side);
+            friction.extra_safety_ticks = state.maker_friction_ticks[side.index()];
+            friction.last_cross_reject_ts = restore_past(
+                state.maker_friction_cross_reject_ago_ms[side.index()],
+                age,
+                now,
+            );
+        }
+        self.pgt_flat_seed_latched_side = state.pgt_flat_seed_latched_side;
+        self.pgt_flat_seed_latched_since =
+            restore_past(state.pgt_flat_seed_latched_since_ago_ms, age, now);
+        self.pgt_flat_seed_latched_until =
+            restore_deadline(state.pgt_flat_seed_latched_until_ms, age, now);
+        if self.pgt_flat_seed_latched_since.is_none() || self.pgt_flat_seed_latched_until.is_none()
+        {
+            self.pgt_flat_seed_latched_side = None;
+        }
+        self.pgt_flat_seed_latch_exhausted = state.pgt_flat_seed_latch_exhausted;
+    }
+
+    pub(super) fn maybe_save_state(&mut self) {
+        let Some(store) = self.state_store.as_ref() else {
+            return;
+        };
+        if self.state_save.due(self.clock.now(), store.interval()) {
+            store.save(COORDINATOR_STATE_ACTOR, &self.export_state());
+        }
+    }
+}
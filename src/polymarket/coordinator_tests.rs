@@ -1,5 +1,6 @@
 use super::coordinator_execution::PgtPairAskRescueCandidate;
 use super::*;
+use crate::polymarket::clock::SimClock;
 use crate::polymarket::glft::{
     DriftMode, FitQuality, GlftFitStatus, GlftReadinessBlockers, GlftSignalState, QuoteRegime,
     ReferenceHealth, WarmStartStatus,
@@ -9,6 +10,7 @@ use crate::polymarket::pair_ledger::{
     build_pair_ledger, EpisodeMetrics, PairLedgerEvent, PairLedgerEventKind, PairLedgerSnapshot,
     PathKind,
 };
+use crate::polymarket::portfolio_risk::PortfolioRiskLimit;
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -1019,6 +1021,7 @@ fn phase_builder_quotes(c: CoordinatorConfig, inv: InventoryState, book: Book) -
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -1041,6 +1044,7 @@ fn gabagool_grid_quotes(c: CoordinatorConfig, inv: InventoryState, book: Book) -
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -1067,6 +1071,7 @@ fn gabagool_corridor_quotes(
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -1094,6 +1099,7 @@ fn pair_arb_quotes(
             ofi: ofi.as_ref(),
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -1130,6 +1136,7 @@ fn pair_gated_tranche_quotes_with_ofi(
             ofi: ofi.as_ref(),
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -1140,6 +1147,7 @@ fn pgt_fill(side: Side, size: f64, price: f64) -> PairLedgerEvent {
         direction: TradeDirection::Buy,
         size,
         price,
+        fee: 0.0,
         ts: Instant::now(),
         kind: PairLedgerEventKind::Fill,
     }
@@ -1169,6 +1177,7 @@ fn pair_arb_quotes_with_snapshot(
             ofi: ofi.as_ref(),
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     )
 }
@@ -5379,6 +5388,43 @@ async fn test_cancel_stats_track_toxic_and_inventory_reasons() {
     assert_eq!(coord.stats.cancel_inv, 1);
 }
 
+#[tokio::test]
+async fn test_toxic_cancel_is_reported_for_attribution() {
+    let mut c = cfg();
+    c.dry_run = true;
+    let (_o, _i, _m, _k, _er, coord) = make(c);
+    let (report_tx, mut report_rx) = mpsc::channel(4);
+    let mut coord = coord.with_toxic_cancel_tx(report_tx);
+
+    coord.yes_target = Some(DesiredTarget {
+        side: Side::Yes,
+        direction: TradeDirection::Buy,
+        price: 0.45,
+        size: 3.0,
+        reason: BidReason::Provide,
+    });
+    coord
+        .clear_slot_target(OrderSlot::YES_BUY, CancelReason::ToxicFlow)
+        .await;
+    let report = report_rx.try_recv().expect("toxic cancel report");
+    assert_eq!(report.side, Side::Yes);
+    assert_eq!(report.direction, TradeDirection::Buy);
+    assert!((report.price - 0.45).abs() < 1e-9);
+    assert!((report.size - 3.0).abs() < 1e-9);
+
+    coord.no_target = Some(DesiredTarget {
+        side: Side::No,
+        direction: TradeDirection::Buy,
+        price: 0.52,
+        size: 2.0,
+        reason: BidReason::Provide,
+    });
+    coord
+        .clear_slot_target(OrderSlot::NO_BUY, CancelReason::Reprice)
+        .await;
+    assert!(report_rx.try_recv().is_err());
+}
+
 #[tokio::test]
 async fn test_dry_run_clear_slot_target_still_notifies_oms() {
     let mut c = cfg();
@@ -5550,6 +5596,38 @@ async fn test_toxic_does_not_clear_existing_hedge_target() {
     let _ = h.await;
 }
 
+#[test]
+fn test_sim_clock_drives_endgame_and_post_close_boundaries() {
+    let end_ts = 1_700_000_300;
+    let mut c = cfg();
+    c.market_end_ts = Some(end_ts);
+    c.endgame_soft_close_secs = 35;
+    c.endgame_hard_close_secs = 12;
+    c.endgame_freeze_secs = 2;
+    c.oracle_lag_sniping.window_secs = 105;
+    let clock = SimClock::shared(end_ts * 1_000 - 36_000);
+    let (_o, _i, _m, _k, _e, coord) = make(c);
+    let coord = coord.with_clock(clock.clone());
+
+    let at = |unix_secs: u64| {
+        clock.advance_to_unix_ms(unix_secs * 1_000);
+        (
+            coord.seconds_to_market_end(),
+            coord.endgame_phase(),
+            coord.is_in_post_close_window(),
+        )
+    };
+    assert_eq!(at(end_ts - 36), (Some(36), EndgamePhase::Normal, false));
+    assert_eq!(at(end_ts - 35), (Some(35), EndgamePhase::SoftClose, false));
+    assert_eq!(at(end_ts - 12), (Some(12), EndgamePhase::HardClose, false));
+    assert_eq!(at(end_ts - 3), (Some(3), EndgamePhase::HardClose, false));
+    assert_eq!(at(end_ts - 2), (Some(2), EndgamePhase::Freeze, false));
+    assert_eq!(at(end_ts - 1), (Some(1), EndgamePhase::Freeze, false));
+    assert_eq!(at(end_ts), (Some(0), EndgamePhase::Freeze, true));
+    assert_eq!(at(end_ts + 104), (Some(0), EndgamePhase::Freeze, true));
+    assert_eq!(at(end_ts + 105), (Some(0), EndgamePhase::Freeze, false));
+}
+
 #[tokio::test]
 async fn test_endgame_freeze_allows_derisk_taker() {
     let mut c = cfg();
@@ -6754,6 +6832,7 @@ fn test_pair_arb_ofi_toxic_does_not_block_pairing_buy_in_execution_layer() {
             ofi: Some(&ofi),
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     );
 
@@ -7430,6 +7509,7 @@ fn test_pair_gated_tranche_flat_seed_shadow_latches_existing_single_side_bias()
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     );
 
@@ -7573,6 +7653,7 @@ fn test_pair_gated_tranche_flat_seed_shadow_bias_exhaustion_restores_dual_seed()
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     );
 
@@ -9306,6 +9387,7 @@ fn test_pair_gated_tranche_rescue_close_blocks_new_flat_seed() {
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         },
     );
 
@@ -10575,3 +10657,325 @@ fn test_github7_hedge_size_grows_on_additional_first_leg_partial_then_full_fill(
     // A full spawned cmd-size-increase test is fragile due to PGT many gates (visible BE,
     // age, repair budget, etc.); the quote growth + code guard is the robust regression.
 }
+
+#[tokio::test]
+async fn portfolio_risk_directive_throttles_restricts_and_cancels_buys() {
+    let mut c = cfg();
+    c.min_order_size = 1.0;
+    let (_o, _i, _m, _k, mut om_rx, coord) = make(c);
+    let (risk_tx, risk_rx) = watch::channel(PortfolioRiskDirective::default());
+    let mut coord = coord.with_portfolio_risk_rx(risk_rx);
+    let inv = InventoryState {
+        yes_qty: 10.0,
+        no_qty: 7.0,
+        net_diff: 3.0,
+        ..Default::default()
+    };
+    let both_buys = || {
+        let mut q = StrategyQuotes::default();
+        for side in [Side::Yes, Side::No] {
+            q.set(StrategyIntent {
+                side,
+                direction: TradeDirection::Buy,
+                price: 0.45,
+                size: 5.0,
+                reason: BidReason::Provide,
+            });
+        }
+        q
+    };
+
+    let mut q = both_buys();
+    coord.apply_portfolio_risk(&inv, &mut q).await;
+    assert_eq!(q.yes_buy.map(|i| i.size), Some(5.0));
+
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::Throttle,
+        size_mult: 0.5,
+        limit: Some(PortfolioRiskLimit::UnpairedNotional),
+    });
+    let mut q = both_buys();
+    coord.apply_portfolio_risk(&inv, &mut q).await;
+    assert_eq!(q.yes_buy.map(|i| i.size), Some(2.5));
+    assert_eq!(q.no_buy.map(|i| i.size), Some(2.5));
+
+    // Reduce-only keeps just the pairing side, capped at the residual gap.
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::ReduceOnly,
+        size_mult: 0.5,
+        limit: Some(PortfolioRiskLimit::Directional),
+    });
+    let mut q = both_buys();
+    coord.apply_portfolio_risk(&inv, &mut q).await;
+    assert!(q.yes_buy.is_none());
+    assert_eq!(q.no_buy.map(|i| i.size), Some(3.0));
+
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::CancelAll,
+        size_mult: 0.0,
+        limit: Some(PortfolioRiskLimit::CapitalAtRisk),
+    });
+    for _ in 0..2 {
+        let mut q = both_buys();
+        coord.apply_portfolio_risk(&inv, &mut q).await;
+        assert!(q.yes_buy.is_none() && q.no_buy.is_none());
+    }
+    assert!(matches!(om_rx.try_recv(), Ok(OrderManagerCmd::CancelAll)));
+    // CancelAll is sent once per episode, not every tick.
+    assert!(om_rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn portfolio_risk_directive_gates_taker_buys() {
+    let (_o, inv_tx, _m, _k, mut om_rx, coord) = make(cfg());
+    let (risk_tx, risk_rx) = watch::channel(PortfolioRiskDirective::default());
+    let mut coord = coord.with_portfolio_risk_rx(risk_rx);
+    inv_tx
+        .send(InventoryState {
+            yes_qty: 10.0,
+            no_qty: 7.0,
+            net_diff: 3.0,
+            ..Default::default()
+        })
+        .unwrap();
+    let taker_size = |om_rx: &mut mpsc::Receiver<OrderManagerCmd>| {
+        let mut size = None;
+        while let Ok(cmd) = om_rx.try_recv() {
+            if let OrderManagerCmd::OneShotTakerHedge { size: s, .. } = cmd {
+                size = Some(s);
+            }
+        }
+        size
+    };
+
+    for action in [
+        PortfolioRiskAction::CancelAll,
+        PortfolioRiskAction::ReduceOnly,
+    ] {
+        risk_tx.send_replace(PortfolioRiskDirective {
+            action,
+            size_mult: 0.5,
+            limit: Some(PortfolioRiskLimit::CapitalAtRisk),
+        });
+        coord
+            .dispatch_taker_intent(
+                Side::Yes,
+                TradeDirection::Buy,
+                5.0,
+                TradePurpose::OracleLagSnipe,
+                Some(0.9),
+                None,
+            )
+            .await;
+        assert_eq!(taker_size(&mut om_rx), None, "{action:?} blocks YES buy");
+        // The pairing side may still buy, capped at the residual gap.
+        coord
+            .dispatch_taker_intent(
+                Side::No,
+                TradeDirection::Buy,
+                5.0,
+                TradePurpose::OracleLagSnipe,
+                Some(0.9),
+                None,
+            )
+            .await;
+        assert_eq!(taker_size(&mut om_rx), Some(3.0), "{action:?} caps NO buy");
+    }
+
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::Throttle,
+        size_mult: 0.5,
+        limit: Some(PortfolioRiskLimit::UnpairedNotional),
+    });
+    coord
+        .dispatch_taker_intent(
+            Side::Yes,
+            TradeDirection::Buy,
+            5.0,
+            TradePurpose::OracleLagSnipe,
+            Some(0.9),
+            None,
+        )
+        .await;
+    assert_eq!(taker_size(&mut om_rx), Some(2.5));
+
+    // Sells are never gated.
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::CancelAll,
+        size_mult: 0.0,
+        limit: Some(PortfolioRiskLimit::CapitalAtRisk),
+    });
+    coord
+        .dispatch_taker_intent(
+            Side::Yes,
+            TradeDirection::Sell,
+            5.0,
+            TradePurpose::Exit,
+            None,
+            None,
+        )
+        .await;
+    assert_eq!(taker_size(&mut om_rx), Some(5.0));
+}
+
+#[tokio::test]
+async fn test_residual_unwind_sells_excess_leg_when_completion_uneconomic() {
+    let mut c = with_strategy(cfg(), StrategyKind::PairArb);
+    c.residual_unwind.enabled = true;
+    let (_o, _i, _m, _k, _er, mut coord) = make(c);
+    let settled = InventoryState {
+        yes_qty: 10.0,
+        no_qty: 0.0,
+        yes_avg_cost: 0.60,
+        no_avg_cost: 0.0,
+        net_diff: 10.0,
+        portfolio_cost: 0.0,
+    };
+    let ledger = build_pair_ledger(&[pgt_fill(Side::Yes, 10.0, 0.60)], PathKind::MakerShadow);
+
+    // 0.60 + 0.55 misses pair_target, but selling at 0.41 does not beat 0.55 completion.
+    let hold = book(0.40, 0.43, 0.44, 0.55);
+    assert!(coord
+        .plan_residual_unwind(&settled, &hold, &ledger.snapshot)
+        .is_none());
+
+    let mut quotes = StrategyQuotes::default();
+    quotes.no_buy = Some(StrategyIntent {
+        side: Side::No,
+        direction: TradeDirection::Buy,
+        price: 0.50,
+        size: 10.0,
+        reason: BidReason::Hedge,
+    });
+    coord
+        .apply_residual_unwind(
+            &settled,
+            &book(0.40, 0.43, 0.44, 0.62),
+            &ledger.snapshot,
+            false,
+            false,
+            &mut quotes,
+        )
+        .await;
+    assert!(
+        quotes.no_buy.is_none(),
+        "completion buy is dropped while unwinding"
+    );
+    let target = coord
+        .slot_target(OrderSlot::YES_SELL)
+        .cloned()
+        .expect("residual sell target");
+    assert_eq!(target.direction, TradeDirection::Sell);
+    assert!((target.price - 0.41).abs() < 1e-9);
+    assert!((target.size - 10.0).abs() < 1e-9);
+
+    let mut quotes = StrategyQuotes::default();
+    coord
+        .apply_residual_unwind(&settled, &hold, &ledger.snapshot, false, false, &mut quotes)
+        .await;
+    assert!(coord.slot_target(OrderSlot::YES_SELL).is_none());
+}
+
+fn residual_unwind_fixture() -> (InventoryState, PairLedgerSnapshot, Book) {
+    let settled = InventoryState {
+        yes_qty: 10.0,
+        no_qty: 0.0,
+        yes_avg_cost: 0.60,
+        no_avg_cost: 0.0,
+        net_diff: 10.0,
+        portfolio_cost: 0.0,
+    };
+    let ledger = build_pair_ledger(&[pgt_fill(Side::Yes, 10.0, 0.60)], PathKind::MakerShadow);
+    (settled, ledger.snapshot, book(0.40, 0.43, 0.44, 0.62))
+}
+
+#[tokio::test]
+async fn test_residual_unwind_skips_completion_first_shadow_mode() {
+    let (settled, ledger, ub) = residual_unwind_fixture();
+    let mut c = with_strategy(cfg(), StrategyKind::CompletionFirst);
+    c.residual_unwind.enabled = true;
+    c.completion_first.mode = CompletionFirstMode::Shadow;
+    let (_o, _i, _m, _k, _er, mut coord) = make(c);
+    assert!(!coord.residual_unwind_applies());
+    let mut quotes = StrategyQuotes::default();
+    coord
+        .apply_residual_unwind(&settled, &ub, &ledger, false, false, &mut quotes)
+        .await;
+    assert!(
+        coord.slot_target(OrderSlot::YES_SELL).is_none(),
+        "shadow mode must not place a live residual sell"
+    );
+
+    let mut c = with_strategy(cfg(), StrategyKind::CompletionFirst);
+    c.residual_unwind.enabled = true;
+    c.completion_first.mode = CompletionFirstMode::Enforce;
+    let (_o, _i, _m, _k, _er, coord) = make(c);
+    assert!(coord.plan_residual_unwind(&settled, &ub, &ledger).is_some());
+}
+
+#[tokio::test]
+async fn test_residual_unwind_skips_while_portfolio_risk_cancel_all() {
+    let (settled, ledger, ub) = residual_unwind_fixture();
+    let mut c = with_strategy(cfg(), StrategyKind::PairArb);
+    c.residual_unwind.enabled = true;
+    let (_o, _i, _m, _k, _er, coord) = make(c);
+    let (risk_tx, risk_rx) = watch::channel(PortfolioRiskDirective::default());
+    let mut coord = coord.with_portfolio_risk_rx(risk_rx);
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::CancelAll,
+        size_mult: 0.0,
+        limit: Some(PortfolioRiskLimit::CapitalAtRisk),
+    });
+    let mut quotes = StrategyQuotes::default();
+    coord
+        .apply_residual_unwind(&settled, &ub, &ledger, false, false, &mut quotes)
+        .await;
+    assert!(coord.slot_target(OrderSlot::YES_SELL).is_none());
+
+    risk_tx.send_replace(PortfolioRiskDirective::default());
+    coord
+        .apply_residual_unwind(&settled, &ub, &ledger, false, false, &mut quotes)
+        .await;
+    assert!(coord.slot_target(OrderSlot::YES_SELL).is_some());
+
+    // A later CancelAll also clears the live sell target the OMS just dropped.
+    risk_tx.send_replace(PortfolioRiskDirective {
+        action: PortfolioRiskAction::CancelAll,
+        size_mult: 0.0,
+        limit: Some(PortfolioRiskLimit::CapitalAtRisk),
+    });
+    coord
+        .apply_residual_unwind(&settled, &ub, &ledger, false, false, &mut quotes)
+        .await;
+    assert!(coord.slot_target(OrderSlot::YES_SELL).is_none());
+}
+
+#[tokio::test]
+async fn test_actor_state_store_restores_maker_friction_for_same_market() {
+    use crate::polymarket::actor_state_store::ActorStateStore;
+
+    let db = ActorStateStore::open_in_memory(60).unwrap();
+    let store = db.for_market("btc-updown-15m-1", Duration::from_millis(1));
+    let (_, _, _, _, _, mut c) = make(cfg());
+    for _ in 0..2 {
+        c.handle_execution_feedback(ExecutionFeedback::PostOnlyCrossed {
+            slot: OrderSlot::NO_BUY,
+            ts: Instant::now(),
+            rejected_action_price: 0.41,
+        });
+    }
+    c = c.with_state_store(store.clone()).await;
+    c.maybe_save_state();
+
+    let (_, _, _, _, _, restarted) = make(cfg());
+    let restarted = restarted.with_state_store(store).await;
+    assert_eq!(restarted.maker_friction(Side::No).extra_safety_ticks, 2);
+    assert_eq!(restarted.maker_friction(Side::Yes).extra_safety_ticks, 0);
+    assert!(restarted.recent_cross_reject(Side::No, Duration::from_secs(3)));
+
+    // A different round starts clean.
+    let other = db.for_market("btc-updown-15m-2", Duration::from_millis(1));
+    let (_, _, _, _, _, fresh) = make(cfg());
+    let fresh = fresh.with_state_store(other).await;
+    assert_eq!(fresh.maker_friction(Side::No).extra_safety_ticks, 0);
+}
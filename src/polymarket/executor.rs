@@ -16,9 +16,15 @@ use std::hash::{Hash, Hasher};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::Context;
-use tokio::sync::{broadcast, mpsc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, watch};
 use tracing::{info, warn};
 
+use super::actor_state_store::{remaining_ms, restore_deadline, ActorStateHandle};
+use super::clock::{system_clock, SharedClock};
+use super::config_validation::{clamp_reported, env_checked};
+use super::fees::LiquidityRole;
+use super::l2_book::L2BookSnapshot;
 use super::messages::*;
 use super::recorder::{MarketBookDepthEvidence, RecorderHandle, RecorderSessionMeta};
 use super::types::Side;
@@ -96,6 +102,51 @@ pub struct ExecutorConfig {
     pub pgt_shadow_same_side_provide_cooldown_ms: u64,
 }
 
+/// Env-driven executor knobs whose rejected or clamped values are reported to
+/// config validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutorTunables {
+    pub dry_run_fill_probability: f64,
+    pub balance_cache_ttl_ms: u64,
+    pub marketable_buy_min_notional_floor: f64,
+    pub marketable_buy_cooldown_ms: u64,
+    pub provide_size_fallback_bid_size: f64,
+}
+
+impl ExecutorTunables {
+    pub fn from_env() -> Self {
+        Self {
+            dry_run_fill_probability: std::env::var("PM_DRY_RUN_FILL_PROBABILITY")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|p| clamp_reported("PM_DRY_RUN_FILL_PROBABILITY", p, 0.0, 1.0))
+                .unwrap_or(1.0),
+            balance_cache_ttl_ms: env_checked::<u64>(
+                "PM_BALANCE_CACHE_TTL_MS",
+                "must be > 0",
+                |v| *v > 0,
+            )
+            .unwrap_or(2000),
+            marketable_buy_min_notional_floor: env_checked::<f64>(
+                "PM_MIN_MARKETABLE_NOTIONAL_FLOOR",
+                "must be >= 0",
+                |v| *v >= 0.0,
+            )
+            .unwrap_or(0.0),
+            marketable_buy_cooldown_ms: env_checked::<u64>(
+                "PM_MIN_MARKETABLE_COOLDOWN_MS",
+                "must be > 0",
+                |v| *v > 0,
+            )
+            .unwrap_or(10_000),
+            provide_size_fallback_bid_size: env_checked::<f64>("PM_BID_SIZE", "must be > 0", |v| {
+                *v > 0.0
+            })
+            .unwrap_or(5.0),
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────
 // Actor
 // ─────────────────────────────────────────────────────────
@@ -180,13 +231,31 @@ pub struct Executor {
     dry_run_market_touch_trade_partial_fills: bool,
     dry_run_market_touch_trade_fill_fraction: f64,
     dry_run_market_touch_min_fill_size: f64,
+    /// Queue-position mode: replaces the touch heuristics above and fills a
+    /// resting BUY only after the visible size ahead of it has traded.
+    dry_run_market_touch_queue_fills: bool,
+    /// Live L2 ladder used to seed and refresh queue positions.
+    l2_book_rx: Option<watch::Receiver<L2BookSnapshot>>,
     dry_run_touch_diag: DryRunTouchDiag,
     /// Short confirm delay for dry-run market-touch fills.
     dry_run_touch_confirm_delay: Duration,
     recorder: Option<RecorderHandle>,
     recorder_meta: Option<RecorderSessionMeta>,
+    state_store: Option<ActorStateHandle>,
+    clock: SharedClock,
 }
 
+/// Restart-surviving executor state: locally enforced cooldowns and floors
+/// learned from exchange rejections.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorStateSnapshot {
+    /// Remaining PGT shadow same-side Provide suppression per side.
+    pub pgt_recent_provide_fill_ms: [Option<u64>; 2],
+    pub marketable_buy_min_notional_floor: f64,
+}
+
+const EXECUTOR_STATE_ACTOR: &str = "executor";
+
 #[derive(Debug, Clone)]
 struct DryRunLiveOrder {
     slot: OrderSlot,
@@ -196,6 +265,37 @@ struct DryRunLiveOrder {
     price: f64,
     size: f64,
     fill_emitted: bool,
+    queue: DryRunQueuePosition,
+}
+
+/// Visible size resting ahead of a dry-run order at its own price. `ahead` is
+/// `None` until a book observation seeds it; unknown queues never fill.
+#[derive(Debug, Clone, Copy, Default)]
+struct DryRunQueuePosition {
+    ahead: Option<f64>,
+}
+
+impl DryRunQueuePosition {
+    /// The queue ahead only shrinks: growth is orders joining behind us, and a
+    /// visible size below `ahead` means cancels ahead of us.
+    fn observe_level_size(&mut self, visible: f64) {
+        let visible = visible.max(0.0);
+        self.ahead = Some(self.ahead.map_or(visible, |ahead| ahead.min(visible)));
+    }
+
+    /// Opposing print at our price; returns the part that reached our order.
+    fn consume_trade(&mut self, size: f64) -> f64 {
+        let Some(ahead) = self.ahead else {
+            return 0.0;
+        };
+        self.ahead = Some((ahead - size).max(0.0));
+        (size - ahead).max(0.0)
+    }
+
+    /// Our level traded through (or an ask reached it): nothing is left ahead.
+    fn clear(&mut self) {
+        self.ahead = Some(0.0);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -288,7 +388,9 @@ impl Executor {
     ) {
         let idx = slot.index();
         let can_emit = self.slot_last_blocked_feedback[idx]
-            .map(|ts| ts.elapsed() >= Duration::from_millis(SLOT_BLOCKED_FEEDBACK_INTERVAL_MS))
+            .map(|ts| {
+                self.clock.elapsed(ts) >= Duration::from_millis(SLOT_BLOCKED_FEEDBACK_INTERVAL_MS)
+            })
             .unwrap_or(true);
         if !can_emit {
             return;
@@ -318,11 +420,7 @@ impl Executor {
         recorder: Option<RecorderHandle>,
         recorder_meta: Option<RecorderSessionMeta>,
     ) -> Self {
-        let dry_run_fill_probability = std::env::var("PM_DRY_RUN_FILL_PROBABILITY")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok())
-            .map(|p| p.clamp(0.0, 1.0))
-            .unwrap_or(1.0);
+        let tunables = ExecutorTunables::from_env();
         Self {
             cfg,
             client,
@@ -337,13 +435,7 @@ impl Executor {
             xuan_b27_dplus_source_truth_tx: None,
             balance_cache_usdc: None,
             balance_cache_ts: Instant::now() - Duration::from_secs(60),
-            balance_cache_ttl: Duration::from_millis(
-                std::env::var("PM_BALANCE_CACHE_TTL_MS")
-                    .ok()
-                    .and_then(|v| v.parse::<u64>().ok())
-                    .filter(|v| *v > 0)
-                    .unwrap_or(2000),
-            ),
+            balance_cache_ttl: Duration::from_millis(tunables.balance_cache_ttl_ms),
             open_orders: std::array::from_fn(|_| HashMap::new()),
             dry_run_live_orders: HashMap::new(),
             dry_run_pending_touch_fills: HashMap::new(),
@@ -356,7 +448,7 @@ impl Executor {
             slot_last_forced_cancel_attempt: std::array::from_fn(|_| {
                 Instant::now() - Duration::from_secs(60)
             }),
-            dry_run_fill_probability,
+            dry_run_fill_probability: tunables.dry_run_fill_probability,
             dry_run_market_touch_fills,
             dry_run_market_touch_book_fills: Self::env_bool_or(
                 "PM_DRY_RUN_MARKET_TOUCH_BOOK_FILLS",
@@ -406,30 +498,80 @@ impl Executor {
             .and_then(|v| v.parse::<f64>().ok())
             .filter(|v| v.is_finite() && *v >= 0.0)
             .unwrap_or(0.0),
+            dry_run_market_touch_queue_fills: Self::env_bool_or(
+                "PM_DRY_RUN_MARKET_TOUCH_QUEUE_FILLS",
+                false,
+            ),
+            l2_book_rx: None,
             dry_run_touch_diag: DryRunTouchDiag::default(),
             dry_run_touch_confirm_delay: Duration::from_millis(DRY_RUN_TOUCH_CONFIRM_MS),
             reconcile_fetch_mode: ReconcileFetchMode::LocalById,
-            marketable_buy_min_notional_floor: std::env::var("PM_MIN_MARKETABLE_NOTIONAL_FLOOR")
-                .ok()
-                .and_then(|v| v.parse::<f64>().ok())
-                .filter(|v| *v >= 0.0)
-                .unwrap_or(0.0),
-            marketable_buy_cooldown_ms: std::env::var("PM_MIN_MARKETABLE_COOLDOWN_MS")
-                .ok()
-                .and_then(|v| v.parse::<u64>().ok())
-                .filter(|v| *v > 0)
-                .unwrap_or(10_000),
+            marketable_buy_min_notional_floor: tunables.marketable_buy_min_notional_floor,
+            marketable_buy_cooldown_ms: tunables.marketable_buy_cooldown_ms,
             marketable_buy_autodetect: std::env::var("PM_MIN_MARKETABLE_AUTO_DETECT")
                 .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
                 .unwrap_or(true),
-            provide_size_fallback_bid_size: std::env::var("PM_BID_SIZE")
-                .ok()
-                .and_then(|v| v.parse::<f64>().ok())
-                .filter(|v| *v > 0.0)
-                .unwrap_or(5.0),
+            provide_size_fallback_bid_size: tunables.provide_size_fallback_bid_size,
             last_guard_reconcile_ts: Instant::now() - Duration::from_secs(60),
             recorder,
             recorder_meta,
+            state_store: None,
+            clock: system_clock(),
+        }
+    }
+
+    /// Inject the time source used for dry-run fills, guards and throttles.
+    /// Construction-time timestamps are re-anchored to the new clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        let stale = clock.now() - Duration::from_secs(60);
+        self.balance_cache_ts = stale;
+        self.slot_last_forced_cancel_attempt = [stale; 4];
+        self.last_guard_reconcile_ts = stale;
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_l2_book_rx(mut self, rx: watch::Receiver<L2BookSnapshot>) -> Self {
+        self.l2_book_rx = Some(rx);
+        self
+    }
+
+    /// Persist cooldowns and learned floors through `store`, reloading the
+    /// previous snapshot of the same market first.
+    pub async fn with_state_store(mut self, store: ActorStateHandle) -> Self {
+        if let Some(restored) = store
+            .load::<ExecutorStateSnapshot>(EXECUTOR_STATE_ACTOR)
+            .await
+        {
+            let now = self.clock.now();
+            for (until, remaining) in self
+                .pgt_recent_provide_fill_until
+                .iter_mut()
+                .zip(restored.state.pgt_recent_provide_fill_ms)
+            {
+                *until = restore_deadline(remaining, restored.age, now);
+            }
+            self.marketable_buy_min_notional_floor = self
+                .marketable_buy_min_notional_floor
+                .max(restored.state.marketable_buy_min_notional_floor);
+            info!(
+                "♻️ Executor state restored | slug={} age={}ms min_notional_floor={:.2}",
+                store.slug(),
+                restored.age.as_millis(),
+                self.marketable_buy_min_notional_floor
+            );
+        }
+        self.state_store = Some(store);
+        self
+    }
+
+    pub fn export_state(&self) -> ExecutorStateSnapshot {
+        let now = self.clock.now();
+        ExecutorStateSnapshot {
+            pgt_recent_provide_fill_ms: std::array::from_fn(|idx| {
+                remaining_ms(self.pgt_recent_provide_fill_until[idx], now)
+            }),
+            marketable_buy_min_notional_floor: self.marketable_buy_min_notional_floor,
         }
     }
 
@@ -446,7 +588,7 @@ impl Executor {
             let _ = tx.try_send(XuanB27DplusSourceTruthEvent::OrderAccepted {
                 slot,
                 venue_order_id_present: !order_id.trim().is_empty(),
-                ts: Instant::now(),
+                ts: self.clock.now(),
             });
         }
     }
@@ -455,7 +597,7 @@ impl Executor {
         if let Some(tx) = &self.xuan_b27_dplus_source_truth_tx {
             let _ = tx.try_send(XuanB27DplusSourceTruthEvent::WalletSnapshot {
                 valid,
-                ts: Instant::now(),
+                ts: self.clock.now(),
             });
         }
     }
@@ -468,14 +610,6 @@ impl Executor {
         format!("dry-{}-{}", slot.as_str(), now_ns)
     }
 
-    fn unix_now_ms() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis()
-            .min(u128::from(u64::MAX)) as u64
-    }
-
     fn depth_matches_side(depth: &MarketBookDepthEvidence, side: Side) -> bool {
         let expected = match side {
             Side::Yes => "YES",
@@ -513,10 +647,7 @@ impl Executor {
         let Some(market_end_ts) = self.cfg.market_end_ts else {
             return false;
         };
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now = self.clock.unix_now_secs();
         now >= market_end_ts
     }
 
@@ -540,7 +671,9 @@ impl Executor {
             price,
             status: FillStatus::Confirmed,
             source,
-            ts: Instant::now(),
+            liquidity: LiquidityRole::from_source(source),
+            fee_rate_bps: None,
+            ts: self.clock.now(),
         })
         .await
         .is_ok()
@@ -556,6 +689,12 @@ impl Executor {
         price: f64,
         size: f64,
     ) {
+        // Our simulated order is not in the venue book, so everything visible
+        // at our price is ahead of it.
+        let mut queue = DryRunQueuePosition::default();
+        if let Some(visible) = self.l2_visible_level_size(side, direction, price) {
+            queue.observe_level_size(visible);
+        }
         self.dry_run_live_orders.insert(
             order_id,
             DryRunLiveOrder {
@@ -566,10 +705,25 @@ impl Executor {
                 price,
                 size,
                 fill_emitted: false,
+                queue,
             },
         );
     }
 
+    /// Visible size at `price` on the order's own side of the live ladder
+    /// (bids for BUY, asks for SELL); `None` when no L2 feed is wired or that
+    /// side of the ladder is still empty.
+    fn l2_visible_level_size(
+        &self,
+        side: Side,
+        direction: TradeDirection,
+        price: f64,
+    ) -> Option<f64> {
+        let book = self.l2_book_rx.as_ref()?.borrow();
+        book.side(side)
+            .visible_size_at(direction == TradeDirection::Buy, price)
+    }
+
     fn clear_dry_run_live_order(&mut self, order_id: &str) {
         self.dry_run_live_orders.remove(order_id);
         self.dry_run_pending_touch_fills.remove(order_id);
@@ -586,7 +740,12 @@ impl Executor {
             self.dry_run_pending_touch_fills.clear();
             return;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
+        if self.dry_run_market_touch_queue_fills {
+            self.apply_dry_run_queue_market_data(&msg, now);
+            self.flush_dry_run_pending_touch_fills().await;
+            return;
+        }
         match msg {
             MarketDataMsg::BookTick {
                 yes_ask,
@@ -911,12 +1070,165 @@ impl Executor {
         self.flush_dry_run_pending_touch_fills().await;
     }
 
+    /// Queue-position fill model: refresh each resting order's queue from the
+    /// L2 ladder / best-level depth on book updates, consume it with opposing
+    /// prints at our price, and queue a `queue_touch` fill for whatever
+    /// reaches us. Trade prints never re-read the ladder: the snapshot may
+    /// already reflect the print, which would subtract its size twice.
+    fn apply_dry_run_queue_market_data(&mut self, msg: &MarketDataMsg, now: Instant) {
+        let book_update = matches!(
+            msg,
+            MarketDataMsg::BookTick { .. } | MarketDataMsg::BookDepthTick { .. }
+        );
+        let l2_book = if book_update {
+            self.l2_book_rx.as_ref().map(|rx| rx.borrow().clone())
+        } else {
+            None
+        };
+        let mut reached_orders = Vec::new();
+        for (order_id, meta) in self.dry_run_live_orders.iter_mut() {
+            if meta.fill_emitted {
+                continue;
+            }
+            let is_buy = meta.direction == TradeDirection::Buy;
+            if let Some(visible) = l2_book
+                .as_ref()
+                .and_then(|book| book.side(meta.side).visible_size_at(is_buy, meta.price))
+            {
+                meta.queue.observe_level_size(visible);
+            }
+            let reached = match *msg {
+                MarketDataMsg::BookTick {
+                    yes_bid,
+                    yes_ask,
+                    no_bid,
+                    no_ask,
+                    ..
+                } => {
+                    let (bid, ask) = match meta.side {
+                        Side::Yes => (yes_bid, yes_ask),
+                        Side::No => (no_bid, no_ask),
+                    };
+                    // The opposite best reached our price: the level is crossed.
+                    let crossed = if is_buy {
+                        ask.is_finite() && ask > 0.0 && meta.price + 1e-9 >= ask
+                    } else {
+                        bid.is_finite() && bid > 0.0 && bid + 1e-9 >= meta.price
+                    };
+                    if crossed {
+                        meta.queue.clear();
+                        f64::INFINITY
+                    } else {
+                        0.0
+                    }
+                }
+                MarketDataMsg::BookDepthTick {
+                    market_side,
+                    best_bid,
+                    best_ask,
+                    best_bid_size,
+                    best_ask_size,
+                    ..
+                } if market_side == meta.side => {
+                    let (best, best_size) = if is_buy {
+                        (Some(best_bid), best_bid_size)
+                    } else {
+                        (best_ask, best_ask_size)
+                    };
+                    if let Some(best) = best.filter(|best| best.is_finite() && *best > 0.0) {
+                        // Best level already behind our price: nothing rests ahead.
+                        let behind = if is_buy {
+                            best + 1e-9 < meta.price
+                        } else {
+                            best > meta.price + 1e-9
+                        };
+                        if behind {
+                            meta.queue.observe_level_size(0.0);
+                        } else if (best - meta.price).abs() <= 1e-9 {
+                            if let Some(size) = best_size {
+                                meta.queue.observe_level_size(size);
+                            }
+                        }
+                    }
+                    0.0
+                }
+                MarketDataMsg::TradeTick {
+                    market_side,
+                    taker_side,
+                    price,
+                    size,
+                    ..
+                } if market_side == meta.side
+                    && (taker_side == TakerSide::Sell) == is_buy
+                    && price.is_finite()
+                    && price > 0.0
+                    && size.is_finite()
+                    && size > 0.0 =>
+                {
+                    let traded_through = if is_buy {
+                        price + 1e-9 < meta.price
+                    } else {
+                        price > meta.price + 1e-9
+                    };
+                    if traded_through {
+                        // Printed beyond us: our whole level traded through.
+                        meta.queue.clear();
+                        f64::INFINITY
+                    } else if (price - meta.price).abs() <= 1e-9 {
+                        meta.queue.consume_trade(size)
+                    } else {
+                        0.0
+                    }
+                }
+                _ => 0.0,
+            };
+            if reached > 0.0 {
+                reached_orders.push((order_id.clone(), reached));
+            }
+        }
+
+        let detected_at = now
+            .checked_sub(self.dry_run_touch_confirm_delay)
+            .unwrap_or(now);
+        for (order_id, reached) in reached_orders {
+            let Some(meta) = self.dry_run_live_orders.get(&order_id) else {
+                continue;
+            };
+            let remaining = self
+                .slot_orders(meta.slot)
+                .get(&order_id)
+                .copied()
+                .unwrap_or(meta.size)
+                .max(0.0);
+            if remaining <= 0.0 {
+                continue;
+            }
+            let (side, direction, price) = (meta.side, meta.direction, meta.price);
+            self.dry_run_pending_touch_fills
+                .entry(order_id)
+                .and_modify(|pending| {
+                    pending.size = remaining.min(pending.size + reached);
+                    pending.detected_at = detected_at;
+                })
+                .or_insert(DryRunPendingTouchFill {
+                    side,
+                    direction,
+                    size: remaining.min(reached),
+                    price,
+                    source: "queue_touch",
+                    depth: None,
+                    detected_at,
+                    evidence: None,
+                });
+        }
+    }
+
     async fn flush_dry_run_pending_touch_fills(&mut self) {
         if self.dry_run_market_touch_cutoff_reached() {
             self.dry_run_pending_touch_fills.clear();
             return;
         }
-        let now = Instant::now();
+        let now = self.clock.now();
         let ready: Vec<(
             String,
             Side,
@@ -1003,6 +1315,7 @@ impl Executor {
                         "book_touch" => FillSource::DryRunBookTouch,
                         "book_depth_touch" => FillSource::DryRunBookDepthTouch,
                         "trade_sell_touch" => FillSource::DryRunTradeSellTouch,
+                        "queue_touch" => FillSource::DryRunQueue,
                         _ => FillSource::Unknown,
                     },
                 )
@@ -1029,7 +1342,7 @@ impl Executor {
                     },
                 );
             } else {
-                let emit_unix_ms = Self::unix_now_ms();
+                let emit_unix_ms = self.clock.unix_now_ms();
                 let depth_ref = depth.as_ref();
                 let evidence_ref = evidence.as_ref();
                 let depth_event_time_ms = depth_ref
@@ -1099,7 +1412,7 @@ impl Executor {
 
     pub async fn run(mut self) {
         info!(
-            "⚡ Executor started | dry_run={} has_client={} dry_run_fill_probability={:.2} dry_run_touch(book/partial_book/book_fraction/depth/depth_fraction/trade/partial_trade/trade_fraction/min_fill)={}/{}/{:.3}/{}/{:.3}/{}/{}/{:.3}/{:.2}",
+            "⚡ Executor started | dry_run={} has_client={} dry_run_fill_probability={:.2} dry_run_touch(book/partial_book/book_fraction/depth/depth_fraction/trade/partial_trade/trade_fraction/min_fill/queue)={}/{}/{:.3}/{}/{:.3}/{}/{}/{:.3}/{:.2}/{}",
             self.cfg.dry_run,
             self.client.is_some(),
             self.dry_run_fill_probability,
@@ -1112,6 +1425,7 @@ impl Executor {
             self.dry_run_market_touch_trade_partial_fills,
             self.dry_run_market_touch_trade_fill_fraction,
             self.dry_run_market_touch_min_fill_size,
+            self.dry_run_market_touch_queue_fills,
         );
         info!(
             "🧭 Reconcile mode: {} (startup CancelAll authoritative)",
@@ -1122,6 +1436,12 @@ impl Executor {
         let mut reconcile_tick =
             tokio::time::interval(Duration::from_secs(self.cfg.reconcile_interval_secs.max(1)));
         let mut dry_run_touch_tick = tokio::time::interval(Duration::from_millis(25));
+        let mut state_save_tick = tokio::time::interval(
+            self.state_store
+                .as_ref()
+                .map(ActorStateHandle::interval)
+                .unwrap_or(Duration::from_secs(1)),
+        );
 
         loop {
             tokio::select! {
@@ -1243,6 +1563,11 @@ impl Executor {
                     let _ = self.cached_free_balance_usdc().await;
                     self.reconcile_open_orders().await;
                 }
+                _ = state_save_tick.tick(), if self.state_store.is_some() => {
+                    if let Some(store) = self.state_store.as_ref() {
+                        store.save(EXECUTOR_STATE_ACTOR, &self.export_state());
+                    }
+                }
             }
         }
 
@@ -1636,7 +1961,7 @@ impl Executor {
     async fn handle_fill_notification(&mut self, fill: &FillEvent) {
         let slot = fill.slot();
         if self.cfg.dry_run {
-            self.prune_dry_run_recently_canceled(Instant::now());
+            self.prune_dry_run_recently_canceled(self.clock.now());
             if self
                 .dry_run_recently_canceled
                 .remove(&fill.order_id)
@@ -1651,10 +1976,10 @@ impl Executor {
             }
         }
         if fill.status != FillStatus::Failed && slot.direction == TradeDirection::Buy {
-            self.last_buy_fill_ts[slot.side.index()] = Some(Instant::now());
+            self.last_buy_fill_ts[slot.side.index()] = Some(self.clock.now());
             if self.cfg.dry_run && self.cfg.pgt_shadow_same_side_provide_cooldown_ms > 0 {
                 self.pgt_recent_provide_fill_until[slot.side.index()] = Some(
-                    Instant::now()
+                    self.clock.now()
                         + Duration::from_millis(self.cfg.pgt_shadow_same_side_provide_cooldown_ms),
                 );
             }
@@ -1825,7 +2150,7 @@ impl Executor {
             && direction == TradeDirection::Buy
             && reason == BidReason::Provide
             && self.pgt_recent_provide_fill_until[side.index()]
-                .is_some_and(|until| until > Instant::now())
+                .is_some_and(|until| until > self.clock.now())
         {
             info!(
                 "🧪 PGT shadow suppressed same-side provide {:?} {:?}@{:.3} size={:.1}",
@@ -1851,7 +2176,7 @@ impl Executor {
         // Slot-keyed maker model: only this exact slot is mutually exclusive.
         let existing_count = self.slot_orders(slot).len();
         if existing_count > 0 {
-            let now = Instant::now();
+            let now = self.clock.now();
             let blocked_for = self.slot_blocked_duration(slot, now);
             self.emit_slot_blocked_feedback(slot, existing_count, blocked_for, now)
                 .await;
@@ -1861,18 +2186,20 @@ impl Executor {
                 price,
                 existing_count,
             );
-            if self.last_guard_reconcile_ts.elapsed() >= Duration::from_millis(1500)
+            if self.clock.elapsed(self.last_guard_reconcile_ts) >= Duration::from_millis(1500)
                 && !self.cfg.dry_run
                 && self.client.is_some()
             {
-                self.last_guard_reconcile_ts = Instant::now();
+                self.last_guard_reconcile_ts = self.clock.now();
                 self.reconcile_open_orders().await;
             }
             let idx = slot.index();
             if !self.cfg.dry_run
                 && self.client.is_some()
                 && blocked_for >= Duration::from_millis(SLOT_LOCK_RECOVERY_MIN_BLOCK_MS)
-                && self.slot_last_forced_cancel_attempt[idx].elapsed()
+                && self
+                    .clock
+                    .elapsed(self.slot_last_forced_cancel_attempt[idx])
                     >= Duration::from_millis(SLOT_LOCK_RECOVERY_RETRY_MS)
             {
                 self.slot_last_forced_cancel_attempt[idx] = now;
@@ -1904,14 +2231,14 @@ impl Executor {
                     kind: RejectKind::Validation,
                     price,
                     size,
-                    ts: Instant::now(),
+                    ts: self.clock.now(),
                 })
                 .await;
             self.emit_execution_feedback(ExecutionFeedback::PlacementRejected {
                 side,
                 reason,
                 kind: RejectKind::Validation,
-                ts: Instant::now(),
+                ts: self.clock.now(),
             })
             .await;
             let _ = self
@@ -1969,14 +2296,14 @@ impl Executor {
                             kind: RejectKind::BalanceOrAllowance,
                             price,
                             size,
-                            ts: Instant::now(),
+                            ts: self.clock.now(),
                         })
                         .await;
                     self.emit_execution_feedback(ExecutionFeedback::PlacementRejected {
                         side,
                         reason,
                         kind: RejectKind::BalanceOrAllowance,
-                        ts: Instant::now(),
+                        ts: self.clock.now(),
                     })
                     .await;
                     let _ = self
@@ -2016,7 +2343,7 @@ impl Executor {
                 direction, side, price, size, order_id
             );
             if direction == TradeDirection::Buy {
-                self.last_buy_place_ts[side.index()] = Some(Instant::now());
+                self.last_buy_place_ts[side.index()] = Some(self.clock.now());
             }
             self.slot_orders_mut(slot).insert(order_id.clone(), size);
             if self.dry_run_market_touch_fills {
@@ -2032,7 +2359,7 @@ impl Executor {
             }
             self.emit_execution_feedback(ExecutionFeedback::OrderAccepted {
                 slot,
-                ts: Instant::now(),
+                ts: self.clock.now(),
             })
             .await;
             self.emit_xuan_b27_dplus_order_truth(slot, &order_id);
@@ -2098,12 +2425,12 @@ impl Executor {
                     direction, side, price, order_id
                 );
                 if direction == TradeDirection::Buy {
-                    self.last_buy_place_ts[side.index()] = Some(Instant::now());
+                    self.last_buy_place_ts[side.index()] = Some(self.clock.now());
                 }
                 self.slot_orders_mut(slot).insert(order_id.clone(), size);
                 self.emit_execution_feedback(ExecutionFeedback::OrderAccepted {
                     slot,
-                    ts: Instant::now(),
+                    ts: self.clock.now(),
                 })
                 .await;
                 self.emit_xuan_b27_dplus_order_truth(slot, &order_id);
@@ -2163,12 +2490,12 @@ impl Executor {
                                 direction, side, price, order_id
                             );
                             if direction == TradeDirection::Buy {
-                                self.last_buy_place_ts[side.index()] = Some(Instant::now());
+                                self.last_buy_place_ts[side.index()] = Some(self.clock.now());
                             }
                             self.slot_orders_mut(slot).insert(order_id.clone(), size);
                             self.emit_execution_feedback(ExecutionFeedback::OrderAccepted {
                                 slot,
-                                ts: Instant::now(),
+                                ts: self.clock.now(),
                             })
                             .await;
                             self.emit_xuan_b27_dplus_order_truth(slot, &order_id);
@@ -2234,13 +2561,13 @@ impl Executor {
                                 direction, side, price, fallback_bid_size, order_id
                             );
                             if direction == TradeDirection::Buy {
-                                self.last_buy_place_ts[side.index()] = Some(Instant::now());
+                                self.last_buy_place_ts[side.index()] = Some(self.clock.now());
                             }
                             self.slot_orders_mut(slot)
                                 .insert(order_id.clone(), fallback_bid_size);
                             self.emit_execution_feedback(ExecutionFeedback::OrderAccepted {
                                 slot,
-                                ts: Instant::now(),
+                                ts: self.clock.now(),
                             })
                             .await;
                             self.emit_xuan_b27_dplus_order_truth(slot, &order_id);
@@ -2379,21 +2706,21 @@ impl Executor {
                             kind: reject_kind,
                             price,
                             size,
-                            ts: Instant::now(),
+                            ts: self.clock.now(),
                         })
                         .await;
                     self.emit_execution_feedback(ExecutionFeedback::PlacementRejected {
                         side,
                         reason,
                         kind: reject_kind,
-                        ts: Instant::now(),
+                        ts: self.clock.now(),
                     })
                     .await;
                 }
                 if is_cross_book {
                     self.emit_execution_feedback(ExecutionFeedback::PostOnlyCrossed {
                         slot,
-                        ts: Instant::now(),
+                        ts: self.clock.now(),
                         rejected_action_price: price,
                     })
                     .await;
@@ -2554,11 +2881,11 @@ impl Executor {
                 "🚫 Refusing Taker {:?} {:?}: {} tracked order(s) still open on side",
                 direction, side, existing_count
             );
-            if self.last_guard_reconcile_ts.elapsed() >= Duration::from_millis(1500)
+            if self.clock.elapsed(self.last_guard_reconcile_ts) >= Duration::from_millis(1500)
                 && !self.cfg.dry_run
                 && self.client.is_some()
             {
-                self.last_guard_reconcile_ts = Instant::now();
+                self.last_guard_reconcile_ts = self.clock.now();
                 self.reconcile_open_orders().await;
             }
             let _ = self
@@ -2640,14 +2967,14 @@ impl Executor {
                         kind: reject_kind,
                         price: 0.0,
                         size,
-                        ts: Instant::now(),
+                        ts: self.clock.now(),
                     })
                     .await;
                 self.emit_execution_feedback(ExecutionFeedback::PlacementRejected {
                     side,
                     reason: purpose.as_bid_reason(),
                     kind: reject_kind,
-                    ts: Instant::now(),
+                    ts: self.clock.now(),
                 })
                 .await;
                 let _ = self
@@ -2694,7 +3021,7 @@ impl Executor {
         if self.cfg.dry_run || self.client.is_none() {
             // DRY-RUN: remove from tracking immediately
             self.dry_run_recently_canceled
-                .insert(order_id.to_string(), Instant::now());
+                .insert(order_id.to_string(), self.clock.now());
             for orders in self.open_orders.iter_mut() {
                 orders.remove(order_id);
             }
@@ -2903,13 +3230,13 @@ impl Executor {
 
     fn is_recent_same_side_buy_fill(&self, side: Side) -> bool {
         self.last_buy_fill_ts[side.index()]
-            .map(|ts| ts.elapsed() <= Duration::from_secs(5))
+            .map(|ts| self.clock.elapsed(ts) <= Duration::from_secs(5))
             .unwrap_or(false)
     }
 
     fn is_recent_same_side_buy_place(&self, side: Side) -> bool {
         self.last_buy_place_ts[side.index()]
-            .map(|ts| ts.elapsed() <= Duration::from_secs(5))
+            .map(|ts| self.clock.elapsed(ts) <= Duration::from_secs(5))
             .unwrap_or(false)
     }
 
@@ -2922,7 +3249,7 @@ impl Executor {
             return None;
         }
         if self.balance_cache_usdc.is_some()
-            && self.balance_cache_ts.elapsed() < self.balance_cache_ttl
+            && self.clock.elapsed(self.balance_cache_ts) < self.balance_cache_ttl
         {
             return self.balance_cache_usdc;
         }
@@ -2941,14 +3268,14 @@ impl Executor {
                 let raw = resp.balance.to_f64().unwrap_or(0.0);
                 let free = (raw / 1_000_000.0).max(0.0);
                 self.balance_cache_usdc = Some(free);
-                self.balance_cache_ts = Instant::now();
+                self.balance_cache_ts = self.clock.now();
                 self.emit_xuan_b27_dplus_wallet_truth(free.is_finite() && free >= 0.0);
                 Some(free)
             }
             Err(e) => {
                 warn!("⚠️ balance precheck fetch failed: {:?}", e);
                 self.balance_cache_usdc = None;
-                self.balance_cache_ts = Instant::now();
+                self.balance_cache_ts = self.clock.now();
                 self.emit_xuan_b27_dplus_wallet_truth(false);
                 None
             }
@@ -3458,9 +3785,11 @@ fn order_attempt_trace_payload(trace: Option<&OrderAttemptTrace>) -> serde_json:
 mod tests {
     use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-    use tokio::sync::{broadcast, mpsc};
+    use tokio::sync::{broadcast, mpsc, watch};
 
     use super::{ExecutionCmd, Executor, ExecutorConfig, OrderResult, ReconcileFetchMode};
+    use crate::polymarket::fees::LiquidityRole;
+    use crate::polymarket::l2_book::{L2BookSnapshot, L2SideBook, PriceLevel};
     use crate::polymarket::messages::{
         BidReason, CancelReason, FillEvent, FillSource, FillStatus, MarketDataMsg, OrderSlot,
         TakerSide, TradeDirection, TradePurpose,
@@ -3623,6 +3952,8 @@ mod tests {
             price: 0.46,
             status: FillStatus::Matched,
             source: FillSource::UserWs,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         })
         .await;
@@ -4633,6 +4964,254 @@ mod tests {
         assert!(matches!(done, OrderResult::OrderFilled { slot } if slot == OrderSlot::YES_BUY));
     }
 
+    fn queue_print(taker_side: TakerSide, price: f64, size: f64) -> MarketDataMsg {
+        MarketDataMsg::TradeTick {
+            asset_id: "1".to_string(),
+            trade_id: None,
+            source_sequence_id: None,
+            event_time_ms: None,
+            market_side: Side::Yes,
+            taker_side,
+            price,
+            size,
+            ts: Instant::now(),
+        }
+    }
+
+    fn queue_sell_print(price: f64, size: f64) -> MarketDataMsg {
+        queue_print(TakerSide::Sell, price, size)
+    }
+
+    fn queue_buy_print(price: f64, size: f64) -> MarketDataMsg {
+        queue_print(TakerSide::Buy, price, size)
+    }
+
+    /// Uncrossed YES book update around a 0.50 bid / 0.52 ask.
+    fn queue_book_tick() -> MarketDataMsg {
+        MarketDataMsg::BookTick {
+            yes_bid: 0.50,
+            yes_ask: 0.52,
+            no_bid: 0.48,
+            no_ask: 0.50,
+            depth: None,
+            ts: Instant::now(),
+        }
+    }
+
+    fn yes_ladder(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> L2BookSnapshot {
+        let levels = |levels: &[(f64, f64)]| {
+            levels
+                .iter()
+                .map(|&(price, size)| PriceLevel { price, size })
+                .collect()
+        };
+        L2BookSnapshot {
+            yes: L2SideBook {
+                bids: levels(bids),
+                asks: levels(asks),
+            },
+            ..L2BookSnapshot::default()
+        }
+    }
+
+    fn yes_bid_ladder(levels: &[(f64, f64)]) -> L2BookSnapshot {
+        yes_ladder(levels, &[])
+    }
+
+    #[tokio::test]
+    async fn dry_run_queue_fill_waits_for_visible_size_ahead() {
+        let (exec, mut result_rx, mut sim_fill_rx) = dry_run_touch_test_executor();
+        let (l2_tx, l2_rx) = watch::channel(yes_bid_ladder(&[(0.50, 10.0), (0.49, 40.0)]));
+        let mut exec = exec.with_l2_book_rx(l2_rx);
+        exec.dry_run_market_touch_queue_fills = true;
+
+        exec.handle_place_bid(
+            Side::Yes,
+            TradeDirection::Buy,
+            0.50,
+            5.0,
+            BidReason::Provide,
+            TradePurpose::Provide,
+            0.0,
+            None,
+        )
+        .await;
+        let _ = result_rx
+            .recv()
+            .await
+            .expect("dry_run should emit OrderPlaced");
+
+        // 6 of the 10 shares ahead trade: nothing reaches us.
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 6.0))
+            .await;
+        assert!(sim_fill_rx.try_recv().is_err());
+
+        // Cancels ahead shrink the visible level to 2; growth behind us is ignored.
+        l2_tx.send_replace(yes_bid_ladder(&[(0.50, 2.0), (0.49, 40.0)]));
+        exec.handle_dry_run_market_data(queue_book_tick()).await;
+        l2_tx.send_replace(yes_bid_ladder(&[(0.50, 30.0), (0.49, 40.0)]));
+        exec.handle_dry_run_market_data(queue_book_tick()).await;
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 4.0))
+            .await;
+        let first_fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("size beyond the queue ahead should fill us");
+        assert_eq!(first_fill.source, FillSource::DryRunQueue);
+        assert!((first_fill.filled_size - 2.0).abs() < 1e-9);
+        exec.handle_fill_notification(&first_fill).await;
+
+        // A print below our price means our level traded through.
+        exec.handle_dry_run_market_data(queue_sell_print(0.49, 1.0))
+            .await;
+        let second_fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("trade-through should fill the remainder");
+        assert_eq!(second_fill.source, FillSource::DryRunQueue);
+        assert!((second_fill.filled_size - 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn dry_run_queue_fill_ignores_post_trade_ladder_on_prints() {
+        let (exec, mut result_rx, mut sim_fill_rx) = dry_run_touch_test_executor();
+        let (l2_tx, l2_rx) = watch::channel(yes_bid_ladder(&[(0.50, 10.0)]));
+        let mut exec = exec.with_l2_book_rx(l2_rx);
+        exec.dry_run_market_touch_queue_fills = true;
+
+        exec.handle_place_bid(
+            Side::Yes,
+            TradeDirection::Buy,
+            0.50,
+            5.0,
+            BidReason::Provide,
+            TradePurpose::Provide,
+            0.0,
+            None,
+        )
+        .await;
+        let _ = result_rx
+            .recv()
+            .await
+            .expect("dry_run should emit OrderPlaced");
+
+        // The ladder already shows the print's effect when the print arrives:
+        // the 6 shares must only be taken off the queue once.
+        l2_tx.send_replace(yes_bid_ladder(&[(0.50, 4.0)]));
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 6.0))
+            .await;
+        assert!(sim_fill_rx.try_recv().is_err());
+        exec.handle_dry_run_market_data(queue_book_tick()).await;
+        assert!(sim_fill_rx.try_recv().is_err());
+
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 5.0))
+            .await;
+        let fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("only size beyond the 4 still ahead reaches us");
+        assert!((fill.filled_size - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn dry_run_queue_fill_handles_sell_orders_on_the_ask_side() {
+        let (exec, mut result_rx, mut sim_fill_rx) = dry_run_touch_test_executor();
+        let (_l2_tx, l2_rx) = watch::channel(yes_ladder(&[(0.50, 20.0)], &[(0.52, 8.0)]));
+        let mut exec = exec.with_l2_book_rx(l2_rx);
+        exec.dry_run_market_touch_queue_fills = true;
+
+        exec.handle_place_bid(
+            Side::Yes,
+            TradeDirection::Sell,
+            0.52,
+            5.0,
+            BidReason::Provide,
+            TradePurpose::Provide,
+            0.0,
+            None,
+        )
+        .await;
+        let _ = result_rx
+            .recv()
+            .await
+            .expect("dry_run should emit OrderPlaced");
+
+        // SELL taker prints and bids below us never reach a resting ask.
+        exec.handle_dry_run_market_data(queue_sell_print(0.52, 50.0))
+            .await;
+        exec.handle_dry_run_market_data(queue_book_tick()).await;
+        assert!(sim_fill_rx.try_recv().is_err());
+
+        exec.handle_dry_run_market_data(queue_buy_print(0.52, 10.0))
+            .await;
+        let first_fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("BUY prints beyond the asks ahead should fill us");
+        assert_eq!(first_fill.source, FillSource::DryRunQueue);
+        assert!((first_fill.filled_size - 2.0).abs() < 1e-9);
+        exec.handle_fill_notification(&first_fill).await;
+
+        // A BUY print above our ask means our level traded through.
+        exec.handle_dry_run_market_data(queue_buy_print(0.53, 1.0))
+            .await;
+        let second_fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("trade-through should fill the remainder");
+        assert!((second_fill.filled_size - 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn dry_run_queue_fill_needs_a_seeded_queue() {
+        let (mut exec, mut result_rx, mut sim_fill_rx) = dry_run_touch_test_executor();
+        exec.dry_run_market_touch_queue_fills = true;
+        exec.dry_run_market_touch_min_fill_size = 0.0;
+
+        exec.handle_place_bid(
+            Side::Yes,
+            TradeDirection::Buy,
+            0.50,
+            5.0,
+            BidReason::Provide,
+            TradePurpose::Provide,
+            0.0,
+            None,
+        )
+        .await;
+        let _ = result_rx
+            .recv()
+            .await
+            .expect("dry_run should emit OrderPlaced");
+
+        // No L2 feed: an unknown queue never fills on prints at our price.
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 50.0))
+            .await;
+        assert!(sim_fill_rx.try_recv().is_err());
+
+        exec.handle_dry_run_market_data(MarketDataMsg::BookDepthTick {
+            market_side: Side::Yes,
+            best_bid: 0.50,
+            best_ask: Some(0.52),
+            best_bid_size: Some(3.0),
+            best_ask_size: Some(40.0),
+            best_bid_drop_qty: 0.0,
+            best_ask_drop_qty: 0.0,
+            event_time_ms: None,
+            source_sequence_id: None,
+            ts: Instant::now(),
+        })
+        .await;
+        exec.handle_dry_run_market_data(queue_sell_print(0.50, 4.0))
+            .await;
+        let fill = sim_fill_rx
+            .recv()
+            .await
+            .expect("best-level depth seeds the queue");
+        assert_eq!(fill.source, FillSource::DryRunQueue);
+        assert!((fill.filled_size - 1.0).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn dry_run_market_trade_partial_fill_fraction_haircuts_trade_support() {
         let (_cmd_tx, cmd_rx) = mpsc::channel::<ExecutionCmd>(4);
@@ -5038,6 +5617,8 @@ mod tests {
             price: 0.50,
             status: FillStatus::Confirmed,
             source: FillSource::DryRunTradeSellTouch,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         })
         .await;
@@ -5085,6 +5666,8 @@ mod tests {
             price: 0.48,
             status: FillStatus::Confirmed,
             source: FillSource::DryRunTradeSellTouch,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         })
         .await;
@@ -0,0 +1,265 @@
+//! Polymarket fee model shared by live, dry-run and backtest accounting.
+//!
+//! Taker fees are charged per share as `rate * min(p, 1 - p)`; makers pay
+//! nothing and earn a rebate estimated as a share of the taker fee their fill
+//! generated. The per-category schedule is the default; a `fee_rate_bps`
+//! reported on the fill itself (user WS) overrides the schedule rate.
+
+use serde::{Deserialize, Serialize};
+
+use super::config_validation::{at_least_reported, clamp_reported};
+use super::messages::{FillEvent, FillSource};
+
+const FEE_EPS: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeCategory {
+    /// Short-dated crypto up/down markets.
+    Crypto,
+    Sports,
+    /// Markets without a taker fee.
+    FeeFree,
+}
+
+impl FeeCategory {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "crypto" => Some(Self::Crypto),
+            "sports" => Some(Self::Sports),
+            "fee_free" | "none" | "free" => Some(Self::FeeFree),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Crypto => "crypto",
+            Self::Sports => "sports",
+            Self::FeeFree => "fee_free",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidityRole {
+    /// Role not reported; charged as taker so estimates stay conservative.
+    #[default]
+    Unknown,
+    Maker,
+    Taker,
+}
+
+impl LiquidityRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Maker => "maker",
+            Self::Taker => "taker",
+        }
+    }
+
+    /// Recorded role name; anything unrecognized stays `Unknown`.
+    pub fn parse(raw: &str) -> Self {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "maker" => Self::Maker,
+            "taker" => Self::Taker,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Role implied by a dry-run fill source; live fills carry their own role.
+    pub fn from_source(source: FillSource) -> Self {
+        match source {
+            FillSource::DryRunTaker => Self::Taker,
+            FillSource::DryRunImmediate
+            | FillSource::DryRunBookTouch
+            | FillSource::DryRunBookDepthTouch
+            | FillSource::DryRunTradeSellTouch
+            | FillSource::DryRunQueue => Self::Maker,
+            FillSource::Unknown | FillSource::UserWs | FillSource::Backtest => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FeeSchedule {
+    pub category: FeeCategory,
+    /// Taker fee rate; fee per share is `rate * min(p, 1 - p)`.
+    pub taker_rate: f64,
+    /// Fraction of the taker fee credited back to the maker side.
+    pub maker_rebate_share: f64,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self::for_category(FeeCategory::Crypto)
+    }
+}
+
+impl FeeSchedule {
+    pub fn for_category(category: FeeCategory) -> Self {
+        let (taker_rate, maker_rebate_share) = match category {
+            FeeCategory::Crypto => (0.07, 0.20),
+            FeeCategory::Sports => (0.03, 0.25),
+            FeeCategory::FeeFree => (0.0, 0.0),
+        };
+        Self {
+            category,
+            taker_rate,
+            maker_rebate_share,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let category = std::env::var("PM_FEE_CATEGORY")
+            .ok()
+            .and_then(|v| FeeCategory::parse(&v))
+            .unwrap_or(FeeCategory::Crypto);
+        let mut schedule = Self::for_category(category);
+        if let Some(v) = std::env::var("PM_TAKER_FEE_RATE")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+        {
+            schedule.taker_rate = at_least_reported("PM_TAKER_FEE_RATE", v, 0.0);
+        }
+        if let Some(v) = std::env::var("PM_MAKER_REBATE_SHARE")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+        {
+            schedule.maker_rebate_share = clamp_reported("PM_MAKER_REBATE_SHARE", v, 0.0, 1.0);
+        }
+        schedule
+    }
+
+    /// Rate applied to one fill: the venue-reported `fee_rate_bps` when
+    /// present, else the schedule rate.
+    pub fn effective_rate(&self, fee_rate_bps: Option<u32>) -> f64 {
+        fee_rate_bps
+            .map(|bps| bps as f64 / 10_000.0)
+            .unwrap_or(self.taker_rate)
+    }
+
+    pub fn quote(
+        &self,
+        role: LiquidityRole,
+        price: f64,
+        size: f64,
+        fee_rate_bps: Option<u32>,
+    ) -> FeeQuote {
+        let size = size.max(0.0);
+        let taker_fee = size * taker_fee_per_share(price, self.effective_rate(fee_rate_bps));
+        match role {
+            LiquidityRole::Maker => FeeQuote {
+                fee: 0.0,
+                rebate: taker_fee * self.maker_rebate_share,
+            },
+            LiquidityRole::Taker | LiquidityRole::Unknown => FeeQuote {
+                fee: taker_fee,
+                rebate: 0.0,
+            },
+        }
+    }
+
+    pub fn quote_fill(&self, fill: &FillEvent) -> FeeQuote {
+        self.quote(
+            fill.liquidity,
+            fill.price,
+            fill.filled_size,
+            fill.fee_rate_bps,
+        )
+    }
+}
+
+/// Fee paid and rebate earned by one fill, both non-negative.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct FeeQuote {
+    pub fee: f64,
+    pub rebate: f64,
+}
+
+impl FeeQuote {
+    /// Cash cost of the fill's fees (negative when the rebate dominates).
+    pub fn net(self) -> f64 {
+        self.fee - self.rebate
+    }
+}
+
+/// Running fee totals for a ledger or PnL summary.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct FeeTally {
+    pub fees_paid: f64,
+    pub maker_rebates: f64,
+}
+
+impl FeeTally {
+    pub fn add(&mut self, quote: FeeQuote) {
+        self.fees_paid += quote.fee;
+        self.maker_rebates += quote.rebate;
+    }
+
+    pub fn net(&self) -> f64 {
+        self.fees_paid - self.maker_rebates
+    }
+}
+
+/// Polymarket taker fee per share: `rate * min(p, 1 - p)`.
+pub fn taker_fee_per_share(price: f64, rate: f64) -> f64 {
+    rate * price.clamp(0.0, 1.0).min((1.0 - price).max(0.0))
+}
+
+/// Share of `fee` attributable to `qty` out of a lot of `lot_qty`.
+pub fn prorate_fee(fee: f64, lot_qty: f64, qty: f64) -> f64 {
+    if lot_qty <= FEE_EPS {
+        0.0
+    } else {
+        (fee * (qty / lot_qty).clamp(0.0, 1.0)).max(0.0)
+    }
+}
+
+/// Parse a venue `fee_rate_bps` value (string or number).
+pub fn parse_fee_rate_bps(raw: &str) -> Option<u32> {
+    let raw = raw.trim();
+    raw.parse::<u32>()
+        .ok()
+        .or_else(|| raw.parse::<f64>().ok().map(|v| v.max(0.0).round() as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maker_earns_rebate_share_of_taker_fee() {
+        let schedule = FeeSchedule::for_category(FeeCategory::Crypto);
+        let taker = schedule.quote(LiquidityRole::Taker, 0.40, 10.0, None);
+        assert!((taker.fee - 10.0 * 0.07 * 0.40).abs() < 1e-12);
+        assert_eq!(taker.rebate, 0.0);
+
+        let maker = schedule.quote(LiquidityRole::Maker, 0.60, 10.0, None);
+        assert_eq!(maker.fee, 0.0);
+        assert!((maker.rebate - taker.fee * 0.20).abs() < 1e-12);
+
+        let unknown = schedule.quote(LiquidityRole::Unknown, 0.40, 10.0, None);
+        assert_eq!(unknown, taker);
+
+        let free = FeeSchedule::for_category(FeeCategory::FeeFree);
+        assert_eq!(free.quote(LiquidityRole::Taker, 0.5, 10.0, None).net(), 0.0);
+    }
+
+    #[test]
+    fn reported_fee_rate_bps_overrides_schedule_rate() {
+        let schedule = FeeSchedule::default();
+        let quote = schedule.quote(LiquidityRole::Taker, 0.50, 4.0, Some(1000));
+        assert!((quote.fee - 4.0 * 0.10 * 0.50).abs() < 1e-12);
+        let zero = schedule.quote(LiquidityRole::Taker, 0.50, 4.0, Some(0));
+        assert_eq!(zero.fee, 0.0);
+
+        assert_eq!(parse_fee_rate_bps("1000"), Some(1000));
+        assert_eq!(parse_fee_rate_bps(" 250.0 "), Some(250));
+        assert_eq!(parse_fee_rate_bps("n/a"), None);
+        assert!((prorate_fee(0.03, 3.0, 1.0) - 0.01).abs() < 1e-12);
+        assert_eq!(prorate_fee(0.03, 0.0, 1.0), 0.0);
+    }
+}
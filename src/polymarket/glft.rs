@@ -11,6 +11,7 @@ use tokio::time::{sleep, timeout};
 use tokio_tungstenite::connect_async;
 use tracing::{debug, info, warn};
 
+use super::glft_fit_store::{GlftFitRecord, GlftFitStore, GlftFitStoreConfig, GlftFitWriter};
 use super::messages::{MarketDataMsg, TakerSide};
 use super::types::Side;
 
@@ -22,6 +23,15 @@ const SIGMA_VAR_FLOOR: f64 = 2e-7;
 const BINANCE_STALE_SECS: u64 = 3;
 const BINANCE_STALE_GRACE_SECS: u64 = 2;
 const BINANCE_IDLE_RECONNECT_SECS: u64 = 6;
+// Own perp aggTrade feed; always blended into the reference with unit weight.
+const GLFT_PRIMARY_REFERENCE_SOURCE: &str = "binance_perp";
+const GLFT_REF_SOURCE_STALE_MS_DEFAULT: u64 = 3000;
+const GLFT_REF_OUTLIER_BPS_DEFAULT: f64 = 30.0;
+const GLFT_REF_MIN_SOURCES_DEFAULT: usize = 2;
+// Outlier rejection needs a majority to vote against; with 2 sources we can only
+// flag divergence, not decide which one is wrong.
+const GLFT_REF_OUTLIER_MIN_SOURCES: usize = 3;
+const GLFT_REF_DEGRADED_CONFIDENCE_CAP: f64 = 0.50;
 const POLY_BOOK_STALE_SECS: u64 = 2;
 // Poly book staleness has two tiers:
 // - > POLY_BOOK_STALE_SECS: degrade to Guarded/Tracking (still tradable if other checks pass)
@@ -255,6 +265,8 @@ pub struct GlftSignalSnapshot {
     pub quote_regime: QuoteRegime,
     pub reference_confidence: f64,
     pub reference_health: ReferenceHealth,
+    /// Fresh, non-outlier venues in the blended reference (0 when unknown).
+    pub reference_sources: u8,
     pub drift_mode: DriftMode,
     pub hard_basis_unstable: bool,
     pub ready: bool,
@@ -294,6 +306,7 @@ impl Default for GlftSignalSnapshot {
             quote_regime: QuoteRegime::Blocked,
             reference_confidence: 0.0,
             reference_health: ReferenceHealth::Blocked,
+            reference_sources: 0,
             drift_mode: DriftMode::Normal,
             hard_basis_unstable: false,
             ready: false,
@@ -392,6 +405,8 @@ pub struct GlftRuntimeConfig {
     pub tick_size: f64,
     pub intensity_window: Duration,
     pub refit_interval: Duration,
+    pub reference: ReferenceBlendConfig,
+    pub fit_store: Option<GlftFitStoreConfig>,
 }
 
 impl GlftRuntimeConfig {
@@ -438,6 +453,8 @@ impl GlftRuntimeConfig {
                     .filter(|v| *v > 0)
                     .unwrap_or(10),
             ),
+            reference: ReferenceBlendConfig::from_env(),
+            fit_store: GlftFitStoreConfig::from_env(),
         })
     }
 
@@ -487,6 +504,185 @@ struct BinanceTick {
     ts: Instant,
 }
 
+/// Multi-venue reference settings for the GLFT anchor.
+///
+/// With `multi_source` disabled the anchor is the Binance perp feed alone, exactly
+/// as before. When enabled, external venue ticks (see
+/// [`GlftSignalEngine::with_reference_rx`]) are blended with it and the engine
+/// degrades `reference_health` instead of blocking when sources drop or diverge.
+#[derive(Debug, Clone)]
+pub struct ReferenceBlendConfig {
+    pub multi_source: bool,
+    pub source_stale: Duration,
+    pub outlier_bps: f64,
+    pub min_healthy_sources: usize,
+}
+
+impl Default for ReferenceBlendConfig {
+    fn default() -> Self {
+        Self {
+            multi_source: false,
+            source_stale: Duration::from_millis(GLFT_REF_SOURCE_STALE_MS_DEFAULT),
+            outlier_bps: GLFT_REF_OUTLIER_BPS_DEFAULT,
+            min_healthy_sources: GLFT_REF_MIN_SOURCES_DEFAULT,
+        }
+    }
+}
+
+impl ReferenceBlendConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            multi_source: std::env::var("PM_GLFT_MULTI_SOURCE_REF_ENABLED")
+                .ok()
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(defaults.multi_source),
+            source_stale: std::env::var("PM_GLFT_REF_SOURCE_STALE_MS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.source_stale),
+            outlier_bps: std::env::var("PM_GLFT_REF_OUTLIER_BPS")
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(defaults.outlier_bps),
+            min_healthy_sources: std::env::var("PM_GLFT_REF_MIN_SOURCES")
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.min_healthy_sources),
+        }
+    }
+}
+
+/// One price observation from a spot/perp venue feeding the GLFT reference.
+#[derive(Debug, Clone, Copy)]
+pub struct ReferencePriceTick {
+    pub source: &'static str,
+    pub price: f64,
+    pub weight: f64,
+    pub ts: Instant,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ReferenceSourceState {
+    source: &'static str,
+    price: f64,
+    weight: f64,
+    ts: Instant,
+    // Per-source anchor so venues quoting different levels (perp vs spot, USD vs
+    // USDT) blend on log returns instead of raw prices.
+    anchor_price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ReferenceBlend {
+    log_return: f64,
+    fresh_sources: usize,
+    used_sources: usize,
+    rejected_sources: usize,
+    divergent: bool,
+}
+
+#[derive(Debug, Default)]
+struct ReferenceBlender {
+    sources: Vec<ReferenceSourceState>,
+    base_price: Option<f64>,
+    last_log_return: Option<f64>,
+}
+
+impl ReferenceBlender {
+    fn observe(&mut self, tick: ReferencePriceTick, stale: Duration) {
+        if !(tick.price.is_finite() && tick.price > 0.0 && tick.weight > 0.0) {
+            return;
+        }
+        if self.base_price.is_none() {
+            self.base_price = Some(tick.price);
+        }
+        // New or recovering sources are re-anchored onto the current consensus so a
+        // venue joining mid-round does not inject its own move since round open.
+        // A recovering source keeps its anchor when no other source is fresh: the
+        // consensus is then as old as the gap and re-anchoring would erase the move.
+        let consensus = self.last_log_return.unwrap_or(0.0).exp();
+        let others_fresh = self
+            .sources
+            .iter()
+            .any(|s| s.source != tick.source && tick.ts.saturating_duration_since(s.ts) <= stale);
+        match self.sources.iter_mut().find(|s| s.source == tick.source) {
+            Some(state) => {
+                if others_fresh && tick.ts.saturating_duration_since(state.ts) > stale {
+                    state.anchor_price = tick.price / consensus;
+                }
+                state.price = tick.price;
+                state.weight = tick.weight;
+                state.ts = tick.ts;
+            }
+            None => self.sources.push(ReferenceSourceState {
+                source: tick.source,
+                price: tick.price,
+                weight: tick.weight,
+                ts: tick.ts,
+                anchor_price: tick.price / consensus,
+            }),
+        }
+    }
+
+    fn blend(&self, now: Instant, cfg: &ReferenceBlendConfig) -> Option<ReferenceBlend> {
+        let fresh: Vec<(f64, f64)> = self
+            .sources
+            .iter()
+            .filter(|s| now.saturating_duration_since(s.ts) <= cfg.source_stale)
+            .map(|s| ((s.price / s.anchor_price).ln(), s.weight))
+            .filter(|(r, _)| r.is_finite())
+            .collect();
+        if fresh.is_empty() {
+            return None;
+        }
+        let mut returns: Vec<f64> = fresh.iter().map(|(r, _)| *r).collect();
+        returns.sort_by(|a, b| a.total_cmp(b));
+        let mid = returns.len() / 2;
+        let median = if returns.len().is_multiple_of(2) {
+            0.5 * (returns[mid - 1] + returns[mid])
+        } else {
+            returns[mid]
+        };
+        let tol = cfg.outlier_bps / 10_000.0;
+        let kept: Vec<(f64, f64)> = if fresh.len() >= GLFT_REF_OUTLIER_MIN_SOURCES {
+            fresh
+                .iter()
+                .copied()
+                .filter(|(r, _)| (r - median).abs() <= tol)
+                .collect()
+        } else {
+            fresh.clone()
+        };
+        // Everything disagrees with the median (pathological spread): fall back to
+        // the median itself rather than dropping the reference.
+        let (log_return, used_sources) = if kept.is_empty() {
+            (median, 0)
+        } else {
+            let weight_sum: f64 = kept.iter().map(|(_, w)| w).sum();
+            let r = kept.iter().map(|(r, w)| r * w).sum::<f64>() / weight_sum.max(1e-12);
+            (r, kept.len())
+        };
+        let rejected_sources = fresh.len() - kept.len();
+        let spread = returns[returns.len() - 1] - returns[0];
+        Some(ReferenceBlend {
+            log_return,
+            fresh_sources: fresh.len(),
+            used_sources,
+            rejected_sources,
+            divergent: rejected_sources > 0 || spread > tol,
+        })
+    }
+
+    fn reference_price(&self, blend: ReferenceBlend) -> Option<f64> {
+        Some(self.base_price? * blend.log_return.exp())
+    }
+}
+
 pub struct GlftSignalEngine {
     cfg: GlftRuntimeConfig,
     md_rx: mpsc::Receiver<MarketDataMsg>,
@@ -500,6 +696,11 @@ pub struct GlftSignalEngine {
     binance_tick_count: usize,
     poly_book_tick_count: usize,
     round_open_binance: Option<f64>,
+    reference: ReferenceBlender,
+    reference_rx: Option<mpsc::Receiver<ReferencePriceTick>>,
+    reference_rx_attached: bool,
+    reference_degraded_active: bool,
+    fit_writer: Option<GlftFitWriter>,
     last_poly_mid_prob: Option<f64>,
     last_poly_mid_ts: Option<Instant>,
     last_poly_book_event_ts: Option<Instant>,
@@ -543,7 +744,12 @@ impl GlftSignalEngine {
         md_rx: mpsc::Receiver<MarketDataMsg>,
         tx: watch::Sender<GlftSignalSnapshot>,
     ) -> Self {
-        let raw_bootstrap = load_bootstrap_snapshot(&cfg);
+        let fit_store = open_fit_store(&cfg);
+        let raw_bootstrap = fit_store
+            .as_ref()
+            .and_then(|store| load_fit_store_bootstrap(&cfg, store))
+            .or_else(|| load_bootstrap_snapshot(&cfg));
+        let fit_writer = fit_store.map(GlftFitWriter::spawn);
         let bootstrap = raw_bootstrap.filter(|s| s.basis_prob.abs() <= GLFT_BASIS_SNAPSHOT_MAX_ABS);
         let bootstrap_rejected = raw_bootstrap.is_some() && bootstrap.is_none();
         let fit = IntensityFitSnapshot {
@@ -565,6 +771,11 @@ impl GlftSignalEngine {
             binance_tick_count: 0,
             poly_book_tick_count: 0,
             round_open_binance: None,
+            reference: ReferenceBlender::default(),
+            reference_rx: None,
+            reference_rx_attached: false,
+            reference_degraded_active: false,
+            fit_writer,
             last_poly_mid_prob: None,
             last_poly_mid_ts: None,
             last_poly_book_event_ts: None,
@@ -606,12 +817,33 @@ impl GlftSignalEngine {
         }
     }
 
+    /// Attach external venue ticks (Bybit/OKX/Coinbase/...) to blend into the anchor.
+    /// Ignored unless `PM_GLFT_MULTI_SOURCE_REF_ENABLED` is set.
+    pub fn with_reference_rx(mut self, rx: mpsc::Receiver<ReferencePriceTick>) -> Self {
+        if self.cfg.reference.multi_source {
+            self.reference_rx = Some(rx);
+        }
+        self
+    }
+
     pub async fn run(mut self) {
         let (binance_tx, mut binance_rx) = mpsc::channel::<BinanceTick>(256);
         let symbol = self.cfg.symbol.clone();
         tokio::spawn(async move {
             run_binance_aggtrade_feed(symbol, binance_tx).await;
         });
+        let mut reference_rx = self.reference_rx.take();
+        let multi_source = reference_rx.is_some();
+        self.reference_rx_attached = multi_source;
+        if multi_source {
+            info!(
+                "📡 GLFT multi-source reference | primary={} stale_ms={} outlier_bps={:.1} min_sources={}",
+                GLFT_PRIMARY_REFERENCE_SOURCE,
+                self.cfg.reference.source_stale.as_millis(),
+                self.cfg.reference.outlier_bps,
+                self.cfg.reference.min_healthy_sources,
+            );
+        }
 
         let mut refit_tick = tokio::time::interval(self.cfg.refit_interval);
         let bootstrap_age_secs = self
@@ -669,7 +901,40 @@ impl GlftSignalEngine {
                     }
                 }
                 Some(binance_tick) = binance_rx.recv() => {
-                    self.handle_binance_tick(binance_tick);
+                    if multi_source {
+                        self.handle_reference_tick(ReferencePriceTick {
+                            source: GLFT_PRIMARY_REFERENCE_SOURCE,
+                            price: binance_tick.price,
+                            weight: 1.0,
+                            ts: binance_tick.ts,
+                        });
+                    } else {
+                        self.handle_binance_tick(binance_tick);
+                    }
+                    let snapshot = self.publish();
+                    if !ready_announced && snapshot.ready {
+                        info!(
+                            "✅ GLFT signal ready | state={:?} warm={:?} fit_status={:?} source={:?} ready_elapsed_ms={} A={:.3} k={:.3} sigma={:.7} basis={:.3}",
+                            snapshot.signal_state,
+                            snapshot.warm_start_status,
+                            snapshot.fit_status,
+                            snapshot.fit_source,
+                            snapshot.ready_elapsed_ms,
+                            snapshot.fit_a,
+                            snapshot.fit_k,
+                            snapshot.sigma_prob,
+                            snapshot.basis_prob,
+                        );
+                        ready_announced = true;
+                    }
+                }
+                Some(reference_tick) = async {
+                    match reference_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending::<Option<ReferencePriceTick>>().await,
+                    }
+                }, if multi_source => {
+                    self.handle_reference_tick(reference_tick);
                     let snapshot = self.publish();
                     if !ready_announced && snapshot.ready {
                         info!(
@@ -790,6 +1055,29 @@ impl GlftSignalEngine {
         }
     }
 
+    fn handle_reference_tick(&mut self, tick: ReferencePriceTick) {
+        self.reference
+            .observe(tick, self.cfg.reference.source_stale);
+        let Some(blend) = self.reference.blend(tick.ts, &self.cfg.reference) else {
+            return;
+        };
+        self.reference.last_log_return = Some(blend.log_return);
+        if let Some(price) = self.reference.reference_price(blend) {
+            self.handle_binance_tick(BinanceTick { price, ts: tick.ts });
+        }
+    }
+
+    /// Multi-source mode only: too few healthy venues, or venues disagreeing,
+    /// caps `reference_health` at Guarded instead of blocking quotes.
+    fn reference_blend_degraded(&self, now: Instant) -> Option<ReferenceBlend> {
+        if !self.reference_rx_attached {
+            return None;
+        }
+        let blend = self.reference.blend(now, &self.cfg.reference)?;
+        (blend.used_sources < self.cfg.reference.min_healthy_sources || blend.divergent)
+            .then_some(blend)
+    }
+
     fn handle_binance_tick(&mut self, tick: BinanceTick) {
         if self.round_open_binance.is_none() {
             self.round_open_binance = Some(tick.price);
@@ -1294,6 +1582,9 @@ impl GlftSignalEngine {
         if !(a.is_finite() && k.is_finite() && a > 0.0 && k > 0.0 && r2 >= min_r2) {
             self.fit.quality = FitQuality::Invalid;
             self.ready_fit_streak = 0;
+            if a.is_finite() && k.is_finite() {
+                self.record_fit_history(a, k, r2, trade_count, FitQuality::Invalid);
+            }
             return;
         }
 
@@ -1316,6 +1607,7 @@ impl GlftSignalEngine {
         self.fit = candidate;
         self.last_live_fit = Some(candidate);
         self.ready_fit_streak = self.ready_fit_streak.saturating_add(1);
+        self.record_fit_history(candidate.a, candidate.k, r2, trade_count, FitQuality::Ready);
         if self.ready_fit_streak >= SNAPSHOT_SAVE_READY_STREAK_MIN {
             save_bootstrap_snapshot(
                 &self.cfg,
@@ -1330,6 +1622,26 @@ impl GlftSignalEngine {
         }
     }
 
+    fn record_fit_history(&self, a: f64, k: f64, r2: f64, trade_count: usize, quality: FitQuality) {
+        let Some(writer) = self.fit_writer.as_ref() else {
+            return;
+        };
+        let record = GlftFitRecord {
+            symbol: self.cfg.symbol.clone(),
+            horizon: self.cfg.horizon_key.clone(),
+            round_end_ts: self.cfg.market_end_ts,
+            saved_at: now_unix(),
+            fit_a: a,
+            fit_k: k,
+            sigma_prob: self.sigma_prob,
+            basis_prob: self.basis_prob,
+            r2,
+            trade_count: trade_count.min(u32::MAX as usize) as u32,
+            quality,
+        };
+        writer.record(record);
+    }
+
     fn publish(&mut self) -> GlftSignalSnapshot {
         let anchor_prob = self.anchor_prob().unwrap_or(0.5);
         let alpha_flow = self.alpha_flow();
@@ -1378,6 +1690,11 @@ impl GlftSignalEngine {
             quote_regime: controller.regime,
             reference_confidence: controller.confidence,
             reference_health: controller.health,
+            reference_sources: self
+                .reference
+                .blend(now_inst, &self.cfg.reference)
+                .map(|b| b.used_sources.min(u8::MAX as usize) as u8)
+                .unwrap_or(0),
             drift_mode: controller.drift_mode,
             hard_basis_unstable: matches!(controller.regime, QuoteRegime::Blocked),
             ready: trade_ready,
@@ -1478,15 +1795,22 @@ impl GlftSignalEngine {
             gate.blockers,
             block_reason,
         );
-        let confidence = reference_confidence(
+        let mut confidence = reference_confidence(
             regime,
             gate.fit_status,
             drift_ewma_ticks,
             binance_ok && poly_ok,
         );
+        let reference_degraded = self.update_reference_degraded(now);
+        if reference_degraded {
+            confidence = confidence.min(GLFT_REF_DEGRADED_CONFIDENCE_CAP);
+        }
         let trusted_mid = trusted_mid(modeled_mid, synthetic_mid_yes, confidence, tick);
         let trusted_mid_slope_tps = self.update_trusted_mid_slope(trusted_mid, now, tick);
-        let health = quote_regime_reference_health(regime);
+        let mut health = quote_regime_reference_health(regime);
+        if reference_degraded && matches!(health, ReferenceHealth::Healthy) {
+            health = ReferenceHealth::Guarded;
+        }
         let drift_mode = quote_regime_drift_mode(regime);
 
         ReferenceController {
@@ -1506,6 +1830,26 @@ impl GlftSignalEngine {
         }
     }
 
+    fn update_reference_degraded(&mut self, now: Instant) -> bool {
+        let degraded = self.reference_blend_degraded(now);
+        if degraded.is_some() != self.reference_degraded_active {
+            if let Some(blend) = degraded {
+                warn!(
+                    "⚠️ GLFT reference degraded | fresh={} used={} rejected={} divergent={} min_sources={} -> health capped at Guarded",
+                    blend.fresh_sources,
+                    blend.used_sources,
+                    blend.rejected_sources,
+                    blend.divergent,
+                    self.cfg.reference.min_healthy_sources,
+                );
+            } else {
+                info!("✅ GLFT reference restored | sources healthy and aligned");
+            }
+        }
+        self.reference_degraded_active = degraded.is_some();
+        self.reference_degraded_active
+    }
+
     fn update_poly_soft_stale(&mut self, now: Instant, gate: &ReadinessGate) -> bool {
         if !gate.ready || gate.blockers.await_poly_book {
             self.poly_soft_stale_active = false;
@@ -2476,6 +2820,47 @@ fn infer_binance_symbol(slug: &str) -> Option<String> {
     }
 }
 
+fn open_fit_store(cfg: &GlftRuntimeConfig) -> Option<GlftFitStore> {
+    let store_cfg = cfg.fit_store.as_ref()?;
+    match GlftFitStore::open(&store_cfg.path) {
+        Ok(store) => Some(store),
+        Err(err) => {
+            warn!(
+                "⚠️ GLFT fit store unavailable | path={} err={:?} -> single-snapshot warm start",
+                store_cfg.path.display(),
+                err
+            );
+            None
+        }
+    }
+}
+
+fn load_fit_store_bootstrap(
+    cfg: &GlftRuntimeConfig,
+    store: &GlftFitStore,
+) -> Option<GlftBootstrapSnapshot> {
+    let store_cfg = cfg.fit_store.as_ref()?;
+    match store.blended_warm_start(&cfg.symbol, &cfg.horizon_key, now_unix(), store_cfg) {
+        Ok(Some(snapshot)) if now_unix().saturating_sub(snapshot.saved_at) <= SNAPSHOT_TTL_SECS => {
+            info!(
+                "📡 GLFT fit store warm start | symbol={} horizon={} A={:.3} k={:.3} sigma={:.7} basis={:.3}",
+                cfg.symbol,
+                cfg.horizon_key,
+                snapshot.fit_a,
+                snapshot.fit_k,
+                snapshot.sigma_prob,
+                snapshot.basis_prob,
+            );
+            Some(snapshot)
+        }
+        Ok(_) => None,
+        Err(err) => {
+            debug!("GLFT fit store read failed: {:?}", err);
+            None
+        }
+    }
+}
+
 fn load_bootstrap_snapshot(cfg: &GlftRuntimeConfig) -> Option<GlftBootstrapSnapshot> {
     let path = cfg.snapshot_path();
     let raw = fs::read_to_string(path).ok()?;
@@ -2601,6 +2986,8 @@ mod tests {
             tick_size: 0.01,
             intensity_window: Duration::from_secs(30),
             refit_interval: Duration::from_secs(10),
+            reference: ReferenceBlendConfig::default(),
+            fit_store: None,
         }
     }
 
@@ -2653,6 +3040,115 @@ mod tests {
         assert!(infer_binance_symbol("election-market").is_none());
     }
 
+    fn ref_tick(source: &'static str, price: f64, ts: Instant) -> ReferencePriceTick {
+        ReferencePriceTick {
+            source,
+            price,
+            weight: 1.0,
+            ts,
+        }
+    }
+
+    #[test]
+    fn reference_blender_single_source_tracks_primary_price() {
+        let cfg = ReferenceBlendConfig::default();
+        let mut blender = ReferenceBlender::default();
+        let now = Instant::now();
+        blender.observe(ref_tick("binance_perp", 100.0, now), cfg.source_stale);
+        blender.observe(ref_tick("binance_perp", 101.0, now), cfg.source_stale);
+        let blend = blender.blend(now, &cfg).expect("fresh source");
+        assert_eq!(blend.used_sources, 1);
+        assert!(!blend.divergent);
+        let price = blender.reference_price(blend).unwrap();
+        assert!((price - 101.0).abs() < 1e-9, "price={}", price);
+    }
+
+    #[test]
+    fn reference_blender_rejects_outlier_and_reanchors_late_source() {
+        let cfg = ReferenceBlendConfig::default();
+        let mut blender = ReferenceBlender::default();
+        let now = Instant::now();
+        // Different venue levels blend on returns, not raw prices.
+        blender.observe(ref_tick("binance_perp", 100.0, now), cfg.source_stale);
+        blender.observe(ref_tick("bybit", 100.5, now), cfg.source_stale);
+        blender.observe(ref_tick("okx", 99.8, now), cfg.source_stale);
+        blender.observe(ref_tick("binance_perp", 101.0, now), cfg.source_stale);
+        blender.observe(ref_tick("bybit", 101.505, now), cfg.source_stale);
+        blender.observe(ref_tick("okx", 99.8 * 1.05, now), cfg.source_stale);
+        let blend = blender.blend(now, &cfg).unwrap();
+        assert_eq!(blend.fresh_sources, 3);
+        assert_eq!(blend.used_sources, 2);
+        assert_eq!(blend.rejected_sources, 1);
+        assert!(blend.divergent);
+        assert!((blend.log_return - 1.01f64.ln()).abs() < 1e-6);
+
+        // A venue joining mid-round inherits the consensus move instead of zero.
+        blender.last_log_return = Some(blend.log_return);
+        blender.observe(ref_tick("coinbase", 200.0, now), cfg.source_stale);
+        let state = blender
+            .sources
+            .iter()
+            .find(|s| s.source == "coinbase")
+            .unwrap();
+        assert!(((state.price / state.anchor_price).ln() - blend.log_return).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reference_blender_keeps_move_when_sole_source_returns_after_gap() {
+        let cfg = ReferenceBlendConfig::default();
+        let mut blender = ReferenceBlender::default();
+        let open_ts = Instant::now();
+        blender.observe(
+            ref_tick("binance_perp", 100_000.0, open_ts),
+            cfg.source_stale,
+        );
+        blender.last_log_return = blender.blend(open_ts, &cfg).map(|b| b.log_return);
+
+        let later = open_ts + Duration::from_secs(10);
+        blender.observe(ref_tick("binance_perp", 101_000.0, later), cfg.source_stale);
+        let blend = blender.blend(later, &cfg).expect("fresh source");
+        let price = blender.reference_price(blend).unwrap();
+        assert!((price - 101_000.0).abs() < 1e-6, "price={}", price);
+
+        // Every source stale: the first one back still carries its full move.
+        blender.last_log_return = Some(blend.log_return);
+        blender.observe(ref_tick("bybit", 100_050.0, later), cfg.source_stale);
+        let much_later = later + Duration::from_secs(10);
+        blender.observe(ref_tick("bybit", 102_050.0, much_later), cfg.source_stale);
+        let blend = blender.blend(much_later, &cfg).expect("bybit fresh");
+        assert_eq!(blend.fresh_sources, 1);
+        let price = blender.reference_price(blend).unwrap();
+        let expected = 101_000.0 * 102_050.0 / 100_050.0;
+        assert!((price - expected).abs() < 1e-6, "price={}", price);
+    }
+
+    #[test]
+    fn binance_outage_degrades_reference_instead_of_blocking() {
+        let mut engine = test_engine("TESTREF_OUTAGE", None);
+        engine.cfg.reference.multi_source = true;
+        engine.cfg.reference.min_healthy_sources = 3;
+        engine.reference_rx_attached = true;
+        prime_ready_inputs(&mut engine);
+        let now = Instant::now();
+        let open_ts = now - Duration::from_secs(4);
+        engine.handle_reference_tick(ref_tick("binance_perp", 100.0, open_ts));
+        engine.handle_reference_tick(ref_tick("bybit", 100.2, open_ts));
+        engine.handle_reference_tick(ref_tick("okx", 100.1, open_ts));
+
+        // Binance goes silent; the other venues keep the anchor fresh.
+        let recent_ts = now - Duration::from_secs(2);
+        engine.handle_reference_tick(ref_tick("bybit", 100.3, recent_ts));
+        engine.handle_reference_tick(ref_tick("okx", 100.2, recent_ts));
+        let gate = engine.readiness_gate(now);
+        assert!(!gate.blockers.await_binance);
+        assert!(engine.update_reference_degraded(now));
+        let anchor_move = engine.last_binance_tick.unwrap().price / 100.0;
+        assert!(anchor_move > 1.0 && anchor_move < 1.002);
+
+        engine.handle_reference_tick(ref_tick("binance_perp", 100.1, now));
+        assert!(!engine.update_reference_degraded(now));
+    }
+
     #[test]
     fn compute_offsets_returns_positive_spread() {
         let fit = IntensityFitSnapshot {
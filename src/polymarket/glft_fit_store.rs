@@ -0,0 +1,468 @@
+//! Durable GLFT intensity-fit history.
+//!
+//! Refits that produce numbers are upserted into a SQLite table holding one row
+//! per symbol/horizon/round: the latest ready fit of the round, or the latest
+//! fit at all until one is ready. Warm starts blend across recent rounds and the
+//! same UTC hour-of-day instead of inheriting a single JSON snapshot from `/tmp`.
+//! The engine hands records to a [`GlftFitWriter`] thread so refits never wait
+//! on SQLite.
+//! The table is plain SQLite and intended to be queried directly for research:
+//!
+//! ```sql
+//! SELECT hour_of_day, AVG(fit_a), AVG(fit_k), COUNT(*)
+//! FROM glft_fits WHERE symbol = 'BTCUSDT' AND horizon = '15m' AND quality = 'ready'
+//! GROUP BY hour_of_day;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
+
+use rusqlite::{params, Connection};
+use tracing::debug;
+
+use super::glft::{FitQuality, GlftBootstrapSnapshot};
+
+const FIT_STORE_QUERY_LIMIT: usize = 5000;
+
+#[derive(Debug, Clone)]
+pub struct GlftFitStoreConfig {
+    pub path: PathBuf,
+    /// Most recent rounds blended into the warm start.
+    pub blend_rounds: usize,
+    /// Extra weight for rounds fitted in the same UTC hour-of-day.
+    pub same_hour_weight: f64,
+    pub max_age_secs: u64,
+}
+
+impl Default for GlftFitStoreConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("data/glft/fits.sqlite"),
+            blend_rounds: 6,
+            same_hour_weight: 2.0,
+            max_age_secs: 7 * 24 * 3600,
+        }
+    }
+}
+
+impl GlftFitStoreConfig {
+    /// `None` when `PM_GLFT_FIT_STORE_ENABLED=false`; the engine then falls back
+    /// to the legacy single-file snapshot only.
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("PM_GLFT_FIT_STORE_ENABLED")
+            .ok()
+            .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if !enabled {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            path: std::env::var("PM_GLFT_FIT_STORE_PATH")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or(defaults.path),
+            blend_rounds: std::env::var("PM_GLFT_FIT_STORE_BLEND_ROUNDS")
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.blend_rounds),
+            same_hour_weight: std::env::var("PM_GLFT_FIT_STORE_SAME_HOUR_WEIGHT")
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v >= 1.0)
+                .unwrap_or(defaults.same_hour_weight),
+            max_age_secs: std::env::var("PM_GLFT_FIT_STORE_MAX_AGE_HOURS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .map(|h| h * 3600)
+                .unwrap_or(defaults.max_age_secs),
+        })
+    }
+}
+
+/// One refit outcome as persisted in `glft_fits`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlftFitRecord {
+    pub symbol: String,
+    pub horizon: String,
+    pub round_end_ts: u64,
+    pub saved_at: u64,
+    pub fit_a: f64,
+    pub fit_k: f64,
+    pub sigma_prob: f64,
+    pub basis_prob: f64,
+    pub r2: f64,
+    pub trade_count: u32,
+    pub quality: FitQuality,
+}
+
+impl GlftFitRecord {
+    pub fn hour_of_day(&self) -> u8 {
+        utc_hour_of_day(self.saved_at)
+    }
+}
+
+pub struct GlftFitStore {
+    conn: Connection,
+}
+
+impl GlftFitStore {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+             CREATE TABLE IF NOT EXISTS glft_fits (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 symbol TEXT NOT NULL,
+                 horizon TEXT NOT NULL,
+                 round_end_ts INTEGER NOT NULL,
+                 saved_at INTEGER NOT NULL,
+                 hour_of_day INTEGER NOT NULL,
+                 fit_a REAL NOT NULL,
+                 fit_k REAL NOT NULL,
+                 sigma_prob REAL NOT NULL,
+                 basis_prob REAL NOT NULL,
+                 r2 REAL NOT NULL,
+                 trade_count INTEGER NOT NULL,
+                 quality TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_glft_fits_key
+                 ON glft_fits(symbol, horizon, saved_at);
+             -- Files from the append-per-refit layout: keep each round's latest
+             -- ready fit (or latest fit) so the per-round unique index applies.
+             DELETE FROM glft_fits WHERE id NOT IN (
+                 SELECT id FROM (
+                     SELECT id, ROW_NUMBER() OVER (
+                         PARTITION BY symbol, horizon, round_end_ts
+                         ORDER BY quality = 'ready' DESC, saved_at DESC, id DESC
+                     ) AS rn
+                     FROM glft_fits
+                 ) WHERE rn = 1
+             );
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_glft_fits_round
+                 ON glft_fits(symbol, horizon, round_end_ts);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Upsert the round's row. A non-ready fit never replaces a ready one.
+    pub fn record(&self, rec: &GlftFitRecord) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO glft_fits (symbol, horizon, round_end_ts, saved_at, hour_of_day,
+                 fit_a, fit_k, sigma_prob, basis_prob, r2, trade_count, quality)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+             ON CONFLICT(symbol, horizon, round_end_ts) DO UPDATE SET
+                 saved_at = excluded.saved_at,
+                 hour_of_day = excluded.hour_of_day,
+                 fit_a = excluded.fit_a,
+                 fit_k = excluded.fit_k,
+                 sigma_prob = excluded.sigma_prob,
+                 basis_prob = excluded.basis_prob,
+                 r2 = excluded.r2,
+                 trade_count = excluded.trade_count,
+                 quality = excluded.quality
+             WHERE excluded.quality = 'ready' OR glft_fits.quality != 'ready'",
+            params![
+                rec.symbol,
+                rec.horizon,
+                rec.round_end_ts as i64,
+                rec.saved_at as i64,
+                rec.hour_of_day() as i64,
+                rec.fit_a,
+                rec.fit_k,
+                rec.sigma_prob,
+                rec.basis_prob,
+                rec.r2,
+                rec.trade_count as i64,
+                quality_as_str(rec.quality),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Newest-first history for one symbol/horizon saved at or after `since_ts`.
+    pub fn history(
+        &self,
+        symbol: &str,
+        horizon: &str,
+        since_ts: u64,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<GlftFitRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT symbol, horizon, round_end_ts, saved_at, fit_a, fit_k, sigma_prob,
+                    basis_prob, r2, trade_count, quality
+             FROM glft_fits
+             WHERE symbol = ?1 AND horizon = ?2 AND saved_at >= ?3
+             ORDER BY saved_at DESC, id DESC
+             LIMIT ?4",
+        )?;
+        let rows = stmt.query_map(
+            params![symbol, horizon, since_ts as i64, limit as i64],
+            |row| {
+                let quality: String = row.get(10)?;
+                Ok(GlftFitRecord {
+                    symbol: row.get(0)?,
+                    horizon: row.get(1)?,
+                    round_end_ts: row.get::<_, i64>(2)?.max(0) as u64,
+                    saved_at: row.get::<_, i64>(3)?.max(0) as u64,
+                    fit_a: row.get(4)?,
+                    fit_k: row.get(5)?,
+                    sigma_prob: row.get(6)?,
+                    basis_prob: row.get(7)?,
+                    r2: row.get(8)?,
+                    trade_count: row.get::<_, i64>(9)?.max(0) as u32,
+                    quality: quality_from_str(&quality),
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
+    /// Warm-start seed blended across stored rounds; `None` without usable history.
+    pub fn blended_warm_start(
+        &self,
+        symbol: &str,
+        horizon: &str,
+        now_ts: u64,
+        cfg: &GlftFitStoreConfig,
+    ) -> rusqlite::Result<Option<GlftBootstrapSnapshot>> {
+        let since = now_ts.saturating_sub(cfg.max_age_secs);
+        let history = self.history(symbol, horizon, since, FIT_STORE_QUERY_LIMIT)?;
+        Ok(blend_fit_history(&history, now_ts, cfg))
+    }
+}
+
+/// Background writer owning the store connection; records are dropped (with a
+/// debug log) if the thread has stopped.
+#[derive(Clone)]
+pub struct GlftFitWriter {
+    tx: std_mpsc::Sender<GlftFitRecord>,
+}
+
+impl GlftFitWriter {
+    pub fn spawn(store: GlftFitStore) -> Self {
+        let (tx, rx) = std_mpsc::channel::<GlftFitRecord>();
+        std::thread::spawn(move || {
+            for rec in rx {
+                if let Err(err) = store.record(&rec) {
+                    debug!("GLFT fit store write failed: {:?}", err);
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    pub fn record(&self, rec: GlftFitRecord) {
+        if self.tx.send(rec).is_err() {
+            debug!("GLFT fit store writer stopped; fit dropped");
+        }
+    }
+}
+
+/// Blend the latest ready fit of each stored round.
+///
+/// The `blend_rounds` most recent rounds are weighted `1/(1+rank)`; rounds from the
+/// same UTC hour-of-day (including older days) get `same_hour_weight` on top.
+/// A/k/sigma blend in log space; basis is round-specific and taken from the newest
+/// round only.
+pub fn blend_fit_history(
+    history: &[GlftFitRecord],
+    now_ts: u64,
+    cfg: &GlftFitStoreConfig,
+) -> Option<GlftBootstrapSnapshot> {
+    let mut rounds: Vec<&GlftFitRecord> = Vec::new();
+    for rec in history {
+        let usable = rec.quality == FitQuality::Ready
+            && rec.fit_a > 0.0
+            && rec.fit_k > 0.0
+            && rec.sigma_prob > 0.0
+            && rec.fit_a.is_finite()
+            && rec.fit_k.is_finite()
+            && rec.sigma_prob.is_finite();
+        if usable && !rounds.iter().any(|r| r.round_end_ts == rec.round_end_ts) {
+            rounds.push(rec);
+        }
+    }
+    rounds.sort_by_key(|r| std::cmp::Reverse(r.saved_at));
+    let newest = *rounds.first()?;
+
+    let current_hour = utc_hour_of_day(now_ts);
+    let mut older_same_hour_taken = 0usize;
+    let mut weight_sum = 0.0;
+    let (mut log_a, mut log_k, mut log_sigma) = (0.0, 0.0, 0.0);
+    for (rank, rec) in rounds.iter().enumerate() {
+        let recent = rank < cfg.blend_rounds;
+        let same_hour = rec.hour_of_day() == current_hour;
+        if !recent {
+            if !same_hour || older_same_hour_taken >= cfg.blend_rounds {
+                continue;
+            }
+            older_same_hour_taken += 1;
+        }
+        let mut weight = 1.0 / (1.0 + rank.min(cfg.blend_rounds) as f64);
+        if same_hour {
+            weight *= cfg.same_hour_weight;
+        }
+        weight_sum += weight;
+        log_a += weight * rec.fit_a.ln();
+        log_k += weight * rec.fit_k.ln();
+        log_sigma += weight * rec.sigma_prob.ln();
+    }
+    if weight_sum <= 0.0 {
+        return None;
+    }
+    Some(GlftBootstrapSnapshot {
+        fit_a: (log_a / weight_sum).exp(),
+        fit_k: (log_k / weight_sum).exp(),
+        sigma_prob: (log_sigma / weight_sum).exp(),
+        basis_prob: newest.basis_prob,
+        saved_at: newest.saved_at,
+    })
+}
+
+fn utc_hour_of_day(ts: u64) -> u8 {
+    ((ts % 86_400) / 3_600) as u8
+}
+
+fn quality_as_str(quality: FitQuality) -> &'static str {
+    match quality {
+        FitQuality::Warm => "warm",
+        FitQuality::Ready => "ready",
+        FitQuality::Invalid => "invalid",
+    }
+}
+
+fn quality_from_str(raw: &str) -> FitQuality {
+    match raw {
+        "ready" => FitQuality::Ready,
+        "invalid" => FitQuality::Invalid,
+        _ => FitQuality::Warm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(round_end_ts: u64, saved_at: u64, fit_a: f64, quality: FitQuality) -> GlftFitRecord {
+        GlftFitRecord {
+            symbol: "BTCUSDT".to_string(),
+            horizon: "15m".to_string(),
+            round_end_ts,
+            saved_at,
+            fit_a,
+            fit_k: 0.5,
+            sigma_prob: 0.02,
+            basis_prob: 0.01,
+            r2: 0.8,
+            trade_count: 40,
+            quality,
+        }
+    }
+
+    #[test]
+    fn store_round_trips_and_blends_latest_fit_per_round() {
+        let store = GlftFitStore::open_in_memory().unwrap();
+        let cfg = GlftFitStoreConfig::default();
+        let now = 1_770_000_000;
+        // Two rounds; only the last ready fit of each round counts.
+        store
+            .record(&rec(now - 900, now - 960, 9.0, FitQuality::Ready))
+            .unwrap();
+        store
+            .record(&rec(now - 900, now - 910, 1.0, FitQuality::Ready))
+            .unwrap();
+        store
+            .record(&rec(now, now - 20, 4.0, FitQuality::Ready))
+            .unwrap();
+        store
+            .record(&rec(now, now - 10, 50.0, FitQuality::Invalid))
+            .unwrap();
+
+        // One row per round; the later invalid fit kept the round's ready row.
+        let history = store.history("BTCUSDT", "15m", 0, 100).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].quality, FitQuality::Ready);
+        assert!((history[0].fit_a - 4.0).abs() < 1e-12);
+        assert!((history[1].fit_a - 1.0).abs() < 1e-12);
+
+        let seed = store
+            .blended_warm_start("BTCUSDT", "15m", now, &cfg)
+            .unwrap()
+            .unwrap();
+        // Same hour for both rounds: weights 1 and 1/2 on A=4 and A=1 in log space.
+        let expected = ((4.0f64.ln() + 0.5 * 1.0f64.ln()) / 1.5).exp();
+        assert!((seed.fit_a - expected).abs() < 1e-9, "a={}", seed.fit_a);
+        assert_eq!(seed.saved_at, now - 20);
+        assert!(store
+            .blended_warm_start("ETHUSDT", "15m", now, &cfg)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn legacy_append_rows_collapse_to_one_row_per_round() {
+        let legacy = GlftFitStore::open_in_memory().unwrap();
+        legacy
+            .conn
+            .execute_batch("DROP INDEX idx_glft_fits_round;")
+            .unwrap();
+        for (saved_at, fit_a, quality) in [
+            (100, 1.0, "ready"),
+            (110, 3.0, "ready"),
+            (120, 9.0, "invalid"),
+        ] {
+            legacy
+                .conn
+                .execute(
+                    "INSERT INTO glft_fits (symbol, horizon, round_end_ts, saved_at, hour_of_day,
+                         fit_a, fit_k, sigma_prob, basis_prob, r2, trade_count, quality)
+                     VALUES ('BTCUSDT', '15m', 900, ?1, 0, ?2, 0.5, 0.02, 0.01, 0.8, 40, ?3)",
+                    params![saved_at, fit_a, quality],
+                )
+                .unwrap();
+        }
+
+        let store = GlftFitStore::init(legacy.conn).unwrap();
+        let history = store.history("BTCUSDT", "15m", 0, 100).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].quality, FitQuality::Ready);
+        assert!((history[0].fit_a - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn same_hour_rounds_from_older_days_join_the_blend() {
+        let cfg = GlftFitStoreConfig {
+            blend_rounds: 1,
+            ..GlftFitStoreConfig::default()
+        };
+        let now = 1_770_000_000;
+        let history = vec![
+            rec(now, now - 5, 2.0, FitQuality::Ready),
+            // Six hours ago: outside blend_rounds and a different hour -> ignored.
+            rec(now - 6 * 3600, now - 6 * 3600, 100.0, FitQuality::Ready),
+            // Yesterday, same hour-of-day -> included with the hour boost.
+            rec(now - 86_400, now - 86_400, 8.0, FitQuality::Ready),
+        ];
+        let seed = blend_fit_history(&history, now, &cfg).unwrap();
+        let w_old = 0.5 * cfg.same_hour_weight;
+        let w_new = cfg.same_hour_weight;
+        let expected = ((w_new * 2.0f64.ln() + w_old * 8.0f64.ln()) / (w_new + w_old)).exp();
+        assert!((seed.fit_a - expected).abs() < 1e-9, "a={}", seed.fit_a);
+        assert!((seed.basis_prob - 0.01).abs() < 1e-12);
+    }
+}
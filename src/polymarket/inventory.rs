@@ -8,6 +8,10 @@ use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use tracing::{info, warn};
 
+use super::clock::{system_clock, SharedClock};
+use super::config_validation::env_checked;
+use super::fees::FeeSchedule;
+use super::inventory_recovery::{InventoryRecovery, RecoveredFill, RecoveredFillKind};
 use super::messages::{
     FillEvent, FillStatus, InventoryEvent, InventorySnapshot, InventoryState, TradeDirection,
 };
@@ -15,6 +19,7 @@ use super::pair_ledger::{
     build_pair_ledger, PairLedgerBuildResult, PairLedgerEvent, PairLedgerEventKind, PathKind,
     TrancheState,
 };
+use super::pnl_ledger::MarketPnlLedger;
 use super::recorder::{RecorderHandle, RecorderSessionMeta};
 use super::types::Side;
 
@@ -23,6 +28,8 @@ pub struct InventoryConfig {
     pub max_net_diff: f64,
     pub max_portfolio_cost: f64,
     pub bid_size: f64,
+    /// Fee model used to book net fees into the pair ledger.
+    pub fees: FeeSchedule,
 }
 
 impl Default for InventoryConfig {
@@ -31,6 +38,7 @@ impl Default for InventoryConfig {
             max_net_diff: 10.0,
             max_portfolio_cost: 1.02,
             bid_size: 5.0,
+            fees: FeeSchedule::default(),
         }
     }
 }
@@ -38,21 +46,19 @@ impl Default for InventoryConfig {
 impl InventoryConfig {
     pub fn from_env() -> Self {
         let mut cfg = Self::default();
-        if let Ok(v) = std::env::var("PM_MAX_NET_DIFF") {
-            if let Ok(f) = v.parse::<f64>() {
-                cfg.max_net_diff = f;
-            }
+        if let Some(f) = env_checked::<f64>("PM_MAX_NET_DIFF", "must be >= 0", |f| *f >= 0.0) {
+            cfg.max_net_diff = f;
         }
-        if let Ok(v) = std::env::var("PM_MAX_PORTFOLIO_COST") {
-            if let Ok(f) = v.parse::<f64>() {
-                cfg.max_portfolio_cost = f;
-            }
+        if let Some(f) = env_checked::<f64>("PM_MAX_PORTFOLIO_COST", "must be > 0", |f| *f > 0.0) {
+            cfg.max_portfolio_cost = f;
         }
+        // Non-positive sizes are reported by `CoordinatorConfig`, which keeps them too.
         if let Ok(v) = std::env::var("PM_BID_SIZE") {
             if let Ok(f) = v.parse::<f64>() {
                 cfg.bid_size = f;
             }
         }
+        cfg.fees = FeeSchedule::from_env();
         cfg
     }
 }
@@ -63,6 +69,8 @@ struct FillRecord {
     direction: TradeDirection,
     size: f64,
     price: f64,
+    /// Net fee (fee paid minus maker rebate) booked with this fill.
+    fee: f64,
     ts: Instant,
     kind: PairLedgerEventKind,
 }
@@ -74,6 +82,7 @@ struct PendingFillRecord {
     direction: TradeDirection,
     size: f64,
     price: f64,
+    fee: f64,
     matched_at: Instant,
 }
 
@@ -94,6 +103,8 @@ pub struct InventoryManager {
     late_failed_after_promotion: u64,
     recorder: Option<RecorderHandle>,
     recorder_meta: Option<RecorderSessionMeta>,
+    pnl_ledger: Option<MarketPnlLedger>,
+    clock: SharedClock,
 }
 
 impl InventoryManager {
@@ -124,7 +135,108 @@ impl InventoryManager {
             late_failed_after_promotion: 0,
             recorder,
             recorder_meta,
+            pnl_ledger: None,
+            clock: system_clock(),
+        }
+    }
+
+    /// Inject the time source used for pending-fill promotion.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record closed tranches and merges in the round PnL ledger.
+    pub fn with_pnl_ledger(mut self, ledger: MarketPnlLedger) -> Self {
+        self.pnl_ledger = Some(ledger);
+        self
+    }
+
+    /// Seed the settled ledger with fills recovered at startup (recorder replay
+    /// reconciled against Data API positions) and publish the rebuilt snapshot,
+    /// so an open tranche survives a restart. Matched-only fills go back into
+    /// the pending set under their order id with a fresh promotion timer, so
+    /// the Confirmed/Failed delivered after the restart resolves them. Fees
+    /// are re-quoted from the fee schedule with each fill's recorded role.
+    pub fn with_recovery(mut self, recovery: &InventoryRecovery) -> Self {
+        if recovery.is_empty() {
+            return self;
+        }
+        let now = self.clock.now();
+        for fill in &recovery.fills {
+            let ts = now
+                .checked_sub(Duration::from_millis(fill.age_ms))
+                .unwrap_or(now);
+            match fill.kind {
+                RecoveredFillKind::Merge => self.apply_merge(fill.size, "recovery", ts),
+                RecoveredFillKind::Fill => {
+                    let size = match fill.direction {
+                        TradeDirection::Buy => fill.size,
+                        TradeDirection::Sell => -fill.size,
+                    };
+                    self.settled_ledger.push(FillRecord {
+                        side: fill.side,
+                        direction: fill.direction,
+                        size,
+                        price: fill.price,
+                        fee: self.recovered_fill_fee(fill),
+                        ts,
+                        kind: PairLedgerEventKind::Fill,
+                    });
+                }
+            }
         }
+        for pending in &recovery.pending {
+            let fill = pending.fill;
+            self.pending_fills.push(PendingFillRecord {
+                order_id: pending.order_id.clone(),
+                side: fill.side,
+                direction: fill.direction,
+                size: match fill.direction {
+                    TradeDirection::Buy => fill.size,
+                    TradeDirection::Sell => -fill.size,
+                },
+                price: fill.price,
+                fee: self.recovered_fill_fee(&fill),
+                matched_at: now,
+            });
+        }
+        self.recompute_snapshot();
+        let _ = self.state_tx.send(self.snapshot);
+        let active = self.snapshot.pair_ledger.active_tranche;
+        info!(
+            "♻️ Inventory recovered | settled YES={:.2}@{:.4} NO={:.2}@{:.4} net={:.2} | active_tranche={:?} residual={:.2}",
+            self.snapshot.settled.yes_qty,
+            self.snapshot.settled.yes_avg_cost,
+            self.snapshot.settled.no_qty,
+            self.snapshot.settled.no_avg_cost,
+            self.snapshot.settled.net_diff,
+            active.and_then(|tranche| tranche.first_side),
+            self.snapshot.pair_ledger.residual_qty,
+        );
+        self.emit_inventory_event(
+            "inventory_recovered",
+            serde_json::json!({
+                "recorder_fills": recovery.recorder_fill_count,
+                "pending_fills": recovery.pending.len(),
+                "synthetic_fills": recovery.synthetic_fill_count,
+                "api_checked": recovery.api_positions.is_some(),
+                "yes_qty": self.snapshot.settled.yes_qty,
+                "yes_avg_cost": self.snapshot.settled.yes_avg_cost,
+                "no_qty": self.snapshot.settled.no_qty,
+                "no_avg_cost": self.snapshot.settled.no_avg_cost,
+                "active_tranche_id": active.map(|tranche| tranche.id),
+                "residual_qty": self.snapshot.pair_ledger.residual_qty,
+            }),
+        );
+        self
+    }
+
+    fn recovered_fill_fee(&self, fill: &RecoveredFill) -> f64 {
+        self.cfg
+            .fees
+            .quote(fill.liquidity, fill.price, fill.size, fill.fee_rate_bps)
+            .net()
     }
 
     pub async fn run(mut self) {
@@ -141,70 +253,10 @@ impl InventoryManager {
                     let Some(event) = maybe_event else {
                         break;
                     };
-                    match event {
-                        InventoryEvent::Fill(fill) => {
-                            self.apply_fill(&fill);
-                            let _ = self.state_tx.send(self.snapshot);
-                            self.log_fill_snapshot(&fill);
-                            self.emit_inventory_event(
-                                "fill_snapshot",
-                                serde_json::json!({
-                                    "slot": fill.slot().as_str(),
-                                    "side": format!("{:?}", fill.side),
-                                    "direction": format!("{:?}", fill.direction),
-                                    "size": fill.filled_size,
-                                    "price": fill.price,
-                                    "status": format!("{:?}", fill.status),
-                                    "fill_source": fill.source.as_str(),
-                                    "order_id": fill.order_id,
-                                    "working_net_diff": self.snapshot.working.net_diff,
-                                    "working_portfolio_cost": self.snapshot.working.portfolio_cost,
-                                    "pending_yes_qty": self.snapshot.pending_yes_qty,
-                                    "pending_no_qty": self.snapshot.pending_no_qty,
-                                    "fragile": self.snapshot.fragile,
-                                }),
-                            );
-                        }
-                        InventoryEvent::Merge {
-                            full_set_size,
-                            merge_id,
-                            ts,
-                        } => {
-                            self.apply_merge(full_set_size, &merge_id, ts);
-                            let _ = self.state_tx.send(self.snapshot);
-                            info!(
-                                "📦 Merge sync: full_set={:.2} id={} → settled net={:.1} cost={:.4} || working net={:.1} cost={:.4} pending_yes={:.2} pending_no={:.2} fragile={}",
-                                full_set_size,
-                                &merge_id[..8.min(merge_id.len())],
-                                self.snapshot.settled.net_diff,
-                                self.snapshot.settled.portfolio_cost,
-                                self.snapshot.working.net_diff,
-                                self.snapshot.working.portfolio_cost,
-                                self.snapshot.pending_yes_qty,
-                                self.snapshot.pending_no_qty,
-                                self.snapshot.fragile,
-                            );
-                            self.emit_inventory_event(
-                                "merge_sync",
-                                serde_json::json!({
-                                    "full_set_size": full_set_size,
-                                    "merge_id": merge_id,
-                                    "working_net_diff": self.snapshot.working.net_diff,
-                                    "working_portfolio_cost": self.snapshot.working.portfolio_cost,
-                                    "settled_net_diff": self.snapshot.settled.net_diff,
-                                    "settled_portfolio_cost": self.snapshot.settled.portfolio_cost,
-                                    "pending_yes_qty": self.snapshot.pending_yes_qty,
-                                    "pending_no_qty": self.snapshot.pending_no_qty,
-                                    "fragile": self.snapshot.fragile,
-                                }),
-                            );
-                        }
-                    }
+                    self.handle_event(event);
                 }
                 _ = promotion_tick.tick() => {
-                    if self.promote_expired_pending(Instant::now()) {
-                        let _ = self.state_tx.send(self.snapshot);
-                    }
+                    self.promote_due_pending();
                 }
             }
         }
@@ -219,6 +271,73 @@ impl InventoryManager {
         );
     }
 
+    /// Apply one inventory event and publish the resulting snapshot.
+    ///
+    /// Shared by the actor loop and by synchronous drivers (backtest harness).
+    pub(crate) fn handle_event(&mut self, event: InventoryEvent) {
+        match event {
+            InventoryEvent::Fill(fill) => {
+                self.apply_fill(&fill);
+                let _ = self.state_tx.send(self.snapshot);
+                self.log_fill_snapshot(&fill);
+                self.emit_inventory_event(
+                    "fill_snapshot",
+                    serde_json::json!({
+                        "slot": fill.slot().as_str(),
+                        "side": format!("{:?}", fill.side),
+                        "direction": format!("{:?}", fill.direction),
+                        "size": fill.filled_size,
+                        "price": fill.price,
+                        "status": format!("{:?}", fill.status),
+                        "fill_source": fill.source.as_str(),
+                        "liquidity": fill.liquidity.as_str(),
+                        "fee_rate_bps": fill.fee_rate_bps,
+                        "order_id": fill.order_id,
+                        "working_net_diff": self.snapshot.working.net_diff,
+                        "working_portfolio_cost": self.snapshot.working.portfolio_cost,
+                        "pending_yes_qty": self.snapshot.pending_yes_qty,
+                        "pending_no_qty": self.snapshot.pending_no_qty,
+                        "fragile": self.snapshot.fragile,
+                    }),
+                );
+            }
+            InventoryEvent::Merge {
+                full_set_size,
+                merge_id,
+                ts,
+            } => {
+                self.apply_merge(full_set_size, &merge_id, ts);
+                let _ = self.state_tx.send(self.snapshot);
+                info!(
+                    "📦 Merge sync: full_set={:.2} id={} → settled net={:.1} cost={:.4} || working net={:.1} cost={:.4} pending_yes={:.2} pending_no={:.2} fragile={}",
+                    full_set_size,
+                    &merge_id[..8.min(merge_id.len())],
+                    self.snapshot.settled.net_diff,
+                    self.snapshot.settled.portfolio_cost,
+                    self.snapshot.working.net_diff,
+                    self.snapshot.working.portfolio_cost,
+                    self.snapshot.pending_yes_qty,
+                    self.snapshot.pending_no_qty,
+                    self.snapshot.fragile,
+                );
+                self.emit_inventory_event(
+                    "merge_sync",
+                    serde_json::json!({
+                        "full_set_size": full_set_size,
+                        "merge_id": merge_id,
+                        "working_net_diff": self.snapshot.working.net_diff,
+                        "working_portfolio_cost": self.snapshot.working.portfolio_cost,
+                        "settled_net_diff": self.snapshot.settled.net_diff,
+                        "settled_portfolio_cost": self.snapshot.settled.portfolio_cost,
+                        "pending_yes_qty": self.snapshot.pending_yes_qty,
+                        "pending_no_qty": self.snapshot.pending_no_qty,
+                        "fragile": self.snapshot.fragile,
+                    }),
+                );
+            }
+        }
+    }
+
     fn log_fill_snapshot(&self, fill: &FillEvent) {
         info!(
             "📦 Fill: slot={} {:?} {:.2}@{:.3} status={:?} id={} → settled YES={:.1}@{:.4} NO={:.1}@{:.4} | net={:.1} cost={:.4} || working YES={:.1}@{:.4} NO={:.1}@{:.4} | net={:.1} cost={:.4} pending_yes={:.2} pending_no={:.2} fragile={}",
@@ -251,6 +370,7 @@ impl InventoryManager {
             TradeDirection::Buy => fill.filled_size,
             TradeDirection::Sell => -fill.filled_size,
         };
+        let fee = self.cfg.fees.quote_fill(fill).net();
 
         match fill.status {
             FillStatus::Matched => {
@@ -261,6 +381,7 @@ impl InventoryManager {
                     direction: fill.direction,
                     size: signed_size,
                     price: fill.price,
+                    fee,
                     matched_at: fill.ts,
                 });
             }
@@ -282,6 +403,7 @@ impl InventoryManager {
                         direction: fill.direction,
                         size: signed_size,
                         price: fill.price,
+                        fee,
                         ts: fill.ts,
                         kind: PairLedgerEventKind::Fill,
                     });
@@ -309,7 +431,7 @@ impl InventoryManager {
         self.recompute_snapshot();
     }
 
-    fn apply_merge(&mut self, full_set_size: f64, _merge_id: &str, merge_ts: Instant) {
+    fn apply_merge(&mut self, full_set_size: f64, merge_id: &str, merge_ts: Instant) {
         let requested = full_set_size.max(0.0);
         if requested <= f64::EPSILON {
             return;
@@ -331,6 +453,7 @@ impl InventoryManager {
             direction: TradeDirection::Sell,
             size: -amount,
             price: current.yes_avg_cost.max(0.0),
+            fee: 0.0,
             ts: merge_ts,
             kind: PairLedgerEventKind::Merge,
         });
@@ -339,9 +462,13 @@ impl InventoryManager {
             direction: TradeDirection::Sell,
             size: -amount,
             price: current.no_avg_cost.max(0.0),
+            fee: 0.0,
             ts: merge_ts,
             kind: PairLedgerEventKind::Merge,
         });
+        if let Some(ledger) = self.pnl_ledger.as_ref() {
+            ledger.record_merge(merge_id, amount, self.clock.unix_now_ms() as i64);
+        }
 
         self.recompute_snapshot();
     }
@@ -363,6 +490,7 @@ impl InventoryManager {
                 direction: pending.direction,
                 size: pending.size,
                 price: pending.price,
+                fee: pending.fee,
                 ts: pending.matched_at,
                 kind: PairLedgerEventKind::Fill,
             });
@@ -384,6 +512,7 @@ impl InventoryManager {
             direction: pending.direction,
             size: pending.size,
             price: pending.price,
+            fee: pending.fee,
             ts: pending.matched_at,
             kind: PairLedgerEventKind::Fill,
         }));
@@ -521,6 +650,7 @@ impl InventoryManager {
                 direction: pending.direction,
                 size: pending.size,
                 price: price_override.unwrap_or(pending.price),
+                fee: pending.fee,
                 ts: pending.matched_at,
                 kind: PairLedgerEventKind::Fill,
             });
@@ -536,6 +666,14 @@ impl InventoryManager {
         }
     }
 
+    /// Promote pending fills whose timeout elapsed on the injected clock and
+    /// publish if anything changed.
+    pub(crate) fn promote_due_pending(&mut self) {
+        if self.promote_expired_pending(self.clock.now()) {
+            let _ = self.state_tx.send(self.snapshot);
+        }
+    }
+
     fn promote_expired_pending(&mut self, now: Instant) -> bool {
         let mut changed = false;
         let mut idx = 0;
@@ -554,6 +692,7 @@ impl InventoryManager {
                     direction: pending.direction,
                     size: pending.size,
                     price: pending.price,
+                    fee: pending.fee,
                     ts: pending.matched_at,
                     kind: PairLedgerEventKind::Fill,
                 });
@@ -578,6 +717,7 @@ impl InventoryManager {
                 direction: record.direction,
                 size: record.size.abs(),
                 price: record.price,
+                fee: record.fee,
                 ts: record.ts,
                 kind: record.kind,
             })
@@ -637,10 +777,15 @@ impl InventoryManager {
                         "pairable_qty": closed.pairable_qty,
                         "pair_cost_tranche": closed.pair_cost_tranche,
                         "pair_cost_fifo_ref": closed.pair_cost_fifo_ref,
+                        "pair_cost_net": closed.pair_cost_net,
+                        "fees_usdc": closed.fees_usdc,
                         "gross_surplus": closed.gross_surplus,
                         "spendable_surplus": closed.spendable_surplus,
                     }),
                 );
+                if let Some(ledger) = self.pnl_ledger.as_ref() {
+                    ledger.record_tranche(&closed, self.clock.unix_now_ms() as i64);
+                }
             }
         }
 
@@ -700,6 +845,18 @@ impl InventoryManager {
             );
         }
 
+        if curr.pair_ledger.residual_unwound_qty > prev.pair_ledger.residual_unwound_qty + 1e-9 {
+            self.emit_inventory_event(
+                "residual_unwound",
+                serde_json::json!({
+                    "unwound_qty": curr.pair_ledger.residual_unwound_qty
+                        - prev.pair_ledger.residual_unwound_qty,
+                    "total_unwound_qty": curr.pair_ledger.residual_unwound_qty,
+                    "realized_pnl": curr.pair_ledger.residual_unwind_realized_pnl,
+                }),
+            );
+        }
+
         if curr.pair_ledger.total_pairable_qty() + 1e-9 < prev.pair_ledger.total_pairable_qty() {
             self.emit_inventory_event(
                 "merge_pairable_reduced",
@@ -736,6 +893,8 @@ impl InventoryManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::polymarket::clock::{Clock, SimClock};
+    use crate::polymarket::fees::LiquidityRole;
     use crate::polymarket::messages::FillSource;
     use crate::polymarket::recorder::{
         RecorderConfig, RecorderHandle, RecorderMarketMode, RecorderSessionMeta,
@@ -753,6 +912,8 @@ mod tests {
             price,
             status: FillStatus::Matched,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         }
     }
@@ -771,6 +932,89 @@ mod tests {
         std::env::temp_dir().join(format!("pm_as_ofi_{}_{}", prefix, ts))
     }
 
+    #[test]
+    fn recovery_seeds_settled_inventory_and_open_tranche() {
+        use crate::polymarket::inventory_recovery::RecoveredFill;
+
+        let (state_tx, state_rx) = watch::channel(InventorySnapshot::default());
+        let (_fill_tx, fill_rx) = mpsc::channel(16);
+        let fill = |side, size, price, age_ms| RecoveredFill {
+            side,
+            direction: TradeDirection::Buy,
+            size,
+            price,
+            kind: RecoveredFillKind::Fill,
+            age_ms,
+            liquidity: LiquidityRole::Maker,
+            fee_rate_bps: None,
+        };
+        let recovery = InventoryRecovery {
+            fills: vec![
+                fill(Side::Yes, 10.0, 0.40, 30_000),
+                fill(Side::No, 6.0, 0.55, 10_000),
+            ],
+            recorder_fill_count: 2,
+            ..Default::default()
+        };
+        let im = InventoryManager::new(InventoryConfig::default(), fill_rx, state_tx, None, None)
+            .with_recovery(&recovery);
+
+        let snap = *state_rx.borrow();
+        assert!((im.snapshot.settled.net_diff - 4.0).abs() < 1e-9);
+        assert!((snap.settled.yes_qty - 10.0).abs() < 1e-9);
+        assert!((snap.settled.no_avg_cost - 0.55).abs() < 1e-9);
+        assert!(!snap.fragile);
+        let active = snap.pair_ledger.active_tranche.expect("open tranche");
+        assert_eq!(active.first_side, Some(Side::Yes));
+        assert!((active.residual_qty - 4.0).abs() < 1e-9);
+        // Maker fills are re-quoted: the crypto rebates show up as negative
+        // tranche fees while the VWAP stays on the fill price.
+        assert!((active.first_vwap - 0.40).abs() < 1e-9);
+        let rebates = 10.0 * 0.07 * 0.40 * 0.20 + 6.0 * 0.07 * 0.45 * 0.20;
+        assert!((active.fees_usdc + rebates).abs() < 1e-9);
+        assert_eq!(snap.pair_ledger.residual_side, Some(Side::Yes));
+    }
+
+    #[test]
+    fn recovered_pending_fill_is_resolved_by_post_restart_confirmed() {
+        use crate::polymarket::inventory_recovery::{RecoveredFill, RecoveredPendingFill};
+
+        let (state_tx, _state_rx) = watch::channel(InventorySnapshot::default());
+        let (_fill_tx, fill_rx) = mpsc::channel(16);
+        let recovery = InventoryRecovery {
+            pending: vec![RecoveredPendingFill {
+                order_id: "order-recovered".to_string(),
+                fill: RecoveredFill {
+                    side: Side::Yes,
+                    direction: TradeDirection::Buy,
+                    size: 10.0,
+                    price: 0.40,
+                    kind: RecoveredFillKind::Fill,
+                    age_ms: 60_000,
+                    liquidity: LiquidityRole::Unknown,
+                    fee_rate_bps: None,
+                },
+            }],
+            recorder_fill_count: 1,
+            ..Default::default()
+        };
+        let mut im =
+            InventoryManager::new(InventoryConfig::default(), fill_rx, state_tx, None, None)
+                .with_recovery(&recovery);
+        assert!((im.snapshot.pending_yes_qty - 10.0).abs() < 1e-9);
+        assert!(im.snapshot.settled.yes_qty.abs() < 1e-9);
+        // The restart resets the promotion timer instead of promoting at once.
+        assert!(!im.promote_expired_pending(Instant::now()));
+
+        let mut confirmed = make_fill(Side::Yes, 10.0, 0.40);
+        confirmed.order_id = "order-recovered".to_string();
+        confirmed.status = FillStatus::Confirmed;
+        im.apply_fill(&confirmed);
+        assert!((im.snapshot.settled.yes_qty - 10.0).abs() < 1e-9);
+        assert!(im.snapshot.pending_yes_qty.abs() < 1e-9);
+        assert_eq!(im.confirmed_promotions, 1);
+    }
+
     #[test]
     fn matched_updates_working_and_sets_fragile() {
         let mut im = make_manager();
@@ -821,6 +1065,24 @@ mod tests {
         assert!(!im.snapshot.fragile);
     }
 
+    #[test]
+    fn timeout_promotion_fires_exactly_at_sim_clock_deadline() {
+        let clock = SimClock::shared(1_700_000_000_000);
+        let mut im = make_manager().with_clock(clock.clone());
+        let mut fill = make_fill(Side::No, 4.0, 0.52);
+        fill.ts = clock.now();
+        im.apply_fill(&fill);
+
+        clock.advance(PENDING_PROMOTION_TIMEOUT - Duration::from_millis(1));
+        im.promote_due_pending();
+        assert!(im.snapshot.fragile);
+
+        clock.advance(Duration::from_millis(1));
+        im.promote_due_pending();
+        assert!((im.snapshot.settled.no_qty - 4.0).abs() < 1e-9);
+        assert!(!im.snapshot.fragile);
+    }
+
     #[test]
     fn merge_sync_reduces_settled_inventory_and_keeps_vwap() {
         let mut im = make_manager();
@@ -832,6 +1094,8 @@ mod tests {
             price: 0.40,
             status: FillStatus::Confirmed,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         };
         let no = FillEvent {
@@ -842,6 +1106,8 @@ mod tests {
             price: 0.58,
             status: FillStatus::Confirmed,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         };
         im.apply_fill(&yes);
@@ -864,6 +1130,8 @@ mod tests {
             price: 0.50,
             status: FillStatus::Confirmed,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         };
         let no = FillEvent {
@@ -874,6 +1142,8 @@ mod tests {
             price: 0.41,
             status: FillStatus::Confirmed,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         };
         let pending_no = FillEvent {
@@ -884,6 +1154,8 @@ mod tests {
             price: 0.51,
             status: FillStatus::Matched,
             source: FillSource::Unknown,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
             ts: Instant::now(),
         };
 
@@ -955,6 +1227,8 @@ mod tests {
                 price: 0.48,
                 status: FillStatus::Confirmed,
                 source: FillSource::DryRunTradeSellTouch,
+                liquidity: LiquidityRole::Maker,
+                fee_rate_bps: None,
                 ts: Instant::now(),
             }))
             .await;
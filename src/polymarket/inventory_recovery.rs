@@ -0,0 +1,740 @@
+//! Startup inventory recovery.
+//!
+//! `InventoryManager` normally starts flat, so a crash mid-round forgets the
+//! YES/NO shares already held and the pair-tranche history built from them.
+//! At session start we rebuild that state for the current market:
+//!
+//! - recorder own-inventory events (`fill_snapshot` / `merge_sync` in
+//!   `<recorder_root>/<date>/<slug>/events.jsonl`) supply fill order and
+//!   prices, replayed through the same Matched → Confirmed/Failed lifecycle the
+//!   live manager uses;
+//! - the Data API positions endpoint is the source of truth for held quantity
+//!   and average cost. Any gap between the two is closed with synthetic
+//!   fills/merges so the rebuilt ledger matches the wallet.
+//!
+//! The result is seeded into the manager's settled ledger, so the pair ledger
+//! reports the open tranche and the strategy resumes completing it. Fills
+//! still Matched at the crash are seeded as pending under their order id, so
+//! the user-WS Confirmed/Failed that follows the restart resolves them instead
+//! of booking them a second time.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Context;
+use polymarket_client_sdk::data::types::request::PositionsRequest;
+use polymarket_client_sdk::data::types::MarketFilter;
+use polymarket_client_sdk::data::Client as DataClient;
+use polymarket_client_sdk::types::{Address, Decimal, B256};
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::fees::LiquidityRole;
+use super::messages::TradeDirection;
+use super::recorder::{ymd_utc, RecorderConfig};
+use super::types::Side;
+
+const RECOVERY_EPS: f64 = 1e-6;
+const DAY_MS: u64 = 86_400_000;
+
+#[derive(Debug, Clone)]
+pub struct InventoryRecoveryConfig {
+    pub enabled: bool,
+    pub data_api_url: String,
+    pub recorder_root: PathBuf,
+    /// Upper bound on the Data API round-trip at session start.
+    pub timeout: Duration,
+    /// Quantity gap (shares) below which recorder replay is trusted as-is.
+    pub qty_tolerance: f64,
+}
+
+impl Default for InventoryRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            data_api_url: "https://data-api.polymarket.com".to_string(),
+            recorder_root: PathBuf::from("data/recorder"),
+            timeout: Duration::from_millis(5_000),
+            qty_tolerance: 0.01,
+        }
+    }
+}
+
+impl InventoryRecoveryConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self {
+            recorder_root: RecorderConfig::from_env().root,
+            ..Self::default()
+        };
+        if let Ok(v) = std::env::var("PM_INVENTORY_RECOVERY_ENABLED") {
+            cfg.enabled = !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no");
+        }
+        if let Ok(v) = std::env::var("POLYMARKET_DATA_API_URL") {
+            if !v.trim().is_empty() {
+                cfg.data_api_url = v.trim().to_string();
+            }
+        }
+        if let Ok(v) = std::env::var("PM_INVENTORY_RECOVERY_TIMEOUT_MS") {
+            if let Ok(ms) = v.trim().parse::<u64>() {
+                cfg.timeout = Duration::from_millis(ms.max(100));
+            }
+        }
+        if let Ok(v) = std::env::var("PM_INVENTORY_RECOVERY_QTY_TOLERANCE") {
+            if let Ok(f) = v.trim().parse::<f64>() {
+                if f.is_finite() && f >= 0.0 {
+                    cfg.qty_tolerance = f;
+                }
+            }
+        }
+        cfg
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveredFillKind {
+    Fill,
+    /// Full-set merge: `size` YES and `size` NO returned for collateral.
+    Merge,
+}
+
+/// One settled inventory change to re-apply at startup. `size` is positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecoveredFill {
+    pub side: Side,
+    pub direction: TradeDirection,
+    pub size: f64,
+    pub price: f64,
+    pub kind: RecoveredFillKind,
+    /// Age of the original event; 0 for synthetic reconciliation entries.
+    pub age_ms: u64,
+    /// Role and venue rate recorded with the fill, so the manager can re-quote
+    /// its fee; synthetic entries are `Unknown` and charged as taker.
+    pub liquidity: LiquidityRole,
+    pub fee_rate_bps: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HeldSide {
+    pub qty: f64,
+    pub avg_price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HeldPositions {
+    pub yes: HeldSide,
+    pub no: HeldSide,
+}
+
+impl HeldPositions {
+    fn side(&self, side: Side) -> HeldSide {
+        match side {
+            Side::Yes => self.yes,
+            Side::No => self.no,
+        }
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut HeldSide {
+        match side {
+            Side::Yes => &mut self.yes,
+            Side::No => &mut self.no,
+        }
+    }
+
+    /// Net holdings implied by replaying `fills` in order (weighted-average cost,
+    /// sells and merges reduce quantity without touching the average).
+    pub fn from_fills(fills: &[RecoveredFill]) -> Self {
+        let mut held = Self::default();
+        for fill in fills {
+            match fill.kind {
+                RecoveredFillKind::Merge => {
+                    let amount = fill.size.min(held.yes.qty).min(held.no.qty).max(0.0);
+                    for side in [Side::Yes, Side::No] {
+                        reduce_side(held.side_mut(side), amount);
+                    }
+                }
+                RecoveredFillKind::Fill => {
+                    let entry = held.side_mut(fill.side);
+                    match fill.direction {
+                        TradeDirection::Buy => {
+                            let next = entry.qty + fill.size;
+                            if next > RECOVERY_EPS {
+                                entry.avg_price =
+                                    (entry.qty * entry.avg_price + fill.size * fill.price) / next;
+                                entry.qty = next;
+                            }
+                        }
+                        TradeDirection::Sell => reduce_side(entry, fill.size),
+                    }
+                }
+            }
+        }
+        held
+    }
+}
+
+fn with_pending(fills: &[RecoveredFill], pending: &[RecoveredPendingFill]) -> Vec<RecoveredFill> {
+    fills
+        .iter()
+        .copied()
+        .chain(pending.iter().map(|p| p.fill))
+        .collect()
+}
+
+fn reduce_side(entry: &mut HeldSide, qty: f64) {
+    entry.qty = (entry.qty - qty).max(0.0);
+    if entry.qty <= RECOVERY_EPS {
+        *entry = HeldSide::default();
+    }
+}
+
+/// A Matched fill with no Confirmed/Failed in the recorder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveredPendingFill {
+    pub order_id: String,
+    pub fill: RecoveredFill,
+}
+
+/// Recorder replay split by finality.
+#[derive(Debug, Clone, Default)]
+pub struct RecorderReplay {
+    pub settled: Vec<RecoveredFill>,
+    pub pending: Vec<RecoveredPendingFill>,
+}
+
+/// Outcome of startup recovery for one market.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryRecovery {
+    pub fills: Vec<RecoveredFill>,
+    /// Matched-only fills, re-armed as pending in the manager.
+    pub pending: Vec<RecoveredPendingFill>,
+    pub recorder_fill_count: usize,
+    pub synthetic_fill_count: usize,
+    /// Data API holdings, when the lookup succeeded.
+    pub api_positions: Option<HeldPositions>,
+}
+
+impl InventoryRecovery {
+    pub fn is_empty(&self) -> bool {
+        self.fills.is_empty() && self.pending.is_empty()
+    }
+
+    /// Holdings including pending fills (the working view).
+    pub fn held(&self) -> HeldPositions {
+        HeldPositions::from_fills(&with_pending(&self.fills, &self.pending))
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Recorder replay
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+struct PendingReplayFill {
+    order_id: String,
+    side: Side,
+    direction: TradeDirection,
+    size: f64,
+    price: f64,
+    liquidity: LiquidityRole,
+    fee_rate_bps: Option<u32>,
+    recv_unix_ms: u64,
+}
+
+/// Rebuild fills for `slug` from recorder own-inventory events.
+///
+/// Reads yesterday's and today's (UTC) event files so rounds straddling
+/// midnight are covered. Matched fills that never saw Confirmed/Failed are
+/// returned as pending, keyed by order id.
+pub fn replay_recorder_fills(root: &Path, slug: &str, now_unix_ms: u64) -> RecorderReplay {
+    let mut dates = vec![
+        ymd_utc(now_unix_ms.saturating_sub(DAY_MS)),
+        ymd_utc(now_unix_ms),
+    ];
+    dates.dedup();
+
+    let mut settled: Vec<(u64, RecoveredFill)> = Vec::new();
+    let mut pending: VecDeque<PendingReplayFill> = VecDeque::new();
+    for date in dates {
+        let path = root.join(date).join(slug).join("events.jsonl");
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for line in text.lines() {
+            let Ok(row) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            replay_row(&row, now_unix_ms, &mut pending, &mut settled);
+        }
+    }
+    settled.sort_by_key(|(recv_unix_ms, _)| *recv_unix_ms);
+    RecorderReplay {
+        settled: settled.into_iter().map(|(_, fill)| fill).collect(),
+        pending: pending
+            .into_iter()
+            .map(|fill| RecoveredPendingFill {
+                fill: settled_fill(&fill, now_unix_ms, fill.recv_unix_ms),
+                order_id: fill.order_id,
+            })
+            .collect(),
+    }
+}
+
+fn replay_row(
+    row: &Value,
+    now_unix_ms: u64,
+    pending: &mut VecDeque<PendingReplayFill>,
+    settled: &mut Vec<(u64, RecoveredFill)>,
+) {
+    let recv_unix_ms = row["recv_unix_ms"].as_u64().unwrap_or(now_unix_ms);
+    let data = &row["payload"]["data"];
+    match row["payload"]["event"].as_str() {
+        Some("fill_snapshot") => {
+            let (Some(side), Some(direction), Some(size), Some(price), Some(status)) = (
+                data["side"].as_str().and_then(parse_side),
+                data["direction"].as_str().and_then(parse_direction),
+                data["size"].as_f64().filter(|v| *v > RECOVERY_EPS),
+                data["price"].as_f64(),
+                data["status"].as_str(),
+            ) else {
+                return;
+            };
+            let fill = PendingReplayFill {
+                order_id: data["order_id"].as_str().unwrap_or_default().to_string(),
+                side,
+                direction,
+                size,
+                price,
+                liquidity: data["liquidity"]
+                    .as_str()
+                    .map_or(LiquidityRole::Unknown, LiquidityRole::parse),
+                fee_rate_bps: data["fee_rate_bps"]
+                    .as_u64()
+                    .map(|bps| bps.min(u32::MAX as u64) as u32),
+                recv_unix_ms,
+            };
+            let position = pending.iter().position(|p| {
+                p.order_id == fill.order_id && p.side == side && p.direction == direction
+            });
+            match status {
+                "Matched" => pending.push_back(fill),
+                "Confirmed" => {
+                    let matched_at = match position.and_then(|idx| pending.remove(idx)) {
+                        Some(prior) => prior.recv_unix_ms,
+                        None => recv_unix_ms,
+                    };
+                    settled.push((matched_at, settled_fill(&fill, now_unix_ms, matched_at)));
+                }
+                "Failed" => {
+                    if let Some(idx) = position {
+                        pending.remove(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some("merge_sync") => {
+            let Some(size) = data["full_set_size"].as_f64().filter(|v| *v > RECOVERY_EPS) else {
+                return;
+            };
+            // The live manager materializes pending fills before merging.
+            for fill in pending.drain(..) {
+                settled.push((
+                    fill.recv_unix_ms,
+                    settled_fill(&fill, now_unix_ms, fill.recv_unix_ms),
+                ));
+            }
+            settled.push((
+                recv_unix_ms,
+                RecoveredFill {
+                    side: Side::Yes,
+                    direction: TradeDirection::Sell,
+                    size,
+                    price: 0.0,
+                    kind: RecoveredFillKind::Merge,
+                    age_ms: now_unix_ms.saturating_sub(recv_unix_ms),
+                    liquidity: LiquidityRole::Unknown,
+                    fee_rate_bps: None,
+                },
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn settled_fill(fill: &PendingReplayFill, now_unix_ms: u64, recv_unix_ms: u64) -> RecoveredFill {
+    RecoveredFill {
+        side: fill.side,
+        direction: fill.direction,
+        size: fill.size,
+        price: fill.price.max(0.0),
+        kind: RecoveredFillKind::Fill,
+        age_ms: now_unix_ms.saturating_sub(recv_unix_ms),
+        liquidity: fill.liquidity,
+        fee_rate_bps: fill.fee_rate_bps,
+    }
+}
+
+fn parse_side(raw: &str) -> Option<Side> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "yes" | "up" => Some(Side::Yes),
+        "no" | "down" => Some(Side::No),
+        _ => None,
+    }
+}
+
+fn parse_direction(raw: &str) -> Option<TradeDirection> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "buy" => Some(TradeDirection::Buy),
+        "sell" => Some(TradeDirection::Sell),
+        _ => None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Data API positions + reconciliation
+// ─────────────────────────────────────────────────────────
+
+/// Held YES/NO shares and average entry price for one market.
+pub async fn fetch_held_positions(
+    data_api_url: &str,
+    user: Address,
+    condition_id: B256,
+) -> anyhow::Result<HeldPositions> {
+    let client = DataClient::new(data_api_url)
+        .with_context(|| format!("invalid data api url: {data_api_url}"))?;
+    let req = PositionsRequest::builder()
+        .user(user)
+        .filter(MarketFilter::markets([condition_id]))
+        .limit(50)?
+        .build();
+    let rows = client.positions(&req).await?;
+
+    let mut yes = (Decimal::ZERO, Decimal::ZERO);
+    let mut no = (Decimal::ZERO, Decimal::ZERO);
+    for row in rows {
+        let outcome = row.outcome.trim().to_ascii_lowercase();
+        let entry = if outcome == "yes" || outcome == "up" || row.outcome_index == 0 {
+            &mut yes
+        } else if outcome == "no" || outcome == "down" || row.outcome_index == 1 {
+            &mut no
+        } else {
+            continue;
+        };
+        entry.0 += row.size;
+        entry.1 += row.size * row.avg_price;
+    }
+    let to_side = |(qty, notional): (Decimal, Decimal)| {
+        let qty = qty.to_f64().unwrap_or(0.0).max(0.0);
+        let notional = notional.to_f64().unwrap_or(0.0).max(0.0);
+        HeldSide {
+            qty,
+            avg_price: if qty > RECOVERY_EPS {
+                notional / qty
+            } else {
+                0.0
+            },
+        }
+    };
+    Ok(HeldPositions {
+        yes: to_side(yes),
+        no: to_side(no),
+    })
+}
+
+/// Close the gap between replayed fills and wallet holdings. `pending` counts
+/// towards the replayed side: the Data API already reports matched trades.
+///
+/// Shortfalls on both sides become a full-set merge, a one-sided shortfall a
+/// sell at the replayed average. Surpluses become buys priced so the side's
+/// average matches the Data API; they are appended larger-first so that with
+/// no recorder history the dominant side opens the tranche and the smaller
+/// side hedges it.
+pub fn reconcile_with_positions(
+    mut fills: Vec<RecoveredFill>,
+    pending: &[RecoveredPendingFill],
+    api: HeldPositions,
+    qty_tolerance: f64,
+) -> (Vec<RecoveredFill>, usize) {
+    let replayed = HeldPositions::from_fills(&with_pending(&fills, pending));
+    let tol = qty_tolerance.max(RECOVERY_EPS);
+    let mut gap_yes = api.yes.qty - replayed.yes.qty;
+    let mut gap_no = api.no.qty - replayed.no.qty;
+    let synthetic_start = fills.len();
+
+    let merged = (-gap_yes).min(-gap_no);
+    if merged > tol {
+        fills.push(RecoveredFill {
+            side: Side::Yes,
+            direction: TradeDirection::Sell,
+            size: merged,
+            price: 0.0,
+            kind: RecoveredFillKind::Merge,
+            age_ms: 0,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
+        });
+        gap_yes += merged;
+        gap_no += merged;
+    }
+
+    let mut top_ups = Vec::new();
+    for (side, gap) in [(Side::Yes, gap_yes), (Side::No, gap_no)] {
+        if gap < -tol {
+            fills.push(RecoveredFill {
+                side,
+                direction: TradeDirection::Sell,
+                size: -gap,
+                price: replayed.side(side).avg_price,
+                kind: RecoveredFillKind::Fill,
+                age_ms: 0,
+                liquidity: LiquidityRole::Unknown,
+                fee_rate_bps: None,
+            });
+        } else if gap > tol {
+            let held = api.side(side);
+            let prior = replayed.side(side);
+            let implied = (held.qty * held.avg_price - prior.qty * prior.avg_price) / gap;
+            let price = if implied.is_finite() && implied > 0.0 && implied < 1.0 {
+                implied
+            } else {
+                held.avg_price
+            };
+            top_ups.push((side, gap, price));
+        }
+    }
+    top_ups.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (side, size, price) in top_ups {
+        fills.push(RecoveredFill {
+            side,
+            direction: TradeDirection::Buy,
+            size,
+            price,
+            kind: RecoveredFillKind::Fill,
+            age_ms: 0,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
+        });
+    }
+    let synthetic = fills.len() - synthetic_start;
+    (fills, synthetic)
+}
+
+/// Recover settled inventory for one market at session start.
+///
+/// `funder_address` is the wallet holding positions; pass `None` (e.g. in
+/// dry-run, or after the first round of a process) to rely on the recorder
+/// alone. Data API failures degrade to the
+/// recorder replay with a warning.
+pub async fn recover_inventory(
+    cfg: &InventoryRecoveryConfig,
+    slug: &str,
+    condition_id: &str,
+    funder_address: Option<&str>,
+    now_unix_ms: u64,
+) -> InventoryRecovery {
+    if !cfg.enabled {
+        return InventoryRecovery::default();
+    }
+    let RecorderReplay {
+        settled: fills,
+        pending,
+    } = replay_recorder_fills(&cfg.recorder_root, slug, now_unix_ms);
+    let recorder_fill_count = fills.len() + pending.len();
+
+    let wallet = funder_address.and_then(|raw| raw.trim().parse::<Address>().ok());
+    let condition = condition_id.trim().parse::<B256>().ok();
+    let api_positions = match (wallet, condition) {
+        (Some(user), Some(condition)) => match tokio::time::timeout(
+            cfg.timeout,
+            fetch_held_positions(&cfg.data_api_url, user, condition),
+        )
+        .await
+        {
+            Ok(Ok(held)) => Some(held),
+            Ok(Err(e)) => {
+                warn!(
+                    "⚠️ inventory recovery: data api positions failed for {}: {:?}",
+                    slug, e
+                );
+                None
+            }
+            Err(_) => {
+                warn!(
+                    "⚠️ inventory recovery: data api positions timed out after {}ms for {}",
+                    cfg.timeout.as_millis(),
+                    slug
+                );
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let (fills, synthetic_fill_count) = match api_positions {
+        Some(api) => reconcile_with_positions(fills, &pending, api, cfg.qty_tolerance),
+        None => (fills, 0),
+    };
+    let recovery = InventoryRecovery {
+        fills,
+        pending,
+        recorder_fill_count,
+        synthetic_fill_count,
+        api_positions,
+    };
+    if !recovery.is_empty() {
+        let held = recovery.held();
+        info!(
+            "♻️ inventory recovery | slug={} recorder_fills={} pending={} synthetic={} api={} → YES={:.2}@{:.4} NO={:.2}@{:.4}",
+            slug,
+            recovery.recorder_fill_count,
+            recovery.pending.len(),
+            recovery.synthetic_fill_count,
+            recovery.api_positions.is_some(),
+            held.yes.qty,
+            held.yes.avg_price,
+            held.no.qty,
+            held.no.avg_price,
+        );
+    }
+    recovery
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_root(prefix: &str) -> PathBuf {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("pm_as_ofi_{}_{}", prefix, ts))
+    }
+
+    fn fill_row(recv_unix_ms: u64, order_id: &str, side: &str, size: f64, status: &str) -> Value {
+        json!({
+            "recv_unix_ms": recv_unix_ms,
+            "stream": "events",
+            "payload": {
+                "event": "fill_snapshot",
+                "data": {
+                    "side": side,
+                    "direction": "Buy",
+                    "size": size,
+                    "price": if side == "Yes" { 0.40 } else { 0.55 },
+                    "status": status,
+                    "liquidity": "maker",
+                    "order_id": order_id,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn replay_follows_fill_lifecycle_and_merges() {
+        let root = temp_root("inventory_recovery");
+        let now_ms = 1_746_000_000_000_u64;
+        let slug = "btc-updown-5m-1746000000";
+        let dir = root.join(ymd_utc(now_ms)).join(slug);
+        fs::create_dir_all(&dir).expect("create recorder dir");
+        let rows = [
+            fill_row(now_ms - 9_000, "a", "Yes", 10.0, "Matched"),
+            fill_row(now_ms - 8_000, "b", "No", 4.0, "Matched"),
+            fill_row(now_ms - 7_000, "b", "No", 4.0, "Failed"),
+            fill_row(now_ms - 6_000, "a", "Yes", 10.0, "Confirmed"),
+            fill_row(now_ms - 5_000, "c", "No", 6.0, "Confirmed"),
+            json!({
+                "recv_unix_ms": now_ms - 4_000,
+                "payload": {"event": "merge_sync", "data": {"full_set_size": 2.0}},
+            }),
+            fill_row(now_ms - 3_000, "d", "Yes", 1.0, "Matched"),
+        ];
+        let text = rows
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(dir.join("events.jsonl"), text).expect("write events");
+
+        let replay = replay_recorder_fills(&root, slug, now_ms);
+        let fills = &replay.settled;
+        let held = HeldPositions::from_fills(fills);
+        assert_eq!(fills.len(), 3);
+        assert_eq!(fills[0].side, Side::Yes);
+        assert_eq!(fills[0].age_ms, 9_000);
+        assert_eq!(fills[0].liquidity, LiquidityRole::Maker);
+        assert_eq!(fills[0].fee_rate_bps, None);
+        assert_eq!(fills[2].kind, RecoveredFillKind::Merge);
+        assert!((held.yes.qty - 8.0).abs() < 1e-9);
+        assert!((held.no.qty - 4.0).abs() < 1e-9);
+        assert!((held.no.avg_price - 0.55).abs() < 1e-9);
+        // The trailing Matched-only fill stays pending under its order id.
+        assert_eq!(replay.pending.len(), 1);
+        assert_eq!(replay.pending[0].order_id, "d");
+        assert!((replay.pending[0].fill.size - 1.0).abs() < 1e-9);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn reconcile_seeds_dominant_side_first_and_matches_api_average() {
+        let api = HeldPositions {
+            yes: HeldSide {
+                qty: 6.0,
+                avg_price: 0.50,
+            },
+            no: HeldSide {
+                qty: 10.0,
+                avg_price: 0.45,
+            },
+        };
+        let (fills, synthetic) = reconcile_with_positions(Vec::new(), &[], api, 0.01);
+        assert_eq!(synthetic, 2);
+        assert_eq!(fills[0].side, Side::No);
+        assert_eq!(fills[1].side, Side::Yes);
+        let held = HeldPositions::from_fills(&fills);
+        assert!((held.no.qty - 10.0).abs() < 1e-9);
+        assert!((held.no.avg_price - 0.45).abs() < 1e-9);
+        assert!((held.yes.avg_price - 0.50).abs() < 1e-9);
+
+        let replayed = vec![RecoveredFill {
+            side: Side::Yes,
+            direction: TradeDirection::Buy,
+            size: 4.0,
+            price: 0.40,
+            kind: RecoveredFillKind::Fill,
+            age_ms: 1_000,
+            liquidity: LiquidityRole::Unknown,
+            fee_rate_bps: None,
+        }];
+        let (fills, synthetic) = reconcile_with_positions(replayed.clone(), &[], api, 0.01);
+        assert_eq!(synthetic, 2);
+        let held = HeldPositions::from_fills(&fills);
+        assert!((held.yes.qty - 6.0).abs() < 1e-9);
+        assert!((held.yes.avg_price - 0.50).abs() < 1e-9);
+        assert!((held.no.qty - 10.0).abs() < 1e-9);
+
+        // A pending NO fill already covers part of the wallet's NO holdings.
+        let pending = [RecoveredPendingFill {
+            order_id: "p".to_string(),
+            fill: RecoveredFill {
+                side: Side::No,
+                direction: TradeDirection::Buy,
+                size: 10.0,
+                price: 0.45,
+                kind: RecoveredFillKind::Fill,
+                age_ms: 500,
+                liquidity: LiquidityRole::Unknown,
+                fee_rate_bps: None,
+            },
+        }];
+        let (fills, synthetic) = reconcile_with_positions(replayed, &pending, api, 0.01);
+        assert_eq!(synthetic, 1);
+        assert_eq!(fills.last().map(|fill| fill.side), Some(Side::Yes));
+    }
+}
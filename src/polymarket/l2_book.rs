@@ -0,0 +1,541 @@
+//! Full-depth L2 order book assembled from market WS frames.
+//!
+//! The market feed only forwards top-of-book (`BookTick`) to the actors. This
+//! module keeps the whole per-token price ladder instead:
+//! - `book` frames replace a token's ladder (snapshot semantics).
+//! - `price_change` frames set the aggregate size at one price level; a zero
+//!   size removes the level. Levels that cross the frame's `best_bid` /
+//!   `best_ask` are pruned so a missed delta cannot leave a stale crossed level.
+//!
+//! `L2BookAssembler` is a plain state machine; the WS loop publishes
+//! `L2BookSnapshot`s over a watch channel (`StrategyCoordinator::with_l2_book_rx`)
+//! and strategies read depth from `StrategyTickInput::l2_book`. Only direct
+//! market WS connections feed it; shared-ingress clients see an empty ladder and
+//! keep the `PM_L2_DEPTH_TAPE_PATH` fallback.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::strategy::L2BookDepth;
+use super::types::Side;
+
+/// Price keys are stored in 1e-4 units (finest Polymarket tick).
+const PRICE_KEY_SCALE: f64 = 10_000.0;
+/// Levels per book side carried in published snapshots.
+pub const L2_SNAPSHOT_MAX_LEVELS: usize = 32;
+/// Number of best levels summed for `L2BookDepth` (matches the depth tape).
+const DEPTH_TAPE_LEVELS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+fn price_key(price: f64) -> Option<u32> {
+    (price.is_finite() && price > 0.0 && price < 1.0)
+        .then(|| (price * PRICE_KEY_SCALE).round() as u32)
+}
+
+fn key_price(key: u32) -> f64 {
+    key as f64 / PRICE_KEY_SCALE
+}
+
+/// Mutable price ladder for one token.
+#[derive(Debug, Clone, Default)]
+pub struct L2Ladder {
+    bids: BTreeMap<u32, f64>,
+    asks: BTreeMap<u32, f64>,
+}
+
+impl L2Ladder {
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// Replace the ladder with a full snapshot. Input order does not matter.
+    pub fn apply_snapshot(&mut self, bids: &[PriceLevel], asks: &[PriceLevel]) {
+        self.clear();
+        for lvl in bids {
+            self.apply_change(true, lvl.price, lvl.size);
+        }
+        for lvl in asks {
+            self.apply_change(false, lvl.price, lvl.size);
+        }
+    }
+
+    /// Set the aggregate size at `price` on the bid (`is_bid`) or ask side;
+    /// a zero size removes the level.
+    pub fn apply_change(&mut self, is_bid: bool, price: f64, size: f64) {
+        let Some(key) = price_key(price) else {
+            return;
+        };
+        let levels = if is_bid {
+            &mut self.bids
+        } else {
+            &mut self.asks
+        };
+        if size.is_finite() && size > 0.0 {
+            levels.insert(key, size);
+        } else {
+            levels.remove(&key);
+        }
+    }
+
+    /// Drop bids above `best_bid` and asks below `best_ask` (0.0 = unknown).
+    pub fn prune_crossed(&mut self, best_bid: f64, best_ask: f64) {
+        if let Some(key) = price_key(best_bid) {
+            self.bids.retain(|k, _| *k <= key);
+        }
+        if let Some(key) = price_key(best_ask) {
+            self.asks.retain(|k, _| *k >= key);
+        }
+    }
+
+    /// Best-first copy of the ladder, capped at `max_levels` per side.
+    pub fn to_side_book(&self, max_levels: usize) -> L2SideBook {
+        let level = |(k, s): (&u32, &f64)| PriceLevel {
+            price: key_price(*k),
+            size: *s,
+        };
+        L2SideBook {
+            bids: self.bids.iter().rev().take(max_levels).map(level).collect(),
+            asks: self.asks.iter().take(max_levels).map(level).collect(),
+        }
+    }
+}
+
+/// Immutable best-first view of one token's book.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct L2SideBook {
+    /// Descending by price.
+    pub bids: Vec<PriceLevel>,
+    /// Ascending by price.
+    pub asks: Vec<PriceLevel>,
+}
+
+impl L2SideBook {
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.first().copied()
+    }
+
+    /// Bid size resting exactly at `price` (0.0 when no level there).
+    pub fn bid_size_at(&self, price: f64) -> f64 {
+        self.bids
+            .iter()
+            .find(|l| (l.price - price).abs() <= 1e-9)
+            .map_or(0.0, |l| l.size)
+    }
+
+    /// Ask size resting exactly at `price` (0.0 when no level there).
+    pub fn ask_size_at(&self, price: f64) -> f64 {
+        self.asks
+            .iter()
+            .find(|l| (l.price - price).abs() <= 1e-9)
+            .map_or(0.0, |l| l.size)
+    }
+
+    /// Size resting at `price` on the bid (`bid_side`) or ask ladder; `None`
+    /// while that ladder is empty, so an unseen side never reads as 0.
+    pub fn visible_size_at(&self, bid_side: bool, price: f64) -> Option<f64> {
+        if bid_side {
+            (!self.bids.is_empty()).then(|| self.bid_size_at(price))
+        } else {
+            (!self.asks.is_empty()).then(|| self.ask_size_at(price))
+        }
+    }
+
+    /// Total size on the best `levels` bid levels.
+    pub fn bid_depth(&self, levels: usize) -> f64 {
+        self.bids.iter().take(levels).map(|l| l.size).sum()
+    }
+
+    /// Total size on the best `levels` ask levels.
+    pub fn ask_depth(&self, levels: usize) -> f64 {
+        self.asks.iter().take(levels).map(|l| l.size).sum()
+    }
+
+    /// Bid size resting at or above `price` (what a sell down to `price` can hit).
+    pub fn bid_depth_to_price(&self, price: f64) -> f64 {
+        self.bids
+            .iter()
+            .take_while(|l| l.price >= price - 1e-9)
+            .map(|l| l.size)
+            .sum()
+    }
+
+    /// Ask size resting at or below `price` (what a buy up to `price` can lift).
+    pub fn ask_depth_to_price(&self, price: f64) -> f64 {
+        self.asks
+            .iter()
+            .take_while(|l| l.price <= price + 1e-9)
+            .map(|l| l.size)
+            .sum()
+    }
+
+    /// Worst ask price a buy of `qty` has to reach; `None` if the visible
+    /// ladder is too thin.
+    pub fn ask_sweep_price(&self, qty: f64) -> Option<f64> {
+        let mut filled = 0.0;
+        for lvl in &self.asks {
+            filled += lvl.size;
+            if filled + 1e-9 >= qty {
+                return Some(lvl.price);
+            }
+        }
+        None
+    }
+}
+
+/// Published L2 state for both outcome tokens.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct L2BookSnapshot {
+    pub yes: L2SideBook,
+    pub no: L2SideBook,
+    /// When the assembler last applied a frame (`None` = never).
+    pub updated_at: Option<Instant>,
+}
+
+impl L2BookSnapshot {
+    pub fn side(&self, side: Side) -> &L2SideBook {
+        match side {
+            Side::Yes => &self.yes,
+            Side::No => &self.no,
+        }
+    }
+
+    /// True once both tokens have at least one level.
+    pub fn is_complete(&self) -> bool {
+        !self.yes.is_empty() && !self.no.is_empty()
+    }
+
+    /// True when a frame was applied within `max_age` of `now`. A dropped WS
+    /// session leaves the last ladder published, so readers must check this.
+    pub fn is_fresh(&self, now: Instant, max_age: Duration) -> bool {
+        self.updated_at
+            .is_some_and(|at| now.saturating_duration_since(at) <= max_age)
+    }
+
+    /// 5-level depth in the depth-tape shape, taking the thinner token per
+    /// book side. `None` until both tokens have been seen.
+    pub(crate) fn depth_5lvl(&self) -> Option<L2BookDepth> {
+        self.is_complete().then(|| L2BookDepth {
+            bid_depth_5lvl: self
+                .yes
+                .bid_depth(DEPTH_TAPE_LEVELS)
+                .min(self.no.bid_depth(DEPTH_TAPE_LEVELS)),
+            ask_depth_5lvl: self
+                .yes
+                .ask_depth(DEPTH_TAPE_LEVELS)
+                .min(self.no.ask_depth(DEPTH_TAPE_LEVELS)),
+        })
+    }
+}
+
+fn value_f64(v: &Value) -> Option<f64> {
+    v.as_f64()
+        .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+}
+
+fn parse_levels(value: &Value, key: &str, alt_key: &str) -> Vec<PriceLevel> {
+    value
+        .get(key)
+        .or_else(|| value.get(alt_key))
+        .and_then(Value::as_array)
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|l| {
+                    Some(PriceLevel {
+                        price: value_f64(&l["price"])?,
+                        size: value_f64(&l["size"])?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds per-token ladders from raw market WS frames for one YES/NO pair.
+/// Recreate (or `reset`) on reconnect: the first `book` frame re-seeds it.
+#[derive(Debug, Clone)]
+pub struct L2BookAssembler {
+    yes_asset_id: String,
+    no_asset_id: String,
+    yes: L2Ladder,
+    no: L2Ladder,
+    updated_at: Option<Instant>,
+}
+
+impl L2BookAssembler {
+    pub fn new(yes_asset_id: impl Into<String>, no_asset_id: impl Into<String>) -> Self {
+        Self {
+            yes_asset_id: yes_asset_id.into(),
+            no_asset_id: no_asset_id.into(),
+            yes: L2Ladder::default(),
+            no: L2Ladder::default(),
+            updated_at: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.yes.clear();
+        self.no.clear();
+        self.updated_at = None;
+    }
+
+    fn ladder_mut(&mut self, asset_id: &str) -> Option<&mut L2Ladder> {
+        if asset_id == self.yes_asset_id {
+            Some(&mut self.yes)
+        } else if asset_id == self.no_asset_id {
+            Some(&mut self.no)
+        } else {
+            None
+        }
+    }
+
+    /// Apply one raw WS text payload (object or array of objects).
+    /// Returns true when a ladder changed.
+    pub fn apply_ws_text(&mut self, text: &str, now: Instant) -> bool {
+        serde_json::from_str::<Value>(text)
+            .map(|value| self.apply_ws_value(&value, now))
+            .unwrap_or(false)
+    }
+
+    pub fn apply_ws_value(&mut self, value: &Value, now: Instant) -> bool {
+        if let Some(items) = value.as_array() {
+            let mut touched = false;
+            for item in items {
+                touched |= self.apply_ws_value(item, now);
+            }
+            return touched;
+        }
+        let touched = match value["event_type"].as_str() {
+            Some("book") => {
+                let bids = parse_levels(value, "bids", "buys");
+                let asks = parse_levels(value, "asks", "sells");
+                match value["asset_id"]
+                    .as_str()
+                    .and_then(|id| self.ladder_mut(id))
+                {
+                    Some(ladder) => {
+                        ladder.apply_snapshot(&bids, &asks);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Some("price_change") => {
+                let mut touched = false;
+                // Current schema: per-asset entries in `price_changes`.
+                for ch in value["price_changes"].as_array().into_iter().flatten() {
+                    touched |= self.apply_level_change(&ch["asset_id"], ch);
+                }
+                // Legacy schema: top-level `asset_id` + `changes`.
+                for ch in value["changes"].as_array().into_iter().flatten() {
+                    touched |= self.apply_level_change(&value["asset_id"], ch);
+                }
+                touched
+            }
+            _ => false,
+        };
+        if touched {
+            self.updated_at = Some(now);
+        }
+        touched
+    }
+
+    fn apply_level_change(&mut self, asset_id: &Value, ch: &Value) -> bool {
+        let Some(ladder) = asset_id.as_str().and_then(|id| self.ladder_mut(id)) else {
+            return false;
+        };
+        let is_bid = match ch["side"].as_str() {
+            Some(s) if s.eq_ignore_ascii_case("buy") => true,
+            Some(s) if s.eq_ignore_ascii_case("sell") => false,
+            _ => return false,
+        };
+        let (Some(price), Some(size)) = (value_f64(&ch["price"]), value_f64(&ch["size"])) else {
+            return false;
+        };
+        ladder.apply_change(is_bid, price, size);
+        ladder.prune_crossed(
+            value_f64(&ch["best_bid"]).unwrap_or(0.0),
+            value_f64(&ch["best_ask"]).unwrap_or(0.0),
+        );
+        true
+    }
+
+    pub fn snapshot(&self) -> L2BookSnapshot {
+        L2BookSnapshot {
+            yes: self.yes.to_side_book(L2_SNAPSHOT_MAX_LEVELS),
+            no: self.no.to_side_book(L2_SNAPSHOT_MAX_LEVELS),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assembler() -> L2BookAssembler {
+        L2BookAssembler::new("yes-asset", "no-asset")
+    }
+
+    #[test]
+    fn book_snapshot_then_price_change_maintains_sorted_ladder() {
+        let mut asm = assembler();
+        let now = Instant::now();
+        assert!(asm.apply_ws_text(
+            r#"{"event_type":"book","asset_id":"yes-asset","bids":[{"price":"0.48","size":"5"},{"price":"0.50","size":"3"}],"asks":[{"price":"0.53","size":"2"},{"price":"0.52","size":"9"}]}"#,
+            now,
+        ));
+        let snap = asm.snapshot();
+        assert_eq!(
+            snap.yes.best_bid(),
+            Some(PriceLevel {
+                price: 0.50,
+                size: 3.0
+            })
+        );
+        assert_eq!(
+            snap.yes.best_ask(),
+            Some(PriceLevel {
+                price: 0.52,
+                size: 9.0
+            })
+        );
+        assert!(snap.no.is_empty());
+        assert_eq!(snap.depth_5lvl(), None);
+
+        // Update one level, add another, remove the best bid.
+        assert!(asm.apply_ws_text(
+            r#"{"event_type":"price_change","price_changes":[
+                {"asset_id":"yes-asset","price":"0.52","size":"4","side":"SELL","best_bid":"0.49","best_ask":"0.52"},
+                {"asset_id":"yes-asset","price":"0.49","size":"7","side":"BUY","best_bid":"0.49","best_ask":"0.52"},
+                {"asset_id":"yes-asset","price":"0.50","size":"0","side":"BUY","best_bid":"0.49","best_ask":"0.52"}
+            ]}"#,
+            now,
+        ));
+        let snap = asm.snapshot();
+        let bid_prices: Vec<f64> = snap.yes.bids.iter().map(|l| l.price).collect();
+        assert_eq!(bid_prices, vec![0.49, 0.48]);
+        assert_eq!(snap.yes.bid_depth(5), 12.0);
+        assert_eq!(snap.yes.ask_depth_to_price(0.525), 4.0);
+        assert_eq!(snap.yes.ask_sweep_price(5.0), Some(0.53));
+        assert_eq!(snap.yes.ask_sweep_price(7.0), None);
+        assert_eq!(snap.updated_at, Some(now));
+    }
+
+    #[test]
+    fn price_change_prunes_levels_crossing_reported_touch() {
+        let mut ladder = L2Ladder::default();
+        ladder.apply_snapshot(
+            &[
+                PriceLevel {
+                    price: 0.47,
+                    size: 10.0,
+                },
+                PriceLevel {
+                    price: 0.46,
+                    size: 1.0,
+                },
+            ],
+            &[
+                PriceLevel {
+                    price: 0.49,
+                    size: 10.0,
+                },
+                PriceLevel {
+                    price: 0.51,
+                    size: 1.0,
+                },
+            ],
+        );
+        // A missed delta left 0.47 bid / 0.49 ask behind; the next frame reports
+        // touch at 0.46 / 0.50.
+        ladder.apply_change(false, 0.50, 2.0);
+        ladder.prune_crossed(0.46, 0.50);
+        let book = ladder.to_side_book(L2_SNAPSHOT_MAX_LEVELS);
+        assert_eq!(book.best_bid().map(|l| l.price), Some(0.46));
+        assert_eq!(book.best_ask().map(|l| l.price), Some(0.50));
+        assert_eq!(book.ask_depth(5), 3.0);
+    }
+
+    #[test]
+    fn depth_5lvl_uses_thinner_token_and_ignores_foreign_assets() {
+        let mut asm = assembler();
+        let now = Instant::now();
+        let levels = |side: &str, n: usize, size: f64| {
+            (0..n)
+                .map(|i| {
+                    let px = if side == "bids" {
+                        0.40 - i as f64 * 0.01
+                    } else {
+                        0.60 + i as f64 * 0.01
+                    };
+                    format!(r#"{{"price":"{px:.2}","size":"{size}"}}"#)
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let frame = |asset: &str, size: f64| {
+            format!(
+                r#"{{"event_type":"book","asset_id":"{asset}","bids":[{}],"asks":[{}]}}"#,
+                levels("bids", 7, size),
+                levels("asks", 7, size)
+            )
+        };
+        assert!(!asm.apply_ws_text(&frame("other-asset", 1.0), now));
+        assert!(asm.apply_ws_text(
+            &format!(
+                "[{},{}]",
+                frame("yes-asset", 100.0),
+                frame("no-asset", 20.0)
+            ),
+            now,
+        ));
+        let depth = asm.snapshot().depth_5lvl().expect("both tokens seen");
+        assert_eq!(depth.bid_depth_5lvl, 100.0);
+        assert_eq!(depth.ask_depth_5lvl, 100.0);
+
+        asm.reset();
+        assert!(asm.snapshot().yes.is_empty());
+    }
+
+    #[test]
+    fn snapshot_freshness_follows_last_applied_frame() {
+        let mut asm = assembler();
+        let t0 = Instant::now();
+        let max_age = Duration::from_millis(5_000);
+        assert!(!asm.snapshot().is_fresh(t0, max_age));
+
+        assert!(asm.apply_ws_text(
+            r#"{"event_type":"book","asset_id":"no-asset","bids":[{"price":"0.40","size":"5"}],"asks":[]}"#,
+            t0,
+        ));
+        // Frames for foreign assets do not refresh the ladder.
+        assert!(!asm.apply_ws_text(
+            r#"{"event_type":"book","asset_id":"other","bids":[{"price":"0.40","size":"5"}],"asks":[]}"#,
+            t0 + Duration::from_millis(4_000),
+        ));
+        let snap = asm.snapshot();
+        assert!(snap.is_fresh(t0 + max_age, max_age));
+        assert!(!snap.is_fresh(t0 + max_age + Duration::from_millis(1), max_age));
+    }
+}
@@ -6,6 +6,7 @@
 
 use std::time::Instant;
 
+use super::fees::LiquidityRole;
 use super::pair_ledger::{EpisodeMetrics, PairLedgerSnapshot};
 use super::recorder::MarketBookDepthEvidence;
 use super::types::Side;
@@ -143,7 +144,7 @@ impl TakerSide {
 }
 
 /// Trade direction for strategy intents and fill accounting.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum TradeDirection {
     Buy,
     Sell,
@@ -635,6 +636,33 @@ pub enum RejectKind {
     Other,
 }
 
+impl RejectKind {
+    pub const COUNT: usize = 6;
+    pub const ALL: [RejectKind; Self::COUNT] = [
+        RejectKind::RateLimit,
+        RejectKind::BalanceOrAllowance,
+        RejectKind::PositionUnavailableLag,
+        RejectKind::CrossBookTransient,
+        RejectKind::Validation,
+        RejectKind::Other,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RejectKind::RateLimit => "rate_limit",
+            RejectKind::BalanceOrAllowance => "balance_or_allowance",
+            RejectKind::PositionUnavailableLag => "position_unavailable_lag",
+            RejectKind::CrossBookTransient => "cross_book_transient",
+            RejectKind::Validation => "validation",
+            RejectKind::Other => "other",
+        }
+    }
+}
+
 /// Placement rejection event for capital/risk side-channels.
 #[derive(Debug, Clone)]
 pub struct PlacementRejectEvent {
@@ -674,7 +702,11 @@ pub enum FillSource {
     DryRunBookTouch,
     DryRunBookDepthTouch,
     DryRunTradeSellTouch,
+    /// Dry-run maker fill released by the queue-position estimator.
+    DryRunQueue,
     DryRunTaker,
+    /// Simulated venue fill produced by the backtest harness.
+    Backtest,
 }
 
 impl FillSource {
@@ -686,7 +718,9 @@ impl FillSource {
             Self::DryRunBookTouch => "dry_run_book_touch",
             Self::DryRunBookDepthTouch => "dry_run_book_depth_touch",
             Self::DryRunTradeSellTouch => "dry_run_trade_sell_touch",
+            Self::DryRunQueue => "dry_run_queue",
             Self::DryRunTaker => "dry_run_taker",
+            Self::Backtest => "backtest",
         }
     }
 }
@@ -711,6 +745,10 @@ pub struct FillEvent {
     /// Fill status from the exchange.
     pub status: FillStatus,
     pub source: FillSource,
+    /// Maker/taker role when the venue (or simulator) reports it.
+    pub liquidity: LiquidityRole,
+    /// Venue-reported fee rate for this fill, when present.
+    pub fee_rate_bps: Option<u32>,
     pub ts: Instant,
 }
 
@@ -747,3 +785,14 @@ pub struct KillSwitchSignal {
     pub ofi_score: f64,
     pub ts: Instant,
 }
+
+/// A quote the Coordinator pulled with `CancelReason::ToxicFlow`, reported back
+/// to the OFI engine for kill-switch attribution.
+#[derive(Debug, Clone, Copy)]
+pub struct ToxicCancelReport {
+    pub side: Side,
+    pub direction: TradeDirection,
+    pub price: f64,
+    pub size: f64,
+    pub ts: Instant,
+}
@@ -0,0 +1,814 @@
+//! Prometheus text-format `/metrics` exporter.
+//!
+//! Per-round collectors mirror the coordinator's watch channels (inventory, OFI,
+//! `CoordinatorObsSnapshot`, market data) into a process-wide registry; a small
+//! local HTTP server renders it on scrape. Nothing here talks to an outside
+//! service and nothing runs unless `PM_METRICS_ENABLED=1`.
+//!
+//! Series are labelled by `market` (the round slug) and dropped when the round's
+//! collector stops, so label cardinality stays bounded across rotations.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use super::config_validation::env_bool;
+use super::coordinator::CoordinatorObsSnapshot;
+use super::messages::{InventorySnapshot, MarketDataMsg, OfiSnapshot, RejectKind, SideOfi};
+use super::recorder::RecorderHandle;
+
+const DEFAULT_METRICS_ADDR: &str = "127.0.0.1:9464";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub addr: SocketAddr,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            addr: DEFAULT_METRICS_ADDR
+                .parse()
+                .expect("valid default metrics addr"),
+        }
+    }
+}
+
+impl MetricsConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self {
+            enabled: env_bool("PM_METRICS_ENABLED", false),
+            ..Self::default()
+        };
+        if let Ok(v) = std::env::var("PM_METRICS_ADDR") {
+            match v.trim().parse::<SocketAddr>() {
+                Ok(addr) => cfg.addr = addr,
+                Err(_) => warn!(
+                    "⚠️ Ignoring invalid PM_METRICS_ADDR={} (expected host:port), using {}",
+                    v, cfg.addr
+                ),
+            }
+        }
+        cfg
+    }
+
+    /// Address for the `idx`-th multi-market worker process: base port + 1 + idx,
+    /// so the supervisor and each worker can all be scraped side by side.
+    pub fn worker_addr(&self, idx: usize) -> SocketAddr {
+        let port = self.addr.port().saturating_add(1 + idx as u16);
+        SocketAddr::new(self.addr.ip(), port)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct MarketMetrics {
+    strategy: String,
+    coord: CoordinatorObsSnapshot,
+    inventory: InventorySnapshot,
+    ofi: Option<OfiSnapshot>,
+    md_updates: u64,
+    last_md_at: Option<Instant>,
+}
+
+/// Process-wide metrics state rendered by the `/metrics` endpoint.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    markets: Mutex<BTreeMap<String, MarketMetrics>>,
+    round_ends: Mutex<BTreeMap<String, u64>>,
+    recorder_md_drops: AtomicU64,
+    recorder_critical_drops: AtomicU64,
+}
+
+pub fn metrics_registry() -> &'static MetricsRegistry {
+    static REGISTRY: OnceLock<MetricsRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(MetricsRegistry::default)
+}
+
+impl MetricsRegistry {
+    fn update_market(&self, market: &str, f: impl FnOnce(&mut MarketMetrics)) {
+        let mut guard = self.markets.lock().unwrap_or_else(|e| e.into_inner());
+        f(guard.entry(market.to_string()).or_default());
+    }
+
+    fn remove_market(&self, market: &str) {
+        let mut guard = self.markets.lock().unwrap_or_else(|e| e.into_inner());
+        guard.remove(market);
+    }
+
+    /// Count a finished round by end reason (`expired`, `ws_degraded`, ...).
+    pub fn note_round_end(&self, reason: &str) {
+        let mut guard = self.round_ends.lock().unwrap_or_else(|e| e.into_inner());
+        *guard.entry(reason.to_string()).or_default() += 1;
+    }
+
+    pub fn note_recorder_drops(&self, md_drops: u64, critical_drops: u64) {
+        self.recorder_md_drops.store(md_drops, Ordering::Relaxed);
+        self.recorder_critical_drops
+            .store(critical_drops, Ordering::Relaxed);
+    }
+
+    /// Render every series in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let markets = self
+            .markets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let round_ends = self
+            .round_ends
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        render_families(
+            &markets,
+            &round_ends,
+            self.recorder_md_drops.load(Ordering::Relaxed),
+            self.recorder_critical_drops.load(Ordering::Relaxed),
+            Instant::now(),
+        )
+    }
+}
+
+type MarketGauge = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&MarketMetrics) -> f64,
+);
+type CoordCounter = (
+    &'static str,
+    &'static str,
+    fn(&CoordinatorObsSnapshot) -> u64,
+);
+
+/// Coordinator counters exported as `pm_coord_<name>_total`. Strategy quote
+/// diagnostics are accumulated into the same snapshot by the coordinator.
+const COORD_COUNTERS: &[CoordCounter] = &[
+    ("ticks", "Strategy evaluation ticks", |s| s.ticks),
+    ("placed", "Orders placed", |s| s.placed),
+    ("publish_events", "Quote publish events", |s| {
+        s.publish_events
+    }),
+    ("replace_events", "Quote replace events", |s| {
+        s.replace_events
+    }),
+    ("cancel_events", "Quote cancel events", |s| s.cancel_events),
+    ("cancel_reprice", "Cancels for reprice", |s| {
+        s.cancel_reprice
+    }),
+    ("cancel_toxic", "Cancels for OFI toxicity", |s| {
+        s.cancel_toxic
+    }),
+    ("cancel_stale", "Cancels for stale data", |s| s.cancel_stale),
+    ("cancel_inv", "Cancels for inventory limits", |s| {
+        s.cancel_inv
+    }),
+    ("ofi_heat_events", "OFI heat events", |s| s.ofi_heat_events),
+    ("ofi_toxic_events", "OFI toxic events", |s| {
+        s.ofi_toxic_events
+    }),
+    ("ofi_kill_events", "OFI kill-switch events", |s| {
+        s.ofi_kill_events
+    }),
+    ("ofi_blocked_ticks", "Ticks blocked by OFI", |s| {
+        s.ofi_blocked_ticks
+    }),
+    (
+        "reference_blocked_ms",
+        "Milliseconds blocked on reference price",
+        |s| s.reference_blocked_ms,
+    ),
+    ("retain_hits", "Quotes retained without republish", |s| {
+        s.retain_hits
+    }),
+    (
+        "publish_budget_suppressed",
+        "Publishes suppressed by budget",
+        |s| s.publish_budget_suppressed,
+    ),
+    ("forced_realign", "Forced quote realigns", |s| {
+        s.forced_realign_count
+    }),
+    (
+        "pair_arb_ofi_softened_quotes",
+        "PairArb quotes softened by OFI",
+        |s| s.pair_arb_ofi_softened_quotes,
+    ),
+    (
+        "pair_arb_ofi_suppressed_quotes",
+        "PairArb quotes suppressed by OFI",
+        |s| s.pair_arb_ofi_suppressed_quotes,
+    ),
+    (
+        "pair_arb_keep_candidates",
+        "PairArb gate keep candidates",
+        |s| s.pair_arb_keep_candidates,
+    ),
+    (
+        "pair_arb_skip_inventory_gate",
+        "PairArb skips on inventory gate",
+        |s| s.pair_arb_skip_inventory_gate,
+    ),
+    (
+        "pair_arb_skip_simulate_buy_none",
+        "PairArb skips on buy simulation",
+        |s| s.pair_arb_skip_simulate_buy_none,
+    ),
+    ("pgt_seed_quotes", "PGT seed quotes", |s| s.pgt_seed_quotes),
+    ("pgt_completion_quotes", "PGT completion quotes", |s| {
+        s.pgt_completion_quotes
+    }),
+    ("pgt_skip_harvest", "PGT skips on harvest", |s| {
+        s.pgt_skip_harvest
+    }),
+    (
+        "pgt_skip_residual_guard",
+        "PGT skips on residual guard",
+        |s| s.pgt_skip_residual_guard,
+    ),
+    (
+        "pgt_skip_capital_guard",
+        "PGT skips on capital guard",
+        |s| s.pgt_skip_capital_guard,
+    ),
+    ("pgt_skip_invalid_book", "PGT skips on invalid book", |s| {
+        s.pgt_skip_invalid_book
+    }),
+    ("pgt_skip_no_seed", "PGT skips without seed", |s| {
+        s.pgt_skip_no_seed
+    }),
+    ("pgt_dispatch_place", "PGT dispatch places", |s| {
+        s.pgt_dispatch_place
+    }),
+    ("pgt_dispatch_taker_open", "PGT taker opens", |s| {
+        s.pgt_dispatch_taker_open
+    }),
+    ("pgt_dispatch_taker_close", "PGT taker closes", |s| {
+        s.pgt_dispatch_taker_close
+    }),
+    ("market_trade_ticks", "Public market trades seen", |s| {
+        s.market_trade_ticks
+    }),
+];
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (i, (k, v)) in labels.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let escaped = v
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            let _ = write!(out, "{k}=\"{escaped}\"");
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {}", if value.is_finite() { value } else { 0.0 });
+}
+
+fn per_market(
+    out: &mut String,
+    markets: &BTreeMap<String, MarketMetrics>,
+    name: &str,
+    kind: &str,
+    help: &str,
+    value: impl Fn(&MarketMetrics) -> f64,
+) {
+    header(out, name, kind, help);
+    for (market, m) in markets {
+        sample(out, name, &[("market", market)], value(m));
+    }
+}
+
+fn per_market_side(
+    out: &mut String,
+    markets: &BTreeMap<String, MarketMetrics>,
+    name: &str,
+    help: &str,
+    value: impl Fn(&MarketMetrics, bool) -> Option<f64>,
+) {
+    header(out, name, "gauge", help);
+    for (market, m) in markets {
+        for (side, is_yes) in [("yes", true), ("no", false)] {
+            if let Some(v) = value(m, is_yes) {
+                sample(out, name, &[("market", market), ("side", side)], v);
+            }
+        }
+    }
+}
+
+fn ofi_side(m: &MarketMetrics, is_yes: bool) -> Option<SideOfi> {
+    m.ofi.map(|o| if is_yes { o.yes } else { o.no })
+}
+
+fn flag(b: bool) -> f64 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn render_families(
+    markets: &BTreeMap<String, MarketMetrics>,
+    round_ends: &BTreeMap<String, u64>,
+    recorder_md_drops: u64,
+    recorder_critical_drops: u64,
+    now: Instant,
+) -> String {
+    let mut out = String::new();
+
+    header(&mut out, "pm_market_info", "gauge", "Active market rounds");
+    for (market, m) in markets {
+        sample(
+            &mut out,
+            "pm_market_info",
+            &[("market", market), ("strategy", &m.strategy)],
+            1.0,
+        );
+    }
+
+    // Inventory (working view: settled + unresolved matched fills).
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_inventory_qty",
+        "Working inventory shares",
+        |m, y| {
+            Some(if y {
+                m.inventory.working.yes_qty
+            } else {
+                m.inventory.working.no_qty
+            })
+        },
+    );
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_inventory_avg_cost",
+        "Working average cost",
+        |m, y| {
+            Some(if y {
+                m.inventory.working.yes_avg_cost
+            } else {
+                m.inventory.working.no_avg_cost
+            })
+        },
+    );
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_inventory_pending_qty",
+        "Unresolved matched shares",
+        |m, y| {
+            Some(if y {
+                m.inventory.pending_yes_qty
+            } else {
+                m.inventory.pending_no_qty
+            })
+        },
+    );
+    per_market(
+        &mut out,
+        markets,
+        "pm_net_diff",
+        "gauge",
+        "Working YES minus NO shares",
+        |m| m.inventory.working.net_diff,
+    );
+    per_market(
+        &mut out,
+        markets,
+        "pm_settled_net_diff",
+        "gauge",
+        "Settled YES minus NO shares",
+        |m| m.inventory.settled.net_diff,
+    );
+    per_market(
+        &mut out,
+        markets,
+        "pm_portfolio_cost",
+        "gauge",
+        "Working portfolio cost (USDC)",
+        |m| m.inventory.working.portfolio_cost,
+    );
+    per_market(
+        &mut out,
+        markets,
+        "pm_inventory_fragile",
+        "gauge",
+        "1 while unresolved fills exist",
+        |m| flag(m.inventory.fragile),
+    );
+
+    // Pair ledger.
+    let ledger: [MarketGauge; 8] = [
+        (
+            "pm_pair_ledger_buy_fills_total",
+            "counter",
+            "Buy fills booked by the pair ledger",
+            |m| m.inventory.pair_ledger.buy_fill_count as f64,
+        ),
+        (
+            "pm_pair_ledger_completed_pairs_total",
+            "counter",
+            "Completed YES+NO pairs",
+            |m| m.inventory.pair_ledger.completed_pair_count as f64,
+        ),
+        (
+            "pm_pair_ledger_residual_qty",
+            "gauge",
+            "Unpaired residual shares",
+            |m| m.inventory.pair_ledger.residual_qty,
+        ),
+        (
+            "pm_pair_ledger_active_tranche",
+            "gauge",
+            "1 while a tranche is open",
+            |m| flag(m.inventory.pair_ledger.active_tranche.is_some()),
+        ),
+        (
+            "pm_pair_ledger_surplus_bank",
+            "gauge",
+            "Banked pair surplus (USDC)",
+            |m| m.inventory.pair_ledger.surplus_bank,
+        ),
+        (
+            "pm_pair_ledger_repair_budget",
+            "gauge",
+            "Repair budget available (USDC)",
+            |m| m.inventory.pair_ledger.repair_budget_available,
+        ),
+        (
+            "pm_pair_ledger_working_capital",
+            "gauge",
+            "Working capital (USDC)",
+            |m| m.inventory.pair_ledger.capital_state.working_capital,
+        ),
+        (
+            "pm_pair_ledger_locked_capital_ratio",
+            "gauge",
+            "Locked / working capital",
+            |m| m.inventory.pair_ledger.capital_state.locked_capital_ratio,
+        ),
+    ];
+    for (name, kind, help, value) in ledger {
+        per_market(&mut out, markets, name, kind, help, value);
+    }
+
+    // OFI.
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_ofi_score",
+        "Signed order-flow imbalance",
+        |m, y| ofi_side(m, y).map(|s| s.ofi_score),
+    );
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_ofi_heat_score",
+        "OFI heat score",
+        |m, y| ofi_side(m, y).map(|s| s.heat_score),
+    );
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_ofi_hot",
+        "1 while the side is hot",
+        |m, y| ofi_side(m, y).map(|s| flag(s.is_hot)),
+    );
+    per_market_side(
+        &mut out,
+        markets,
+        "pm_ofi_toxic",
+        "1 while the side is toxic",
+        |m, y| ofi_side(m, y).map(|s| flag(s.is_toxic)),
+    );
+
+    // Coordinator / strategy diagnostics.
+    for (field, help, value) in COORD_COUNTERS {
+        let name = format!("pm_coord_{field}_total");
+        per_market(&mut out, markets, &name, "counter", help, |m| {
+            value(&m.coord) as f64
+        });
+    }
+    header(
+        &mut out,
+        "pm_placement_rejects_total",
+        "counter",
+        "Placement rejects by kind",
+    );
+    for (market, m) in markets {
+        for kind in RejectKind::ALL {
+            sample(
+                &mut out,
+                "pm_placement_rejects_total",
+                &[("market", market), ("kind", kind.as_str())],
+                m.coord.placement_rejects[kind.index()] as f64,
+            );
+        }
+    }
+
+    // Market WS health as seen by the coordinator feed.
+    per_market(
+        &mut out,
+        markets,
+        "pm_market_data_updates_total",
+        "counter",
+        "Market data updates delivered",
+        |m| m.md_updates as f64,
+    );
+    header(
+        &mut out,
+        "pm_market_data_age_seconds",
+        "gauge",
+        "Seconds since the last market data update",
+    );
+    for (market, m) in markets {
+        if let Some(at) = m.last_md_at {
+            let age = now.saturating_duration_since(at).as_secs_f64();
+            sample(
+                &mut out,
+                "pm_market_data_age_seconds",
+                &[("market", market)],
+                age,
+            );
+        }
+    }
+    header(
+        &mut out,
+        "pm_market_rounds_total",
+        "counter",
+        "Finished rounds by end reason",
+    );
+    for (reason, n) in round_ends {
+        sample(
+            &mut out,
+            "pm_market_rounds_total",
+            &[("reason", reason)],
+            *n as f64,
+        );
+    }
+
+    header(
+        &mut out,
+        "pm_recorder_md_drops_total",
+        "counter",
+        "Recorder market-data events dropped on a full queue",
+    );
+    sample(
+        &mut out,
+        "pm_recorder_md_drops_total",
+        &[],
+        recorder_md_drops as f64,
+    );
+    header(
+        &mut out,
+        "pm_recorder_critical_drops_total",
+        "counter",
+        "Recorder ops events dropped on a full queue",
+    );
+    sample(
+        &mut out,
+        "pm_recorder_critical_drops_total",
+        &[],
+        recorder_critical_drops as f64,
+    );
+    out
+}
+
+/// Removes the market's series when the collector stops or is aborted.
+struct MarketSeriesGuard(String);
+
+impl Drop for MarketSeriesGuard {
+    fn drop(&mut self) {
+        metrics_registry().remove_market(&self.0);
+    }
+}
+
+/// Mirror one round's coordinator-side watch channels into the registry.
+/// Returns when the coordinator's obs sender is dropped.
+pub async fn run_market_metrics_collector(
+    market: String,
+    strategy: String,
+    mut inv_rx: watch::Receiver<InventorySnapshot>,
+    mut ofi_rx: watch::Receiver<OfiSnapshot>,
+    mut coord_obs_rx: watch::Receiver<CoordinatorObsSnapshot>,
+    mut md_rx: watch::Receiver<MarketDataMsg>,
+    recorder: Option<RecorderHandle>,
+) {
+    let registry = metrics_registry();
+    let _guard = MarketSeriesGuard(market.clone());
+    registry.update_market(&market, |m| {
+        m.strategy = strategy;
+        m.inventory = *inv_rx.borrow();
+        m.coord = *coord_obs_rx.borrow();
+    });
+    let mut sample_tick = tokio::time::interval(Duration::from_secs(1));
+    sample_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut inv_open = true;
+    let mut ofi_open = true;
+    let mut md_open = true;
+
+    loop {
+        tokio::select! {
+            changed = coord_obs_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let snap = *coord_obs_rx.borrow_and_update();
+                registry.update_market(&market, |m| m.coord = snap);
+            }
+            changed = inv_rx.changed(), if inv_open => {
+                if changed.is_err() {
+                    inv_open = false;
+                    continue;
+                }
+                let snap = *inv_rx.borrow_and_update();
+                registry.update_market(&market, |m| m.inventory = snap);
+            }
+            changed = ofi_rx.changed(), if ofi_open => {
+                if changed.is_err() {
+                    ofi_open = false;
+                    continue;
+                }
+                let snap = *ofi_rx.borrow_and_update();
+                registry.update_market(&market, |m| m.ofi = Some(snap));
+            }
+            changed = md_rx.changed(), if md_open => {
+                if changed.is_err() {
+                    md_open = false;
+                    continue;
+                }
+                md_rx.borrow_and_update();
+                registry.update_market(&market, |m| {
+                    m.md_updates += 1;
+                    m.last_md_at = Some(Instant::now());
+                });
+            }
+            _ = sample_tick.tick() => {
+                if let Some(recorder) = recorder.as_ref() {
+                    registry.note_recorder_drops(
+                        recorder.md_drop_count(),
+                        recorder.critical_drop_count(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+async fn serve_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < 8192 {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", CONTENT_TYPE, metrics_registry().render()),
+        ("GET", "/") | ("GET", "/health") => ("200 OK", "text/plain", "ok\n".to_string()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Bind the `/metrics` listener and serve it in the background.
+/// Bind failures are logged and leave the bot running without an exporter.
+pub async fn spawn_metrics_server(cfg: &MetricsConfig) -> Option<tokio::task::JoinHandle<()>> {
+    if !cfg.enabled {
+        return None;
+    }
+    let listener = match TcpListener::bind(cfg.addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            warn!(
+                "⚠️ metrics exporter disabled: bind {} failed: {}",
+                cfg.addr, e
+            );
+            return None;
+        }
+    };
+    let local = listener.local_addr().unwrap_or(cfg.addr);
+    info!("📈 metrics exporter listening on http://{}/metrics", local);
+    Some(tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    // e.g. EMFILE: retrying at once would spin until an fd frees up.
+                    warn!("⚠️ metrics exporter accept failed: {}", e);
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
+            tokio::spawn(async move {
+                let _ = serve_connection(stream).await;
+            });
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_emits_labelled_series_per_market() {
+        let mut m = MarketMetrics {
+            strategy: "pair_arb".to_string(),
+            ..Default::default()
+        };
+        m.inventory.working.yes_qty = 12.5;
+        m.inventory.working.net_diff = 2.5;
+        m.inventory.pair_ledger.completed_pair_count = 3;
+        m.coord.ticks = 42;
+        m.coord.placement_rejects[RejectKind::RateLimit.index()] = 2;
+        let now = Instant::now();
+        m.last_md_at = Some(now);
+        let mut markets = BTreeMap::new();
+        markets.insert("btc-updown-15m-1".to_string(), m);
+        let mut ends = BTreeMap::new();
+        ends.insert("expired".to_string(), 4);
+
+        let text = render_families(&markets, &ends, 7, 0, now);
+        let market = "market=\"btc-updown-15m-1\"";
+        assert!(text.contains(&format!("pm_inventory_qty{{{market},side=\"yes\"}} 12.5")));
+        assert!(text.contains(&format!("pm_net_diff{{{market}}} 2.5")));
+        assert!(text.contains(&format!(
+            "pm_pair_ledger_completed_pairs_total{{{market}}} 3"
+        )));
+        assert!(text.contains(&format!("pm_coord_ticks_total{{{market}}} 42")));
+        assert!(text.contains(&format!(
+            "pm_placement_rejects_total{{{market},kind=\"rate_limit\"}} 2"
+        )));
+        assert!(text.contains("pm_market_rounds_total{reason=\"expired\"} 4"));
+        assert!(text.contains("pm_recorder_md_drops_total 7"));
+        // OFI series are omitted until the first OFI snapshot arrives.
+        assert!(!text.contains("pm_ofi_score{"));
+        // Every family is announced exactly once.
+        let types = text
+            .lines()
+            .filter(|l| l.starts_with("# TYPE pm_net_diff "))
+            .count();
+        assert_eq!(types, 1);
+    }
+
+    #[tokio::test]
+    async fn server_serves_metrics_and_404() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let _ = serve_connection(stream).await;
+            }
+        });
+        for (path, expect) in [("/metrics", "200 OK"), ("/nope", "404 Not Found")] {
+            let mut s = TcpStream::connect(addr).await.unwrap();
+            s.write_all(format!("GET {path} HTTP/1.1\r\nHost: x\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut resp = String::new();
+            s.read_to_string(&mut resp).await.unwrap();
+            assert!(resp.starts_with(&format!("HTTP/1.1 {expect}")), "{resp}");
+            if path == "/metrics" {
+                assert!(resp.contains("# TYPE pm_recorder_md_drops_total counter"));
+            }
+        }
+    }
+}
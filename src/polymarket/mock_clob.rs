@@ -0,0 +1,1642 @@
+//! Local mock CLOB venue for end-to-end tests.
+//!
+//! Serves the subset of the Polymarket CLOB surface the live stack touches, on
+//! loopback ports, so `polymarket_v2` (or a test) can run live mode against a
+//! scripted venue by pointing `POLYMARKET_REST_URL` / `POLYMARKET_WS_BASE_URL`
+//! at `MockClobServer::rest_url` / `MockClobServer::ws_base_url`:
+//! - REST: `time`, `auth/api-key` + `auth/derive-api-key`, `book` / `books`,
+//!   `tick-size` / `neg-risk` / `fee-rate`, `balance-allowance[/update]`,
+//!   `order` (post + cancel), `orders` / `cancel-all` / `cancel-market-orders`,
+//!   `data/orders` and `data/order/{id}`. Authenticated routes check `POLY_API_KEY`
+//!   only; signatures are not verified.
+//! - `ws/market`: `book` dump on subscribe, then `price_change` / `last_trade_price`.
+//! - `ws/user`: `order` (PLACEMENT / UPDATE / CANCELLATION) and `trade` events
+//!   (MATCHED then CONFIRMED), shaped like the venue so `UserWsListener` parses them.
+//!
+//! Matching: street liquidity is scripted per level (`set_level`). Our resting
+//! orders queue FIFO behind the street size visible when they were posted, and
+//! only fill when a scripted external taker (`external_trade`) reaches them.
+//! Our marketable orders (FAK/FOK, or GTC without post-only) sweep street
+//! liquidity only — the mock never self-trades. Post-only orders that would
+//! cross the street book are rejected with the venue's "order crosses book" error.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+use super::messages::TradeDirection;
+use super::types::Side;
+
+const PRICE_SCALE: f64 = 10_000.0;
+const AMOUNT_SCALE: f64 = 1_000_000.0;
+const QTY_EPS: f64 = 1e-9;
+const TERMINAL_CURSOR: &str = "LTE=";
+const STREET_OWNER: &str = "mock-street";
+
+// ─────────────────────────────────────────────────────────
+// Configuration
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct MockClobConfig {
+    /// REST listen address (`127.0.0.1:0` picks a free port).
+    pub rest_addr: SocketAddr,
+    /// WebSocket listen address (`127.0.0.1:0` picks a free port).
+    pub ws_addr: SocketAddr,
+    /// Condition id (0x-prefixed 32-byte hex) reported as `market`.
+    pub market_id: String,
+    pub yes_asset_id: String,
+    pub no_asset_id: String,
+    /// L2 credentials handed out by `auth/*` and required on authenticated routes.
+    pub api_key: String,
+    pub api_secret: String,
+    pub api_passphrase: String,
+    pub tick_size: f64,
+    pub min_order_size: f64,
+    /// Collateral balance reported by `balance-allowance` (USDC).
+    pub balance_usdc: f64,
+}
+
+impl Default for MockClobConfig {
+    fn default() -> Self {
+        let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+        Self {
+            rest_addr: loopback,
+            ws_addr: loopback,
+            market_id: format!("0x{:064x}", 0xC10Bu64),
+            yes_asset_id:
+                "71321045679252212594626385532706912750332728571942532289631379312455583992563"
+                    .to_string(),
+            no_asset_id:
+                "52114319501245915516055106046884209969926127482827954674443846427813813222426"
+                    .to_string(),
+            api_key: "00000000-0000-4000-8000-00000000c10b".to_string(),
+            api_secret: "bW9jay1jbG9iLXNlY3JldC1rZXk=".to_string(),
+            api_passphrase: "mock-clob-passphrase".to_string(),
+            tick_size: 0.01,
+            min_order_size: 5.0,
+            balance_usdc: 1_000.0,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Orders & book
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockOrderStatus {
+    Live,
+    Matched,
+    Canceled,
+}
+
+impl MockOrderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Live => "LIVE",
+            Self::Matched => "MATCHED",
+            Self::Canceled => "CANCELED",
+        }
+    }
+}
+
+/// One of our orders as the mock venue sees it.
+#[derive(Debug, Clone)]
+pub struct MockOrder {
+    pub id: String,
+    pub asset_id: String,
+    pub side: Side,
+    pub direction: TradeDirection,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    pub status: MockOrderStatus,
+    pub order_type: String,
+    pub maker_address: String,
+    pub created_at: u64,
+    /// Street size still ahead of this order at its price level.
+    queue_ahead: f64,
+    seq: u64,
+}
+
+impl MockOrder {
+    pub fn remaining(&self) -> f64 {
+        (self.original_size - self.size_matched).max(0.0)
+    }
+
+    fn is_live(&self) -> bool {
+        self.status == MockOrderStatus::Live
+    }
+
+    fn rests_on_bid(&self) -> bool {
+        matches!(self.direction, TradeDirection::Buy)
+    }
+}
+
+/// Street (non-ours) liquidity per price level, keyed in 1e-4 price units.
+#[derive(Debug, Clone, Default)]
+struct StreetBook {
+    bids: BTreeMap<u32, f64>,
+    asks: BTreeMap<u32, f64>,
+}
+
+impl StreetBook {
+    fn levels(&self, is_bid: bool) -> &BTreeMap<u32, f64> {
+        if is_bid {
+            &self.bids
+        } else {
+            &self.asks
+        }
+    }
+
+    fn levels_mut(&mut self, is_bid: bool) -> &mut BTreeMap<u32, f64> {
+        if is_bid {
+            &mut self.bids
+        } else {
+            &mut self.asks
+        }
+    }
+
+    fn size_at(&self, is_bid: bool, key: u32) -> f64 {
+        self.levels(is_bid).get(&key).copied().unwrap_or(0.0)
+    }
+
+    fn set(&mut self, is_bid: bool, key: u32, size: f64) {
+        if size > QTY_EPS {
+            self.levels_mut(is_bid).insert(key, size);
+        } else {
+            self.levels_mut(is_bid).remove(&key);
+        }
+    }
+}
+
+fn price_key(price: f64) -> u32 {
+    (price * PRICE_SCALE).round().max(0.0) as u32
+}
+
+fn key_price(key: u32) -> f64 {
+    key as f64 / PRICE_SCALE
+}
+
+fn fmt_num(v: f64) -> String {
+    let s = format!("{:.6}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s.is_empty() {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+fn direction_str(direction: TradeDirection) -> &'static str {
+    match direction {
+        TradeDirection::Buy => "BUY",
+        TradeDirection::Sell => "SELL",
+    }
+}
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Decoded `POST /order` body.
+#[derive(Debug, Clone)]
+struct PlaceRequest {
+    asset_id: String,
+    direction: TradeDirection,
+    price: f64,
+    size: f64,
+    order_type: String,
+    post_only: bool,
+    maker_address: String,
+}
+
+impl PlaceRequest {
+    /// Recover price/size from the signed maker/taker amounts (6dp fixed point),
+    /// mirroring `clob_v2::build_signed_limit_order_v2`.
+    fn from_body(body: &Value) -> Result<Self, String> {
+        let order = body.get("order").ok_or("missing order")?;
+        let str_field = |key: &str| -> Option<String> {
+            order.get(key).and_then(|v| match v {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+        let amount = |key: &str| -> Result<f64, String> {
+            str_field(key)
+                .and_then(|s| s.parse::<f64>().ok())
+                .map(|v| v / AMOUNT_SCALE)
+                .ok_or_else(|| format!("invalid {key}"))
+        };
+        let asset_id = str_field("tokenId").ok_or("missing tokenId")?;
+        let maker_amount = amount("makerAmount")?;
+        let taker_amount = amount("takerAmount")?;
+        let direction = match str_field("side").as_deref() {
+            Some("BUY") | Some("0") => TradeDirection::Buy,
+            Some("SELL") | Some("1") => TradeDirection::Sell,
+            _ => return Err("invalid side".to_string()),
+        };
+        let (price, size) = match direction {
+            TradeDirection::Buy if taker_amount > 0.0 => {
+                (maker_amount / taker_amount, taker_amount)
+            }
+            TradeDirection::Sell if maker_amount > 0.0 => {
+                (taker_amount / maker_amount, maker_amount)
+            }
+            _ => return Err("invalid amounts".to_string()),
+        };
+        Ok(Self {
+            asset_id,
+            direction,
+            price: key_price(price_key(price)),
+            size,
+            order_type: body
+                .get("orderType")
+                .and_then(|v| v.as_str())
+                .unwrap_or("GTC")
+                .to_ascii_uppercase(),
+            post_only: body
+                .get("postOnly")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            maker_address: str_field("maker").unwrap_or_default(),
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Venue state
+// ─────────────────────────────────────────────────────────
+
+struct MockState {
+    cfg: MockClobConfig,
+    books: HashMap<String, StreetBook>,
+    orders: Vec<MockOrder>,
+    next_seq: u64,
+    market_tx: broadcast::Sender<String>,
+    user_tx: broadcast::Sender<String>,
+    /// Count of accepted user WS subscriptions.
+    user_subscribed: watch::Sender<usize>,
+}
+
+impl MockState {
+    fn new(cfg: MockClobConfig) -> Self {
+        let (market_tx, _) = broadcast::channel(1024);
+        let (user_tx, _) = broadcast::channel(1024);
+        let (user_subscribed, _) = watch::channel(0);
+        let mut books = HashMap::new();
+        books.insert(cfg.yes_asset_id.clone(), StreetBook::default());
+        books.insert(cfg.no_asset_id.clone(), StreetBook::default());
+        Self {
+            cfg,
+            books,
+            orders: Vec::new(),
+            next_seq: 1,
+            market_tx,
+            user_tx,
+            user_subscribed,
+        }
+    }
+
+    fn asset_for(&self, side: Side) -> String {
+        match side {
+            Side::Yes => self.cfg.yes_asset_id.clone(),
+            Side::No => self.cfg.no_asset_id.clone(),
+        }
+    }
+
+    fn side_for(&self, asset_id: &str) -> Option<Side> {
+        if asset_id == self.cfg.yes_asset_id {
+            Some(Side::Yes)
+        } else if asset_id == self.cfg.no_asset_id {
+            Some(Side::No)
+        } else {
+            None
+        }
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    fn publish_market(&self, event: Value) {
+        let _ = self.market_tx.send(event.to_string());
+    }
+
+    fn publish_user(&self, event: Value) {
+        let _ = self.user_tx.send(event.to_string());
+    }
+
+    fn street(&self, asset_id: &str) -> StreetBook {
+        self.books.get(asset_id).cloned().unwrap_or_default()
+    }
+
+    /// Visible size at a level: street plus our live orders resting there.
+    fn visible_size(&self, asset_id: &str, is_bid: bool, key: u32) -> f64 {
+        let ours: f64 = self
+            .orders
+            .iter()
+            .filter(|o| {
+                o.is_live()
+                    && o.asset_id == asset_id
+                    && o.rests_on_bid() == is_bid
+                    && price_key(o.price) == key
+            })
+            .map(MockOrder::remaining)
+            .sum();
+        self.street(asset_id).size_at(is_bid, key) + ours
+    }
+
+    /// Price keys with street or our liquidity on one book side, best first.
+    fn level_keys(&self, asset_id: &str, is_bid: bool) -> Vec<u32> {
+        let mut keys: Vec<u32> = self
+            .street(asset_id)
+            .levels(is_bid)
+            .keys()
+            .copied()
+            .collect();
+        keys.extend(
+            self.orders
+                .iter()
+                .filter(|o| o.is_live() && o.asset_id == asset_id && o.rests_on_bid() == is_bid)
+                .map(|o| price_key(o.price)),
+        );
+        keys.sort_unstable();
+        keys.dedup();
+        if is_bid {
+            keys.reverse();
+        }
+        keys
+    }
+
+    /// Visible ladder, best level first.
+    fn visible_levels(&self, asset_id: &str, is_bid: bool) -> Vec<(f64, f64)> {
+        self.level_keys(asset_id, is_bid)
+            .into_iter()
+            .map(|k| (key_price(k), self.visible_size(asset_id, is_bid, k)))
+            .filter(|(_, size)| *size > QTY_EPS)
+            .collect()
+    }
+
+    fn best_visible(&self, asset_id: &str, is_bid: bool) -> Option<f64> {
+        self.visible_levels(asset_id, is_bid)
+            .first()
+            .map(|(p, _)| *p)
+    }
+
+    fn levels_json(levels: &[(f64, f64)]) -> Value {
+        Value::Array(
+            levels
+                .iter()
+                .map(|(p, s)| json!({"price": fmt_num(*p), "size": fmt_num(*s)}))
+                .collect(),
+        )
+    }
+
+    fn book_event(&self, asset_id: &str) -> Value {
+        // Venue ships bids ascending / asks descending (best last).
+        let mut bids = self.visible_levels(asset_id, true);
+        bids.reverse();
+        let mut asks = self.visible_levels(asset_id, false);
+        asks.reverse();
+        json!({
+            "event_type": "book",
+            "market": self.cfg.market_id,
+            "asset_id": asset_id,
+            "bids": Self::levels_json(&bids),
+            "asks": Self::levels_json(&asks),
+            "timestamp": now_unix_ms().to_string(),
+        })
+    }
+
+    fn book_summary(&self, asset_id: &str) -> Value {
+        let mut book = self.book_event(asset_id);
+        if let Some(obj) = book.as_object_mut() {
+            obj.remove("event_type");
+            obj.insert(
+                "min_order_size".into(),
+                json!(fmt_num(self.cfg.min_order_size)),
+            );
+            obj.insert("tick_size".into(), json!(fmt_num(self.cfg.tick_size)));
+            obj.insert("neg_risk".into(), json!(false));
+        }
+        book
+    }
+
+    fn publish_level(&self, asset_id: &str, is_bid: bool, key: u32) {
+        let fmt_best = |p: Option<f64>| p.map(fmt_num).unwrap_or_else(|| "0".to_string());
+        self.publish_market(json!({
+            "event_type": "price_change",
+            "market": self.cfg.market_id,
+            "price_changes": [{
+                "asset_id": asset_id,
+                "price": fmt_num(key_price(key)),
+                "size": fmt_num(self.visible_size(asset_id, is_bid, key)),
+                "side": if is_bid { "BUY" } else { "SELL" },
+                "best_bid": fmt_best(self.best_visible(asset_id, true)),
+                "best_ask": fmt_best(self.best_visible(asset_id, false)),
+            }],
+            "timestamp": now_unix_ms().to_string(),
+        }));
+    }
+
+    fn publish_last_trade(&self, asset_id: &str, taker: TradeDirection, price: f64, size: f64) {
+        self.publish_market(json!({
+            "event_type": "last_trade_price",
+            "market": self.cfg.market_id,
+            "asset_id": asset_id,
+            "price": fmt_num(price),
+            "size": fmt_num(size),
+            "side": direction_str(taker),
+            "fee_rate_bps": "0",
+            "timestamp": now_unix_ms().to_string(),
+        }));
+    }
+
+    fn order_json(&self, order: &MockOrder) -> Value {
+        json!({
+            "id": order.id,
+            "status": order.status.as_str(),
+            "owner": self.cfg.api_key,
+            "maker_address": order.maker_address,
+            "market": self.cfg.market_id,
+            "asset_id": order.asset_id,
+            "side": direction_str(order.direction),
+            "original_size": fmt_num(order.original_size),
+            "size_matched": fmt_num(order.size_matched),
+            "price": fmt_num(order.price),
+            "associate_trades": [],
+            "outcome": match order.side { Side::Yes => "Yes", Side::No => "No" },
+            "created_at": order.created_at,
+            "expiration": "0",
+            "order_type": order.order_type,
+        })
+    }
+
+    fn publish_order_event(&self, order: &MockOrder, kind: &str) {
+        let mut event = self.order_json(order);
+        if let Some(obj) = event.as_object_mut() {
+            obj.insert("event_type".into(), json!("order"));
+            obj.insert("type".into(), json!(kind));
+            obj.insert("timestamp".into(), json!(now_unix_ms().to_string()));
+        }
+        self.publish_user(event);
+    }
+
+    /// Emit a trade event twice: MATCHED, then CONFIRMED.
+    fn publish_trade_lifecycle(&self, event: Value) {
+        for status in ["MATCHED", "CONFIRMED"] {
+            let mut e = event.clone();
+            if let Some(obj) = e.as_object_mut() {
+                obj.insert("status".into(), json!(status));
+            }
+            self.publish_user(e);
+        }
+    }
+
+    fn set_level(&mut self, side: Side, is_bid: bool, price: f64, size: f64) {
+        let asset_id = self.asset_for(side);
+        let key = price_key(price);
+        self.books
+            .entry(asset_id.clone())
+            .or_default()
+            .set(is_bid, key, size.max(0.0));
+        self.publish_level(&asset_id, is_bid, key);
+    }
+
+    fn clear_book(&mut self, side: Side) {
+        let asset_id = self.asset_for(side);
+        self.books.insert(asset_id.clone(), StreetBook::default());
+        self.publish_market(self.book_event(&asset_id));
+    }
+
+    /// A scripted external taker. SELL takers hit bids (our BUY orders), BUY
+    /// takers lift asks (our SELL orders). Levels are walked best-first down to
+    /// `limit_price`; at each level the street queue ahead of each of our orders
+    /// is consumed first, FIFO by posting order. Returns our filled size.
+    fn external_trade(
+        &mut self,
+        side: Side,
+        taker: TradeDirection,
+        limit_price: f64,
+        size: f64,
+    ) -> f64 {
+        let asset_id = self.asset_for(side);
+        let is_bid = matches!(taker, TradeDirection::Sell);
+        let limit_key = price_key(limit_price);
+        let mut remaining = size.max(0.0);
+        let mut ours_filled = 0.0;
+
+        let keys = self.level_keys(&asset_id, is_bid);
+        let taker_order_id = format!("ext-{}", self.next_seq());
+        for key in keys {
+            if remaining <= QTY_EPS {
+                break;
+            }
+            let reachable = if is_bid {
+                key >= limit_key
+            } else {
+                key <= limit_key
+            };
+            if !reachable {
+                break;
+            }
+            let street_size = self.street(&asset_id).size_at(is_bid, key);
+            let mut idxs: Vec<usize> = self
+                .orders
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| {
+                    o.is_live()
+                        && o.asset_id == asset_id
+                        && o.rests_on_bid() == is_bid
+                        && price_key(o.price) == key
+                })
+                .map(|(i, _)| i)
+                .collect();
+            idxs.sort_by_key(|&i| self.orders[i].seq);
+
+            let mut street_consumed = 0.0;
+            let mut level_volume = 0.0;
+            let mut maker_fills: Vec<(usize, f64)> = Vec::new();
+            for &i in &idxs {
+                let ahead = (self.orders[i].queue_ahead - street_consumed)
+                    .min(street_size - street_consumed)
+                    .max(0.0);
+                let take = remaining.min(ahead);
+                street_consumed += take;
+                remaining -= take;
+                level_volume += take;
+                let fill = remaining.min(self.orders[i].remaining());
+                if fill > QTY_EPS {
+                    remaining -= fill;
+                    level_volume += fill;
+                    maker_fills.push((i, fill));
+                }
+                if remaining <= QTY_EPS {
+                    break;
+                }
+            }
+            let tail = remaining.min((street_size - street_consumed).max(0.0));
+            street_consumed += tail;
+            remaining -= tail;
+            level_volume += tail;
+
+            if street_consumed > QTY_EPS {
+                self.books.entry(asset_id.clone()).or_default().set(
+                    is_bid,
+                    key,
+                    street_size - street_consumed,
+                );
+                for &i in &idxs {
+                    let o = &mut self.orders[i];
+                    o.queue_ahead = (o.queue_ahead - street_consumed).max(0.0);
+                }
+            }
+
+            let price = key_price(key);
+            for (i, fill) in maker_fills {
+                let order = {
+                    let o = &mut self.orders[i];
+                    o.size_matched += fill;
+                    if o.remaining() <= QTY_EPS {
+                        o.status = MockOrderStatus::Matched;
+                    }
+                    o.clone()
+                };
+                ours_filled += fill;
+                self.publish_trade_lifecycle(json!({
+                    "event_type": "trade",
+                    "type": "TRADE",
+                    "id": uuid::Uuid::new_v4().to_string(),
+                    "market": self.cfg.market_id,
+                    "asset_id": asset_id,
+                    "side": direction_str(taker),
+                    "size": fmt_num(fill),
+                    "price": fmt_num(price),
+                    "taker_order_id": taker_order_id,
+                    "trader_side": "MAKER",
+                    "owner": STREET_OWNER,
+                    "maker_orders": [{
+                        "order_id": order.id,
+                        "owner": self.cfg.api_key,
+                        "maker_address": order.maker_address,
+                        "asset_id": order.asset_id,
+                        "matched_amount": fmt_num(fill),
+                        "price": fmt_num(order.price),
+                        "side": direction_str(order.direction),
+                        "outcome": match order.side { Side::Yes => "Yes", Side::No => "No" },
+                        "fee_rate_bps": "0",
+                    }],
+                    "fee_rate_bps": "0",
+                    "timestamp": now_unix_ms().to_string(),
+                }));
+                self.publish_order_event(&order, "UPDATE");
+            }
+            if level_volume > QTY_EPS {
+                self.publish_last_trade(&asset_id, taker, price, level_volume);
+                self.publish_level(&asset_id, is_bid, key);
+            }
+        }
+        ours_filled
+    }
+
+    fn place(&mut self, req: PlaceRequest) -> Result<Value, String> {
+        let Some(side) = self.side_for(&req.asset_id) else {
+            return Err(format!("invalid token id: {}", req.asset_id));
+        };
+        let tick = self.cfg.tick_size;
+        let on_tick = ((req.price / tick).round() * tick - req.price).abs() < 1e-6;
+        if !(req.price > 0.0 && req.price < 1.0 && on_tick) {
+            return Err(format!(
+                "invalid price ({}), min: {} - max: {}",
+                fmt_num(req.price),
+                fmt_num(tick),
+                fmt_num(1.0 - tick)
+            ));
+        }
+        if req.size <= QTY_EPS {
+            return Err("invalid amounts".to_string());
+        }
+        let is_marketable_type = matches!(req.order_type.as_str(), "FAK" | "FOK");
+        if !is_marketable_type && req.size + QTY_EPS < self.cfg.min_order_size {
+            return Err(format!(
+                "Size ({}) lower than the minimum: {}",
+                fmt_num(req.size),
+                fmt_num(self.cfg.min_order_size)
+            ));
+        }
+
+        // Our BUY consumes street asks, SELL consumes street bids.
+        let takes_asks = matches!(req.direction, TradeDirection::Buy);
+        let limit_key = price_key(req.price);
+        let street = self.street(&req.asset_id);
+        let crossing: Vec<(u32, f64)> = if takes_asks {
+            street
+                .asks
+                .range(..=limit_key)
+                .map(|(k, s)| (*k, *s))
+                .collect()
+        } else {
+            street
+                .bids
+                .range(limit_key..)
+                .rev()
+                .map(|(k, s)| (*k, *s))
+                .collect()
+        };
+        if req.post_only && !is_marketable_type && !crossing.is_empty() {
+            return Err("invalid post-only order: order crosses book".to_string());
+        }
+        let available: f64 = crossing.iter().map(|(_, s)| s).sum();
+        if req.order_type == "FOK" && available + QTY_EPS < req.size {
+            return Err(
+                "order couldn't be fully filled. FOK orders are fully filled or killed."
+                    .to_string(),
+            );
+        }
+        if req.order_type == "FAK" && available <= QTY_EPS {
+            return Err("no orders found to match with FAK order. FAK orders are partially filled or killed if no match is found.".to_string());
+        }
+
+        let seq = self.next_seq();
+        let mut order = MockOrder {
+            id: format!("0x{:064x}", seq),
+            asset_id: req.asset_id.clone(),
+            side,
+            direction: req.direction,
+            price: req.price,
+            original_size: req.size,
+            size_matched: 0.0,
+            status: MockOrderStatus::Live,
+            order_type: req.order_type.clone(),
+            maker_address: req.maker_address.clone(),
+            created_at: now_unix_ms() / 1000,
+            queue_ahead: 0.0,
+            seq,
+        };
+
+        // Taker leg against the street book.
+        let mut notional = 0.0;
+        let mut trade_ids = Vec::new();
+        for (key, level_size) in crossing {
+            let fill = order.remaining().min(level_size);
+            if fill <= QTY_EPS {
+                break;
+            }
+            order.size_matched += fill;
+            notional += fill * key_price(key);
+            self.books.entry(req.asset_id.clone()).or_default().set(
+                !takes_asks,
+                key,
+                level_size - fill,
+            );
+            let trade_id = uuid::Uuid::new_v4().to_string();
+            self.publish_trade_lifecycle(json!({
+                "event_type": "trade",
+                "type": "TRADE",
+                "id": trade_id,
+                "market": self.cfg.market_id,
+                "asset_id": req.asset_id,
+                "side": direction_str(req.direction),
+                "size": fmt_num(fill),
+                "price": fmt_num(key_price(key)),
+                "taker_order_id": order.id,
+                "trader_side": "TAKER",
+                "owner": self.cfg.api_key,
+                "maker_orders": [{
+                    "order_id": format!("street-{}", key),
+                    "owner": STREET_OWNER,
+                    "asset_id": req.asset_id,
+                    "matched_amount": fmt_num(fill),
+                    "price": fmt_num(key_price(key)),
+                }],
+                "fee_rate_bps": "0",
+                "timestamp": now_unix_ms().to_string(),
+            }));
+            trade_ids.push(trade_id);
+            self.publish_last_trade(&req.asset_id, req.direction, key_price(key), fill);
+            self.publish_level(&req.asset_id, !takes_asks, key);
+        }
+
+        let rests = !is_marketable_type && order.remaining() > QTY_EPS;
+        if rests {
+            order.queue_ahead = self.street(&req.asset_id).size_at(takes_asks, limit_key);
+        } else if order.size_matched > QTY_EPS {
+            order.status = MockOrderStatus::Matched;
+        } else {
+            order.status = MockOrderStatus::Canceled;
+        }
+
+        let (making, taking) = match req.direction {
+            TradeDirection::Buy => (notional, order.size_matched),
+            TradeDirection::Sell => (order.size_matched, notional),
+        };
+        let status = if order.size_matched > QTY_EPS {
+            "matched"
+        } else {
+            "live"
+        };
+        let response = json!({
+            "success": true,
+            "errorMsg": "",
+            "orderID": order.id,
+            "status": status,
+            "makingAmount": if order.size_matched > QTY_EPS { fmt_num(making) } else { String::new() },
+            "takingAmount": if order.size_matched > QTY_EPS { fmt_num(taking) } else { String::new() },
+            "transactionsHashes": [],
+            "tradeIds": trade_ids,
+        });
+
+        debug!(
+            "🧪 mock CLOB order | id={} {:?} {} {:.2}@{:.2} type={} matched={:.2} rests={}",
+            &order.id[order.id.len().saturating_sub(6)..],
+            side,
+            direction_str(req.direction),
+            req.size,
+            req.price,
+            req.order_type,
+            order.size_matched,
+            rests
+        );
+        self.publish_order_event(&order, "PLACEMENT");
+        self.orders.push(order);
+        if rests {
+            self.publish_level(&req.asset_id, takes_asks, limit_key);
+        }
+        Ok(response)
+    }
+
+    /// Cancel the given live orders. Returns (canceled, not_canceled reasons).
+    fn cancel_where(
+        &mut self,
+        ids: Option<&[String]>,
+        filter: impl Fn(&MockOrder) -> bool,
+    ) -> Value {
+        let mut canceled = Vec::new();
+        let mut not_canceled = serde_json::Map::new();
+        if let Some(ids) = ids {
+            for id in ids {
+                match self.orders.iter().find(|o| &o.id == id) {
+                    None => {
+                        not_canceled.insert(id.clone(), json!("order not found"));
+                    }
+                    Some(o) if !o.is_live() => {
+                        not_canceled.insert(
+                            id.clone(),
+                            json!("order can't be found - already canceled or matched"),
+                        );
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        let mut touched = Vec::new();
+        for o in self.orders.iter_mut() {
+            let selected = ids.is_none_or(|ids| ids.contains(&o.id));
+            if o.is_live() && selected && filter(o) {
+                o.status = MockOrderStatus::Canceled;
+                canceled.push(o.id.clone());
+                touched.push(o.clone());
+            }
+        }
+        for o in &touched {
+            self.publish_order_event(o, "CANCELLATION");
+            self.publish_level(&o.asset_id, o.rests_on_bid(), price_key(o.price));
+        }
+        json!({"canceled": canceled, "not_canceled": not_canceled})
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Server handle
+// ─────────────────────────────────────────────────────────
+
+/// Running mock venue. Dropping the handle stops both listeners.
+pub struct MockClobServer {
+    state: Arc<Mutex<MockState>>,
+    rest_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockClobServer {
+    pub async fn start(cfg: MockClobConfig) -> anyhow::Result<Self> {
+        let rest_listener = TcpListener::bind(cfg.rest_addr).await?;
+        let ws_listener = TcpListener::bind(cfg.ws_addr).await?;
+        let rest_addr = rest_listener.local_addr()?;
+        let ws_addr = ws_listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState::new(cfg)));
+
+        let rest_state = state.clone();
+        let rest_task = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = rest_listener.accept().await else {
+                    continue;
+                };
+                let state = rest_state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_http(stream, state).await {
+                        debug!("mock CLOB REST connection error: {:?}", e);
+                    }
+                });
+            }
+        });
+        let ws_state = state.clone();
+        let ws_task = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = ws_listener.accept().await else {
+                    continue;
+                };
+                let state = ws_state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_ws(stream, state).await {
+                        debug!("mock CLOB WS connection error: {:?}", e);
+                    }
+                });
+            }
+        });
+
+        info!(
+            "🧪 Mock CLOB listening | rest=http://{} ws=ws://{}/ws",
+            rest_addr, ws_addr
+        );
+        Ok(Self {
+            state,
+            rest_addr,
+            ws_addr,
+            tasks: vec![rest_task, ws_task],
+        })
+    }
+
+    /// Value for `POLYMARKET_REST_URL`.
+    pub fn rest_url(&self) -> String {
+        format!("http://{}", self.rest_addr)
+    }
+
+    /// Value for `POLYMARKET_WS_BASE_URL` (`/market` and `/user` are appended).
+    pub fn ws_base_url(&self) -> String {
+        format!("ws://{}/ws", self.ws_addr)
+    }
+
+    pub fn config(&self) -> MockClobConfig {
+        self.lock().cfg.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set street size at one level (`size <= 0` removes it).
+    pub fn set_level(&self, side: Side, is_bid: bool, price: f64, size: f64) {
+        self.lock().set_level(side, is_bid, price, size);
+    }
+
+    /// Replace one outcome's street book and broadcast a fresh `book` snapshot.
+    pub fn set_book(&self, side: Side, bids: &[(f64, f64)], asks: &[(f64, f64)]) {
+        let mut state = self.lock();
+        state.clear_book(side);
+        let asset_id = state.asset_for(side);
+        let book = state.books.entry(asset_id.clone()).or_default();
+        for &(price, size) in bids {
+            book.set(true, price_key(price), size);
+        }
+        for &(price, size) in asks {
+            book.set(false, price_key(price), size);
+        }
+        let event = state.book_event(&asset_id);
+        state.publish_market(event);
+    }
+
+    /// Run a scripted external taker through the book. Returns our filled size.
+    pub fn external_trade(
+        &self,
+        side: Side,
+        taker: TradeDirection,
+        limit_price: f64,
+        size: f64,
+    ) -> f64 {
+        self.lock().external_trade(side, taker, limit_price, size)
+    }
+
+    /// Resolves once at least one user WS client has subscribed, so user events
+    /// published afterwards are guaranteed to reach it.
+    pub async fn wait_for_user_subscriber(&self) {
+        let mut rx = self.lock().user_subscribed.subscribe();
+        let _ = rx.wait_for(|n| *n > 0).await;
+    }
+
+    pub fn orders(&self) -> Vec<MockOrder> {
+        self.lock().orders.clone()
+    }
+
+    pub fn open_orders(&self) -> Vec<MockOrder> {
+        self.lock()
+            .orders
+            .iter()
+            .filter(|o| o.is_live())
+            .cloned()
+            .collect()
+    }
+
+    /// Apply script steps at their `at_ms` offsets from now.
+    pub async fn run_script(&self, steps: &[MockScriptStep]) {
+        let started = tokio::time::Instant::now();
+        for step in steps {
+            tokio::time::sleep_until(started + Duration::from_millis(step.at_ms)).await;
+            match step.op {
+                MockScriptOp::Level {
+                    side,
+                    is_bid,
+                    price,
+                    size,
+                } => self.set_level(side, is_bid, price, size),
+                MockScriptOp::Trade {
+                    side,
+                    taker,
+                    price,
+                    size,
+                } => {
+                    let ours = self.external_trade(side, taker, price, size);
+                    info!(
+                        "🧪 mock CLOB script trade | {:?} taker={} {:.2}@{:.2} ours_filled={:.2}",
+                        side,
+                        direction_str(taker),
+                        size,
+                        price,
+                        ours
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Drop for MockClobServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Script
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MockScriptOp {
+    Level {
+        side: Side,
+        is_bid: bool,
+        price: f64,
+        size: f64,
+    },
+    Trade {
+        side: Side,
+        taker: TradeDirection,
+        price: f64,
+        size: f64,
+    },
+}
+
+/// One line of a JSONL venue script, e.g.
+/// `{"at_ms":0,"op":"level","side":"YES","book":"bid","price":0.48,"size":100}` or
+/// `{"at_ms":1500,"op":"trade","side":"YES","taker":"SELL","price":0.48,"size":20}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MockScriptStep {
+    pub at_ms: u64,
+    pub op: MockScriptOp,
+}
+
+impl MockScriptStep {
+    /// Blank lines and `#` comments yield `Ok(None)`.
+    pub fn parse_line(line: &str) -> anyhow::Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let v: Value = serde_json::from_str(line)?;
+        let text = |key: &str| {
+            v.get(key)
+                .and_then(|x| x.as_str())
+                .map(|s| s.to_ascii_uppercase())
+                .ok_or_else(|| anyhow::anyhow!("script step missing '{}': {}", key, line))
+        };
+        let num = |key: &str| {
+            v.get(key)
+                .and_then(|x| {
+                    x.as_f64()
+                        .or_else(|| x.as_str().and_then(|s| s.parse().ok()))
+                })
+                .ok_or_else(|| anyhow::anyhow!("script step missing '{}': {}", key, line))
+        };
+        let side = match text("side")?.as_str() {
+            "YES" | "UP" => Side::Yes,
+            "NO" | "DOWN" => Side::No,
+            other => anyhow::bail!("unknown script side '{}'", other),
+        };
+        let op = match text("op")?.as_str() {
+            "LEVEL" => MockScriptOp::Level {
+                side,
+                is_bid: match text("book")?.as_str() {
+                    "BID" | "BUY" => true,
+                    "ASK" | "SELL" => false,
+                    other => anyhow::bail!("unknown script book '{}'", other),
+                },
+                price: num("price")?,
+                size: num("size")?,
+            },
+            "TRADE" => MockScriptOp::Trade {
+                side,
+                taker: match text("taker")?.as_str() {
+                    "BUY" => TradeDirection::Buy,
+                    "SELL" => TradeDirection::Sell,
+                    other => anyhow::bail!("unknown script taker '{}'", other),
+                },
+                price: num("price")?,
+                size: num("size")?,
+            },
+            other => anyhow::bail!("unknown script op '{}'", other),
+        };
+        Ok(Some(Self {
+            at_ms: v.get("at_ms").and_then(|x| x.as_u64()).unwrap_or(0),
+            op,
+        }))
+    }
+}
+
+pub fn load_script(path: &Path) -> anyhow::Result<Vec<MockScriptStep>> {
+    let text = std::fs::read_to_string(path)?;
+    let mut steps = Vec::new();
+    for line in text.lines() {
+        if let Some(step) = MockScriptStep::parse_line(line)? {
+            steps.push(step);
+        }
+    }
+    steps.sort_by_key(|s| s.at_ms);
+    Ok(steps)
+}
+
+// ─────────────────────────────────────────────────────────
+// REST
+// ─────────────────────────────────────────────────────────
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+async fn read_http_request(stream: &mut TcpStream) -> anyhow::Result<Option<HttpRequest>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_ascii_uppercase();
+    let target = parts.next().unwrap_or("/").to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            headers.insert(k.trim().to_ascii_lowercase(), v.trim().to_string());
+        }
+    }
+    let len = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await?;
+
+    let (path, query_str) = target.split_once('?').unwrap_or((target.as_str(), ""));
+    let query = url::form_urlencoded::parse(query_str.as_bytes())
+        .into_owned()
+        .collect();
+    Ok(Some(HttpRequest {
+        method,
+        path: path.trim_end_matches('/').to_string(),
+        query,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    }))
+}
+
+async fn serve_http(mut stream: TcpStream, state: Arc<Mutex<MockState>>) -> anyhow::Result<()> {
+    let Some(req) = read_http_request(&mut stream).await? else {
+        return Ok(());
+    };
+    let (status, body) = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        route(&mut state, &req)
+    };
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        _ => "Not Found",
+    };
+    let payload = body.map(|b| b.to_string()).unwrap_or_default();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        status,
+        reason,
+        payload.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(payload.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn route(state: &mut MockState, req: &HttpRequest) -> (u16, Option<Value>) {
+    let ok = |v: Value| (200, Some(v));
+    let bad = |msg: String| (400, Some(json!({ "error": msg })));
+    let body_json = || serde_json::from_str::<Value>(&req.body).unwrap_or(Value::Null);
+
+    // Public routes.
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "") => return ok(json!("OK")),
+        ("GET", "/time") => return ok(json!(now_unix_ms() / 1000)),
+        ("POST", "/auth/api-key") | ("GET", "/auth/derive-api-key") => {
+            return ok(json!({
+                "apiKey": state.cfg.api_key,
+                "secret": state.cfg.api_secret,
+                "passphrase": state.cfg.api_passphrase,
+            }))
+        }
+        ("GET", "/tick-size") => return ok(json!({ "minimum_tick_size": state.cfg.tick_size })),
+        ("GET", "/neg-risk") => return ok(json!({ "neg_risk": false })),
+        ("GET", "/fee-rate") => return ok(json!({ "base_fee": 0 })),
+        ("GET", "/book") => {
+            let token = req.query.get("token_id").cloned().unwrap_or_default();
+            if state.side_for(&token).is_none() {
+                return (
+                    404,
+                    Some(json!({"error": "No orderbook exists for the requested token id"})),
+                );
+            }
+            return ok(state.book_summary(&token));
+        }
+        ("POST", "/books") => {
+            let books: Vec<Value> = body_json()
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|r| r.get("token_id").and_then(|v| v.as_str()))
+                .filter(|token| state.side_for(token).is_some())
+                .map(|token| state.book_summary(token))
+                .collect();
+            return ok(Value::Array(books));
+        }
+        _ => {}
+    }
+
+    let authorized = req
+        .headers
+        .get("poly_api_key")
+        .is_some_and(|k| k.eq_ignore_ascii_case(&state.cfg.api_key));
+    if !authorized {
+        return (401, Some(json!({"error": "Unauthorized/Invalid api key"})));
+    }
+
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/balance-allowance") => ok(json!({
+            "balance": fmt_num(state.cfg.balance_usdc * AMOUNT_SCALE),
+            "allowances": {},
+        })),
+        ("GET", "/balance-allowance/update") => (200, None),
+        ("POST", "/order") => match PlaceRequest::from_body(&body_json()) {
+            Ok(place) => match state.place(place) {
+                Ok(resp) => ok(resp),
+                Err(msg) => bad(msg),
+            },
+            Err(msg) => bad(msg),
+        },
+        ("DELETE", "/order") => {
+            let body = body_json();
+            let Some(id) = body.get("orderId").and_then(|v| v.as_str()) else {
+                return bad("missing orderId".to_string());
+            };
+            ok(state.cancel_where(Some(&[id.to_string()]), |_| true))
+        }
+        ("DELETE", "/orders") => {
+            let ids: Vec<String> = body_json()
+                .as_array()
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            ok(state.cancel_where(Some(&ids), |_| true))
+        }
+        ("DELETE", "/cancel-all") => ok(state.cancel_where(None, |_| true)),
+        ("DELETE", "/cancel-market-orders") => {
+            let body = body_json();
+            let asset = body
+                .get("asset_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            ok(state.cancel_where(None, |o| asset.is_empty() || o.asset_id == asset))
+        }
+        ("GET", "/data/orders") => {
+            let data: Vec<Value> = state
+                .orders
+                .iter()
+                .filter(|o| o.is_live())
+                .filter(|o| req.query.get("id").is_none_or(|id| &o.id == id))
+                .filter(|o| req.query.get("asset_id").is_none_or(|a| &o.asset_id == a))
+                .map(|o| state.order_json(o))
+                .collect();
+            ok(json!({
+                "data": data,
+                "next_cursor": TERMINAL_CURSOR,
+                "limit": 500,
+                "count": data.len(),
+            }))
+        }
+        ("GET", path) if path.starts_with("/data/order/") => {
+            let id = &path["/data/order/".len()..];
+            match state.orders.iter().find(|o| o.id == id) {
+                Some(o) => ok(state.order_json(o)),
+                None => (404, Some(json!({"error": "order not found"}))),
+            }
+        }
+        _ => (404, Some(json!({"error": "not found"}))),
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// WebSocket
+// ─────────────────────────────────────────────────────────
+
+// `accept_hdr_async` callbacks return the full handshake `ErrorResponse`.
+#[allow(clippy::result_large_err)]
+async fn serve_ws(stream: TcpStream, state: Arc<Mutex<MockState>>) -> anyhow::Result<()> {
+    let mut path = String::new();
+    let ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, resp: Response| {
+        path = req.uri().path().trim_end_matches('/').to_string();
+        Ok(resp)
+    })
+    .await?;
+    let (mut write, mut read) = ws.split();
+
+    // First text frame is the subscription.
+    let subscribe = loop {
+        match read.next().await {
+            Some(Ok(Message::Text(text))) if text.trim() != "PING" => {
+                break serde_json::from_str::<Value>(&text).unwrap_or(Value::Null);
+            }
+            Some(Ok(Message::Close(_))) | None => return Ok(()),
+            Some(Err(e)) => return Err(e.into()),
+            _ => {}
+        }
+    };
+
+    // Resolve the channel without holding the state lock across an await.
+    let accepted = {
+        let state = state.lock().unwrap_or_else(|e| e.into_inner());
+        if path.ends_with("/user") {
+            let api_key = subscribe
+                .pointer("/auth/apiKey")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            if api_key.eq_ignore_ascii_case(&state.cfg.api_key) {
+                let rx = state.user_tx.subscribe();
+                state.user_subscribed.send_modify(|n| *n += 1);
+                Some((rx, None))
+            } else {
+                warn!("🧪 mock CLOB user WS rejected subscription (bad apiKey)");
+                None
+            }
+        } else if path.ends_with("/market") {
+            let assets: Vec<String> = ["assets_ids", "asset_ids"]
+                .iter()
+                .filter_map(|k| subscribe.get(*k).and_then(|v| v.as_array()))
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            let dump: Vec<Value> = [&state.cfg.yes_asset_id, &state.cfg.no_asset_id]
+                .into_iter()
+                .filter(|a| assets.is_empty() || assets.contains(a))
+                .map(|a| state.book_event(a))
+                .collect();
+            Some((
+                state.market_tx.subscribe(),
+                Some(Value::Array(dump).to_string()),
+            ))
+        } else {
+            None
+        }
+    };
+    let Some((mut rx, initial_dump)) = accepted else {
+        let _ = write.send(Message::Close(None)).await;
+        return Ok(());
+    };
+    if let Some(dump) = initial_dump {
+        write.send(Message::Text(dump)).await?;
+    }
+
+    loop {
+        tokio::select! {
+            incoming = read.next() => match incoming {
+                Some(Ok(Message::Text(text))) if text.trim() == "PING" => {
+                    write.send(Message::Text("PONG".to_string())).await?;
+                }
+                Some(Ok(Message::Ping(p))) => write.send(Message::Pong(p)).await?,
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Err(e)) => return Err(e.into()),
+                _ => {}
+            },
+            event = rx.recv() => match event {
+                Ok(text) => write.send(Message::Text(text)).await?,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("🧪 mock CLOB WS subscriber lagged {} events", n);
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::clob_v2::{
+        build_signed_limit_order_v2, post_order_v2, OrderSizingV2, V2OrderContext,
+        V2_STANDARD_CONFIG,
+    };
+    use crate::polymarket::executor::init_clob_client;
+    use crate::polymarket::messages::FillEvent;
+    use crate::polymarket::user_ws::{UserWsConfig, UserWsListener};
+    use alloy::primitives::{B256, U256};
+    use polymarket_client_sdk::clob::types::{OrderStatusType, OrderType, SignatureType};
+    use tokio::sync::mpsc;
+
+    // Well-known local devnet key; never funded on Polygon.
+    const TEST_PK: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn place(direction: TradeDirection, price: f64, size: f64, post_only: bool) -> PlaceRequest {
+        PlaceRequest {
+            asset_id: MockClobConfig::default().yes_asset_id,
+            direction,
+            price,
+            size,
+            order_type: if post_only { "GTC" } else { "FAK" }.to_string(),
+            post_only,
+            maker_address: String::new(),
+        }
+    }
+
+    #[test]
+    fn resting_bid_fills_only_after_street_queue_ahead() {
+        let mut state = MockState::new(MockClobConfig::default());
+        state.set_level(Side::Yes, true, 0.48, 30.0);
+        state.set_level(Side::Yes, false, 0.52, 50.0);
+
+        let crossing = state.place(place(TradeDirection::Buy, 0.52, 10.0, true));
+        assert!(crossing.unwrap_err().contains("order crosses book"));
+
+        state
+            .place(place(TradeDirection::Buy, 0.48, 10.0, true))
+            .unwrap();
+        assert_eq!(
+            state.visible_size(&state.asset_for(Side::Yes), true, price_key(0.48)),
+            40.0
+        );
+
+        // 25 of the 30 ahead trade — still queued.
+        assert_eq!(
+            state.external_trade(Side::Yes, TradeDirection::Sell, 0.48, 25.0),
+            0.0
+        );
+        // Next 12: 5 clears the queue, 7 fills us.
+        let filled = state.external_trade(Side::Yes, TradeDirection::Sell, 0.48, 12.0);
+        assert!((filled - 7.0).abs() < 1e-9);
+        let order = &state.orders[0];
+        assert_eq!(order.status, MockOrderStatus::Live);
+        assert!((order.remaining() - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn marketable_orders_sweep_street_liquidity() {
+        let mut state = MockState::new(MockClobConfig::default());
+        state.set_level(Side::Yes, false, 0.52, 4.0);
+        state.set_level(Side::Yes, false, 0.53, 10.0);
+
+        let resp = state
+            .place(place(TradeDirection::Buy, 0.53, 6.0, false))
+            .unwrap();
+        assert_eq!(resp["status"], "matched");
+        assert_eq!(resp["takingAmount"], "6");
+        let asset = state.asset_for(Side::Yes);
+        assert_eq!(state.best_visible(&asset, false), Some(0.53));
+        assert_eq!(state.visible_size(&asset, false, price_key(0.53)), 8.0);
+        assert_eq!(state.orders[0].status, MockOrderStatus::Matched);
+
+        let miss = state.place(place(TradeDirection::Buy, 0.50, 6.0, false));
+        assert!(miss.unwrap_err().contains("no orders found to match"));
+    }
+
+    #[test]
+    fn script_lines_parse_levels_and_trades() {
+        let level = MockScriptStep::parse_line(
+            r#"{"at_ms":0,"op":"level","side":"YES","book":"bid","price":0.48,"size":100}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            level.op,
+            MockScriptOp::Level {
+                side: Side::Yes,
+                is_bid: true,
+                price: 0.48,
+                size: 100.0
+            }
+        );
+        let trade = MockScriptStep::parse_line(
+            r#"{"at_ms":1500,"op":"trade","side":"down","taker":"BUY","price":"0.55","size":"20"}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(trade.at_ms, 1500);
+        assert!(matches!(
+            trade.op,
+            MockScriptOp::Trade {
+                side: Side::No,
+                taker: TradeDirection::Buy,
+                ..
+            }
+        ));
+        assert!(MockScriptStep::parse_line("# comment").unwrap().is_none());
+        assert!(MockScriptStep::parse_line(r#"{"op":"nope","side":"YES"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn live_client_posts_orders_and_receives_user_ws_fills() {
+        let server = MockClobServer::start(MockClobConfig::default())
+            .await
+            .unwrap();
+        let cfg = server.config();
+        server.set_book(Side::Yes, &[(0.48, 20.0)], &[(0.52, 20.0)]);
+
+        let (client, signer) =
+            init_clob_client(&server.rest_url(), Some(TEST_PK), None, None).await;
+        let client = client.expect("mock CLOB auth");
+        let signer = signer.unwrap();
+        let ctx = |direction| V2OrderContext {
+            exchange: V2_STANDARD_CONFIG.exchange,
+            maker: signer.address(),
+            token_id: U256::from_str_radix(&cfg.yes_asset_id, 10).unwrap(),
+            direction,
+            signature_type: SignatureType::Eoa,
+            expiration: 0,
+            metadata: B256::ZERO,
+            builder: B256::ZERO,
+        };
+
+        let (fill_tx, mut fill_rx) = mpsc::channel::<FillEvent>(16);
+        let listener = UserWsListener::new(
+            UserWsConfig {
+                ws_base_url: server.ws_base_url(),
+                api_key: cfg.api_key.clone(),
+                api_secret: cfg.api_secret.clone(),
+                api_passphrase: cfg.api_passphrase.clone(),
+                market_id: cfg.market_id.clone(),
+                yes_asset_id: cfg.yes_asset_id.clone(),
+                no_asset_id: cfg.no_asset_id.clone(),
+            },
+            fill_tx,
+        );
+        let ws_task = tokio::spawn(listener.run());
+
+        let signed = build_signed_limit_order_v2(
+            &signer,
+            137,
+            ctx(TradeDirection::Buy),
+            OrderSizingV2 {
+                price: 0.52,
+                size_shares: 5.0,
+            },
+        )
+        .await
+        .unwrap();
+        let err = post_order_v2(&client, &signed, OrderType::GTC, true, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("order crosses book"));
+
+        let signed = build_signed_limit_order_v2(
+            &signer,
+            137,
+            ctx(TradeDirection::Buy),
+            OrderSizingV2 {
+                price: 0.48,
+                size_shares: 5.0,
+            },
+        )
+        .await
+        .unwrap();
+        let resp = post_order_v2(&client, &signed, OrderType::GTC, true, false)
+            .await
+            .unwrap();
+        assert!(resp.success);
+        assert_eq!(resp.status, OrderStatusType::Live);
+        let open = client.order(&resp.order_id).await.unwrap();
+        assert_eq!(open.status, OrderStatusType::Live);
+
+        // The scripted taker must print only after the user WS has subscribed.
+        tokio::time::timeout(Duration::from_secs(5), server.wait_for_user_subscriber())
+            .await
+            .unwrap();
+        let ours = server.external_trade(Side::Yes, TradeDirection::Sell, 0.48, 23.0);
+        assert!((ours - 3.0).abs() < 1e-9);
+
+        let fill = tokio::time::timeout(Duration::from_secs(5), fill_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fill.order_id, resp.order_id);
+        assert_eq!(fill.side, Side::Yes);
+        assert!((fill.filled_size - 3.0).abs() < 1e-9);
+
+        let canceled = client.cancel_order(&resp.order_id).await.unwrap();
+        assert_eq!(canceled.canceled, vec![resp.order_id.clone()]);
+        assert!(server.open_orders().is_empty());
+        ws_task.abort();
+    }
+}
@@ -1,16 +1,36 @@
 // ─── Polymarket V2 Core Modules ───
+pub mod actor_state_store;
+pub mod backtest;
 pub mod claims;
 pub mod clob_v2;
+pub mod clock;
+pub mod config_file;
+pub mod config_validation;
 pub mod coordinator;
+pub mod data_streams;
 pub mod executor;
+pub mod fees;
 pub mod glft;
+pub mod glft_fit_store;
 pub mod inventory;
+pub mod inventory_recovery;
+pub mod l2_book;
 pub mod messages;
+pub mod metrics;
+pub mod mock_clob;
 pub mod ofi;
+pub mod ofi_attribution;
+pub mod ofi_toxicity;
 pub mod order_manager;
 pub mod pair_ledger;
+pub mod pnl_ledger;
+pub mod portfolio_risk;
+pub mod price_agg;
 pub mod recorder;
+pub mod replay;
+pub mod rtds_pool;
 pub mod strategy;
+pub mod sweep;
 pub mod user_ws;
 pub mod xuan_b27_dplus_correlation;
 pub mod xuan_b27_dplus_execution_controller;
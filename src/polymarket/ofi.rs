@@ -11,9 +11,16 @@ use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use tracing::{info, warn};
 
+use super::clock::{system_clock, SharedClock};
+use super::config_validation::{
+    at_least_reported, clamp_reported, env_checked, report_config_issue, ConfigIssueKind,
+};
 use super::messages::{
-    KillSwitchSignal, MarketDataMsg, OfiSnapshot, SideOfi, TakerSide, TradeDirection,
+    KillSwitchSignal, MarketDataMsg, OfiSnapshot, SideOfi, TakerSide, ToxicCancelReport,
+    TradeDirection,
 };
+use super::ofi_attribution::OfiAttribution;
+use super::ofi_toxicity::{build_toxicity_model, FlowTrade, ToxicityModel, ToxicityModelKind};
 use super::types::Side;
 
 /// Tail quantiles for regime-aware OFI gating.
@@ -94,6 +101,26 @@ pub struct OfiConfig {
     /// Minimum toxic hold duration before allowing recovery (ms).
     /// Default: 800ms.
     pub min_toxic_ms: u64,
+
+    // ── Toxicity model ─────────────────────────────────────────
+    /// Which `ToxicityModel` scores each side. The raw score is rescaled so the
+    /// model's own threshold lands on `toxicity_threshold`. Default: window.
+    pub toxicity_model: ToxicityModelKind,
+    /// Per-market overrides as (slug prefix, model); longest prefix wins.
+    /// Applied by `for_market`.
+    pub toxicity_model_by_market: Vec<(String, ToxicityModelKind)>,
+    /// VPIN bucket size in shares. Default: 50.
+    pub vpin_bucket_volume: f64,
+    /// Number of closed VPIN buckets averaged. Default: 10.
+    pub vpin_buckets: usize,
+    /// Toxic VPIN level (0..1). Default: 0.6.
+    pub vpin_threshold: f64,
+    /// Hawkes kernel half-life. Default: 1500ms.
+    pub hawkes_half_life: Duration,
+    /// Toxic Hawkes intensity imbalance (shares/s). Default: 60.
+    pub hawkes_threshold: f64,
+    /// Toxic trade-to-mid adverse move (size × ticks). Default: 100.
+    pub adverse_move_threshold: f64,
 }
 
 impl Default for OfiConfig {
@@ -113,6 +140,14 @@ impl Default for OfiConfig {
             toxicity_ratio_exit: 0.30,
             toxicity_exit_ratio: 0.85,
             min_toxic_ms: 800,
+            toxicity_model: ToxicityModelKind::Window,
+            toxicity_model_by_market: Vec::new(),
+            vpin_bucket_volume: 50.0,
+            vpin_buckets: 10,
+            vpin_threshold: 0.6,
+            hawkes_half_life: Duration::from_millis(1500),
+            hawkes_threshold: 60.0,
+            adverse_move_threshold: 100.0,
         }
     }
 }
@@ -121,12 +156,10 @@ impl OfiConfig {
     /// Load overrides from environment variables (if set).
     pub fn from_env() -> Self {
         let mut cfg = Self::default();
-        if let Ok(v) = std::env::var("PM_TICK_SIZE") {
-            if let Ok(f) = v.parse::<f64>() {
-                if (0.0..1.0).contains(&f) {
-                    cfg.tick_size = f;
-                }
-            }
+        if let Some(f) = env_checked::<f64>("PM_TICK_SIZE", "must satisfy 0 <= tick < 1", |f| {
+            (0.0..1.0).contains(f)
+        }) {
+            cfg.tick_size = f;
         }
         if let Ok(v) = std::env::var("PM_OFI_WINDOW_MS") {
             if let Ok(ms) = v.parse::<u64>() {
@@ -148,12 +181,12 @@ impl OfiConfig {
         }
         if let Ok(v) = std::env::var("PM_OFI_ADAPTIVE_K") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.adaptive_k = f.max(0.5);
+                cfg.adaptive_k = at_least_reported("PM_OFI_ADAPTIVE_K", f, 0.5);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_ADAPTIVE_MIN") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.adaptive_min = f.max(1.0);
+                cfg.adaptive_min = at_least_reported("PM_OFI_ADAPTIVE_MIN", f, 1.0);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_ADAPTIVE_MAX") {
@@ -161,36 +194,45 @@ impl OfiConfig {
                 cfg.adaptive_max = if f <= 0.0 {
                     0.0 // 0 => no hard upper cap
                 } else {
-                    f.max(cfg.adaptive_min)
+                    at_least_reported("PM_OFI_ADAPTIVE_MAX", f, cfg.adaptive_min)
                 };
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_ADAPTIVE_RISE_CAP_PCT") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.adaptive_rise_cap_pct = f.clamp(0.0, 5.0);
+                cfg.adaptive_rise_cap_pct =
+                    clamp_reported("PM_OFI_ADAPTIVE_RISE_CAP_PCT", f, 0.0, 5.0);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_ADAPTIVE_WINDOW") {
             if let Ok(n) = v.parse::<usize>() {
-                cfg.adaptive_window = n.max(10);
+                cfg.adaptive_window = at_least_reported("PM_OFI_ADAPTIVE_WINDOW", n, 10);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_RATIO_ENTER") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.toxicity_ratio_enter = f.clamp(0.0, 1.0);
+                cfg.toxicity_ratio_enter = clamp_reported("PM_OFI_RATIO_ENTER", f, 0.0, 1.0);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_RATIO_EXIT") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.toxicity_ratio_exit = f.clamp(0.0, 1.0);
+                cfg.toxicity_ratio_exit = clamp_reported("PM_OFI_RATIO_EXIT", f, 0.0, 1.0);
             }
         }
         if cfg.toxicity_ratio_exit > cfg.toxicity_ratio_enter {
+            report_config_issue(
+                ConfigIssueKind::Conflict,
+                "PM_OFI_RATIO_EXIT",
+                format!(
+                    "exit {} > enter {}; lowered to enter",
+                    cfg.toxicity_ratio_exit, cfg.toxicity_ratio_enter
+                ),
+            );
             cfg.toxicity_ratio_exit = cfg.toxicity_ratio_enter;
         }
         if let Ok(v) = std::env::var("PM_OFI_EXIT_RATIO") {
             if let Ok(f) = v.parse::<f64>() {
-                cfg.toxicity_exit_ratio = f.clamp(0.05, 0.99);
+                cfg.toxicity_exit_ratio = clamp_reported("PM_OFI_EXIT_RATIO", f, 0.05, 0.99);
             }
         }
         if let Ok(v) = std::env::var("PM_OFI_MIN_TOXIC_MS") {
@@ -198,21 +240,91 @@ impl OfiConfig {
                 cfg.min_toxic_ms = ms;
             }
         }
+        if let Ok(v) = std::env::var("PM_OFI_TOXICITY_MODEL") {
+            match ToxicityModelKind::parse(&v) {
+                Some(kind) => cfg.toxicity_model = kind,
+                None => {
+                    warn!("⚠️ PM_OFI_TOXICITY_MODEL='{}' unknown, using window", v);
+                    report_config_issue(
+                        ConfigIssueKind::Invalid,
+                        "PM_OFI_TOXICITY_MODEL",
+                        format!("'{}' (supported: window, vpin, hawkes, adverse_move)", v),
+                    );
+                }
+            }
+        }
+        if let Ok(v) = std::env::var("PM_OFI_TOXICITY_MODEL_BY_MARKET") {
+            cfg.toxicity_model_by_market = parse_model_overrides(&v);
+        }
+        if let Some(f) =
+            env_checked::<f64>("PM_OFI_VPIN_BUCKET_VOLUME", "must be > 0", |f| *f > 0.0)
+        {
+            cfg.vpin_bucket_volume = f;
+        }
+        if let Ok(v) = std::env::var("PM_OFI_VPIN_BUCKETS") {
+            if let Ok(n) = v.parse::<usize>() {
+                cfg.vpin_buckets = at_least_reported("PM_OFI_VPIN_BUCKETS", n, 1);
+            }
+        }
+        if let Ok(v) = std::env::var("PM_OFI_VPIN_THRESHOLD") {
+            if let Ok(f) = v.parse::<f64>() {
+                cfg.vpin_threshold = clamp_reported("PM_OFI_VPIN_THRESHOLD", f, 0.01, 1.0);
+            }
+        }
+        if let Ok(v) = std::env::var("PM_OFI_HAWKES_HALF_LIFE_MS") {
+            if let Ok(ms) = v.parse::<u64>() {
+                cfg.hawkes_half_life =
+                    Duration::from_millis(at_least_reported("PM_OFI_HAWKES_HALF_LIFE_MS", ms, 1));
+            }
+        }
+        if let Some(f) = env_checked::<f64>("PM_OFI_HAWKES_THRESHOLD", "must be > 0", |f| *f > 0.0)
+        {
+            cfg.hawkes_threshold = f;
+        }
+        if let Some(f) = env_checked::<f64>("PM_OFI_ADVERSE_THRESHOLD", "must be > 0", |f| *f > 0.0)
+        {
+            cfg.adverse_move_threshold = f;
+        }
         cfg
     }
+
+    /// Resolve the per-market toxicity model for `slug` (longest matching prefix).
+    pub fn for_market(mut self, slug: &str) -> Self {
+        if let Some((_, kind)) = self
+            .toxicity_model_by_market
+            .iter()
+            .filter(|(prefix, _)| slug.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+        {
+            self.toxicity_model = *kind;
+        }
+        self
+    }
+}
+
+/// Parse `btc-updown=vpin,eth-updown-15m=hawkes`; malformed entries are skipped.
+fn parse_model_overrides(raw: &str) -> Vec<(String, ToxicityModelKind)> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (prefix, model) = entry.split_once('=')?;
+            let prefix = prefix.trim();
+            let kind = ToxicityModelKind::parse(model);
+            if prefix.is_empty() || kind.is_none() {
+                warn!(
+                    "⚠️ PM_OFI_TOXICITY_MODEL_BY_MARKET entry '{}' ignored",
+                    entry
+                );
+                return None;
+            }
+            Some((prefix.to_string(), kind?))
+        })
+        .collect()
 }
 
 // ─────────────────────────────────────────────────────────
-// Internal tick record
+// Side window
 // ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
-struct TradeTick {
-    taker_side: TakerSide,
-    size: f64,
-    ts: Instant,
-}
-
 /// Hard cap on ticks per side window.
 /// Prevents unbounded memory growth in high-frequency markets where thousands
 /// of trades can arrive within the 3-second sliding window.
@@ -222,7 +334,7 @@ const MAX_WINDOW_TICKS: usize = 4096;
 /// Per-side sliding window.
 #[derive(Debug)]
 struct SideWindow {
-    ticks: VecDeque<TradeTick>,
+    ticks: VecDeque<FlowTrade>,
 }
 
 impl SideWindow {
@@ -232,16 +344,17 @@ impl SideWindow {
         }
     }
 
-    fn push(&mut self, taker_side: TakerSide, size: f64, ts: Instant) {
+    fn push(&mut self, taker_side: TakerSide, size: f64, price: f64, ts: Instant) {
         // ISSUE 9 FIX: Enforce capacity cap before inserting.
         // Previously the VecDeque could grow without bound in active markets,
         // making each heartbeat O(n) on a potentially huge collection.
         if self.ticks.len() >= MAX_WINDOW_TICKS {
             self.ticks.pop_front(); // Drop oldest tick to stay within budget.
         }
-        self.ticks.push_back(TradeTick {
+        self.ticks.push_back(FlowTrade {
             taker_side,
             size,
+            price,
             ts,
         });
     }
@@ -399,7 +512,7 @@ fn evaluate_flow_side(
 ) -> FlowSideEval {
     let abs = side_ofi.ofi_score.abs();
     let total = (side_ofi.buy_volume + side_ofi.sell_volume).max(1e-9);
-    let ratio = abs / total;
+    let ratio = (side_ofi.buy_volume - side_ofi.sell_volume).abs() / total;
     let norm_score = abs / baseline.max(1.0);
     state.smoothed_heat_score = if state.smoothed_heat_score <= f64::EPSILON {
         norm_score
@@ -676,6 +789,8 @@ pub struct OfiEngine {
     cfg: OfiConfig,
     yes_window: SideWindow,
     no_window: SideWindow,
+    yes_model: Box<dyn ToxicityModel>,
+    no_model: Box<dyn ToxicityModel>,
     md_rx: mpsc::Receiver<MarketDataMsg>,
     snapshot_tx: watch::Sender<OfiSnapshot>,
     /// Opt-2: Per-side rolling history of |ofi_score| observations.
@@ -701,6 +816,12 @@ pub struct OfiEngine {
     /// Opt-4: High-priority kill channel to the Coordinator (edge-triggered on toxicity onset).
     /// None when running without kill channel wiring (backward-compatible).
     kill_tx: Option<mpsc::Sender<KillSwitchSignal>>,
+    /// Kill-switch attribution; fed with episodes, trades and ToxicFlow cancels.
+    attribution: Option<OfiAttribution>,
+    /// ToxicFlow cancels reported back by the Coordinator (dead channel when unwired).
+    toxic_cancel_rx: mpsc::Receiver<ToxicCancelReport>,
+    /// Time source for window eviction (system clock unless injected).
+    clock: SharedClock,
 }
 
 impl OfiEngine {
@@ -720,10 +841,15 @@ impl OfiEngine {
             initial_threshold.max(cfg.adaptive_min)
         };
         let adaptive_window = cfg.adaptive_window;
+        let yes_model = build_toxicity_model(&cfg);
+        let no_model = build_toxicity_model(&cfg);
+        let (_dead_toxic_cancel_tx, dead_toxic_cancel_rx) = mpsc::channel(1);
         Self {
             cfg,
             yes_window: SideWindow::new(),
             no_window: SideWindow::new(),
+            yes_model,
+            no_model,
             md_rx,
             snapshot_tx,
             yes_score_history: VecDeque::new(),
@@ -744,6 +870,9 @@ impl OfiEngine {
             no_threshold_saturated: false,
             reference_mid_yes: 0.5,
             kill_tx: None,
+            attribution: None,
+            toxic_cancel_rx: dead_toxic_cancel_rx,
+            clock: system_clock(),
         }
     }
 
@@ -755,6 +884,62 @@ impl OfiEngine {
         self
     }
 
+    /// Inject the time source used for window eviction.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Track kill-switch effectiveness; `toxic_cancel_rx` carries the quotes the
+    /// Coordinator pulled under `CancelReason::ToxicFlow`.
+    pub fn with_attribution(
+        mut self,
+        attribution: OfiAttribution,
+        toxic_cancel_rx: mpsc::Receiver<ToxicCancelReport>,
+    ) -> Self {
+        self.attribution = Some(attribution);
+        self.toxic_cancel_rx = toxic_cancel_rx;
+        self
+    }
+
+    /// Replace the configured toxicity models (e.g. a custom `ToxicityModel`).
+    pub fn with_toxicity_models(
+        mut self,
+        yes_model: Box<dyn ToxicityModel>,
+        no_model: Box<dyn ToxicityModel>,
+    ) -> Self {
+        self.yes_model = yes_model;
+        self.no_model = no_model;
+        self
+    }
+
+    /// Window volumes plus the model score, rescaled onto `toxicity_threshold`
+    /// so thresholds, adaptive quantiles and kill signals stay model-agnostic.
+    fn score_side(&mut self, side: Side, now: Instant) -> SideOfi {
+        let (window, model, side_mid) = match side {
+            Side::Yes => (
+                &self.yes_window,
+                &mut self.yes_model,
+                self.reference_mid_yes,
+            ),
+            Side::No => (
+                &self.no_window,
+                &mut self.no_model,
+                1.0 - self.reference_mid_yes,
+            ),
+        };
+        let mut side_ofi = window.compute();
+        let raw = model.score(&window.ticks, side_mid, now);
+        let model_threshold = model.threshold();
+        let scale = if model_threshold > f64::EPSILON {
+            self.cfg.toxicity_threshold / model_threshold
+        } else {
+            1.0
+        };
+        side_ofi.ofi_score = raw * scale;
+        side_ofi
+    }
+
     /// Opt-2: Update per-side adaptive thresholds from per-side histories.
     fn update_adaptive_thresholds(&mut self, yes_score: f64, no_score: f64) {
         if !self.cfg.adaptive_threshold {
@@ -874,7 +1059,8 @@ impl OfiEngine {
             "off".to_string()
         };
         info!(
-            "🔬 OFI Engine started | window={}ms threshold={:.1} heartbeat={}ms adaptive={} mode={}",
+            "🔬 OFI Engine started | model={} window={}ms threshold={:.1} heartbeat={}ms adaptive={} mode={}",
+            self.yes_model.kind().as_str(),
             self.cfg.window_duration.as_millis(),
             self.cfg.toxicity_threshold,
             self.cfg.heartbeat_ms,
@@ -912,10 +1098,21 @@ impl OfiEngine {
             tokio::select! {
                 msg = self.md_rx.recv() => {
                     match msg {
-                        Some(MarketDataMsg::TradeTick { market_side, taker_side, size, ts, .. }) => {
+                        Some(MarketDataMsg::TradeTick { market_side, taker_side, price, size, ts, .. }) => {
+                            let trade = FlowTrade { taker_side, size, price, ts };
+                            if let Some(attribution) = &self.attribution {
+                                attribution
+                                    .with_tracker(|t| t.on_trade(market_side, taker_side, price, size, ts));
+                            }
                             match market_side {
-                                Side::Yes => self.yes_window.push(taker_side, size, ts),
-                                Side::No => self.no_window.push(taker_side, size, ts),
+                                Side::Yes => {
+                                    self.yes_model.on_trade(&trade);
+                                    self.yes_window.push(taker_side, size, price, ts);
+                                }
+                                Side::No => {
+                                    self.no_model.on_trade(&trade);
+                                    self.no_window.push(taker_side, size, price, ts);
+                                }
                             }
                         }
                         Some(MarketDataMsg::BookTick { yes_bid, yes_ask, no_bid, no_ask, .. }) => {
@@ -936,21 +1133,27 @@ impl OfiEngine {
                         None => break, // Channel closed
                     }
                 }
+                Some(report) = self.toxic_cancel_rx.recv() => {
+                    if let Some(attribution) = &self.attribution {
+                        attribution.with_tracker(|t| t.on_cancel(&report));
+                    }
+                    continue;
+                }
                 _ = ticker.tick() => {
                     heartbeat_fired = true;
                     // Force time-based eviction and broadcast
                 }
             }
 
-            let now = Instant::now();
+            let now = self.clock.now();
 
-            // Evict expired ticks from both windows using real-time clock
+            // Evict expired ticks from both windows using the injected clock
             self.yes_window.evict_expired(now, self.cfg.window_duration);
             self.no_window.evict_expired(now, self.cfg.window_duration);
 
             // Compute per-side snapshots using current per-side thresholds.
-            let yes_ofi = self.yes_window.compute();
-            let no_ofi = self.no_window.compute();
+            let yes_ofi = self.score_side(Side::Yes, now);
+            let no_ofi = self.score_side(Side::No, now);
 
             let yes_baseline = if self.cfg.adaptive_threshold {
                 self.yes_regime_baseline.max(1.0)
@@ -1041,6 +1244,29 @@ impl OfiEngine {
 
             let _ = self.snapshot_tx.send(snapshot);
 
+            if let Some(attribution) = &self.attribution {
+                attribution.with_tracker(|t| {
+                    for (side, eval) in [(Side::Yes, &yes_eval), (Side::No, &no_eval)] {
+                        if eval.toxic_entered {
+                            t.on_toxic_enter(
+                                side,
+                                eval.side_ofi.toxic_buy,
+                                eval.side_ofi.ofi_score,
+                                self.reference_mid_yes,
+                                now,
+                            );
+                        } else if eval.toxic_recovered {
+                            t.on_toxic_exit(side, now);
+                        } else if eval.side_ofi.is_toxic {
+                            t.on_toxic_score(side, eval.side_ofi.ofi_score);
+                        }
+                    }
+                });
+                if heartbeat_fired {
+                    attribution.poll(self.reference_mid_yes, now);
+                }
+            }
+
             if heartbeat_fired {
                 if yes_saturated {
                     yes_state.saturated_count = yes_state.saturated_count.saturating_add(1);
@@ -1304,12 +1530,12 @@ mod tests {
         let now = Instant::now();
 
         // YES side: heavy buying
-        engine.yes_window.push(TakerSide::Buy, 15.0, now);
-        engine.yes_window.push(TakerSide::Sell, 2.0, now);
+        engine.yes_window.push(TakerSide::Buy, 15.0, 0.5, now);
+        engine.yes_window.push(TakerSide::Sell, 2.0, 0.5, now);
 
         // NO side: balanced
-        engine.no_window.push(TakerSide::Buy, 5.0, now);
-        engine.no_window.push(TakerSide::Sell, 4.0, now);
+        engine.no_window.push(TakerSide::Buy, 5.0, 0.5, now);
+        engine.no_window.push(TakerSide::Sell, 4.0, 0.5, now);
 
         let yes_ofi = engine.yes_window.compute();
         let no_ofi = engine.no_window.compute();
@@ -1324,8 +1550,8 @@ mod tests {
         let now = Instant::now();
 
         // NO side: heavy selling (panic dump)
-        engine.no_window.push(TakerSide::Sell, 20.0, now);
-        engine.no_window.push(TakerSide::Buy, 3.0, now);
+        engine.no_window.push(TakerSide::Sell, 20.0, 0.5, now);
+        engine.no_window.push(TakerSide::Buy, 3.0, 0.5, now);
 
         let no_ofi = engine.no_window.compute();
         assert!((no_ofi.ofi_score - (-17.0)).abs() < 1e-9);
@@ -1337,11 +1563,11 @@ mod tests {
         let t0 = Instant::now();
 
         // Old tick on YES
-        engine.yes_window.push(TakerSide::Buy, 100.0, t0);
+        engine.yes_window.push(TakerSide::Buy, 100.0, 0.5, t0);
 
         // 4 seconds later
         let t1 = t0 + Duration::from_secs(4);
-        engine.yes_window.push(TakerSide::Sell, 1.0, t1);
+        engine.yes_window.push(TakerSide::Sell, 1.0, 0.5, t1);
         engine.yes_window.evict_expired(t1, Duration::from_secs(3));
 
         let yes_ofi = engine.yes_window.compute();
@@ -1365,11 +1591,11 @@ mod tests {
         let now = Instant::now();
 
         // YES: toxic (heavy sell = dumping)
-        engine.yes_window.push(TakerSide::Sell, 50.0, now);
+        engine.yes_window.push(TakerSide::Sell, 50.0, 0.5, now);
 
         // NO: safe
-        engine.no_window.push(TakerSide::Buy, 3.0, now);
-        engine.no_window.push(TakerSide::Sell, 2.0, now);
+        engine.no_window.push(TakerSide::Buy, 3.0, 0.5, now);
+        engine.no_window.push(TakerSide::Sell, 2.0, 0.5, now);
 
         let yes_ofi = engine.yes_window.compute();
         let no_ofi = engine.no_window.compute();
@@ -1380,6 +1606,46 @@ mod tests {
         assert!(no_ofi.ofi_score > -10.0);
     }
 
+    #[test]
+    fn test_toxicity_model_resolves_per_market_and_rescales() {
+        let cfg = OfiConfig {
+            toxicity_threshold: 10.0,
+            toxicity_model_by_market: parse_model_overrides(
+                "btc-updown=vpin, btc-updown-15m=hawkes,bogus=nope",
+            ),
+            vpin_bucket_volume: 10.0,
+            vpin_threshold: 0.5,
+            ..OfiConfig::default()
+        };
+        assert_eq!(cfg.toxicity_model_by_market.len(), 2);
+        assert_eq!(
+            cfg.clone().for_market("btc-updown-15m").toxicity_model,
+            ToxicityModelKind::Hawkes
+        );
+        assert_eq!(
+            cfg.clone().for_market("eth-updown-5m").toxicity_model,
+            ToxicityModelKind::Window
+        );
+
+        let cfg = cfg.for_market("btc-updown-5m");
+        let (_tx, rx) = mpsc::channel(16);
+        let (snap_tx, _snap_rx) = watch::channel(OfiSnapshot::default());
+        let mut engine = OfiEngine::new(cfg, rx, snap_tx);
+        let now = Instant::now();
+        let trade = FlowTrade {
+            taker_side: TakerSide::Sell,
+            size: 10.0,
+            price: 0.5,
+            ts: now,
+        };
+        engine.no_model.on_trade(&trade);
+        engine.no_window.push(TakerSide::Sell, 10.0, 0.5, now);
+        // One all-sell bucket -> VPIN 1.0, i.e. 2x the model threshold -> 2x toxicity_threshold.
+        let no_ofi = engine.score_side(Side::No, now);
+        assert!((no_ofi.ofi_score - (-20.0)).abs() < 1e-9);
+        assert!((no_ofi.sell_volume - 10.0).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn test_ratio_gate_avoids_high_volume_balanced_false_toxic() {
         let cfg = OfiConfig {
@@ -0,0 +1,781 @@
+//! OFI kill-switch attribution.
+//!
+//! Every confirmed toxic episode is tracked from onset to recovery together with
+//! the quotes the Coordinator pulled under `CancelReason::ToxicFlow`. After
+//! `horizon` the episode is scored: how far the side mid moved in the harmful
+//! direction, and which public trades would have filled the cancelled quotes.
+//! Marking those would-be fills to the horizon mid splits each kill into saved
+//! loss (fill would have lost money) or missed profit (fill would have been
+//! fine). Per-round summaries go to `<dir>/rounds.jsonl`; `<dir>/<market>.json`
+//! keeps running totals over all rounds of a market so `toxicity_threshold` can
+//! be tuned from evidence. Each round is folded into the stored totals; the
+//! rounds file is only rescanned when that report is missing or unreadable.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::messages::{TakerSide, ToxicCancelReport, TradeDirection};
+use super::types::Side;
+
+/// Cap on finished-but-unreported episodes held per round.
+const MAX_ROUND_EPISODES: usize = 512;
+
+#[derive(Debug, Clone)]
+pub struct OfiAttributionConfig {
+    pub enabled: bool,
+    /// Look-ahead window for mid move and would-be fills.
+    pub horizon: Duration,
+    /// Episodes whose harmful mid move stays below this many ticks are false positives.
+    pub false_positive_ticks: f64,
+    pub dir: PathBuf,
+}
+
+impl Default for OfiAttributionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            horizon: Duration::from_secs(10),
+            false_positive_ticks: 1.0,
+            dir: PathBuf::from("data/ofi_attribution"),
+        }
+    }
+}
+
+impl OfiAttributionConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(v) = std::env::var("PM_OFI_ATTRIBUTION_ENABLED") {
+            cfg.enabled = !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no");
+        }
+        if let Ok(v) = std::env::var("PM_OFI_ATTRIBUTION_HORIZON_SECS") {
+            if let Ok(secs) = v.trim().parse::<u64>() {
+                cfg.horizon = Duration::from_secs(secs.max(1));
+            }
+        }
+        if let Ok(v) = std::env::var("PM_OFI_ATTRIBUTION_FP_TICKS") {
+            if let Ok(f) = v.trim().parse::<f64>() {
+                cfg.false_positive_ticks = f.max(0.0);
+            }
+        }
+        if let Ok(v) = std::env::var("PM_OFI_ATTRIBUTION_DIR") {
+            if !v.trim().is_empty() {
+                cfg.dir = PathBuf::from(v.trim());
+            }
+        }
+        cfg
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Episode tracking
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+struct CancelledQuote {
+    direction: TradeDirection,
+    price: f64,
+    size: f64,
+    cancelled_at: Instant,
+    would_fill: f64,
+}
+
+#[derive(Debug, Clone)]
+struct OpenEpisode {
+    side: Side,
+    /// Direction our quotes are harmed in (toxic_buy => resting bids).
+    harmful: TradeDirection,
+    started_at: Instant,
+    started_ts_ms: u64,
+    ended_at: Option<Instant>,
+    start_score: f64,
+    peak_score: f64,
+    start_mid_yes: f64,
+    horizon_mid_yes: Option<f64>,
+    quotes: Vec<CancelledQuote>,
+}
+
+/// Scored toxic episode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToxicEpisodeOutcome {
+    pub side: String,
+    pub harmful: String,
+    pub started_ts_ms: u64,
+    /// `None` when the round ended while the side was still toxic.
+    pub duration_ms: Option<u64>,
+    pub start_score: f64,
+    pub peak_score: f64,
+    pub start_mid: f64,
+    pub horizon_mid: f64,
+    /// Side-mid move over the horizon in the harmful direction (ticks).
+    pub adverse_ticks: f64,
+    pub false_positive: bool,
+    pub cancelled_quotes: u32,
+    pub missed_fill_qty: f64,
+    pub saved_loss_usdc: f64,
+    pub missed_profit_usdc: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OfiAttributionSummary {
+    pub episodes: u64,
+    pub false_positives: u64,
+    pub cancelled_quotes: u64,
+    pub missed_fill_qty: f64,
+    pub saved_loss_usdc: f64,
+    pub missed_profit_usdc: f64,
+    pub total_adverse_ticks: f64,
+}
+
+impl OfiAttributionSummary {
+    pub fn absorb(&mut self, outcome: &ToxicEpisodeOutcome) {
+        self.episodes += 1;
+        self.false_positives += u64::from(outcome.false_positive);
+        self.cancelled_quotes += u64::from(outcome.cancelled_quotes);
+        self.missed_fill_qty += outcome.missed_fill_qty;
+        self.saved_loss_usdc += outcome.saved_loss_usdc;
+        self.missed_profit_usdc += outcome.missed_profit_usdc;
+        self.total_adverse_ticks += outcome.adverse_ticks;
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.episodes += other.episodes;
+        self.false_positives += other.false_positives;
+        self.cancelled_quotes += other.cancelled_quotes;
+        self.missed_fill_qty += other.missed_fill_qty;
+        self.saved_loss_usdc += other.saved_loss_usdc;
+        self.missed_profit_usdc += other.missed_profit_usdc;
+        self.total_adverse_ticks += other.total_adverse_ticks;
+    }
+
+    pub fn false_positive_rate(&self) -> f64 {
+        if self.episodes == 0 {
+            0.0
+        } else {
+            self.false_positives as f64 / self.episodes as f64
+        }
+    }
+
+    pub fn mean_adverse_ticks(&self) -> f64 {
+        if self.episodes == 0 {
+            0.0
+        } else {
+            self.total_adverse_ticks / self.episodes as f64
+        }
+    }
+
+    /// Saved loss minus missed profit: > 0 means the kills paid for themselves.
+    pub fn net_saved_usdc(&self) -> f64 {
+        self.saved_loss_usdc - self.missed_profit_usdc
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfiAttributionRound {
+    pub generated_at_ts_ms: u64,
+    pub market: String,
+    pub round_slug: String,
+    pub toxicity_model: String,
+    pub toxicity_threshold: f64,
+    pub horizon_secs: u64,
+    pub partial_round: bool,
+    pub summary: OfiAttributionSummary,
+    pub episodes: Vec<ToxicEpisodeOutcome>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfiAttributionMarketReport {
+    pub generated_at_ts_ms: u64,
+    pub market: String,
+    pub rounds: u64,
+    /// Per toxicity threshold, so threshold changes can be compared side by side.
+    pub by_threshold: Vec<(f64, OfiAttributionSummary)>,
+    pub all: OfiAttributionSummary,
+}
+
+impl OfiAttributionMarketReport {
+    fn empty(market: &str) -> Self {
+        Self {
+            generated_at_ts_ms: unix_now_ms(),
+            market: market.to_string(),
+            rounds: 0,
+            by_threshold: Vec::new(),
+            all: OfiAttributionSummary::default(),
+        }
+    }
+
+    /// Fold one round into the running totals.
+    pub fn absorb(&mut self, round: &OfiAttributionRound) {
+        self.rounds += 1;
+        self.all.merge(&round.summary);
+        match self
+            .by_threshold
+            .iter_mut()
+            .find(|(t, _)| (t - round.toxicity_threshold).abs() < 1e-9)
+        {
+            Some((_, summary)) => summary.merge(&round.summary),
+            None => {
+                self.by_threshold
+                    .push((round.toxicity_threshold, round.summary.clone()));
+                self.by_threshold.sort_by(|a, b| a.0.total_cmp(&b.0));
+            }
+        }
+        self.generated_at_ts_ms = unix_now_ms();
+    }
+}
+
+/// Pure episode bookkeeping; driven by `OfiEngine`.
+
+#[derive(Debug)]
+pub struct OfiAttributionTracker {
+    horizon: Duration,
+    false_positive_ticks: f64,
+    tick_size: f64,
+    open: Vec<OpenEpisode>,
+    finished: VecDeque<ToxicEpisodeOutcome>,
+}
+
+impl OfiAttributionTracker {
+    pub fn new(cfg: &OfiAttributionConfig, tick_size: f64) -> Self {
+        Self {
+            horizon: cfg.horizon,
+            false_positive_ticks: cfg.false_positive_ticks,
+            tick_size: tick_size.max(1e-9),
+            open: Vec::new(),
+            finished: VecDeque::new(),
+        }
+    }
+
+    fn active_mut(&mut self, side: Side) -> Option<&mut OpenEpisode> {
+        self.open
+            .iter_mut()
+            .rev()
+            .find(|e| e.side == side && e.ended_at.is_none())
+    }
+
+    pub fn on_toxic_enter(
+        &mut self,
+        side: Side,
+        toxic_buy: bool,
+        score: f64,
+        mid_yes: f64,
+        now: Instant,
+    ) {
+        if self.active_mut(side).is_some() {
+            return;
+        }
+        self.open.push(OpenEpisode {
+            side,
+            harmful: if toxic_buy {
+                TradeDirection::Buy
+            } else {
+                TradeDirection::Sell
+            },
+            started_at: now,
+            started_ts_ms: unix_now_ms(),
+            ended_at: None,
+            start_score: score,
+            peak_score: score.abs(),
+            start_mid_yes: mid_yes,
+            horizon_mid_yes: None,
+            quotes: Vec::new(),
+        });
+    }
+
+    pub fn on_toxic_score(&mut self, side: Side, score: f64) {
+        if let Some(episode) = self.active_mut(side) {
+            episode.peak_score = episode.peak_score.max(score.abs());
+        }
+    }
+
+    pub fn on_toxic_exit(&mut self, side: Side, now: Instant) {
+        if let Some(episode) = self.active_mut(side) {
+            episode.ended_at = Some(now);
+        }
+    }
+
+    /// Attach a ToxicFlow cancel to the side's active episode; cancels outside
+    /// an episode (e.g. a held toxic block) are ignored.
+    pub fn on_cancel(&mut self, report: &ToxicCancelReport) {
+        if report.price <= 0.0 || report.size <= 0.0 {
+            return;
+        }
+        if let Some(episode) = self.active_mut(report.side) {
+            episode.quotes.push(CancelledQuote {
+                direction: report.direction,
+                price: report.price,
+                size: report.size,
+                cancelled_at: report.ts,
+                would_fill: 0.0,
+            });
+        }
+    }
+
+    /// Public trade on `side`: a taker sell at or below a cancelled bid (or a
+    /// taker buy at or above a cancelled ask) would have filled it.
+    pub fn on_trade(
+        &mut self,
+        side: Side,
+        taker_side: TakerSide,
+        price: f64,
+        size: f64,
+        ts: Instant,
+    ) {
+        let horizon = self.horizon;
+        for episode in self.open.iter_mut().filter(|e| e.side == side) {
+            let mut remaining = size;
+            for quote in &mut episode.quotes {
+                if remaining <= 0.0 {
+                    break;
+                }
+                if ts < quote.cancelled_at
+                    || ts.saturating_duration_since(quote.cancelled_at) > horizon
+                {
+                    continue;
+                }
+                let crosses = match (quote.direction, taker_side) {
+                    (TradeDirection::Buy, TakerSide::Sell) => price <= quote.price + 1e-9,
+                    (TradeDirection::Sell, TakerSide::Buy) => price >= quote.price - 1e-9,
+                    _ => false,
+                };
+                if !crosses {
+                    continue;
+                }
+                let fill = remaining.min(quote.size - quote.would_fill).max(0.0);
+                quote.would_fill += fill;
+                remaining -= fill;
+            }
+        }
+    }
+
+    /// Capture horizon mids and score episodes that are both past the horizon
+    /// and recovered. Returns newly scored episodes.
+    pub fn poll(&mut self, mid_yes: f64, now: Instant) -> Vec<ToxicEpisodeOutcome> {
+        for episode in &mut self.open {
+            if episode.horizon_mid_yes.is_none()
+                && now.saturating_duration_since(episode.started_at) >= self.horizon
+            {
+                episode.horizon_mid_yes = Some(mid_yes);
+            }
+        }
+        let mut scored = Vec::new();
+        let mut idx = 0;
+        while idx < self.open.len() {
+            let episode = &self.open[idx];
+            if episode.horizon_mid_yes.is_some() && episode.ended_at.is_some() {
+                let episode = self.open.remove(idx);
+                scored.push(self.score(episode, mid_yes));
+            } else {
+                idx += 1;
+            }
+        }
+        self.push_finished(&scored);
+        scored
+    }
+
+    /// Score everything still open (round end). Episodes short of the horizon
+    /// are marked to the latest mid.
+    pub fn finish(&mut self, mid_yes: f64) -> Vec<ToxicEpisodeOutcome> {
+        let open = std::mem::take(&mut self.open);
+        let scored: Vec<_> = open.into_iter().map(|e| self.score(e, mid_yes)).collect();
+        self.push_finished(&scored);
+        scored
+    }
+
+    pub fn take_finished(&mut self) -> Vec<ToxicEpisodeOutcome> {
+        self.finished.drain(..).collect()
+    }
+
+    fn push_finished(&mut self, scored: &[ToxicEpisodeOutcome]) {
+        for outcome in scored {
+            if self.finished.len() >= MAX_ROUND_EPISODES {
+                self.finished.pop_front();
+            }
+            self.finished.push_back(outcome.clone());
+        }
+    }
+
+    fn score(&self, episode: OpenEpisode, latest_mid_yes: f64) -> ToxicEpisodeOutcome {
+        let to_side_mid = |mid_yes: f64| match episode.side {
+            Side::Yes => mid_yes,
+            Side::No => 1.0 - mid_yes,
+        };
+        let start_mid = to_side_mid(episode.start_mid_yes);
+        let horizon_mid = to_side_mid(episode.horizon_mid_yes.unwrap_or(latest_mid_yes));
+        let adverse_ticks = match episode.harmful {
+            TradeDirection::Buy => (start_mid - horizon_mid) / self.tick_size,
+            TradeDirection::Sell => (horizon_mid - start_mid) / self.tick_size,
+        };
+        let mut missed_fill_qty = 0.0;
+        let mut saved_loss_usdc = 0.0;
+        let mut missed_profit_usdc = 0.0;
+        for quote in &episode.quotes {
+            if quote.would_fill <= 0.0 {
+                continue;
+            }
+            missed_fill_qty += quote.would_fill;
+            let pnl = match quote.direction {
+                TradeDirection::Buy => (horizon_mid - quote.price) * quote.would_fill,
+                TradeDirection::Sell => (quote.price - horizon_mid) * quote.would_fill,
+            };
+            if pnl < 0.0 {
+                saved_loss_usdc += -pnl;
+            } else {
+                missed_profit_usdc += pnl;
+            }
+        }
+        ToxicEpisodeOutcome {
+            side: episode.side.as_str().to_string(),
+            harmful: match episode.harmful {
+                TradeDirection::Buy => "buy".to_string(),
+                TradeDirection::Sell => "sell".to_string(),
+            },
+            started_ts_ms: episode.started_ts_ms,
+            duration_ms: episode.ended_at.map(|end| {
+                end.saturating_duration_since(episode.started_at)
+                    .as_millis() as u64
+            }),
+            start_score: episode.start_score,
+            peak_score: episode.peak_score,
+            start_mid,
+            horizon_mid,
+            adverse_ticks,
+            false_positive: adverse_ticks < self.false_positive_ticks,
+            cancelled_quotes: episode.quotes.len() as u32,
+            missed_fill_qty,
+            saved_loss_usdc,
+            missed_profit_usdc,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Shared handle + persistence
+// ─────────────────────────────────────────────────────────
+
+/// Cloneable handle: the OFI engine feeds it, the round driver calls
+/// `finish_round` after the engine task is aborted.
+#[derive(Clone)]
+pub struct OfiAttribution {
+    cfg: OfiAttributionConfig,
+    tracker: Arc<Mutex<OfiAttributionTracker>>,
+    last_mid_yes: Arc<Mutex<f64>>,
+}
+
+impl OfiAttribution {
+    pub fn new(cfg: OfiAttributionConfig, tick_size: f64) -> Self {
+        let tracker = OfiAttributionTracker::new(&cfg, tick_size);
+        Self {
+            cfg,
+            tracker: Arc::new(Mutex::new(tracker)),
+            last_mid_yes: Arc::new(Mutex::new(0.5)),
+        }
+    }
+
+    pub fn with_tracker<R>(&self, f: impl FnOnce(&mut OfiAttributionTracker) -> R) -> R {
+        let mut guard = self.tracker.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut guard)
+    }
+
+    /// Heartbeat hook: score matured episodes and log them.
+    pub fn poll(&self, mid_yes: f64, now: Instant) {
+        *self.last_mid_yes.lock().unwrap_or_else(|e| e.into_inner()) = mid_yes;
+        for outcome in self.with_tracker(|t| t.poll(mid_yes, now)) {
+            log_episode(&outcome);
+        }
+    }
+
+    /// Score open episodes, persist the round row and refresh the market report.
+    pub fn finish_round(
+        &self,
+        market: &str,
+        round_slug: &str,
+        toxicity_model: &str,
+        toxicity_threshold: f64,
+        partial_round: bool,
+    ) {
+        let mid_yes = *self.last_mid_yes.lock().unwrap_or_else(|e| e.into_inner());
+        let episodes = self.with_tracker(|t| {
+            for outcome in t.finish(mid_yes) {
+                log_episode(&outcome);
+            }
+            t.take_finished()
+        });
+        let mut summary = OfiAttributionSummary::default();
+        for outcome in &episodes {
+            summary.absorb(outcome);
+        }
+        let round = OfiAttributionRound {
+            generated_at_ts_ms: unix_now_ms(),
+            market: market.to_string(),
+            round_slug: round_slug.to_string(),
+            toxicity_model: toxicity_model.to_string(),
+            toxicity_threshold,
+            horizon_secs: self.cfg.horizon.as_secs(),
+            partial_round,
+            summary,
+            episodes,
+        };
+        info!(
+            "📘 OfiAttribution | market={} round={} threshold={:.1} episodes={} false_pos={} ({:.0}%) cancels={} missed_fill={:.1} saved_loss=${:.3} missed_profit=${:.3} mean_adverse_ticks={:.2}",
+            round.market,
+            round.round_slug,
+            round.toxicity_threshold,
+            round.summary.episodes,
+            round.summary.false_positives,
+            round.summary.false_positive_rate() * 100.0,
+            round.summary.cancelled_quotes,
+            round.summary.missed_fill_qty,
+            round.summary.saved_loss_usdc,
+            round.summary.missed_profit_usdc,
+            round.summary.mean_adverse_ticks(),
+        );
+        if let Err(e) = append_round(&self.cfg.dir, &round) {
+            warn!("⚠️ Failed to append OFI attribution round: {:?}", e);
+            return;
+        }
+        match update_market_report(&self.cfg.dir, &round) {
+            Ok(report) => info!(
+                "📊 OfiAttribution[{} rounds] market={} episodes={} false_pos={:.0}% net_saved=${:.3} mean_adverse_ticks={:.2}",
+                report.rounds,
+                report.market,
+                report.all.episodes,
+                report.all.false_positive_rate() * 100.0,
+                report.all.net_saved_usdc(),
+                report.all.mean_adverse_ticks(),
+            ),
+            Err(e) => warn!("⚠️ Failed to refresh OFI attribution report: {:?}", e),
+        }
+    }
+}
+
+fn log_episode(outcome: &ToxicEpisodeOutcome) {
+    info!(
+        "🧪 OFI kill attribution | side={} harmful={} dur={} peak={:.1} mid {:.3}->{:.3} adverse_ticks={:.2} false_pos={} cancels={} missed_fill={:.1} saved_loss=${:.3} missed_profit=${:.3}",
+        outcome.side,
+        outcome.harmful,
+        outcome
+            .duration_ms
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "open".to_string()),
+        outcome.peak_score,
+        outcome.start_mid,
+        outcome.horizon_mid,
+        outcome.adverse_ticks,
+        outcome.false_positive,
+        outcome.cancelled_quotes,
+        outcome.missed_fill_qty,
+        outcome.saved_loss_usdc,
+        outcome.missed_profit_usdc,
+    );
+}
+
+fn rounds_path(dir: &Path) -> PathBuf {
+    dir.join("rounds.jsonl")
+}
+
+fn append_round(dir: &Path, round: &OfiAttributionRound) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(rounds_path(dir))?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(serde_json::to_string(round)?.as_bytes())?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn aggregate_market(
+    market: &str,
+    rounds: &[OfiAttributionRound],
+) -> OfiAttributionMarketReport {
+    let mut report = OfiAttributionMarketReport::empty(market);
+    for round in rounds.iter().filter(|r| r.market == market) {
+        report.absorb(round);
+    }
+    report
+}
+
+/// Fold `round` (already appended to `rounds.jsonl`) into the market report.
+fn update_market_report(
+    dir: &Path,
+    round: &OfiAttributionRound,
+) -> anyhow::Result<OfiAttributionMarketReport> {
+    let path = market_report_path(dir, &round.market);
+    let stored = fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<OfiAttributionMarketReport>(&bytes).ok());
+    let report = match stored {
+        Some(mut report) => {
+            report.absorb(round);
+            report
+        }
+        // First round, or a lost report: rebuild once (this round included).
+        None => {
+            let text = fs::read_to_string(rounds_path(dir))?;
+            let rounds: Vec<OfiAttributionRound> = text
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect();
+            aggregate_market(&round.market, &rounds)
+        }
+    };
+    fs::write(&path, serde_json::to_vec_pretty(&report)?)?;
+    Ok(report)
+}
+
+fn market_report_path(dir: &Path, market: &str) -> PathBuf {
+    let file_name: String = market
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}.json", file_name))
+}
+
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> OfiAttributionTracker {
+        OfiAttributionTracker::new(&OfiAttributionConfig::default(), 0.01)
+    }
+
+    fn cancel(side: Side, price: f64, size: f64, ts: Instant) -> ToxicCancelReport {
+        ToxicCancelReport {
+            side,
+            direction: TradeDirection::Buy,
+            price,
+            size,
+            ts,
+        }
+    }
+
+    #[test]
+    fn kill_before_dump_counts_saved_loss() {
+        let t0 = Instant::now();
+        let mut tracker = tracker();
+        // YES sell pressure: buying YES is toxic, our 0.50 bid gets pulled.
+        tracker.on_toxic_enter(Side::Yes, true, -300.0, 0.51, t0);
+        tracker.on_cancel(&cancel(Side::Yes, 0.50, 20.0, t0));
+        tracker.on_toxic_score(Side::Yes, -450.0);
+        // Taker sells through our old price: 15 + 10 shares, capped at 20.
+        tracker.on_trade(
+            Side::Yes,
+            TakerSide::Sell,
+            0.49,
+            15.0,
+            t0 + Duration::from_secs(1),
+        );
+        tracker.on_trade(
+            Side::Yes,
+            TakerSide::Sell,
+            0.48,
+            10.0,
+            t0 + Duration::from_secs(2),
+        );
+        // A taker buy never fills a bid.
+        tracker.on_trade(
+            Side::Yes,
+            TakerSide::Buy,
+            0.50,
+            50.0,
+            t0 + Duration::from_secs(2),
+        );
+        tracker.on_toxic_exit(Side::Yes, t0 + Duration::from_secs(3));
+
+        assert!(tracker.poll(0.47, t0 + Duration::from_secs(5)).is_empty());
+        let scored = tracker.poll(0.46, t0 + Duration::from_secs(10));
+        assert_eq!(scored.len(), 1);
+        let outcome = &scored[0];
+        assert_eq!(outcome.duration_ms, Some(3000));
+        assert!((outcome.peak_score - 450.0).abs() < 1e-9);
+        assert!((outcome.adverse_ticks - 5.0).abs() < 1e-6);
+        assert!(!outcome.false_positive);
+        assert!((outcome.missed_fill_qty - 20.0).abs() < 1e-9);
+        // 20 shares bought at 0.50, marked at 0.46.
+        assert!((outcome.saved_loss_usdc - 0.8).abs() < 1e-9);
+        assert!(outcome.missed_profit_usdc.abs() < 1e-9);
+    }
+
+    #[test]
+    fn kill_without_follow_through_is_false_positive() {
+        let t0 = Instant::now();
+        let mut tracker = tracker();
+        // NO side: mid_yes 0.40 -> NO mid 0.60; buying NO is toxic.
+        tracker.on_toxic_enter(Side::No, true, -250.0, 0.40, t0);
+        tracker.on_cancel(&cancel(Side::No, 0.59, 10.0, t0));
+        tracker.on_trade(
+            Side::No,
+            TakerSide::Sell,
+            0.59,
+            4.0,
+            t0 + Duration::from_secs(1),
+        );
+        // Still toxic at round end; NO mid rose to 0.62 instead of dumping.
+        let scored = tracker.finish(0.38);
+        assert_eq!(scored.len(), 1);
+        let outcome = &scored[0];
+        assert_eq!(outcome.duration_ms, None);
+        assert!(outcome.false_positive);
+        assert!((outcome.missed_profit_usdc - 4.0 * 0.03).abs() < 1e-9);
+
+        let mut summary = OfiAttributionSummary::default();
+        summary.absorb(outcome);
+        let round = OfiAttributionRound {
+            generated_at_ts_ms: 0,
+            market: "btc-updown-15m".to_string(),
+            round_slug: "btc-updown-15m-1".to_string(),
+            toxicity_model: "window".to_string(),
+            toxicity_threshold: 300.0,
+            horizon_secs: 10,
+            partial_round: false,
+            summary,
+            episodes: scored,
+        };
+        let report = aggregate_market("btc-updown-15m", &[round.clone(), round.clone()]);
+        assert_eq!(report.rounds, 2);
+        assert_eq!(report.by_threshold.len(), 1);
+        assert!((report.all.false_positive_rate() - 1.0).abs() < 1e-9);
+        assert!(report.all.net_saved_usdc() < 0.0);
+
+        // The on-disk report keeps running totals instead of rescanning rounds.
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("pm_as_ofi_ofi_attr_{}", ts));
+        append_round(&dir, &round).unwrap();
+        assert_eq!(update_market_report(&dir, &round).unwrap().rounds, 1);
+        fs::remove_file(rounds_path(&dir)).unwrap();
+        let mut tighter = round.clone();
+        tighter.toxicity_threshold = 200.0;
+        append_round(&dir, &tighter).unwrap();
+        let report = update_market_report(&dir, &tighter).unwrap();
+        assert_eq!(report.rounds, 2);
+        assert_eq!(report.by_threshold.len(), 2);
+        assert!((report.by_threshold[0].0 - 200.0).abs() < 1e-9);
+        assert_eq!(report.all.episodes, 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,397 @@
+//! Pluggable OFI toxicity models.
+//!
+//! A `ToxicityModel` turns one side's trade flow into a signed score (positive =
+//! taker buy pressure). `OfiEngine` rescales that score onto the
+//! `toxicity_threshold` scale and runs the shared heat / adverse-move
+//! confirmation on it, so every model produces the same `SideOfi` and the
+//! Coordinator cannot tell them apart. Models are picked per market via
+//! `PM_OFI_TOXICITY_MODEL` / `PM_OFI_TOXICITY_MODEL_BY_MARKET`.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::messages::TakerSide;
+use super::ofi::OfiConfig;
+
+/// One trade in a side's OFI window.
+#[derive(Debug, Clone, Copy)]
+pub struct FlowTrade {
+    pub taker_side: TakerSide,
+    pub size: f64,
+    /// Trade price in the side's own token (0 when unknown).
+    pub price: f64,
+    pub ts: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToxicityModelKind {
+    /// Sliding-window buy-minus-sell volume (the original OFI score).
+    #[default]
+    Window,
+    /// VPIN-style volume-bucketed imbalance.
+    Vpin,
+    /// Exponential-kernel (Hawkes) intensity imbalance.
+    Hawkes,
+    /// Trade-to-mid adverse move: how far mid ran in the taker's favour.
+    AdverseMove,
+}
+
+impl ToxicityModelKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Window => "window",
+            Self::Vpin => "vpin",
+            Self::Hawkes => "hawkes",
+            Self::AdverseMove => "adverse_move",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "window" | "ofi" | "default" => Some(Self::Window),
+            "vpin" => Some(Self::Vpin),
+            "hawkes" => Some(Self::Hawkes),
+            "adverse_move" | "adverse" | "trade_to_mid" => Some(Self::AdverseMove),
+            _ => None,
+        }
+    }
+}
+
+/// Per-side toxicity scorer. One instance per side per engine.
+pub trait ToxicityModel: Send {
+    fn kind(&self) -> ToxicityModelKind;
+
+    /// Called for every trade on this side before it enters the window.
+    fn on_trade(&mut self, _trade: &FlowTrade) {}
+
+    /// Signed raw score for the current window (positive = buy pressure).
+    /// `side_mid` is this side's token mid (YES mid, or `1 - YES mid` for NO).
+    fn score(&mut self, window: &VecDeque<FlowTrade>, side_mid: f64, now: Instant) -> f64;
+
+    /// |raw score| that counts as toxic, in the model's own units. The engine
+    /// maps this onto `OfiConfig::toxicity_threshold`.
+    fn threshold(&self) -> f64;
+}
+
+pub fn build_toxicity_model(cfg: &OfiConfig) -> Box<dyn ToxicityModel> {
+    match cfg.toxicity_model {
+        ToxicityModelKind::Window => Box::new(WindowImbalanceModel {
+            threshold: cfg.toxicity_threshold,
+        }),
+        ToxicityModelKind::Vpin => Box::new(VpinModel::new(
+            cfg.vpin_bucket_volume,
+            cfg.vpin_buckets,
+            cfg.vpin_threshold,
+            cfg.window_duration * cfg.vpin_buckets.max(1) as u32,
+        )),
+        ToxicityModelKind::Hawkes => Box::new(HawkesImbalanceModel::new(
+            cfg.hawkes_half_life,
+            cfg.hawkes_threshold,
+        )),
+        ToxicityModelKind::AdverseMove => Box::new(AdverseMoveModel {
+            tick_size: cfg.tick_size.max(1e-9),
+            threshold: cfg.adverse_move_threshold,
+        }),
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Window imbalance (default)
+// ─────────────────────────────────────────────────────────
+
+pub struct WindowImbalanceModel {
+    threshold: f64,
+}
+
+impl ToxicityModel for WindowImbalanceModel {
+    fn kind(&self) -> ToxicityModelKind {
+        ToxicityModelKind::Window
+    }
+
+    fn score(&mut self, window: &VecDeque<FlowTrade>, _side_mid: f64, _now: Instant) -> f64 {
+        window
+            .iter()
+            .map(|t| match t.taker_side {
+                TakerSide::Buy => t.size,
+                TakerSide::Sell => -t.size,
+            })
+            .sum()
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// VPIN
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct VolumeBucket {
+    buy: f64,
+    sell: f64,
+    closed_at: Instant,
+}
+
+/// Volume-synchronised imbalance: trades fill fixed-size buckets; the score is
+/// `sign(net) * Σ|buy - sell| / Σvolume` over the last `buckets` full buckets
+/// (the partial bucket is used until the first one closes).
+pub struct VpinModel {
+    bucket_volume: f64,
+    buckets: usize,
+    threshold: f64,
+    max_age: Duration,
+    current_buy: f64,
+    current_sell: f64,
+    closed: VecDeque<VolumeBucket>,
+}
+
+impl VpinModel {
+    pub fn new(bucket_volume: f64, buckets: usize, threshold: f64, max_age: Duration) -> Self {
+        Self {
+            bucket_volume: bucket_volume.max(1e-9),
+            buckets: buckets.max(1),
+            threshold,
+            max_age,
+            current_buy: 0.0,
+            current_sell: 0.0,
+            closed: VecDeque::new(),
+        }
+    }
+}
+
+impl ToxicityModel for VpinModel {
+    fn kind(&self) -> ToxicityModelKind {
+        ToxicityModelKind::Vpin
+    }
+
+    fn on_trade(&mut self, trade: &FlowTrade) {
+        let mut remaining = trade.size.max(0.0);
+        while remaining > 0.0 {
+            let room = self.bucket_volume - (self.current_buy + self.current_sell);
+            let fill = remaining.min(room.max(0.0));
+            match trade.taker_side {
+                TakerSide::Buy => self.current_buy += fill,
+                TakerSide::Sell => self.current_sell += fill,
+            }
+            remaining -= fill;
+            if self.current_buy + self.current_sell >= self.bucket_volume - 1e-9 {
+                self.closed.push_back(VolumeBucket {
+                    buy: self.current_buy,
+                    sell: self.current_sell,
+                    closed_at: trade.ts,
+                });
+                while self.closed.len() > self.buckets {
+                    self.closed.pop_front();
+                }
+                self.current_buy = 0.0;
+                self.current_sell = 0.0;
+            }
+        }
+    }
+
+    fn score(&mut self, _window: &VecDeque<FlowTrade>, _side_mid: f64, now: Instant) -> f64 {
+        while let Some(front) = self.closed.front() {
+            if now.saturating_duration_since(front.closed_at) > self.max_age {
+                self.closed.pop_front();
+            } else {
+                break;
+            }
+        }
+        let (abs_imbalance, net, volume) = if self.closed.is_empty() {
+            let net = self.current_buy - self.current_sell;
+            (net.abs(), net, self.current_buy + self.current_sell)
+        } else {
+            self.closed.iter().fold((0.0, 0.0, 0.0), |(a, n, v), b| {
+                (
+                    a + (b.buy - b.sell).abs(),
+                    n + (b.buy - b.sell),
+                    v + b.buy + b.sell,
+                )
+            })
+        };
+        if volume <= f64::EPSILON {
+            return 0.0;
+        }
+        net.signum() * abs_imbalance / volume
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Hawkes intensity imbalance
+// ─────────────────────────────────────────────────────────
+
+/// Size-marked exponential-kernel intensities per taker direction; the baseline
+/// rate cancels in `λ_buy - λ_sell`, leaving the self-excited part (shares/s).
+pub struct HawkesImbalanceModel {
+    decay_per_sec: f64,
+    threshold: f64,
+    buy_intensity: f64,
+    sell_intensity: f64,
+    last_ts: Option<Instant>,
+}
+
+impl HawkesImbalanceModel {
+    pub fn new(half_life: Duration, threshold: f64) -> Self {
+        let half_life_secs = half_life.as_secs_f64().max(1e-3);
+        Self {
+            decay_per_sec: std::f64::consts::LN_2 / half_life_secs,
+            threshold,
+            buy_intensity: 0.0,
+            sell_intensity: 0.0,
+            last_ts: None,
+        }
+    }
+
+    fn decay_to(&mut self, ts: Instant) {
+        if let Some(last) = self.last_ts {
+            let dt = ts.saturating_duration_since(last).as_secs_f64();
+            let factor = (-self.decay_per_sec * dt).exp();
+            self.buy_intensity *= factor;
+            self.sell_intensity *= factor;
+        }
+        if self.last_ts.map(|last| ts > last).unwrap_or(true) {
+            self.last_ts = Some(ts);
+        }
+    }
+}
+
+impl ToxicityModel for HawkesImbalanceModel {
+    fn kind(&self) -> ToxicityModelKind {
+        ToxicityModelKind::Hawkes
+    }
+
+    fn on_trade(&mut self, trade: &FlowTrade) {
+        self.decay_to(trade.ts);
+        // Kernel α·e^{-βt} with α = β·size integrates to the trade size.
+        let jump = self.decay_per_sec * trade.size.max(0.0);
+        match trade.taker_side {
+            TakerSide::Buy => self.buy_intensity += jump,
+            TakerSide::Sell => self.sell_intensity += jump,
+        }
+    }
+
+    fn score(&mut self, _window: &VecDeque<FlowTrade>, _side_mid: f64, now: Instant) -> f64 {
+        self.decay_to(now);
+        self.buy_intensity - self.sell_intensity
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Trade-to-mid adverse move
+// ─────────────────────────────────────────────────────────
+
+/// Size-weighted ticks the mid has run past each trade in the taker's favour:
+/// buys scored by `mid - price`, sells by `price - mid`. Informed flow leaves
+/// the mid behind it; uninformed flow pays the half-spread and scores zero.
+pub struct AdverseMoveModel {
+    tick_size: f64,
+    threshold: f64,
+}
+
+impl ToxicityModel for AdverseMoveModel {
+    fn kind(&self) -> ToxicityModelKind {
+        ToxicityModelKind::AdverseMove
+    }
+
+    fn score(&mut self, window: &VecDeque<FlowTrade>, side_mid: f64, _now: Instant) -> f64 {
+        if !(side_mid > 0.0 && side_mid < 1.0) {
+            return 0.0;
+        }
+        window
+            .iter()
+            .filter(|t| t.price > 0.0)
+            .map(|t| match t.taker_side {
+                TakerSide::Buy => t.size * ((side_mid - t.price) / self.tick_size).max(0.0),
+                TakerSide::Sell => -t.size * ((t.price - side_mid) / self.tick_size).max(0.0),
+            })
+            .sum()
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(taker_side: TakerSide, size: f64, price: f64, ts: Instant) -> FlowTrade {
+        FlowTrade {
+            taker_side,
+            size,
+            price,
+            ts,
+        }
+    }
+
+    #[test]
+    fn vpin_buckets_volume_and_signs_by_net_flow() {
+        let now = Instant::now();
+        let mut model = VpinModel::new(10.0, 3, 0.6, Duration::from_secs(60));
+        // 25 shares of buys + 5 sells: buckets [10b], [10b], partial [5b,5s].
+        model.on_trade(&trade(TakerSide::Buy, 25.0, 0.5, now));
+        model.on_trade(&trade(TakerSide::Sell, 5.0, 0.5, now));
+        let window = VecDeque::new();
+        let score = model.score(&window, 0.5, now);
+        // Three closed buckets: [10b], [10b], [5b,5s] -> (10 + 10 + 0) / 30.
+        assert!((score - 20.0 / 30.0).abs() < 1e-9, "score={}", score);
+
+        // Buckets expire after max_age and the score falls back to the partial bucket.
+        let later = now + Duration::from_secs(120);
+        assert!(model.score(&window, 0.5, later).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hawkes_intensity_decays_with_half_life() {
+        let now = Instant::now();
+        let mut model = HawkesImbalanceModel::new(Duration::from_secs(1), 10.0);
+        model.on_trade(&trade(TakerSide::Sell, 20.0, 0.5, now));
+        let window = VecDeque::new();
+        let fresh = model.score(&window, 0.5, now);
+        assert!(fresh < 0.0);
+        let decayed = model.score(&window, 0.5, now + Duration::from_secs(1));
+        assert!((decayed - fresh * 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn adverse_move_scores_only_trades_the_mid_ran_past() {
+        let now = Instant::now();
+        let mut model = AdverseMoveModel {
+            tick_size: 0.01,
+            threshold: 100.0,
+        };
+        let window: VecDeque<FlowTrade> = vec![
+            // Buy at 0.50, mid now 0.53 -> informed buy, 3 ticks x 10.
+            trade(TakerSide::Buy, 10.0, 0.50, now),
+            // Sell at 0.52 with mid 0.53 -> seller paid the spread, scores 0.
+            trade(TakerSide::Sell, 50.0, 0.52, now),
+        ]
+        .into();
+        let score = model.score(&window, 0.53, now);
+        assert!((score - 30.0).abs() < 1e-9, "score={}", score);
+    }
+
+    #[test]
+    fn model_kind_parses_aliases() {
+        assert_eq!(
+            ToxicityModelKind::parse("trade_to_mid"),
+            Some(ToxicityModelKind::AdverseMove)
+        );
+        assert_eq!(
+            ToxicityModelKind::parse("VPIN"),
+            Some(ToxicityModelKind::Vpin)
+        );
+        assert_eq!(ToxicityModelKind::parse("nope"), None);
+    }
+}
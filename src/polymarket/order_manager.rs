@@ -1,8 +1,13 @@
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+use super::actor_state_store::{
+    remaining_ms, restore_deadline, ActorStateHandle, RestoredState, StateSaveThrottle,
+};
+use super::clock::{system_clock, SharedClock};
 use super::messages::{
     BidReason, CancelReason, DesiredTarget, ExecutionCmd, OrderAttemptTrace, OrderManagerCmd,
     OrderResult, OrderSlot, SlotReleaseEvent, TradeDirection, TradeIntent, TradePurpose,
@@ -19,6 +24,7 @@ const DEFAULT_BUY_FILL_REOPEN_COOLDOWN: Duration = Duration::ZERO;
 const ORACLE_LAG_REPRICE_MIN_INTERVAL: Duration = Duration::from_millis(200);
 const ORACLE_LAG_REPRICE_PRICE_EPS: f64 = 5e-4;
 const ORACLE_LAG_REPRICE_SIZE_EPS: f64 = 0.05;
+const OMS_STATE_ACTOR: &str = "oms";
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum OrderState {
@@ -62,6 +68,17 @@ impl SlotTracker {
     }
 }
 
+/// Restart-surviving OMS state. Live order state is not persisted: the
+/// executor cancels every open order at startup, so only cooldowns and
+/// sell-availability warmups carry over.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OmsStateSnapshot {
+    /// Remaining slot cooldown, indexed like `OrderSlot::ALL`.
+    pub slot_cooldown_ms: [Option<u64>; 4],
+    /// Remaining post-fill SELL warmup per side.
+    pub sell_available_after_ms: [Option<u64>; 2],
+}
+
 pub struct OrderManager {
     slots: [SlotTracker; 4],
     side_takers: [SideTakerState; 2],
@@ -71,6 +88,9 @@ pub struct OrderManager {
     result_rx: mpsc::Receiver<OrderResult>,
     slot_release_tx: mpsc::Sender<SlotReleaseEvent>,
     buy_fill_reopen_cooldown: Duration,
+    state_store: Option<ActorStateHandle>,
+    state_save: StateSaveThrottle,
+    clock: SharedClock,
 }
 
 impl OrderManager {
@@ -105,6 +125,69 @@ impl OrderManager {
             result_rx,
             slot_release_tx,
             buy_fill_reopen_cooldown,
+            state_store: None,
+            state_save: StateSaveThrottle::default(),
+            clock: system_clock(),
+        }
+    }
+
+    /// Inject the time source used for cooldowns and pending timeouts.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        let now = clock.now();
+        for tracker in &mut self.slots {
+            tracker.last_action = now;
+        }
+        self.clock = clock;
+        self
+    }
+
+    /// Persist cooldowns through `store` and reload the previous snapshot of
+    /// the same market, if any.
+    pub async fn with_state_store(mut self, store: ActorStateHandle) -> Self {
+        if let Some(restored) = store.load::<OmsStateSnapshot>(OMS_STATE_ACTOR).await {
+            info!(
+                "♻️ OMS state restored | slug={} age={}ms",
+                store.slug(),
+                restored.age.as_millis()
+            );
+            self.restore_state(restored);
+        }
+        self.state_store = Some(store);
+        self
+    }
+
+    pub fn export_state(&self) -> OmsStateSnapshot {
+        let now = self.clock.now();
+        OmsStateSnapshot {
+            slot_cooldown_ms: std::array::from_fn(|idx| {
+                remaining_ms(self.slots[idx].cooldown_until, now)
+            }),
+            sell_available_after_ms: std::array::from_fn(|idx| {
+                remaining_ms(self.sell_available_after[idx], now)
+            }),
+        }
+    }
+
+    pub fn restore_state(&mut self, restored: RestoredState<OmsStateSnapshot>) {
+        let now = self.clock.now();
+        for (tracker, remaining) in self.slots.iter_mut().zip(restored.state.slot_cooldown_ms) {
+            tracker.cooldown_until = restore_deadline(remaining, restored.age, now);
+        }
+        for (until, remaining) in self
+            .sell_available_after
+            .iter_mut()
+            .zip(restored.state.sell_available_after_ms)
+        {
+            *until = restore_deadline(remaining, restored.age, now);
+        }
+    }
+
+    fn maybe_save_state(&mut self) {
+        let Some(store) = self.state_store.as_ref() else {
+            return;
+        };
+        if self.state_save.due(self.clock.now(), store.interval()) {
+            store.save(OMS_STATE_ACTOR, &self.export_state());
         }
     }
 
@@ -150,7 +233,7 @@ impl OrderManager {
             return false;
         }
 
-        let elapsed = self.tracker(slot).last_action.elapsed();
+        let elapsed = self.clock.elapsed(self.tracker(slot).last_action);
         if elapsed < ORACLE_LAG_REPRICE_MIN_INTERVAL {
             debug!(
                 "⏭️ OMS oracle_lag reprice suppressed | slot={} live={:.4}@{:.2} desired={:.4}@{:.2} price_delta={:.6} size_delta={:.3} elapsed_ms={}",
@@ -185,123 +268,144 @@ impl OrderManager {
                 biased;
                 result = self.result_rx.recv() => {
                     match result {
-                        Some(OrderResult::OrderPlaced { slot, target }) => {
-                            self.handle_placed(slot, target).await;
-                            self.pump_slot(slot).await;
-                        }
-                        Some(OrderResult::OrderFailed { slot, cooldown_ms }) => {
-                            self.handle_failed(slot, cooldown_ms).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderResult::OrderSuppressed { slot }) => {
-                            self.handle_suppressed(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderResult::SlotBusy { slot }) => {
-                            self.handle_slot_busy(slot).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderResult::OrderFilled { slot }) => {
-                            self.handle_filled(slot).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderResult::TakerHedgeDone { side }) => {
-                            self.handle_taker_done(side).await;
-                            for slot in OrderSlot::side_slots(side) {
-                                self.pump_slot(slot).await;
-                            }
-                        }
-                        Some(OrderResult::TakerHedgeFailed { side, cooldown_ms }) => {
-                            self.handle_taker_failed(side, cooldown_ms).await;
-                        }
-                        Some(OrderResult::CancelAck { slot }) => {
-                            self.handle_cancel_ack(slot).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
+                        Some(result) => self.handle_result(result).await,
                         None => break,
                     }
                 }
                 cmd = self.cmd_rx.recv() => {
                     match cmd {
-                        Some(OrderManagerCmd::SetTarget(target)) => {
-                            let slot = target.slot();
-                            self.handle_target(target, None).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderManagerCmd::SetTargetWithTrace { target, trace }) => {
-                            let slot = target.slot();
-                            self.handle_target(target, Some(trace)).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderManagerCmd::SetPairArbHeadroom {
-                            slot,
-                            local_unreleased_matched_notional_usdc,
-                        }) => {
-                            self.tracker_mut(slot).pair_arb_local_unreleased_matched_notional_usdc =
-                                local_unreleased_matched_notional_usdc.max(0.0);
-                        }
-                        Some(OrderManagerCmd::ClearTarget { slot, reason }) => {
-                            self.handle_clear(slot, reason).await;
-                            self.pump_slot(slot).await;
-                            self.pump_side_taker(slot.side).await;
-                        }
-                        Some(OrderManagerCmd::OneShotTakerHedge {
-                            side,
-                            direction,
-                            size,
-                            purpose,
-                            limit_price,
-                            expected_fill_price,
-                        }) => {
-                            let oracle_lag_fast_path = matches!(purpose, TradePurpose::OracleLagSnipe)
-                                && self.side_slots_idle(side);
-                            self.handle_one_shot_taker(
-                                side,
-                                direction,
-                                size,
-                                purpose,
-                                limit_price,
-                                expected_fill_price,
-                            )
-                            .await;
-                            if oracle_lag_fast_path {
-                                debug!(
-                                    "⚡ OMS fast one-shot taker path | side={:?} purpose={:?} reason=slots_idle_skip_side_pump",
-                                    side, purpose
-                                );
-                            } else {
-                                for slot in OrderSlot::side_slots(side) {
-                                    self.pump_slot(slot).await;
-                                }
-                            }
-                            self.pump_side_taker(side).await;
-                        }
-                        Some(OrderManagerCmd::CancelAll) => {
-                            self.handle_cancel_all();
-                            for slot in OrderSlot::ALL {
-                                self.pump_slot(slot).await;
-                            }
-                        }
+                        Some(cmd) => self.handle_command(cmd).await,
                         None => break,
                     }
                 }
                 _ = heartbeat.tick() => {
-                    for slot in OrderSlot::ALL {
+                    self.pump_all().await;
+                    self.maybe_save_state();
+                }
+            }
+        }
+        info!("🚦 OrderManager shutting down");
+    }
+
+    /// Apply one executor result and re-pump the affected slots.
+    pub(crate) async fn handle_result(&mut self, result: OrderResult) {
+        match result {
+            OrderResult::OrderPlaced { slot, target } => {
+                self.handle_placed(slot, target).await;
+                self.pump_slot(slot).await;
+            }
+            OrderResult::OrderFailed { slot, cooldown_ms } => {
+                self.handle_failed(slot, cooldown_ms).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderResult::OrderSuppressed { slot } => {
+                self.handle_suppressed(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderResult::SlotBusy { slot } => {
+                self.handle_slot_busy(slot).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderResult::OrderFilled { slot } => {
+                self.handle_filled(slot).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderResult::TakerHedgeDone { side } => {
+                self.handle_taker_done(side).await;
+                for slot in OrderSlot::side_slots(side) {
+                    self.pump_slot(slot).await;
+                }
+            }
+            OrderResult::TakerHedgeFailed { side, cooldown_ms } => {
+                self.handle_taker_failed(side, cooldown_ms).await;
+            }
+            OrderResult::CancelAck { slot } => {
+                self.handle_cancel_ack(slot).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+        }
+    }
+
+    /// Apply one coordinator command and re-pump the affected slots.
+    pub(crate) async fn handle_command(&mut self, cmd: OrderManagerCmd) {
+        match cmd {
+            OrderManagerCmd::SetTarget(target) => {
+                let slot = target.slot();
+                self.handle_target(target, None).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderManagerCmd::SetTargetWithTrace { target, trace } => {
+                let slot = target.slot();
+                self.handle_target(target, Some(trace)).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderManagerCmd::SetPairArbHeadroom {
+                slot,
+                local_unreleased_matched_notional_usdc,
+            } => {
+                self.tracker_mut(slot)
+                    .pair_arb_local_unreleased_matched_notional_usdc =
+                    local_unreleased_matched_notional_usdc.max(0.0);
+            }
+            OrderManagerCmd::ClearTarget { slot, reason } => {
+                self.handle_clear(slot, reason).await;
+                self.pump_slot(slot).await;
+                self.pump_side_taker(slot.side).await;
+            }
+            OrderManagerCmd::OneShotTakerHedge {
+                side,
+                direction,
+                size,
+                purpose,
+                limit_price,
+                expected_fill_price,
+            } => {
+                let oracle_lag_fast_path =
+                    matches!(purpose, TradePurpose::OracleLagSnipe) && self.side_slots_idle(side);
+                self.handle_one_shot_taker(
+                    side,
+                    direction,
+                    size,
+                    purpose,
+                    limit_price,
+                    expected_fill_price,
+                )
+                .await;
+                if oracle_lag_fast_path {
+                    debug!(
+                        "⚡ OMS fast one-shot taker path | side={:?} purpose={:?} reason=slots_idle_skip_side_pump",
+                        side, purpose
+                    );
+                } else {
+                    for slot in OrderSlot::side_slots(side) {
                         self.pump_slot(slot).await;
                     }
-                    for side in [Side::Yes, Side::No] {
-                        self.pump_side_taker(side).await;
-                    }
+                }
+                self.pump_side_taker(side).await;
+            }
+            OrderManagerCmd::CancelAll => {
+                self.handle_cancel_all();
+                for slot in OrderSlot::ALL {
+                    self.pump_slot(slot).await;
                 }
             }
         }
-        info!("🚦 OrderManager shutting down");
+    }
+
+    /// Heartbeat pump: retry every slot and side taker whose cooldown may have lapsed.
+    pub(crate) async fn pump_all(&mut self) {
+        for slot in OrderSlot::ALL {
+            self.pump_slot(slot).await;
+        }
+        for side in [Side::Yes, Side::No] {
+            self.pump_side_taker(side).await;
+        }
     }
 
     async fn handle_target(&mut self, target: DesiredTarget, trace: Option<OrderAttemptTrace>) {
@@ -366,14 +470,13 @@ impl OrderManager {
     }
 
     async fn handle_placed(&mut self, slot: OrderSlot, target: DesiredTarget) {
+        let now = self.clock.now();
         let tracker = self.tracker_mut(slot);
         match tracker.state {
             OrderState::PendingSubmit(_) | OrderState::Idle => {
                 if matches!(tracker.state, OrderState::Idle)
                     && slot.direction == TradeDirection::Buy
-                    && tracker
-                        .cooldown_until
-                        .is_some_and(|until| Instant::now() < until)
+                    && tracker.cooldown_until.is_some_and(|until| now < until)
                 {
                     warn!(
                         "🧭 OMS: {} late OrderPlaced ignored during buy reopen cooldown",
@@ -382,7 +485,7 @@ impl OrderManager {
                     return;
                 }
                 tracker.state = OrderState::Live(target);
-                tracker.last_action = Instant::now();
+                tracker.last_action = now;
                 info!("✅ OMS: {} OrderPlaced -> Live", slot.as_str());
             }
             _ => {}
@@ -390,10 +493,11 @@ impl OrderManager {
     }
 
     async fn handle_failed(&mut self, slot: OrderSlot, cooldown_ms: u64) {
+        let now = self.clock.now();
         let tracker = self.tracker_mut(slot);
         tracker.state = OrderState::Idle;
         if cooldown_ms > 0 {
-            let until = Instant::now() + Duration::from_millis(cooldown_ms);
+            let until = now + Duration::from_millis(cooldown_ms);
             tracker.cooldown_until = Some(until);
             warn!(
                 "⏳ OMS: {} OrderFailed — cooldown {}s",
@@ -413,13 +517,14 @@ impl OrderManager {
     }
 
     async fn handle_slot_busy(&mut self, slot: OrderSlot) {
+        let now = self.clock.now();
         let tracker = self.tracker_mut(slot);
         warn!(
             "⛔ OMS: {} SlotBusy — switching to PendingCancel to recover stale live slot",
             slot.as_str()
         );
         tracker.state = OrderState::PendingCancel(None);
-        tracker.last_action = Instant::now();
+        tracker.last_action = now;
         let _ = self
             .exec_tx
             .send(ExecutionCmd::CancelSlot {
@@ -431,16 +536,17 @@ impl OrderManager {
 
     async fn handle_filled(&mut self, slot: OrderSlot) {
         let buy_fill_reopen_cooldown = self.buy_fill_reopen_cooldown;
+        let now = self.clock.now();
         let tracker = self.tracker_mut(slot);
         info!("✅ OMS: {} OrderFilled -> Slot freed", slot.as_str());
         tracker.state = OrderState::Idle;
         tracker.desired = None;
         if slot.direction == TradeDirection::Buy && !buy_fill_reopen_cooldown.is_zero() {
-            tracker.cooldown_until = Some(Instant::now() + buy_fill_reopen_cooldown);
+            tracker.cooldown_until = Some(now + buy_fill_reopen_cooldown);
         }
         let _ = self.slot_release_tx.send(SlotReleaseEvent { slot }).await;
         if slot.direction == TradeDirection::Buy {
-            let until = Instant::now() + SELL_AVAILABLE_WARMUP;
+            let until = now + SELL_AVAILABLE_WARMUP;
             self.set_sell_available_after(slot.side, Some(until));
             info!(
                 "⏳ OMS: {:?} sell availability warmup {}ms after {} fill",
@@ -463,7 +569,7 @@ impl OrderManager {
         );
         *self.side_taker_mut(side) = SideTakerState::Idle;
         if cooldown_ms > 0 {
-            let until = Instant::now() + Duration::from_millis(cooldown_ms);
+            let until = self.clock.now() + Duration::from_millis(cooldown_ms);
             for slot in OrderSlot::side_slots(side) {
                 self.tracker_mut(slot).cooldown_until = Some(until);
             }
@@ -503,9 +609,10 @@ impl OrderManager {
     }
 
     async fn pump_slot(&mut self, slot: OrderSlot) {
+        let now = self.clock.now();
         if slot.direction == TradeDirection::Sell {
             if let Some(until) = self.sell_available_after(slot.side) {
-                if Instant::now() < until {
+                if now < until {
                     return;
                 }
                 self.set_sell_available_after(slot.side, None);
@@ -516,7 +623,7 @@ impl OrderManager {
         {
             let tracker = self.tracker_mut(slot);
             if let Some(until) = tracker.cooldown_until {
-                if Instant::now() < until {
+                if now < until {
                     return;
                 }
                 tracker.cooldown_until = None;
@@ -526,7 +633,7 @@ impl OrderManager {
         let submit_timed_out = {
             let tracker = self.tracker(slot);
             matches!(tracker.state, OrderState::PendingSubmit(_))
-                && tracker.last_action.elapsed() > PENDING_SUBMIT_TIMEOUT
+                && now.saturating_duration_since(tracker.last_action) > PENDING_SUBMIT_TIMEOUT
         };
         if submit_timed_out {
             warn!(
@@ -551,17 +658,17 @@ impl OrderManager {
                     .await;
                 let tracker = self.tracker_mut(slot);
                 tracker.state = OrderState::PendingCancel(None);
-                tracker.last_action = Instant::now();
+                tracker.last_action = now;
             } else {
                 self.tracker_mut(slot).state = OrderState::Idle;
             }
-            self.tracker_mut(slot).cooldown_until = Some(Instant::now() + PENDING_TIMEOUT_COOLDOWN);
+            self.tracker_mut(slot).cooldown_until = Some(now + PENDING_TIMEOUT_COOLDOWN);
         }
 
         let cancel_timed_out = {
             let tracker = self.tracker(slot);
             matches!(tracker.state, OrderState::PendingCancel(_))
-                && tracker.last_action.elapsed() > PENDING_CANCEL_TIMEOUT
+                && now.saturating_duration_since(tracker.last_action) > PENDING_CANCEL_TIMEOUT
         };
         if cancel_timed_out {
             warn!(
@@ -577,7 +684,7 @@ impl OrderManager {
                 .await;
             let tracker = self.tracker_mut(slot);
             tracker.state = OrderState::Idle;
-            tracker.cooldown_until = Some(Instant::now() + PENDING_TIMEOUT_COOLDOWN);
+            tracker.cooldown_until = Some(now + PENDING_TIMEOUT_COOLDOWN);
         }
 
         let current_state = self.tracker(slot).state.clone();
@@ -606,7 +713,7 @@ impl OrderManager {
                     };
                     let tracker = self.tracker_mut(slot);
                     tracker.state = OrderState::PendingSubmit(desired.clone());
-                    tracker.last_action = Instant::now();
+                    tracker.last_action = now;
                     tracker.pair_arb_local_unreleased_matched_notional_usdc = 0.0;
                     let _ = self.exec_tx.send(cmd).await;
                 }
@@ -631,7 +738,7 @@ impl OrderManager {
                             .await;
                         let tracker = self.tracker_mut(slot);
                         tracker.state = OrderState::PendingCancel(Some(live));
-                        tracker.last_action = Instant::now();
+                        tracker.last_action = now;
                     }
                 } else {
                     let clear_reason = self.tracker(slot).clear_reason;
@@ -644,7 +751,7 @@ impl OrderManager {
                         .await;
                     let tracker = self.tracker_mut(slot);
                     tracker.state = OrderState::PendingCancel(Some(live));
-                    tracker.last_action = Instant::now();
+                    tracker.last_action = now;
                 }
             }
             OrderState::PendingCancel(_) => {}
@@ -1203,4 +1310,34 @@ mod tests {
         drop(cmd_tx);
         let _ = h.await;
     }
+
+    #[tokio::test]
+    async fn test_state_store_restores_failure_cooldown_for_same_market() {
+        use crate::polymarket::actor_state_store::ActorStateStore;
+
+        let store = ActorStateStore::open_in_memory(60)
+            .unwrap()
+            .for_market("btc-updown-15m-1", Duration::from_millis(1));
+        let (_cmd_tx, cmd_rx) = mpsc::channel(4);
+        let (exec_tx, _exec_rx) = mpsc::channel(4);
+        let (_result_tx, result_rx) = mpsc::channel(4);
+        let (slot_release_tx, _slot_release_rx) = mpsc::channel(4);
+        let mut om = OrderManager::new(cmd_rx, exec_tx, result_rx, slot_release_tx)
+            .with_state_store(store.clone())
+            .await;
+        om.handle_failed(OrderSlot::NO_BUY, 30_000).await;
+        om.maybe_save_state();
+
+        let (_cmd_tx, cmd_rx) = mpsc::channel(4);
+        let (exec_tx, _exec_rx) = mpsc::channel(4);
+        let (_result_tx, result_rx) = mpsc::channel(4);
+        let (slot_release_tx, _slot_release_rx) = mpsc::channel(4);
+        let restarted = OrderManager::new(cmd_rx, exec_tx, result_rx, slot_release_tx)
+            .with_state_store(store)
+            .await;
+        let snapshot = restarted.export_state();
+        let remaining = snapshot.slot_cooldown_ms[OrderSlot::NO_BUY.index()].unwrap();
+        assert!(remaining > 25_000 && remaining <= 30_000, "{remaining}");
+        assert!(snapshot.slot_cooldown_ms[OrderSlot::YES_BUY.index()].is_none());
+    }
 }
@@ -43,6 +43,11 @@ pub struct PairTranche {
     pub pairable_qty: f64,
     pub pair_cost_tranche: f64,
     pub pair_cost_fifo_ref: f64,
+    /// Fee-inclusive `pair_cost_tranche` (net fees per share folded into each
+    /// leg's VWAP). Accounting only; the VWAPs above stay raw fill prices.
+    pub pair_cost_net: f64,
+    /// Net fees (paid minus maker rebates) of the tranche's live lots, USDC.
+    pub fees_usdc: f64,
     pub gross_surplus: f64,
     pub spendable_surplus: f64,
     pub repair_spent: f64,
@@ -87,6 +92,10 @@ pub struct PairLedgerSnapshot {
     pub repair_budget_available: f64,
     pub capital_state: CapitalState,
     pub recent_closed: [Option<PairTranche>; PGT_RECENT_CLOSED_LIMIT],
+    /// Residual shares sold back into the book instead of being completed.
+    pub residual_unwound_qty: f64,
+    /// Realized PnL of residual unwinds: sell proceeds minus first-leg lot cost.
+    pub residual_unwind_realized_pnl: f64,
 }
 
 impl Default for PairLedgerSnapshot {
@@ -101,6 +110,8 @@ impl Default for PairLedgerSnapshot {
             repair_budget_available: 0.0,
             capital_state: CapitalState::default(),
             recent_closed: [None; PGT_RECENT_CLOSED_LIMIT],
+            residual_unwound_qty: 0.0,
+            residual_unwind_realized_pnl: 0.0,
         }
     }
 }
@@ -145,6 +156,8 @@ pub(crate) struct PairLedgerEvent {
     pub(crate) direction: TradeDirection,
     pub(crate) size: f64,
     pub(crate) price: f64,
+    /// Net fee of the fill (fee paid minus maker rebate).
+    pub(crate) fee: f64,
     pub(crate) ts: Instant,
     pub(crate) kind: PairLedgerEventKind,
 }
@@ -159,6 +172,8 @@ pub(crate) struct PairLedgerBuildResult {
 struct Lot {
     qty: f64,
     price: f64,
+    /// Net fee per share.
+    fee: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -170,7 +185,15 @@ struct WorkingTranche {
 }
 
 impl WorkingTranche {
-    fn new(id: u64, side: Side, qty: f64, price: f64, ts: Instant, path_kind: PathKind) -> Self {
+    fn new(
+        id: u64,
+        side: Side,
+        qty: f64,
+        price: f64,
+        fee: f64,
+        ts: Instant,
+        path_kind: PathKind,
+    ) -> Self {
         let mut tranche = Self {
             snapshot: PairTranche {
                 id,
@@ -184,6 +207,8 @@ impl WorkingTranche {
                 pairable_qty: 0.0,
                 pair_cost_tranche: 0.0,
                 pair_cost_fifo_ref: 0.0,
+                pair_cost_net: 0.0,
+                fees_usdc: 0.0,
                 gross_surplus: 0.0,
                 spendable_surplus: 0.0,
                 repair_spent: 0.0,
@@ -197,7 +222,7 @@ impl WorkingTranche {
             hedge_lots: VecDeque::new(),
             same_side_add_qty: 0.0,
         };
-        tranche.add_first(qty, price, ts, false);
+        tranche.add_first(qty, price, fee, ts, false);
         tranche
     }
 
@@ -205,11 +230,11 @@ impl WorkingTranche {
         self.snapshot.first_side
     }
 
-    fn add_first(&mut self, qty: f64, price: f64, ts: Instant, mark_same_side_add: bool) {
+    fn add_first(&mut self, qty: f64, price: f64, fee: f64, ts: Instant, mark_same_side_add: bool) {
         if qty <= PAIR_LEDGER_EPS {
             return;
         }
-        self.first_lots.push_back(Lot { qty, price });
+        self.first_lots.push_back(Lot { qty, price, fee });
         if mark_same_side_add {
             self.same_side_add_qty += qty.max(0.0);
             self.snapshot.same_side_add_count = self.snapshot.same_side_add_count.saturating_add(1);
@@ -218,11 +243,11 @@ impl WorkingTranche {
         self.recompute();
     }
 
-    fn add_hedge(&mut self, qty: f64, price: f64, ts: Instant) {
+    fn add_hedge(&mut self, qty: f64, price: f64, fee: f64, ts: Instant) {
         if qty <= PAIR_LEDGER_EPS {
             return;
         }
-        self.hedge_lots.push_back(Lot { qty, price });
+        self.hedge_lots.push_back(Lot { qty, price, fee });
         self.snapshot.closed_at = Some(ts);
         self.snapshot.last_transition_at = Some(ts);
         self.recompute();
@@ -253,6 +278,35 @@ impl WorkingTranche {
         qty
     }
 
+    /// Sell back unhedged first-leg shares. Residual lots are the newest ones
+    /// (hedges pair FIFO), so they are consumed from the back. Returns
+    /// `(unwound_qty, realized_pnl)`; the lots' buy fees count against it.
+    fn unwind_residual(&mut self, qty: f64, price: f64, ts: Instant) -> (f64, f64) {
+        let mut remaining = qty.min(self.snapshot.residual_qty.max(0.0));
+        if remaining <= PAIR_LEDGER_EPS {
+            return (0.0, 0.0);
+        }
+        let mut unwound = 0.0;
+        let mut realized = 0.0;
+        while remaining > PAIR_LEDGER_EPS {
+            let Some(mut lot) = self.first_lots.pop_back() else {
+                break;
+            };
+            let take = lot.qty.min(remaining);
+            lot.qty -= take;
+            remaining -= take;
+            unwound += take;
+            realized += take * (price - lot.price - lot.fee);
+            if lot.qty > PAIR_LEDGER_EPS {
+                self.first_lots.push_back(lot);
+                break;
+            }
+        }
+        self.snapshot.last_transition_at = Some(ts);
+        self.recompute();
+        (unwound, realized)
+    }
+
     fn recompute(&mut self) {
         let first_qty = sum_lots(&self.first_lots);
         let hedge_qty = sum_lots(&self.hedge_lots);
@@ -265,6 +319,19 @@ impl WorkingTranche {
         } else {
             0.0
         };
+        let pair_cost_net = if pairable_qty > PAIR_LEDGER_EPS {
+            pair_cost_tranche
+                + weighted_avg_fee(&self.first_lots)
+                + weighted_avg_fee(&self.hedge_lots)
+        } else {
+            0.0
+        };
+        let fees_usdc = self
+            .first_lots
+            .iter()
+            .chain(self.hedge_lots.iter())
+            .map(|lot| lot.qty.max(0.0) * lot.fee)
+            .sum::<f64>();
         let pair_cost_fifo_ref = if pairable_qty > PAIR_LEDGER_EPS {
             fifo_avg_for_qty(&self.first_lots, pairable_qty)
                 + fifo_avg_for_qty(&self.hedge_lots, pairable_qty)
@@ -284,6 +351,8 @@ impl WorkingTranche {
         self.snapshot.pairable_qty = pairable_qty;
         self.snapshot.pair_cost_tranche = pair_cost_tranche;
         self.snapshot.pair_cost_fifo_ref = pair_cost_fifo_ref;
+        self.snapshot.pair_cost_net = pair_cost_net;
+        self.snapshot.fees_usdc = fees_usdc;
         self.snapshot.gross_surplus = gross_surplus;
         self.snapshot.spendable_surplus = spendable_surplus;
         self.snapshot.repair_spent = repair_spent;
@@ -309,6 +378,8 @@ struct EpisodeStats {
     residual_before_new_open: Vec<f64>,
     close_delays_secs: Vec<f64>,
     conditional_second_same_side_would_allow: u64,
+    residual_unwound_qty: f64,
+    residual_unwind_realized_pnl: f64,
 }
 
 #[derive(Debug)]
@@ -333,14 +404,18 @@ impl PairLedgerBuilder {
         }
     }
 
-    fn push_buy(&mut self, side: Side, qty: f64, price: f64, ts: Instant) {
+    /// The buy's net fee rides on its lots as a per-share amount: VWAPs and
+    /// surplus stay on raw prices (strategies price off them), while
+    /// `pair_cost_net` / `fees_usdc` carry the fee-inclusive accounting.
+    fn push_buy(&mut self, side: Side, qty: f64, price: f64, fee: f64, ts: Instant) {
         if qty <= PAIR_LEDGER_EPS {
             return;
         }
+        let fee = fee / qty;
         self.stats.buy_fill_count = self.stats.buy_fill_count.saturating_add(1);
         if let Some(active) = self.active.as_mut() {
             if active.first_side() == Some(side) {
-                active.add_first(qty, price, ts, true);
+                active.add_first(qty, price, fee, ts, true);
                 self.stats.same_side_add_qty += qty.max(0.0);
                 self.stats.conditional_second_same_side_would_allow = self
                     .stats
@@ -352,7 +427,7 @@ impl PairLedgerBuilder {
             let residual_before = active.snapshot.residual_qty.max(0.0);
             let consumed = qty.min(residual_before);
             if consumed > PAIR_LEDGER_EPS {
-                active.add_hedge(consumed, price, ts);
+                active.add_hedge(consumed, price, fee, ts);
             }
             let overshoot = (qty - consumed).max(0.0);
             if active.snapshot.residual_qty <= PAIR_LEDGER_EPS {
@@ -363,13 +438,39 @@ impl PairLedgerBuilder {
             }
             if overshoot > PAIR_LEDGER_EPS {
                 self.stats.residual_before_new_open.push(0.0);
-                self.open_new_tranche(side, overshoot, price, ts);
+                self.open_new_tranche(side, overshoot, price, fee, ts);
             }
             return;
         }
 
         self.stats.residual_before_new_open.push(0.0);
-        self.open_new_tranche(side, qty, price, ts);
+        self.open_new_tranche(side, qty, price, fee, ts);
+    }
+
+    /// A SELL fill on the active tranche's residual side is a residual unwind;
+    /// anything beyond the residual keeps the legacy merge accounting. The
+    /// unwound share of the sell's net fee is booked against realized PnL.
+    fn push_sell(&mut self, side: Side, qty: f64, price: f64, fee: f64, ts: Instant) {
+        let mut leftover = qty;
+        if let Some(active) = self.active.as_mut() {
+            if active.first_side() == Some(side) && active.snapshot.residual_qty > PAIR_LEDGER_EPS {
+                let (unwound, realized) = active.unwind_residual(qty, price, ts);
+                leftover -= unwound;
+                self.stats.residual_unwound_qty += unwound;
+                self.stats.residual_unwind_realized_pnl += realized - fee * (unwound / qty);
+                if active.snapshot.residual_qty <= PAIR_LEDGER_EPS {
+                    let mut closed = self.active.take().expect("active tranche present");
+                    closed.snapshot.closed_at.get_or_insert(ts);
+                    if closed.snapshot.state == TrancheState::PairCovered {
+                        self.record_close(&closed.snapshot);
+                        self.covered.push(closed);
+                    } else {
+                        self.archived.push(closed);
+                    }
+                }
+            }
+        }
+        self.apply_merge(leftover, ts);
     }
 
     fn apply_merge(&mut self, mut qty: f64, ts: Instant) {
@@ -398,7 +499,7 @@ impl PairLedgerBuilder {
         let _ = qty;
     }
 
-    fn open_new_tranche(&mut self, side: Side, qty: f64, price: f64, ts: Instant) {
+    fn open_new_tranche(&mut self, side: Side, qty: f64, price: f64, fee: f64, ts: Instant) {
         if qty <= PAIR_LEDGER_EPS {
             return;
         }
@@ -410,6 +511,7 @@ impl PairLedgerBuilder {
             side,
             qty,
             price,
+            fee,
             ts,
             self.path_kind,
         ));
@@ -503,6 +605,8 @@ impl PairLedgerBuilder {
             repair_budget_available,
             capital_state,
             recent_closed,
+            residual_unwound_qty: self.stats.residual_unwound_qty,
+            residual_unwind_realized_pnl: self.stats.residual_unwind_realized_pnl,
         };
         let episode_metrics = EpisodeMetrics {
             clean_closed_episode_ratio: ratio(self.stats.clean_closed, self.stats.total_closed),
@@ -541,10 +645,10 @@ pub(crate) fn build_pair_ledger(
         match (event.kind, event.direction) {
             (PairLedgerEventKind::Merge, _) => builder.apply_merge(qty, event.ts),
             (PairLedgerEventKind::Fill, TradeDirection::Buy) => {
-                builder.push_buy(event.side, qty, event.price.max(0.0), event.ts)
+                builder.push_buy(event.side, qty, event.price.max(0.0), event.fee, event.ts)
             }
             (PairLedgerEventKind::Fill, TradeDirection::Sell) => {
-                builder.apply_merge(qty, event.ts);
+                builder.push_sell(event.side, qty, event.price.max(0.0), event.fee, event.ts);
             }
         }
     }
@@ -620,6 +724,17 @@ fn fifo_avg_for_qty(lots: &VecDeque<Lot>, target_qty: f64) -> f64 {
     }
 }
 
+fn weighted_avg_fee(lots: &VecDeque<Lot>) -> f64 {
+    let qty = sum_lots(lots);
+    if qty <= PAIR_LEDGER_EPS {
+        return 0.0;
+    }
+    lots.iter()
+        .map(|lot| lot.qty.max(0.0) * lot.fee)
+        .sum::<f64>()
+        / qty
+}
+
 fn consume_lots_fifo(lots: &mut VecDeque<Lot>, mut target_qty: f64) {
     while target_qty > PAIR_LEDGER_EPS {
         let Some(mut lot) = lots.pop_front() else {
@@ -688,6 +803,7 @@ mod tests {
             direction: TradeDirection::Buy,
             size,
             price,
+            fee: 0.0,
             ts,
             kind: PairLedgerEventKind::Fill,
         }
@@ -699,6 +815,7 @@ mod tests {
             direction: TradeDirection::Sell,
             size,
             price: 0.0,
+            fee: 0.0,
             ts,
             kind: PairLedgerEventKind::Merge,
         }
@@ -825,6 +942,80 @@ mod tests {
         assert!((active.residual_qty - 20.0).abs() < 1e-9);
     }
 
+    #[test]
+    fn residual_sell_books_realized_pnl_and_covers_tranche() {
+        let now = Instant::now();
+        let sell = PairLedgerEvent {
+            side: Side::Yes,
+            direction: TradeDirection::Sell,
+            size: 20.0,
+            price: 0.46,
+            fee: 0.0,
+            ts: now,
+            kind: PairLedgerEventKind::Fill,
+        };
+        let result = build_pair_ledger(
+            &[
+                fill(Side::Yes, 80.0, 0.40, now),
+                fill(Side::Yes, 20.0, 0.50, now),
+                fill(Side::No, 80.0, 0.52, now),
+                sell,
+            ],
+            PathKind::MakerShadow,
+        );
+        assert!(result.snapshot.active_tranche.is_none());
+        let closed = result.snapshot.recent_closed[0].expect("covered tranche");
+        assert_eq!(closed.state, TrancheState::PairCovered);
+        assert!((closed.pairable_qty - 80.0).abs() < 1e-9);
+        assert!((closed.first_vwap - 0.40).abs() < 1e-9);
+        assert!((result.snapshot.residual_unwound_qty - 20.0).abs() < 1e-9);
+        assert!((result.snapshot.residual_unwind_realized_pnl - -0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn buy_fees_are_tracked_beside_raw_tranche_cost() {
+        let now = Instant::now();
+        let mut first = fill(Side::Yes, 100.0, 0.45, now);
+        // Maker rebate on the first leg, taker fee on the hedge.
+        first.fee = -0.5;
+        let mut hedge = fill(Side::No, 100.0, 0.50, now);
+        hedge.fee = 1.0;
+        let result = build_pair_ledger(&[first, hedge], PathKind::MakerShadow);
+        let closed = result.snapshot.recent_closed[0].expect("covered tranche");
+        // Strategy-facing fields stay on raw fill prices.
+        assert!((closed.first_vwap - 0.45).abs() < 1e-9);
+        assert!((closed.hedge_vwap - 0.50).abs() < 1e-9);
+        assert!((closed.pair_cost_tranche - 0.95).abs() < 1e-9);
+        assert!((closed.gross_surplus - 5.0).abs() < 1e-9);
+        assert!((closed.pair_cost_net - 0.955).abs() < 1e-9);
+        assert!((closed.fees_usdc - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn residual_sell_fee_is_prorated_to_the_unwound_share() {
+        let now = Instant::now();
+        let sell = PairLedgerEvent {
+            side: Side::Yes,
+            direction: TradeDirection::Sell,
+            size: 40.0,
+            price: 0.50,
+            fee: 0.40,
+            ts: now,
+            kind: PairLedgerEventKind::Fill,
+        };
+        let result = build_pair_ledger(
+            &[
+                fill(Side::Yes, 100.0, 0.50, now),
+                fill(Side::No, 80.0, 0.45, now),
+                sell,
+            ],
+            PathKind::MakerShadow,
+        );
+        // Only 20 of the 40 sold shares were residual: half the fee is booked.
+        assert!((result.snapshot.residual_unwound_qty - 20.0).abs() < 1e-9);
+        assert!((result.snapshot.residual_unwind_realized_pnl - -0.20).abs() < 1e-9);
+    }
+
     #[test]
     fn urgency_budget_shadow_5m_steps_up_into_close() {
         assert!((urgency_budget_shadow_5m(121, true) - 0.0).abs() < 1e-9);
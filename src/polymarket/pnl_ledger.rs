@@ -0,0 +1,827 @@
+//! Round-level PnL ledger.
+//!
+//! Round summaries, their fills, closed pair tranches, merges and redeems are
+//! written to one local SQLite file shared by every instance on the host, so
+//! rolling aggregates are queries rather than rescans of the JSONL log and
+//! research tools can read the same DB directly:
+//!
+//! ```sql
+//! SELECT strategy, COUNT(*), SUM(realized_cash_pnl), SUM(fees_paid - maker_rebate)
+//! FROM rounds WHERE partial_round = 0 GROUP BY strategy;
+//! ```
+//!
+//! Every row carries the writing `instance_id`; rolling aggregates filter on
+//! it so instances sharing the file never mix rounds. Tranche, merge and
+//! redeem rows come from trading actors and go through `PnlLedgerWriter`'s
+//! thread, so a lock held by another process never stalls them.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::fees::FeeQuote;
+use super::messages::TradeDirection;
+use super::pair_ledger::PairTranche;
+use super::types::Side;
+
+#[derive(Debug, Clone)]
+pub struct PnlLedgerConfig {
+    pub path: PathBuf,
+    /// Tag written on every row so instances sharing the file stay separable.
+    pub instance_id: String,
+}
+
+impl Default for PnlLedgerConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("data/ledger/pnl.sqlite"),
+            instance_id: "default".to_string(),
+        }
+    }
+}
+
+impl PnlLedgerConfig {
+    /// `None` when `PM_PNL_LEDGER_ENABLED=false`; round aggregates then fall
+    /// back to rescanning the JSONL log.
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("PM_PNL_LEDGER_ENABLED")
+            .ok()
+            .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if !enabled {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            path: std::env::var("PM_PNL_LEDGER_PATH")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or(defaults.path),
+            instance_id: std::env::var("PM_INSTANCE_ID")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or(defaults.instance_id),
+        })
+    }
+}
+
+/// One finished round; `summary_json` keeps the full validation summary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoundRecord {
+    pub strategy: String,
+    pub market_slug: String,
+    pub round_start_ts_ms: i64,
+    pub round_end_ts_ms: i64,
+    pub partial_round: bool,
+    pub buy_fill_count: u64,
+    pub sell_fill_count: u64,
+    pub realized_cash_pnl: f64,
+    pub realized_round_pnl_ex_residual: f64,
+    pub mark_to_mid_pnl_end: Option<f64>,
+    pub residual_inventory_cost_end: f64,
+    pub fees_paid: f64,
+    pub maker_rebate: f64,
+    pub max_abs_net_diff: f64,
+    pub time_weighted_abs_net_diff: f64,
+    pub fill_to_adverse_move_3s: Option<f64>,
+    pub fill_to_adverse_move_10s: Option<f64>,
+    pub loss_attribution: Option<String>,
+    pub summary_json: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LedgerFill {
+    pub ts_ms: i64,
+    pub side: Side,
+    pub direction: TradeDirection,
+    pub size: f64,
+    pub price: f64,
+    pub fee: FeeQuote,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedeemRecord {
+    pub condition_id: String,
+    pub execution_mode: String,
+    pub tx_hash: Option<String>,
+    pub relayer_tx_id: Option<String>,
+    pub confirmed: bool,
+    pub value_usdc: f64,
+}
+
+/// Rolling round statistics; field names match the JSON aggregate report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundAggregate {
+    pub rounds_total: usize,
+    pub rounds_partial: usize,
+    pub rounds_full: usize,
+    pub total_realized_cash_pnl: f64,
+    pub total_realized_round_pnl_ex_residual: f64,
+    pub total_mark_to_mid_pnl_end: f64,
+    pub total_residual_inventory_cost_end: f64,
+    #[serde(default)]
+    pub total_fees_paid: f64,
+    #[serde(default)]
+    pub total_maker_rebate: f64,
+    pub median_round_pnl: Option<f64>,
+    pub median_round_pnl_ex_residual: Option<f64>,
+    pub mean_fill_to_adverse_move_3s: Option<f64>,
+    pub mean_fill_to_adverse_move_10s: Option<f64>,
+    pub mean_max_abs_net_diff: Option<f64>,
+    pub mean_time_weighted_abs_net_diff: Option<f64>,
+}
+
+/// Which rounds an aggregate covers; `None` fields match everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundFilter<'a> {
+    pub instance_id: Option<&'a str>,
+    pub strategy: Option<&'a str>,
+    pub market_slug: Option<&'a str>,
+}
+
+/// Shared SQLite handle; cheap to clone across actors.
+#[derive(Clone)]
+pub struct PnlLedger {
+    conn: Arc<Mutex<Connection>>,
+    instance_id: String,
+}
+
+impl PnlLedger {
+    pub fn open(path: &Path, instance_id: &str) -> rusqlite::Result<Self> {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        Self::init(Connection::open(path)?, instance_id)
+    }
+
+    pub fn open_in_memory(instance_id: &str) -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?, instance_id)
+    }
+
+    /// Open the ledger from config, logging and returning `None` on failure so
+    /// a broken ledger file never blocks trading.
+    pub fn open_from_config(cfg: &PnlLedgerConfig) -> Option<Self> {
+        match Self::open(&cfg.path, &cfg.instance_id) {
+            Ok(ledger) => Some(ledger),
+            Err(e) => {
+                warn!("⚠️ pnl ledger unavailable at {}: {}", cfg.path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn init(conn: Connection, instance_id: &str) -> rusqlite::Result<Self> {
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+             PRAGMA synchronous=NORMAL;
+             PRAGMA foreign_keys=ON;
+             CREATE TABLE IF NOT EXISTS rounds (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 instance_id TEXT NOT NULL,
+                 strategy TEXT NOT NULL,
+                 market_slug TEXT NOT NULL,
+                 round_start_ts_ms INTEGER NOT NULL,
+                 round_end_ts_ms INTEGER NOT NULL,
+                 partial_round INTEGER NOT NULL,
+                 buy_fill_count INTEGER NOT NULL,
+                 sell_fill_count INTEGER NOT NULL,
+                 realized_cash_pnl REAL NOT NULL,
+                 realized_round_pnl_ex_residual REAL NOT NULL,
+                 mark_to_mid_pnl_end REAL,
+                 residual_inventory_cost_end REAL NOT NULL,
+                 fees_paid REAL NOT NULL,
+                 maker_rebate REAL NOT NULL,
+                 max_abs_net_diff REAL NOT NULL,
+                 time_weighted_abs_net_diff REAL NOT NULL,
+                 fill_to_adverse_move_3s REAL,
+                 fill_to_adverse_move_10s REAL,
+                 loss_attribution TEXT,
+                 summary_json TEXT NOT NULL,
+                 UNIQUE (instance_id, strategy, market_slug, round_start_ts_ms)
+             );
+             CREATE INDEX IF NOT EXISTS idx_rounds_strategy_end
+                 ON rounds (strategy, round_end_ts_ms);
+             CREATE INDEX IF NOT EXISTS idx_rounds_market_end
+                 ON rounds (market_slug, round_end_ts_ms);
+             CREATE TABLE IF NOT EXISTS fills (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 round_id INTEGER NOT NULL REFERENCES rounds (id) ON DELETE CASCADE,
+                 market_slug TEXT NOT NULL,
+                 ts_ms INTEGER NOT NULL,
+                 side TEXT NOT NULL,
+                 direction TEXT NOT NULL,
+                 size REAL NOT NULL,
+                 price REAL NOT NULL,
+                 fee REAL NOT NULL,
+                 rebate REAL NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_fills_round ON fills (round_id);
+             CREATE INDEX IF NOT EXISTS idx_fills_market_ts ON fills (market_slug, ts_ms);
+             CREATE TABLE IF NOT EXISTS tranches (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 instance_id TEXT NOT NULL,
+                 market_slug TEXT NOT NULL,
+                 tranche_id INTEGER NOT NULL,
+                 first_side TEXT,
+                 state TEXT NOT NULL,
+                 pairable_qty REAL NOT NULL,
+                 residual_qty REAL NOT NULL,
+                 pair_cost REAL NOT NULL,
+                 gross_surplus REAL NOT NULL,
+                 closed_ts_ms INTEGER NOT NULL,
+                 fees REAL NOT NULL DEFAULT 0
+             );
+             CREATE INDEX IF NOT EXISTS idx_tranches_market_ts
+                 ON tranches (market_slug, closed_ts_ms);
+             CREATE TABLE IF NOT EXISTS merges (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 instance_id TEXT NOT NULL,
+                 market_slug TEXT NOT NULL,
+                 merge_id TEXT NOT NULL,
+                 full_set_size REAL NOT NULL,
+                 ts_ms INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_merges_market_ts ON merges (market_slug, ts_ms);
+             CREATE TABLE IF NOT EXISTS redeems (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 instance_id TEXT NOT NULL,
+                 market_slug TEXT NOT NULL,
+                 condition_id TEXT NOT NULL,
+                 execution_mode TEXT NOT NULL,
+                 tx_hash TEXT,
+                 relayer_tx_id TEXT,
+                 confirmed INTEGER NOT NULL,
+                 value_usdc REAL NOT NULL,
+                 ts_ms INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_redeems_market_ts ON redeems (market_slug, ts_ms);",
+        )?;
+        // Files created before tranche fees were recorded.
+        if conn.prepare("SELECT fees FROM tranches LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE tranches ADD COLUMN fees REAL NOT NULL DEFAULT 0;")?;
+        }
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            instance_id: instance_id.to_string(),
+        })
+    }
+
+    /// Write a round and its fills atomically. Re-recording the same round
+    /// (restart mid-round) replaces the previous row and its fills.
+    pub fn record_round(&self, round: &RoundRecord, fills: &[LedgerFill]) -> rusqlite::Result<i64> {
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM rounds
+             WHERE instance_id = ?1 AND strategy = ?2 AND market_slug = ?3
+               AND round_start_ts_ms = ?4",
+            params![
+                self.instance_id,
+                round.strategy,
+                round.market_slug,
+                round.round_start_ts_ms
+            ],
+        )?;
+        tx.execute(
+            "INSERT INTO rounds (
+                 instance_id, strategy, market_slug, round_start_ts_ms, round_end_ts_ms,
+                 partial_round, buy_fill_count, sell_fill_count, realized_cash_pnl,
+                 realized_round_pnl_ex_residual, mark_to_mid_pnl_end,
+                 residual_inventory_cost_end, fees_paid, maker_rebate, max_abs_net_diff,
+                 time_weighted_abs_net_diff, fill_to_adverse_move_3s,
+                 fill_to_adverse_move_10s, loss_attribution, summary_json
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+                       ?16, ?17, ?18, ?19, ?20)",
+            params![
+                self.instance_id,
+                round.strategy,
+                round.market_slug,
+                round.round_start_ts_ms,
+                round.round_end_ts_ms,
+                round.partial_round,
+                round.buy_fill_count as i64,
+                round.sell_fill_count as i64,
+                round.realized_cash_pnl,
+                round.realized_round_pnl_ex_residual,
+                round.mark_to_mid_pnl_end,
+                round.residual_inventory_cost_end,
+                round.fees_paid,
+                round.maker_rebate,
+                round.max_abs_net_diff,
+                round.time_weighted_abs_net_diff,
+                round.fill_to_adverse_move_3s,
+                round.fill_to_adverse_move_10s,
+                round.loss_attribution,
+                round.summary_json,
+            ],
+        )?;
+        let round_id = tx.last_insert_rowid();
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO fills (
+                     round_id, market_slug, ts_ms, side, direction, size, price, fee, rebate
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for fill in fills {
+                stmt.execute(params![
+                    round_id,
+                    round.market_slug,
+                    fill.ts_ms,
+                    fill.side.as_str(),
+                    direction_str(fill.direction),
+                    fill.size,
+                    fill.price,
+                    fill.fee.fee,
+                    fill.fee.rebate,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(round_id)
+    }
+
+    pub fn record_tranche(
+        &self,
+        market_slug: &str,
+        tranche: &PairTranche,
+        closed_ts_ms: i64,
+    ) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT INTO tranches (
+                 instance_id, market_slug, tranche_id, first_side, state, pairable_qty,
+                 residual_qty, pair_cost, gross_surplus, closed_ts_ms, fees
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                self.instance_id,
+                market_slug,
+                tranche.id as i64,
+                tranche.first_side.as_ref().map(Side::as_str),
+                format!("{:?}", tranche.state),
+                tranche.pairable_qty,
+                tranche.residual_qty,
+                tranche.pair_cost_tranche,
+                tranche.gross_surplus,
+                closed_ts_ms,
+                tranche.fees_usdc,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_merge(
+        &self,
+        market_slug: &str,
+        merge_id: &str,
+        full_set_size: f64,
+        ts_ms: i64,
+    ) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT INTO merges (instance_id, market_slug, merge_id, full_set_size, ts_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.instance_id,
+                market_slug,
+                merge_id,
+                full_set_size,
+                ts_ms
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_redeem(
+        &self,
+        market_slug: &str,
+        redeem: &RedeemRecord,
+        ts_ms: i64,
+    ) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT INTO redeems (
+                 instance_id, market_slug, condition_id, execution_mode, tx_hash,
+                 relayer_tx_id, confirmed, value_usdc, ts_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                self.instance_id,
+                market_slug,
+                redeem.condition_id,
+                redeem.execution_mode,
+                redeem.tx_hash,
+                redeem.relayer_tx_id,
+                redeem.confirmed,
+                redeem.value_usdc,
+                ts_ms,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn round_count(&self, filter: RoundFilter<'_>) -> rusqlite::Result<usize> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let n: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM rounds
+             WHERE (?1 IS NULL OR strategy = ?1) AND (?2 IS NULL OR market_slug = ?2)
+               AND (?3 IS NULL OR instance_id = ?3)",
+            params![filter.strategy, filter.market_slug, filter.instance_id],
+            |row| row.get(0),
+        )?;
+        Ok(n.max(0) as usize)
+    }
+
+    /// Aggregate over all matching rounds, or the most recent `last_n`.
+    pub fn aggregate(
+        &self,
+        filter: RoundFilter<'_>,
+        last_n: Option<usize>,
+    ) -> rusqlite::Result<RoundAggregate> {
+        let limit = last_n.map(|n| n as i64).unwrap_or(-1);
+        let recent = "WITH recent AS (
+                          SELECT * FROM rounds
+                          WHERE (?1 IS NULL OR strategy = ?1)
+                            AND (?2 IS NULL OR market_slug = ?2)
+                            AND (?4 IS NULL OR instance_id = ?4)
+                          ORDER BY round_end_ts_ms DESC, id DESC
+                          LIMIT ?3
+                      )";
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let mut agg = conn.query_row(
+            &format!(
+                "{recent}
+                 SELECT COUNT(*),
+                        COALESCE(SUM(partial_round), 0),
+                        COALESCE(SUM(realized_cash_pnl), 0.0),
+                        COALESCE(SUM(realized_round_pnl_ex_residual), 0.0),
+                        COALESCE(SUM(COALESCE(mark_to_mid_pnl_end, 0.0)), 0.0),
+                        COALESCE(SUM(residual_inventory_cost_end), 0.0),
+                        COALESCE(SUM(fees_paid), 0.0),
+                        COALESCE(SUM(maker_rebate), 0.0),
+                        AVG(fill_to_adverse_move_3s),
+                        AVG(fill_to_adverse_move_10s),
+                        AVG(max_abs_net_diff),
+                        AVG(time_weighted_abs_net_diff)
+                 FROM recent"
+            ),
+            params![
+                filter.strategy,
+                filter.market_slug,
+                limit,
+                filter.instance_id
+            ],
+            |row| {
+                let rounds_total = row.get::<_, i64>(0)?.max(0) as usize;
+                let rounds_partial = row.get::<_, i64>(1)?.max(0) as usize;
+                Ok(RoundAggregate {
+                    rounds_total,
+                    rounds_partial,
+                    rounds_full: rounds_total.saturating_sub(rounds_partial),
+                    total_realized_cash_pnl: row.get(2)?,
+                    total_realized_round_pnl_ex_residual: row.get(3)?,
+                    total_mark_to_mid_pnl_end: row.get(4)?,
+                    total_residual_inventory_cost_end: row.get(5)?,
+                    total_fees_paid: row.get(6)?,
+                    total_maker_rebate: row.get(7)?,
+                    mean_fill_to_adverse_move_3s: row.get(8)?,
+                    mean_fill_to_adverse_move_10s: row.get(9)?,
+                    mean_max_abs_net_diff: row.get(10)?,
+                    mean_time_weighted_abs_net_diff: row.get(11)?,
+                    ..RoundAggregate::default()
+                })
+            },
+        )?;
+        let median = |expr: &str| -> rusqlite::Result<Option<f64>> {
+            conn.query_row(
+                &format!(
+                    "{recent},
+                     n AS (SELECT COUNT(*) AS c FROM recent)
+                     SELECT AVG(v) FROM (
+                         SELECT {expr} AS v FROM recent ORDER BY v
+                         LIMIT 2 - (SELECT c FROM n) % 2
+                         OFFSET ((SELECT c FROM n) - 1) / 2
+                     )"
+                ),
+                params![
+                    filter.strategy,
+                    filter.market_slug,
+                    limit,
+                    filter.instance_id
+                ],
+                |row| row.get(0),
+            )
+            .optional()
+            .map(Option::flatten)
+        };
+        agg.median_round_pnl = median("realized_cash_pnl + COALESCE(mark_to_mid_pnl_end, 0.0)")?;
+        agg.median_round_pnl_ex_residual = median("realized_round_pnl_ex_residual")?;
+        Ok(agg)
+    }
+
+    /// Aggregate over the most recent `n` rounds, `None` until `n` exist.
+    pub fn aggregate_last(
+        &self,
+        filter: RoundFilter<'_>,
+        n: usize,
+    ) -> rusqlite::Result<Option<RoundAggregate>> {
+        if self.round_count(filter)? < n {
+            return Ok(None);
+        }
+        self.aggregate(filter, Some(n)).map(Some)
+    }
+}
+
+enum LedgerWrite {
+    Tranche {
+        market_slug: String,
+        tranche: PairTranche,
+        closed_ts_ms: i64,
+    },
+    Merge {
+        market_slug: String,
+        merge_id: String,
+        full_set_size: f64,
+        ts_ms: i64,
+    },
+    Redeem {
+        market_slug: String,
+        redeem: RedeemRecord,
+        ts_ms: i64,
+    },
+    Flush(std_mpsc::Sender<()>),
+}
+
+/// Background writer owning a ledger handle; write failures are logged and
+/// never propagate into trading.
+#[derive(Clone)]
+pub struct PnlLedgerWriter {
+    tx: std_mpsc::Sender<LedgerWrite>,
+}
+
+impl PnlLedgerWriter {
+    pub fn spawn(ledger: PnlLedger) -> Self {
+        let (tx, rx) = std_mpsc::channel::<LedgerWrite>();
+        std::thread::spawn(move || {
+            for write in rx {
+                let (what, result) = match write {
+                    LedgerWrite::Tranche {
+                        market_slug,
+                        tranche,
+                        closed_ts_ms,
+                    } => (
+                        "tranche",
+                        ledger.record_tranche(&market_slug, &tranche, closed_ts_ms),
+                    ),
+                    LedgerWrite::Merge {
+                        market_slug,
+                        merge_id,
+                        full_set_size,
+                        ts_ms,
+                    } => (
+                        "merge",
+                        ledger.record_merge(&market_slug, &merge_id, full_set_size, ts_ms),
+                    ),
+                    LedgerWrite::Redeem {
+                        market_slug,
+                        redeem,
+                        ts_ms,
+                    } => ("redeem", ledger.record_redeem(&market_slug, &redeem, ts_ms)),
+                    LedgerWrite::Flush(ack) => {
+                        let _ = ack.send(());
+                        continue;
+                    }
+                };
+                if let Err(e) = result {
+                    warn!("⚠️ pnl ledger {} write failed: {}", what, e);
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    /// Block until every queued write has reached SQLite.
+    pub fn flush(&self) {
+        let (ack_tx, ack_rx) = std_mpsc::channel();
+        if self.tx.send(LedgerWrite::Flush(ack_tx)).is_ok() {
+            let _ = ack_rx.recv();
+        }
+    }
+
+    /// Bind the writer to one market round.
+    pub fn for_market(&self, market_slug: &str) -> MarketPnlLedger {
+        MarketPnlLedger {
+            writer: self.clone(),
+            market_slug: market_slug.to_string(),
+        }
+    }
+
+    fn send(&self, write: LedgerWrite) {
+        if self.tx.send(write).is_err() {
+            warn!("⚠️ pnl ledger writer stopped; row dropped");
+        }
+    }
+}
+
+/// Per-round view handed to actors that record tranche, merge and redeem rows.
+#[derive(Clone)]
+pub struct MarketPnlLedger {
+    writer: PnlLedgerWriter,
+    market_slug: String,
+}
+
+impl MarketPnlLedger {
+    pub fn market_slug(&self) -> &str {
+        &self.market_slug
+    }
+
+    pub fn record_tranche(&self, tranche: &PairTranche, closed_ts_ms: i64) {
+        self.writer.send(LedgerWrite::Tranche {
+            market_slug: self.market_slug.clone(),
+            tranche: *tranche,
+            closed_ts_ms,
+        });
+    }
+
+    pub fn record_merge(&self, merge_id: &str, full_set_size: f64, ts_ms: i64) {
+        self.writer.send(LedgerWrite::Merge {
+            market_slug: self.market_slug.clone(),
+            merge_id: merge_id.to_string(),
+            full_set_size,
+            ts_ms,
+        });
+    }
+
+    pub fn record_redeem(&self, redeem: &RedeemRecord, ts_ms: i64) {
+        self.writer.send(LedgerWrite::Redeem {
+            market_slug: self.market_slug.clone(),
+            redeem: redeem.clone(),
+            ts_ms,
+        });
+    }
+}
+
+fn direction_str(direction: TradeDirection) -> &'static str {
+    match direction {
+        TradeDirection::Buy => "BUY",
+        TradeDirection::Sell => "SELL",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(strategy: &str, slug: &str, start: i64, pnl: f64, partial: bool) -> RoundRecord {
+        RoundRecord {
+            strategy: strategy.to_string(),
+            market_slug: slug.to_string(),
+            round_start_ts_ms: start,
+            round_end_ts_ms: start + 900_000,
+            partial_round: partial,
+            realized_cash_pnl: pnl,
+            realized_round_pnl_ex_residual: pnl,
+            fees_paid: 0.1,
+            max_abs_net_diff: 2.0,
+            summary_json: "{}".to_string(),
+            ..RoundRecord::default()
+        }
+    }
+
+    #[test]
+    fn aggregates_are_filtered_windowed_queries() {
+        let ledger = PnlLedger::open_in_memory("test").unwrap();
+        for (i, pnl) in [1.0, -2.0, 3.0, 4.0, 10.0].into_iter().enumerate() {
+            let start = i as i64 * 900_000;
+            ledger
+                .record_round(&round("pgt", "btc", start, pnl, i == 0), &[])
+                .unwrap();
+        }
+        ledger
+            .record_round(&round("pgt", "eth", 0, 100.0, false), &[])
+            .unwrap();
+
+        let btc = RoundFilter {
+            strategy: Some("pgt"),
+            market_slug: Some("btc"),
+            ..RoundFilter::default()
+        };
+        let all = ledger.aggregate(btc, None).unwrap();
+        assert_eq!(all.rounds_total, 5);
+        assert_eq!(all.rounds_partial, 1);
+        assert_eq!(all.rounds_full, 4);
+        assert!((all.total_realized_cash_pnl - 16.0).abs() < 1e-9);
+        assert!((all.total_fees_paid - 0.5).abs() < 1e-9);
+        assert_eq!(all.median_round_pnl, Some(3.0));
+        assert_eq!(all.mean_max_abs_net_diff, Some(2.0));
+        assert_eq!(all.mean_fill_to_adverse_move_3s, None);
+
+        // Last 4 rounds: -2, 3, 4, 10 → even-count median averages the middle pair.
+        let last_4 = ledger.aggregate_last(btc, 4).unwrap().unwrap();
+        assert_eq!(last_4.rounds_total, 4);
+        assert_eq!(last_4.median_round_pnl_ex_residual, Some(3.5));
+        assert!(ledger.aggregate_last(btc, 6).unwrap().is_none());
+
+        let strategy_wide = RoundFilter {
+            strategy: Some("pgt"),
+            ..RoundFilter::default()
+        };
+        assert_eq!(
+            ledger.aggregate(strategy_wide, None).unwrap().rounds_total,
+            6
+        );
+    }
+
+    #[test]
+    fn aggregates_are_scoped_to_instance() {
+        let a = PnlLedger::open_in_memory("a").unwrap();
+        let b = PnlLedger {
+            conn: a.conn.clone(),
+            instance_id: "b".to_string(),
+        };
+        a.record_round(&round("pgt", "btc", 0, 1.0, false), &[])
+            .unwrap();
+        b.record_round(&round("pgt", "btc", 0, 50.0, false), &[])
+            .unwrap();
+        b.record_round(&round("pgt", "btc", 900_000, 60.0, false), &[])
+            .unwrap();
+
+        fn only(ledger: &PnlLedger) -> RoundFilter<'_> {
+            RoundFilter {
+                instance_id: Some(ledger.instance_id()),
+                strategy: Some("pgt"),
+                market_slug: Some("btc"),
+            }
+        }
+        let agg_a = a.aggregate(only(&a), None).unwrap();
+        assert_eq!(agg_a.rounds_total, 1);
+        assert!((agg_a.total_realized_cash_pnl - 1.0).abs() < 1e-9);
+        assert_eq!(b.round_count(only(&b)).unwrap(), 2);
+        assert_eq!(
+            b.aggregate(only(&b), None).unwrap().median_round_pnl,
+            Some(55.0)
+        );
+        assert!(a.aggregate_last(only(&a), 2).unwrap().is_none());
+        assert_eq!(a.round_count(RoundFilter::default()).unwrap(), 3);
+    }
+
+    #[test]
+    fn writer_thread_persists_market_rows() {
+        let ledger = PnlLedger::open_in_memory("test").unwrap();
+        let writer = PnlLedgerWriter::spawn(ledger.clone());
+        let market = writer.for_market("btc");
+        market.record_tranche(&PairTranche::default(), 7);
+        market.record_merge("m1", 5.0, 8);
+        writer.flush();
+
+        let conn = ledger.conn.lock().unwrap();
+        let (tranches, merges): (i64, i64) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM tranches), (SELECT COUNT(*) FROM merges)",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((tranches, merges), (1, 1));
+    }
+
+    #[test]
+    fn rerecording_a_round_replaces_row_and_fills() {
+        let ledger = PnlLedger::open_in_memory("test").unwrap();
+        let fill = LedgerFill {
+            ts_ms: 5,
+            side: Side::Yes,
+            direction: TradeDirection::Buy,
+            size: 5.0,
+            price: 0.45,
+            fee: FeeQuote::default(),
+        };
+        let first = ledger
+            .record_round(&round("pgt", "btc", 0, 1.0, true), &[fill, fill])
+            .unwrap();
+        let second = ledger
+            .record_round(&round("pgt", "btc", 0, 2.0, false), &[fill])
+            .unwrap();
+        assert_ne!(first, second);
+
+        let conn = ledger.conn.lock().unwrap();
+        let (rounds, fills): (i64, i64) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM rounds), (SELECT COUNT(*) FROM fills)",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((rounds, fills), (1, 1));
+    }
+}
@@ -0,0 +1,917 @@
+//! Portfolio-level risk manager across concurrently running market workers.
+//!
+//! Every worker keeps its own `InventoryManager` and per-market limits; this
+//! actor sits above them. Workers report a compact `WorkerExposure` whenever
+//! their inventory changes, the actor aggregates all fresh reports and derives
+//! three portfolio totals:
+//!
+//! - unpaired notional: cost of the shares that are not matched by the other side
+//! - capital at risk: total cost minus the $1/pair guaranteed by paired shares
+//! - directional notional: signed unpaired notional (+ = YES/Up), summed across
+//!   markets, since the crypto up/down rounds move together
+//!
+//! Each total is compared against its cap and turned into a per-worker
+//! `PortfolioRiskDirective` (throttle sizes, reduce-only, or cancel-all) that the
+//! worker's coordinator applies on top of its own strategy output, maker
+//! quotes and taker BUYs alike.
+//!
+//! In-proc supervisors pass reports over channels. Process supervisors use a
+//! directory of small JSON files (`<worker>.exposure.json` written by the
+//! child, `<worker>.directive.json` written by the supervisor), so no extra
+//! service is needed. Directive files carry a `ts_ms` the supervisor keeps
+//! refreshing; workers fall back to `Normal` once it is older than
+//! `stale_secs`, so a dead supervisor cannot pin a leftover cancel-all.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use super::messages::{InventorySnapshot, InventoryState};
+use super::types::Side;
+
+const EXPOSURE_EPS: f64 = 1e-9;
+const EXPOSURE_FILE_SUFFIX: &str = ".exposure.json";
+const DIRECTIVE_FILE_SUFFIX: &str = ".directive.json";
+
+#[derive(Debug, Clone)]
+pub struct PortfolioRiskConfig {
+    pub enabled: bool,
+    /// Cap on Σ unpaired notional (USDC). 0 disables the limit.
+    pub max_unpaired_notional_usdc: f64,
+    /// Cap on Σ capital at risk (USDC). 0 disables the limit.
+    pub max_capital_at_risk_usdc: f64,
+    /// Cap on |Σ signed directional notional| (USDC). 0 disables the limit.
+    pub max_directional_notional_usdc: f64,
+    /// Utilisation at which workers are throttled.
+    pub throttle_ratio: f64,
+    /// Buy size multiplier while throttled.
+    pub throttle_size_mult: f64,
+    /// Utilisation at which contributing workers cancel all orders.
+    pub cancel_all_ratio: f64,
+    /// Reports older than this are dropped from the aggregate; file directives
+    /// older than this are ignored by workers.
+    pub stale_secs: u64,
+    pub eval_interval: Duration,
+    /// Exchange directory for process-based supervisors.
+    pub root: PathBuf,
+}
+
+impl Default for PortfolioRiskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_unpaired_notional_usdc: 0.0,
+            max_capital_at_risk_usdc: 0.0,
+            max_directional_notional_usdc: 0.0,
+            throttle_ratio: 0.8,
+            throttle_size_mult: 0.5,
+            cancel_all_ratio: 1.5,
+            stale_secs: 30,
+            eval_interval: Duration::from_millis(500),
+            root: PathBuf::from("data/portfolio_risk"),
+        }
+    }
+}
+
+impl PortfolioRiskConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_RISK_ENABLED") {
+            cfg.enabled = v == "1" || v.eq_ignore_ascii_case("true");
+        }
+        let read_cap = |key: &str, slot: &mut f64| {
+            if let Ok(v) = std::env::var(key) {
+                match v.parse::<f64>() {
+                    Ok(f) if f.is_finite() && f >= 0.0 => *slot = f,
+                    _ => warn!(
+                        "⚠️ Ignoring invalid {}={} (must satisfy x >= 0), using {}",
+                        key, v, slot
+                    ),
+                }
+            }
+        };
+        read_cap(
+            "PM_PORTFOLIO_MAX_UNPAIRED_NOTIONAL_USDC",
+            &mut cfg.max_unpaired_notional_usdc,
+        );
+        read_cap(
+            "PM_PORTFOLIO_MAX_CAPITAL_AT_RISK_USDC",
+            &mut cfg.max_capital_at_risk_usdc,
+        );
+        read_cap(
+            "PM_PORTFOLIO_MAX_DIRECTIONAL_NOTIONAL_USDC",
+            &mut cfg.max_directional_notional_usdc,
+        );
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_THROTTLE_RATIO") {
+            if let Ok(f) = v.parse::<f64>() {
+                if f > 0.0 && f <= 1.0 {
+                    cfg.throttle_ratio = f;
+                }
+            }
+        }
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_THROTTLE_SIZE_MULT") {
+            if let Ok(f) = v.parse::<f64>() {
+                if f > 0.0 && f <= 1.0 {
+                    cfg.throttle_size_mult = f;
+                }
+            }
+        }
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_CANCEL_ALL_RATIO") {
+            if let Ok(f) = v.parse::<f64>() {
+                if f >= 1.0 {
+                    cfg.cancel_all_ratio = f;
+                }
+            }
+        }
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_STALE_SECS") {
+            if let Ok(secs) = v.parse::<u64>() {
+                cfg.stale_secs = secs.max(1);
+            }
+        }
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_EVAL_INTERVAL_MS") {
+            if let Ok(ms) = v.parse::<u64>() {
+                cfg.eval_interval = Duration::from_millis(ms.max(50));
+            }
+        }
+        if let Ok(v) = std::env::var("PM_PORTFOLIO_RISK_ROOT") {
+            if !v.trim().is_empty() {
+                cfg.root = PathBuf::from(v.trim());
+            }
+        }
+        cfg
+    }
+
+    pub fn has_limits(&self) -> bool {
+        self.max_unpaired_notional_usdc > 0.0
+            || self.max_capital_at_risk_usdc > 0.0
+            || self.max_directional_notional_usdc > 0.0
+    }
+}
+
+/// Compact per-worker inventory used for portfolio aggregation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkerExposure {
+    pub yes_qty: f64,
+    pub no_qty: f64,
+    pub yes_avg_cost: f64,
+    pub no_avg_cost: f64,
+    pub ts_ms: u64,
+}
+
+impl WorkerExposure {
+    pub fn from_inventory(inv: &InventoryState, ts_ms: u64) -> Self {
+        Self {
+            yes_qty: inv.yes_qty.max(0.0),
+            no_qty: inv.no_qty.max(0.0),
+            yes_avg_cost: inv.yes_avg_cost.max(0.0),
+            no_avg_cost: inv.no_avg_cost.max(0.0),
+            ts_ms,
+        }
+    }
+
+    pub fn paired_qty(&self) -> f64 {
+        self.yes_qty.min(self.no_qty)
+    }
+
+    /// Signed unpaired notional: + for excess YES, - for excess NO.
+    pub fn directional_notional(&self) -> f64 {
+        let net = self.yes_qty - self.no_qty;
+        if net > EXPOSURE_EPS {
+            net * self.yes_avg_cost
+        } else if net < -EXPOSURE_EPS {
+            net * self.no_avg_cost
+        } else {
+            0.0
+        }
+    }
+
+    pub fn unpaired_notional(&self) -> f64 {
+        self.directional_notional().abs()
+    }
+
+    /// Worst-case loss: everything paid minus the $1 each complete pair redeems for.
+    pub fn capital_at_risk(&self) -> f64 {
+        let cost = self.yes_qty * self.yes_avg_cost + self.no_qty * self.no_avg_cost;
+        (cost - self.paired_qty()).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PortfolioRiskAction {
+    #[default]
+    Normal,
+    /// Scale new buy sizes by `size_mult`.
+    Throttle,
+    /// Only buys that pair existing residual inventory are allowed.
+    ReduceOnly,
+    /// Cancel every resting order and stop quoting.
+    CancelAll,
+}
+
+impl PortfolioRiskAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Throttle => "throttle",
+            Self::ReduceOnly => "reduce_only",
+            Self::CancelAll => "cancel_all",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PortfolioRiskLimit {
+    UnpairedNotional,
+    CapitalAtRisk,
+    Directional,
+}
+
+impl PortfolioRiskLimit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnpairedNotional => "unpaired_notional",
+            Self::CapitalAtRisk => "capital_at_risk",
+            Self::Directional => "directional",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioRiskDirective {
+    pub action: PortfolioRiskAction,
+    pub size_mult: f64,
+    /// Limit that drove the action (`None` while `Normal`).
+    pub limit: Option<PortfolioRiskLimit>,
+}
+
+impl Default for PortfolioRiskDirective {
+    fn default() -> Self {
+        Self {
+            action: PortfolioRiskAction::Normal,
+            size_mult: 1.0,
+            limit: None,
+        }
+    }
+}
+
+impl PortfolioRiskDirective {
+    fn escalate(&mut self, action: PortfolioRiskAction, size_mult: f64, limit: PortfolioRiskLimit) {
+        if action > self.action {
+            self.action = action;
+            self.limit = Some(limit);
+        }
+        self.size_mult = self.size_mult.min(size_mult);
+    }
+
+    /// Buy side allowed under `ReduceOnly`, given the worker's own inventory:
+    /// the side that pairs its residual. `None` means no buy reduces risk.
+    pub fn reduce_only_side(inv: &InventoryState) -> Option<Side> {
+        let net = inv.yes_qty - inv.no_qty;
+        if net > EXPOSURE_EPS {
+            Some(Side::No)
+        } else if net < -EXPOSURE_EPS {
+            Some(Side::Yes)
+        } else {
+            None
+        }
+    }
+}
+
+/// `<worker>.directive.json` payload. Files written before `ts_ms` existed
+/// read as expired.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct DirectiveFile {
+    #[serde(flatten)]
+    directive: PortfolioRiskDirective,
+    #[serde(default)]
+    ts_ms: u64,
+}
+
+impl DirectiveFile {
+    fn current(&self, now_ms: u64, ttl_ms: u64) -> PortfolioRiskDirective {
+        if self.ts_ms > 0 && now_ms.saturating_sub(self.ts_ms) <= ttl_ms {
+            self.directive
+        } else {
+            PortfolioRiskDirective::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PortfolioTotals {
+    pub workers: usize,
+    pub unpaired_notional: f64,
+    pub capital_at_risk: f64,
+    pub directional_notional: f64,
+}
+
+/// Latest fresh exposure per worker plus the evaluation rules.
+#[derive(Debug, Clone)]
+pub struct PortfolioRiskBook {
+    cfg: PortfolioRiskConfig,
+    workers: BTreeMap<String, WorkerExposure>,
+}
+
+impl PortfolioRiskBook {
+    pub fn new(cfg: PortfolioRiskConfig) -> Self {
+        Self {
+            cfg,
+            workers: BTreeMap::new(),
+        }
+    }
+
+    pub fn update(&mut self, worker: &str, exposure: Option<WorkerExposure>) {
+        match exposure {
+            Some(e) => {
+                self.workers.insert(worker.to_string(), e);
+            }
+            None => {
+                self.workers.remove(worker);
+            }
+        }
+    }
+
+    fn is_fresh(&self, e: &WorkerExposure, now_ms: u64) -> bool {
+        now_ms.saturating_sub(e.ts_ms) <= self.cfg.stale_secs.saturating_mul(1_000)
+    }
+
+    pub fn totals(&self, now_ms: u64) -> PortfolioTotals {
+        let mut totals = PortfolioTotals::default();
+        for e in self.workers.values().filter(|e| self.is_fresh(e, now_ms)) {
+            totals.workers += 1;
+            totals.unpaired_notional += e.unpaired_notional();
+            totals.capital_at_risk += e.capital_at_risk();
+            totals.directional_notional += e.directional_notional();
+        }
+        totals
+    }
+
+    fn level(&self, used: f64, cap: f64) -> (PortfolioRiskAction, f64) {
+        if cap <= 0.0 {
+            return (PortfolioRiskAction::Normal, 1.0);
+        }
+        let util = used / cap;
+        if util >= self.cfg.cancel_all_ratio {
+            (PortfolioRiskAction::CancelAll, 0.0)
+        } else if util >= 1.0 {
+            (PortfolioRiskAction::ReduceOnly, self.cfg.throttle_size_mult)
+        } else if util >= self.cfg.throttle_ratio {
+            (PortfolioRiskAction::Throttle, self.cfg.throttle_size_mult)
+        } else {
+            (PortfolioRiskAction::Normal, 1.0)
+        }
+    }
+
+    /// Directive for every known worker, `workers` included even when stale so
+    /// they fall back to `Normal` once their report ages out.
+    pub fn evaluate(&self, now_ms: u64) -> BTreeMap<String, PortfolioRiskDirective> {
+        let totals = self.totals(now_ms);
+        let (unpaired_action, unpaired_mult) = self.level(
+            totals.unpaired_notional,
+            self.cfg.max_unpaired_notional_usdc,
+        );
+        let (capital_action, capital_mult) =
+            self.level(totals.capital_at_risk, self.cfg.max_capital_at_risk_usdc);
+        let (dir_action, dir_mult) = self.level(
+            totals.directional_notional.abs(),
+            self.cfg.max_directional_notional_usdc,
+        );
+        let dir_sign = totals.directional_notional.signum();
+
+        let mut out = BTreeMap::new();
+        for (worker, e) in &self.workers {
+            let mut d = PortfolioRiskDirective::default();
+            if self.is_fresh(e, now_ms) {
+                let holds_inventory = e.yes_qty + e.no_qty > EXPOSURE_EPS;
+                let apply = |d: &mut PortfolioRiskDirective,
+                             action: PortfolioRiskAction,
+                             mult: f64,
+                             limit: PortfolioRiskLimit,
+                             contributes: bool| {
+                    // Cancel-all is reserved for workers that actually hold the
+                    // breaching exposure; everyone else stops adding to it.
+                    let action = if action == PortfolioRiskAction::CancelAll && !contributes {
+                        PortfolioRiskAction::ReduceOnly
+                    } else {
+                        action
+                    };
+                    d.escalate(action, mult, limit);
+                };
+                apply(
+                    &mut d,
+                    unpaired_action,
+                    unpaired_mult,
+                    PortfolioRiskLimit::UnpairedNotional,
+                    e.unpaired_notional() > EXPOSURE_EPS,
+                );
+                apply(
+                    &mut d,
+                    capital_action,
+                    capital_mult,
+                    PortfolioRiskLimit::CapitalAtRisk,
+                    holds_inventory,
+                );
+                let own_dir = e.directional_notional();
+                // Workers leaning against the portfolio direction are hedging it.
+                let against = own_dir.abs() > EXPOSURE_EPS && own_dir.signum() != dir_sign;
+                if !against {
+                    apply(
+                        &mut d,
+                        dir_action,
+                        dir_mult,
+                        PortfolioRiskLimit::Directional,
+                        own_dir.abs() > EXPOSURE_EPS,
+                    );
+                }
+            }
+            out.insert(worker.clone(), d);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PortfolioRiskReport {
+    Exposure {
+        worker: String,
+        exposure: WorkerExposure,
+    },
+    Clear {
+        worker: String,
+    },
+}
+
+type DirectiveSenders = Arc<Mutex<HashMap<String, watch::Sender<PortfolioRiskDirective>>>>;
+
+/// Supervisor-side handle of the portfolio risk actor.
+#[derive(Clone)]
+pub struct PortfolioRiskHub {
+    report_tx: mpsc::Sender<PortfolioRiskReport>,
+    directives: DirectiveSenders,
+}
+
+impl PortfolioRiskHub {
+    /// Spawn the actor. With `file_exchange` the actor also ingests
+    /// `<root>/*.exposure.json` and writes `<root>/<worker>.directive.json`,
+    /// for workers running as separate processes.
+    pub fn spawn(cfg: PortfolioRiskConfig, file_exchange: bool) -> Self {
+        let (report_tx, report_rx) = mpsc::channel(256);
+        let directives: DirectiveSenders = Arc::new(Mutex::new(HashMap::new()));
+        if file_exchange {
+            if let Err(e) = std::fs::create_dir_all(&cfg.root) {
+                warn!(
+                    "⚠️ portfolio_risk: cannot create exchange dir {}: {}",
+                    cfg.root.display(),
+                    e
+                );
+            }
+        }
+        info!(
+            "🛡️ portfolio_risk enabled | max_unpaired={:.2} max_capital_at_risk={:.2} max_directional={:.2} throttle_ratio={:.2} cancel_all_ratio={:.2} file_exchange={}",
+            cfg.max_unpaired_notional_usdc,
+            cfg.max_capital_at_risk_usdc,
+            cfg.max_directional_notional_usdc,
+            cfg.throttle_ratio,
+            cfg.cancel_all_ratio,
+            file_exchange
+        );
+        tokio::spawn(run_portfolio_risk_actor(
+            cfg,
+            report_rx,
+            directives.clone(),
+            file_exchange,
+        ));
+        Self {
+            report_tx,
+            directives,
+        }
+    }
+
+    /// Channel-backed link for an in-proc worker. `worker` must be stable across
+    /// rounds (the market prefix, not the per-round slug).
+    pub fn worker(&self, worker: &str) -> PortfolioRiskWorker {
+        let mut guard = self.directives.lock().unwrap_or_else(|e| e.into_inner());
+        let directive_rx = guard
+            .entry(worker.to_string())
+            .or_insert_with(|| watch::channel(PortfolioRiskDirective::default()).0)
+            .subscribe();
+        PortfolioRiskWorker {
+            worker: worker.to_string(),
+            transport: WorkerTransport::Channel(self.report_tx.clone()),
+            directive_rx,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum WorkerTransport {
+    Channel(mpsc::Sender<PortfolioRiskReport>),
+    File(PathBuf),
+}
+
+/// Worker-side link: publishes exposure, receives directives.
+#[derive(Clone)]
+pub struct PortfolioRiskWorker {
+    worker: String,
+    transport: WorkerTransport,
+    directive_rx: watch::Receiver<PortfolioRiskDirective>,
+}
+
+fn file_stem(worker: &str) -> String {
+    worker
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec(value)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+impl PortfolioRiskWorker {
+    /// File-backed link for a worker process spawned by the process supervisor.
+    /// Polls `<root>/<worker>.directive.json` once per second and ignores a
+    /// directive whose `ts_ms` is older than `ttl`.
+    pub fn file_backed(root: &Path, worker: &str, ttl: Duration) -> Self {
+        let (directive_tx, directive_rx) = watch::channel(PortfolioRiskDirective::default());
+        let path = root.join(format!("{}{}", file_stem(worker), DIRECTIVE_FILE_SUFFIX));
+        let ttl_ms = ttl.as_millis() as u64;
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(Duration::from_secs(1));
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tick.tick().await;
+                let directive = read_json::<DirectiveFile>(&path)
+                    .map(|file| file.current(unix_now_ms(), ttl_ms))
+                    .unwrap_or_default();
+                directive_tx.send_if_modified(|cur| {
+                    let changed = *cur != directive;
+                    *cur = directive;
+                    changed
+                });
+                if directive_tx.is_closed() {
+                    break;
+                }
+            }
+        });
+        Self {
+            worker: worker.to_string(),
+            transport: WorkerTransport::File(root.to_path_buf()),
+            directive_rx,
+        }
+    }
+
+    pub fn directive_rx(&self) -> watch::Receiver<PortfolioRiskDirective> {
+        self.directive_rx.clone()
+    }
+
+    async fn publish(&self, exposure: Option<WorkerExposure>) {
+        match &self.transport {
+            WorkerTransport::Channel(tx) => {
+                let report = match exposure {
+                    Some(exposure) => PortfolioRiskReport::Exposure {
+                        worker: self.worker.clone(),
+                        exposure,
+                    },
+                    None => PortfolioRiskReport::Clear {
+                        worker: self.worker.clone(),
+                    },
+                };
+                let _ = tx.send(report).await;
+            }
+            WorkerTransport::File(root) => {
+                let path = root.join(format!(
+                    "{}{}",
+                    file_stem(&self.worker),
+                    EXPOSURE_FILE_SUFFIX
+                ));
+                let res = match exposure {
+                    Some(exposure) => write_json_atomic(&path, &exposure),
+                    None => match std::fs::remove_file(&path) {
+                        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                        _ => Ok(()),
+                    },
+                };
+                if let Err(e) = res {
+                    debug!(
+                        "portfolio_risk: exposure write failed {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Forward this round's working inventory until the inventory channel closes.
+    /// The report is also refreshed periodically so a quiet but live round never
+    /// ages out of the aggregate.
+    pub fn spawn_reporter(&self, mut inv_rx: watch::Receiver<InventorySnapshot>) -> JoinHandle<()> {
+        let link = self.clone();
+        tokio::spawn(async move {
+            let mut refresh = tokio::time::interval(Duration::from_secs(5));
+            refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                let working = inv_rx.borrow_and_update().working;
+                link.publish(Some(WorkerExposure::from_inventory(
+                    &working,
+                    unix_now_ms(),
+                )))
+                .await;
+                tokio::select! {
+                    changed = inv_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = refresh.tick() => {}
+                }
+            }
+        })
+    }
+
+    /// Drop this worker from the aggregate (round finished).
+    pub async fn clear(&self) {
+        self.publish(None).await;
+    }
+}
+
+fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn ingest_exposure_files(root: &Path, book: &mut PortfolioRiskBook) -> Vec<String> {
+    let mut seen = Vec::new();
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return seen;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(worker) = name.strip_suffix(EXPOSURE_FILE_SUFFIX) else {
+            continue;
+        };
+        if let Some(exposure) = read_json::<WorkerExposure>(&entry.path()) {
+            book.update(worker, Some(exposure));
+            seen.push(worker.to_string());
+        }
+    }
+    seen
+}
+
+async fn run_portfolio_risk_actor(
+    cfg: PortfolioRiskConfig,
+    mut report_rx: mpsc::Receiver<PortfolioRiskReport>,
+    directives: DirectiveSenders,
+    file_exchange: bool,
+) {
+    let root = cfg.root.clone();
+    // Rewrite unchanged directive files well inside the workers' TTL.
+    let directive_refresh_ms = cfg.stale_secs.saturating_mul(1_000) / 3;
+    let mut directive_written_ms: HashMap<String, u64> = HashMap::new();
+    let mut tick = tokio::time::interval(cfg.eval_interval);
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut book = PortfolioRiskBook::new(cfg);
+    let mut last: BTreeMap<String, PortfolioRiskDirective> = BTreeMap::new();
+    let mut file_workers: Vec<String> = Vec::new();
+    let mut reports_open = true;
+
+    loop {
+        tokio::select! {
+            maybe = report_rx.recv(), if reports_open => {
+                match maybe {
+                    Some(PortfolioRiskReport::Exposure { worker, exposure }) => {
+                        book.update(&worker, Some(exposure));
+                    }
+                    Some(PortfolioRiskReport::Clear { worker }) => {
+                        book.update(&worker, None);
+                    }
+                    None if !file_exchange => break,
+                    None => reports_open = false,
+                }
+                continue;
+            }
+            _ = tick.tick() => {}
+        }
+
+        if file_exchange {
+            let seen = ingest_exposure_files(&root, &mut book);
+            for gone in file_workers.iter().filter(|w| !seen.contains(w)) {
+                book.update(gone, None);
+            }
+            file_workers = seen;
+        }
+
+        let now_ms = unix_now_ms();
+        let totals = book.totals(now_ms);
+        let next = book.evaluate(now_ms);
+        for (worker, directive) in &next {
+            let prev = last.get(worker).copied().unwrap_or_default();
+            if prev.action != directive.action {
+                let msg = format!(
+                    "🛡️ portfolio_risk | worker={} action={}->{} limit={} size_mult={:.2} unpaired={:.2} capital_at_risk={:.2} directional={:+.2} workers={}",
+                    worker,
+                    prev.action.as_str(),
+                    directive.action.as_str(),
+                    directive.limit.map(PortfolioRiskLimit::as_str).unwrap_or("-"),
+                    directive.size_mult,
+                    totals.unpaired_notional,
+                    totals.capital_at_risk,
+                    totals.directional_notional,
+                    totals.workers
+                );
+                if directive.action >= PortfolioRiskAction::ReduceOnly {
+                    warn!("{}", msg);
+                } else {
+                    info!("{}", msg);
+                }
+            }
+            {
+                let mut guard = directives.lock().unwrap_or_else(|e| e.into_inner());
+                let tx = guard
+                    .entry(worker.clone())
+                    .or_insert_with(|| watch::channel(PortfolioRiskDirective::default()).0);
+                tx.send_if_modified(|cur| {
+                    let changed = cur != directive;
+                    *cur = *directive;
+                    changed
+                });
+            }
+            let written_ms = directive_written_ms.get(worker).copied();
+            let refresh_due =
+                written_ms.is_none_or(|ts| now_ms.saturating_sub(ts) >= directive_refresh_ms);
+            if file_exchange && (prev != *directive || refresh_due) {
+                let path = root.join(format!("{}{}", worker, DIRECTIVE_FILE_SUFFIX));
+                let file = DirectiveFile {
+                    directive: *directive,
+                    ts_ms: now_ms,
+                };
+                match write_json_atomic(&path, &file) {
+                    Ok(()) => {
+                        directive_written_ms.insert(worker.clone(), now_ms);
+                    }
+                    Err(e) => warn!(
+                        "⚠️ portfolio_risk: directive write failed {}: {}",
+                        path.display(),
+                        e
+                    ),
+                }
+            }
+        }
+        // Workers that left the book go back to Normal.
+        for (worker, prev) in &last {
+            if next.contains_key(worker) || prev.action == PortfolioRiskAction::Normal {
+                continue;
+            }
+            if let Some(tx) = directives
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(worker)
+            {
+                tx.send_replace(PortfolioRiskDirective::default());
+            }
+            if file_exchange {
+                let _ =
+                    std::fs::remove_file(root.join(format!("{}{}", worker, DIRECTIVE_FILE_SUFFIX)));
+            }
+        }
+        directive_written_ms.retain(|worker, _| next.contains_key(worker));
+        last = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exposure(yes_qty: f64, no_qty: f64, yes_avg: f64, no_avg: f64) -> WorkerExposure {
+        WorkerExposure {
+            yes_qty,
+            no_qty,
+            yes_avg_cost: yes_avg,
+            no_avg_cost: no_avg,
+            ts_ms: 1_000,
+        }
+    }
+
+    fn cfg() -> PortfolioRiskConfig {
+        PortfolioRiskConfig {
+            enabled: true,
+            max_unpaired_notional_usdc: 24.0,
+            max_capital_at_risk_usdc: 0.0,
+            max_directional_notional_usdc: 15.0,
+            ..PortfolioRiskConfig::default()
+        }
+    }
+
+    #[test]
+    fn exposure_metrics_price_unpaired_and_worst_case() {
+        let e = exposure(30.0, 10.0, 0.45, 0.50);
+        assert!((e.directional_notional() - 9.0).abs() < 1e-9);
+        assert!((e.unpaired_notional() - 9.0).abs() < 1e-9);
+        // cost 13.5 + 5.0 = 18.5, minus 10 paired → 8.5 at risk.
+        assert!((e.capital_at_risk() - 8.5).abs() < 1e-9);
+        let short = exposure(0.0, 20.0, 0.0, 0.40);
+        assert!((short.directional_notional() + 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn correlated_direction_restricts_only_same_side_workers() {
+        let mut book = PortfolioRiskBook::new(cfg());
+        book.update("btc", Some(exposure(20.0, 0.0, 0.5, 0.0))); // +10
+        book.update("eth", Some(exposure(16.0, 0.0, 0.5, 0.0))); // +8
+        book.update("sol", Some(exposure(0.0, 4.0, 0.0, 0.5))); // -2
+        let totals = book.totals(1_000);
+        assert!((totals.directional_notional - 16.0).abs() < 1e-9);
+        let d = book.evaluate(1_000);
+        assert_eq!(d["btc"].action, PortfolioRiskAction::ReduceOnly);
+        assert_eq!(d["btc"].limit, Some(PortfolioRiskLimit::Directional));
+        assert_eq!(d["eth"].action, PortfolioRiskAction::ReduceOnly);
+        // sol hedges the portfolio direction; only the unpaired cap (20 / 24) throttles it.
+        assert_eq!(d["sol"].action, PortfolioRiskAction::Throttle);
+        assert_eq!(d["sol"].limit, Some(PortfolioRiskLimit::UnpairedNotional));
+    }
+
+    #[test]
+    fn hard_breach_cancels_contributors_and_stale_reports_age_out() {
+        let mut book = PortfolioRiskBook::new(cfg());
+        book.update("btc", Some(exposure(80.0, 0.0, 0.5, 0.0))); // 40 > 1.5 × 24
+        book.update("eth", Some(exposure(10.0, 10.0, 0.5, 0.5)));
+        let d = book.evaluate(1_000);
+        assert_eq!(d["btc"].action, PortfolioRiskAction::CancelAll);
+        assert_eq!(d["eth"].action, PortfolioRiskAction::ReduceOnly);
+
+        let later = 1_000 + (book.cfg.stale_secs + 1) * 1_000;
+        assert_eq!(book.totals(later).workers, 0);
+        assert_eq!(
+            book.evaluate(later)["btc"],
+            PortfolioRiskDirective::default()
+        );
+    }
+
+    #[test]
+    fn reduce_only_side_pairs_residual() {
+        let inv = InventoryState {
+            yes_qty: 5.0,
+            no_qty: 2.0,
+            ..InventoryState::default()
+        };
+        assert_eq!(
+            PortfolioRiskDirective::reduce_only_side(&inv),
+            Some(Side::No)
+        );
+        assert_eq!(
+            PortfolioRiskDirective::reduce_only_side(&InventoryState::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn directive_file_expires_without_supervisor_refresh() {
+        let cancel = PortfolioRiskDirective {
+            action: PortfolioRiskAction::CancelAll,
+            size_mult: 0.0,
+            limit: Some(PortfolioRiskLimit::UnpairedNotional),
+        };
+        let raw = serde_json::to_string(&DirectiveFile {
+            directive: cancel,
+            ts_ms: 10_000,
+        })
+        .unwrap();
+        let file: DirectiveFile = serde_json::from_str(&raw).unwrap();
+        assert_eq!(file.current(10_000 + 30_000, 30_000), cancel);
+        assert_eq!(
+            file.current(10_000 + 30_001, 30_000),
+            PortfolioRiskDirective::default()
+        );
+
+        // Files from before the timestamp existed never pin a directive.
+        let legacy: DirectiveFile =
+            serde_json::from_str(r#"{"action":"CancelAll","size_mult":0.0,"limit":null}"#).unwrap();
+        assert_eq!(
+            legacy.current(1_000, 30_000),
+            PortfolioRiskDirective::default()
+        );
+    }
+}
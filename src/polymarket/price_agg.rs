@@ -0,0 +1,178 @@
+//! Local price aggregator (extracted from the monolithic bin).
+//! Fuses spot trade/ticker feeds from several venues into a per-symbol tick stream
+//! and estimates Chainlink-style round open/close prices at 5m/15m boundaries:
+//! - `venues`: per-venue symbol mapping + WS message parsers
+//! - `hub`: `LocalPriceHub`, one broadcast channel + recent-tick ring per symbol
+//! - `boundary`: per-source boundary picks and the close-at-boundary query
+//! - `policy`: symbol-tuned boundary shadow policies (tape based)
+//! - `bias`: per-(symbol, source) bias learned against RTDS closes
+//! - `uncertainty_gate`: gate model file + online bucket statistics
+//!
+//! Symbols are Chainlink style (`btc/usd`); see `normalize_chainlink_symbol`.
+
+pub mod bias;
+pub mod boundary;
+pub mod config;
+pub mod hub;
+pub mod policy;
+pub mod uncertainty_gate;
+pub mod venues;
+
+pub use bias::SourceBiasBook;
+pub use boundary::{
+    AggregatedPricePoint, CloseAggReject, LocalCloseOnlyAggHit, LocalCloseSourceContribution,
+    LocalSourceBoundaryState, LocalSourceBoundaryTapeState,
+};
+pub use config::PriceAggConfig;
+pub use hub::LocalPriceHub;
+pub use venues::SymbolTick;
+
+pub fn normalize_chainlink_symbol(raw: &str) -> String {
+    raw.trim()
+        .to_ascii_lowercase()
+        .replace(['-', '_'], "/")
+        .replace("//", "/")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocalPriceSource {
+    Binance,
+    Bybit,
+    Okx,
+    Coinbase,
+    Hyperliquid,
+    Kraken,
+    Bitstamp,
+    Gate,
+    CryptoCom,
+}
+
+impl LocalPriceSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Binance => "binance",
+            Self::Bybit => "bybit",
+            Self::Okx => "okx",
+            Self::Coinbase => "coinbase",
+            Self::Hyperliquid => "hyperliquid",
+            Self::Kraken => "kraken",
+            Self::Bitstamp => "bitstamp",
+            Self::Gate => "gate",
+            Self::CryptoCom => "crypto_com",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "binance" => Some(Self::Binance),
+            "bybit" => Some(Self::Bybit),
+            "okx" => Some(Self::Okx),
+            "coinbase" => Some(Self::Coinbase),
+            "hyperliquid" => Some(Self::Hyperliquid),
+            "kraken" => Some(Self::Kraken),
+            "bitstamp" => Some(Self::Bitstamp),
+            "gate" | "gateio" => Some(Self::Gate),
+            "crypto_com" | "cryptocom" => Some(Self::CryptoCom),
+            _ => None,
+        }
+    }
+}
+
+pub const LOCAL_PRICE_SOURCES: [LocalPriceSource; 9] = [
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+    LocalPriceSource::Kraken,
+    LocalPriceSource::Bitstamp,
+    LocalPriceSource::Gate,
+    LocalPriceSource::CryptoCom,
+];
+
+/// Venues the hub always connects to; the rest are opt-in via
+/// `PriceAggConfig::extra_sources` so tuned boundary policies keep their inputs.
+pub const LOCAL_PRICE_DEFAULT_SOURCES: [LocalPriceSource; 5] = [
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+];
+
+/// One price observation from a single venue, as delivered by `LocalPriceHub`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceTick {
+    pub price: f64,
+    pub ts_ms: u64,
+    pub source: LocalPriceSource,
+}
+
+pub fn robust_median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Some(values[mid])
+    } else {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    }
+}
+
+pub fn robust_center(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    match values.len() {
+        1 => Some(values[0]),
+        2 => Some((values[0] + values[1]) / 2.0),
+        // Drop one-side outliers and average the center mass; this is usually
+        // closer to Chainlink-style aggregated closes than picking one median tick.
+        _ => {
+            let inner = &values[1..values.len() - 1];
+            let sum: f64 = inner.iter().sum();
+            Some(sum / inner.len() as f64)
+        }
+    }
+}
+
+pub fn weighted_mean(values: &[(f64, f64)]) -> Option<f64> {
+    let mut weighted_sum = 0.0f64;
+    let mut weight_sum = 0.0f64;
+    for (value, weight) in values {
+        if !value.is_finite() || !weight.is_finite() || *weight <= 0.0 {
+            continue;
+        }
+        weighted_sum += value * weight;
+        weight_sum += weight;
+    }
+    if weight_sum <= 0.0 {
+        None
+    } else {
+        Some(weighted_sum / weight_sum)
+    }
+}
+
+pub fn spread_bps(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let min_v = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_v = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let median = robust_median(values.to_vec())?;
+    if !median.is_finite() || median <= 0.0 {
+        return None;
+    }
+    Some(((max_v - min_v) / median.abs()) * 10_000.0)
+}
+
+pub fn median_u64(mut values: Vec<u64>) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
@@ -0,0 +1,257 @@
+//! Per-(symbol, source) price bias learned against RTDS closes.
+//!
+//! Each venue trades at a small, fairly stable offset from the Chainlink
+//! aggregate (USDT vs USD, venue mix). After every round the residual between
+//! the RTDS close and the raw venue close is folded into an EMA; later closes
+//! are shifted by the learned bias before aggregation.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::boundary::LocalCloseSourceContribution;
+use super::{normalize_chainlink_symbol, LocalPriceSource};
+
+pub const LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA: f64 = 0.30;
+pub const LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS: f64 = 10.0;
+pub const LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS: f64 = 25.0;
+
+type LocalPriceAggBiasMap = HashMap<(String, LocalPriceSource), f64>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LocalPriceAggBiasCacheEntry {
+    symbol: String,
+    source: String,
+    bias_bps: f64,
+}
+
+/// One bias update produced by `SourceBiasBook::learn_from_reference`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceBiasUpdate {
+    pub source: LocalPriceSource,
+    pub residual_bps: f64,
+    pub new_bias_bps: f64,
+}
+
+#[derive(Debug)]
+pub struct SourceBiasBook {
+    path: Option<PathBuf>,
+    map: Mutex<LocalPriceAggBiasMap>,
+}
+
+impl SourceBiasBook {
+    /// Book backed by a JSON cache file; a missing or corrupt file starts empty.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let map = load_local_price_agg_bias_cache(&path);
+        Self {
+            path: Some(path),
+            map: Mutex::new(map),
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            map: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn bias_bps(&self, symbol: &str, source: LocalPriceSource) -> f64 {
+        let normalized = normalize_chainlink_symbol(symbol);
+        if normalized.is_empty() {
+            return 0.0;
+        }
+        let Ok(guard) = self.map.lock() else {
+            return 0.0;
+        };
+        guard.get(&(normalized, source)).copied().unwrap_or(0.0)
+    }
+
+    pub fn apply(&self, symbol: &str, source: LocalPriceSource, raw: f64) -> f64 {
+        if !raw.is_finite() || raw <= 0.0 {
+            return raw;
+        }
+        let bias_bps = self.bias_bps(symbol, source);
+        let adjusted = raw * (1.0 + bias_bps / 10_000.0);
+        if adjusted.is_finite() && adjusted > 0.0 {
+            adjusted
+        } else {
+            raw
+        }
+    }
+
+    /// Folds one residual into the EMA and persists the book; returns the new bias.
+    pub fn update(&self, symbol: &str, source: LocalPriceSource, residual_bps: f64) -> Option<f64> {
+        let normalized = normalize_chainlink_symbol(symbol);
+        if normalized.is_empty() || !residual_bps.is_finite() {
+            return None;
+        }
+        let clamped_residual = residual_bps.clamp(
+            -LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS,
+            LOCAL_PRICE_AGG_SOURCE_BIAS_RESIDUAL_MAX_ABS_BPS,
+        );
+        let Ok(mut guard) = self.map.lock() else {
+            return None;
+        };
+        let key = (normalized, source);
+        let prev = guard.get(&key).copied().unwrap_or(0.0);
+        let next = (prev * (1.0 - LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA)
+            + clamped_residual * LOCAL_PRICE_AGG_SOURCE_BIAS_EMA_ALPHA)
+            .clamp(
+                -LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS,
+                LOCAL_PRICE_AGG_SOURCE_BIAS_MAX_ABS_BPS,
+            );
+        guard.insert(key, next);
+        let snapshot = guard.clone();
+        drop(guard);
+        if let Some(path) = &self.path {
+            persist_local_price_agg_bias_cache(path, &snapshot);
+        }
+        Some(next)
+    }
+
+    /// Learns every source's residual against the reference (RTDS) close.
+    pub fn learn_from_reference(
+        &self,
+        symbol: &str,
+        reference_close: f64,
+        points: &[LocalCloseSourceContribution],
+    ) -> Vec<SourceBiasUpdate> {
+        if !reference_close.is_finite() || reference_close <= 0.0 {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        for point in points {
+            if !point.raw_close_price.is_finite() || point.raw_close_price <= 0.0 {
+                continue;
+            }
+            let residual_bps = ((reference_close - point.raw_close_price)
+                / reference_close.abs().max(1e-12))
+                * 10_000.0;
+            if let Some(new_bias_bps) = self.update(symbol, point.source, residual_bps) {
+                out.push(SourceBiasUpdate {
+                    source: point.source,
+                    residual_bps,
+                    new_bias_bps,
+                });
+            }
+        }
+        out
+    }
+}
+
+fn load_local_price_agg_bias_cache(path: &Path) -> LocalPriceAggBiasMap {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    let Ok(entries) = serde_json::from_str::<Vec<LocalPriceAggBiasCacheEntry>>(&raw) else {
+        warn!(
+            "⚠️ Failed to parse local price agg bias cache | path={}",
+            path.display()
+        );
+        return HashMap::new();
+    };
+    let mut out = HashMap::new();
+    for entry in entries {
+        let symbol = normalize_chainlink_symbol(&entry.symbol);
+        let Some(source) = LocalPriceSource::parse(&entry.source) else {
+            continue;
+        };
+        if symbol.is_empty() || !entry.bias_bps.is_finite() {
+            continue;
+        }
+        out.insert((symbol, source), entry.bias_bps);
+    }
+    if !out.is_empty() {
+        info!(
+            "🧠 Loaded local price agg bias cache | path={} entries={}",
+            path.display(),
+            out.len()
+        );
+    }
+    out
+}
+
+fn persist_local_price_agg_bias_cache(path: &Path, map: &LocalPriceAggBiasMap) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let entries = map
+        .iter()
+        .map(|((symbol, source), bias_bps)| LocalPriceAggBiasCacheEntry {
+            symbol: symbol.clone(),
+            source: source.as_str().to_string(),
+            bias_bps: *bias_bps,
+        })
+        .collect::<Vec<_>>();
+    let Ok(payload) = serde_json::to_vec(&entries) else {
+        return;
+    };
+    let tmp = path.with_extension("json.tmp");
+    if fs::write(&tmp, payload).is_err() {
+        return;
+    }
+    if fs::rename(&tmp, path).is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contribution(source: LocalPriceSource, raw: f64) -> LocalCloseSourceContribution {
+        LocalCloseSourceContribution {
+            source,
+            raw_close_price: raw,
+            adjusted_close_price: raw,
+            close_ts_ms: 1_000,
+            close_exact: false,
+            close_abs_delta_ms: 0,
+            close_pick: "local_nearest_close",
+        }
+    }
+
+    #[test]
+    fn bias_update_is_ema_clamped_and_applied() {
+        let book = SourceBiasBook::in_memory();
+        // residual is clamped to 25bps, then EMA(0.3) => 7.5bps
+        let next = book.update("BTC-USD", LocalPriceSource::Okx, 80.0).unwrap();
+        assert!((next - 7.5).abs() < 1e-9);
+        assert!((book.bias_bps("btc/usd", LocalPriceSource::Okx) - 7.5).abs() < 1e-9);
+        assert_eq!(book.bias_bps("btc/usd", LocalPriceSource::Bybit), 0.0);
+        let adjusted = book.apply("btc/usd", LocalPriceSource::Okx, 100.0);
+        assert!((adjusted - 100.075).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bias_book_round_trips_through_cache_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "pm_price_agg_bias_{}_{}",
+            std::process::id(),
+            line!()
+        ));
+        let path = dir.join("bias.json");
+        let book = SourceBiasBook::load(&path);
+        let updates = book.learn_from_reference(
+            "eth/usd",
+            2_000.0,
+            &[
+                contribution(LocalPriceSource::Binance, 1_999.0),
+                contribution(LocalPriceSource::Coinbase, f64::NAN),
+            ],
+        );
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].source, LocalPriceSource::Binance);
+        assert!((updates[0].residual_bps - 5.0).abs() < 1e-9);
+
+        let reloaded = SourceBiasBook::load(&path);
+        assert!((reloaded.bias_bps("eth/usd", LocalPriceSource::Binance) - 1.5).abs() < 1e-9);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,588 @@
+//! Per-source round boundary picks and the close-at-boundary query.
+//!
+//! Each source keeps the exact / first-after / nearest tick around the round
+//! open and close (`LocalSourceBoundaryState`). `local_close_source_contributions`
+//! turns those into bias-adjusted per-source closes and `aggregate_local_close`
+//! fuses them into one weighted close, rejecting rounds whose sources disagree.
+
+use std::collections::HashMap;
+
+use super::bias::SourceBiasBook;
+use super::config::PriceAggConfig;
+use super::{median_u64, robust_center, spread_bps, weighted_mean, LocalPriceSource};
+use crate::polymarket::types::Side;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AggregatedPricePoint {
+    pub price: f64,
+    pub ts_ms: u64,
+    pub exact: bool,
+    pub abs_delta_ms: u64,
+    pub source: &'static str,
+}
+
+impl AggregatedPricePoint {
+    pub fn from_exact(price: f64, ts_ms: u64, source: &'static str) -> Self {
+        Self {
+            price,
+            ts_ms,
+            exact: true,
+            abs_delta_ms: 0,
+            source,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ingest_boundary_tick(
+    price: f64,
+    ts_ms: u64,
+    start_ms: u64,
+    end_ms: u64,
+    first_tick_ts_ms: &mut Option<u64>,
+    open_exact: &mut Option<AggregatedPricePoint>,
+    close_exact: &mut Option<AggregatedPricePoint>,
+    nearest_start: &mut Option<(u64, u64, f64)>,
+    nearest_end: &mut Option<(u64, u64, f64)>,
+    first_after_start: &mut Option<(u64, u64, f64)>,
+    first_after_end: &mut Option<(u64, u64, f64)>,
+) {
+    first_tick_ts_ms.get_or_insert(ts_ms);
+
+    if open_exact.is_none() && ts_ms == start_ms {
+        *open_exact = Some(AggregatedPricePoint::from_exact(
+            price,
+            ts_ms,
+            "hub_open_exact",
+        ));
+    }
+    if close_exact.is_none() && ts_ms == end_ms {
+        *close_exact = Some(AggregatedPricePoint::from_exact(
+            price,
+            ts_ms,
+            "hub_close_exact",
+        ));
+    }
+
+    let start_delta = ts_ms.abs_diff(start_ms);
+    match *nearest_start {
+        Some((best_delta, _, _)) if start_delta >= best_delta => {}
+        _ => *nearest_start = Some((start_delta, ts_ms, price)),
+    }
+    if ts_ms >= start_ms {
+        let after_delta = ts_ms.saturating_sub(start_ms);
+        match *first_after_start {
+            Some((best_delta, _, _)) if after_delta >= best_delta => {}
+            _ => *first_after_start = Some((after_delta, ts_ms, price)),
+        }
+    }
+
+    let end_delta = ts_ms.abs_diff(end_ms);
+    match *nearest_end {
+        Some((best_delta, _, _)) if end_delta >= best_delta => {}
+        _ => *nearest_end = Some((end_delta, ts_ms, price)),
+    }
+    if ts_ms >= end_ms {
+        let after_delta = ts_ms.saturating_sub(end_ms);
+        match *first_after_end {
+            Some((best_delta, _, _)) if after_delta >= best_delta => {}
+            _ => *first_after_end = Some((after_delta, ts_ms, price)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalSourceBoundaryState {
+    pub first_tick_ts_ms: Option<u64>,
+    pub open_exact: Option<AggregatedPricePoint>,
+    pub close_exact: Option<AggregatedPricePoint>,
+    pub nearest_start: Option<(u64, u64, f64)>,
+    pub nearest_end: Option<(u64, u64, f64)>,
+    pub first_after_start: Option<(u64, u64, f64)>,
+    pub first_after_end: Option<(u64, u64, f64)>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LocalSourceBoundaryTapeState {
+    pub open_window_ticks: Vec<(u64, f64)>,
+    pub close_window_ticks: Vec<(u64, f64)>,
+}
+
+pub const LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE: usize = 512;
+
+pub fn format_local_source_boundary_state(
+    source: LocalPriceSource,
+    state: &LocalSourceBoundaryState,
+) -> String {
+    let first_tick = state
+        .first_tick_ts_ms
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let open_exact = state
+        .open_exact
+        .map(|p| format!("{}@{}", p.price, p.ts_ms))
+        .unwrap_or_else(|| "-".to_string());
+    let close_exact = state
+        .close_exact
+        .map(|p| format!("{}@{}", p.price, p.ts_ms))
+        .unwrap_or_else(|| "-".to_string());
+    let nearest_start = state
+        .nearest_start
+        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
+        .unwrap_or_else(|| "-".to_string());
+    let first_after_start = state
+        .first_after_start
+        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
+        .unwrap_or_else(|| "-".to_string());
+    let nearest_end = state
+        .nearest_end
+        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
+        .unwrap_or_else(|| "-".to_string());
+    let first_after_end = state
+        .first_after_end
+        .map(|(delta, ts_ms, px)| format!("{delta}ms/{px}@{ts_ms}"))
+        .unwrap_or_else(|| "-".to_string());
+    format!(
+        "{}:first_tick={} open_exact={} first_after_start={} nearest_start={} close_exact={} first_after_end={} nearest_end={}",
+        source.as_str(),
+        first_tick,
+        open_exact,
+        first_after_start,
+        nearest_start,
+        close_exact,
+        first_after_end,
+        nearest_end,
+    )
+}
+
+pub fn local_price_agg_collect_boundary_tape_tick(
+    tapes: &mut HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
+    source: LocalPriceSource,
+    price: f64,
+    ts_ms: u64,
+    start_ms: u64,
+    end_ms: u64,
+    boundary_window_ms: u64,
+) {
+    let tape = tapes.entry(source).or_default();
+    let open_low = start_ms.saturating_sub(boundary_window_ms);
+    let open_high = start_ms.saturating_add(boundary_window_ms);
+    if ts_ms >= open_low
+        && ts_ms <= open_high
+        && tape.open_window_ticks.len() < LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE
+    {
+        tape.open_window_ticks.push((ts_ms, price));
+    }
+    let close_low = end_ms.saturating_sub(boundary_window_ms);
+    let close_high = end_ms.saturating_add(boundary_window_ms);
+    if ts_ms >= close_low
+        && ts_ms <= close_high
+        && tape.close_window_ticks.len() < LOCAL_PRICE_AGG_BOUNDARY_TAPE_MAX_TICKS_PER_SIDE
+    {
+        tape.close_window_ticks.push((ts_ms, price));
+    }
+}
+
+pub fn pick_local_source_points(
+    state: &LocalSourceBoundaryState,
+    open_tol_ms: u64,
+    close_tol_ms: u64,
+) -> Option<(AggregatedPricePoint, AggregatedPricePoint)> {
+    let open = if let Some(p) = state.open_exact {
+        Some(p)
+    } else if let Some((delta, ts_ms, px)) = state.first_after_start {
+        if delta <= open_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_first_after_open",
+            })
+        } else {
+            None
+        }
+    } else if let Some((delta, ts_ms, px)) = state.nearest_start {
+        if delta <= open_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_nearest_open",
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    }?;
+
+    let close = if let Some(p) = state.close_exact {
+        Some(p)
+    } else if let Some((delta, ts_ms, px)) = state.first_after_end {
+        if delta <= close_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_first_after_close",
+            })
+        } else {
+            None
+        }
+    } else if let Some((delta, ts_ms, px)) = state.nearest_end {
+        if delta <= close_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_nearest_close",
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    }?;
+
+    Some((open, close))
+}
+
+pub fn pick_local_source_close_point(
+    state: &LocalSourceBoundaryState,
+    close_tol_ms: u64,
+) -> Option<AggregatedPricePoint> {
+    if let Some(p) = state.close_exact {
+        Some(p)
+    } else if let Some((delta, ts_ms, px)) = state.first_after_end {
+        if delta <= close_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_first_after_close",
+            })
+        } else {
+            None
+        }
+    } else if let Some((delta, ts_ms, px)) = state.nearest_end {
+        if delta <= close_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_nearest_close",
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+pub fn pick_local_source_open_point(
+    state: &LocalSourceBoundaryState,
+    open_tol_ms: u64,
+) -> Option<AggregatedPricePoint> {
+    if let Some(p) = state.open_exact {
+        Some(p)
+    } else if let Some((delta, ts_ms, px)) = state.first_after_start {
+        if delta <= open_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_first_after_open",
+            })
+        } else {
+            None
+        }
+    } else if let Some((delta, ts_ms, px)) = state.nearest_start {
+        if delta <= open_tol_ms {
+            Some(AggregatedPricePoint {
+                price: px,
+                ts_ms,
+                exact: false,
+                abs_delta_ms: delta,
+                source: "local_nearest_open",
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+pub fn local_price_agg_ingest_state(
+    states: &mut HashMap<LocalPriceSource, LocalSourceBoundaryState>,
+    source: LocalPriceSource,
+    price: f64,
+    ts_ms: u64,
+    start_ms: u64,
+    end_ms: u64,
+) {
+    let st = states.entry(source).or_default();
+    ingest_boundary_tick(
+        price,
+        ts_ms,
+        start_ms,
+        end_ms,
+        &mut st.first_tick_ts_ms,
+        &mut st.open_exact,
+        &mut st.close_exact,
+        &mut st.nearest_start,
+        &mut st.nearest_end,
+        &mut st.first_after_start,
+        &mut st.first_after_end,
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocalCloseSourceContribution {
+    pub source: LocalPriceSource,
+    pub raw_close_price: f64,
+    pub adjusted_close_price: f64,
+    pub close_ts_ms: u64,
+    pub close_exact: bool,
+    pub close_abs_delta_ms: u64,
+    /// Which boundary pick produced the close (`AggregatedPricePoint::source`).
+    pub close_pick: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalCloseOnlyAggHit {
+    pub close_price: f64,
+    pub close_ts_ms: u64,
+    pub source_count: usize,
+    pub source_spread_bps: f64,
+    pub close_exact_sources: usize,
+    pub source_contributions: Vec<LocalCloseSourceContribution>,
+}
+
+pub fn local_close_only_source_agreement(hit: &LocalCloseOnlyAggHit, rtds_open: f64) -> f64 {
+    if hit.source_contributions.is_empty() {
+        return 0.0;
+    }
+    let close_only_side = if hit.close_price >= rtds_open {
+        Side::Yes
+    } else {
+        Side::No
+    };
+    let agree_cnt = hit
+        .source_contributions
+        .iter()
+        .filter(|src| {
+            let src_side = if src.adjusted_close_price >= rtds_open {
+                Side::Yes
+            } else {
+                Side::No
+            };
+            src_side == close_only_side
+        })
+        .count();
+    agree_cnt as f64 / hit.source_contributions.len() as f64
+}
+
+impl LocalCloseOnlyAggHit {
+    /// Confidence that the round settles on the side implied by `open_price`.
+    pub fn confidence(&self, open_price: f64, max_source_spread_bps: f64) -> f64 {
+        source_confidence(
+            self.source_count,
+            local_close_only_source_agreement(self, open_price),
+            self.source_spread_bps,
+            max_source_spread_bps,
+            self.close_exact_sources,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CloseAggReject {
+    InsufficientSources { found: usize, required: usize },
+    NoWeightedClose,
+    SpreadTooWide { spread_bps: f64, max_bps: f64 },
+}
+
+/// Heuristic confidence shared by the open/close and close-only paths.
+/// A lone source scores by exactness only; multiple sources blend direction
+/// agreement, source count, spread headroom and boundary-exact coverage.
+pub fn source_confidence(
+    source_count: usize,
+    source_agreement: f64,
+    source_spread_bps: f64,
+    max_source_spread_bps: f64,
+    exact_sources: usize,
+) -> f64 {
+    if source_count == 1 {
+        return if exact_sources > 0 { 0.93 } else { 0.87 };
+    }
+    let source_factor = (source_count.min(3) as f64) / 3.0;
+    let spread_factor = if max_source_spread_bps > 0.0 {
+        (1.0 - (source_spread_bps / max_source_spread_bps)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let exact_factor = (exact_sources.min(2) as f64) / 2.0;
+    (0.50 * source_agreement + 0.20 * source_factor + 0.20 * spread_factor + 0.10 * exact_factor)
+        .clamp(0.0, 1.0)
+}
+
+/// Close pick per source (within `close_tol_ms`), adjusted by the learned
+/// source bias when a book is given. Sorted by source for stable output.
+pub fn local_close_source_contributions(
+    symbol: &str,
+    states: &HashMap<LocalPriceSource, LocalSourceBoundaryState>,
+    close_tol_ms: u64,
+    bias: Option<&SourceBiasBook>,
+) -> Vec<LocalCloseSourceContribution> {
+    let mut out = states
+        .iter()
+        .filter_map(|(source, st)| {
+            let close = pick_local_source_close_point(st, close_tol_ms)?;
+            let adjusted = bias
+                .map(|book| book.apply(symbol, *source, close.price))
+                .unwrap_or(close.price);
+            Some(LocalCloseSourceContribution {
+                source: *source,
+                raw_close_price: close.price,
+                adjusted_close_price: adjusted,
+                close_ts_ms: close.ts_ms,
+                close_exact: close.exact,
+                close_abs_delta_ms: close.abs_delta_ms,
+                close_pick: close.source,
+            })
+        })
+        .collect::<Vec<_>>();
+    out.sort_by_key(|c| c.source);
+    out
+}
+
+/// Weighted close across sources; see `PriceAggConfig::contribution_weight`.
+pub fn aggregate_local_close(
+    source_contributions: Vec<LocalCloseSourceContribution>,
+    end_ms: u64,
+    cfg: &PriceAggConfig,
+) -> Result<LocalCloseOnlyAggHit, CloseAggReject> {
+    if source_contributions.len() < cfg.min_sources {
+        return Err(CloseAggReject::InsufficientSources {
+            found: source_contributions.len(),
+            required: cfg.min_sources,
+        });
+    }
+    let close_values = source_contributions
+        .iter()
+        .map(|c| c.adjusted_close_price)
+        .collect::<Vec<_>>();
+    let close_weighted = source_contributions
+        .iter()
+        .map(|c| (c.adjusted_close_price, cfg.contribution_weight(c, end_ms)))
+        .collect::<Vec<_>>();
+    let close_price = weighted_mean(&close_weighted)
+        .or_else(|| robust_center(close_values.clone()))
+        .ok_or(CloseAggReject::NoWeightedClose)?;
+    let close_ts_ms =
+        median_u64(source_contributions.iter().map(|c| c.close_ts_ms).collect()).unwrap_or(end_ms);
+    let source_spread_bps = spread_bps(&close_values).unwrap_or(0.0);
+    if source_spread_bps > cfg.max_source_spread_bps + 1e-9 {
+        return Err(CloseAggReject::SpreadTooWide {
+            spread_bps: source_spread_bps,
+            max_bps: cfg.max_source_spread_bps,
+        });
+    }
+    Ok(LocalCloseOnlyAggHit {
+        close_price,
+        close_ts_ms,
+        source_count: source_contributions.len(),
+        source_spread_bps,
+        close_exact_sources: source_contributions
+            .iter()
+            .filter(|c| c.close_exact)
+            .count(),
+        source_contributions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contribution(
+        source: LocalPriceSource,
+        price: f64,
+        ts_ms: u64,
+        exact: bool,
+    ) -> LocalCloseSourceContribution {
+        LocalCloseSourceContribution {
+            source,
+            raw_close_price: price,
+            adjusted_close_price: price,
+            close_ts_ms: ts_ms,
+            close_exact: exact,
+            close_abs_delta_ms: 0,
+            close_pick: "exact_end",
+        }
+    }
+
+    #[test]
+    fn source_confidence_single_source_scores_by_exactness() {
+        assert_eq!(source_confidence(1, 1.0, 0.0, 12.0, 1), 0.93);
+        assert_eq!(source_confidence(1, 1.0, 0.0, 12.0, 0), 0.87);
+        // 3 agreeing sources, half the spread budget, one exact
+        let conf = source_confidence(3, 1.0, 6.0, 12.0, 1);
+        assert!((conf - (0.50 + 0.20 + 0.10 + 0.05)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn aggregate_local_close_weights_exact_and_rejects_wide_spread() {
+        let cfg = PriceAggConfig::default();
+        let end_ms = 10_000;
+        let hit = aggregate_local_close(
+            vec![
+                contribution(LocalPriceSource::Binance, 100.0, end_ms, true),
+                contribution(LocalPriceSource::Okx, 100.05, end_ms + 900, false),
+            ],
+            end_ms,
+            &cfg,
+        )
+        .expect("two close sources within spread");
+        assert_eq!(hit.source_count, 2);
+        assert_eq!(hit.close_exact_sources, 1);
+        assert!(hit.close_price > 100.0 && hit.close_price < 100.025);
+
+        let err = aggregate_local_close(
+            vec![
+                contribution(LocalPriceSource::Binance, 100.0, end_ms, true),
+                contribution(LocalPriceSource::Okx, 101.0, end_ms, true),
+            ],
+            end_ms,
+            &cfg,
+        )
+        .unwrap_err();
+        assert!(matches!(err, CloseAggReject::SpreadTooWide { .. }));
+
+        let cfg = PriceAggConfig {
+            min_sources: 2,
+            ..PriceAggConfig::default()
+        };
+        assert_eq!(
+            aggregate_local_close(
+                vec![contribution(LocalPriceSource::Binance, 100.0, end_ms, true)],
+                end_ms,
+                &cfg,
+            )
+            .unwrap_err(),
+            CloseAggReject::InsufficientSources {
+                found: 1,
+                required: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Tunables for the local price aggregator (`PM_LOCAL_PRICE_AGG_*`).
+
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::boundary::{AggregatedPricePoint, LocalCloseSourceContribution};
+use super::{LocalPriceSource, LOCAL_PRICE_DEFAULT_SOURCES};
+use crate::polymarket::config_validation::clamp_reported;
+
+pub const LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS_DEFAULT: u64 = 600;
+pub const LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS_DEFAULT: u64 = 2_500;
+pub const LOCAL_PRICE_AGG_MIN_CONFIDENCE_DEFAULT: f64 = 0.85;
+pub const LOCAL_PRICE_AGG_MIN_SOURCES_DEFAULT: usize = 1;
+pub const LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS_DEFAULT: f64 = 12.0;
+pub const LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS_DEFAULT: f64 = 1.25;
+pub const LOCAL_PRICE_AGG_WEIGHT_BINANCE_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_BYBIT_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_OKX_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_COINBASE_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID_DEFAULT: f64 = 0.5;
+pub const LOCAL_PRICE_AGG_WEIGHT_KRAKEN_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_BITSTAMP_DEFAULT: f64 = 1.0;
+pub const LOCAL_PRICE_AGG_WEIGHT_GATE_DEFAULT: f64 = 0.75;
+pub const LOCAL_PRICE_AGG_WEIGHT_CRYPTO_COM_DEFAULT: f64 = 0.75;
+pub const LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS_DEFAULT: f64 = 900.0;
+pub const LOCAL_PRICE_AGG_EXACT_BOOST_DEFAULT: f64 = 1.25;
+pub const LOCAL_PRICE_AGG_BOUNDARY_WINDOW_MS_DEFAULT: u64 = 5_000;
+pub const LOCAL_PRICE_AGG_FEED_STALL_MS_DEFAULT: u64 = 30_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAggConfig {
+    /// Max distance from the round start for a non-exact open pick.
+    pub open_tolerance_ms: u64,
+    /// Max distance from the round end for a non-exact close pick.
+    pub close_tolerance_ms: u64,
+    pub min_confidence: f64,
+    pub min_sources: usize,
+    /// Reject the round when per-source prices disagree by more than this.
+    pub max_source_spread_bps: f64,
+    pub single_source_min_direction_margin_bps: f64,
+    pub weight_binance: f64,
+    pub weight_bybit: f64,
+    pub weight_okx: f64,
+    pub weight_coinbase: f64,
+    pub weight_hyperliquid: f64,
+    pub weight_kraken: f64,
+    pub weight_bitstamp: f64,
+    pub weight_gate: f64,
+    pub weight_crypto_com: f64,
+    /// Half-weight distance for non-exact boundary picks.
+    pub close_time_decay_ms: f64,
+    /// Weight multiplier for ticks that land exactly on the boundary.
+    pub exact_boost: f64,
+    /// Tape window (each side of the boundary) kept for boundary policies.
+    pub boundary_window_ms: u64,
+    /// Opt-in venues on top of `LOCAL_PRICE_DEFAULT_SOURCES`.
+    pub extra_sources: Vec<LocalPriceSource>,
+    /// Reconnect a venue feed after this long without any WS frame.
+    pub feed_stall_ms: u64,
+}
+
+impl Default for PriceAggConfig {
+    fn default() -> Self {
+        Self {
+            open_tolerance_ms: LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS_DEFAULT,
+            close_tolerance_ms: LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS_DEFAULT,
+            min_confidence: LOCAL_PRICE_AGG_MIN_CONFIDENCE_DEFAULT,
+            min_sources: LOCAL_PRICE_AGG_MIN_SOURCES_DEFAULT,
+            max_source_spread_bps: LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS_DEFAULT,
+            single_source_min_direction_margin_bps:
+                LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS_DEFAULT,
+            weight_binance: LOCAL_PRICE_AGG_WEIGHT_BINANCE_DEFAULT,
+            weight_bybit: LOCAL_PRICE_AGG_WEIGHT_BYBIT_DEFAULT,
+            weight_okx: LOCAL_PRICE_AGG_WEIGHT_OKX_DEFAULT,
+            weight_coinbase: LOCAL_PRICE_AGG_WEIGHT_COINBASE_DEFAULT,
+            weight_hyperliquid: LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID_DEFAULT,
+            weight_kraken: LOCAL_PRICE_AGG_WEIGHT_KRAKEN_DEFAULT,
+            weight_bitstamp: LOCAL_PRICE_AGG_WEIGHT_BITSTAMP_DEFAULT,
+            weight_gate: LOCAL_PRICE_AGG_WEIGHT_GATE_DEFAULT,
+            weight_crypto_com: LOCAL_PRICE_AGG_WEIGHT_CRYPTO_COM_DEFAULT,
+            close_time_decay_ms: LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS_DEFAULT,
+            exact_boost: LOCAL_PRICE_AGG_EXACT_BOOST_DEFAULT,
+            boundary_window_ms: LOCAL_PRICE_AGG_BOUNDARY_WINDOW_MS_DEFAULT,
+            extra_sources: Vec::new(),
+            feed_stall_ms: LOCAL_PRICE_AGG_FEED_STALL_MS_DEFAULT,
+        }
+    }
+}
+
+/// Comma separated source names; unknown names are ignored.
+fn parse_source_list(raw: &str) -> Vec<LocalPriceSource> {
+    raw.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .filter_map(LocalPriceSource::parse)
+        .collect()
+}
+
+fn env_clamped<T: FromStr + PartialOrd + Copy + Display>(
+    name: &str,
+    lo: T,
+    hi: T,
+    default: T,
+) -> T {
+    match env::var(name).ok().and_then(|v| v.parse::<T>().ok()) {
+        Some(v) => clamp_reported(name, v, lo, hi),
+        None => default,
+    }
+}
+
+impl PriceAggConfig {
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        Self {
+            open_tolerance_ms: env_clamped(
+                "PM_LOCAL_PRICE_AGG_OPEN_TOLERANCE_MS",
+                50,
+                5_000,
+                d.open_tolerance_ms,
+            ),
+            close_tolerance_ms: env_clamped(
+                "PM_LOCAL_PRICE_AGG_CLOSE_TOLERANCE_MS",
+                50,
+                5_000,
+                d.close_tolerance_ms,
+            ),
+            min_confidence: env_clamped(
+                "PM_LOCAL_PRICE_AGG_MIN_CONFIDENCE",
+                0.0,
+                1.0,
+                d.min_confidence,
+            ),
+            min_sources: env_clamped("PM_LOCAL_PRICE_AGG_MIN_SOURCES", 1, 8, d.min_sources),
+            max_source_spread_bps: env_clamped(
+                "PM_LOCAL_PRICE_AGG_MAX_SOURCE_SPREAD_BPS",
+                0.0,
+                500.0,
+                d.max_source_spread_bps,
+            ),
+            single_source_min_direction_margin_bps: env_clamped(
+                "PM_LOCAL_PRICE_AGG_SINGLE_SOURCE_MIN_DIRECTION_MARGIN_BPS",
+                0.0,
+                20.0,
+                d.single_source_min_direction_margin_bps,
+            ),
+            weight_binance: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_BINANCE",
+                0.0,
+                10.0,
+                d.weight_binance,
+            ),
+            weight_bybit: env_clamped("PM_LOCAL_PRICE_AGG_WEIGHT_BYBIT", 0.0, 10.0, d.weight_bybit),
+            weight_okx: env_clamped("PM_LOCAL_PRICE_AGG_WEIGHT_OKX", 0.0, 10.0, d.weight_okx),
+            weight_coinbase: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_COINBASE",
+                0.0,
+                10.0,
+                d.weight_coinbase,
+            ),
+            weight_hyperliquid: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_HYPERLIQUID",
+                0.0,
+                10.0,
+                d.weight_hyperliquid,
+            ),
+            weight_kraken: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_KRAKEN",
+                0.0,
+                10.0,
+                d.weight_kraken,
+            ),
+            weight_bitstamp: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_BITSTAMP",
+                0.0,
+                10.0,
+                d.weight_bitstamp,
+            ),
+            weight_gate: env_clamped("PM_LOCAL_PRICE_AGG_WEIGHT_GATE", 0.0, 10.0, d.weight_gate),
+            weight_crypto_com: env_clamped(
+                "PM_LOCAL_PRICE_AGG_WEIGHT_CRYPTO_COM",
+                0.0,
+                10.0,
+                d.weight_crypto_com,
+            ),
+            close_time_decay_ms: env_clamped(
+                "PM_LOCAL_PRICE_AGG_CLOSE_TIME_DECAY_MS",
+                50.0,
+                10_000.0,
+                d.close_time_decay_ms,
+            ),
+            exact_boost: env_clamped("PM_LOCAL_PRICE_AGG_EXACT_BOOST", 1.0, 3.0, d.exact_boost),
+            boundary_window_ms: env_clamped(
+                "PM_LOCAL_PRICE_AGG_BOUNDARY_WINDOW_MS",
+                100,
+                30_000,
+                d.boundary_window_ms,
+            ),
+            extra_sources: env::var("PM_LOCAL_PRICE_AGG_EXTRA_SOURCES")
+                .map(|raw| parse_source_list(&raw))
+                .unwrap_or(d.extra_sources),
+            feed_stall_ms: env_clamped(
+                "PM_LOCAL_PRICE_AGG_FEED_STALL_MS",
+                1_000,
+                300_000,
+                d.feed_stall_ms,
+            ),
+        }
+    }
+
+    /// Default venues plus `extra_sources`, deduplicated, in declaration order.
+    pub fn enabled_sources(&self) -> Vec<LocalPriceSource> {
+        let mut out = LOCAL_PRICE_DEFAULT_SOURCES.to_vec();
+        out.extend(self.extra_sources.iter().copied());
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    pub fn source_weight(&self, source: LocalPriceSource) -> f64 {
+        match source {
+            LocalPriceSource::Binance => self.weight_binance,
+            LocalPriceSource::Bybit => self.weight_bybit,
+            LocalPriceSource::Okx => self.weight_okx,
+            LocalPriceSource::Coinbase => self.weight_coinbase,
+            LocalPriceSource::Hyperliquid => self.weight_hyperliquid,
+            LocalPriceSource::Kraken => self.weight_kraken,
+            LocalPriceSource::Bitstamp => self.weight_bitstamp,
+            LocalPriceSource::Gate => self.weight_gate,
+            LocalPriceSource::CryptoCom => self.weight_crypto_com,
+        }
+    }
+
+    pub fn temporal_weight(&self, abs_delta_ms: u64) -> f64 {
+        let decay = self.close_time_decay_ms;
+        if !decay.is_finite() || decay <= 0.0 {
+            return 1.0;
+        }
+        1.0 / (1.0 + (abs_delta_ms as f64 / decay))
+    }
+
+    pub fn point_weight(&self, source: LocalPriceSource, point: &AggregatedPricePoint) -> f64 {
+        let base = self.source_weight(source);
+        let temporal = self.temporal_weight(point.abs_delta_ms);
+        let exact = if point.exact { self.exact_boost } else { 1.0 };
+        base * temporal * exact
+    }
+
+    /// Same weighting as `point_weight`, measured against the round end.
+    pub fn contribution_weight(
+        &self,
+        contribution: &LocalCloseSourceContribution,
+        end_ms: u64,
+    ) -> f64 {
+        let abs_delta_ms = contribution.close_ts_ms.abs_diff(end_ms);
+        let exact = if contribution.close_exact {
+            self.exact_boost
+        } else {
+            1.0
+        };
+        self.source_weight(contribution.source) * self.temporal_weight(abs_delta_ms) * exact
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enabled_sources_adds_known_extra_venues_once() {
+        let cfg = PriceAggConfig {
+            extra_sources: parse_source_list("kraken, crypto_com,unknown,,okx,kraken"),
+            ..PriceAggConfig::default()
+        };
+        assert_eq!(
+            cfg.extra_sources,
+            vec![
+                LocalPriceSource::Kraken,
+                LocalPriceSource::CryptoCom,
+                LocalPriceSource::Okx,
+                LocalPriceSource::Kraken,
+            ]
+        );
+        let enabled = cfg.enabled_sources();
+        assert_eq!(enabled.len(), LOCAL_PRICE_DEFAULT_SOURCES.len() + 2);
+        assert!(enabled.contains(&LocalPriceSource::Kraken));
+        assert!(enabled.contains(&LocalPriceSource::CryptoCom));
+        assert!(!enabled.contains(&LocalPriceSource::Gate));
+        assert_eq!(PriceAggConfig::default().enabled_sources().len(), 5);
+    }
+}
@@ -0,0 +1,460 @@
+//! `LocalPriceHub`: fans venue ticks out per symbol. Each symbol gets a
+//! broadcast channel for live ticks plus a bounded ring of recent ticks so
+//! late subscribers can replay the round boundary.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::time::sleep;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use tracing::warn;
+
+use super::venues::{
+    binance_stream_symbol_from_chainlink_symbol, bitstamp_feed_control,
+    bitstamp_pair_from_chainlink_symbol, bybit_stream_symbol_from_chainlink_symbol,
+    coinbase_product_id_from_chainlink_symbol, crypto_com_feed_control,
+    crypto_com_instrument_from_chainlink_symbol, gate_pair_from_chainlink_symbol,
+    hyperliquid_coin_from_chainlink_symbol, kraken_symbol_from_chainlink_symbol,
+    okx_inst_id_from_chainlink_symbol, parse_binance_combined_trade_tick,
+    parse_bitstamp_trade_tick, parse_bybit_public_trade_ticks, parse_coinbase_ticker_tick,
+    parse_crypto_com_trade_ticks, parse_gate_trade_tick, parse_hyperliquid_all_mids_ticks,
+    parse_kraken_trade_ticks, parse_okx_trade_ticks, FeedControl, SymbolTick,
+};
+use super::{normalize_chainlink_symbol, LocalPriceSource, PriceAggConfig, SourceTick};
+use crate::polymarket::ingress::now_ms;
+
+pub const LOCAL_PRICE_HUB_RECENT_TICKS_PER_SYMBOL: usize = 16_384;
+
+#[derive(Clone)]
+pub struct LocalPriceHub {
+    senders: Arc<HashMap<String, broadcast::Sender<SourceTick>>>,
+    recent_ticks: Arc<Mutex<HashMap<String, VecDeque<SourceTick>>>>,
+}
+
+impl LocalPriceHub {
+    /// Hub with channels for `symbols` but no venue feeders; ticks arrive via
+    /// `publish_tick` (remote ingress clients, replay tools, tests).
+    pub fn new(symbols: &HashSet<String>) -> Arc<Self> {
+        let mut senders = HashMap::new();
+        let mut recent_ticks = HashMap::new();
+        for sym in symbols {
+            let (tx, _rx) = broadcast::channel::<SourceTick>(4096);
+            senders.insert(sym.clone(), tx);
+            recent_ticks.insert(sym.clone(), VecDeque::new());
+        }
+        Arc::new(Self {
+            senders: Arc::new(senders),
+            recent_ticks: Arc::new(Mutex::new(recent_ticks)),
+        })
+    }
+
+    /// Hub plus one WS feeder task per enabled venue that lists any of
+    /// `symbols`; feeders reconnect with backoff and after `feed_stall_ms`
+    /// without a frame.
+    pub fn spawn(symbols: HashSet<String>, cfg: &PriceAggConfig) -> Arc<Self> {
+        let hub = Self::new(&symbols);
+        let stall = Duration::from_millis(cfg.feed_stall_ms);
+        for source in cfg.enabled_sources() {
+            if let Some(feed) = VenueFeed::for_source(source, &symbols) {
+                Self::spawn_feeder(Arc::clone(&hub), feed, stall);
+            }
+        }
+        hub
+    }
+
+    pub fn publish_tick(&self, symbol: &str, price: f64, ts_ms: u64, source: LocalPriceSource) {
+        let sym = normalize_chainlink_symbol(symbol);
+        if sym.is_empty() || !price.is_finite() || price <= 0.0 {
+            return;
+        }
+        let tick = SourceTick {
+            price,
+            ts_ms,
+            source,
+        };
+        if let Ok(mut guard) = self.recent_ticks.lock() {
+            if let Some(buf) = guard.get_mut(&sym) {
+                buf.push_back(tick);
+                while buf.len() > LOCAL_PRICE_HUB_RECENT_TICKS_PER_SYMBOL {
+                    buf.pop_front();
+                }
+            }
+        }
+        if let Some(tx) = self.senders.get(&sym) {
+            let _ = tx.send(tick);
+        }
+    }
+
+    /// Live ticks for `symbol`; `None` when the hub was not built for it.
+    pub fn subscribe(&self, symbol: &str) -> Option<broadcast::Receiver<SourceTick>> {
+        let sym = normalize_chainlink_symbol(symbol);
+        self.senders.get(&sym).map(|tx| tx.subscribe())
+    }
+
+    /// Recent ticks for `symbol`, oldest first (bounded ring per symbol).
+    pub fn snapshot_recent_ticks(&self, symbol: &str) -> Vec<SourceTick> {
+        let sym = normalize_chainlink_symbol(symbol);
+        if sym.is_empty() {
+            return Vec::new();
+        }
+        let Ok(guard) = self.recent_ticks.lock() else {
+            return Vec::new();
+        };
+        guard
+            .get(&sym)
+            .map(|buf| buf.iter().copied().collect::<Vec<_>>())
+            .unwrap_or_default()
+    }
+
+    fn spawn_feeder(hub: Arc<Self>, feed: VenueFeed, stall: Duration) {
+        tokio::spawn(async move {
+            let mut backoff = Duration::from_millis(300);
+            loop {
+                let connect =
+                    tokio::time::timeout(Duration::from_secs(3), connect_async(&feed.url)).await;
+                let Ok(Ok((ws, _))) = connect else {
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(3));
+                    continue;
+                };
+                backoff = Duration::from_millis(300);
+                let (mut write, mut read) = ws.split();
+                if !feed.settle.is_zero() {
+                    sleep(feed.settle).await;
+                }
+                let mut subscribed = true;
+                for frame in (feed.subscribe)() {
+                    if write.send(Message::Text(frame)).await.is_err() {
+                        subscribed = false;
+                        break;
+                    }
+                }
+                if !subscribed {
+                    continue;
+                }
+                loop {
+                    let next = match tokio::time::timeout(stall, read.next()).await {
+                        Ok(Some(next)) => next,
+                        Ok(None) => break,
+                        Err(_) => {
+                            warn!(
+                                "⚠️ local_price_feed_stalled | source={} stall_ms={} — reconnecting",
+                                feed.source.as_str(),
+                                stall.as_millis(),
+                            );
+                            break;
+                        }
+                    };
+                    let msg = match next {
+                        Ok(m) => m,
+                        Err(_) => break,
+                    };
+                    let text = match msg {
+                        Message::Text(t) => t.to_string(),
+                        Message::Binary(b) => match String::from_utf8(b.to_vec()) {
+                            Ok(t) => t,
+                            Err(_) => continue,
+                        },
+                        _ => continue,
+                    };
+                    match feed.control.and_then(|control| control(&text)) {
+                        Some(FeedControl::Reply(frame)) => {
+                            if write.send(Message::Text(frame)).await.is_err() {
+                                break;
+                            }
+                            continue;
+                        }
+                        Some(FeedControl::Reconnect) => break,
+                        None => {}
+                    }
+                    for tick in (feed.parse)(&text) {
+                        hub.publish_tick(&tick.symbol, tick.price, tick.ts_ms, feed.source);
+                    }
+                }
+            }
+        });
+    }
+}
+
+type SubscribeFrames = Box<dyn Fn() -> Vec<String> + Send + Sync>;
+type TickParser = Box<dyn Fn(&str) -> Vec<SymbolTick> + Send + Sync>;
+
+/// One venue WS connection. `subscribe` is re-evaluated on every reconnect
+/// (Gate and Crypto.com stamp their requests with the current time).
+struct VenueFeed {
+    source: LocalPriceSource,
+    url: String,
+    /// Wait between connect and subscribe (Crypto.com rate-limits early requests).
+    settle: Duration,
+    subscribe: SubscribeFrames,
+    parse: TickParser,
+    control: Option<fn(&str) -> Option<FeedControl>>,
+}
+
+impl VenueFeed {
+    fn new(
+        source: LocalPriceSource,
+        url: impl Into<String>,
+        parse: impl Fn(&str) -> Vec<SymbolTick> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            source,
+            url: url.into(),
+            settle: Duration::ZERO,
+            subscribe: Box::new(Vec::new),
+            parse: Box::new(parse),
+            control: None,
+        }
+    }
+
+    fn with_subscribe(
+        mut self,
+        subscribe: impl Fn() -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.subscribe = Box::new(subscribe);
+        self
+    }
+
+    fn with_settle(mut self, settle: Duration) -> Self {
+        self.settle = settle;
+        self
+    }
+
+    fn with_control(mut self, control: fn(&str) -> Option<FeedControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// `None` when the venue lists none of `symbols`.
+    fn for_source(source: LocalPriceSource, symbols: &HashSet<String>) -> Option<Self> {
+        let mut symbols = symbols.iter().collect::<Vec<_>>();
+        symbols.sort();
+        let mapped = |map: fn(&str) -> Option<String>| -> Vec<String> {
+            symbols.iter().filter_map(|sym| map(sym)).collect()
+        };
+        let feed = match source {
+            LocalPriceSource::Binance => {
+                let streams = mapped(binance_stream_symbol_from_chainlink_symbol)
+                    .into_iter()
+                    .map(|sym| format!("{}@trade", sym))
+                    .collect::<Vec<_>>();
+                if streams.is_empty() {
+                    return None;
+                }
+                Self::new(
+                    source,
+                    format!(
+                        "wss://stream.binance.com:9443/stream?streams={}",
+                        streams.join("/")
+                    ),
+                    |text| {
+                        parse_binance_combined_trade_tick(text)
+                            .into_iter()
+                            .collect()
+                    },
+                )
+            }
+            LocalPriceSource::Bybit => {
+                let topics = mapped(bybit_stream_symbol_from_chainlink_symbol)
+                    .into_iter()
+                    .map(|sym| format!("publicTrade.{}", sym))
+                    .collect::<Vec<_>>();
+                if topics.is_empty() {
+                    return None;
+                }
+                let frame = json!({"op": "subscribe", "args": topics}).to_string();
+                Self::new(
+                    source,
+                    "wss://stream.bybit.com/v5/public/spot",
+                    parse_bybit_public_trade_ticks,
+                )
+                .with_subscribe(move || vec![frame.clone()])
+            }
+            LocalPriceSource::Okx => {
+                let args = mapped(okx_inst_id_from_chainlink_symbol)
+                    .into_iter()
+                    .map(|inst| json!({"channel": "trades", "instId": inst}))
+                    .collect::<Vec<Value>>();
+                if args.is_empty() {
+                    return None;
+                }
+                let frame = json!({"op": "subscribe", "args": args}).to_string();
+                Self::new(
+                    source,
+                    "wss://ws.okx.com:8443/ws/v5/public",
+                    parse_okx_trade_ticks,
+                )
+                .with_subscribe(move || vec![frame.clone()])
+            }
+            LocalPriceSource::Coinbase => {
+                let product_ids = mapped(coinbase_product_id_from_chainlink_symbol);
+                if product_ids.is_empty() {
+                    return None;
+                }
+                let frame = json!({
+                    "type": "subscribe",
+                    "product_ids": product_ids,
+                    "channels": ["ticker"],
+                })
+                .to_string();
+                Self::new(source, "wss://ws-feed.exchange.coinbase.com", |text| {
+                    parse_coinbase_ticker_tick(text).into_iter().collect()
+                })
+                .with_subscribe(move || vec![frame.clone()])
+            }
+            LocalPriceSource::Hyperliquid => {
+                let wanted: HashMap<String, String> = symbols
+                    .iter()
+                    .filter_map(|sym| {
+                        hyperliquid_coin_from_chainlink_symbol(sym)
+                            .map(|coin| (coin, normalize_chainlink_symbol(sym)))
+                    })
+                    .collect();
+                if wanted.is_empty() {
+                    return None;
+                }
+                let frame = json!({
+                    "method": "subscribe",
+                    "subscription": { "type": "allMids" },
+                })
+                .to_string();
+                Self::new(source, "wss://api.hyperliquid.xyz/ws", move |text| {
+                    parse_hyperliquid_all_mids_ticks(text, &wanted)
+                })
+                .with_subscribe(move || vec![frame.clone()])
+            }
+            LocalPriceSource::Kraken => {
+                let pairs = mapped(kraken_symbol_from_chainlink_symbol);
+                if pairs.is_empty() {
+                    return None;
+                }
+                let frame = json!({
+                    "method": "subscribe",
+                    "params": { "channel": "trade", "symbol": pairs, "snapshot": false },
+                })
+                .to_string();
+                Self::new(source, "wss://ws.kraken.com/v2", parse_kraken_trade_ticks)
+                    .with_subscribe(move || vec![frame.clone()])
+            }
+            LocalPriceSource::Bitstamp => {
+                let frames = mapped(bitstamp_pair_from_chainlink_symbol)
+                    .into_iter()
+                    .map(|pair| {
+                        json!({
+                            "event": "bts:subscribe",
+                            "data": { "channel": format!("live_trades_{}", pair) },
+                        })
+                        .to_string()
+                    })
+                    .collect::<Vec<_>>();
+                if frames.is_empty() {
+                    return None;
+                }
+                Self::new(source, "wss://ws.bitstamp.net", |text| {
+                    parse_bitstamp_trade_tick(text).into_iter().collect()
+                })
+                .with_subscribe(move || frames.clone())
+                .with_control(bitstamp_feed_control)
+            }
+            LocalPriceSource::Gate => {
+                let pairs = mapped(gate_pair_from_chainlink_symbol);
+                if pairs.is_empty() {
+                    return None;
+                }
+                Self::new(source, "wss://api.gateio.ws/ws/v4/", |text| {
+                    parse_gate_trade_tick(text).into_iter().collect()
+                })
+                .with_subscribe(move || {
+                    vec![json!({
+                        "time": now_ms() / 1_000,
+                        "channel": "spot.trades",
+                        "event": "subscribe",
+                        "payload": pairs,
+                    })
+                    .to_string()]
+                })
+            }
+            LocalPriceSource::CryptoCom => {
+                let channels = mapped(crypto_com_instrument_from_chainlink_symbol)
+                    .into_iter()
+                    .map(|inst| format!("trade.{}", inst))
+                    .collect::<Vec<_>>();
+                if channels.is_empty() {
+                    return None;
+                }
+                Self::new(
+                    source,
+                    "wss://stream.crypto.com/exchange/v1/market",
+                    parse_crypto_com_trade_ticks,
+                )
+                .with_settle(Duration::from_secs(1))
+                .with_subscribe(move || {
+                    let nonce = now_ms();
+                    vec![json!({
+                        "id": nonce,
+                        "method": "subscribe",
+                        "params": { "channels": channels },
+                        "nonce": nonce,
+                    })
+                    .to_string()]
+                })
+                .with_control(crypto_com_feed_control)
+            }
+        };
+        Some(feed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publish_tick_fans_out_to_subscribers_and_recent_ring() {
+        let symbols = HashSet::from(["btc/usd".to_string()]);
+        let hub = LocalPriceHub::new(&symbols);
+        let mut rx = hub.subscribe("BTC-USD").expect("hub built for btc/usd");
+        assert!(hub.subscribe("eth/usd").is_none());
+
+        hub.publish_tick("btc/usd", 64_000.5, 1_000, LocalPriceSource::Okx);
+        hub.publish_tick("btc/usd", f64::NAN, 1_001, LocalPriceSource::Okx);
+        hub.publish_tick("eth/usd", 3_000.0, 1_002, LocalPriceSource::Okx);
+
+        let expected = SourceTick {
+            price: 64_000.5,
+            ts_ms: 1_000,
+            source: LocalPriceSource::Okx,
+        };
+        assert_eq!(rx.try_recv().unwrap(), expected);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(hub.snapshot_recent_ticks("btc/usd"), vec![expected]);
+        assert!(hub.snapshot_recent_ticks("eth/usd").is_empty());
+    }
+
+    #[test]
+    fn venue_feeds_map_symbols_and_skip_unlisted_venues() {
+        let symbols = HashSet::from(["eth/usd".to_string(), "btc/usd".to_string()]);
+        assert!(VenueFeed::for_source(LocalPriceSource::Hyperliquid, &symbols).is_none());
+
+        let binance = VenueFeed::for_source(LocalPriceSource::Binance, &symbols).unwrap();
+        assert!(binance.url.ends_with("streams=btcusdt@trade/ethusdt@trade"));
+        assert!((binance.subscribe)().is_empty());
+
+        let bitstamp = VenueFeed::for_source(LocalPriceSource::Bitstamp, &symbols).unwrap();
+        let frames = (bitstamp.subscribe)();
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].contains("live_trades_btcusd"));
+        assert!(bitstamp.control.is_some());
+
+        let gate = VenueFeed::for_source(LocalPriceSource::Gate, &symbols).unwrap();
+        let frame: Value = serde_json::from_str(&(gate.subscribe)()[0]).unwrap();
+        assert_eq!(frame["payload"], json!(["BTC_USDT", "ETH_USDT"]));
+        let tick = r#"{"channel":"spot.trades","event":"update","result":{"create_time_ms":"1776488226996.1","currency_pair":"ETH_USDT","price":"3200.5"}}"#;
+        assert_eq!((gate.parse)(tick)[0].symbol, "eth/usd");
+    }
+}
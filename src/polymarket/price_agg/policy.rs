@@ -0,0 +1,4144 @@
+//! Symbol-tuned boundary shadow policies.
+//!
+//! Each policy picks one close per allowed source from the boundary tape
+//! (`LocalSourceBoundaryTapeState`) with a fixed rule, fuses them, then runs
+//! the per-symbol candidate filters and router fallbacks. The thresholds are
+//! fitted against recorded RTDS closes; treat them as data, not design.
+
+use std::collections::HashMap;
+
+use super::boundary::{
+    local_close_only_source_agreement, AggregatedPricePoint, LocalCloseOnlyAggHit,
+    LocalCloseSourceContribution, LocalSourceBoundaryTapeState,
+};
+use super::config::PriceAggConfig;
+use super::{median_u64, robust_center, spread_bps, weighted_mean, LocalPriceSource};
+use crate::polymarket::types::Side;
+
+const LOCAL_BOUNDARY_SOURCES_FULL: &[LocalPriceSource] = &[
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+];
+const LOCAL_BOUNDARY_SOURCES_DROP_BINANCE: &[LocalPriceSource] = &[
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+];
+const LOCAL_BOUNDARY_SOURCES_DROP_BYBIT: &[LocalPriceSource] = &[
+    LocalPriceSource::Binance,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+];
+const LOCAL_BOUNDARY_SOURCES_DROP_OKX: &[LocalPriceSource] = &[
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Coinbase,
+    LocalPriceSource::Hyperliquid,
+];
+const LOCAL_BOUNDARY_SOURCES_DROP_COINBASE: &[LocalPriceSource] = &[
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Hyperliquid,
+];
+const LOCAL_BOUNDARY_SOURCES_DROP_HYPERLIQUID: &[LocalPriceSource] = &[
+    LocalPriceSource::Binance,
+    LocalPriceSource::Bybit,
+    LocalPriceSource::Okx,
+    LocalPriceSource::Coinbase,
+];
+const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE: &[LocalPriceSource] =
+    &[LocalPriceSource::Binance, LocalPriceSource::Coinbase];
+pub const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_HYPERLIQUID: &[LocalPriceSource] =
+    &[LocalPriceSource::Binance, LocalPriceSource::Hyperliquid];
+pub const LOCAL_BOUNDARY_SOURCES_ONLY_BYBIT_COINBASE: &[LocalPriceSource] =
+    &[LocalPriceSource::Bybit, LocalPriceSource::Coinbase];
+const LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE: &[LocalPriceSource] = &[LocalPriceSource::Binance];
+const LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE: &[LocalPriceSource] = &[LocalPriceSource::Coinbase];
+const LOCAL_BOUNDARY_SOURCES_ONLY_HYPERLIQUID: &[LocalPriceSource] =
+    &[LocalPriceSource::Hyperliquid];
+const LOCAL_BOUNDARY_SOURCES_ONLY_OKX: &[LocalPriceSource] = &[LocalPriceSource::Okx];
+const LOCAL_BOUNDARY_SOURCES_ONLY_OKX_COINBASE: &[LocalPriceSource] =
+    &[LocalPriceSource::Okx, LocalPriceSource::Coinbase];
+
+const LOCAL_BOUNDARY_POLICY_PRE_MS: u64 = 5_000;
+pub const LOCAL_BOUNDARY_POLICY_POST_MS: u64 = 250;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalBoundaryCloseRule {
+    LastBefore,
+    NearestAbs,
+    AfterThenBefore,
+}
+
+impl LocalBoundaryCloseRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LastBefore => "last_before",
+            Self::NearestAbs => "nearest_abs",
+            Self::AfterThenBefore => "after_then_before",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocalBoundaryShadowPolicySpec {
+    pub policy_name: &'static str,
+    pub source_subset_name: &'static str,
+    pub rule: LocalBoundaryCloseRule,
+    pub min_sources: usize,
+    pub allowed_sources: &'static [LocalPriceSource],
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalBoundaryShadowHit {
+    pub policy_name: &'static str,
+    pub source_subset_name: &'static str,
+    pub rule: LocalBoundaryCloseRule,
+    pub min_sources: usize,
+    pub close_price: f64,
+    pub close_ts_ms: u64,
+    pub source_count: usize,
+    pub source_spread_bps: f64,
+    pub close_exact_sources: usize,
+    pub source_contributions: Vec<LocalCloseSourceContribution>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalBoundaryShadowOutcome {
+    pub policy_name: &'static str,
+    pub source_subset_name: &'static str,
+    pub rule: LocalBoundaryCloseRule,
+    pub min_sources: usize,
+    pub hit: Option<LocalBoundaryShadowHit>,
+}
+
+fn pick_local_boundary_tape_close_point(
+    tape: &LocalSourceBoundaryTapeState,
+    end_ms: u64,
+    pre_ms: u64,
+    post_ms: u64,
+    rule: LocalBoundaryCloseRule,
+) -> Option<(AggregatedPricePoint, f64)> {
+    let ticks = tape
+        .close_window_ticks
+        .iter()
+        .filter_map(|(ts_ms, price)| {
+            let offset_ms = (*ts_ms as i64) - (end_ms as i64);
+            let in_range = offset_ms >= -(pre_ms as i64) && offset_ms <= post_ms as i64;
+            if in_range {
+                Some((*ts_ms, *price, offset_ms))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if ticks.is_empty() {
+        return None;
+    }
+
+    let picked = match rule {
+        LocalBoundaryCloseRule::LastBefore => ticks
+            .iter()
+            .filter(|(_, _, offset_ms)| *offset_ms <= 0)
+            .max_by_key(|(ts_ms, _, _)| *ts_ms)
+            .copied(),
+        LocalBoundaryCloseRule::NearestAbs => ticks
+            .iter()
+            .min_by_key(|(ts_ms, _, offset_ms)| (offset_ms.abs(), *offset_ms > 0, *ts_ms))
+            .copied(),
+        LocalBoundaryCloseRule::AfterThenBefore => ticks
+            .iter()
+            .filter(|(_, _, offset_ms)| *offset_ms >= 0)
+            .min_by_key(|(ts_ms, _, _)| *ts_ms)
+            .copied()
+            .or_else(|| {
+                ticks
+                    .iter()
+                    .filter(|(_, _, offset_ms)| *offset_ms <= 0)
+                    .max_by_key(|(ts_ms, _, _)| *ts_ms)
+                    .copied()
+            }),
+    }?;
+
+    let (ts_ms, raw_price, offset_ms) = picked;
+    Some((
+        AggregatedPricePoint {
+            price: raw_price,
+            ts_ms,
+            exact: offset_ms == 0,
+            abs_delta_ms: offset_ms.unsigned_abs(),
+            source: rule.as_str(),
+        },
+        raw_price,
+    ))
+}
+
+pub fn local_boundary_policy_specs_fully_free(symbol: &str) -> LocalBoundaryShadowPolicySpec {
+    match symbol {
+        "bnb/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "full",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
+        },
+        "btc/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        "doge/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        "eth/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        "hype/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_hyperliquid",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_HYPERLIQUID,
+        },
+        "sol/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_bybit",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 2,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BYBIT,
+        },
+        "xrp/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        _ => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_fully_free",
+            source_subset_name: "full",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
+        },
+    }
+}
+
+pub fn local_boundary_policy_specs_shared_core(symbol: &str) -> LocalBoundaryShadowPolicySpec {
+    match symbol {
+        "bnb/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        "btc/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        "doge/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        "eth/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "full",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
+        },
+        "hype/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_coinbase",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_COINBASE,
+        },
+        "sol/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        "xrp/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        _ => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_shared_core",
+            source_subset_name: "full",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
+        },
+    }
+}
+
+pub fn local_boundary_policy_specs_weighted(symbol: &str) -> LocalBoundaryShadowPolicySpec {
+    match symbol {
+        "bnb/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "drop_okx",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_OKX,
+        },
+        "btc/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "only_coinbase",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
+        },
+        "doge/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        "eth/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "only_coinbase",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
+        },
+        "hype/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "drop_binance",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 2,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_DROP_BINANCE,
+        },
+        "sol/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "only_okx_coinbase",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 2,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX_COINBASE,
+        },
+        "xrp/usd" => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "only_binance_coinbase",
+            rule: LocalBoundaryCloseRule::NearestAbs,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE,
+        },
+        _ => LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_weighted",
+            source_subset_name: "full",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_FULL,
+        },
+    }
+}
+
+pub fn local_boundary_symbol_router_fallback_policy_specs(
+    symbol: &str,
+) -> Vec<LocalBoundaryShadowPolicySpec> {
+    match symbol {
+        "bnb/usd" => vec![
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "bnb_okx_no_fallback",
+                rule: LocalBoundaryCloseRule::AfterThenBefore,
+                min_sources: 1,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
+            },
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "bnb_okx_fallback",
+                rule: LocalBoundaryCloseRule::AfterThenBefore,
+                min_sources: 1,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
+            },
+        ],
+        "doge/usd" => vec![LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_symbol_router_fallback",
+            source_subset_name: "doge_binance_fallback",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
+        }],
+        "eth/usd" => vec![LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_symbol_router_fallback",
+            source_subset_name: "eth_binance_missing_fallback",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
+        }],
+        "hype/usd" => vec![LocalBoundaryShadowPolicySpec {
+            policy_name: "boundary_symbol_router_fallback",
+            source_subset_name: "hype_hyperliquid_fallback",
+            rule: LocalBoundaryCloseRule::AfterThenBefore,
+            min_sources: 1,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_HYPERLIQUID,
+        }],
+        "sol/usd" => vec![
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "sol_binance_coinbase_fallback",
+                rule: LocalBoundaryCloseRule::NearestAbs,
+                min_sources: 2,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE,
+            },
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "sol_coinbase_fallback",
+                rule: LocalBoundaryCloseRule::AfterThenBefore,
+                min_sources: 1,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_COINBASE,
+            },
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "sol_binance_fallback",
+                rule: LocalBoundaryCloseRule::AfterThenBefore,
+                min_sources: 1,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE,
+            },
+            LocalBoundaryShadowPolicySpec {
+                policy_name: "boundary_symbol_router_fallback",
+                source_subset_name: "sol_okx_missing_fallback",
+                rule: LocalBoundaryCloseRule::AfterThenBefore,
+                min_sources: 1,
+                allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_OKX,
+            },
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn local_boundary_policy_source_weight(
+    policy_name: &str,
+    symbol: &str,
+    source: LocalPriceSource,
+    cfg: &PriceAggConfig,
+) -> f64 {
+    if policy_name != "boundary_weighted" {
+        return cfg.source_weight(source);
+    }
+    if symbol == "doge/usd" {
+        return match source {
+            LocalPriceSource::Binance => 0.5,
+            LocalPriceSource::Bybit => 0.5,
+            LocalPriceSource::Coinbase => 1.0,
+            LocalPriceSource::Hyperliquid => 1.5,
+            LocalPriceSource::Okx => 0.5,
+            // Weights above were fitted before the opt-in venues existed.
+            _ => 0.0,
+        };
+    }
+    if symbol == "xrp/usd" {
+        return match source {
+            LocalPriceSource::Binance => 0.462_086,
+            LocalPriceSource::Bybit => 0.0,
+            LocalPriceSource::Coinbase => 2.329_335,
+            LocalPriceSource::Okx => 0.050_000,
+            LocalPriceSource::Hyperliquid => 1.083_887,
+            _ => 0.0,
+        };
+    }
+    if symbol == "btc/usd" {
+        return match source {
+            LocalPriceSource::Coinbase => 1.0,
+            _ => 0.0,
+        };
+    }
+    if symbol == "bnb/usd" {
+        return match source {
+            LocalPriceSource::Binance => 0.5,
+            LocalPriceSource::Bybit => 0.5,
+            LocalPriceSource::Okx => 0.5,
+            LocalPriceSource::Coinbase => 1.0,
+            LocalPriceSource::Hyperliquid => 1.5,
+            _ => 0.0,
+        };
+    }
+    if symbol == "hype/usd" {
+        return match source {
+            LocalPriceSource::Bybit => 1.0,
+            LocalPriceSource::Coinbase => 1.0,
+            LocalPriceSource::Okx => 1.0,
+            LocalPriceSource::Hyperliquid => 0.25,
+            LocalPriceSource::Binance => 0.0,
+            _ => 0.0,
+        };
+    }
+    match source {
+        LocalPriceSource::Binance => 0.5,
+        LocalPriceSource::Bybit => 0.5,
+        LocalPriceSource::Okx => 0.5,
+        LocalPriceSource::Coinbase => 1.0,
+        LocalPriceSource::Hyperliquid => 1.5,
+        _ => cfg.source_weight(source),
+    }
+}
+
+pub fn local_boundary_weighted_candidate_filter_reason_for_policy(
+    symbol: &str,
+    close_only_filter_reason: Option<&'static str>,
+    close_only_direction_margin_bps: Option<f64>,
+    round_end_ts: u64,
+    rtds_open: f64,
+    hit: &LocalBoundaryShadowHit,
+) -> Option<&'static str> {
+    let weighted_direction_margin_bps =
+        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
+    if hit.source_count >= 2 && hit.source_spread_bps > 20.0 {
+        return Some("cross_source_extreme_spread");
+    }
+    match symbol {
+        "bnb/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.source_subset_name == "bnb_okx_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+            {
+                return Some("bnb_okx_fallback_far_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 4.5
+            {
+                return Some("bnb_single_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 7.6
+                && (400..=1_200).contains(&close_abs_delta_ms)
+            {
+                return Some("bnb_single_yes_fast_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 9.3 + 1e-9
+                && !(400..1_800).contains(&close_abs_delta_ms)
+            {
+                return Some("bnb_single_yes_upper_mid_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 9.3 + 1e-9
+                && hit.source_spread_bps <= 0.5 + 1e-9
+                && close_abs_delta_ms >= 2_500
+            {
+                return Some("bnb_two_lowspread_yes_upper_mid_stale_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 5.8
+                && close_abs_delta_ms <= 500
+            {
+                return Some("bnb_single_binance_yes_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 5.5 + 1e-9
+                && close_abs_delta_ms >= 1_800
+            {
+                return Some("bnb_single_binance_yes_stale_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_direction_margin_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps < 8.0
+                && close_abs_delta_ms >= 1_800
+            {
+                return Some("bnb_single_binance_yes_stale_upper_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_direction_margin_bps + 1e-9 >= 11.5
+                && close_abs_delta_ms >= 1_800
+            {
+                return Some("bnb_single_binance_yes_stale_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count <= 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+                && (100..1_500).contains(&close_abs_delta_ms)
+            {
+                return Some("bnb_no_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps <= 2.0 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 2.2
+            {
+                return Some("bnb_two_no_midspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 6.0
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("bnb_single_binance_no_fast_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+            {
+                return Some("bnb_single_bybit_no_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 2.5
+            {
+                return Some("bnb_three_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps + 1e-9 >= 30.0
+                && close_abs_delta_ms <= 100
+            {
+                return Some("bnb_three_high_spread_fast_far_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps + 1e-9 < 3.5
+            {
+                return Some("bnb_three_wide_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps <= 0.55 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps < 7.3
+                && close_abs_delta_ms < 250
+            {
+                return Some("bnb_two_tight_spread_yes_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources >= 1
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 0.5 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+            {
+                return Some("bnb_two_binance_bybit_exact_lowspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 0.6 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 15.0
+                && close_abs_delta_ms <= 200
+            {
+                return Some("bnb_two_binance_bybit_yes_lowspread_fast_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 1.3 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps < 3.3
+                && close_abs_delta_ms <= 800
+            {
+                return Some("bnb_two_binance_bybit_yes_lowspread_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 1.0 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 5.5
+                && weighted_direction_margin_bps < 7.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("bnb_two_binance_bybit_yes_lowspread_fast_upper_mid_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 0.3 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 9.0
+                && weighted_direction_margin_bps < 10.0
+                && (800..=1_500).contains(&close_abs_delta_ms)
+            {
+                return Some("bnb_two_binance_bybit_yes_lowspread_fast_high_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 2.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 8.5
+                && (1_000..=1_500).contains(&close_abs_delta_ms)
+            {
+                return Some("bnb_two_binance_bybit_yes_midspread_stale_upper_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 < 5.0
+            {
+                return Some("bnb_two_wide_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+            {
+                return Some("bnb_high_spread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+            {
+                return Some("bnb_mid_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps <= 1.3 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 2.2
+            {
+                return Some("bnb_tight_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 1.2 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 3.0
+                && close_abs_delta_ms <= 800
+            {
+                return Some("bnb_two_binance_bybit_yes_midspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 1.3 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 3.0
+            {
+                return Some("bnb_two_binance_bybit_yes_lowspread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps <= 1.3 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps < 8.0
+                && close_abs_delta_ms >= 2_500
+            {
+                return Some("bnb_two_binance_bybit_yes_stale_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+            {
+                return Some("bnb_yes_near_flat");
+            }
+            if close_only_filter_reason == Some("preclose_near_flat")
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+            {
+                return Some("bnb_close_only_preclose_near_flat");
+            }
+        }
+        "btc/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 0.451
+            {
+                return Some("btc_single_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 < 0.9
+                && close_abs_delta_ms <= 300
+            {
+                return Some("btc_single_coinbase_yes_fast_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 < 0.9
+                && close_abs_delta_ms > 300
+                && close_abs_delta_ms <= 600
+            {
+                return Some("btc_single_coinbase_yes_midlag_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 1.0
+                && weighted_direction_margin_bps < 1.5
+                && close_abs_delta_ms <= 200
+            {
+                return Some("btc_single_coinbase_yes_fast_low_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 2.2
+                && weighted_direction_margin_bps < 2.5
+                && close_abs_delta_ms <= 100
+            {
+                return Some("btc_single_coinbase_yes_very_fast_upper_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 0.9
+                && weighted_direction_margin_bps < 1.2
+                && (500..=550).contains(&close_abs_delta_ms)
+            {
+                return Some("btc_single_coinbase_yes_midlag_upper_near_flat_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 1.8
+                && weighted_direction_margin_bps < 2.1
+                && close_abs_delta_ms > 300
+                && close_abs_delta_ms <= 500
+            {
+                return Some("btc_single_coinbase_yes_midlag_upper_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 2.4
+                && weighted_direction_margin_bps < 2.7
+                && close_abs_delta_ms > 500
+                && close_abs_delta_ms <= 700
+            {
+                return Some("btc_single_coinbase_yes_midlag_upper_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 < 0.9
+                && close_abs_delta_ms >= 1_800
+            {
+                return Some("btc_single_coinbase_yes_stale_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 < 0.5
+                && close_abs_delta_ms <= 100
+            {
+                return Some("btc_single_coinbase_no_fast_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 < 0.5
+                && close_abs_delta_ms > 300
+                && close_abs_delta_ms <= 700
+            {
+                return Some("btc_single_coinbase_no_midlag_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 2.6
+                && close_abs_delta_ms <= 300
+            {
+                return Some("btc_single_coinbase_no_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+            {
+                return Some("btc_two_source_near_flat");
+            }
+        }
+        "xrp/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps < 0.05
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+                && close_abs_delta_ms <= 300
+            {
+                return Some("xrp_binance_coinbase_yes_zero_spread_fast_near_flat");
+            }
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 1.7
+                && weighted_direction_margin_bps + 1e-9 < 0.35
+                && close_abs_delta_ms <= 300
+            {
+                return Some("xrp_binance_coinbase_yes_fast_midspread_near_flat");
+            }
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 1.7
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+                && (500..=800).contains(&close_abs_delta_ms)
+            {
+                return Some("xrp_binance_coinbase_yes_midlag_midspread_near_flat");
+            }
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 0.5
+                && hit.source_spread_bps < 1.0
+                && weighted_direction_margin_bps + 1e-9 < 0.7
+                && close_abs_delta_ms <= 300
+            {
+                return Some("xrp_binance_coinbase_yes_fast_tightspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 0.45
+                && !((!weighted_side_yes && close_abs_delta_ms >= 400)
+                    || (weighted_side_yes && close_abs_delta_ms < 300))
+            {
+                return Some("xrp_nearest_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+                && (weighted_side_yes || close_abs_delta_ms < 50)
+            {
+                return Some("xrp_nearest_wide_spread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.3
+                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 >= 1.13)
+            {
+                return Some("xrp_single_nearest_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && !weighted_side_yes
+                && hit.source_subset_name == "only_binance_coinbase"
+                && weighted_direction_margin_bps + 1e-9 < 0.7
+                && close_abs_delta_ms <= 300
+            {
+                return Some("xrp_single_binance_no_fast_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_side_yes
+                && weighted_direction_margin_bps < 2.5
+                && close_abs_delta_ms >= 1_000
+            {
+                return Some("xrp_single_binance_yes_stale_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 4.5
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("xrp_single_binance_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps < 2.6
+                && close_abs_delta_ms <= 100
+            {
+                return Some("xrp_nearest_wide_spread_yes_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 3.0
+                && close_abs_delta_ms >= 800
+            {
+                return Some("xrp_binance_coinbase_no_stale_mid_margin");
+            }
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 1.7
+                && weighted_direction_margin_bps + 1e-9 < 0.8
+                && close_abs_delta_ms >= 1_000
+            {
+                return Some("xrp_binance_coinbase_no_stale_midspread_near_flat");
+            }
+            if hit.source_subset_name == "only_binance_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Binance)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 < 1.2
+                && close_abs_delta_ms <= 500
+            {
+                return Some("xrp_binance_coinbase_no_fast_midspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Binance)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 2.5
+                && weighted_direction_margin_bps < 3.5
+                && close_abs_delta_ms >= 2_000
+            {
+                return Some("xrp_single_binance_yes_stale_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+            {
+                return Some("xrp_last_yes_near_flat");
+            }
+        }
+        "doge/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && close_abs_delta_ms <= 1_000
+                && weighted_direction_margin_bps + 1e-9 < 4.0
+                && !(hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
+                    && weighted_direction_margin_bps + 1e-9 >= 0.45)
+            {
+                return Some("doge_single_last_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps < 5.0
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("doge_single_bybit_yes_fast_upper_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Bybit)
+                && weighted_direction_margin_bps + 1e-9 >= 14.0
+                && close_abs_delta_ms <= 300
+            {
+                return Some("doge_single_bybit_fast_high_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
+                && (900..=1_000).contains(&close_abs_delta_ms)
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 15.0
+            {
+                return Some("doge_single_okx_late_fast_mid_margin_tail");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 2.5
+                && (4_000..=4_500).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_single_okx_last_very_stale_yes_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 3.8
+                && weighted_direction_margin_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps < 8.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("doge_three_last_fast_spread_tail");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 6.0
+                && hit.source_spread_bps < 7.0
+                && weighted_direction_margin_bps + 1e-9 >= 25.0
+                && weighted_direction_margin_bps < 27.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("doge_three_bybit_coinbase_okx_last_fast_highspread_far_margin_tail");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 10.0
+                && (400..=600).contains(&close_abs_delta_ms)
+            {
+                return Some(
+                    "doge_three_bybit_coinbase_okx_last_fast_midspread_no_mid_margin_tail",
+                );
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps + 1e-9 < 3.5
+                && close_abs_delta_ms <= 800
+            {
+                return Some("doge_three_last_fast_spread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps <= 1.1 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 15.0
+                && close_abs_delta_ms <= 800
+            {
+                return Some("doge_three_last_fast_tightspread_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps <= 1.1 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && hit.source_spread_bps + 1e-9 >= 0.9
+                && hit.source_spread_bps < 1.0
+                && close_abs_delta_ms <= 800
+            {
+                return Some("doge_multi_last_fast_tightspread_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps <= 0.1 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 6.0
+                && close_abs_delta_ms <= 800
+            {
+                return Some("doge_two_last_tightspread_fast_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 0.8
+                && hit.source_spread_bps < 1.1
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps < 15.0
+                && close_abs_delta_ms >= 1_500
+            {
+                return Some("doge_two_bybit_okx_last_stale_tightspread_high_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && hit.source_spread_bps < 2.0
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("doge_multi_last_fast_midspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && close_abs_delta_ms <= 500
+                && matches!(
+                    close_only_filter_reason,
+                    Some("preclose_near_flat" | "low_confidence")
+                )
+                && close_only_direction_margin_bps
+                    .map(|margin_bps| margin_bps + 1e-9 < 0.5)
+                    .unwrap_or(false)
+            {
+                return Some("doge_three_last_fast_midspread_low_signal_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 3.0
+                && (1_000..=1_800).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_two_bybit_coinbase_last_stale_widespread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 7.5
+                && hit.source_spread_bps < 2.0
+                && close_abs_delta_ms <= 700
+            {
+                return Some("doge_multi_last_fast_midspread_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps < 10.0
+                && (1_000..=1_800).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_multi_last_midspread_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 >= 15.0
+                && weighted_direction_margin_bps < 23.0
+                && close_abs_delta_ms >= 2_500
+            {
+                return Some("doge_multi_last_stale_midspread_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 >= 23.0
+                && close_abs_delta_ms >= 4_500
+            {
+                return Some("doge_multi_last_very_stale_midspread_high_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && close_abs_delta_ms >= 1_800
+            {
+                return Some("doge_three_last_stale_highspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 < 3.5
+                && (1_800..2_148).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_multi_last_stale_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 0.8
+                && hit.source_spread_bps < 1.1
+                && weighted_direction_margin_bps + 1e-9 < 0.5
+                && (1_800..=3_000).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_three_last_stale_tightspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 0.8
+                && hit.source_spread_bps < 1.1
+                && weighted_direction_margin_bps + 1e-9 < 0.2
+                && (2_000..=2_500).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_two_bybit_coinbase_last_stale_tightspread_no_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 2.0
+                && weighted_direction_margin_bps + 1e-9 < 3.5
+                && (2_148..=3_000).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_multi_last_stale_midspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+                && close_abs_delta_ms >= 3_000
+            {
+                return Some("doge_three_last_stale_midspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 2.2
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && close_abs_delta_ms >= 1_500
+            {
+                return Some("doge_two_bybit_okx_last_stale_midspread_near_flat");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.5
+                && hit.source_spread_bps < 3.1
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+                && (2_000..=2_600).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_two_bybit_okx_last_stale_highmidspread_yes_near_flat");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 2.2
+                && weighted_direction_margin_bps + 1e-9 < 0.3
+                && (2_500..=3_000).contains(&close_abs_delta_ms)
+            {
+                return Some("doge_two_bybit_okx_last_stale_midspread_no_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 0.8
+                && hit.source_spread_bps < 1.1
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+                && close_abs_delta_ms >= 2_500
+            {
+                return Some("doge_two_bybit_okx_last_very_stale_tight_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+            {
+                return Some("doge_single_last_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 0.8
+                && hit.source_spread_bps < 1.2
+                && weighted_direction_margin_bps + 1e-9 < 0.8
+                && close_abs_delta_ms <= 300
+            {
+                return Some("doge_two_coinbase_okx_last_fast_tightspread_no_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+                && !((!weighted_side_yes && weighted_direction_margin_bps + 1e-9 < 1.25)
+                    || close_abs_delta_ms >= 1_500)
+            {
+                return Some("doge_last_multi_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && !(weighted_side_yes
+                    && weighted_direction_margin_bps + 1e-9 >= 20.0
+                    && hit.source_spread_bps <= 11.0 + 1e-9
+                    && close_abs_delta_ms <= 500)
+            {
+                return Some("doge_last_high_spread");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 0.5
+            {
+                return Some("doge_nearest_near_flat");
+            }
+            if hit.source_subset_name == "doge_binance_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && ((weighted_direction_margin_bps + 1e-9 >= 7.8
+                    && weighted_direction_margin_bps < 8.2)
+                    || weighted_direction_margin_bps + 1e-9 >= 13.5)
+            {
+                return Some("doge_binance_fallback_tail_margin");
+            }
+            if hit.source_subset_name == "doge_binance_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps < 7.8
+            {
+                return Some("doge_binance_fallback_mid_margin");
+            }
+        }
+        "hype/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps + 1e-9 < 8.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_three_after_high_spread_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && hit.source_spread_bps < 2.5 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps < 8.0
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("hype_three_after_mid_spread_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 4
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps + 1e-9 < 8.0
+            {
+                return Some("hype_four_after_high_spread_mid_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 4
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && hit.source_spread_bps < 10.0
+                && weighted_direction_margin_bps + 1e-9 < 8.0
+                && close_abs_delta_ms >= 1_500
+            {
+                return Some("hype_four_drop_binance_stale_highspread_yes_mid_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 4
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps + 1e-9 < 20.0
+            {
+                return Some("hype_four_after_high_spread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 4
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 15.0
+                && weighted_direction_margin_bps + 1e-9 < 5.0
+            {
+                return Some("hype_after_very_high_spread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps + 1e-9 >= 30.0
+                && close_abs_delta_ms >= 900
+            {
+                return Some("hype_after_high_spread_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 30.0
+            {
+                return Some("hype_multi_after_high_spread_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+                && close_abs_delta_ms >= 3_000
+            {
+                return Some("hype_multi_after_stale_wide_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 4
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 4.5
+                && hit.source_spread_bps < 5.5
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+                && (1_000..=1_500).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_four_drop_binance_stale_midspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps <= 4.0 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 >= 40.0
+            {
+                return Some("hype_three_source_stale_spread_fallback");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+            {
+                return Some("hype_single_after_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.2
+            {
+                return Some("hype_three_after_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 2.5
+                && weighted_direction_margin_bps + 1e-9 >= 2.5
+                && weighted_direction_margin_bps < 3.2
+                && (2_500..=3_000).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_stale_tightspread_yes_near_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 2.5
+                && weighted_direction_margin_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps < 2.0
+                && (1_000..=1_200).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_midlag_midspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 4.5
+                && hit.source_spread_bps < 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 2.5
+                && (3_000..=3_500).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_stale_widespread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 2.5
+                && hit.source_spread_bps < 3.1
+                && weighted_direction_margin_bps + 1e-9 >= 13.0
+                && weighted_direction_margin_bps < 15.0
+                && (500..=700).contains(&close_abs_delta_ms)
+            {
+                return Some(
+                    "hype_three_bybit_hyperliquid_okx_midlag_midspread_yes_upper_margin_tail",
+                );
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 7.0
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && close_abs_delta_ms <= 1_500
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_fast_highspread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && hit.source_spread_bps < 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 20.0
+                && weighted_direction_margin_bps < 25.0
+                && close_abs_delta_ms <= 100
+            {
+                return Some(
+                    "hype_three_bybit_hyperliquid_okx_fast_highspread_yes_far_margin_tail",
+                );
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 7.0
+                && weighted_direction_margin_bps + 1e-9 >= 20.0
+                && weighted_direction_margin_bps < 25.0
+                && (500..=700).contains(&close_abs_delta_ms)
+            {
+                return Some(
+                    "hype_three_bybit_hyperliquid_okx_fast_highspread_yes_midlag_far_margin_tail",
+                );
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && hit.source_spread_bps < 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps < 5.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_fast_midspread_no_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 2.5
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 < 2.5
+                && close_abs_delta_ms <= 600
+            {
+                return Some("hype_two_hyperliquid_okx_yes_midspread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 0.7
+                && hit.source_spread_bps < 1.0
+                && weighted_direction_margin_bps + 1e-9 >= 1.8
+                && weighted_direction_margin_bps < 2.4
+                && (700..=1_200).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_hyperliquid_okx_yes_tightspread_midlag_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 1.5 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 1.8
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("hype_two_after_mid_spread_fast_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 2.6
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_midspread_yes_near_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.0
+                && weighted_direction_margin_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps < 5.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_midspread_yes_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && hit.source_spread_bps < 3.5
+                && weighted_direction_margin_bps + 1e-9 < 2.2
+                && (500..=800).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_bybit_hyperliquid_midlag_widespread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 1.5
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps < 12.5
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_tightspread_yes_upper_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && hit.source_spread_bps < 4.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_direction_margin_bps < 3.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_widespread_yes_near_margin");
+            }
+            if hit.source_subset_name == "drop_binance"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit.source_spread_bps + 1e-9 >= 3.5
+                && hit.source_spread_bps < 4.0
+                && weighted_direction_margin_bps + 1e-9 >= 2.5
+                && weighted_direction_margin_bps < 3.0
+                && (500..=900).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_bybit_hyperliquid_midlag_widespread_yes_near_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.5
+                && hit.source_spread_bps < 4.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 9.0
+                && close_abs_delta_ms <= 100
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_widespread_yes_upper_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 6.0
+                && weighted_direction_margin_bps + 1e-9 >= 3.5
+                && weighted_direction_margin_bps < 4.5
+                && (500..=700).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_bybit_hyperliquid_midlag_highspread_yes_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_subset_name == "drop_binance"
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && hit.source_spread_bps < 4.5
+                && weighted_direction_margin_bps + 1e-9 >= 13.0
+                && weighted_direction_margin_bps < 15.0
+                && (500..=700).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_bybit_hyperliquid_midlag_midspread_yes_upper_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps + 1e-9 < 4.0
+            {
+                return Some("hype_three_coinbase_hl_okx_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit.source_spread_bps + 1e-9 >= 4.0
+                && hit.source_spread_bps < 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 40.0
+                && close_abs_delta_ms >= 800
+            {
+                return Some("hype_three_coinbase_hl_okx_no_midspread_far_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 3
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && hit.source_spread_bps < 5.0
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps < 15.0
+                && close_abs_delta_ms >= 1_500
+            {
+                return Some("hype_three_bybit_hyperliquid_okx_stale_midspread_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 10.0
+                && (close_abs_delta_ms < 200
+                    || (!weighted_side_yes
+                        && hit
+                            .source_contributions
+                            .iter()
+                            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                        && hit
+                            .source_contributions
+                            .iter()
+                            .any(|c| c.source == LocalPriceSource::Okx)
+                        && !hit
+                            .source_contributions
+                            .iter()
+                            .any(|c| c.source == LocalPriceSource::Bybit)
+                        && !hit
+                            .source_contributions
+                            .iter()
+                            .any(|c| c.source == LocalPriceSource::Coinbase)
+                        && weighted_direction_margin_bps + 1e-9 < 1.5))
+            {
+                return Some("hype_two_after_very_high_spread");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 6.0
+                && weighted_direction_margin_bps + 1e-9 < 4.5
+                && close_abs_delta_ms <= 1_000
+            {
+                return Some("hype_two_hyperliquid_okx_yes_widespread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 6.5
+                && weighted_direction_margin_bps + 1e-9 >= 15.0
+                && weighted_direction_margin_bps < 22.0
+                && close_abs_delta_ms <= 700
+            {
+                return Some("hype_two_hyperliquid_okx_yes_highspread_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && !hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 6.5
+                && weighted_direction_margin_bps + 1e-9 >= 15.0
+                && weighted_direction_margin_bps < 22.0
+                && close_abs_delta_ms <= 700
+            {
+                return Some("hype_two_hyperliquid_okx_no_highspread_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 6.0
+                && (weighted_direction_margin_bps + 1e-9 >= 30.0
+                    || (weighted_direction_margin_bps + 1e-9 >= 16.0
+                        && hit.source_spread_bps < 6.5))
+                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 >= 40.0)
+            {
+                return Some("hype_two_after_high_spread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit.source_spread_bps + 1e-9 >= 6.0
+                && hit.source_spread_bps < 7.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 9.0
+                && close_abs_delta_ms <= 200
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_highspread_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit.source_spread_bps + 1e-9 >= 5.5
+                && hit.source_spread_bps < 8.0
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 10.0
+                && (800..=1_200).contains(&close_abs_delta_ms)
+            {
+                return Some("hype_two_bybit_hyperliquid_stale_highspread_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Bybit)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Hyperliquid)
+                && hit.source_spread_bps + 1e-9 >= 5.0
+                && hit.source_spread_bps < 6.0
+                && weighted_direction_margin_bps + 1e-9 >= 20.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("hype_two_bybit_hyperliquid_fast_highspread_high_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 6.0
+                && hit.source_spread_bps < 8.0
+                && weighted_direction_margin_bps + 1e-9 < 3.5
+            {
+                return Some("hype_after_high_spread_yes_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count >= 3
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit.source_spread_bps + 1e-9 >= 6.0
+                && hit.source_spread_bps < 8.0
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && weighted_direction_margin_bps < 20.0 + 1e-9
+            {
+                return Some("hype_three_after_high_spread_yes_mid_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps <= 1.0 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 2.0
+            {
+                return Some("hype_two_after_tight_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps + 1e-9 >= 1.5
+                && weighted_direction_margin_bps + 1e-9 < 1.8
+                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 >= 1.25)
+            {
+                return Some("hype_two_after_wide_spread_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.close_exact_sources == 0
+                && ((hit.source_count >= 2
+                    && hit.source_spread_bps <= 1.0 + 1e-9
+                    && weighted_direction_margin_bps + 1e-9 < 1.8)
+                    || (hit.source_count == 1 && weighted_direction_margin_bps + 1e-9 < 2.0))
+            {
+                return Some("hype_nearest_near_flat");
+            }
+        }
+        "eth/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+                && (weighted_side_yes || weighted_direction_margin_bps + 1e-9 < 0.63)
+            {
+                return Some("eth_single_last_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && !weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 0.63
+                && weighted_direction_margin_bps < 0.8
+                && (3_000..=5_000).contains(&close_abs_delta_ms)
+            {
+                return Some("eth_single_coinbase_no_very_stale_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 1.0
+                && weighted_direction_margin_bps < 2.2
+                && close_abs_delta_ms <= 500
+            {
+                return Some("eth_single_coinbase_yes_fast_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 1.0
+                && weighted_direction_margin_bps < 2.5
+                && close_abs_delta_ms >= 3_000
+            {
+                return Some("eth_single_coinbase_yes_stale_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 1.0
+                && weighted_direction_margin_bps < 1.3
+                && (500..3_000).contains(&close_abs_delta_ms)
+            {
+                return Some("eth_single_coinbase_yes_mid_stale_near_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_subset_name == "only_coinbase"
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 2.5
+                && weighted_direction_margin_bps < 3.0
+                && close_abs_delta_ms <= 150
+            {
+                return Some("eth_single_coinbase_yes_fast_upper_mid_margin");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_subset_name == "only_coinbase"
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Coinbase)
+                && weighted_side_yes
+                && weighted_direction_margin_bps + 1e-9 >= 8.0
+                && weighted_direction_margin_bps < 13.0
+                && close_abs_delta_ms <= 50
+            {
+                return Some("eth_single_coinbase_yes_fast_mid_margin_tail");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps <= 1.5 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 0.35
+            {
+                return Some("eth_two_last_near_flat");
+            }
+            if hit.rule == LocalBoundaryCloseRule::LastBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && hit.source_spread_bps <= 1.1 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 0.2
+            {
+                return Some("eth_two_last_tight_near_flat");
+            }
+        }
+        "sol/usd" => {
+            let weighted_side_yes = hit.close_price >= rtds_open;
+            if hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_direction_margin_bps + 1e-9 < 1.0
+                && (weighted_side_yes || hit.source_spread_bps < 2.5)
+            {
+                return Some("sol_after_near_flat");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 1.0
+                && hit.source_spread_bps < 2.5
+                && weighted_direction_margin_bps + 1e-9 >= 1.45
+                && weighted_direction_margin_bps < 1.7
+                && (500..=900).contains(&close_abs_delta_ms)
+            {
+                return Some("sol_okx_coinbase_no_midlag_midmargin_near_flat");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && hit.source_spread_bps < 4.2
+                && weighted_direction_margin_bps + 1e-9 >= 4.0
+                && weighted_direction_margin_bps < 5.0
+                && close_abs_delta_ms <= 250
+            {
+                return Some("sol_okx_coinbase_no_fast_midspread_midmargin_tail");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 7.0
+                && weighted_direction_margin_bps + 1e-9 < 1.5
+                && close_abs_delta_ms <= 600
+            {
+                return Some("sol_okx_coinbase_high_spread_yes_near_flat");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.0
+                && weighted_direction_margin_bps + 1e-9 < 3.0
+                && close_abs_delta_ms <= 100
+            {
+                return Some("sol_okx_coinbase_mid_spread_yes_fast_near_flat");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps < 3.0
+                && weighted_direction_margin_bps + 1e-9 < 1.6
+                && close_abs_delta_ms > 100
+                && close_abs_delta_ms <= 500
+            {
+                return Some("sol_okx_coinbase_mid_spread_yes_fastish_near_flat");
+            }
+            if hit.source_subset_name == "only_okx_coinbase"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && !weighted_side_yes
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Coinbase)
+                && hit
+                    .source_contributions
+                    .iter()
+                    .any(|c| c.source == LocalPriceSource::Okx)
+                && hit.source_spread_bps + 1e-9 >= 3.0
+                && weighted_direction_margin_bps + 1e-9 >= 10.0
+                && close_abs_delta_ms <= 500
+            {
+                return Some("sol_okx_coinbase_no_fast_high_spread_tail");
+            }
+            if hit.source_subset_name == "sol_okx_missing_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && weighted_side_yes
+                && hit
+                    .source_contributions
+                    .first()
+                    .is_some_and(|contribution| contribution.source == LocalPriceSource::Okx)
+                && weighted_direction_margin_bps + 1e-9 >= 9.0
+                && weighted_direction_margin_bps < 10.5
+                && close_abs_delta_ms >= 2_000
+            {
+                return Some("sol_okx_missing_stale_borderline_tail");
+            }
+            if close_only_filter_reason == Some("preclose_near_flat")
+                && close_only_direction_margin_bps
+                    .map(|v| v + 1e-9 < 0.2)
+                    .unwrap_or(false)
+                && hit.source_count == 2
+                && hit.source_spread_bps <= 0.1 + 1e-9
+            {
+                return Some("sol_close_only_preclose_near_flat");
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+pub fn local_boundary_weighted_candidate_allowed_for_policy(
+    symbol: &str,
+    close_only_filter_reason: Option<&'static str>,
+    close_only_direction_margin_bps: Option<f64>,
+    round_end_ts: u64,
+    rtds_open: f64,
+    hit: &LocalBoundaryShadowHit,
+) -> bool {
+    local_boundary_weighted_candidate_filter_reason_for_policy(
+        symbol,
+        close_only_filter_reason,
+        close_only_direction_margin_bps,
+        round_end_ts,
+        rtds_open,
+        hit,
+    )
+    .is_none()
+}
+
+pub fn local_boundary_symbol_router_prefers_weighted_primary(symbol: &str) -> bool {
+    let _ = symbol;
+    true
+}
+
+pub fn local_boundary_symbol_router_allows_close_only_fallback(
+    symbol: &str,
+    hit: &LocalCloseOnlyAggHit,
+    round_end_ts: u64,
+    rtds_open: f64,
+) -> bool {
+    if symbol != "hype/usd" {
+        return false;
+    }
+    let direction_margin_bps =
+        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_only_side_yes = hit.close_price >= rtds_open;
+    if hit.source_count == 1 && hit.close_exact_sources == 0 {
+        let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
+        if direction_margin_bps + 1e-9 < 2.6
+            && !(close_only_side_yes && direction_margin_bps + 1e-9 < 2.5)
+        {
+            return false;
+        }
+        if close_only_side_yes && direction_margin_bps + 1e-9 >= 3.5 && direction_margin_bps < 5.5 {
+            return false;
+        }
+        if close_only_side_yes && direction_margin_bps + 1e-9 < 7.0 && close_abs_delta_ms >= 500 {
+            return false;
+        }
+        let close_only_single_hyperliquid = hit
+            .source_contributions
+            .first()
+            .is_some_and(|contribution| contribution.source == LocalPriceSource::Hyperliquid);
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 7.0
+            && direction_margin_bps < 13.0
+            && close_abs_delta_ms <= 500
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 6.5
+            && direction_margin_bps < 7.0
+            && (300..=500).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 5.5
+            && direction_margin_bps < 6.0
+            && close_abs_delta_ms <= 150
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 15.0
+            && direction_margin_bps < 17.0
+            && close_abs_delta_ms <= 200
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 7.5
+            && direction_margin_bps < 8.2
+            && (840..=950).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 5.0
+            && direction_margin_bps < 20.0
+            && (650..=850).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 10.0
+            && direction_margin_bps < 13.0
+            && (950..=1_100).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 3.0
+            && direction_margin_bps < 4.0
+            && close_abs_delta_ms <= 150
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 2.0
+            && direction_margin_bps < 2.5
+            && close_abs_delta_ms <= 100
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 6.0
+            && direction_margin_bps < 7.0
+            && (200..=500).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 5.0
+            && direction_margin_bps < 6.0
+            && (300..=500).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 10.0
+            && direction_margin_bps < 11.5
+            && (350..=500).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 7.0
+            && direction_margin_bps < 8.0
+            && close_abs_delta_ms <= 300
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 4.0
+            && direction_margin_bps < 10.0
+            && (650..720).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 10.0
+            && direction_margin_bps < 16.0
+            && (720..850).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+        if close_only_single_hyperliquid
+            && !close_only_side_yes
+            && direction_margin_bps + 1e-9 >= 17.0
+            && direction_margin_bps < 19.0
+            && (700..=750).contains(&close_abs_delta_ms)
+        {
+            return false;
+        }
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit.source_spread_bps + 1e-9 >= 1.0
+        && hit.source_spread_bps < 1.5
+        && direction_margin_bps + 1e-9 >= 7.0
+        && direction_margin_bps < 10.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 500
+    {
+        return false;
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && direction_margin_bps + 1e-9 < 5.0
+    {
+        return false;
+    }
+    if hit.source_count == 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Bybit)
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+        && hit.source_spread_bps + 1e-9 >= 0.5
+        && hit.source_spread_bps < 1.0
+        && direction_margin_bps + 1e-9 >= 5.5
+        && direction_margin_bps < 7.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_500
+    {
+        return false;
+    }
+    if hit.source_count == 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Bybit)
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+        && hit.source_spread_bps + 1e-9 >= 1.0
+        && hit.source_spread_bps < 2.5
+        && direction_margin_bps + 1e-9 >= 5.0
+        && direction_margin_bps < 7.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
+    {
+        return false;
+    }
+    if hit.source_count == 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Bybit)
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+        && hit.source_spread_bps + 1e-9 >= 1.0
+        && hit.source_spread_bps < 1.5
+        && direction_margin_bps + 1e-9 >= 20.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_000
+    {
+        return false;
+    }
+    if hit.source_count == 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Bybit)
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+        && hit.source_spread_bps < 0.5
+        && direction_margin_bps + 1e-9 >= 30.0
+        && direction_margin_bps < 40.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_500
+    {
+        return false;
+    }
+    if hit.source_count == 2
+        && hit.close_exact_sources == 0
+        && close_only_side_yes
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Bybit)
+        && hit
+            .source_contributions
+            .iter()
+            .any(|c| c.source == LocalPriceSource::Hyperliquid)
+        && hit.source_spread_bps + 1e-9 >= 0.5
+        && hit.source_spread_bps < 0.7
+        && direction_margin_bps + 1e-9 >= 12.0
+        && direction_margin_bps < 15.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 2_000
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 2_500
+    {
+        return false;
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && hit.source_spread_bps <= 1.0 + 1e-9
+        && direction_margin_bps + 1e-9 >= 40.0
+    {
+        return false;
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && hit.source_spread_bps + 1e-9 >= 1.5
+        && direction_margin_bps + 1e-9 >= 8.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) <= 1_000
+    {
+        return false;
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && hit.source_spread_bps + 1e-9 >= 2.0
+        && hit.source_spread_bps < 4.0 + 1e-9
+        && direction_margin_bps + 1e-9 >= 40.0
+        && hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000)) >= 1_000
+    {
+        return false;
+    }
+    if hit.source_count >= 2
+        && hit.close_exact_sources == 0
+        && hit.source_spread_bps + 1e-9 >= 4.0
+        && direction_margin_bps + 1e-9 >= 8.0
+    {
+        return false;
+    }
+    true
+}
+
+pub fn local_boundary_symbol_router_source_fallback_allowed(
+    symbol: &str,
+    weighted_shadow_hit: Option<&LocalBoundaryShadowHit>,
+    weighted_shadow_filter_reason: Option<&'static str>,
+    hit: &LocalBoundaryShadowHit,
+    round_end_ts: u64,
+    rtds_open: f64,
+) -> bool {
+    if weighted_shadow_hit.is_some()
+        && !local_boundary_symbol_router_source_fallback_rescues_filtered(
+            symbol,
+            weighted_shadow_filter_reason,
+            hit,
+        )
+    {
+        return false;
+    }
+    let direction_margin_bps =
+        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
+    let source_fallback_allowed = match symbol {
+        "bnb/usd" => {
+            let fallback_side_yes = hit.close_price >= rtds_open;
+            (hit.source_subset_name == "bnb_okx_no_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && !fallback_side_yes
+                && direction_margin_bps + 1e-9 >= 4.0
+                && direction_margin_bps < 7.0 + 1e-9)
+                || (hit.source_subset_name == "bnb_okx_fallback"
+                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                    && hit.source_count == 1
+                    && hit.close_exact_sources == 0
+                    && direction_margin_bps + 1e-9 >= 7.0
+                    && direction_margin_bps < 8.0 + 1e-9)
+        }
+        "doge/usd" => {
+            hit.source_subset_name == "doge_binance_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && direction_margin_bps + 1e-9 >= 5.0
+                && direction_margin_bps <= 15.0 + 1e-9
+                && close_abs_delta_ms <= 4_000
+        }
+        "eth/usd" => {
+            hit.source_subset_name == "eth_binance_missing_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && direction_margin_bps + 1e-9 >= 3.0
+                && close_abs_delta_ms <= 2_500
+        }
+        "hype/usd" => {
+            hit.source_subset_name == "hype_hyperliquid_fallback"
+                && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                && hit.source_count == 1
+                && hit.close_exact_sources == 0
+                && direction_margin_bps + 1e-9 >= 4.0
+                && direction_margin_bps < 5.0 + 1e-9
+                && close_abs_delta_ms <= 900
+        }
+        "sol/usd" => {
+            (hit.source_subset_name == "sol_binance_coinbase_fallback"
+                && hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && hit.source_count == 2
+                && hit.close_exact_sources == 0
+                && direction_margin_bps + 1e-9 >= 2.0
+                && hit.source_spread_bps <= 7.0 + 1e-9)
+                || (hit.source_subset_name == "sol_coinbase_fallback"
+                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                    && hit.source_count == 1
+                    && hit.close_exact_sources == 0
+                    && direction_margin_bps + 1e-9 >= 5.0)
+                || (hit.source_subset_name == "sol_binance_fallback"
+                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                    && hit.source_count == 1
+                    && hit.close_exact_sources == 0
+                    && direction_margin_bps + 1e-9 >= 12.0
+                    && close_abs_delta_ms <= 2_500)
+                || (hit.source_subset_name == "sol_okx_missing_fallback"
+                    && hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+                    && hit.source_count == 1
+                    && hit.close_exact_sources == 0
+                    && direction_margin_bps + 1e-9 >= 4.0
+                    && close_abs_delta_ms <= 4_000)
+        }
+        _ => false,
+    };
+    if !source_fallback_allowed {
+        return false;
+    }
+    local_boundary_weighted_candidate_filter_reason_for_policy(
+        symbol,
+        None,
+        None,
+        round_end_ts,
+        rtds_open,
+        hit,
+    )
+    .is_none()
+}
+
+fn local_boundary_symbol_router_source_fallback_rescues_filtered(
+    symbol: &str,
+    weighted_shadow_filter_reason: Option<&'static str>,
+    hit: &LocalBoundaryShadowHit,
+) -> bool {
+    let Some(reason) = weighted_shadow_filter_reason else {
+        return false;
+    };
+    match symbol {
+        "bnb/usd" => {
+            hit.source_subset_name == "bnb_okx_no_fallback"
+                && matches!(
+                    reason,
+                    "bnb_no_near_flat"
+                        | "bnb_single_binance_no_fast_tail"
+                        | "bnb_two_no_midspread_near_flat"
+                )
+        }
+        "doge/usd" => {
+            hit.source_subset_name == "doge_binance_fallback"
+                && matches!(
+                    reason,
+                    "doge_multi_last_fast_midspread_tail"
+                        | "doge_multi_last_midspread_tail"
+                        | "doge_multi_last_stale_near_flat"
+                        | "doge_two_bybit_okx_last_stale_tightspread_high_margin_tail"
+                )
+        }
+        "sol/usd" => {
+            hit.source_subset_name == "sol_binance_fallback" && reason == "sol_after_near_flat"
+        }
+        "hype/usd" => {
+            hit.source_subset_name == "hype_hyperliquid_fallback"
+                && reason == "hype_three_after_near_flat"
+        }
+        _ => false,
+    }
+}
+
+pub fn local_boundary_hybrid_prefers_weighted_over_close_only(
+    symbol: &str,
+    close_only_hit: &LocalCloseOnlyAggHit,
+    weighted_hit: &LocalBoundaryShadowHit,
+    rtds_open: f64,
+) -> bool {
+    let close_only_direction_margin_bps =
+        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let weighted_direction_margin_bps =
+        ((weighted_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_only_side = if close_only_hit.close_price >= rtds_open {
+        Side::Yes
+    } else {
+        Side::No
+    };
+    let weighted_side = if weighted_hit.close_price >= rtds_open {
+        Side::Yes
+    } else {
+        Side::No
+    };
+    if close_only_side == weighted_side {
+        return false;
+    }
+    match symbol {
+        "bnb/usd" => {
+            (close_only_hit.source_count == 1
+                && close_only_hit.close_exact_sources == 0
+                && close_only_direction_margin_bps + 1e-9 < 2.5
+                && weighted_hit.source_count >= 2
+                && weighted_direction_margin_bps + 1e-9 >= 1.25)
+                || (close_only_hit.source_count == 1
+                    && close_only_hit.close_exact_sources == 0
+                    && close_only_direction_margin_bps + 1e-9 < 3.6
+                    && weighted_hit.source_count >= 2
+                    && weighted_hit.source_spread_bps + 1e-9 >= 3.5
+                    && weighted_direction_margin_bps + 1e-9 >= 0.65)
+        }
+        "eth/usd" => {
+            close_only_hit.close_exact_sources == 0
+                && weighted_hit.source_count >= 2
+                && weighted_hit.rule == LocalBoundaryCloseRule::LastBefore
+        }
+        "doge/usd" => close_only_hit.close_exact_sources == 0 && weighted_hit.source_count >= 2,
+        "sol/usd" => {
+            close_only_hit.close_exact_sources == 0
+                && weighted_hit.source_count >= 2
+                && weighted_hit.rule == LocalBoundaryCloseRule::AfterThenBefore
+        }
+        _ => false,
+    }
+}
+
+pub fn local_boundary_hybrid_prefers_filtered_close_only_over_weighted(
+    symbol: &str,
+    close_only_filter_reason: Option<&'static str>,
+    close_only_hit: &LocalCloseOnlyAggHit,
+    weighted_hit: &LocalBoundaryShadowHit,
+    rtds_open: f64,
+) -> bool {
+    let close_only_direction_margin_bps =
+        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let weighted_direction_margin_bps =
+        ((weighted_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_only_side = if close_only_hit.close_price >= rtds_open {
+        Side::Yes
+    } else {
+        Side::No
+    };
+    let weighted_side = if weighted_hit.close_price >= rtds_open {
+        Side::Yes
+    } else {
+        Side::No
+    };
+    if close_only_side == weighted_side {
+        return false;
+    }
+    let source_agreement = local_close_only_source_agreement(close_only_hit, rtds_open);
+    match symbol {
+        "doge/usd" => {
+            (close_only_filter_reason == Some("low_confidence")
+                && close_only_hit.source_count >= 2
+                && close_only_hit.close_exact_sources == 0
+                && source_agreement >= 1.0 - 1e-9
+                && close_only_hit.source_spread_bps <= 0.5 + 1e-9
+                && close_only_direction_margin_bps + 1e-9 >= 2.0
+                && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && weighted_hit.source_count >= 2
+                && weighted_hit.close_exact_sources == 0
+                && weighted_hit.source_spread_bps <= 0.1 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 0.5)
+                || (close_only_filter_reason == Some("preclose_near_flat")
+                    && close_only_hit.source_count >= 3
+                    && close_only_hit.close_exact_sources == 0
+                    && source_agreement >= 1.0 - 1e-9
+                    && close_only_hit.source_spread_bps <= 3.1 + 1e-9
+                    && close_only_direction_margin_bps + 1e-9 >= 1.5
+                    && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
+                    && weighted_hit.source_count >= 2
+                    && weighted_hit.close_exact_sources == 0
+                    && weighted_hit.source_spread_bps <= 0.1 + 1e-9
+                    && weighted_direction_margin_bps + 1e-9 < 0.5)
+        }
+        "hype/usd" => {
+            close_only_filter_reason == Some("low_confidence")
+                && close_only_hit.source_count >= 2
+                && close_only_hit.close_exact_sources == 0
+                && source_agreement >= 1.0 - 1e-9
+                && close_only_hit.source_spread_bps <= 3.0 + 1e-9
+                && close_only_direction_margin_bps + 1e-9 >= 2.3
+                && weighted_hit.rule == LocalBoundaryCloseRule::NearestAbs
+                && weighted_hit.source_count >= 2
+                && weighted_hit.close_exact_sources == 0
+                && weighted_hit.source_spread_bps <= 1.0 + 1e-9
+                && weighted_direction_margin_bps + 1e-9 < 1.8
+        }
+        _ => false,
+    }
+}
+
+pub fn local_boundary_filtered_close_only_allowed_without_weighted(
+    symbol: &str,
+    close_only_filter_reason: Option<&'static str>,
+    close_only_hit: &LocalCloseOnlyAggHit,
+    round_end_ts: u64,
+    rtds_open: f64,
+) -> bool {
+    if symbol != "hype/usd"
+        || close_only_filter_reason != Some("hype_close_only_single_far_margin")
+        || close_only_hit.source_count != 1
+        || close_only_hit.close_exact_sources != 0
+    {
+        return false;
+    }
+    let close_only_single_hyperliquid = close_only_hit
+        .source_contributions
+        .first()
+        .is_some_and(|contribution| contribution.source == LocalPriceSource::Hyperliquid);
+    if !close_only_single_hyperliquid {
+        return false;
+    }
+    let direction_margin_bps =
+        ((close_only_hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_abs_delta_ms = close_only_hit
+        .close_ts_ms
+        .abs_diff(round_end_ts.saturating_mul(1_000));
+    close_abs_delta_ms <= 500 && direction_margin_bps + 1e-9 >= 21.0 && direction_margin_bps < 24.0
+}
+
+pub fn local_boundary_symbol_router_missing_filter_reason(
+    symbol: &str,
+    close_only_filter_reason: Option<&'static str>,
+    close_only_direction_margin_bps: Option<f64>,
+    close_only_hit: &LocalCloseOnlyAggHit,
+) -> Option<&'static str> {
+    if symbol == "sol/usd"
+        && close_only_filter_reason == Some("preclose_near_flat")
+        && close_only_direction_margin_bps
+            .map(|margin_bps| margin_bps + 1e-9 < 1.0)
+            .unwrap_or(false)
+        && close_only_hit.source_count >= 2
+        && close_only_hit.close_exact_sources == 0
+        && close_only_hit.source_spread_bps <= 5.0 + 1e-9
+    {
+        return Some("sol_missing_preclose_near_flat");
+    }
+    None
+}
+
+pub fn run_local_boundary_shadow_policy(
+    symbol: &str,
+    spec: LocalBoundaryShadowPolicySpec,
+    tapes: &HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
+    end_ms: u64,
+    cfg: &PriceAggConfig,
+) -> LocalBoundaryShadowOutcome {
+    let mut contributions = Vec::new();
+    for source in spec.allowed_sources {
+        let Some(tape) = tapes.get(source) else {
+            continue;
+        };
+        let Some((point, raw_close_price)) = pick_local_boundary_tape_close_point(
+            tape,
+            end_ms,
+            LOCAL_BOUNDARY_POLICY_PRE_MS,
+            LOCAL_BOUNDARY_POLICY_POST_MS,
+            spec.rule,
+        ) else {
+            continue;
+        };
+        contributions.push(LocalCloseSourceContribution {
+            source: *source,
+            raw_close_price,
+            adjusted_close_price: point.price,
+            close_ts_ms: point.ts_ms,
+            close_exact: point.exact,
+            close_abs_delta_ms: point.abs_delta_ms,
+            close_pick: point.source,
+        });
+    }
+
+    if contributions.len() < spec.min_sources {
+        return LocalBoundaryShadowOutcome {
+            policy_name: spec.policy_name,
+            source_subset_name: spec.source_subset_name,
+            rule: spec.rule,
+            min_sources: spec.min_sources,
+            hit: None,
+        };
+    }
+
+    let close_values = contributions
+        .iter()
+        .map(|src| src.adjusted_close_price)
+        .collect::<Vec<_>>();
+    let close_timestamps = contributions
+        .iter()
+        .map(|src| src.close_ts_ms)
+        .collect::<Vec<_>>();
+    let num = contributions
+        .iter()
+        .map(|src| {
+            local_boundary_policy_source_weight(spec.policy_name, symbol, src.source, cfg)
+                * src.adjusted_close_price
+        })
+        .sum::<f64>();
+    let den = contributions
+        .iter()
+        .map(|src| local_boundary_policy_source_weight(spec.policy_name, symbol, src.source, cfg))
+        .sum::<f64>();
+    let close_price = if den > 0.0 {
+        num / den
+    } else {
+        robust_center(close_values.clone()).unwrap_or_default()
+    };
+    let close_ts_ms = median_u64(close_timestamps).unwrap_or(end_ms);
+    let source_spread_bps = spread_bps(&close_values).unwrap_or(0.0);
+    let close_exact_sources = contributions.iter().filter(|src| src.close_exact).count();
+    LocalBoundaryShadowOutcome {
+        policy_name: spec.policy_name,
+        source_subset_name: spec.source_subset_name,
+        rule: spec.rule,
+        min_sources: spec.min_sources,
+        hit: Some(LocalBoundaryShadowHit {
+            policy_name: spec.policy_name,
+            source_subset_name: spec.source_subset_name,
+            rule: spec.rule,
+            min_sources: spec.min_sources,
+            close_price,
+            close_ts_ms,
+            source_count: contributions.len(),
+            source_spread_bps,
+            close_exact_sources,
+            source_contributions: contributions,
+        }),
+    }
+}
+
+pub fn local_boundary_shadow_candidate_ready(
+    symbol: &str,
+    tapes: &HashMap<LocalPriceSource, LocalSourceBoundaryTapeState>,
+    end_ms: u64,
+    cfg: &PriceAggConfig,
+) -> bool {
+    let weighted = run_local_boundary_shadow_policy(
+        symbol,
+        local_boundary_policy_specs_weighted(symbol),
+        tapes,
+        end_ms,
+        cfg,
+    );
+    if weighted.hit.is_some() {
+        return true;
+    }
+    local_boundary_symbol_router_fallback_policy_specs(symbol)
+        .into_iter()
+        .any(|spec| {
+            run_local_boundary_shadow_policy(symbol, spec, tapes, end_ms, cfg)
+                .hit
+                .is_some()
+        })
+}
+
+fn local_close_only_contribution_weight(
+    contribution: &LocalCloseSourceContribution,
+    round_end_ts: u64,
+    cfg: &PriceAggConfig,
+) -> f64 {
+    cfg.contribution_weight(contribution, round_end_ts.saturating_mul(1_000))
+}
+
+fn local_close_only_apply_raw_contributions(
+    hit: &mut LocalCloseOnlyAggHit,
+    round_end_ts: u64,
+    cfg: &PriceAggConfig,
+) -> bool {
+    let weighted = hit
+        .source_contributions
+        .iter()
+        .map(|contribution| {
+            (
+                contribution.raw_close_price,
+                local_close_only_contribution_weight(contribution, round_end_ts, cfg),
+            )
+        })
+        .collect::<Vec<_>>();
+    let Some(raw_close) = weighted_mean(&weighted) else {
+        return false;
+    };
+    let raw_values = hit
+        .source_contributions
+        .iter()
+        .map(|contribution| contribution.raw_close_price)
+        .collect::<Vec<_>>();
+    let raw_spread_bps = spread_bps(&raw_values).unwrap_or(0.0);
+    if raw_spread_bps > cfg.max_source_spread_bps + 1e-9 {
+        return false;
+    }
+    hit.close_price = raw_close;
+    hit.source_spread_bps = raw_spread_bps;
+    for contribution in &mut hit.source_contributions {
+        contribution.adjusted_close_price = contribution.raw_close_price;
+    }
+    true
+}
+
+pub fn local_boundary_maybe_debias_hype_close_only(
+    symbol: &str,
+    hit: &mut LocalCloseOnlyAggHit,
+    round_end_ts: u64,
+    rtds_open: f64,
+    cfg: &PriceAggConfig,
+) {
+    if symbol != "hype/usd" || !rtds_open.is_finite() || rtds_open <= 0.0 {
+        return;
+    }
+    if hit.source_count == 1 && hit.close_exact_sources == 0 {
+        let Some(contribution) = hit.source_contributions.first() else {
+            return;
+        };
+        if contribution.source != LocalPriceSource::Hyperliquid {
+            return;
+        }
+        let adjusted = hit.close_price;
+        let raw = contribution.raw_close_price;
+        if !adjusted.is_finite() || !raw.is_finite() || adjusted < rtds_open || raw < rtds_open {
+            return;
+        }
+        let adjusted_margin_bps =
+            ((adjusted - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+        let raw_margin_bps = ((raw - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+        if adjusted_margin_bps + 1e-9 >= 10.0 && raw_margin_bps + 1e-9 >= 7.0 {
+            let _ = local_close_only_apply_raw_contributions(hit, round_end_ts, cfg);
+        }
+        return;
+    }
+
+    if hit.source_count < 2 || hit.close_exact_sources != 0 {
+        return;
+    }
+    let end_ms = round_end_ts.saturating_mul(1_000);
+    let all_preclose = hit
+        .source_contributions
+        .iter()
+        .all(|contribution| contribution.close_ts_ms < end_ms);
+    let all_postclose = hit
+        .source_contributions
+        .iter()
+        .all(|contribution| contribution.close_ts_ms > end_ms);
+    if !all_preclose && !all_postclose {
+        return;
+    }
+    let raw_weighted = hit
+        .source_contributions
+        .iter()
+        .map(|contribution| {
+            (
+                contribution.raw_close_price,
+                local_close_only_contribution_weight(contribution, round_end_ts, cfg),
+            )
+        })
+        .collect::<Vec<_>>();
+    let Some(raw_close) = weighted_mean(&raw_weighted) else {
+        return;
+    };
+    let adjusted = hit.close_price;
+    if !adjusted.is_finite()
+        || !raw_close.is_finite()
+        || adjusted >= rtds_open
+        || raw_close >= rtds_open
+    {
+        return;
+    }
+    let adjusted_margin_bps =
+        ((adjusted - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    if (all_preclose && (2.0..12.0).contains(&adjusted_margin_bps))
+        || (all_postclose && (4.0..12.0).contains(&adjusted_margin_bps))
+    {
+        let _ = local_close_only_apply_raw_contributions(hit, round_end_ts, cfg);
+    }
+}
+
+pub fn local_boundary_maybe_equalize_xrp_binance_coinbase_fast_no(
+    symbol: &str,
+    hit: &mut LocalBoundaryShadowHit,
+    round_end_ts: u64,
+    rtds_open: f64,
+) {
+    if symbol != "xrp/usd"
+        || hit.policy_name != "boundary_weighted"
+        || hit.source_subset_name != "only_binance_coinbase"
+        || hit.rule != LocalBoundaryCloseRule::NearestAbs
+        || hit.source_count != 2
+        || hit.close_exact_sources != 0
+        || !rtds_open.is_finite()
+        || rtds_open <= 0.0
+        || hit.close_price >= rtds_open
+        || hit.source_spread_bps < 4.0
+    {
+        return;
+    }
+    let direction_margin_bps =
+        ((hit.close_price - rtds_open).abs() / rtds_open.abs().max(1e-12)) * 10_000.0;
+    let close_abs_delta_ms = hit.close_ts_ms.abs_diff(round_end_ts.saturating_mul(1_000));
+    if direction_margin_bps < 6.0 || close_abs_delta_ms > 150 {
+        return;
+    }
+    let Some(binance) = hit
+        .source_contributions
+        .iter()
+        .find(|contribution| contribution.source == LocalPriceSource::Binance)
+    else {
+        return;
+    };
+    let Some(coinbase) = hit
+        .source_contributions
+        .iter()
+        .find(|contribution| contribution.source == LocalPriceSource::Coinbase)
+    else {
+        return;
+    };
+    let end_ms = round_end_ts.saturating_mul(1_000);
+    if binance.close_ts_ms.abs_diff(end_ms) >= coinbase.close_ts_ms.abs_diff(end_ms)
+        || binance.adjusted_close_price <= coinbase.adjusted_close_price
+    {
+        return;
+    }
+    let equalized = (binance.adjusted_close_price + coinbase.adjusted_close_price) / 2.0;
+    if equalized.is_finite() && equalized > 0.0 {
+        hit.close_price = equalized;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const END_MS: u64 = 1_700_000_000_000;
+
+    fn tape(ticks: &[(u64, f64)]) -> LocalSourceBoundaryTapeState {
+        LocalSourceBoundaryTapeState {
+            open_window_ticks: Vec::new(),
+            close_window_ticks: ticks.to_vec(),
+        }
+    }
+
+    fn hype_hit(adjusted: f64, raw: f64) -> LocalCloseOnlyAggHit {
+        LocalCloseOnlyAggHit {
+            close_price: adjusted,
+            close_ts_ms: END_MS - 200,
+            source_count: 1,
+            source_spread_bps: 0.0,
+            close_exact_sources: 0,
+            source_contributions: vec![LocalCloseSourceContribution {
+                source: LocalPriceSource::Hyperliquid,
+                raw_close_price: raw,
+                adjusted_close_price: adjusted,
+                close_ts_ms: END_MS - 200,
+                close_exact: false,
+                close_abs_delta_ms: 200,
+                close_pick: "last_before",
+            }],
+        }
+    }
+
+    #[test]
+    fn shadow_policy_weights_sources_from_config() {
+        let spec = LocalBoundaryShadowPolicySpec {
+            policy_name: "test_policy",
+            source_subset_name: "only_binance_coinbase",
+            rule: LocalBoundaryCloseRule::LastBefore,
+            min_sources: 2,
+            allowed_sources: LOCAL_BOUNDARY_SOURCES_ONLY_BINANCE_COINBASE,
+        };
+        let tapes = HashMap::from([
+            (LocalPriceSource::Binance, tape(&[(END_MS - 100, 100.0)])),
+            (LocalPriceSource::Coinbase, tape(&[(END_MS - 50, 102.0)])),
+        ]);
+        let cfg = PriceAggConfig {
+            weight_binance: 1.0,
+            weight_coinbase: 3.0,
+            ..PriceAggConfig::default()
+        };
+
+        let hit = run_local_boundary_shadow_policy("btc/usd", spec, &tapes, END_MS, &cfg)
+            .hit
+            .expect("two sources inside the close window");
+        assert_eq!(hit.source_count, 2);
+        assert!((hit.close_price - 101.5).abs() < 1e-9);
+
+        let strict = LocalBoundaryShadowPolicySpec {
+            min_sources: 3,
+            ..spec
+        };
+        assert!(
+            run_local_boundary_shadow_policy("btc/usd", strict, &tapes, END_MS, &cfg)
+                .hit
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn shadow_candidate_ready_needs_a_tick_inside_the_close_window() {
+        let cfg = PriceAggConfig::default();
+        assert!(!local_boundary_shadow_candidate_ready(
+            "btc/usd",
+            &HashMap::new(),
+            END_MS,
+            &cfg
+        ));
+
+        let stale = HashMap::from([(
+            LocalPriceSource::Coinbase,
+            tape(&[(END_MS - LOCAL_BOUNDARY_POLICY_PRE_MS - 1, 100.0)]),
+        )]);
+        assert!(!local_boundary_shadow_candidate_ready(
+            "btc/usd", &stale, END_MS, &cfg
+        ));
+
+        let fresh = HashMap::from([(LocalPriceSource::Coinbase, tape(&[(END_MS - 10, 100.0)]))]);
+        assert!(local_boundary_shadow_candidate_ready(
+            "btc/usd", &fresh, END_MS, &cfg
+        ));
+    }
+
+    #[test]
+    fn hype_close_only_debias_reverts_to_raw_close_with_config_weights() {
+        let round_end_ts = END_MS / 1_000;
+        let cfg = PriceAggConfig::default();
+
+        let mut hit = hype_hit(100.2, 100.1);
+        local_boundary_maybe_debias_hype_close_only(
+            "hype/usd",
+            &mut hit,
+            round_end_ts,
+            100.0,
+            &cfg,
+        );
+        assert!((hit.close_price - 100.1).abs() < 1e-9);
+        assert_eq!(hit.source_contributions[0].adjusted_close_price, 100.1);
+
+        let mut other_symbol = hype_hit(100.2, 100.1);
+        local_boundary_maybe_debias_hype_close_only(
+            "btc/usd",
+            &mut other_symbol,
+            round_end_ts,
+            100.0,
+            &cfg,
+        );
+        assert_eq!(other_symbol.close_price, 100.2);
+
+        let muted = PriceAggConfig {
+            weight_hyperliquid: 0.0,
+            ..PriceAggConfig::default()
+        };
+        let mut unweighted = hype_hit(100.2, 100.1);
+        local_boundary_maybe_debias_hype_close_only(
+            "hype/usd",
+            &mut unweighted,
+            round_end_ts,
+            100.0,
+            &muted,
+        );
+        assert_eq!(unweighted.close_price, 100.2);
+    }
+}
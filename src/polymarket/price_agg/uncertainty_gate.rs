@@ -0,0 +1,446 @@
+//! Bucket statistics for the local-agg uncertainty gate.
+//!
+//! The model file (`local_agg_uncertainty_gate_v1`) is produced offline by
+//! `scripts/evaluate_local_agg_uncertainty_gate.py`. The engine can keep it
+//! fresh between refits: every finalized round folds its close error (vs the
+//! RTDS close) into the matching buckets and the result is written to a
+//! separate online file. Offline stats act as a prior that is retired one
+//! sample at a time as online samples arrive. Online snapshots are written by
+//! an [`UncertaintyGateWriter`] thread so training never blocks on disk.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
+use std::thread::JoinHandle;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+pub const UNCERTAINTY_GATE_MODEL_NAME: &str = "local_agg_uncertainty_gate_v1";
+pub const UNCERTAINTY_GATE_QUANTILE_DEFAULT: f64 = 0.95;
+pub const UNCERTAINTY_GATE_ONLINE_WINDOW_DEFAULT: usize = 512;
+
+fn default_quantile() -> f64 {
+    UNCERTAINTY_GATE_QUANTILE_DEFAULT
+}
+
+/// serde_json writes non-finite floats as `null`; read them back as NaN.
+fn f64_or_nan<'de, D: serde::Deserializer<'de>>(de: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(de)?.unwrap_or(f64::NAN))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UncertaintyGateConfig {
+    pub min_samples: usize,
+    pub min_margin_bps: f64,
+    pub max_train_quantile_bps: f64,
+    pub safety_bps: f64,
+    pub max_side_rate: f64,
+    pub max_source_spread_bps: f64,
+    pub max_round_max_margin_bps: f64,
+    #[serde(default)]
+    pub rescue_min_samples: usize,
+    #[serde(default)]
+    pub rescue_max_train_max_bps: f64,
+    /// Quantile behind `q_bps`; older exports omit it (script default 0.95).
+    #[serde(default = "default_quantile")]
+    pub quantile: f64,
+    /// Fields the engine does not read but must write back unchanged.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Online accumulator kept next to a bucket's effective stats.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct OnlineBucketState {
+    pub prior_n: usize,
+    pub prior_side_errors: usize,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub prior_q_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub prior_q95_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub prior_q99_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub prior_mean_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub prior_max_bps: f64,
+    pub n: usize,
+    pub side_errors: usize,
+    pub sum_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub max_bps: f64,
+    /// Most recent online close errors, oldest first (bounded window).
+    pub recent_bps: VecDeque<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncertaintyGateBucket {
+    pub level: String,
+    pub key: Vec<String>,
+    pub n: usize,
+    pub side_errors: usize,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub side_rate: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub q_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub q95_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub q99_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub mean_bps: f64,
+    #[serde(deserialize_with = "f64_or_nan")]
+    pub max_bps: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online: Option<OnlineBucketState>,
+}
+
+/// Linear-interpolated percentile over sorted values (matches the script).
+pub fn percentile(sorted: &[f64], q: f64) -> f64 {
+    match sorted.len() {
+        0 => f64::NAN,
+        1 => sorted[0],
+        len => {
+            let pos = q.clamp(0.0, 1.0) * (len - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = pos.ceil() as usize;
+            let weight = pos - lo as f64;
+            sorted[lo] * (1.0 - weight) + sorted[hi] * weight
+        }
+    }
+}
+
+fn blend(prior: f64, prior_weight: usize, online: f64, online_weight: usize) -> f64 {
+    if prior_weight == 0 || !prior.is_finite() {
+        return online;
+    }
+    if online_weight == 0 || !online.is_finite() {
+        return prior;
+    }
+    (prior * prior_weight as f64 + online * online_weight as f64)
+        / (prior_weight + online_weight) as f64
+}
+
+impl UncertaintyGateBucket {
+    pub fn new(level: &str, key: Vec<String>) -> Self {
+        Self {
+            level: level.to_string(),
+            key,
+            n: 0,
+            side_errors: 0,
+            side_rate: 0.0,
+            q_bps: f64::NAN,
+            q95_bps: f64::NAN,
+            q99_bps: f64::NAN,
+            mean_bps: f64::NAN,
+            max_bps: f64::NAN,
+            online: None,
+        }
+    }
+
+    /// Folds one finalized round into the bucket. Counts stay cumulative;
+    /// quantiles blend the offline prior (weighted by its not yet retired
+    /// samples) with the recent online window.
+    pub fn record(&mut self, close_diff_bps: f64, side_error: bool, quantile: f64, window: usize) {
+        if !close_diff_bps.is_finite() {
+            return;
+        }
+        let online = self.online.get_or_insert_with(|| OnlineBucketState {
+            prior_n: self.n,
+            prior_side_errors: self.side_errors,
+            prior_q_bps: self.q_bps,
+            prior_q95_bps: self.q95_bps,
+            prior_q99_bps: self.q99_bps,
+            prior_mean_bps: self.mean_bps,
+            prior_max_bps: self.max_bps,
+            ..OnlineBucketState::default()
+        });
+        online.n += 1;
+        online.side_errors += usize::from(side_error);
+        online.sum_bps += close_diff_bps;
+        online.max_bps = if online.n == 1 {
+            close_diff_bps
+        } else {
+            online.max_bps.max(close_diff_bps)
+        };
+        online.recent_bps.push_back(close_diff_bps);
+        while online.recent_bps.len() > window.max(1) {
+            online.recent_bps.pop_front();
+        }
+
+        let mut sorted = online.recent_bps.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let prior_weight = online.prior_n.saturating_sub(online.n);
+        let window_weight = sorted.len();
+
+        self.n = online.prior_n + online.n;
+        self.side_errors = online.prior_side_errors + online.side_errors;
+        self.side_rate = self.side_errors as f64 / self.n as f64;
+        self.q_bps = blend(
+            online.prior_q_bps,
+            prior_weight,
+            percentile(&sorted, quantile),
+            window_weight,
+        );
+        self.q95_bps = blend(
+            online.prior_q95_bps,
+            prior_weight,
+            percentile(&sorted, 0.95),
+            window_weight,
+        );
+        self.q99_bps = blend(
+            online.prior_q99_bps,
+            prior_weight,
+            percentile(&sorted, 0.99),
+            window_weight,
+        );
+        let prior_sum = if online.prior_mean_bps.is_finite() {
+            online.prior_mean_bps * online.prior_n as f64
+        } else {
+            0.0
+        };
+        self.mean_bps = (prior_sum + online.sum_bps) / self.n as f64;
+        self.max_bps = if online.prior_max_bps.is_finite() {
+            online.prior_max_bps.max(online.max_bps)
+        } else {
+            online.max_bps
+        };
+    }
+}
+
+/// Where the online file came from; a changed base (offline refit) resets it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnlineModelMeta {
+    pub base_path: PathBuf,
+    pub base_modified_ms: u64,
+    pub rounds: u64,
+    pub updated_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncertaintyGateFile {
+    pub model: String,
+    pub config: UncertaintyGateConfig,
+    pub buckets: Vec<UncertaintyGateBucket>,
+    #[serde(default)]
+    pub rescue_buckets: Vec<UncertaintyGateBucket>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online: Option<OnlineModelMeta>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UncertaintyGateFile {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    /// Atomic write (tmp file + rename).
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        if let Err(err) = fs::rename(&tmp, path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// True when this online file was trained on top of `base_path` as it is now.
+    pub fn continues_base(&self, base_path: &Path) -> bool {
+        let Some(meta) = self.online.as_ref() else {
+            return false;
+        };
+        meta.base_path == base_path && Some(meta.base_modified_ms) == file_modified_ms(base_path)
+    }
+}
+
+/// Background writer for online model snapshots. Snapshots queued while a write
+/// is in flight are coalesced, so only the newest one per burst reaches disk.
+pub struct UncertaintyGateWriter {
+    tx: std_mpsc::Sender<(PathBuf, UncertaintyGateFile)>,
+    handle: JoinHandle<()>,
+}
+
+impl UncertaintyGateWriter {
+    pub fn spawn() -> Self {
+        let (tx, rx) = std_mpsc::channel::<(PathBuf, UncertaintyGateFile)>();
+        let handle = std::thread::spawn(move || {
+            while let Ok(mut next) = rx.recv() {
+                while let Ok(newer) = rx.try_recv() {
+                    next = newer;
+                }
+                let (path, file) = next;
+                if let Err(err) = file.save(&path) {
+                    warn!(
+                        "⚠️ local_price_agg_uncertainty_gate_model_persist_failed | path={} err={}",
+                        path.display(),
+                        err
+                    );
+                }
+            }
+        });
+        Self { tx, handle }
+    }
+
+    pub fn save(&self, path: PathBuf, file: UncertaintyGateFile) {
+        if self.tx.send((path, file)).is_err() {
+            warn!("⚠️ local_price_agg_uncertainty_gate_writer_stopped | snapshot dropped");
+        }
+    }
+
+    /// Flushes queued snapshots and waits for the writer thread to exit.
+    pub fn close(self) {
+        drop(self.tx);
+        let _ = self.handle.join();
+    }
+}
+
+pub fn file_modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offline_bucket() -> UncertaintyGateBucket {
+        UncertaintyGateBucket {
+            n: 4,
+            side_errors: 1,
+            side_rate: 0.25,
+            q_bps: 2.0,
+            q95_bps: 2.5,
+            q99_bps: 3.0,
+            mean_bps: 1.0,
+            max_bps: 3.0,
+            ..UncertaintyGateBucket::new("symbol", vec!["btc/usd".to_string(), "yes".to_string()])
+        }
+    }
+
+    #[test]
+    fn percentile_matches_script_interpolation() {
+        assert!(percentile(&[], 0.5).is_nan());
+        assert_eq!(percentile(&[4.0], 0.95), 4.0);
+        assert!((percentile(&[0.0, 1.0, 2.0, 3.0], 0.5) - 1.5).abs() < 1e-12);
+        assert_eq!(percentile(&[0.0, 1.0, 2.0, 3.0], 1.0), 3.0);
+    }
+
+    #[test]
+    fn record_blends_prior_and_retires_it_with_online_samples() {
+        let mut bucket = offline_bucket();
+        bucket.record(6.0, true, 0.95, 16);
+        assert_eq!(bucket.n, 5);
+        assert_eq!(bucket.side_errors, 2);
+        assert!((bucket.side_rate - 0.4).abs() < 1e-12);
+        // prior weight 3 (4 offline - 1 online), window weight 1
+        assert!((bucket.q_bps - (2.0 * 3.0 + 6.0) / 4.0).abs() < 1e-12);
+        assert!((bucket.mean_bps - (4.0 + 6.0) / 5.0).abs() < 1e-12);
+        assert_eq!(bucket.max_bps, 6.0);
+
+        for _ in 0..4 {
+            bucket.record(0.5, false, 0.95, 16);
+        }
+        // prior fully retired: quantiles come from the online window only
+        let mut window = vec![6.0, 0.5, 0.5, 0.5, 0.5];
+        window.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((bucket.q_bps - percentile(&window, 0.95)).abs() < 1e-12);
+        assert_eq!(bucket.n, 9);
+        assert_eq!(bucket.max_bps, 6.0);
+    }
+
+    #[test]
+    fn record_on_new_bucket_uses_bounded_window() {
+        let mut bucket = UncertaintyGateBucket::new("all", vec!["ALL".to_string()]);
+        for err in [9.0, 1.0, 2.0, 3.0] {
+            bucket.record(err, false, 0.5, 3);
+        }
+        bucket.record(f64::NAN, true, 0.5, 3);
+        assert_eq!(bucket.n, 4);
+        assert_eq!(bucket.side_errors, 0);
+        assert_eq!(bucket.q_bps, 2.0);
+        assert_eq!(bucket.max_bps, 9.0);
+        assert!((bucket.mean_bps - 3.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn gate_file_round_trips_unknown_fields_and_tracks_base() {
+        let dir = std::env::temp_dir().join(format!(
+            "pm_uncertainty_gate_{}_{}",
+            std::process::id(),
+            line!()
+        ));
+        let base = dir.join("model.latest.json");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            &base,
+            r#"{"model":"local_agg_uncertainty_gate_v1","config":{"min_samples":3,"min_margin_bps":1.0,"max_train_quantile_bps":5.0,"safety_bps":0.5,"max_side_rate":0.0,"max_source_spread_bps":12.0,"max_round_max_margin_bps":0.0,"max_train_max_bps":8.0},"bucket_key_order":["symbol"],"buckets":[]}"#,
+        )
+        .unwrap();
+        let mut file = UncertaintyGateFile::load(&base).unwrap();
+        assert_eq!(file.config.quantile, UNCERTAINTY_GATE_QUANTILE_DEFAULT);
+        assert!(!file.continues_base(&base));
+
+        let mut bucket = UncertaintyGateBucket::new("all", vec!["ALL".to_string()]);
+        bucket.record(1.5, false, 0.95, 8);
+        file.buckets.push(bucket);
+        file.online = Some(OnlineModelMeta {
+            base_path: base.clone(),
+            base_modified_ms: file_modified_ms(&base).unwrap(),
+            rounds: 1,
+            updated_ms: 0,
+        });
+        let online_path = dir.join("model.online.json");
+        file.save(&online_path).unwrap();
+        let reloaded = UncertaintyGateFile::load(&online_path).unwrap();
+        assert!(reloaded.continues_base(&base));
+        let online = reloaded.buckets[0].online.as_ref().unwrap();
+        assert!(online.prior_q_bps.is_nan());
+        assert_eq!(online.recent_bps, VecDeque::from([1.5]));
+        assert_eq!(reloaded.config.extra["max_train_max_bps"], 8.0);
+        assert_eq!(reloaded.extra["bucket_key_order"][0], "symbol");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writer_persists_the_newest_queued_snapshot() {
+        let dir = std::env::temp_dir().join(format!(
+            "pm_uncertainty_gate_{}_{}",
+            std::process::id(),
+            line!()
+        ));
+        let path = dir.join("model.online.json");
+        let snapshot = |rounds: u64| UncertaintyGateFile {
+            model: UNCERTAINTY_GATE_MODEL_NAME.to_string(),
+            config: UncertaintyGateConfig::default(),
+            buckets: Vec::new(),
+            rescue_buckets: Vec::new(),
+            online: Some(OnlineModelMeta {
+                base_path: dir.join("model.latest.json"),
+                base_modified_ms: 0,
+                rounds,
+                updated_ms: 0,
+            }),
+            extra: serde_json::Map::new(),
+        };
+
+        let writer = UncertaintyGateWriter::spawn();
+        for rounds in 1..=3 {
+            writer.save(path.clone(), snapshot(rounds));
+        }
+        writer.close();
+
+        let saved = UncertaintyGateFile::load(&path).unwrap();
+        assert_eq!(saved.online.unwrap().rounds, 3);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,685 @@
+//! Per-venue symbol mapping and WS message parsers for the local price feeds.
+//! Every venue is mapped onto Chainlink style symbols (`btc/usd`); USDT
+//! quotes are treated as USD.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::polymarket::ingress::now_ms;
+
+/// A parsed venue tick, already mapped onto its Chainlink symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolTick {
+    pub symbol: String,
+    pub price: f64,
+    pub ts_ms: u64,
+}
+
+fn parse_u64_value(v: &Value) -> Option<u64> {
+    if let Some(u) = v.as_u64() {
+        return Some(u);
+    }
+    if let Some(i) = v.as_i64() {
+        return (i >= 0).then_some(i as u64);
+    }
+    if let Some(f) = v.as_f64() {
+        return (f.is_finite() && f >= 0.0).then_some(f as u64);
+    }
+    v.as_str()?.trim().parse::<u64>().ok()
+}
+
+fn parse_f64_value(v: &Value) -> Option<f64> {
+    v.as_f64()
+        .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+        .filter(|x| x.is_finite())
+}
+
+/// Venue timestamps arrive as either unix seconds or unix millis.
+fn parse_unix_ms_value(v: &Value) -> Option<u64> {
+    let raw = parse_u64_value(v)?;
+    if raw > 10_000_000_000 {
+        Some(raw)
+    } else {
+        Some(raw.saturating_mul(1_000))
+    }
+}
+
+pub fn binance_stream_symbol_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim().to_ascii_lowercase();
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}usdt", base))
+}
+
+pub fn chainlink_symbol_from_binance_stream_symbol(binance_symbol: &str) -> Option<String> {
+    let lower = binance_symbol.trim().to_ascii_lowercase();
+    let base = lower.strip_suffix("usdt")?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}/usd", base))
+}
+
+pub fn parse_binance_combined_trade_tick(text: &str) -> Option<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return None;
+    };
+    let stream = value
+        .get("stream")
+        .and_then(|v| v.as_str())
+        .or_else(|| value.get("s").and_then(|v| v.as_str()))?;
+    let stream_symbol = stream
+        .split('@')
+        .next()
+        .map(str::to_string)
+        .unwrap_or_default();
+    let chainlink_symbol = chainlink_symbol_from_binance_stream_symbol(&stream_symbol)?;
+    let data = value.get("data").unwrap_or(&value);
+    let price = data.get("p").and_then(parse_f64_value)?;
+    if !price.is_finite() || price <= 0.0 {
+        return None;
+    }
+    let ts_ms = data
+        .get("T")
+        .and_then(parse_unix_ms_value)
+        .or_else(|| data.get("E").and_then(parse_unix_ms_value))
+        .unwrap_or_else(now_ms);
+    Some(SymbolTick {
+        symbol: chainlink_symbol,
+        price,
+        ts_ms,
+    })
+}
+
+pub fn bybit_stream_symbol_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim();
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}USDT", base.to_ascii_uppercase()))
+}
+
+pub fn chainlink_symbol_from_bybit_stream_symbol(bybit_symbol: &str) -> Option<String> {
+    let upper = bybit_symbol.trim().to_ascii_uppercase();
+    let base = upper.strip_suffix("USDT")?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}/usd", base.to_ascii_lowercase()))
+}
+
+pub fn parse_bybit_public_trade_ticks(text: &str) -> Vec<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return vec![];
+    };
+    let topic = value.get("topic").and_then(|v| v.as_str()).unwrap_or("");
+    if !topic.starts_with("publicTrade.") {
+        return vec![];
+    }
+    let bybit_symbol = topic.trim_start_matches("publicTrade.");
+    let Some(chainlink_symbol) = chainlink_symbol_from_bybit_stream_symbol(bybit_symbol) else {
+        return vec![];
+    };
+    let mut out = Vec::new();
+    if let Some(items) = value.get("data").and_then(|v| v.as_array()) {
+        for item in items {
+            let price = item.get("p").and_then(parse_f64_value).unwrap_or(0.0);
+            if !price.is_finite() || price <= 0.0 {
+                continue;
+            }
+            let ts_ms = item
+                .get("T")
+                .and_then(parse_unix_ms_value)
+                .or_else(|| item.get("ts").and_then(parse_unix_ms_value))
+                .unwrap_or_else(now_ms);
+            out.push(SymbolTick {
+                symbol: chainlink_symbol.clone(),
+                price,
+                ts_ms,
+            });
+        }
+    }
+    out
+}
+
+pub fn okx_inst_id_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim();
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}-USDT", base.to_ascii_uppercase()))
+}
+
+pub fn chainlink_symbol_from_okx_inst_id(inst_id: &str) -> Option<String> {
+    let upper = inst_id.trim().to_ascii_uppercase();
+    let base = upper.strip_suffix("-USDT")?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}/usd", base.to_ascii_lowercase()))
+}
+
+pub fn parse_okx_trade_ticks(text: &str) -> Vec<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return vec![];
+    };
+    let arg = value.get("arg");
+    let channel = arg
+        .and_then(|a| a.get("channel"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if channel != "trades" {
+        return vec![];
+    }
+    let inst_id = arg
+        .and_then(|a| a.get("instId"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let Some(chainlink_symbol) = chainlink_symbol_from_okx_inst_id(inst_id) else {
+        return vec![];
+    };
+    let mut out = Vec::new();
+    if let Some(items) = value.get("data").and_then(|v| v.as_array()) {
+        for item in items {
+            let price = item.get("px").and_then(parse_f64_value).unwrap_or(0.0);
+            if !price.is_finite() || price <= 0.0 {
+                continue;
+            }
+            let ts_ms = item
+                .get("ts")
+                .and_then(parse_unix_ms_value)
+                .unwrap_or_else(now_ms);
+            out.push(SymbolTick {
+                symbol: chainlink_symbol.clone(),
+                price,
+                ts_ms,
+            });
+        }
+    }
+    out
+}
+
+pub fn coinbase_product_id_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim();
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}-USD", base.to_ascii_uppercase()))
+}
+
+pub fn hyperliquid_coin_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim().to_ascii_uppercase();
+    if base == "HYPE" {
+        Some(base)
+    } else {
+        None
+    }
+}
+
+pub fn chainlink_symbol_from_coinbase_product_id(product_id: &str) -> Option<String> {
+    let upper = product_id.trim().to_ascii_uppercase();
+    let base = upper.strip_suffix("-USD")?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}/usd", base.to_ascii_lowercase()))
+}
+
+pub fn parse_coinbase_ticker_tick(text: &str) -> Option<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return None;
+    };
+    if value.get("type").and_then(|v| v.as_str()) != Some("ticker") {
+        return None;
+    }
+    let product_id = value.get("product_id").and_then(|v| v.as_str())?;
+    let chainlink_symbol = chainlink_symbol_from_coinbase_product_id(product_id)?;
+    let price = value.get("price").and_then(parse_f64_value)?;
+    if !price.is_finite() || price <= 0.0 {
+        return None;
+    }
+    let ts_ms = value
+        .get("time")
+        .and_then(|v| v.as_str())
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.timestamp_millis().max(0) as u64)
+        .unwrap_or_else(now_ms);
+    Some(SymbolTick {
+        symbol: chainlink_symbol,
+        price,
+        ts_ms,
+    })
+}
+
+pub fn parse_hyperliquid_all_mids_ticks(
+    text: &str,
+    wanted_coins: &HashMap<String, String>,
+) -> Vec<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let channel = value
+        .get("channel")
+        .and_then(|v| v.as_str())
+        .or_else(|| value.get("type").and_then(|v| v.as_str()))
+        .unwrap_or_default();
+    if channel != "allMids" {
+        return Vec::new();
+    }
+    let Some(mids) = value
+        .get("data")
+        .and_then(|v| v.get("mids"))
+        .and_then(|v| v.as_object())
+    else {
+        return Vec::new();
+    };
+    let ts_ms = value
+        .get("data")
+        .and_then(|v| v.get("time"))
+        .and_then(parse_u64_value)
+        .unwrap_or_else(now_ms);
+    let mut out = Vec::new();
+    for (coin, pxv) in mids {
+        let Some(symbol) = wanted_coins.get(&coin.to_ascii_uppercase()) else {
+            continue;
+        };
+        let Some(price) = parse_f64_value(pxv).filter(|v| *v > 0.0) else {
+            continue;
+        };
+        out.push(SymbolTick {
+            symbol: symbol.clone(),
+            price,
+            ts_ms,
+        });
+    }
+    out
+}
+
+/// Non-data frames a venue feed has to act on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedControl {
+    /// Send this frame back (application level keepalive).
+    Reply(String),
+    /// Venue asked us to drop and re-establish the connection.
+    Reconnect,
+}
+
+fn rfc3339_ms(v: &Value) -> Option<u64> {
+    let dt = chrono::DateTime::parse_from_rfc3339(v.as_str()?).ok()?;
+    Some(dt.timestamp_millis().max(0) as u64)
+}
+
+fn chainlink_usd_base(chainlink_symbol: &str) -> Option<String> {
+    let (base, quote) = chainlink_symbol.split_once('/')?;
+    if !quote.eq_ignore_ascii_case("usd") {
+        return None;
+    }
+    let base = base.trim();
+    if base.is_empty() {
+        return None;
+    }
+    Some(base.to_ascii_uppercase())
+}
+
+fn chainlink_symbol_from_pair(pair: &str, sep_quote: &str) -> Option<String> {
+    let upper = pair.trim().to_ascii_uppercase();
+    let base = upper.strip_suffix(sep_quote)?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!("{}/usd", base.to_ascii_lowercase()))
+}
+
+pub fn kraken_symbol_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    Some(format!("{}/USD", chainlink_usd_base(chainlink_symbol)?))
+}
+
+pub fn chainlink_symbol_from_kraken_symbol(kraken_symbol: &str) -> Option<String> {
+    chainlink_symbol_from_pair(kraken_symbol, "/USD")
+}
+
+/// Kraken spot v2 `trade` channel (snapshot and update frames).
+pub fn parse_kraken_trade_ticks(text: &str) -> Vec<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return vec![];
+    };
+    if value.get("channel").and_then(|v| v.as_str()) != Some("trade") {
+        return vec![];
+    }
+    let mut out = Vec::new();
+    if let Some(items) = value.get("data").and_then(|v| v.as_array()) {
+        for item in items {
+            let Some(symbol) = item
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .and_then(chainlink_symbol_from_kraken_symbol)
+            else {
+                continue;
+            };
+            let price = item.get("price").and_then(parse_f64_value).unwrap_or(0.0);
+            if price <= 0.0 {
+                continue;
+            }
+            let ts_ms = item
+                .get("timestamp")
+                .and_then(rfc3339_ms)
+                .unwrap_or_else(now_ms);
+            out.push(SymbolTick {
+                symbol,
+                price,
+                ts_ms,
+            });
+        }
+    }
+    out
+}
+
+pub fn bitstamp_pair_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    Some(format!(
+        "{}usd",
+        chainlink_usd_base(chainlink_symbol)?.to_ascii_lowercase()
+    ))
+}
+
+pub fn chainlink_symbol_from_bitstamp_pair(pair: &str) -> Option<String> {
+    chainlink_symbol_from_pair(pair, "USD")
+}
+
+/// Bitstamp `live_trades_<pair>` channel; `microtimestamp` is unix micros.
+pub fn parse_bitstamp_trade_tick(text: &str) -> Option<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return None;
+    };
+    if value.get("event").and_then(|v| v.as_str()) != Some("trade") {
+        return None;
+    }
+    let pair = value
+        .get("channel")
+        .and_then(|v| v.as_str())?
+        .strip_prefix("live_trades_")?;
+    let symbol = chainlink_symbol_from_bitstamp_pair(pair)?;
+    let data = value.get("data")?;
+    let price = data
+        .get("price_str")
+        .or_else(|| data.get("price"))
+        .and_then(parse_f64_value)?;
+    if price <= 0.0 {
+        return None;
+    }
+    let ts_ms = data
+        .get("microtimestamp")
+        .and_then(parse_u64_value)
+        .map(|us| us / 1_000)
+        .or_else(|| data.get("timestamp").and_then(parse_unix_ms_value))
+        .unwrap_or_else(now_ms);
+    Some(SymbolTick {
+        symbol,
+        price,
+        ts_ms,
+    })
+}
+
+pub fn bitstamp_feed_control(text: &str) -> Option<FeedControl> {
+    let value = serde_json::from_str::<Value>(text).ok()?;
+    (value.get("event").and_then(|v| v.as_str()) == Some("bts:request_reconnect"))
+        .then_some(FeedControl::Reconnect)
+}
+
+pub fn gate_pair_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    Some(format!("{}_USDT", chainlink_usd_base(chainlink_symbol)?))
+}
+
+pub fn chainlink_symbol_from_gate_pair(pair: &str) -> Option<String> {
+    chainlink_symbol_from_pair(pair, "_USDT")
+}
+
+/// Gate spot v4 `spot.trades` update; `create_time_ms` is a decimal string.
+pub fn parse_gate_trade_tick(text: &str) -> Option<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return None;
+    };
+    if value.get("channel").and_then(|v| v.as_str()) != Some("spot.trades")
+        || value.get("event").and_then(|v| v.as_str()) != Some("update")
+    {
+        return None;
+    }
+    let result = value.get("result")?;
+    let symbol = result
+        .get("currency_pair")
+        .and_then(|v| v.as_str())
+        .and_then(chainlink_symbol_from_gate_pair)?;
+    let price = result.get("price").and_then(parse_f64_value)?;
+    if price <= 0.0 {
+        return None;
+    }
+    let ts_ms = result
+        .get("create_time_ms")
+        .and_then(parse_f64_value)
+        .filter(|ms| *ms > 0.0)
+        .map(|ms| ms as u64)
+        .or_else(|| result.get("create_time").and_then(parse_unix_ms_value))
+        .unwrap_or_else(now_ms);
+    Some(SymbolTick {
+        symbol,
+        price,
+        ts_ms,
+    })
+}
+
+pub fn crypto_com_instrument_from_chainlink_symbol(chainlink_symbol: &str) -> Option<String> {
+    Some(format!("{}_USD", chainlink_usd_base(chainlink_symbol)?))
+}
+
+pub fn chainlink_symbol_from_crypto_com_instrument(instrument: &str) -> Option<String> {
+    chainlink_symbol_from_pair(instrument, "_USD")
+}
+
+/// Crypto.com Exchange v1 market `trade.<instrument>` channel.
+pub fn parse_crypto_com_trade_ticks(text: &str) -> Vec<SymbolTick> {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return vec![];
+    };
+    let Some(result) = value.get("result") else {
+        return vec![];
+    };
+    if result.get("channel").and_then(|v| v.as_str()) != Some("trade") {
+        return vec![];
+    }
+    let result_symbol = result
+        .get("instrument_name")
+        .and_then(|v| v.as_str())
+        .and_then(chainlink_symbol_from_crypto_com_instrument);
+    let mut out = Vec::new();
+    if let Some(items) = result.get("data").and_then(|v| v.as_array()) {
+        for item in items {
+            let Some(symbol) = item
+                .get("i")
+                .and_then(|v| v.as_str())
+                .and_then(chainlink_symbol_from_crypto_com_instrument)
+                .or_else(|| result_symbol.clone())
+            else {
+                continue;
+            };
+            let price = item.get("p").and_then(parse_f64_value).unwrap_or(0.0);
+            if price <= 0.0 {
+                continue;
+            }
+            let ts_ms = item
+                .get("t")
+                .and_then(parse_unix_ms_value)
+                .unwrap_or_else(now_ms);
+            out.push(SymbolTick {
+                symbol,
+                price,
+                ts_ms,
+            });
+        }
+    }
+    out
+}
+
+/// Crypto.com drops clients that do not answer `public/heartbeat` in time.
+pub fn crypto_com_feed_control(text: &str) -> Option<FeedControl> {
+    let value = serde_json::from_str::<Value>(text).ok()?;
+    if value.get("method").and_then(|v| v.as_str()) != Some("public/heartbeat") {
+        return None;
+    }
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    Some(FeedControl::Reply(
+        serde_json::json!({"id": id, "method": "public/respond-heartbeat"}).to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(symbol: &str, price: f64, ts_ms: u64) -> SymbolTick {
+        SymbolTick {
+            symbol: symbol.to_string(),
+            price,
+            ts_ms,
+        }
+    }
+
+    #[test]
+    fn test_parse_binance_combined_trade_tick() {
+        let msg = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1776488227000,"s":"BTCUSDT","t":1,"p":"85000.12000000","q":"0.001","T":1776488226999}}"#;
+        let tick_out = parse_binance_combined_trade_tick(msg);
+        assert_eq!(tick_out, Some(tick("btc/usd", 85000.12, 1_776_488_226_999)));
+    }
+
+    #[test]
+    fn test_parse_bybit_public_trade_ticks() {
+        let msg = r#"{"topic":"publicTrade.BTCUSDT","type":"snapshot","ts":1776488227000,"data":[{"T":1776488226998,"s":"BTCUSDT","S":"Buy","v":"0.001","p":"85000.12"}]}"#;
+        let ticks = parse_bybit_public_trade_ticks(msg);
+        assert_eq!(ticks, vec![tick("btc/usd", 85000.12, 1_776_488_226_998)]);
+    }
+
+    #[test]
+    fn test_parse_okx_trade_ticks() {
+        let msg = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"1","px":"85000.12","sz":"0.1","side":"buy","ts":"1776488226997"}]}"#;
+        let ticks = parse_okx_trade_ticks(msg);
+        assert_eq!(ticks, vec![tick("btc/usd", 85000.12, 1_776_488_226_997)]);
+    }
+
+    #[test]
+    fn test_parse_coinbase_ticker_tick() {
+        let msg = r#"{"type":"ticker","sequence":1,"product_id":"ETH-USD","price":"3200.55","time":"2026-04-18T05:03:46.996Z"}"#;
+        assert_eq!(
+            parse_coinbase_ticker_tick(msg),
+            Some(tick("eth/usd", 3200.55, 1_776_488_626_996))
+        );
+        let heartbeat = r#"{"type":"heartbeat","product_id":"ETH-USD"}"#;
+        assert_eq!(parse_coinbase_ticker_tick(heartbeat), None);
+    }
+
+    #[test]
+    fn test_parse_hyperliquid_all_mids_ticks_keeps_wanted_coins() {
+        let wanted = HashMap::from([("HYPE".to_string(), "hype/usd".to_string())]);
+        let msg = r#"{"channel":"allMids","data":{"time":1776488226995,"mids":{"HYPE":"41.25","BTC":"85000.0"}}}"#;
+        assert_eq!(
+            parse_hyperliquid_all_mids_ticks(msg, &wanted),
+            vec![tick("hype/usd", 41.25, 1_776_488_226_995)]
+        );
+    }
+
+    #[test]
+    fn test_venue_symbol_maps_round_trip() {
+        assert_eq!(
+            binance_stream_symbol_from_chainlink_symbol("sol/usd").as_deref(),
+            Some("solusdt")
+        );
+        assert_eq!(
+            chainlink_symbol_from_okx_inst_id("SOL-USDT").as_deref(),
+            Some("sol/usd")
+        );
+        assert_eq!(coinbase_product_id_from_chainlink_symbol("sol/eur"), None);
+        assert_eq!(hyperliquid_coin_from_chainlink_symbol("btc/usd"), None);
+    }
+
+    #[test]
+    fn test_parse_kraken_trade_ticks() {
+        let msg = r#"{"channel":"trade","type":"update","data":[{"symbol":"BTC/USD","side":"buy","price":85000.1,"qty":0.01,"ord_type":"market","trade_id":1,"timestamp":"2026-04-18T05:03:46.996123Z"}]}"#;
+        assert_eq!(
+            parse_kraken_trade_ticks(msg),
+            vec![tick("btc/usd", 85000.1, 1_776_488_626_996)]
+        );
+        let heartbeat = r#"{"channel":"heartbeat"}"#;
+        assert!(parse_kraken_trade_ticks(heartbeat).is_empty());
+        assert_eq!(
+            kraken_symbol_from_chainlink_symbol("eth/usd").as_deref(),
+            Some("ETH/USD")
+        );
+    }
+
+    #[test]
+    fn test_parse_bitstamp_trade_tick_and_reconnect_request() {
+        let msg = r#"{"data":{"id":1,"timestamp":"1776488226","amount":0.01,"amount_str":"0.01","price":85000.1,"price_str":"85000.10","type":0,"microtimestamp":"1776488226996123"},"channel":"live_trades_btcusd","event":"trade"}"#;
+        assert_eq!(
+            parse_bitstamp_trade_tick(msg),
+            Some(tick("btc/usd", 85000.1, 1_776_488_226_996))
+        );
+        let reconnect = r#"{"event":"bts:request_reconnect","channel":"","data":""}"#;
+        assert_eq!(parse_bitstamp_trade_tick(reconnect), None);
+        assert_eq!(
+            bitstamp_feed_control(reconnect),
+            Some(FeedControl::Reconnect)
+        );
+        assert_eq!(bitstamp_feed_control(msg), None);
+        assert_eq!(
+            bitstamp_pair_from_chainlink_symbol("xrp/usd").as_deref(),
+            Some("xrpusd")
+        );
+    }
+
+    #[test]
+    fn test_parse_gate_trade_tick() {
+        let msg = r#"{"time":1776488227,"time_ms":1776488227001,"channel":"spot.trades","event":"update","result":{"id":1,"create_time":1776488226,"create_time_ms":"1776488226996.123","side":"buy","currency_pair":"SOL_USDT","amount":"1.5","price":"142.35"}}"#;
+        assert_eq!(
+            parse_gate_trade_tick(msg),
+            Some(tick("sol/usd", 142.35, 1_776_488_226_996))
+        );
+        let ack = r#"{"time":1776488227,"channel":"spot.trades","event":"subscribe","result":{"status":"success"}}"#;
+        assert_eq!(parse_gate_trade_tick(ack), None);
+    }
+
+    #[test]
+    fn test_parse_crypto_com_trade_ticks_and_heartbeat() {
+        let msg = r#"{"id":-1,"method":"subscribe","code":0,"result":{"instrument_name":"ETH_USD","subscription":"trade.ETH_USD","channel":"trade","data":[{"d":"4611686018439397540","t":1776488226996,"p":"3200.55","q":"0.1","s":"BUY","i":"ETH_USD","m":"1"}]}}"#;
+        assert_eq!(
+            parse_crypto_com_trade_ticks(msg),
+            vec![tick("eth/usd", 3200.55, 1_776_488_226_996)]
+        );
+        let heartbeat = r#"{"id":1776488226000,"method":"public/heartbeat","code":0}"#;
+        assert!(parse_crypto_com_trade_ticks(heartbeat).is_empty());
+        let Some(FeedControl::Reply(reply)) = crypto_com_feed_control(heartbeat) else {
+            panic!("heartbeat must be answered");
+        };
+        let reply: Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(reply["id"], 1_776_488_226_000u64);
+        assert_eq!(reply["method"], "public/respond-heartbeat");
+    }
+}
@@ -10,7 +10,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -261,6 +261,7 @@ pub struct RecorderHandle {
     market_mode: RecorderMarketMode,
     md_tx: Option<mpsc::Sender<RecorderEnvelope>>,
     ops_tx: Option<mpsc::Sender<RecorderEnvelope>>,
+    flush_tx: Option<mpsc::Sender<oneshot::Sender<()>>>,
     md_drop_count: Arc<AtomicU64>,
     critical_drop_count: Arc<AtomicU64>,
 }
@@ -272,6 +273,7 @@ impl RecorderHandle {
             market_mode: RecorderMarketMode::Structured,
             md_tx: None,
             ops_tx: None,
+            flush_tx: None,
             md_drop_count: Arc::new(AtomicU64::new(0)),
             critical_drop_count: Arc::new(AtomicU64::new(0)),
         }
@@ -284,6 +286,7 @@ impl RecorderHandle {
 
         let (md_tx, mut md_rx) = mpsc::channel::<RecorderEnvelope>(cfg.md_queue_cap);
         let (ops_tx, mut ops_rx) = mpsc::channel::<RecorderEnvelope>(cfg.ops_queue_cap);
+        let (flush_tx, mut flush_rx) = mpsc::channel::<oneshot::Sender<()>>(4);
         let root = cfg.root.clone();
         let flush_every = cfg.flush_every;
 
@@ -293,6 +296,7 @@ impl RecorderHandle {
             flush_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             let mut md_closed = false;
             let mut ops_closed = false;
+            let mut flush_closed = false;
 
             loop {
                 tokio::select! {
@@ -324,6 +328,31 @@ impl RecorderHandle {
                         }
                     }
 
+                    maybe_ack = flush_rx.recv(), if !flush_closed => {
+                        match maybe_ack {
+                            Some(ack) => {
+                                // Drain whatever was queued before the request, then flush.
+                                while let Ok(env) = ops_rx.try_recv() {
+                                    if let Err(e) = state.write_envelope(env) {
+                                        warn!("⚠️ recorder write(op) failed: {:?}", e);
+                                    }
+                                }
+                                while let Ok(env) = md_rx.try_recv() {
+                                    if let Err(e) = state.write_envelope(env) {
+                                        warn!("⚠️ recorder write(md) failed: {:?}", e);
+                                    }
+                                }
+                                if let Err(e) = state.flush_all() {
+                                    warn!("⚠️ recorder flush failed: {:?}", e);
+                                }
+                                let _ = ack.send(());
+                            }
+                            None => {
+                                flush_closed = true;
+                            }
+                        }
+                    }
+
                     _ = flush_tick.tick() => {
                         if let Err(e) = state.flush_all() {
                             warn!("⚠️ recorder flush failed: {:?}", e);
@@ -354,6 +383,7 @@ impl RecorderHandle {
             market_mode: cfg.market_mode,
             md_tx: Some(md_tx),
             ops_tx: Some(ops_tx),
+            flush_tx: Some(flush_tx),
             md_drop_count: Arc::new(AtomicU64::new(0)),
             critical_drop_count: Arc::new(AtomicU64::new(0)),
         }
@@ -364,6 +394,18 @@ impl RecorderHandle {
         Self::from_config(&cfg)
     }
 
+    /// Resolves once every record sent before the call is written and flushed
+    /// to disk. No-op when the recorder is disabled.
+    pub async fn flush(&self) {
+        let Some(tx) = &self.flush_tx else {
+            return;
+        };
+        let (ack_tx, ack_rx) = oneshot::channel();
+        if tx.send(ack_tx).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+    }
+
     pub fn enabled(&self) -> bool {
         self.enabled
     }
@@ -697,7 +739,7 @@ fn open_append(path: &Path) -> anyhow::Result<File> {
     Ok(OpenOptions::new().create(true).append(true).open(path)?)
 }
 
-fn ymd_utc(unix_ms: u64) -> String {
+pub(crate) fn ymd_utc(unix_ms: u64) -> String {
     let dt = Utc
         .timestamp_millis_opt(unix_ms as i64)
         .single()
@@ -0,0 +1,910 @@
+//! Recorder JSONL replay.
+//!
+//! Reads a `data/recorder/<date>/<slug>/` session written by `RecorderHandle`
+//! and rebuilds the `MarketDataMsg` stream the live process saw:
+//! - `market_md.jsonl` (structured mode): `book_l1` → `BookTick` (+ `BookDepthTick`
+//!   when depth evidence was captured), `trade` → `TradeTick`.
+//! - `market_ws.jsonl` (raw mode): `book` / `price_change` / `best_bid_ask` /
+//!   `last_trade_price` frames, merged into full L1 the same way ingress does.
+//!   Only used when no structured rows exist (hybrid sessions record both).
+//! - `meta.jsonl`: session meta + round window + YES/NO asset ids.
+//! - `events.jsonl`: own order / inventory events, kept verbatim for diffing.
+//!
+//! Records keep their original `recv_unix_ms` so they can be re-emitted with the
+//! original inter-arrival timing (`ReplaySpeed::Original`), accelerated, or fed
+//! into the synchronous backtest harness via `to_backtest_window`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tokio::sync::{broadcast, mpsc, watch};
+use tracing::{info, warn};
+
+use super::backtest::{restamp, BacktestEvent, BacktestInput, BacktestWindow};
+use super::clock::{Clock, SharedClock, SimClock};
+use super::coordinator::{CoordinatorConfig, CoordinatorObsSnapshot, StrategyCoordinator};
+use super::executor::{Executor, ExecutorConfig};
+use super::glft::GlftSignalSnapshot;
+use super::inventory::{InventoryConfig, InventoryManager};
+use super::messages::{
+    ExecutionCmd, ExecutionFeedback, FillEvent, InventoryEvent, InventorySnapshot,
+    KillSwitchSignal, MarketDataMsg, OfiSnapshot, OrderManagerCmd, OrderResult, SlotReleaseEvent,
+    TakerSide,
+};
+use super::ofi::{OfiConfig, OfiEngine};
+use super::order_manager::OrderManager;
+use super::recorder::{MarketBookDepthEvidence, RecorderSessionMeta, RecorderSessionStart};
+use super::types::Side;
+
+/// One rebuilt market-data message with its original capture position.
+#[derive(Debug, Clone)]
+pub struct ReplayRecord {
+    pub recv_unix_ms: u64,
+    pub capture_seq: u64,
+    pub msg: MarketDataMsg,
+}
+
+/// A recorder session loaded back from disk.
+#[derive(Debug, Clone, Default)]
+pub struct RecorderReplaySession {
+    pub dir: PathBuf,
+    pub meta: Option<RecorderSessionMeta>,
+    pub session: Option<RecorderSessionStart>,
+    pub records: Vec<ReplayRecord>,
+    /// Raw `events.jsonl` rows (own order / inventory / strategy events).
+    pub own_events: Vec<Value>,
+    /// Lines that failed to parse as JSON.
+    pub malformed_lines: usize,
+}
+
+/// `data/recorder/<date>/<slug>/`.
+pub fn session_dir(root: &Path, date: &str, slug: &str) -> PathBuf {
+    root.join(date).join(slug)
+}
+
+impl RecorderReplaySession {
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        if !dir.is_dir() {
+            anyhow::bail!("recorder session dir not found: {}", dir.display());
+        }
+        let mut out = Self {
+            dir: dir.to_path_buf(),
+            ..Self::default()
+        };
+
+        for row in out.read_rows(&dir.join("meta.jsonl")) {
+            if out.meta.is_none() {
+                out.meta = envelope_meta(&row);
+            }
+            let payload = &row["payload"];
+            if payload["event"].as_str() == Some("session_start") {
+                out.session = serde_json::from_value(payload.clone()).ok();
+            }
+        }
+
+        let structured = out.read_rows(&dir.join("market_md.jsonl"));
+        if !structured.is_empty() {
+            for row in structured {
+                let (recv_unix_ms, capture_seq) = envelope_pos(&row);
+                for msg in parse_structured_md(&row["payload"]) {
+                    out.records.push(ReplayRecord {
+                        recv_unix_ms,
+                        capture_seq,
+                        msg,
+                    });
+                }
+            }
+        } else {
+            let raw = out.read_rows(&dir.join("market_ws.jsonl"));
+            let mut book = RawL1Book::default();
+            for row in raw {
+                let (recv_unix_ms, capture_seq) = envelope_pos(&row);
+                let Some(text) = row["payload"]["raw_text"].as_str() else {
+                    continue;
+                };
+                let Ok(value) = serde_json::from_str::<Value>(text) else {
+                    out.malformed_lines += 1;
+                    continue;
+                };
+                for msg in book.apply_raw(&value, out.session.as_ref()) {
+                    out.records.push(ReplayRecord {
+                        recv_unix_ms,
+                        capture_seq,
+                        msg,
+                    });
+                }
+            }
+        }
+        out.records.sort_by_key(|r| (r.recv_unix_ms, r.capture_seq));
+
+        out.own_events = out.read_rows(&dir.join("events.jsonl"));
+        info!(
+            "📼 Replay session loaded | dir={} slug={} records={} own_events={} malformed={}",
+            dir.display(),
+            out.meta.as_ref().map(|m| m.slug.as_str()).unwrap_or("?"),
+            out.records.len(),
+            out.own_events.len(),
+            out.malformed_lines,
+        );
+        Ok(out)
+    }
+
+    fn read_rows(&mut self, path: &Path) -> Vec<Value> {
+        let Ok(raw) = fs::read_to_string(path) else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for line in raw.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<Value>(line) {
+                Ok(v) => rows.push(v),
+                Err(_) => self.malformed_lines += 1,
+            }
+        }
+        rows
+    }
+
+    /// Round end in UNIX ms (from `session_start`), falling back to the last record.
+    pub fn end_ts_ms(&self) -> u64 {
+        self.session
+            .as_ref()
+            .map(|s| s.round_end_ts.saturating_mul(1_000))
+            .filter(|ms| *ms > 0)
+            .or_else(|| self.records.last().map(|r| r.recv_unix_ms))
+            .unwrap_or_default()
+    }
+
+    /// Convert into a backtest window for deterministic synchronous replay.
+    pub fn to_backtest_window(&self, outcome: Option<Side>) -> BacktestWindow {
+        BacktestWindow {
+            id: self
+                .meta
+                .as_ref()
+                .map(|m| m.slug.clone())
+                .unwrap_or_else(|| self.dir.display().to_string()),
+            end_ts_ms: self.end_ts_ms(),
+            outcome,
+            events: self
+                .records
+                .iter()
+                .map(|r| BacktestEvent {
+                    ts_ms: r.recv_unix_ms,
+                    input: BacktestInput::Market(r.msg.clone()),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn envelope_pos(row: &Value) -> (u64, u64) {
+    (
+        row["recv_unix_ms"].as_u64().unwrap_or_default(),
+        row["capture_seq"].as_u64().unwrap_or_default(),
+    )
+}
+
+fn envelope_meta(row: &Value) -> Option<RecorderSessionMeta> {
+    Some(RecorderSessionMeta {
+        slug: row["slug"].as_str()?.to_string(),
+        condition_id: row["condition_id"].as_str().unwrap_or_default().to_string(),
+        market_id: row["market_id"].as_str().unwrap_or_default().to_string(),
+        strategy: row["strategy"].as_str().unwrap_or_default().to_string(),
+        dry_run: row["dry_run"].as_bool().unwrap_or(true),
+    })
+}
+
+fn parse_side(raw: Option<&str>) -> Option<Side> {
+    match raw?.trim().to_ascii_uppercase().as_str() {
+        "YES" | "UP" => Some(Side::Yes),
+        "NO" | "DOWN" => Some(Side::No),
+        _ => None,
+    }
+}
+
+fn parse_taker_side(raw: Option<&str>) -> Option<TakerSide> {
+    match raw?.trim().to_ascii_uppercase().as_str() {
+        "BUY" => Some(TakerSide::Buy),
+        "SELL" => Some(TakerSide::Sell),
+        _ => None,
+    }
+}
+
+fn value_f64(v: &Value) -> Option<f64> {
+    v.as_f64()
+        .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+}
+
+fn non_empty(v: &Value) -> Option<String> {
+    v.as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn non_zero(v: &Value) -> Option<u64> {
+    v.as_u64().filter(|v| *v > 0)
+}
+
+/// Rebuild messages from one structured `market_md` payload.
+fn parse_structured_md(payload: &Value) -> Vec<MarketDataMsg> {
+    let now = Instant::now();
+    match payload["kind"].as_str() {
+        Some("book_l1") => {
+            let depth = serde_json::from_value::<MarketBookDepthEvidence>(payload.clone())
+                .ok()
+                .filter(|d| d.market_side.is_some() && d.best_bid.is_some());
+            let mut out = Vec::with_capacity(2);
+            if let Some(d) = depth.as_ref() {
+                if let (Some(side), Some(best_bid)) =
+                    (parse_side(d.market_side.as_deref()), d.best_bid)
+                {
+                    out.push(MarketDataMsg::BookDepthTick {
+                        market_side: side,
+                        best_bid,
+                        best_ask: d.best_ask,
+                        best_bid_size: d.best_bid_size,
+                        best_ask_size: d.best_ask_size,
+                        best_bid_drop_qty: d.best_bid_drop_qty.unwrap_or(0.0),
+                        best_ask_drop_qty: d.best_ask_drop_qty.unwrap_or(0.0),
+                        event_time_ms: d.canonical_event_time_ms(),
+                        source_sequence_id: d.canonical_source_sequence_id().map(str::to_string),
+                        ts: now,
+                    });
+                }
+            }
+            out.insert(
+                0,
+                MarketDataMsg::BookTick {
+                    yes_bid: value_f64(&payload["yes_bid"]).unwrap_or(0.0),
+                    yes_ask: value_f64(&payload["yes_ask"]).unwrap_or(0.0),
+                    no_bid: value_f64(&payload["no_bid"]).unwrap_or(0.0),
+                    no_ask: value_f64(&payload["no_ask"]).unwrap_or(0.0),
+                    depth,
+                    ts: now,
+                },
+            );
+            out
+        }
+        Some("trade") => {
+            let (Some(market_side), Some(taker_side), Some(price), Some(size)) = (
+                parse_side(payload["market_side"].as_str()),
+                parse_taker_side(payload["taker_side"].as_str()),
+                value_f64(&payload["price"]),
+                value_f64(&payload["size"]),
+            ) else {
+                return Vec::new();
+            };
+            vec![MarketDataMsg::TradeTick {
+                asset_id: payload["asset_id"].as_str().unwrap_or_default().to_string(),
+                trade_id: non_empty(&payload["trade_id"]),
+                source_sequence_id: non_empty(&payload["source_sequence_id"]),
+                event_time_ms: non_zero(&payload["event_time_ms"]),
+                market_side,
+                taker_side,
+                price,
+                size,
+                ts: now,
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Merged top-of-book for raw WS frames (raw frames are per-asset partials).
+#[derive(Debug, Default)]
+struct RawL1Book {
+    yes: (f64, f64),
+    no: (f64, f64),
+}
+
+impl RawL1Book {
+    fn apply_raw(
+        &mut self,
+        value: &Value,
+        session: Option<&RecorderSessionStart>,
+    ) -> Vec<MarketDataMsg> {
+        if let Some(items) = value.as_array() {
+            return items
+                .iter()
+                .flat_map(|v| self.apply_raw(v, session))
+                .collect();
+        }
+        let side_of = |asset_id: &str| -> Option<Side> {
+            let session = session?;
+            if asset_id == session.yes_asset_id {
+                Some(Side::Yes)
+            } else if asset_id == session.no_asset_id {
+                Some(Side::No)
+            } else {
+                None
+            }
+        };
+        let now = Instant::now();
+        let mut touched = false;
+        let mut out = Vec::new();
+        match value["event_type"].as_str() {
+            Some("book") => {
+                if let Some(side) = value["asset_id"].as_str().and_then(side_of) {
+                    let levels = |a: &str, b: &str| {
+                        value
+                            .get(a)
+                            .or_else(|| value.get(b))
+                            .and_then(Value::as_array)
+                            .map(|lvls| {
+                                lvls.iter()
+                                    .filter_map(|l| value_f64(&l["price"]))
+                                    .collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default()
+                    };
+                    // Don't assume arrays are sorted.
+                    let bid = levels("bids", "buys").into_iter().fold(0.0_f64, f64::max);
+                    let ask = levels("asks", "sells").into_iter().fold(f64::MAX, f64::min);
+                    self.set(side, bid, if ask == f64::MAX { 0.0 } else { ask });
+                    touched = true;
+                }
+            }
+            Some("price_change") => {
+                for ch in value["price_changes"].as_array().into_iter().flatten() {
+                    if let Some(side) = ch["asset_id"].as_str().and_then(side_of) {
+                        self.set(
+                            side,
+                            value_f64(&ch["best_bid"]).unwrap_or(0.0),
+                            value_f64(&ch["best_ask"]).unwrap_or(0.0),
+                        );
+                        touched = true;
+                    }
+                }
+            }
+            Some("best_bid_ask") => {
+                if let Some(side) = value["asset_id"].as_str().and_then(side_of) {
+                    self.set(
+                        side,
+                        value_f64(&value["best_bid"]).unwrap_or(0.0),
+                        value_f64(&value["best_ask"]).unwrap_or(0.0),
+                    );
+                    touched = true;
+                }
+            }
+            Some("last_trade_price") => {
+                let asset_id = value["asset_id"].as_str().unwrap_or_default();
+                if let (Some(market_side), Some(taker_side), Some(price), Some(size)) = (
+                    side_of(asset_id),
+                    parse_taker_side(value["side"].as_str()),
+                    value_f64(&value["price"]).filter(|p| *p > 0.0),
+                    value_f64(&value["size"]).filter(|s| *s > 0.0),
+                ) {
+                    out.push(MarketDataMsg::TradeTick {
+                        asset_id: asset_id.to_string(),
+                        trade_id: non_empty(&value["trade_id"])
+                            .or_else(|| non_empty(&value["id"]))
+                            .or_else(|| non_empty(&value["hash"])),
+                        source_sequence_id: None,
+                        event_time_ms: value_f64(&value["timestamp"]).map(|v| v as u64),
+                        market_side,
+                        taker_side,
+                        price,
+                        size,
+                        ts: now,
+                    });
+                }
+            }
+            _ => {}
+        }
+        if touched {
+            out.insert(
+                0,
+                MarketDataMsg::BookTick {
+                    yes_bid: self.yes.0,
+                    yes_ask: self.yes.1,
+                    no_bid: self.no.0,
+                    no_ask: self.no.1,
+                    depth: None,
+                    ts: now,
+                },
+            );
+        }
+        out
+    }
+
+    fn set(&mut self, side: Side, bid: f64, ask: f64) {
+        let slot = match side {
+            Side::Yes => &mut self.yes,
+            Side::No => &mut self.no,
+        };
+        *slot = (bid, ask);
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Timed re-emission into live channels
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Original inter-arrival gaps.
+    Original,
+    /// Gaps divided by the factor (e.g. 10.0 = 10x faster).
+    Accelerated(f64),
+    /// No sleeping between records.
+    Max,
+}
+
+impl ReplaySpeed {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "original" | "1" | "1x" => Some(Self::Original),
+            "max" | "0" => Some(Self::Max),
+            other => other
+                .trim_end_matches('x')
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite() && *f > 0.0)
+                .map(Self::Accelerated),
+        }
+    }
+
+    fn scale(self, gap: Duration) -> Option<Duration> {
+        match self {
+            Self::Original => Some(gap),
+            Self::Accelerated(f) => Some(gap.div_f64(f)),
+            Self::Max => None,
+        }
+    }
+}
+
+/// The same fan-out the live ingress feeds: coordinator watch, OFI mpsc and
+/// dry-run executor broadcast. Any sink may be left unwired.
+#[derive(Default)]
+pub struct ReplaySinks {
+    pub coordinator_md_tx: Option<watch::Sender<MarketDataMsg>>,
+    pub ofi_md_tx: Option<mpsc::Sender<MarketDataMsg>>,
+    pub executor_md_tx: Option<broadcast::Sender<MarketDataMsg>>,
+    /// When set, advanced to each record's receive time before it is sent, so
+    /// actors sharing it see recorded time instead of the wall clock.
+    pub clock: Option<Arc<SimClock>>,
+}
+
+/// Re-emit records with restamped `ts`, pacing by `speed`. Returns records sent.
+pub async fn replay_into(
+    records: &[ReplayRecord],
+    sinks: &ReplaySinks,
+    speed: ReplaySpeed,
+) -> usize {
+    let mut prev_ms: Option<u64> = None;
+    let mut sent = 0usize;
+    for record in records {
+        if let Some(prev) = prev_ms {
+            let gap = Duration::from_millis(record.recv_unix_ms.saturating_sub(prev));
+            if let Some(wait) = speed.scale(gap).filter(|d| !d.is_zero()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        prev_ms = Some(record.recv_unix_ms);
+        let now = match &sinks.clock {
+            Some(clock) => {
+                clock.advance_to_unix_ms(record.recv_unix_ms);
+                clock.now()
+            }
+            None => Instant::now(),
+        };
+        let msg = restamp(record.msg.clone(), now);
+        if let Some(tx) = &sinks.executor_md_tx {
+            let _ = tx.send(msg.clone());
+        }
+        if let Some(tx) = &sinks.ofi_md_tx {
+            let _ = tx.send(msg.clone()).await;
+        }
+        if let Some(tx) = &sinks.coordinator_md_tx {
+            let _ = tx.send(msg);
+        }
+        sent += 1;
+    }
+    sent
+}
+
+// ─────────────────────────────────────────────────────────
+// Full dry-run pipeline
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct PipelineReplayReport {
+    pub records_sent: usize,
+    pub inventory: InventorySnapshot,
+    pub coordinator: CoordinatorObsSnapshot,
+}
+
+/// Replay a session through OFI → Coordinator → OMS → Executor(dry-run) →
+/// Inventory, wired as in the live bin with no CLOB client. Every actor shares
+/// a `SimClock` driven by the records, so round-end and TTL logic read recorded
+/// time rather than wall time.
+///
+/// Not deterministic: the actors run concurrently behind coalescing `watch`
+/// channels, so which ticks each one observes (and hence the exact orders and
+/// fills) can vary between runs and with `speed`. For a reproducible replay
+/// feed `to_backtest_window` to the synchronous backtest harness instead.
+pub async fn run_dry_run_pipeline(
+    session: &RecorderReplaySession,
+    mut coord_cfg: CoordinatorConfig,
+    speed: ReplaySpeed,
+) -> PipelineReplayReport {
+    coord_cfg.dry_run = true;
+    if let Some(start) = session.session.as_ref() {
+        coord_cfg.market_end_ts = Some(start.round_end_ts).filter(|ts| *ts > 0);
+    }
+    let (yes_asset_id, no_asset_id) = session
+        .session
+        .as_ref()
+        .map(|s| (s.yes_asset_id.clone(), s.no_asset_id.clone()))
+        .unwrap_or_default();
+    let market_id = session
+        .meta
+        .as_ref()
+        .map(|m| m.market_id.clone())
+        .unwrap_or_default();
+    // All actors run on recorded time.
+    let start_ms = session
+        .records
+        .first()
+        .map(|r| r.recv_unix_ms)
+        .unwrap_or_default();
+    let clock = SimClock::shared(start_ms);
+    let shared: SharedClock = clock.clone();
+
+    let (ofi_snap_tx, ofi_rx) = watch::channel(OfiSnapshot::default());
+    let (inv_tx, inv_rx) = watch::channel(InventorySnapshot::default());
+    let inv_report_rx = inv_rx.clone();
+    let (coord_md_tx, coord_md_rx) = watch::channel(MarketDataMsg::BookTick {
+        yes_bid: 0.0,
+        yes_ask: 0.0,
+        no_bid: 0.0,
+        no_ask: 0.0,
+        depth: None,
+        ts: clock.now(),
+    });
+    let (_glft_tx, glft_rx) = watch::channel(GlftSignalSnapshot::default());
+    let (obs_tx, obs_rx) = watch::channel(CoordinatorObsSnapshot::default());
+    let (_winner_hint_tx, winner_hint_rx) = mpsc::channel::<MarketDataMsg>(8);
+    let (kill_tx, kill_rx) = mpsc::channel::<KillSwitchSignal>(16);
+    let (ofi_md_tx, ofi_md_rx) = mpsc::channel::<MarketDataMsg>(512);
+    let (exec_md_tx, exec_md_rx) = broadcast::channel::<MarketDataMsg>(1024);
+    let (om_tx, om_rx) = mpsc::channel::<OrderManagerCmd>(64);
+    let (exec_tx, exec_rx) = mpsc::channel::<ExecutionCmd>(32);
+    let (result_tx, result_rx) = mpsc::channel::<OrderResult>(32);
+    let (feedback_tx, feedback_rx) = mpsc::channel::<ExecutionFeedback>(32);
+    let (slot_release_tx, slot_release_rx) = mpsc::channel::<SlotReleaseEvent>(32);
+    let (fill_tx, mut fill_rx) = mpsc::channel::<FillEvent>(64);
+    let (inv_event_tx, inv_event_rx) = mpsc::channel::<InventoryEvent>(64);
+    let (exec_fill_tx, exec_fill_rx) = mpsc::channel::<FillEvent>(64);
+
+    let mut handles = Vec::new();
+    // Fill fanout mirrors live: sim fills → (InventoryManager, Executor).
+    handles.push(tokio::spawn(async move {
+        while let Some(fill) = fill_rx.recv().await {
+            let _ = inv_event_tx.send(InventoryEvent::Fill(fill.clone())).await;
+            let _ = exec_fill_tx.send(fill).await;
+        }
+    }));
+    handles.push(tokio::spawn(
+        OfiEngine::new(OfiConfig::from_env(), ofi_md_rx, ofi_snap_tx)
+            .with_kill_tx(kill_tx)
+            .with_clock(shared.clone())
+            .run(),
+    ));
+    handles.push(tokio::spawn(
+        InventoryManager::new(
+            InventoryConfig::from_env(),
+            inv_event_rx,
+            inv_tx,
+            None,
+            None,
+        )
+        .with_clock(shared.clone())
+        .run(),
+    ));
+    handles.push(tokio::spawn(
+        OrderManager::new(om_rx, exec_tx, result_rx, slot_release_tx)
+            .with_clock(shared.clone())
+            .run(),
+    ));
+    handles.push(tokio::spawn(
+        Executor::new(
+            ExecutorConfig {
+                rest_url: String::new(),
+                market_id,
+                yes_asset_id,
+                no_asset_id,
+                tick_size: coord_cfg.tick_size,
+                reconcile_interval_secs: 0,
+                dry_run: true,
+                market_end_ts: coord_cfg.market_end_ts,
+                pgt_shadow_same_side_provide_cooldown_ms: 0,
+            },
+            None,
+            None,
+            exec_rx,
+            result_tx,
+            exec_fill_rx,
+            Some(fill_tx),
+            Some(exec_md_rx),
+            true,
+            None,
+            Some(feedback_tx),
+            None,
+            None,
+        )
+        .with_clock(shared.clone())
+        .run(),
+    ));
+    let coordinator = StrategyCoordinator::with_aux_rx(
+        coord_cfg,
+        ofi_rx,
+        inv_rx,
+        coord_md_rx,
+        winner_hint_rx,
+        glft_rx,
+        om_tx,
+        kill_rx,
+        feedback_rx,
+        slot_release_rx,
+    )
+    .with_obs_tx(obs_tx)
+    .with_clock(shared);
+    let coord_handle = tokio::spawn(coordinator.run());
+
+    let sinks = ReplaySinks {
+        coordinator_md_tx: Some(coord_md_tx),
+        ofi_md_tx: Some(ofi_md_tx),
+        executor_md_tx: Some(exec_md_tx),
+        clock: Some(clock),
+    };
+    let records_sent = replay_into(&session.records, &sinks, speed).await;
+    // Let in-flight fills / cancels settle before tearing the pipeline down.
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let inventory = *inv_report_rx.borrow();
+    let coordinator = *obs_rx.borrow();
+    drop(sinks);
+    if tokio::time::timeout(Duration::from_secs(2), coord_handle)
+        .await
+        .is_err()
+    {
+        warn!("📼 Replay: coordinator did not shut down within 2s");
+    }
+    for handle in handles {
+        handle.abort();
+    }
+    PipelineReplayReport {
+        records_sent,
+        inventory,
+        coordinator,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::recorder::{RecorderConfig, RecorderHandle, RecorderMarketMode};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_root(prefix: &str) -> PathBuf {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("pm_as_ofi_{}_{}", prefix, ts))
+    }
+
+    fn only_session_dir(root: &Path) -> PathBuf {
+        let date_dir = fs::read_dir(root).unwrap().next().unwrap().unwrap().path();
+        fs::read_dir(date_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path()
+    }
+
+    async fn record_session(mode: RecorderMarketMode, prefix: &str) -> PathBuf {
+        let root = temp_root(prefix);
+        let rec = RecorderHandle::from_config(&RecorderConfig {
+            enabled: true,
+            root: root.clone(),
+            md_queue_cap: 64,
+            ops_queue_cap: 64,
+            flush_every: Duration::from_millis(5),
+            market_mode: mode,
+        });
+        let meta = RecorderSessionMeta {
+            slug: "btc-updown-5m-replay".to_string(),
+            condition_id: "0xcond".to_string(),
+            market_id: "0xmarket".to_string(),
+            strategy: "pair_arb".to_string(),
+            dry_run: true,
+        };
+        rec.emit_session_start(
+            &meta,
+            &RecorderSessionStart {
+                round_start_ts: 100,
+                round_end_ts: 400,
+                yes_asset_id: "yes-asset".to_string(),
+                no_asset_id: "no-asset".to_string(),
+            },
+        );
+        let depth = MarketBookDepthEvidence {
+            market_side: Some("NO".to_string()),
+            best_bid: Some(0.47),
+            best_ask: Some(0.49),
+            best_bid_size: Some(20.0),
+            best_bid_drop_qty: Some(4.0),
+            ..MarketBookDepthEvidence::default()
+        };
+        rec.record_market_book_l1(&meta, 0.50, 0.52, 0.47, 0.49, Some(&depth));
+        rec.record_market_trade(
+            &meta,
+            "no-asset",
+            Side::No,
+            TakerSide::Sell,
+            0.47,
+            6.0,
+            Some("t-1"),
+            None,
+            None,
+            Some(1_746_000_000_000),
+        );
+        rec.record_market_ws_raw(
+            &meta,
+            r#"{"event_type":"book","asset_id":"yes-asset","bids":[{"price":"0.48","size":"5"},{"price":"0.50","size":"3"}],"asks":[{"price":"0.53","size":"2"},{"price":"0.52","size":"9"}]}"#,
+        );
+        rec.record_market_ws_raw(
+            &meta,
+            r#"[{"event_type":"price_change","price_changes":[{"asset_id":"no-asset","best_bid":"0.46","best_ask":"0.48"}]},{"event_type":"last_trade_price","asset_id":"yes-asset","side":"BUY","price":"0.52","size":"4"}]"#,
+        );
+        rec.emit_own_order_event(&meta, "intent_sent", serde_json::json!({"price": 0.5}));
+        rec.flush().await;
+        only_session_dir(&root)
+    }
+
+    #[tokio::test]
+    async fn test_load_structured_session_rebuilds_book_depth_and_trades() {
+        let dir = record_session(RecorderMarketMode::Hybrid, "replay_structured").await;
+        let session = RecorderReplaySession::load(&dir).unwrap();
+
+        assert_eq!(session.meta.as_ref().unwrap().slug, "btc-updown-5m-replay");
+        assert_eq!(session.end_ts_ms(), 400_000);
+        assert_eq!(session.own_events.len(), 1);
+        // Hybrid sessions prefer structured rows: BookTick + BookDepthTick + TradeTick.
+        assert_eq!(session.records.len(), 3);
+        assert!(matches!(
+            session.records[0].msg,
+            MarketDataMsg::BookTick { yes_bid, no_ask, .. } if yes_bid == 0.50 && no_ask == 0.49
+        ));
+        assert!(matches!(
+            session.records[1].msg,
+            MarketDataMsg::BookDepthTick { market_side: Side::No, best_bid, best_bid_drop_qty, .. }
+                if best_bid == 0.47 && best_bid_drop_qty == 4.0
+        ));
+        assert!(matches!(
+            &session.records[2].msg,
+            MarketDataMsg::TradeTick { market_side: Side::No, taker_side: TakerSide::Sell, trade_id: Some(id), size, .. }
+                if id == "t-1" && *size == 6.0
+        ));
+        let window = session.to_backtest_window(Some(Side::Yes));
+        assert_eq!(window.events.len(), 3);
+        assert_eq!(window.end_ts_ms, 400_000);
+    }
+
+    #[tokio::test]
+    async fn test_load_raw_session_merges_partial_frames_into_full_l1() {
+        let dir = record_session(RecorderMarketMode::Raw, "replay_raw").await;
+        let session = RecorderReplaySession::load(&dir).unwrap();
+
+        let books: Vec<(f64, f64, f64, f64)> = session
+            .records
+            .iter()
+            .filter_map(|r| match r.msg {
+                MarketDataMsg::BookTick {
+                    yes_bid,
+                    yes_ask,
+                    no_bid,
+                    no_ask,
+                    ..
+                } => Some((yes_bid, yes_ask, no_bid, no_ask)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            books,
+            vec![(0.50, 0.52, 0.0, 0.0), (0.50, 0.52, 0.46, 0.48)]
+        );
+        assert!(session.records.iter().any(|r| matches!(
+            r.msg,
+            MarketDataMsg::TradeTick {
+                market_side: Side::Yes,
+                taker_side: TakerSide::Buy,
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_replay_into_fans_out_to_all_sinks() {
+        let records: Vec<ReplayRecord> = (0..3u64)
+            .map(|i| ReplayRecord {
+                recv_unix_ms: 1_000 + i * 10,
+                capture_seq: i,
+                msg: MarketDataMsg::BookTick {
+                    yes_bid: 0.40 + i as f64 * 0.01,
+                    yes_ask: 0.60,
+                    no_bid: 0.40,
+                    no_ask: 0.60,
+                    depth: None,
+                    ts: Instant::now(),
+                },
+            })
+            .collect();
+        let (coord_tx, coord_rx) = watch::channel(records[0].msg.clone());
+        let (ofi_tx, mut ofi_rx) = mpsc::channel(8);
+        let (exec_tx, mut exec_rx) = broadcast::channel(8);
+        let sinks = ReplaySinks {
+            coordinator_md_tx: Some(coord_tx),
+            ofi_md_tx: Some(ofi_tx),
+            executor_md_tx: Some(exec_tx),
+            clock: None,
+        };
+        let started = Instant::now();
+        let sent = replay_into(&records, &sinks, ReplaySpeed::Accelerated(2.0)).await;
+        assert_eq!(sent, 3);
+        assert!(started.elapsed() >= Duration::from_millis(10));
+        assert!(matches!(
+            *coord_rx.borrow(),
+            MarketDataMsg::BookTick { yes_bid, .. } if (yes_bid - 0.42).abs() < 1e-9
+        ));
+        for _ in 0..3 {
+            assert!(ofi_rx.try_recv().is_ok());
+            assert!(exec_rx.try_recv().is_ok());
+        }
+        assert_eq!(
+            ReplaySpeed::parse("10x"),
+            Some(ReplaySpeed::Accelerated(10.0))
+        );
+        assert_eq!(ReplaySpeed::parse("max"), Some(ReplaySpeed::Max));
+    }
+
+    #[tokio::test]
+    async fn test_replay_into_drives_sim_clock_to_record_time() {
+        let records: Vec<ReplayRecord> = [1_000u64, 61_000]
+            .iter()
+            .enumerate()
+            .map(|(i, ms)| ReplayRecord {
+                recv_unix_ms: *ms,
+                capture_seq: i as u64,
+                msg: MarketDataMsg::BookTick {
+                    yes_bid: 0.40,
+                    yes_ask: 0.60,
+                    no_bid: 0.40,
+                    no_ask: 0.60,
+                    depth: None,
+                    ts: Instant::now(),
+                },
+            })
+            .collect();
+        let clock = SimClock::shared(1_000);
+        let (coord_tx, coord_rx) = watch::channel(records[0].msg.clone());
+        let sinks = ReplaySinks {
+            coordinator_md_tx: Some(coord_tx),
+            clock: Some(clock.clone()),
+            ..ReplaySinks::default()
+        };
+        let started = Instant::now();
+        assert_eq!(replay_into(&records, &sinks, ReplaySpeed::Max).await, 2);
+        // A minute of recorded time, no real sleeping.
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(clock.unix_now_ms(), 61_000);
+        assert!(matches!(
+            *coord_rx.borrow(),
+            MarketDataMsg::BookTick { ts, .. } if ts == clock.now()
+        ));
+    }
+}
@@ -0,0 +1,483 @@
+//! Connection-pool bookkeeping for the Chainlink RTDS hub.
+//!
+//! RTDS honours one subscription per WebSocket, so the hub still runs one
+//! connection per symbol; this pool coordinates them. Connect attempts share a
+//! bounded number of slots and are staggered so a server-side drop does not
+//! turn into N simultaneous handshakes, failures feed one pool-wide backoff,
+//! and every connection keeps a health score used to decide when a symbol's
+//! stream is stalled and should fail over to another price path.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::config_validation::clamp_reported;
+
+pub const RTDS_POOL_MAX_CONCURRENT_CONNECTS_DEFAULT: usize = 4;
+pub const RTDS_POOL_RECONNECT_STAGGER_MS_DEFAULT: u64 = 250;
+pub const RTDS_POOL_BACKOFF_BASE_MS_DEFAULT: u64 = 300;
+pub const RTDS_POOL_BACKOFF_MAX_MS_DEFAULT: u64 = 10_000;
+pub const RTDS_POOL_STALL_MS_DEFAULT: u64 = 15_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtdsPoolConfig {
+    /// Connect handshakes allowed in flight at once (not total connections).
+    pub max_concurrent_connects: usize,
+    /// Minimum spacing between two connect attempts anywhere in the pool.
+    pub reconnect_stagger_ms: u64,
+    pub backoff_base_ms: u64,
+    pub backoff_max_ms: u64,
+    /// No tick for this long marks the symbol stalled.
+    pub stall_ms: u64,
+    /// Let stalled symbols fail over to Data Streams / frontend API.
+    pub stall_failover: bool,
+}
+
+impl Default for RtdsPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_connects: RTDS_POOL_MAX_CONCURRENT_CONNECTS_DEFAULT,
+            reconnect_stagger_ms: RTDS_POOL_RECONNECT_STAGGER_MS_DEFAULT,
+            backoff_base_ms: RTDS_POOL_BACKOFF_BASE_MS_DEFAULT,
+            backoff_max_ms: RTDS_POOL_BACKOFF_MAX_MS_DEFAULT,
+            stall_ms: RTDS_POOL_STALL_MS_DEFAULT,
+            stall_failover: true,
+        }
+    }
+}
+
+fn env_u64_clamped(name: &str, lo: u64, hi: u64, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(|v| clamp_reported(name, v, lo, hi))
+        .unwrap_or(default)
+}
+
+impl RtdsPoolConfig {
+    /// `stall_ms` comes from the hub's existing stall-reconnect setting.
+    pub fn from_env(stall_ms: u64) -> Self {
+        let d = Self::default();
+        let backoff_base_ms = d.backoff_base_ms;
+        Self {
+            max_concurrent_connects: env_u64_clamped(
+                "PM_CHAINLINK_HUB_MAX_CONCURRENT_CONNECTS",
+                1,
+                64,
+                d.max_concurrent_connects as u64,
+            ) as usize,
+            reconnect_stagger_ms: env_u64_clamped(
+                "PM_CHAINLINK_HUB_RECONNECT_STAGGER_MS",
+                0,
+                5_000,
+                d.reconnect_stagger_ms,
+            ),
+            backoff_base_ms,
+            backoff_max_ms: env_u64_clamped(
+                "PM_CHAINLINK_HUB_BACKOFF_MAX_MS",
+                backoff_base_ms,
+                120_000,
+                d.backoff_max_ms,
+            ),
+            stall_ms,
+            stall_failover: env::var("PM_CHAINLINK_HUB_STALL_FAILOVER")
+                .ok()
+                .map(|v| {
+                    !matches!(
+                        v.trim().to_ascii_lowercase().as_str(),
+                        "0" | "false" | "no" | "off"
+                    )
+                })
+                .unwrap_or(d.stall_failover),
+        }
+    }
+}
+
+/// Pool-wide exponential backoff. Any connection's failure raises the delay
+/// for all of them; a success only halves the failure count so one lucky
+/// handshake during an outage does not release the whole pool at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedBackoff {
+    base_ms: u64,
+    max_ms: u64,
+    failures: u32,
+}
+
+impl SharedBackoff {
+    pub fn new(base_ms: u64, max_ms: u64) -> Self {
+        Self {
+            base_ms,
+            max_ms: max_ms.max(base_ms),
+            failures: 0,
+        }
+    }
+
+    pub fn delay_ms(&self) -> u64 {
+        if self.failures == 0 {
+            return 0;
+        }
+        let shift = (self.failures - 1).min(16);
+        self.base_ms.saturating_mul(1 << shift).min(self.max_ms)
+    }
+
+    pub fn on_failure(&mut self) -> u64 {
+        self.failures = self.failures.saturating_add(1);
+        self.delay_ms()
+    }
+
+    pub fn on_success(&mut self) {
+        self.failures /= 2;
+    }
+}
+
+/// Per-connection counters; all times are unix ms.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionHealth {
+    pub connected: bool,
+    pub connects: u64,
+    pub connect_failures: u64,
+    pub stalls: u64,
+    pub ticks: u64,
+    /// Consecutive failed connects; reset on a successful connect.
+    pub failure_streak: u32,
+    /// Consecutive stall reconnects; reset on the next tick.
+    pub stall_streak: u32,
+    pub last_connect_ms: Option<u64>,
+    pub last_tick_ms: Option<u64>,
+    /// When the connection last went down (or was created).
+    pub down_since_ms: Option<u64>,
+}
+
+impl ConnectionHealth {
+    fn new(now_ms: u64) -> Self {
+        Self {
+            down_since_ms: Some(now_ms),
+            ..Self::default()
+        }
+    }
+
+    /// Time since the last tick, or since the stream went down if it never ticked.
+    pub fn idle_ms(&self, now_ms: u64) -> u64 {
+        let since = self
+            .last_tick_ms
+            .into_iter()
+            .chain(self.last_connect_ms)
+            .chain(self.down_since_ms)
+            .max()
+            .unwrap_or(now_ms);
+        now_ms.saturating_sub(since)
+    }
+
+    /// Stalled once no tick arrived for `stall_ms` (connected or not).
+    pub fn is_stalled(&self, now_ms: u64, stall_ms: u64) -> bool {
+        let last_activity = match (self.last_tick_ms, self.down_since_ms) {
+            (Some(tick), _) => tick,
+            (None, Some(down)) => down,
+            (None, None) => return false,
+        };
+        now_ms.saturating_sub(last_activity) >= stall_ms
+    }
+
+    /// 0..=1: tick freshness (linear decay from `stall_ms` to `3 * stall_ms`)
+    /// discounted by the current failure and stall streaks.
+    pub fn score(&self, now_ms: u64, stall_ms: u64) -> f64 {
+        let freshness = match self.last_tick_ms {
+            None => 0.0,
+            Some(tick) => {
+                let age = now_ms.saturating_sub(tick) as f64;
+                let stall = stall_ms.max(1) as f64;
+                (1.0 - (age - stall).max(0.0) / (2.0 * stall)).clamp(0.0, 1.0)
+            }
+        };
+        let connected = if self.connected { 1.0 } else { 0.5 };
+        freshness * connected
+            / (1.0 + self.failure_streak as f64)
+            / (1.0 + self.stall_streak as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionHealthSnapshot {
+    pub symbol: String,
+    pub score: f64,
+    pub stalled: bool,
+    pub health: ConnectionHealth,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    backoff: SharedBackoff,
+    /// Earliest time the next connect attempt may start.
+    next_connect_ms: u64,
+    health: HashMap<String, ConnectionHealth>,
+}
+
+#[derive(Debug)]
+pub struct RtdsConnectionPool {
+    cfg: RtdsPoolConfig,
+    connect_slots: Arc<Semaphore>,
+    state: Mutex<PoolState>,
+}
+
+impl RtdsConnectionPool {
+    pub fn new<'a>(
+        cfg: RtdsPoolConfig,
+        symbols: impl IntoIterator<Item = &'a str>,
+        now_ms: u64,
+    ) -> Self {
+        let health = symbols
+            .into_iter()
+            .map(|sym| (sym.to_string(), ConnectionHealth::new(now_ms)))
+            .collect();
+        Self {
+            connect_slots: Arc::new(Semaphore::new(cfg.max_concurrent_connects.max(1))),
+            state: Mutex::new(PoolState {
+                backoff: SharedBackoff::new(cfg.backoff_base_ms, cfg.backoff_max_ms),
+                next_connect_ms: 0,
+                health,
+            }),
+            cfg,
+        }
+    }
+
+    pub fn config(&self) -> &RtdsPoolConfig {
+        &self.cfg
+    }
+
+    /// Reserves the caller's connect start time and returns how long to wait
+    /// for it: the later of the next stagger slot and, for a symbol whose last
+    /// attempt failed, the shared backoff.
+    pub fn reserve_connect_delay_ms(&self, symbol: &str, now_ms: u64) -> u64 {
+        let Ok(mut state) = self.state.lock() else {
+            return 0;
+        };
+        let retrying = state
+            .health
+            .get(symbol)
+            .is_some_and(|h| h.failure_streak > 0 || h.stall_streak > 0);
+        let backoff_at = if retrying {
+            now_ms.saturating_add(state.backoff.delay_ms())
+        } else {
+            now_ms
+        };
+        let start_ms = state.next_connect_ms.max(backoff_at);
+        state.next_connect_ms = start_ms.saturating_add(self.cfg.reconnect_stagger_ms);
+        start_ms.saturating_sub(now_ms)
+    }
+
+    /// Waits for the reserved slot, then holds one of the bounded connect
+    /// permits. Drop the permit once the subscription is sent.
+    pub async fn acquire_connect(&self, symbol: &str, now_ms: u64) -> OwnedSemaphorePermit {
+        let delay_ms = self.reserve_connect_delay_ms(symbol, now_ms);
+        if delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        }
+        Arc::clone(&self.connect_slots)
+            .acquire_owned()
+            .await
+            .expect("rtds connect semaphore is never closed")
+    }
+
+    fn with_health<R>(
+        &self,
+        symbol: &str,
+        now_ms: u64,
+        f: impl FnOnce(&mut PoolState, &mut ConnectionHealth) -> R,
+    ) -> Option<R> {
+        let mut state = self.state.lock().ok()?;
+        let mut health = state
+            .health
+            .remove(symbol)
+            .unwrap_or_else(|| ConnectionHealth::new(now_ms));
+        let out = f(&mut state, &mut health);
+        state.health.insert(symbol.to_string(), health);
+        Some(out)
+    }
+
+    pub fn on_connected(&self, symbol: &str, now_ms: u64) {
+        self.with_health(symbol, now_ms, |state, h| {
+            state.backoff.on_success();
+            h.connected = true;
+            h.connects = h.connects.saturating_add(1);
+            h.failure_streak = 0;
+            h.last_connect_ms = Some(now_ms);
+        });
+    }
+
+    /// Returns the shared backoff now in effect.
+    pub fn on_connect_failed(&self, symbol: &str, now_ms: u64) -> u64 {
+        self.with_health(symbol, now_ms, |state, h| {
+            h.connected = false;
+            h.connect_failures = h.connect_failures.saturating_add(1);
+            h.failure_streak = h.failure_streak.saturating_add(1);
+            state.backoff.on_failure()
+        })
+        .unwrap_or(0)
+    }
+
+    pub fn on_tick(&self, symbol: &str, now_ms: u64) {
+        self.with_health(symbol, now_ms, |_, h| {
+            h.ticks = h.ticks.saturating_add(1);
+            h.stall_streak = 0;
+            h.last_tick_ms = Some(now_ms);
+        });
+    }
+
+    /// Stall reconnect: counts as a failure for the shared backoff.
+    pub fn on_stall(&self, symbol: &str, now_ms: u64) {
+        self.with_health(symbol, now_ms, |state, h| {
+            h.stalls = h.stalls.saturating_add(1);
+            h.stall_streak = h.stall_streak.saturating_add(1);
+            state.backoff.on_failure();
+        });
+        self.on_disconnected(symbol, now_ms);
+    }
+
+    pub fn on_disconnected(&self, symbol: &str, now_ms: u64) {
+        self.with_health(symbol, now_ms, |_, h| {
+            if h.connected {
+                h.down_since_ms = Some(now_ms);
+            }
+            h.connected = false;
+        });
+    }
+
+    pub fn is_stalled(&self, symbol: &str, now_ms: u64) -> bool {
+        let Ok(state) = self.state.lock() else {
+            return false;
+        };
+        state
+            .health
+            .get(symbol)
+            .is_some_and(|h| h.is_stalled(now_ms, self.cfg.stall_ms))
+    }
+
+    pub fn backoff_ms(&self) -> u64 {
+        self.state
+            .lock()
+            .map(|state| state.backoff.delay_ms())
+            .unwrap_or(0)
+    }
+
+    /// Sorted by symbol.
+    pub fn health_snapshot(&self, now_ms: u64) -> Vec<ConnectionHealthSnapshot> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut out = state
+            .health
+            .iter()
+            .map(|(symbol, health)| ConnectionHealthSnapshot {
+                symbol: symbol.clone(),
+                score: health.score(now_ms, self.cfg.stall_ms),
+                stalled: health.is_stalled(now_ms, self.cfg.stall_ms),
+                health: health.clone(),
+            })
+            .collect::<Vec<_>>();
+        out.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(stagger_ms: u64) -> RtdsConnectionPool {
+        let cfg = RtdsPoolConfig {
+            reconnect_stagger_ms: stagger_ms,
+            stall_ms: 10_000,
+            ..RtdsPoolConfig::default()
+        };
+        RtdsConnectionPool::new(cfg, ["btc/usd", "eth/usd", "sol/usd"], 0)
+    }
+
+    #[test]
+    fn shared_backoff_doubles_caps_and_decays() {
+        let mut backoff = SharedBackoff::new(300, 2_000);
+        assert_eq!(backoff.delay_ms(), 0);
+        assert_eq!(backoff.on_failure(), 300);
+        assert_eq!(backoff.on_failure(), 600);
+        assert_eq!(backoff.on_failure(), 1_200);
+        assert_eq!(backoff.on_failure(), 2_000);
+        backoff.on_success();
+        assert_eq!(backoff.delay_ms(), 600);
+        backoff.on_success();
+        backoff.on_success();
+        assert_eq!(backoff.delay_ms(), 0);
+    }
+
+    #[test]
+    fn connects_are_staggered_and_retries_wait_for_shared_backoff() {
+        let pool = pool(250);
+        // Cold start: three symbols get consecutive stagger slots.
+        assert_eq!(pool.reserve_connect_delay_ms("btc/usd", 1_000), 0);
+        assert_eq!(pool.reserve_connect_delay_ms("eth/usd", 1_000), 250);
+        assert_eq!(pool.reserve_connect_delay_ms("sol/usd", 1_000), 500);
+
+        // A failure on one connection raises the backoff for every retrying one.
+        assert_eq!(pool.on_connect_failed("btc/usd", 5_000), 300);
+        pool.on_stall("eth/usd", 5_000);
+        assert_eq!(pool.backoff_ms(), 600);
+        assert_eq!(pool.reserve_connect_delay_ms("btc/usd", 5_000), 600);
+        assert_eq!(pool.reserve_connect_delay_ms("eth/usd", 5_000), 850);
+        // A healthy symbol only waits for the stagger slot.
+        assert_eq!(pool.reserve_connect_delay_ms("sol/usd", 5_000), 1_100);
+
+        pool.on_connected("btc/usd", 6_000);
+        assert_eq!(pool.backoff_ms(), 300);
+    }
+
+    #[test]
+    fn health_score_and_stall_track_ticks_and_streaks() {
+        let pool = pool(0);
+        assert!(!pool.is_stalled("btc/usd", 9_999));
+        assert!(pool.is_stalled("btc/usd", 10_000));
+
+        pool.on_connected("btc/usd", 100);
+        pool.on_tick("btc/usd", 1_000);
+        let snap = pool.health_snapshot(2_000);
+        assert_eq!(snap[0].symbol, "btc/usd");
+        assert!((snap[0].score - 1.0).abs() < 1e-9);
+        assert!(!snap[0].stalled);
+
+        // Half-way through the decay window.
+        let snap = pool.health_snapshot(21_000);
+        assert!((snap[0].score - 0.5).abs() < 1e-9);
+        assert!(snap[0].stalled);
+        assert!(pool.is_stalled("btc/usd", 21_000));
+
+        pool.on_stall("btc/usd", 21_000);
+        pool.on_connected("btc/usd", 21_500);
+        pool.on_tick("btc/usd", 22_000);
+        let h = &pool.health_snapshot(22_000)[0];
+        assert_eq!(h.health.stalls, 1);
+        assert_eq!(h.health.stall_streak, 0);
+        assert!(!h.stalled);
+
+        // Down after a failed reconnect: halved for the drop, halved again for the streak.
+        pool.on_connect_failed("btc/usd", 23_000);
+        let h = &pool.health_snapshot(23_000)[0];
+        assert!((h.score - 0.25).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn acquire_connect_bounds_in_flight_handshakes() {
+        let cfg = RtdsPoolConfig {
+            max_concurrent_connects: 2,
+            reconnect_stagger_ms: 0,
+            ..RtdsPoolConfig::default()
+        };
+        let pool = RtdsConnectionPool::new(cfg, ["a", "b", "c"], 0);
+        let a = pool.acquire_connect("a", 0).await;
+        let _b = pool.acquire_connect("b", 0).await;
+        let third = tokio::time::timeout(Duration::from_millis(20), pool.acquire_connect("c", 0));
+        assert!(third.await.is_err());
+        drop(a);
+        let third = tokio::time::timeout(Duration::from_millis(20), pool.acquire_connect("c", 0));
+        assert!(third.await.is_ok());
+    }
+}
@@ -1,7 +1,9 @@
 use tracing::warn;
 
+use super::config_validation::{report_config_issue, ConfigIssueKind};
 use super::coordinator::{Book, StrategyCoordinator, StrategyInventoryMetrics};
 use super::glft::GlftSignalSnapshot;
+use super::l2_book::L2BookSnapshot;
 use super::messages::{
     BidReason, InventorySnapshot, InventoryState, OfiSnapshot, OrderSlot, TradeDirection,
 };
@@ -569,6 +571,15 @@ impl StrategyKind {
                     StrategyRegistry::supported_names().join(", "),
                     default.as_str()
                 );
+                report_config_issue(
+                    ConfigIssueKind::Invalid,
+                    "PM_STRATEGY",
+                    format!(
+                        "'{}' (supported: {})",
+                        raw,
+                        StrategyRegistry::supported_names().join(", ")
+                    ),
+                );
                 default
             }
         }
@@ -605,6 +616,8 @@ pub(crate) struct StrategyTickInput<'a> {
     pub(crate) ofi: Option<&'a OfiSnapshot>,
     pub(crate) glft: Option<&'a GlftSignalSnapshot>,
     pub(crate) l2_depth: Option<L2BookDepth>,
+    /// Live per-token price ladder from the market WS (`None` when not wired).
+    pub(crate) l2_book: Option<&'a L2BookSnapshot>,
 }
 
 use std::collections::HashMap;
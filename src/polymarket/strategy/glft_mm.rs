@@ -330,6 +330,7 @@ mod tests {
             quote_regime: QuoteRegime::Aligned,
             reference_confidence: 1.0,
             reference_health: crate::polymarket::glft::ReferenceHealth::Healthy,
+            reference_sources: 1,
             drift_mode: DriftMode::Normal,
             hard_basis_unstable: false,
             ready: true,
@@ -383,6 +384,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(quotes.yes_buy.is_none());
@@ -425,6 +427,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(quotes.yes_buy.is_some(), "expected YES buy slot");
@@ -468,6 +471,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         let yes_buy = quotes.yes_buy.expect("YES buy quote expected");
@@ -515,6 +519,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -561,6 +566,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -617,6 +623,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -661,6 +668,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -705,6 +713,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -753,6 +762,7 @@ mod tests {
                 ofi: None,
                 glft: Some(&snapshot),
                 l2_depth: None,
+                l2_book: None,
             },
         );
         assert!(
@@ -3,6 +3,7 @@ use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::polymarket::coordinator::{StrategyCoordinator, PAIR_ARB_NET_EPS};
+use crate::polymarket::l2_book::L2BookSnapshot;
 use crate::polymarket::messages::{BidReason, TradeDirection};
 use crate::polymarket::pair_ledger::{urgency_budget_shadow_5m, PairLedgerSnapshot, PairTranche};
 use crate::polymarket::types::Side;
@@ -454,6 +455,21 @@ fn get_p5_thin_risk(tuning_cap: f64, l2_depth: Option<crate::polymarket::strateg
     }
 }
 
+/// Taker close limit for `size`: the live-ladder sweep price when the visible
+/// asks fill the whole clip at or under `ceiling`, otherwise top-of-book.
+fn pgt_live_taker_close_limit(
+    l2_book: Option<&L2BookSnapshot>,
+    hedge_side: Side,
+    size: f64,
+    best_ask: f64,
+    ceiling: f64,
+) -> f64 {
+    l2_book
+        .and_then(|book| book.side(hedge_side).ask_sweep_price(size))
+        .filter(|sweep| *sweep <= ceiling + 1e-9)
+        .map_or(best_ask, |sweep| sweep.max(best_ask))
+}
+
 struct CompletionPlan {
     intent: StrategyIntent,
     taker_shadow_would_close: bool,
@@ -1574,7 +1590,22 @@ impl PairGatedTrancheStrategy {
                 || taker_insurance_would_close
                 || last_chance_forced_taker_close);
         let taker_close_limit = if taker_shadow_would_close {
-            Some(coordinator.safe_price(best_ask))
+            let sweep_ceiling = [
+                profit_taker_would_close.then_some(positive_edge_ceiling.min(taker_close_ceiling)),
+                breakeven_taker_would_close.then_some(funded_loss_ceiling.min(taker_close_ceiling)),
+                tail_insurance_ceiling.filter(|_| tail_insurance_taker_would_close),
+                taker_insurance_ceiling.filter(|_| taker_insurance_would_close),
+            ]
+            .into_iter()
+            .flatten()
+            .fold(best_ask, f64::max);
+            Some(coordinator.safe_price(pgt_live_taker_close_limit(
+                input.l2_book,
+                hedge_side,
+                size,
+                best_ask,
+                sweep_ceiling,
+            )))
         } else {
             None
         };
@@ -1725,7 +1756,7 @@ impl PairGatedTrancheStrategy {
                 .clamp(tuning.min_clip_qty, tuning.max_clip_qty);
         }
 
-        let session_mult = session_clip_mult_utc();
+        let session_mult = session_clip_mult_utc(coordinator.clock().unix_now_secs());
         let imbalance_mult = imbalance_clip_mult(coordinator, input, active);
         let trade_index = input.episode_metrics.round_buy_fill_count.max(1) as f64;
         let trade_index_mult = (1.0 - 0.05 * (trade_index - 1.0)).max(0.70);
@@ -2762,11 +2793,7 @@ fn imbalance_clip_mult(
     }
 }
 
-fn session_clip_mult_utc() -> f64 {
-    let now_secs = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
+fn session_clip_mult_utc(now_secs: u64) -> f64 {
     let hour = ((now_secs / 3600) % 24) as u8;
     if (16..=22).contains(&hour) {
         1.15
@@ -3188,6 +3215,53 @@ mod profile_tests {
         );
     }
 
+    #[test]
+    fn live_taker_close_limit_sweeps_ladder_only_within_ceiling() {
+        use crate::polymarket::l2_book::{L2Ladder, PriceLevel, L2_SNAPSHOT_MAX_LEVELS};
+
+        let mut ladder = L2Ladder::default();
+        ladder.apply_snapshot(
+            &[],
+            &[
+                PriceLevel {
+                    price: 0.45,
+                    size: 4.0,
+                },
+                PriceLevel {
+                    price: 0.46,
+                    size: 6.0,
+                },
+            ],
+        );
+        let book = L2BookSnapshot {
+            no: ladder.to_side_book(L2_SNAPSHOT_MAX_LEVELS),
+            ..L2BookSnapshot::default()
+        };
+        assert_eq!(
+            pgt_live_taker_close_limit(None, Side::No, 10.0, 0.45, 0.50),
+            0.45
+        );
+        assert_eq!(
+            pgt_live_taker_close_limit(Some(&book), Side::No, 3.0, 0.45, 0.50),
+            0.45
+        );
+        assert_eq!(
+            pgt_live_taker_close_limit(Some(&book), Side::No, 10.0, 0.45, 0.50),
+            0.46,
+            "full clip needs the second level"
+        );
+        assert_eq!(
+            pgt_live_taker_close_limit(Some(&book), Side::No, 10.0, 0.45, 0.455),
+            0.45,
+            "sweep beyond the qualifying ceiling falls back to top-of-book"
+        );
+        assert_eq!(
+            pgt_live_taker_close_limit(Some(&book), Side::No, 12.0, 0.45, 0.50),
+            0.45,
+            "visible ladder too thin"
+        );
+    }
+
     #[test]
     fn xuan_ladder_reopens_after_only_high_quality_rescue_close() {
         let inv = InventoryState::default();
@@ -3231,6 +3305,7 @@ mod profile_tests {
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         };
         let tuning = PgtTuning::xuan_ladder_v1();
 
@@ -3328,6 +3403,7 @@ mod profile_tests {
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         };
         let tuning = PgtTuning::xuan_ladder_v1();
 
@@ -3674,6 +3750,7 @@ mod tests {
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         };
 
         let mut quotes = StrategyQuotes::default();
@@ -3708,6 +3785,7 @@ mod tests {
             ofi: None,
             glft: None,
             l2_depth: None,
+            l2_book: None,
         };
 
         let mut quotes_maker = StrategyQuotes::default();
@@ -1,5 +1,3 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-
 use crate::polymarket::coordinator::StrategyCoordinator;
 
 use super::{QuoteStrategy, StrategyKind, StrategyQuotes, StrategyTickInput};
@@ -24,21 +22,21 @@ impl QuoteStrategy for PostCloseHypeStrategy {
         if !cfg.oracle_lag_sniping.market_enabled || cfg.oracle_lag_sniping.window_secs == 0 {
             return StrategyQuotes::default();
         }
-        if !is_in_post_close_window(cfg.market_end_ts, cfg.oracle_lag_sniping.window_secs) {
+        if !is_in_post_close_window(
+            coordinator.clock().unix_now_secs(),
+            cfg.market_end_ts,
+            cfg.oracle_lag_sniping.window_secs,
+        ) {
             return StrategyQuotes::default();
         }
         StrategyQuotes::default()
     }
 }
 
-fn is_in_post_close_window(market_end_ts: Option<u64>, window_secs: u64) -> bool {
+fn is_in_post_close_window(now: u64, market_end_ts: Option<u64>, window_secs: u64) -> bool {
     let Some(end_ts) = market_end_ts else {
         return false;
     };
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_else(|_| Duration::from_secs(0))
-        .as_secs();
     now >= end_ts && now < end_ts.saturating_add(window_secs)
 }
 
@@ -0,0 +1,841 @@
+//! Parameter sweeps over the backtest harness.
+//!
+//! A sweep spec lists `PM_*` keys (or bare lowercase names, as in the config
+//! file) with candidate values. Because every `CoordinatorConfig` /
+//! `InventoryConfig` field is already read from env, each combination is
+//! materialised by applying its overrides to the environment, calling the
+//! normal `from_env()` builders and restoring the environment — so any field
+//! can be swept without a second schema. Configs are built up front on the
+//! calling thread; only the backtests themselves run in parallel, so a key is
+//! only sweepable when one of those builders reads it (see
+//! [`SWEEPABLE_ENV_KEYS`]); anything else would silently run the base config.
+//!
+//! Results are reported per combination over all windows and, when a
+//! walk-forward split is requested, per fold: the best combination on each
+//! train slice is scored on the following test slice.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Context};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use super::backtest::{run_window, BacktestConfig, BacktestWindow, WindowReport};
+use super::config_file::normalize_key;
+use super::config_validation::{known_env_kind, suggest_known_key};
+use super::recorder::ymd_utc;
+
+/// Env key → value applied for one combination.
+pub type SweepOverrides = BTreeMap<String, String>;
+
+/// Hard cap on grid expansion so a typo cannot queue millions of runs.
+const MAX_SWEEP_COMBINATIONS: usize = 100_000;
+
+/// Keys read while building a `BacktestConfig` (`CoordinatorConfig`,
+/// `InventoryConfig` and `FeeSchedule` `from_env`). Keys read by live-only
+/// actors (OFI, PGT shadow profiles, runtime risk caps) never reach a backtest.
+pub const SWEEPABLE_ENV_KEYS: &[&str] = &[
+    "PM_AS_SKEW_FACTOR",
+    "PM_AS_TIME_DECAY_K",
+    "PM_BID_SIZE",
+    "PM_COMPLETION_FIRST_GATE_DEFAULTS",
+    "PM_COMPLETION_FIRST_MODE",
+    "PM_COORD_WATCHDOG_MS",
+    "PM_DEBOUNCE_MS",
+    "PM_DIP_BUY_MAX_ENTRY_PRICE",
+    "PM_ENDGAME_EDGE_EXIT_MULT",
+    "PM_ENDGAME_EDGE_KEEP_MULT",
+    "PM_ENDGAME_FREEZE_SECS",
+    "PM_ENDGAME_HARD_CLOSE_SECS",
+    "PM_ENDGAME_MAKER_REPAIR_MIN_SECS",
+    "PM_ENDGAME_SOFT_CLOSE_SECS",
+    "PM_FEE_CATEGORY",
+    "PM_GLFT_GAMMA",
+    "PM_GLFT_OFI_ALPHA",
+    "PM_GLFT_OFI_SPREAD_BETA",
+    "PM_GLFT_XI",
+    "PM_HEDGE_DEBOUNCE_MS",
+    "PM_HEDGE_MIN_MARKETABLE_MAX_EXTRA",
+    "PM_HEDGE_MIN_MARKETABLE_MAX_EXTRA_PCT",
+    "PM_HEDGE_MIN_MARKETABLE_NOTIONAL",
+    "PM_HEDGE_ROUND_UP",
+    "PM_MAKER_REBATE_SHARE",
+    "PM_MAX_LOSS_PCT",
+    "PM_MAX_NET_DIFF",
+    "PM_MAX_PORTFOLIO_COST",
+    "PM_MIN_HALF_SPREAD_TICKS",
+    "PM_MIN_HEDGE_SIZE",
+    "PM_MIN_ORDER_SIZE",
+    "PM_OPEN_PAIR_BAND",
+    "PM_ORACLE_LAG_ARBITER_BOOK_MAX_AGE_MS",
+    "PM_ORACLE_LAG_CROSS_MARKET_ARBITER_ENABLED",
+    "PM_ORACLE_LAG_LAB_ONLY",
+    "PM_ORACLE_LAG_MAX_ORDER_NOTIONAL_USDC",
+    "PM_PAIR_ARB_MIN_OPEN_EDGE_FOR_RISK_ADD",
+    "PM_PAIR_ARB_PAIR_COST_SAFETY_MARGIN",
+    "PM_PAIR_ARB_RISK_OPEN_CUTOFF_SECS",
+    "PM_PAIR_ARB_TIER_1_MULT",
+    "PM_PAIR_ARB_TIER_2_MULT",
+    "PM_PAIR_ARB_TIER_MODE",
+    "PM_PAIR_TARGET",
+    "PM_POST_CLOSE_WINDOW_SECS",
+    "PM_POST_ONLY_EXTRA_TIGHT_TICKS",
+    "PM_POST_ONLY_SAFETY_TICKS",
+    "PM_POST_ONLY_TIGHT_SPREAD_TICKS",
+    "PM_REPRICE_THRESHOLD",
+    "PM_RESIDUAL_UNWIND_ENABLED",
+    "PM_RESIDUAL_UNWIND_MIN_EDGE",
+    "PM_STALE_TTL_MS",
+    "PM_STRATEGY",
+    "PM_STRATEGY_METRICS_LOG_SECS",
+    "PM_TAKER_FEE_RATE",
+    "PM_TICK_SIZE",
+    "PM_TOXIC_RECOVERY_HOLD_MS",
+    "PM_XUAN_B27_DPLUS_ALLOW_PASSIVE_TAKER",
+    "PM_XUAN_B27_DPLUS_EDGE",
+    "PM_XUAN_B27_DPLUS_EXPLICIT_CANARY_APPROVAL",
+    "PM_XUAN_B27_DPLUS_IMBALANCE_QTY_CAP",
+    "PM_XUAN_B27_DPLUS_MARKET_SLUG",
+    "PM_XUAN_B27_DPLUS_MAX_ACTIVE_MARKETS",
+    "PM_XUAN_B27_DPLUS_MAX_LIVE_ORDERS",
+    "PM_XUAN_B27_DPLUS_MAX_OPEN_COST_USDC",
+    "PM_XUAN_B27_DPLUS_MODE",
+    "PM_XUAN_B27_DPLUS_OMS_ADAPTER_ENABLED",
+    "PM_XUAN_B27_DPLUS_POST_ONLY",
+    "PM_XUAN_B27_DPLUS_RUNTIME_WIRING_ENABLED",
+    "PM_XUAN_B27_DPLUS_SALVAGE_NET_CAP",
+    "PM_XUAN_B27_DPLUS_SEED_PX_HI",
+    "PM_XUAN_B27_DPLUS_SEED_PX_LO",
+    "PM_XUAN_B27_DPLUS_STOP_ON_UNKNOWN",
+    "PM_XUAN_B27_DPLUS_TARGET_QTY",
+];
+
+/// Reject keys the backtest would ignore: unregistered keys (typos) and
+/// registered keys no backtest config builder reads.
+fn check_sweepable_key(key: &str) -> anyhow::Result<()> {
+    if known_env_kind(key).is_none() {
+        return match suggest_known_key(key) {
+            Some(hint) => Err(anyhow!(
+                "sweep param {} is not a known env key (did you mean {}?)",
+                key,
+                hint
+            )),
+            None => Err(anyhow!("sweep param {} is not a known env key", key)),
+        };
+    }
+    if !SWEEPABLE_ENV_KEYS.contains(&key) {
+        bail!(
+            "sweep param {} is not read by the backtest config builders",
+            key
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepValues {
+    /// Explicit candidates (`KEY=a,b,c`); strings, so booleans and enums work too.
+    List(Vec<String>),
+    /// Numeric range (`KEY=lo:hi[:step]`). Grid mode needs a step; random mode
+    /// samples uniformly. Integer bounds yield integer values.
+    Range {
+        lo: f64,
+        hi: f64,
+        step: Option<f64>,
+        integer: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepParam {
+    pub key: String,
+    pub values: SweepValues,
+}
+
+impl SweepParam {
+    /// Parse `KEY=a,b,c` or `KEY=lo:hi[:step]`.
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let (key, spec) = raw
+            .split_once('=')
+            .ok_or_else(|| anyhow!("sweep param '{}' must look like KEY=values", raw))?;
+        let key = normalize_key(key, None);
+        check_sweepable_key(&key)?;
+        let spec = spec.trim();
+        if spec.is_empty() {
+            bail!("sweep param {} has no values", key);
+        }
+        if spec.contains(':') {
+            let parts: Vec<&str> = spec.split(':').map(str::trim).collect();
+            if !(2..=3).contains(&parts.len()) {
+                bail!("sweep range for {} must be lo:hi[:step]", key);
+            }
+            let parse = |v: &str| {
+                v.parse::<f64>()
+                    .ok()
+                    .filter(|v| v.is_finite())
+                    .ok_or_else(|| {
+                        anyhow!("sweep range for {} has a non-numeric bound '{}'", key, v)
+                    })
+            };
+            let (lo, hi) = (parse(parts[0])?, parse(parts[1])?);
+            if hi < lo {
+                bail!("sweep range for {} has hi < lo", key);
+            }
+            let step = parts.get(2).map(|v| parse(v)).transpose()?;
+            if step.is_some_and(|s| s <= 0.0) {
+                bail!("sweep range step for {} must be > 0", key);
+            }
+            let integer = parts
+                .iter()
+                .all(|v| !v.contains('.') && !v.contains(['e', 'E']));
+            return Ok(Self {
+                key,
+                values: SweepValues::Range {
+                    lo,
+                    hi,
+                    step,
+                    integer,
+                },
+            });
+        }
+        let values: Vec<String> = spec
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if values.is_empty() {
+            bail!("sweep param {} has no values", key);
+        }
+        Ok(Self {
+            key,
+            values: SweepValues::List(values),
+        })
+    }
+
+    fn grid_values(&self) -> anyhow::Result<Vec<String>> {
+        match &self.values {
+            SweepValues::List(values) => Ok(values.clone()),
+            SweepValues::Range {
+                lo,
+                hi,
+                step,
+                integer,
+            } => {
+                let step = step.ok_or_else(|| {
+                    anyhow!("grid sweep of {} needs a step (lo:hi:step)", self.key)
+                })?;
+                let count = ((hi - lo) / step + 1e-9).floor() as usize + 1;
+                if count > MAX_SWEEP_COMBINATIONS {
+                    bail!("grid range for {} expands to {} values", self.key, count);
+                }
+                Ok((0..count)
+                    .map(|i| format_value(lo + step * i as f64, *integer))
+                    .collect())
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> String {
+        match &self.values {
+            SweepValues::List(values) => values[rng.gen_range(0..values.len())].clone(),
+            SweepValues::Range {
+                lo,
+                hi,
+                step,
+                integer,
+            } => {
+                let mut v = if hi > lo {
+                    rng.gen_range(*lo..=*hi)
+                } else {
+                    *lo
+                };
+                if let Some(step) = step {
+                    v = (lo + ((v - lo) / step).round() * step).min(*hi);
+                }
+                format_value(v, *integer)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepMode {
+    Grid,
+    Random { samples: usize, seed: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepSpec {
+    pub params: Vec<SweepParam>,
+    pub mode: SweepMode,
+}
+
+impl SweepSpec {
+    /// Every combination to run. Grid order is row-major in `params` order;
+    /// random samples are deduplicated.
+    pub fn combinations(&self) -> anyhow::Result<Vec<SweepOverrides>> {
+        if self.params.is_empty() {
+            return Ok(vec![SweepOverrides::new()]);
+        }
+        match self.mode {
+            SweepMode::Grid => {
+                let mut combos = vec![SweepOverrides::new()];
+                for param in &self.params {
+                    let values = param.grid_values()?;
+                    if combos.len().saturating_mul(values.len()) > MAX_SWEEP_COMBINATIONS {
+                        bail!(
+                            "grid expands beyond {} combinations",
+                            MAX_SWEEP_COMBINATIONS
+                        );
+                    }
+                    combos = combos
+                        .into_iter()
+                        .flat_map(|combo| {
+                            values.iter().map(move |v| {
+                                let mut next = combo.clone();
+                                next.insert(param.key.clone(), v.clone());
+                                next
+                            })
+                        })
+                        .collect();
+                }
+                Ok(combos)
+            }
+            SweepMode::Random { samples, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut seen = BTreeSet::new();
+                let mut combos = Vec::new();
+                // Small discrete spaces cannot yield `samples` distinct draws.
+                for _ in 0..samples.saturating_mul(20) {
+                    if combos.len() >= samples {
+                        break;
+                    }
+                    let combo: SweepOverrides = self
+                        .params
+                        .iter()
+                        .map(|p| (p.key.clone(), p.sample(&mut rng)))
+                        .collect();
+                    if seen.insert(combo.clone()) {
+                        combos.push(combo);
+                    }
+                }
+                Ok(combos)
+            }
+        }
+    }
+}
+
+fn format_value(v: f64, integer: bool) -> String {
+    if integer {
+        format!("{}", v.round() as i64)
+    } else {
+        // Trim float noise from `lo + step * i`.
+        let s = format!("{:.10}", v);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Apply `overrides` to the process environment, build, then restore.
+///
+/// Not thread-safe with respect to other env readers: call it before any
+/// backtest worker starts (the sweep runner only reads prebuilt configs).
+pub fn with_env_overrides<T>(overrides: &SweepOverrides, build: impl FnOnce() -> T) -> T {
+    let previous: Vec<(String, Option<String>)> = overrides
+        .keys()
+        .map(|k| (k.clone(), std::env::var(k).ok()))
+        .collect();
+    for (k, v) in overrides {
+        std::env::set_var(k, v);
+    }
+    let out = build();
+    for (k, v) in previous {
+        match v {
+            Some(v) => std::env::set_var(&k, v),
+            None => std::env::remove_var(&k),
+        }
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────
+// Walk-forward splits
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkForwardSpec {
+    pub train_days: usize,
+    pub test_days: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WalkForwardFold {
+    pub index: usize,
+    pub train_dates: Vec<String>,
+    pub test_dates: Vec<String>,
+}
+
+/// UTC date (`YYYY-MM-DD`) a window belongs to, by its market end.
+pub fn window_date(window: &BacktestWindow) -> String {
+    ymd_utc(window.end_ts_ms)
+}
+
+/// Rolling folds over sorted distinct dates: train on `train_days`, test on the
+/// next `test_days`, then roll forward by `test_days`.
+pub fn walk_forward_folds(dates: &[String], spec: WalkForwardSpec) -> Vec<WalkForwardFold> {
+    let dates: Vec<String> = dates
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let (train, test) = (spec.train_days.max(1), spec.test_days.max(1));
+    let mut folds = Vec::new();
+    let mut start = 0;
+    while start + train + test <= dates.len() {
+        folds.push(WalkForwardFold {
+            index: folds.len(),
+            train_dates: dates[start..start + train].to_vec(),
+            test_dates: dates[start + train..start + train + test].to_vec(),
+        });
+        start += test;
+    }
+    folds
+}
+
+// ─────────────────────────────────────────────────────────
+// Metrics
+// ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SweepMetrics {
+    pub windows: usize,
+    pub active_windows: usize,
+    pub fills: usize,
+    pub maker_fills: usize,
+    pub taker_fills: usize,
+    pub fees: f64,
+    pub rebates: f64,
+    pub pnl: f64,
+    pub avg_pnl: f64,
+    /// Largest peak-to-trough fall of cumulative PnL, windows in time order.
+    pub max_drawdown: f64,
+    pub residual_qty: f64,
+    /// Unpaired shares over all shares held at settlement.
+    pub residual_exposure_ratio: f64,
+}
+
+impl SweepMetrics {
+    pub fn from_reports<'a>(reports: impl IntoIterator<Item = &'a WindowReport>) -> Self {
+        let mut m = Self::default();
+        let (mut cum, mut peak, mut held) = (0.0_f64, 0.0_f64, 0.0_f64);
+        for r in reports {
+            m.windows += 1;
+            if r.fills > 0 {
+                m.active_windows += 1;
+            }
+            m.fills += r.fills;
+            m.maker_fills += r.maker_fills;
+            m.taker_fills += r.taker_fills;
+            m.fees += r.fees;
+            m.rebates += r.rebates;
+            m.pnl += r.pnl;
+            m.residual_qty += r.residual_qty;
+            held += r.yes_qty.max(0.0) + r.no_qty.max(0.0);
+            cum += r.pnl;
+            peak = peak.max(cum);
+            m.max_drawdown = m.max_drawdown.max(peak - cum);
+        }
+        if m.windows > 0 {
+            m.avg_pnl = m.pnl / m.windows as f64;
+        }
+        if held > 1e-9 {
+            m.residual_exposure_ratio = m.residual_qty / held;
+        }
+        m
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FoldMetrics {
+    pub fold: usize,
+    pub train: SweepMetrics,
+    pub test: SweepMetrics,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepComboResult {
+    pub index: usize,
+    pub overrides: SweepOverrides,
+    pub all: SweepMetrics,
+    pub folds: Vec<FoldMetrics>,
+}
+
+/// Out-of-sample pick of one walk-forward fold.
+#[derive(Debug, Clone, Serialize)]
+pub struct WalkForwardSelection {
+    pub fold: WalkForwardFold,
+    pub best_combo: usize,
+    pub train: SweepMetrics,
+    pub test: SweepMetrics,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepReport {
+    pub combos: Vec<SweepComboResult>,
+    pub walk_forward: Vec<WalkForwardSelection>,
+    /// Chained test-slice metrics of the per-fold selections.
+    pub out_of_sample: Option<SweepMetrics>,
+}
+
+impl SweepReport {
+    /// Aggregate per-window reports (`reports[combo][window]`, windows in the
+    /// same order as `windows`). Train selection maximises total PnL.
+    pub fn build(
+        combos: &[SweepOverrides],
+        windows: &[BacktestWindow],
+        reports: &[Vec<WindowReport>],
+        walk_forward: Option<WalkForwardSpec>,
+    ) -> Self {
+        let dates: Vec<String> = windows.iter().map(window_date).collect();
+        let folds = walk_forward
+            .map(|spec| walk_forward_folds(&dates, spec))
+            .unwrap_or_default();
+        let slice = |combo: usize, slice_dates: &[String]| {
+            SweepMetrics::from_reports(
+                reports[combo]
+                    .iter()
+                    .zip(&dates)
+                    .filter(|(_, d)| slice_dates.contains(d))
+                    .map(|(r, _)| r),
+            )
+        };
+
+        let results: Vec<SweepComboResult> = combos
+            .iter()
+            .enumerate()
+            .map(|(index, overrides)| SweepComboResult {
+                index,
+                overrides: overrides.clone(),
+                all: SweepMetrics::from_reports(&reports[index]),
+                folds: folds
+                    .iter()
+                    .map(|fold| FoldMetrics {
+                        fold: fold.index,
+                        train: slice(index, &fold.train_dates),
+                        test: slice(index, &fold.test_dates),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut selections = Vec::new();
+        let mut oos_reports: Vec<&WindowReport> = Vec::new();
+        for fold in &folds {
+            let Some(best) = results.iter().max_by(|a, b| {
+                a.folds[fold.index]
+                    .train
+                    .pnl
+                    .total_cmp(&b.folds[fold.index].train.pnl)
+                    .then(b.index.cmp(&a.index))
+            }) else {
+                continue;
+            };
+            oos_reports.extend(
+                reports[best.index]
+                    .iter()
+                    .zip(&dates)
+                    .filter(|(_, d)| fold.test_dates.contains(d))
+                    .map(|(r, _)| r),
+            );
+            selections.push(WalkForwardSelection {
+                fold: fold.clone(),
+                best_combo: best.index,
+                train: best.folds[fold.index].train.clone(),
+                test: best.folds[fold.index].test.clone(),
+            });
+        }
+        Self {
+            combos: results,
+            out_of_sample: (!selections.is_empty())
+                .then(|| SweepMetrics::from_reports(oos_reports)),
+            walk_forward: selections,
+        }
+    }
+
+    /// Combination indices by descending total PnL.
+    pub fn ranked(&self) -> Vec<&SweepComboResult> {
+        let mut ranked: Vec<&SweepComboResult> = self.combos.iter().collect();
+        ranked.sort_by(|a, b| b.all.pnl.total_cmp(&a.all.pnl).then(a.index.cmp(&b.index)));
+        ranked
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Runner
+// ─────────────────────────────────────────────────────────
+
+/// Backtest every (config, window) pair on `jobs` worker threads, each with its
+/// own current-thread runtime. Returns `reports[config][window]`.
+pub fn run_sweep(
+    configs: &[BacktestConfig],
+    windows: &[BacktestWindow],
+    jobs: usize,
+) -> anyhow::Result<Vec<Vec<WindowReport>>> {
+    let total = configs.len() * windows.len();
+    let slots: Mutex<Vec<Option<WindowReport>>> = Mutex::new(vec![None; total]);
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let jobs = jobs.clamp(1, total.max(1));
+
+    std::thread::scope(|scope| -> anyhow::Result<()> {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| -> anyhow::Result<()> {
+                    let rt = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .context("sweep worker runtime")?;
+                    loop {
+                        let task = next.fetch_add(1, Ordering::Relaxed);
+                        if task >= total {
+                            return Ok(());
+                        }
+                        let (combo, window) = (task / windows.len(), task % windows.len());
+                        let report = rt.block_on(run_window(&configs[combo], &windows[window]));
+                        slots.lock().unwrap_or_else(|e| e.into_inner())[task] = Some(report);
+                        let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+                        if finished.is_multiple_of(windows.len().max(1)) || finished == total {
+                            tracing::info!("🧪 sweep progress {}/{} runs", finished, total);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker
+                .join()
+                .map_err(|_| anyhow!("sweep worker panicked"))??;
+        }
+        Ok(())
+    })?;
+
+    let mut slots = slots.into_inner().unwrap_or_else(|e| e.into_inner());
+    Ok((0..configs.len())
+        .map(|combo| {
+            slots[combo * windows.len()..(combo + 1) * windows.len()]
+                .iter_mut()
+                .map(|slot| slot.take().unwrap_or_default())
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(pnl: f64, yes: f64, no: f64) -> WindowReport {
+        WindowReport {
+            fills: 2,
+            pnl,
+            yes_qty: yes,
+            no_qty: no,
+            residual_qty: (yes - no).abs(),
+            ..WindowReport::default()
+        }
+    }
+
+    fn window_on(day: u64) -> BacktestWindow {
+        BacktestWindow {
+            id: format!("w{day}"),
+            end_ts_ms: 1_770_000_000_000 + day * 86_400_000,
+            outcome: None,
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn grid_and_random_specs_expand_over_env_keys() {
+        let spec = SweepSpec {
+            params: vec![
+                SweepParam::parse("pair_target=0.95:0.97:0.01").unwrap(),
+                SweepParam::parse("PM_BID_SIZE=5,10").unwrap(),
+            ],
+            mode: SweepMode::Grid,
+        };
+        let combos = spec.combinations().unwrap();
+        assert_eq!(combos.len(), 6);
+        assert_eq!(combos[0]["PM_PAIR_TARGET"], "0.95");
+        assert_eq!(combos[0]["PM_BID_SIZE"], "5");
+        assert_eq!(combos[5]["PM_PAIR_TARGET"], "0.97");
+        assert_eq!(combos[5]["PM_BID_SIZE"], "10");
+        assert!(SweepParam::parse("PM_MAX_NET_DIFF=1:0").is_err());
+
+        let random = SweepSpec {
+            params: vec![
+                SweepParam::parse("PM_MAX_NET_DIFF=5:20").unwrap(),
+                SweepParam::parse("PM_PAIR_TARGET=0.9:1.0").unwrap(),
+            ],
+            mode: SweepMode::Random {
+                samples: 8,
+                seed: 7,
+            },
+        };
+        let combos = random.combinations().unwrap();
+        assert_eq!(combos.len(), 8);
+        assert_eq!(combos, random.combinations().unwrap(), "seeded");
+        for combo in &combos {
+            let net: i64 = combo["PM_MAX_NET_DIFF"].parse().unwrap();
+            assert!((5..=20).contains(&net));
+            let target: f64 = combo["PM_PAIR_TARGET"].parse().unwrap();
+            assert!((0.9..=1.0).contains(&target));
+        }
+        // Range without a step cannot be gridded.
+        let bad = SweepSpec {
+            params: vec![SweepParam::parse("PM_PAIR_TARGET=0.9:1.0").unwrap()],
+            mode: SweepMode::Grid,
+        };
+        assert!(bad.combinations().is_err());
+    }
+
+    #[test]
+    fn sweep_keys_must_be_read_by_the_backtest_config() {
+        for key in SWEEPABLE_ENV_KEYS {
+            assert!(known_env_kind(key).is_some(), "{key} is not registered");
+        }
+        for raw in [
+            "PM_OFI_WINDOW_MS=1000,2000",
+            "PM_PGT_SHADOW_PROFILE=a,b",
+            "PM_NAGI_5M_P5_RISK_CAP=5:10",
+        ] {
+            let err = SweepParam::parse(raw).unwrap_err().to_string();
+            assert!(err.contains("not read by the backtest"), "{raw}: {err}");
+        }
+        let err = SweepParam::parse("PM_PAIR_TARGT=0.9,0.95")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("did you mean PM_PAIR_TARGET"), "{err}");
+        assert!(SweepParam::parse("fee_category=crypto,sports").is_ok());
+    }
+
+    #[test]
+    fn metrics_track_drawdown_and_residual_ratio() {
+        let reports = [
+            report(1.0, 10.0, 10.0),
+            report(-3.0, 10.0, 0.0),
+            report(0.5, 5.0, 5.0),
+        ];
+        let m = SweepMetrics::from_reports(&reports);
+        assert_eq!(m.windows, 3);
+        assert_eq!(m.fills, 6);
+        assert!((m.pnl + 1.5).abs() < 1e-12);
+        assert!((m.max_drawdown - 3.0).abs() < 1e-12);
+        assert!((m.residual_exposure_ratio - 10.0 / 40.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn walk_forward_picks_best_train_combo_and_scores_next_slice() {
+        let windows: Vec<BacktestWindow> = (0..4).map(window_on).collect();
+        let dates: Vec<String> = windows.iter().map(window_date).collect();
+        let folds = walk_forward_folds(
+            &dates,
+            WalkForwardSpec {
+                train_days: 2,
+                test_days: 1,
+            },
+        );
+        assert_eq!(folds.len(), 2);
+        assert_eq!(folds[1].train_dates, dates[1..3].to_vec());
+        assert_eq!(folds[1].test_dates, vec![dates[3].clone()]);
+
+        let combos = vec![SweepOverrides::new(), SweepOverrides::new()];
+        // Combo 0 wins early days, combo 1 wins later ones.
+        let reports = vec![
+            vec![
+                report(2.0, 1.0, 1.0),
+                report(2.0, 1.0, 1.0),
+                report(-1.0, 1.0, 1.0),
+                report(-1.0, 1.0, 1.0),
+            ],
+            vec![
+                report(0.0, 1.0, 1.0),
+                report(0.0, 1.0, 1.0),
+                report(3.0, 1.0, 1.0),
+                report(1.0, 1.0, 1.0),
+            ],
+        ];
+        let sweep = SweepReport::build(
+            &combos,
+            &windows,
+            &reports,
+            Some(WalkForwardSpec {
+                train_days: 2,
+                test_days: 1,
+            }),
+        );
+        assert_eq!(sweep.walk_forward[0].best_combo, 0);
+        assert!((sweep.walk_forward[0].test.pnl + 1.0).abs() < 1e-12);
+        assert_eq!(sweep.walk_forward[1].best_combo, 1);
+        assert!((sweep.walk_forward[1].test.pnl - 1.0).abs() < 1e-12);
+        let oos = sweep.out_of_sample.clone().unwrap();
+        assert_eq!(oos.windows, 2);
+        assert!(oos.pnl.abs() < 1e-12);
+        assert_eq!(sweep.ranked()[0].index, 1);
+    }
+
+    #[test]
+    fn run_sweep_backtests_every_combo_in_parallel() {
+        use crate::polymarket::backtest::{BacktestEvent, BacktestInput};
+        use crate::polymarket::coordinator::CoordinatorConfig;
+        use crate::polymarket::inventory::InventoryConfig;
+        use crate::polymarket::messages::MarketDataMsg;
+        use crate::polymarket::types::Side;
+
+        let book = |yb: f64, ya: f64, nb: f64, na: f64| MarketDataMsg::BookTick {
+            yes_bid: yb,
+            yes_ask: ya,
+            no_bid: nb,
+            no_ask: na,
+            depth: None,
+            ts: std::time::Instant::now(),
+        };
+        let start_ms = 1_700_000_000_000;
+        let mut events: Vec<BacktestEvent> = (0..20u64)
+            .map(|i| BacktestEvent {
+                ts_ms: start_ms + i * 1_000,
+                input: BacktestInput::Market(book(0.47, 0.49, 0.49, 0.51)),
+            })
+            .collect();
+        events.push(BacktestEvent {
+            ts_ms: start_ms + 21_000,
+            input: BacktestInput::Market(book(0.20, 0.21, 0.20, 0.21)),
+        });
+        let window = BacktestWindow {
+            id: "synthetic".to_string(),
+            end_ts_ms: start_ms + 300_000,
+            outcome: Some(Side::Yes),
+            events,
+        };
+        let configs: Vec<BacktestConfig> = [2.0, 4.0]
+            .into_iter()
+            .map(|bid_size| {
+                let coordinator = CoordinatorConfig {
+                    bid_size,
+                    dry_run: true,
+                    ..CoordinatorConfig::default()
+                };
+                BacktestConfig::new(coordinator, InventoryConfig::default())
+            })
+            .collect();
+        let windows = vec![window.clone(), window];
+
+        let reports = run_sweep(&configs, &windows, 3).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|combo| combo.len() == 2));
+        assert!(reports[0][0].maker_fills > 0, "{:?}", reports[0][0]);
+        assert_eq!(reports[0][0].pnl, reports[0][1].pnl, "deterministic");
+    }
+}
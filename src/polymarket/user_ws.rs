@@ -23,6 +23,7 @@ use tokio::time::sleep;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, info, warn};
 
+use super::fees::{parse_fee_rate_bps, LiquidityRole};
 use super::messages::{
     FillEvent, FillSource, FillStatus, OrderSlot, TradeDirection, XuanB27DplusSourceTruthEvent,
 };
@@ -606,6 +607,12 @@ impl UserWsListener {
                 price,
                 status,
                 source: FillSource::UserWs,
+                liquidity: LiquidityRole::Maker,
+                // The rebate is a share of the taker's fee, so it follows the
+                // trade-level rate; the maker order's own rate is usually 0.
+                fee_rate_bps: fee_rate_bps_field(val, None)
+                    .as_deref()
+                    .and_then(parse_fee_rate_bps),
                 ts: Instant::now(),
             });
         }
@@ -691,6 +698,10 @@ impl UserWsListener {
             price,
             status,
             source: FillSource::UserWs,
+            liquidity: LiquidityRole::Taker,
+            fee_rate_bps: fee_rate_bps_field(val, None)
+                .as_deref()
+                .and_then(parse_fee_rate_bps),
             ts: Instant::now(),
         })
     }
@@ -713,12 +724,7 @@ impl UserWsListener {
             .get("asset_id")
             .or_else(|| trade_event.get("asset_id"))
             .map(json_value_to_string);
-        let fee_rate_bps = source
-            .get("fee_rate_bps")
-            .or_else(|| source.get("feeRateBps"))
-            .or_else(|| trade_event.get("fee_rate_bps"))
-            .or_else(|| trade_event.get("feeRateBps"))
-            .map(json_value_to_string);
+        let fee_rate_bps = fee_rate_bps_field(trade_event, maker_order);
         let has_fee_rate_bps = fee_rate_bps.is_some();
         let has_order_id = !order_id.trim().is_empty() && order_id != "unknown";
         let has_trade_id = !trade_id.trim().is_empty();
@@ -773,6 +779,17 @@ impl UserWsListener {
     }
 }
 
+/// `fee_rate_bps` of the maker order when present, else of the trade event.
+fn fee_rate_bps_field(trade_event: &Value, maker_order: Option<&Value>) -> Option<String> {
+    let source = maker_order.unwrap_or(trade_event);
+    source
+        .get("fee_rate_bps")
+        .or_else(|| source.get("feeRateBps"))
+        .or_else(|| trade_event.get("fee_rate_bps"))
+        .or_else(|| trade_event.get("feeRateBps"))
+        .map(json_value_to_string)
+}
+
 fn json_value_to_string(value: &Value) -> String {
     value
         .as_str()
@@ -962,4 +979,51 @@ mod tests {
         assert_eq!(f2.len(), 1);
         assert_eq!(f3.len(), 0);
     }
+
+    #[test]
+    fn test_taker_fill_carries_role_and_fee_rate_bps() {
+        let ws = listener();
+        let mut dedup = DedupCache::new(Duration::from_secs(60), 16);
+        let event = json!({
+            "event_type": "trade",
+            "status": "MATCHED",
+            "asset_id": "2",
+            "order_id": "o-2",
+            "size": "3.0",
+            "price": "0.40",
+            "fee_rate_bps": "1000"
+        });
+
+        let fills = ws.parse_trade_event(&event, &mut dedup);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].liquidity, LiquidityRole::Taker);
+        assert_eq!(fills[0].fee_rate_bps, Some(1000));
+    }
+
+    #[test]
+    fn test_maker_fill_takes_trade_level_fee_rate_bps() {
+        let ws = listener();
+        let mut dedup = DedupCache::new(Duration::from_secs(60), 16);
+        let event = json!({
+            "event_type": "trade",
+            "id": "t-1",
+            "status": "MATCHED",
+            "trader_side": "MAKER",
+            "fee_rate_bps": "1000",
+            "maker_orders": [{
+                "owner": "api-key",
+                "order_id": "o-1",
+                "asset_id": "1",
+                "matched_amount": "5",
+                "price": "0.45",
+                "side": "BUY",
+                "fee_rate_bps": "0"
+            }]
+        });
+
+        let fills = ws.parse_trade_event(&event, &mut dedup);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].liquidity, LiquidityRole::Maker);
+        assert_eq!(fills[0].fee_rate_bps, Some(1000));
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 
+use super::fees::prorate_fee;
 use super::types::Side;
 
 const LOT_EPS: f64 = 1e-9;
@@ -129,8 +130,8 @@ impl XuanB27DplusLedger {
             if qty <= LOT_EPS {
                 break;
             }
-            let yes_fee = prorated_fee(*yes, qty);
-            let no_fee = prorated_fee(*no, qty);
+            let yes_fee = prorate_fee(yes.fee, yes.qty, qty);
+            let no_fee = prorate_fee(no.fee, no.qty, qty);
             let gross_pair_cost = yes.price + no.price;
             let net_pair_cost = gross_pair_cost + (yes_fee + no_fee) / qty;
 
@@ -170,14 +171,6 @@ impl XuanB27DplusLedger {
     }
 }
 
-fn prorated_fee(lot: XuanB27DplusLot, qty: f64) -> f64 {
-    if lot.qty <= LOT_EPS {
-        0.0
-    } else {
-        (lot.fee * (qty / lot.qty)).max(0.0)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;